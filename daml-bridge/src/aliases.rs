@@ -1,8 +1,10 @@
 use crate::config::BridgeConfigData;
+use crate::metrics::BridgeMetrics;
+use crate::validator::SchemaValidator;
 use daml_grpc::DamlGrpcClient;
 use daml_lf::element::DamlArchive;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Notify, RwLock};
 
 /// A [`DamlArchive`] suitable for use by a multi-threaded async executor.
 pub type Archive = Arc<RwLock<DamlArchive<'static>>>;
@@ -12,3 +14,12 @@ pub type GrpcClient = Arc<DamlGrpcClient>;
 
 /// A [`BridgeConfigData`] suitable for use by a multi-threaded async executor.
 pub type BridgeConfig = Arc<BridgeConfigData>;
+
+/// A [`BridgeMetrics`] suitable for use by a multi-threaded async executor.
+pub type Metrics = Arc<BridgeMetrics>;
+
+/// Notified whenever a package is uploaded via the bridge.
+pub type PackageUploaded = Arc<Notify>;
+
+/// A [`SchemaValidator`] suitable for use by a multi-threaded async executor.
+pub type Validator = Arc<SchemaValidator>;
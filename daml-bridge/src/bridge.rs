@@ -1,11 +1,18 @@
-use crate::aliases::{Archive, BridgeConfig, GrpcClient};
+use crate::aliases::{Archive, BridgeConfig, GrpcClient, Metrics, PackageUploaded, Validator};
+use crate::metrics::BridgeMetrics;
 use crate::server::make_server;
+use crate::validator::SchemaValidator;
 use anyhow::Result;
 use daml_grpc::DamlGrpcClientBuilder;
-use daml_lf::element::DamlArchive;
+use daml_lf::element::{DamlArchive, DamlPackage};
+use daml_lf::{DamlLfConvertError, DamlLfError, DarFile};
 use daml_util::package::{ArchiveAutoNamingStyle, DamlPackages};
+use futures::future::{join, select, Either};
+use futures::pin_mut;
+use std::collections::HashSet;
+use std::iter;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Notify, RwLock};
 use tokio::time::Duration;
 use tracing::{error, info};
 
@@ -24,47 +31,192 @@ impl Bridge {
 
     /// Start the bridge.
     pub async fn run(&self) -> Result<()> {
-        let grpc_client = Arc::new(
-            DamlGrpcClientBuilder::uri(self.config.ledger_uri())
-                .connect_timeout(Some(self.config.ledger_connect_timeout()))
-                .timeout(self.config.ledger_timeout())
-                .with_auth(self.config.ledger_token().to_owned())
-                .connect()
-                .await?,
-        );
-        let archive: Archive = Arc::new(RwLock::new(fetch_archive(&grpc_client).await?));
-        let http_server = make_server(self.config.clone(), archive.clone(), grpc_client.clone())?;
-        let package_refresher = refresh(archive.clone(), grpc_client.clone(), self.config.package_reload_interval());
+        let grpc_client = Arc::new(self.make_grpc_client_builder().connect().await?);
+        let metrics: Metrics = Arc::new(BridgeMetrics::new());
+        let (known_package_ids, archive) = fetch_all_packages(&grpc_client).await?;
+        let archive: Archive = Arc::new(RwLock::new(archive));
+        metrics.record_package_reload();
+        let validator: Validator = Arc::new(SchemaValidator::new());
+        let package_uploaded: PackageUploaded = Arc::new(Notify::new());
+        let http_server = make_server(
+            self.config.clone(),
+            archive.clone(),
+            grpc_client.clone(),
+            metrics.clone(),
+            validator.clone(),
+            package_uploaded.clone(),
+        )?;
+        let package_refresher =
+            PackageRefresher::new(known_package_ids, archive.clone(), grpc_client.clone(), metrics.clone(), validator)
+                .run(package_uploaded, self.config.package_reload_interval(), self.config.package_reload_polling());
         info!("Ready");
         let http_handle = tokio::spawn(http_server);
         let refresher_handle = tokio::spawn(package_refresher);
-        let _result = tokio::join!(http_handle, refresher_handle);
+        let _result = join(http_handle, refresher_handle).await;
         Ok(())
     }
+
+    fn make_grpc_client_builder(&self) -> DamlGrpcClientBuilder {
+        let builder = DamlGrpcClientBuilder::uri(self.config.ledger_uri())
+            .connect_timeout(Some(self.config.ledger_connect_timeout()))
+            .timeout(self.config.ledger_timeout())
+            .with_auth(self.config.ledger_token().to_owned());
+        match self.config.ledger_ca_cert() {
+            Some(ca_cert) => builder.with_tls(ca_cert),
+            None => builder,
+        }
+    }
 }
 
-/// Refresh the [`Archive`] from the ledger server.
-async fn refresh(archive: Archive, grpc_client: GrpcClient, interval: Duration) {
-    let mut timer = tokio::time::interval(interval);
-    let _ = timer.tick().await;
-    loop {
-        let now = timer.tick().await;
-        info!("refreshing Dar (Time now = {:?})", now);
-        let new_archive = fetch_archive(&grpc_client).await;
-        match new_archive {
-            Ok(new_arch) => *archive.write().await = new_arch,
-            Err(e) => {
-                error!("error refreshing Dar from ledger: {}", e);
-            },
+/// Incrementally refresh the [`Archive`] from the ledger server.
+///
+/// Only the ids of the known packages are retained between refreshes.  A refresh is skipped if the ledger server has no
+/// packages which are not already known, otherwise only the new packages, and those packages they depend on, are
+/// downloaded.  Only the new packages are converted, the dependencies are used only to resolve references from the new
+/// packages and are then discarded.
+///
+/// The cached schemas of the [`Validator`] are invalidated whenever the [`Archive`] is changed.
+struct PackageRefresher {
+    known_package_ids: HashSet<String>,
+    archive: Archive,
+    grpc_client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
+}
+
+impl PackageRefresher {
+    const fn new(
+        known_package_ids: HashSet<String>,
+        archive: Archive,
+        grpc_client: GrpcClient,
+        metrics: Metrics,
+        validator: Validator,
+    ) -> Self {
+        Self {
+            known_package_ids,
+            archive,
+            grpc_client,
+            metrics,
+            validator,
         }
     }
+
+    /// Refresh the [`Archive`] periodically (if polling is enabled) and whenever a package is uploaded.
+    async fn run(mut self, package_uploaded: PackageUploaded, interval: Duration, polling: bool) {
+        let mut timer = tokio::time::interval(interval);
+        let _ = timer.tick().await;
+        loop {
+            if polling {
+                let tick = timer.tick();
+                let notified = package_uploaded.notified();
+                pin_mut!(tick, notified);
+                match select(tick, notified).await {
+                    Either::Left((now, _)) => info!("refreshing Dar (Time now = {:?})", now),
+                    Either::Right(((), _)) => info!("refreshing Dar (package uploaded)"),
+                }
+            } else {
+                package_uploaded.notified().await;
+                info!("refreshing Dar (package uploaded)");
+            }
+            match self.refresh().await {
+                Ok(count) => {
+                    info!("refreshed Dar ({} new packages)", count);
+                    self.metrics.record_package_reload();
+                },
+                Err(e) => {
+                    error!("error refreshing Dar from ledger: {}", e);
+                    self.metrics.record_package_reload_failure();
+                },
+            }
+        }
+    }
+
+    /// Add any packages which are not already known to the [`Archive`] and return the number of packages added.
+    async fn refresh(&mut self) -> Result<usize> {
+        let all_package_ids = self.grpc_client.package_service().list_packages().await?;
+        let new_package_ids = unknown_package_ids(&self.known_package_ids, all_package_ids);
+        if new_package_ids.is_empty() {
+            return Ok(0);
+        }
+        let new_packages = fetch_packages(&self.grpc_client, new_package_ids.clone()).await?;
+        let count = new_packages.len();
+        self.archive.write().await.extend(new_packages);
+        self.validator.invalidate();
+        self.known_package_ids.extend(new_package_ids);
+        Ok(count)
+    }
 }
 
-async fn fetch_archive(grpc_client: &GrpcClient) -> Result<DamlArchive<'static>> {
+/// Fetch all packages from the ledger server and convert them to a [`DamlArchive`].
+///
+/// The ids of all fetched packages are returned along with the [`DamlArchive`], the raw packages are discarded.
+async fn fetch_all_packages(grpc_client: &GrpcClient) -> Result<(HashSet<String>, DamlArchive<'static>)> {
     let all_packages = DamlPackages::from_ledger(grpc_client).await?;
     tokio::task::spawn_blocking(move || create_archive(all_packages)).await?
 }
 
-fn create_archive(packages: DamlPackages) -> Result<DamlArchive<'static>> {
-    Ok(packages.into_dar(ArchiveAutoNamingStyle::Uuid)?.to_owned_archive()?)
+/// Fetch the packages with the given ids from the ledger server and convert them to owned [`DamlPackage`].
+///
+/// The packages which the requested packages depend on are also required for the conversion.  These are discovered as
+/// the conversion proceeds and are fetched individually from the ledger server and then discarded once all requested
+/// packages have been converted.
+async fn fetch_packages(grpc_client: &GrpcClient, requested_ids: Vec<String>) -> Result<Vec<DamlPackage<'static>>> {
+    let mut dar = DamlPackages::from_ledger_with_ids(grpc_client, &requested_ids)
+        .await?
+        .into_dar(ArchiveAutoNamingStyle::Uuid)?;
+    let requested_ids = Arc::new(requested_ids);
+    loop {
+        let requested = requested_ids.clone();
+        let (returned_dar, converted) = tokio::task::spawn_blocking(move || {
+            let converted = dar.to_owned_packages(&requested);
+            (dar, converted)
+        })
+        .await?;
+        dar = returned_dar;
+        match converted {
+            Err(DamlLfError::DamlLfConvertError(DamlLfConvertError::UnknownPackage(package_id)))
+                if !package_ids(&dar).contains(&package_id) =>
+            {
+                let dependency = DamlPackages::from_ledger_with_ids(grpc_client, &[package_id]).await?;
+                dar.dependencies.extend(dependency.into_archives(ArchiveAutoNamingStyle::Uuid)?);
+            },
+            converted => return Ok(converted?),
+        }
+    }
+}
+
+fn create_archive(packages: DamlPackages) -> Result<(HashSet<String>, DamlArchive<'static>)> {
+    let dar = packages.into_dar(ArchiveAutoNamingStyle::Uuid)?;
+    let archive = dar.to_owned_archive()?;
+    Ok((package_ids(&dar), archive))
+}
+
+fn package_ids(dar: &DarFile) -> HashSet<String> {
+    iter::once(dar.main()).chain(dar.dependencies()).map(|archive| archive.hash().to_owned()).collect()
+}
+
+/// Return the `package_ids` which are not contained in `known_package_ids`.
+fn unknown_package_ids(known_package_ids: &HashSet<String>, package_ids: Vec<String>) -> Vec<String> {
+    package_ids.into_iter().filter(|id| !known_package_ids.contains(id)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_package_ids() {
+        let known: HashSet<String> = ["a", "b"].iter().map(ToString::to_string).collect();
+        assert!(unknown_package_ids(&known, vec!["b".into(), "a".into()]).is_empty());
+        assert_eq!(vec!["c".to_string()], unknown_package_ids(&known, vec!["a".into(), "c".into()]));
+    }
+
+    #[test]
+    fn test_package_ids() -> Result<()> {
+        let dar = DarFile::from_file("../resources/testing_types_sandbox/TestingTypes-latest.dar")?;
+        let ids = package_ids(&dar);
+        assert_eq!(dar.dependencies().len() + 1, ids.len());
+        assert!(ids.contains(dar.main().hash()));
+        Ok(())
+    }
 }
@@ -1,6 +1,8 @@
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// Daml Bridge configuration.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug)]
 pub struct BridgeConfigData {
     ledger_uri: String,
@@ -10,8 +12,14 @@ pub struct BridgeConfigData {
     http_host: String,
     http_port: u16,
     package_reload_interval: Duration,
+    package_reload_polling: bool,
     encode_int64_as_string: bool,
     encode_decimal_as_string: bool,
+    validate_all: bool,
+    validate_schema: bool,
+    ledger_ca_cert: Option<Vec<u8>>,
+    http_tls: Option<BridgeHttpTlsConfig>,
+    http_cors: Option<BridgeCorsConfig>,
 }
 
 impl BridgeConfigData {
@@ -35,8 +43,74 @@ impl BridgeConfigData {
             http_host,
             http_port,
             package_reload_interval,
+            package_reload_polling: true,
             encode_int64_as_string,
             encode_decimal_as_string,
+            validate_all: false,
+            validate_schema: false,
+            ledger_ca_cert: None,
+            http_tls: None,
+            http_cors: None,
+        }
+    }
+
+    /// Sets whether packages are periodically reloaded from the ledger server.
+    ///
+    /// Packages are always reloaded when a Dar is uploaded via the bridge.  Disable polling if packages are only ever
+    /// uploaded via the bridge.
+    #[must_use]
+    pub fn with_package_reload_polling(self, package_reload_polling: bool) -> Self {
+        Self {
+            package_reload_polling,
+            ..self
+        }
+    }
+
+    /// Sets whether all errors in the JSON values of a request are reported rather than only the first.
+    #[must_use]
+    pub fn with_validate_all(self, validate_all: bool) -> Self {
+        Self {
+            validate_all,
+            ..self
+        }
+    }
+
+    /// Sets whether the JSON values of a request are validated against the JSON schema of their Daml types.
+    ///
+    /// If enabled, requests which do not conform to their schema are rejected before being submitted to the ledger
+    /// with a report of every violation.
+    #[must_use]
+    pub fn with_validate_schema(self, validate_schema: bool) -> Self {
+        Self {
+            validate_schema,
+            ..self
+        }
+    }
+
+    /// Connect to the ledger server over TLS, verifying the server with the given PEM encoded CA certificate.
+    #[must_use]
+    pub fn with_ledger_tls(self, ca_cert: impl Into<Vec<u8>>) -> Self {
+        Self {
+            ledger_ca_cert: Some(ca_cert.into()),
+            ..self
+        }
+    }
+
+    /// Serve the JSON API over HTTPS.
+    #[must_use]
+    pub fn with_http_tls(self, http_tls: BridgeHttpTlsConfig) -> Self {
+        Self {
+            http_tls: Some(http_tls),
+            ..self
+        }
+    }
+
+    /// Enable CORS for the JSON API.
+    #[must_use]
+    pub fn with_http_cors(self, http_cors: BridgeCorsConfig) -> Self {
+        Self {
+            http_cors: Some(http_cors),
+            ..self
         }
     }
 
@@ -68,6 +142,10 @@ impl BridgeConfigData {
         self.package_reload_interval
     }
 
+    pub const fn package_reload_polling(&self) -> bool {
+        self.package_reload_polling
+    }
+
     pub const fn encode_int64_as_string(&self) -> bool {
         self.encode_int64_as_string
     }
@@ -75,4 +153,116 @@ impl BridgeConfigData {
     pub const fn encode_decimal_as_string(&self) -> bool {
         self.encode_decimal_as_string
     }
+
+    pub const fn validate_all(&self) -> bool {
+        self.validate_all
+    }
+
+    pub const fn validate_schema(&self) -> bool {
+        self.validate_schema
+    }
+
+    pub fn ledger_ca_cert(&self) -> Option<&[u8]> {
+        self.ledger_ca_cert.as_deref()
+    }
+
+    pub const fn http_tls(&self) -> Option<&BridgeHttpTlsConfig> {
+        self.http_tls.as_ref()
+    }
+
+    pub const fn http_cors(&self) -> Option<&BridgeCorsConfig> {
+        self.http_cors.as_ref()
+    }
+}
+
+/// Daml Bridge HTTPS configuration.
+#[derive(Debug, Clone)]
+pub struct BridgeHttpTlsConfig {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    client_auth: Option<BridgeClientAuth>,
+}
+
+impl BridgeHttpTlsConfig {
+    /// Create a new [`BridgeHttpTlsConfig`] from PEM encoded certificate chain and private key files.
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            client_auth: None,
+        }
+    }
+
+    /// Authenticate http clients with certificates signed by the trust anchors in the given PEM file.
+    #[must_use]
+    pub fn with_client_auth(self, client_auth: BridgeClientAuth) -> Self {
+        Self {
+            client_auth: Some(client_auth),
+            ..self
+        }
+    }
+
+    pub fn cert_path(&self) -> &Path {
+        &self.cert_path
+    }
+
+    pub fn key_path(&self) -> &Path {
+        &self.key_path
+    }
+
+    pub const fn client_auth(&self) -> Option<&BridgeClientAuth> {
+        self.client_auth.as_ref()
+    }
+}
+
+/// Daml Bridge http client certificate authentication mode.
+#[derive(Debug, Clone)]
+pub enum BridgeClientAuth {
+    /// Clients may present a certificate, if they do it must be signed by a trust anchor in the given PEM file.
+    Optional(PathBuf),
+    /// Clients must present a certificate signed by a trust anchor in the given PEM file.
+    Required(PathBuf),
+}
+
+/// Daml Bridge CORS configuration.
+///
+/// If no origins are provided then requests from any origin are allowed, in which case credentials may not be allowed.
+#[derive(Debug, Clone, Default)]
+pub struct BridgeCorsConfig {
+    allowed_origins: Vec<String>,
+    allowed_headers: Vec<String>,
+    allow_credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl BridgeCorsConfig {
+    pub const fn new(
+        allowed_origins: Vec<String>,
+        allowed_headers: Vec<String>,
+        allow_credentials: bool,
+        max_age: Option<Duration>,
+    ) -> Self {
+        Self {
+            allowed_origins,
+            allowed_headers,
+            allow_credentials,
+            max_age,
+        }
+    }
+
+    pub fn allowed_origins(&self) -> &[String] {
+        &self.allowed_origins
+    }
+
+    pub fn allowed_headers(&self) -> &[String] {
+        &self.allowed_headers
+    }
+
+    pub const fn allow_credentials(&self) -> bool {
+        self.allow_credentials
+    }
+
+    pub const fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
 }
@@ -0,0 +1,103 @@
+use crate::aliases::{Archive, BridgeConfig, GrpcClient, Metrics, Validator};
+use crate::handler::common::{
+    extract_from_token, internal_server_error, ledger_error, parse_auth_header, request_conversion_error, JsonResult,
+};
+use daml_grpc::data::command::DamlCommand;
+use daml_grpc::data::DamlTransactionTree;
+use daml_grpc::DamlSimpleExecutorBuilder;
+use daml_json::request::{DamlJsonBatchRequest, DamlJsonBatchResponse};
+use daml_json::request_converter::JsonToGrpcRequestConverter;
+use daml_json::response_converter::GrpcToJsonResponseConverter;
+use daml_json::value_encode::JsonValueEncoder;
+use daml_lf::element::DamlArchive;
+
+/// Handle batch requests.
+///
+/// All commands of a batch are submitted to the ledger as a single atomic transaction.
+pub struct BatchHandler {
+    config: BridgeConfig,
+    archive: Archive,
+    client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
+}
+
+impl BatchHandler {
+    pub fn new(
+        config: BridgeConfig,
+        archive: Archive,
+        client: GrpcClient,
+        metrics: Metrics,
+        validator: Validator,
+    ) -> Self {
+        Self {
+            config,
+            archive,
+            client,
+            metrics,
+            validator,
+        }
+    }
+
+    /// Submit every command of the batch in a single transaction and return the result of each command, in order.
+    pub async fn batch(
+        &self,
+        batch: DamlJsonBatchRequest,
+        auth_header: Option<&str>,
+    ) -> JsonResult<DamlJsonBatchResponse> {
+        let command_id = batch.meta.as_ref().map(|id| id.command_id.as_str());
+        // the archive read guard must be released before the batch is submitted to the ledger
+        let commands = {
+            let archive_locked = self.archive.read().await;
+            self.make_commands(&archive_locked, &batch)?
+        };
+        let command_count = commands.len();
+        let transaction = self.execute_commands(commands, command_id, auth_header).await?;
+        self.make_response(&transaction, command_count)
+    }
+
+    fn make_commands(
+        &self,
+        archive_locked: &DamlArchive<'_>,
+        batch: &DamlJsonBatchRequest,
+    ) -> JsonResult<Vec<DamlCommand>> {
+        if self.config.validate_schema() {
+            self.validator.validate_batch(archive_locked, batch)?;
+        }
+        let request_converter =
+            JsonToGrpcRequestConverter::new(archive_locked).with_validate_all(self.config.validate_all());
+        request_converter.convert_batch_request(batch).map_err(request_conversion_error)
+    }
+
+    async fn execute_commands(
+        &self,
+        commands: Vec<DamlCommand>,
+        command_id: Option<&str>,
+        auth_header: Option<&str>,
+    ) -> JsonResult<DamlTransactionTree> {
+        let (token, parsed_token) = parse_auth_header(auth_header)?;
+        let (acting_party, _ledger_id, application_id) = extract_from_token(&parsed_token)?;
+        DamlSimpleExecutorBuilder::new(&self.client)
+            .application_id(application_id)
+            .act_as(acting_party)
+            .auth_token(token)
+            .build()
+            .map_err(internal_server_error)?
+            .execute_all_for_transaction_tree(commands, command_id)
+            .await
+            .map_err(|err| ledger_error(&self.metrics, err))
+    }
+
+    fn make_response(
+        &self,
+        transaction: &DamlTransactionTree,
+        command_count: usize,
+    ) -> JsonResult<DamlJsonBatchResponse> {
+        GrpcToJsonResponseConverter::new(JsonValueEncoder::new(
+            self.config.encode_decimal_as_string(),
+            self.config.encode_int64_as_string(),
+        ))
+        .convert_batch_response(transaction, command_count)
+        .map_err(internal_server_error)
+    }
+}
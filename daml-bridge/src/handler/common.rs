@@ -1,3 +1,6 @@
+use crate::metrics::BridgeMetrics;
+use daml_grpc::data::DamlError;
+use daml_json::error::{DamlJsonCodecError, DamlJsonReqConError};
 use daml_json::request::DamlJsonErrorResponse;
 use daml_util::DamlSandboxAuthToken;
 use std::error::Error;
@@ -10,10 +13,25 @@ pub fn internal_server_error(err: impl Error) -> DamlJsonErrorResponse {
     make_error_response(err.to_string(), 500) // TODO constants
 }
 
+/// Record an error returned by the ledger server and map it to an internal server error.
+pub fn ledger_error(metrics: &BridgeMetrics, err: DamlError) -> DamlJsonErrorResponse {
+    metrics.record_ledger_error(&err);
+    internal_server_error(err)
+}
+
 pub fn bad_request(err: impl Error) -> DamlJsonErrorResponse {
     make_error_response(err.to_string(), 400)
 }
 
+/// Map a request conversion error to a bad request, reporting each JSON value which could not be decoded separately.
+pub fn request_conversion_error(err: DamlJsonReqConError) -> DamlJsonErrorResponse {
+    match err {
+        DamlJsonReqConError::CodecError(DamlJsonCodecError::DecodeErrors(decode_errors)) =>
+            DamlJsonErrorResponse::from_decode_errors(400, &decode_errors),
+        err => bad_request(err),
+    }
+}
+
 pub fn unauthorized(err: impl Error) -> DamlJsonErrorResponse {
     auth_err(err.to_string())
 }
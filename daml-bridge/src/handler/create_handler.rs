@@ -1,5 +1,7 @@
-use crate::aliases::{Archive, BridgeConfig, GrpcClient};
-use crate::handler::common::{bad_request, extract_from_token, internal_server_error, parse_auth_header, JsonResult};
+use crate::aliases::{Archive, BridgeConfig, GrpcClient, Metrics, Validator};
+use crate::handler::common::{
+    extract_from_token, internal_server_error, ledger_error, parse_auth_header, request_conversion_error, JsonResult,
+};
 use daml_grpc::data::command::{DamlCommand, DamlCreateCommand};
 use daml_grpc::data::DamlTransaction;
 use daml_grpc::{CommandExecutor, DamlSimpleExecutorBuilder};
@@ -13,14 +15,24 @@ pub struct CreateHandler {
     config: BridgeConfig,
     archive: Archive,
     client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
 }
 
 impl CreateHandler {
-    pub fn new(config: BridgeConfig, archive: Archive, client: GrpcClient) -> Self {
+    pub fn new(
+        config: BridgeConfig,
+        archive: Archive,
+        client: GrpcClient,
+        metrics: Metrics,
+        validator: Validator,
+    ) -> Self {
         Self {
             config,
             archive,
             client,
+            metrics,
+            validator,
         }
     }
 
@@ -38,8 +50,12 @@ impl CreateHandler {
 
     async fn make_command(&self, create: &DamlJsonCreateRequest) -> JsonResult<DamlCreateCommand> {
         let archive_locked = &self.archive.read().await;
-        let request_converter = JsonToGrpcRequestConverter::new(archive_locked);
-        request_converter.convert_create_request(create).map_err(bad_request)
+        if self.config.validate_schema() {
+            self.validator.validate_create(archive_locked, create)?;
+        }
+        let request_converter =
+            JsonToGrpcRequestConverter::new(archive_locked).with_validate_all(self.config.validate_all());
+        request_converter.convert_create_request(create).map_err(request_conversion_error)
     }
 
     async fn execute_command(
@@ -58,7 +74,7 @@ impl CreateHandler {
             .map_err(internal_server_error)?
             .execute_for_transaction(DamlCommand::Create(command))
             .await
-            .map_err(internal_server_error)
+            .map_err(|err| ledger_error(&self.metrics, err))
     }
 
     fn make_response(&self, transaction: &DamlTransaction) -> JsonResult<DamlJsonCreateResponse> {
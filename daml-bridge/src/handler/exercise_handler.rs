@@ -1,5 +1,7 @@
-use crate::aliases::{Archive, BridgeConfig, GrpcClient};
-use crate::handler::common::{bad_request, extract_from_token, internal_server_error, parse_auth_header, JsonResult};
+use crate::aliases::{Archive, BridgeConfig, GrpcClient, Metrics, Validator};
+use crate::handler::common::{
+    extract_from_token, internal_server_error, ledger_error, parse_auth_header, request_conversion_error, JsonResult,
+};
 use daml_grpc::data::command::{DamlCommand, DamlExerciseCommand};
 use daml_grpc::data::DamlTransactionTree;
 use daml_grpc::{CommandExecutor, DamlSimpleExecutorBuilder};
@@ -13,14 +15,35 @@ pub struct ExerciseHandler {
     config: BridgeConfig,
     archive: Archive,
     client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
+    verbose: bool,
 }
 
 impl ExerciseHandler {
-    pub fn new(config: BridgeConfig, archive: Archive, client: GrpcClient) -> Self {
+    pub fn new(
+        config: BridgeConfig,
+        archive: Archive,
+        client: GrpcClient,
+        metrics: Metrics,
+        validator: Validator,
+    ) -> Self {
         Self {
             config,
             archive,
             client,
+            metrics,
+            validator,
+            verbose: false,
+        }
+    }
+
+    /// Include the full transaction tree in the exercise result.
+    #[must_use]
+    pub fn with_verbose(self, verbose: bool) -> Self {
+        Self {
+            verbose,
+            ..self
         }
     }
 
@@ -37,8 +60,12 @@ impl ExerciseHandler {
 
     async fn make_command(&self, exercise: DamlJsonExerciseRequest) -> JsonResult<DamlExerciseCommand> {
         let archive_locked = &self.archive.read().await;
-        let request_converter = JsonToGrpcRequestConverter::new(archive_locked);
-        request_converter.convert_exercise_request(&exercise).map_err(bad_request)
+        if self.config.validate_schema() {
+            self.validator.validate_exercise(archive_locked, &exercise)?;
+        }
+        let request_converter =
+            JsonToGrpcRequestConverter::new(archive_locked).with_validate_all(self.config.validate_all());
+        request_converter.convert_exercise_request(&exercise).map_err(request_conversion_error)
     }
 
     async fn execute_command(
@@ -56,7 +83,7 @@ impl ExerciseHandler {
             .map_err(internal_server_error)?
             .execute_for_transaction_tree(DamlCommand::Exercise(command))
             .await
-            .map_err(internal_server_error)
+            .map_err(|err| ledger_error(&self.metrics, err))
     }
 
     fn make_response(&self, transaction: &DamlTransactionTree) -> JsonResult<DamlJsonExerciseResponse> {
@@ -64,6 +91,7 @@ impl ExerciseHandler {
             self.config.encode_decimal_as_string(),
             self.config.encode_int64_as_string(),
         ))
+        .with_verbose(self.verbose)
         .convert_exercise_response(transaction)
         .map_err(internal_server_error)
     }
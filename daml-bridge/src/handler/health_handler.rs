@@ -0,0 +1,77 @@
+use crate::aliases::{GrpcClient, Metrics};
+use std::fmt::Write;
+
+/// The result of a health check.
+pub struct HealthCheckResponse {
+    pub healthy: bool,
+    pub report: String,
+}
+
+/// Handle the health, readiness and metrics endpoints.
+pub struct HealthHandler {
+    client: GrpcClient,
+    metrics: Metrics,
+}
+
+impl HealthHandler {
+    pub const fn new(client: GrpcClient, metrics: Metrics) -> Self {
+        Self {
+            client,
+            metrics,
+        }
+    }
+
+    /// Check the bridge is alive.
+    ///
+    /// The bridge is considered alive if it is able to serve http requests.
+    pub fn livez() -> HealthCheckResponse {
+        HealthCheckResponse {
+            healthy: true,
+            report: String::from("livez check passed\n"),
+        }
+    }
+
+    /// Check the bridge is ready to serve requests.
+    ///
+    /// The bridge is considered ready if the ledger server is reachable and the Daml packages have been loaded.
+    pub async fn readyz(&self) -> HealthCheckResponse {
+        let ledger_check = match self.client.ledger_identity_service().get_ledger_identity().await {
+            Ok(ledger_id) => Ok(format!("ledger ok (ledger id {ledger_id})")),
+            Err(err) => {
+                self.metrics.record_ledger_error(&err);
+                Err(format!("ledger failed: {err}"))
+            },
+        };
+        let packages_check = self.metrics.since_last_package_reload().map_or_else(
+            || Err(String::from("packages failed: not loaded")),
+            |elapsed| Ok(format!("packages ok (reloaded {:.1}s ago)", elapsed.as_secs_f64())),
+        );
+        make_check_response("readyz", &[ledger_check, packages_check])
+    }
+
+    /// Render the bridge metrics.
+    pub fn metrics(&self) -> String {
+        self.metrics.render()
+    }
+}
+
+fn make_check_response(name: &str, checks: &[Result<String, String>]) -> HealthCheckResponse {
+    let mut report = String::new();
+    for check in checks {
+        let _ = match check {
+            Ok(msg) => writeln!(report, "[+] {msg}"),
+            Err(msg) => writeln!(report, "[-] {msg}"),
+        };
+    }
+    let healthy = checks.iter().all(Result::is_ok);
+    let outcome = if healthy {
+        "passed"
+    } else {
+        "failed"
+    };
+    let _ = writeln!(report, "{name} check {outcome}");
+    HealthCheckResponse {
+        healthy,
+        report,
+    }
+}
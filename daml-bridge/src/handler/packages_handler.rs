@@ -1,5 +1,5 @@
-use crate::aliases::{BridgeConfig, GrpcClient};
-use crate::handler::common::{internal_server_error, parse_auth_header, JsonResult};
+use crate::aliases::{BridgeConfig, GrpcClient, Metrics};
+use crate::handler::common::{ledger_error, parse_auth_header, JsonResult};
 use bytes::Bytes;
 use daml_json::request::{DamlJsonListPackagesResponse, DamlJsonUploadDarResponse};
 
@@ -7,13 +7,15 @@ use daml_json::request::{DamlJsonListPackagesResponse, DamlJsonUploadDarResponse
 pub struct PackagesHandler {
     _config: BridgeConfig,
     client: GrpcClient,
+    metrics: Metrics,
 }
 
 impl PackagesHandler {
-    pub fn new(_config: BridgeConfig, client: GrpcClient) -> Self {
+    pub fn new(_config: BridgeConfig, client: GrpcClient, metrics: Metrics) -> Self {
         Self {
             _config,
             client,
+            metrics,
         }
     }
 
@@ -36,7 +38,12 @@ impl PackagesHandler {
 
     async fn execute_list_all(&self, auth_header: Option<&str>) -> JsonResult<Vec<String>> {
         let (token, _) = parse_auth_header(auth_header)?;
-        self.client.package_service().with_token(token).list_packages().await.map_err(internal_server_error)
+        self.client
+            .package_service()
+            .with_token(token)
+            .list_packages()
+            .await
+            .map_err(|err| ledger_error(&self.metrics, err))
     }
 
     async fn execute_get(&self, package_id: &str, auth_header: Option<&str>) -> JsonResult<Vec<u8>> {
@@ -47,7 +54,7 @@ impl PackagesHandler {
             .with_token(token)
             .get_package(package_id)
             .await
-            .map_err(internal_server_error)?
+            .map_err(|err| ledger_error(&self.metrics, err))?
             .take_payload())
     }
 
@@ -58,7 +65,7 @@ impl PackagesHandler {
             .with_token(token)
             .upload_dar_file(payload, None)
             .await
-            .map_err(internal_server_error)
+            .map_err(|err| ledger_error(&self.metrics, err))
     }
 }
 
@@ -1,5 +1,5 @@
-use crate::aliases::{BridgeConfig, GrpcClient};
-use crate::handler::common::{internal_server_error, parse_auth_header, JsonResult};
+use crate::aliases::{BridgeConfig, GrpcClient, Metrics};
+use crate::handler::common::{ledger_error, parse_auth_header, JsonResult};
 use daml_grpc::data::party::DamlPartyDetails;
 use daml_json::data::DamlJsonParty;
 use daml_json::request::{
@@ -11,13 +11,15 @@ use daml_json::request::{
 pub struct PartiesHandler {
     _config: BridgeConfig,
     client: GrpcClient,
+    metrics: Metrics,
 }
 
 impl PartiesHandler {
-    pub fn new(_config: BridgeConfig, client: GrpcClient) -> Self {
+    pub fn new(_config: BridgeConfig, client: GrpcClient, metrics: Metrics) -> Self {
         Self {
             _config,
             client,
+            metrics,
         }
     }
 
@@ -59,7 +61,7 @@ impl PartiesHandler {
             .with_token(token)
             .get_parties(fetch_request.0)
             .await
-            .map_err(internal_server_error)
+            .map_err(|err| ledger_error(&self.metrics, err))
     }
 
     async fn execute_fetch_all(&self, auth_header: Option<&str>) -> JsonResult<Vec<DamlPartyDetails>> {
@@ -69,7 +71,7 @@ impl PartiesHandler {
             .with_token(token)
             .list_known_parties()
             .await
-            .map_err(internal_server_error)
+            .map_err(|err| ledger_error(&self.metrics, err))
     }
 
     async fn execute_allocate(
@@ -86,7 +88,7 @@ impl PartiesHandler {
                 allocate_request.display_name.unwrap_or_default(),
             )
             .await
-            .map_err(internal_server_error)
+            .map_err(|err| ledger_error(&self.metrics, err))
     }
 }
 
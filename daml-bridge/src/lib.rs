@@ -17,16 +17,20 @@
 mod aliases;
 mod bridge;
 mod config;
+mod metrics;
 mod server;
+mod validator;
 mod handler {
-    mod common;
+    pub mod batch_handler;
+    pub mod common;
     pub mod create_and_exercise_handler;
     pub mod create_handler;
     pub mod exercise_by_key_handler;
     pub mod exercise_handler;
+    pub mod health_handler;
     pub mod packages_handler;
     pub mod parties_handler;
 }
 
 pub use bridge::Bridge;
-pub use config::BridgeConfigData;
+pub use config::{BridgeClientAuth, BridgeConfigData, BridgeCorsConfig, BridgeHttpTlsConfig};
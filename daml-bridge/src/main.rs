@@ -5,7 +5,7 @@ use clap::{crate_description, crate_name, crate_version, Arg, Command};
 use tracing::info;
 use tracing_subscriber::fmt::format::FmtSpan;
 
-use daml_bridge::{Bridge, BridgeConfigData};
+use daml_bridge::{Bridge, BridgeClientAuth, BridgeConfigData, BridgeCorsConfig, BridgeHttpTlsConfig};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -57,6 +57,86 @@ async fn main() -> anyhow::Result<()> {
                 .value_name("port")
                 .help("The port the http server should listen on"),
         )
+        .arg(
+            Arg::new("http-tls-cert")
+                .long("http-tls-cert")
+                .takes_value(true)
+                .required(false)
+                .requires("http-tls-key")
+                .value_name("file")
+                .help("The PEM encoded certificate chain file, if provided the http server will use HTTPS"),
+        )
+        .arg(
+            Arg::new("http-tls-key")
+                .long("http-tls-key")
+                .takes_value(true)
+                .required(false)
+                .requires("http-tls-cert")
+                .value_name("file")
+                .help("The PEM encoded private key file for the http server"),
+        )
+        .arg(
+            Arg::new("http-tls-client-ca")
+                .long("http-tls-client-ca")
+                .takes_value(true)
+                .required(false)
+                .requires("http-tls-cert")
+                .value_name("file")
+                .help("The PEM encoded trust anchors file used to authenticate http client certificates"),
+        )
+        .arg(
+            Arg::new("http-tls-client-auth-required")
+                .long("http-tls-client-auth-required")
+                .required(false)
+                .requires("http-tls-client-ca")
+                .help("Sets whether http clients must present a certificate"),
+        )
+        .arg(
+            Arg::new("http-cors-origin")
+                .long("http-cors-origin")
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .required(false)
+                .value_name("origin")
+                .help("Enable CORS for the given origin (i.e. https://example.com) or for any origin if set to '*'"),
+        )
+        .arg(
+            Arg::new("http-cors-header")
+                .long("http-cors-header")
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .required(false)
+                .requires("http-cors-origin")
+                .value_name("header")
+                .help("Additional request header to allow for CORS requests"),
+        )
+        .arg(
+            Arg::new("http-cors-allow-credentials")
+                .long("http-cors-allow-credentials")
+                .required(false)
+                .requires("http-cors-origin")
+                .help("Sets whether CORS requests may include credentials (not permitted for any origin)"),
+        )
+        .arg(
+            Arg::new("http-cors-max-age")
+                .long("http-cors-max-age")
+                .takes_value(true)
+                .required(false)
+                .requires("http-cors-origin")
+                .value_name("duration")
+                .help("How long the results of a CORS preflight request may be cached"),
+        )
+        .arg(
+            Arg::new("ledger-ca-cert")
+                .long("ledger-ca-cert")
+                .takes_value(true)
+                .required(false)
+                .value_name("file")
+                .help(
+                    "The PEM encoded CA certificate file, if provided the bridge will connect to the ledger server \
+                     using TLS",
+                ),
+        )
         .arg(
             Arg::new("package-reload-interval")
                 .long("package-reload-interval")
@@ -66,6 +146,9 @@ async fn main() -> anyhow::Result<()> {
                 .value_name("interval")
                 .help("How frequently the bridge should refresh the Daml packages from the ledger server"),
         )
+        .arg(Arg::new("no-package-reload-polling").long("no-package-reload-polling").required(false).help(
+            "Disable periodic package refresh, packages are then only refreshed when a Dar is uploaded via the bridge",
+        ))
         .arg(
             Arg::new("bridge-token")
                 .long("bridge-token")
@@ -86,6 +169,18 @@ async fn main() -> anyhow::Result<()> {
                 .required(false)
                 .help("Sets whether int64 values are encoded as JSON strings"),
         )
+        .arg(
+            Arg::new("validate-all")
+                .long("validate-all")
+                .required(false)
+                .help("Sets whether all invalid values in a request are reported rather than only the first"),
+        )
+        .arg(
+            Arg::new("validate-schema")
+                .long("validate-schema")
+                .required(false)
+                .help("Sets whether request values are validated against their JSON schema before submission"),
+        )
         .arg(
             Arg::new("log-filter")
                 .long("log-filter")
@@ -101,7 +196,7 @@ async fn main() -> anyhow::Result<()> {
         .json()
         .init();
 
-    let config = Arc::new(BridgeConfigData::new(
+    let mut config = BridgeConfigData::new(
         matches.value_of("ledger-uri").unwrap().to_string(),
         humantime::parse_duration(matches.value_of("ledger-connect-timeout").unwrap())?,
         humantime::parse_duration(matches.value_of("ledger-timeout").unwrap())?,
@@ -111,7 +206,45 @@ async fn main() -> anyhow::Result<()> {
         humantime::parse_duration(matches.value_of("package-reload-interval").unwrap())?,
         matches.is_present("encode-int64-as-string"),
         matches.is_present("encode-decimal-as-string"),
-    ));
+    );
+    if matches.is_present("no-package-reload-polling") {
+        config = config.with_package_reload_polling(false);
+    }
+    if matches.is_present("validate-all") {
+        config = config.with_validate_all(true);
+    }
+    if matches.is_present("validate-schema") {
+        config = config.with_validate_schema(true);
+    }
+    if let Some(ca_cert_path) = matches.value_of("ledger-ca-cert") {
+        config = config.with_ledger_tls(std::fs::read(ca_cert_path)?);
+    }
+    if let (Some(cert_path), Some(key_path)) = (matches.value_of("http-tls-cert"), matches.value_of("http-tls-key")) {
+        let tls_config = BridgeHttpTlsConfig::new(cert_path, key_path);
+        config = config.with_http_tls(match matches.value_of("http-tls-client-ca") {
+            Some(ca_path) if matches.is_present("http-tls-client-auth-required") =>
+                tls_config.with_client_auth(BridgeClientAuth::Required(ca_path.into())),
+            Some(ca_path) => tls_config.with_client_auth(BridgeClientAuth::Optional(ca_path.into())),
+            None => tls_config,
+        });
+    }
+    if let Some(origins) = matches.values_of("http-cors-origin") {
+        let origins: Vec<_> = origins.map(ToString::to_string).collect();
+        config = config.with_http_cors(BridgeCorsConfig::new(
+            if origins.iter().any(|origin| origin == "*") {
+                vec![]
+            } else {
+                origins
+            },
+            matches
+                .values_of("http-cors-header")
+                .map(|headers| headers.map(ToString::to_string).collect())
+                .unwrap_or_default(),
+            matches.is_present("http-cors-allow-credentials"),
+            matches.value_of("http-cors-max-age").map(humantime::parse_duration).transpose()?,
+        ));
+    }
+    let config = Arc::new(config);
 
     let bridge = Bridge::new(config);
     info!("Starting");
@@ -0,0 +1,255 @@
+use daml_grpc::data::DamlError;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The upper bounds (in seconds) of the http request latency histogram buckets.
+const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// The known http endpoints, used to label request metrics.
+const KNOWN_ENDPOINTS: [&str; 12] = [
+    "/v1/create",
+    "/v1/exercise",
+    "/v1/create-and-exercise",
+    "/v1/batch",
+    "/v1/parties",
+    "/v1/parties/allocate",
+    "/v1/packages",
+    "/v1/packages/:packageid",
+    "/livez",
+    "/readyz",
+    "/metrics",
+    "unknown",
+];
+
+/// Daml Bridge runtime metrics.
+///
+/// Metrics are rendered in the [Prometheus text exposition format](https://prometheus.io/docs/instrumenting/exposition_formats/).
+#[derive(Debug)]
+pub struct BridgeMetrics {
+    requests: Mutex<BTreeMap<RequestKey, RequestMetrics>>,
+    ledger_errors: Mutex<BTreeMap<String, u64>>,
+    packages: Mutex<PackageMetrics>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestKey {
+    method: String,
+    endpoint: &'static str,
+}
+
+#[derive(Debug, Default)]
+struct RequestMetrics {
+    statuses: BTreeMap<u16, u64>,
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl RequestMetrics {
+    fn record(&mut self, status: u16, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        *self.statuses.entry(status).or_default() += 1;
+        for (bucket, _) in self.buckets.iter_mut().zip(LATENCY_BUCKETS).filter(|(_, bound)| secs <= *bound) {
+            *bucket += 1;
+        }
+        self.count += 1;
+        self.sum += secs;
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct PackageMetrics {
+    last_reload: Option<Instant>,
+    reload_failures: u64,
+}
+
+impl BridgeMetrics {
+    pub fn new() -> Self {
+        Self {
+            requests: Mutex::new(BTreeMap::new()),
+            ledger_errors: Mutex::new(BTreeMap::new()),
+            packages: Mutex::new(PackageMetrics::default()),
+        }
+    }
+
+    /// Record the outcome of a single http request.
+    pub fn record_request(&self, method: &str, path: &str, status: u16, elapsed: Duration) {
+        let key = RequestKey {
+            method: method.to_owned(),
+            endpoint: endpoint_label(path),
+        };
+        self.requests.lock().expect("metrics lock poisoned").entry(key).or_default().record(status, elapsed);
+    }
+
+    /// Record an error returned by the ledger server.
+    pub fn record_ledger_error(&self, error: &DamlError) {
+        let kind = ledger_error_kind(error);
+        *self.ledger_errors.lock().expect("metrics lock poisoned").entry(kind).or_default() += 1;
+    }
+
+    /// Record a successful reload of the Daml packages from the ledger server.
+    pub fn record_package_reload(&self) {
+        self.packages.lock().expect("metrics lock poisoned").last_reload = Some(Instant::now());
+    }
+
+    /// Record a failed reload of the Daml packages from the ledger server.
+    pub fn record_package_reload_failure(&self) {
+        self.packages.lock().expect("metrics lock poisoned").reload_failures += 1;
+    }
+
+    /// The time elapsed since the last successful package reload, if any.
+    pub fn since_last_package_reload(&self) -> Option<Duration> {
+        self.packages.lock().expect("metrics lock poisoned").last_reload.map(|last| last.elapsed())
+    }
+
+    /// Render all metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.render_requests(&mut out);
+        self.render_ledger_errors(&mut out);
+        self.render_packages(&mut out);
+        out
+    }
+
+    fn render_requests(&self, out: &mut String) {
+        let requests = self.requests.lock().expect("metrics lock poisoned");
+        out.push_str("# HELP daml_bridge_http_requests_total The total number of http requests.\n");
+        out.push_str("# TYPE daml_bridge_http_requests_total counter\n");
+        for (key, metrics) in requests.iter() {
+            for (status, count) in &metrics.statuses {
+                let _ = writeln!(
+                    out,
+                    "daml_bridge_http_requests_total{{method=\"{}\",endpoint=\"{}\",status=\"{status}\"}} {count}",
+                    key.method, key.endpoint
+                );
+            }
+        }
+        out.push_str("# HELP daml_bridge_http_request_duration_seconds The http request latencies in seconds.\n");
+        out.push_str("# TYPE daml_bridge_http_request_duration_seconds histogram\n");
+        for (key, metrics) in requests.iter() {
+            let labels = format!("method=\"{}\",endpoint=\"{}\"", key.method, key.endpoint);
+            for (bound, bucket) in LATENCY_BUCKETS.iter().zip(metrics.buckets) {
+                let _ = writeln!(
+                    out,
+                    "daml_bridge_http_request_duration_seconds_bucket{{{labels},le=\"{bound}\"}} {bucket}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "daml_bridge_http_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}",
+                metrics.count
+            );
+            let _ = writeln!(out, "daml_bridge_http_request_duration_seconds_sum{{{labels}}} {}", metrics.sum);
+            let _ = writeln!(out, "daml_bridge_http_request_duration_seconds_count{{{labels}}} {}", metrics.count);
+        }
+    }
+
+    fn render_ledger_errors(&self, out: &mut String) {
+        let ledger_errors = self.ledger_errors.lock().expect("metrics lock poisoned");
+        out.push_str(
+            "# HELP daml_bridge_ledger_errors_total The total number of errors returned by the ledger server.\n",
+        );
+        out.push_str("# TYPE daml_bridge_ledger_errors_total counter\n");
+        for (kind, count) in ledger_errors.iter() {
+            let _ = writeln!(out, "daml_bridge_ledger_errors_total{{kind=\"{kind}\"}} {count}");
+        }
+    }
+
+    fn render_packages(&self, out: &mut String) {
+        let packages = *self.packages.lock().expect("metrics lock poisoned");
+        out.push_str(
+            "# HELP daml_bridge_package_reload_age_seconds The time since the last successful package reload in \
+             seconds.\n",
+        );
+        out.push_str("# TYPE daml_bridge_package_reload_age_seconds gauge\n");
+        if let Some(last_reload) = packages.last_reload {
+            let _ = writeln!(out, "daml_bridge_package_reload_age_seconds {}", last_reload.elapsed().as_secs_f64());
+        }
+        out.push_str("# HELP daml_bridge_package_reload_failures_total The total number of failed package reloads.\n");
+        out.push_str("# TYPE daml_bridge_package_reload_failures_total counter\n");
+        let _ = writeln!(out, "daml_bridge_package_reload_failures_total {}", packages.reload_failures);
+    }
+}
+
+impl Default for BridgeMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Map a request path to a known endpoint label to bound the cardinality of the request metrics.
+fn endpoint_label(path: &str) -> &'static str {
+    match path {
+        p if p.starts_with("/v1/packages/") => "/v1/packages/:packageid",
+        p => KNOWN_ENDPOINTS.iter().find(|&&known| known == p).copied().unwrap_or("unknown"),
+    }
+}
+
+fn ledger_error_kind(error: &DamlError) -> String {
+    match error {
+        DamlError::GrpcStatusError(status) | DamlError::GrpcPermissionError(status) => format!("{:?}", status.code()),
+        DamlError::GrpcTransportError(_) => String::from("transport"),
+        DamlError::TimeoutError(_) => String::from("timeout"),
+        _ => String::from("other"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_request() {
+        let metrics = BridgeMetrics::new();
+        metrics.record_request("POST", "/v1/create", 200, Duration::from_millis(20));
+        metrics.record_request("POST", "/v1/create", 400, Duration::from_millis(700));
+        let rendered = metrics.render();
+        assert!(
+            rendered.contains(r#"daml_bridge_http_requests_total{method="POST",endpoint="/v1/create",status="200"} 1"#)
+        );
+        assert!(
+            rendered.contains(r#"daml_bridge_http_requests_total{method="POST",endpoint="/v1/create",status="400"} 1"#)
+        );
+        assert!(rendered.contains(
+            r#"daml_bridge_http_request_duration_seconds_bucket{method="POST",endpoint="/v1/create",le="0.025"} 1"#
+        ));
+        assert!(rendered.contains(
+            r#"daml_bridge_http_request_duration_seconds_bucket{method="POST",endpoint="/v1/create",le="1"} 2"#
+        ));
+        assert!(rendered
+            .contains(r#"daml_bridge_http_request_duration_seconds_count{method="POST",endpoint="/v1/create"} 2"#));
+    }
+
+    #[test]
+    fn test_endpoint_label() {
+        assert_eq!(endpoint_label("/v1/parties/allocate"), "/v1/parties/allocate");
+        assert_eq!(endpoint_label("/v1/batch"), "/v1/batch");
+        assert_eq!(endpoint_label("/v1/packages/abc123"), "/v1/packages/:packageid");
+        assert_eq!(endpoint_label("/v1/nope"), "unknown");
+    }
+
+    #[test]
+    fn test_ledger_errors() {
+        let metrics = BridgeMetrics::new();
+        metrics.record_ledger_error(&DamlError::new_timeout_error(DamlError::Other(String::from("slow"))));
+        metrics.record_ledger_error(&DamlError::new_timeout_error(DamlError::Other(String::from("slow"))));
+        metrics.record_ledger_error(&DamlError::Other(String::from("oops")));
+        let rendered = metrics.render();
+        assert!(rendered.contains(r#"daml_bridge_ledger_errors_total{kind="timeout"} 2"#));
+        assert!(rendered.contains(r#"daml_bridge_ledger_errors_total{kind="other"} 1"#));
+    }
+
+    #[test]
+    fn test_package_reload() {
+        let metrics = BridgeMetrics::new();
+        assert!(metrics.since_last_package_reload().is_none());
+        assert!(!metrics.render().contains("\ndaml_bridge_package_reload_age_seconds "));
+        metrics.record_package_reload();
+        metrics.record_package_reload_failure();
+        assert!(metrics.since_last_package_reload().is_some());
+        assert!(metrics.render().contains("\ndaml_bridge_package_reload_age_seconds "));
+        assert!(metrics.render().contains("daml_bridge_package_reload_failures_total 1"));
+    }
+}
@@ -1,23 +1,31 @@
-use crate::aliases::{Archive, BridgeConfig, GrpcClient};
+use crate::aliases::{Archive, BridgeConfig, GrpcClient, Metrics, PackageUploaded, Validator};
+use crate::config::{BridgeClientAuth, BridgeCorsConfig};
+use crate::handler::batch_handler::BatchHandler;
 use crate::handler::create_and_exercise_handler::CreateAndExerciseHandler;
 use crate::handler::create_handler::CreateHandler;
 use crate::handler::exercise_by_key_handler::ExerciseByKeyHandler;
 use crate::handler::exercise_handler::ExerciseHandler;
+use crate::handler::health_handler::{HealthCheckResponse, HealthHandler};
 use crate::handler::packages_handler::PackagesHandler;
 use crate::handler::parties_handler::PartiesHandler;
+use anyhow::{anyhow, bail};
 use daml_json::request::{
-    DamlJsonAllocatePartyRequest, DamlJsonCreateAndExerciseRequest, DamlJsonCreateRequest, DamlJsonErrorResponse,
-    DamlJsonExerciseRequestType, DamlJsonFetchPartiesRequest,
+    DamlJsonAllocatePartyRequest, DamlJsonBatchRequest, DamlJsonCreateAndExerciseRequest, DamlJsonCreateRequest,
+    DamlJsonErrorResponse, DamlJsonExerciseRequestType, DamlJsonFetchPartiesRequest,
 };
-use futures::Future;
-use serde::Serialize;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
 use std::convert::Infallible;
 use std::net::SocketAddr;
-use warp::http::StatusCode;
+use std::str::FromStr;
+use warp::cors::{Cors, CorsForbidden};
+use warp::http::uri::{Authority, Scheme};
+use warp::http::{Method, StatusCode};
 use warp::hyper::body::Bytes;
 use warp::path::FullPath;
 use warp::reply::{json, with_status, WithStatus};
-use warp::Filter;
+use warp::{Filter, Rejection};
 
 /// Make the server.
 ///
@@ -29,31 +37,102 @@ use warp::Filter;
 /// turn uses [`tokio`](https://tokio.rs/).
 /// - All warp specific code lives in this module and the handlers for the various REST endpoints are delegated to the
 /// [`handler`] module which is agnostic to the http server or the driving async runtime.
+/// - If configured, the api is served over HTTPS (optionally requiring client certificates) and is wrapped in a CORS
+///   filter, requests from disallowed origins are rejected with a `403` error response.
 pub fn make_server(
     config: BridgeConfig,
     archive: Archive,
     grpc_client: GrpcClient,
-) -> anyhow::Result<impl Future<Output = ()>> {
-    let address = format!("{}:{}", config.http_host(), config.http_port());
-    let api = make_api(config, archive, grpc_client);
-    Ok(warp::serve(api).run(address.parse::<SocketAddr>()?))
+    metrics: Metrics,
+    validator: Validator,
+    package_uploaded: PackageUploaded,
+) -> anyhow::Result<BoxFuture<'static, ()>> {
+    let address = format!("{}:{}", config.http_host(), config.http_port()).parse::<SocketAddr>()?;
+    let api = make_api(config.clone(), archive, grpc_client, metrics, validator, package_uploaded);
+    Ok(match config.http_cors() {
+        Some(cors) => serve(api.with(make_cors(cors)?).recover(cors_rejection_handler), &config, address),
+        None => serve(api, &config, address),
+    })
+}
+
+/// Serve the api over HTTP or, if configured, HTTPS.
+fn serve<F>(api: F, config: &BridgeConfig, address: SocketAddr) -> BoxFuture<'static, ()>
+where
+    F: Filter<Error = Infallible> + Clone + Send + Sync + 'static,
+    F::Extract: warp::Reply,
+{
+    match config.http_tls() {
+        Some(tls) => {
+            let server = warp::serve(api).tls().cert_path(tls.cert_path()).key_path(tls.key_path());
+            match tls.client_auth() {
+                Some(BridgeClientAuth::Optional(path)) => server.client_auth_optional_path(path).run(address).boxed(),
+                Some(BridgeClientAuth::Required(path)) => server.client_auth_required_path(path).run(address).boxed(),
+                None => server.run(address).boxed(),
+            }
+        },
+        None => warp::serve(api).run(address).boxed(),
+    }
+}
+
+fn make_cors(cors_config: &BridgeCorsConfig) -> anyhow::Result<Cors> {
+    let cors = warp::cors()
+        .allow_methods(vec![Method::GET, Method::POST])
+        .allow_headers(vec!["Authorization", "Content-Type"])
+        .allow_headers(cors_config.allowed_headers().iter().map(String::as_str))
+        .allow_credentials(cors_config.allow_credentials());
+    let cors = if cors_config.allowed_origins().is_empty() {
+        if cors_config.allow_credentials() {
+            bail!("CORS credentials cannot be allowed for any origin, explicit origins must be provided");
+        }
+        cors.allow_any_origin()
+    } else {
+        cors_config.allowed_origins().iter().try_for_each(|origin| validate_origin(origin))?;
+        cors.allow_origins(cors_config.allowed_origins().iter().map(String::as_str))
+    };
+    Ok(match cors_config.max_age() {
+        Some(max_age) => cors.max_age(max_age),
+        None => cors,
+    }
+    .build())
 }
 
 fn make_api(
     config: BridgeConfig,
     archive: Archive,
     grpc_client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
+    package_uploaded: PackageUploaded,
 ) -> impl Filter<Extract = impl warp::Reply, Error = Infallible> + Clone {
-    make_create_filter(config.clone(), archive.clone(), grpc_client.clone())
-        .or(make_exercise_filter(config.clone(), archive.clone(), grpc_client.clone()))
-        .or(make_create_and_exercise_filter(config.clone(), archive, grpc_client.clone()))
-        .or(make_fetch_parties_filter(config.clone(), grpc_client.clone()))
-        .or(make_fetch_all_parties_filter(config.clone(), grpc_client.clone()))
-        .or(make_parties_allocate_filter(config.clone(), grpc_client.clone()))
-        .or(make_list_all_packages_filter(config.clone(), grpc_client.clone()))
-        .or(make_get_package_filter(config.clone(), grpc_client.clone()))
-        .or(make_upload_dar_filter(config, grpc_client))
+    make_create_filter(config.clone(), archive.clone(), grpc_client.clone(), metrics.clone(), validator.clone())
+        .or(make_exercise_filter(
+            config.clone(),
+            archive.clone(),
+            grpc_client.clone(),
+            metrics.clone(),
+            validator.clone(),
+        ))
+        .or(make_create_and_exercise_filter(
+            config.clone(),
+            archive.clone(),
+            grpc_client.clone(),
+            metrics.clone(),
+            validator.clone(),
+        ))
+        .or(make_batch_filter(config.clone(), archive, grpc_client.clone(), metrics.clone(), validator))
+        .or(make_fetch_parties_filter(config.clone(), grpc_client.clone(), metrics.clone()))
+        .or(make_fetch_all_parties_filter(config.clone(), grpc_client.clone(), metrics.clone()))
+        .or(make_parties_allocate_filter(config.clone(), grpc_client.clone(), metrics.clone()))
+        .or(make_list_all_packages_filter(config.clone(), grpc_client.clone(), metrics.clone()))
+        .or(make_get_package_filter(config.clone(), grpc_client.clone(), metrics.clone()))
+        .or(make_upload_dar_filter(config, grpc_client.clone(), metrics.clone(), package_uploaded))
+        .or(make_livez_filter())
+        .or(make_readyz_filter(grpc_client.clone(), metrics.clone()))
+        .or(make_metrics_filter(grpc_client, metrics.clone()))
         .or(make_unknown_filter())
+        .with(warp::log::custom(move |info| {
+            metrics.record_request(info.method().as_str(), info.path(), info.status().as_u16(), info.elapsed());
+        }))
 }
 
 /// POST /v1/create
@@ -61,6 +140,8 @@ fn make_create_filter(
     config: BridgeConfig,
     archive: Archive,
     grpc_client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("v1" / "create")
         .and(warp::post())
@@ -69,45 +150,87 @@ fn make_create_filter(
         .and(with_config(config))
         .and(with_archive(archive))
         .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
+        .and(with_validator(validator))
         .and_then(create_handler)
 }
 
-/// POST /v1/exercise
+/// Query parameters supported by the exercise endpoints.
+///
+/// If `verbose` is set then the full transaction tree is included in the exercise result.
+#[derive(Debug, Deserialize)]
+struct ExerciseQuery {
+    #[serde(default)]
+    verbose: bool,
+}
+
+/// POST /v1/exercise[?verbose=true]
 fn make_exercise_filter(
     config: BridgeConfig,
     archive: Archive,
     grpc_client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("v1" / "exercise")
         .and(warp::post())
         .and(warp::body::json())
+        .and(warp::query::<ExerciseQuery>())
         .and(warp::header::optional("Authorization"))
         .and(with_config(config))
         .and(with_archive(archive))
         .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
+        .and(with_validator(validator))
         .and_then(exercise_handler)
 }
 
-/// POST /v1/create-and-exercise
+/// POST /v1/create-and-exercise[?verbose=true]
 fn make_create_and_exercise_filter(
     config: BridgeConfig,
     archive: Archive,
     grpc_client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("v1" / "create-and-exercise")
         .and(warp::post())
         .and(warp::body::json())
+        .and(warp::query::<ExerciseQuery>())
         .and(warp::header::optional("Authorization"))
         .and(with_config(config))
         .and(with_archive(archive))
         .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
+        .and(with_validator(validator))
         .and_then(create_and_exercise_handler)
 }
 
+/// POST /v1/batch
+fn make_batch_filter(
+    config: BridgeConfig,
+    archive: Archive,
+    grpc_client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("v1" / "batch")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(warp::header::optional("Authorization"))
+        .and(with_config(config))
+        .and(with_archive(archive))
+        .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
+        .and(with_validator(validator))
+        .and_then(batch_handler)
+}
+
 /// POST /v1/parties
 fn make_fetch_parties_filter(
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("v1" / "parties")
         .and(warp::post())
@@ -115,6 +238,7 @@ fn make_fetch_parties_filter(
         .and(warp::header::optional("Authorization"))
         .and(with_config(config))
         .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
         .and_then(fetch_parties_handler)
 }
 
@@ -122,12 +246,14 @@ fn make_fetch_parties_filter(
 fn make_fetch_all_parties_filter(
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("v1" / "parties")
         .and(warp::get())
         .and(warp::header::optional("Authorization"))
         .and(with_config(config))
         .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
         .and_then(fetch_all_parties_handler)
 }
 
@@ -135,6 +261,7 @@ fn make_fetch_all_parties_filter(
 fn make_parties_allocate_filter(
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("v1" / "parties" / "allocate")
         .and(warp::post())
@@ -142,6 +269,7 @@ fn make_parties_allocate_filter(
         .and(warp::header::optional("Authorization"))
         .and(with_config(config))
         .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
         .and_then(allocate_party_handler)
 }
 
@@ -149,12 +277,14 @@ fn make_parties_allocate_filter(
 fn make_list_all_packages_filter(
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("v1" / "packages")
         .and(warp::get())
         .and(warp::header::optional("Authorization"))
         .and(with_config(config))
         .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
         .and_then(list_all_packages_handler)
 }
 
@@ -162,12 +292,14 @@ fn make_list_all_packages_filter(
 fn make_get_package_filter(
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("v1" / "packages" / String)
         .and(warp::get())
         .and(warp::header::optional("Authorization"))
         .and(with_config(config))
         .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
         .and_then(get_package_handler)
         .with(warp::reply::with::header("Transfer-Encoding", "chunked"))
 }
@@ -176,6 +308,8 @@ fn make_get_package_filter(
 fn make_upload_dar_filter(
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
+    package_uploaded: PackageUploaded,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("v1" / "packages")
         .and(warp::post())
@@ -184,9 +318,40 @@ fn make_upload_dar_filter(
         .and(warp::header::optional("Authorization"))
         .and(with_config(config))
         .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
+        .and(warp::any().map(move || package_uploaded.clone()))
         .and_then(upload_dar_handler)
 }
 
+/// GET /livez
+fn make_livez_filter() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("livez").and(warp::get()).map(livez_handler)
+}
+
+/// GET /readyz
+fn make_readyz_filter(
+    grpc_client: GrpcClient,
+    metrics: Metrics,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("readyz")
+        .and(warp::get())
+        .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
+        .and_then(readyz_handler)
+}
+
+/// GET /metrics
+fn make_metrics_filter(
+    grpc_client: GrpcClient,
+    metrics: Metrics,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("metrics")
+        .and(warp::get())
+        .and(with_grpc(grpc_client))
+        .and(with_metrics(metrics))
+        .and_then(metrics_handler)
+}
+
 /// Catch all filter to provide a well formed not found error response
 fn make_unknown_filter() -> impl Filter<Extract = impl warp::Reply, Error = Infallible> + Clone {
     warp::any().and(warp::path::full().map(unknown_handler))
@@ -206,34 +371,60 @@ fn with_grpc(grpc: GrpcClient) -> impl Filter<Extract = (GrpcClient,), Error = s
     warp::any().map(move || grpc.clone())
 }
 
+fn with_metrics(metrics: Metrics) -> impl Filter<Extract = (Metrics,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || metrics.clone())
+}
+
+fn with_validator(
+    validator: Validator,
+) -> impl Filter<Extract = (Validator,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || validator.clone())
+}
+
 async fn create_handler(
     create_request: DamlJsonCreateRequest,
     jwt_token: Option<String>,
     config: BridgeConfig,
     archive: Archive,
     grpc_client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
 ) -> Result<impl warp::Reply, Infallible> {
-    Ok(match CreateHandler::new(config, archive, grpc_client).create(create_request, jwt_token.as_deref()).await {
-        Ok(response) => ok_response(&response),
-        Err(error) => err_response(&error),
-    })
+    Ok(
+        match CreateHandler::new(config, archive, grpc_client, metrics, validator)
+            .create(create_request, jwt_token.as_deref())
+            .await
+        {
+            Ok(response) => ok_response(&response),
+            Err(error) => err_response(&error),
+        },
+    )
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn exercise_handler(
     exercise_request: DamlJsonExerciseRequestType,
+    query: ExerciseQuery,
     jwt_token: Option<String>,
     config: BridgeConfig,
     archive: Archive,
     grpc_client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
 ) -> Result<impl warp::Reply, Infallible> {
     Ok(match exercise_request {
         DamlJsonExerciseRequestType::Exercise(req) =>
-            match ExerciseHandler::new(config, archive, grpc_client).exercise(req, jwt_token.as_deref()).await {
+            match ExerciseHandler::new(config, archive, grpc_client, metrics, validator)
+                .with_verbose(query.verbose)
+                .exercise(req, jwt_token.as_deref())
+                .await
+            {
                 Ok(response) => ok_response(&response),
                 Err(error) => err_response(&error),
             },
         DamlJsonExerciseRequestType::ExerciseByKey(req) =>
-            match ExerciseByKeyHandler::new(config, archive, grpc_client)
+            match ExerciseByKeyHandler::new(config, archive, grpc_client, metrics, validator)
+                .with_verbose(query.verbose)
                 .exercise_by_key(req, jwt_token.as_deref())
                 .await
             {
@@ -247,15 +438,20 @@ async fn exercise_handler(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn create_and_exercise_handler(
     create_and_exercise_request: DamlJsonCreateAndExerciseRequest,
+    query: ExerciseQuery,
     jwt_token: Option<String>,
     config: BridgeConfig,
     archive: Archive,
     grpc_client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
 ) -> Result<impl warp::Reply, Infallible> {
     Ok(
-        match CreateAndExerciseHandler::new(config, archive, grpc_client)
+        match CreateAndExerciseHandler::new(config, archive, grpc_client, metrics, validator)
+            .with_verbose(query.verbose)
             .create_and_exercise(create_and_exercise_request, jwt_token.as_deref())
             .await
         {
@@ -265,24 +461,49 @@ async fn create_and_exercise_handler(
     )
 }
 
+async fn batch_handler(
+    batch_request: DamlJsonBatchRequest,
+    jwt_token: Option<String>,
+    config: BridgeConfig,
+    archive: Archive,
+    grpc_client: GrpcClient,
+    metrics: Metrics,
+    validator: Validator,
+) -> Result<impl warp::Reply, Infallible> {
+    Ok(
+        match BatchHandler::new(config, archive, grpc_client, metrics, validator)
+            .batch(batch_request, jwt_token.as_deref())
+            .await
+        {
+            Ok(response) => ok_response(&response),
+            Err(error) => err_response(&error),
+        },
+    )
+}
+
 async fn fetch_parties_handler(
     fetch_request: DamlJsonFetchPartiesRequest,
     jwt_token: Option<String>,
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
 ) -> Result<impl warp::Reply, Infallible> {
-    Ok(match PartiesHandler::new(config, grpc_client).fetch_parties(fetch_request, jwt_token.as_deref()).await {
-        Ok(response) => ok_response(&response),
-        Err(error) => err_response(&error),
-    })
+    Ok(
+        match PartiesHandler::new(config, grpc_client, metrics).fetch_parties(fetch_request, jwt_token.as_deref()).await
+        {
+            Ok(response) => ok_response(&response),
+            Err(error) => err_response(&error),
+        },
+    )
 }
 
 async fn fetch_all_parties_handler(
     jwt_token: Option<String>,
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
 ) -> Result<impl warp::Reply, Infallible> {
-    Ok(match PartiesHandler::new(config, grpc_client).fetch_all_parties(jwt_token.as_deref()).await {
+    Ok(match PartiesHandler::new(config, grpc_client, metrics).fetch_all_parties(jwt_token.as_deref()).await {
         Ok(response) => ok_response(&response),
         Err(error) => err_response(&error),
     })
@@ -293,19 +514,26 @@ async fn allocate_party_handler(
     jwt_token: Option<String>,
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
 ) -> Result<impl warp::Reply, Infallible> {
-    Ok(match PartiesHandler::new(config, grpc_client).allocate_parties(allocate_request, jwt_token.as_deref()).await {
-        Ok(response) => ok_response(&response),
-        Err(error) => err_response(&error),
-    })
+    Ok(
+        match PartiesHandler::new(config, grpc_client, metrics)
+            .allocate_parties(allocate_request, jwt_token.as_deref())
+            .await
+        {
+            Ok(response) => ok_response(&response),
+            Err(error) => err_response(&error),
+        },
+    )
 }
 
 async fn list_all_packages_handler(
     jwt_token: Option<String>,
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
 ) -> Result<impl warp::Reply, Infallible> {
-    Ok(match PackagesHandler::new(config, grpc_client).list_all_packages(jwt_token.as_deref()).await {
+    Ok(match PackagesHandler::new(config, grpc_client, metrics).list_all_packages(jwt_token.as_deref()).await {
         Ok(response) => ok_response(&response),
         Err(error) => err_response(&error),
     })
@@ -316,8 +544,9 @@ async fn get_package_handler(
     jwt_token: Option<String>,
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
 ) -> Result<Box<dyn warp::Reply>, Infallible> {
-    Ok(match PackagesHandler::new(config, grpc_client).get_package(&package_id, jwt_token.as_deref()).await {
+    Ok(match PackagesHandler::new(config, grpc_client, metrics).get_package(&package_id, jwt_token.as_deref()).await {
         Ok(response) => Box::new(ok_response_bytes(response)),
         Err(error) => Box::new(err_response(&error)),
     })
@@ -328,13 +557,50 @@ async fn upload_dar_handler(
     jwt_token: Option<String>,
     config: BridgeConfig,
     grpc_client: GrpcClient,
+    metrics: Metrics,
+    package_uploaded: PackageUploaded,
 ) -> Result<impl warp::Reply, Infallible> {
-    Ok(match PackagesHandler::new(config, grpc_client).upload_dar(payload, jwt_token.as_deref()).await {
-        Ok(response) => ok_response(&response),
+    Ok(match PackagesHandler::new(config, grpc_client, metrics).upload_dar(payload, jwt_token.as_deref()).await {
+        Ok(response) => {
+            package_uploaded.notify_one();
+            ok_response(&response)
+        },
         Err(error) => err_response(&error),
     })
 }
 
+fn livez_handler() -> impl warp::Reply {
+    health_response(HealthHandler::livez())
+}
+
+async fn readyz_handler(grpc_client: GrpcClient, metrics: Metrics) -> Result<impl warp::Reply, Infallible> {
+    Ok(health_response(HealthHandler::new(grpc_client, metrics).readyz().await))
+}
+
+async fn metrics_handler(grpc_client: GrpcClient, metrics: Metrics) -> Result<impl warp::Reply, Infallible> {
+    Ok(warp::reply::with_header(
+        HealthHandler::new(grpc_client, metrics).metrics(),
+        "Content-Type",
+        "text/plain; version=0.0.4",
+    ))
+}
+
+/// Check an origin is of the form `scheme://host[:port]` as `warp` will panic otherwise.
+fn validate_origin(origin: &str) -> anyhow::Result<()> {
+    let (scheme, authority) = origin.split_once("://").ok_or_else(|| anyhow!("invalid CORS origin: {origin}"))?;
+    Scheme::from_str(scheme).map_err(|e| anyhow!("invalid CORS origin {origin}: {e}"))?;
+    Authority::from_str(authority).map_err(|e| anyhow!("invalid CORS origin {origin}: {e}"))?;
+    Ok(())
+}
+
+async fn cors_rejection_handler(rejection: Rejection) -> Result<impl warp::Reply, Infallible> {
+    let error = rejection.find::<CorsForbidden>().map_or_else(
+        || DamlJsonErrorResponse::single(500, format!("unhandled rejection: {rejection:?}")),
+        |forbidden| DamlJsonErrorResponse::single(403, forbidden.to_string()),
+    );
+    Ok(err_response(&error))
+}
+
 fn unknown_handler(path: FullPath) -> impl warp::Reply {
     err_response(&DamlJsonErrorResponse::single(404, format!("not found: {}", path.as_str())))
 }
@@ -347,6 +613,46 @@ fn ok_response_bytes(bytes: Vec<u8>) -> WithStatus<Vec<u8>> {
     with_status(bytes, StatusCode::OK)
 }
 
+fn health_response(response: HealthCheckResponse) -> WithStatus<String> {
+    let status = if response.healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    with_status(response.report, status)
+}
+
 fn err_response(error: &DamlJsonErrorResponse) -> WithStatus<warp::reply::Json> {
     with_status(json(error), StatusCode::from_u16(error.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_origin() {
+        assert!(validate_origin("https://example.com").is_ok());
+        assert!(validate_origin("http://localhost:8080").is_ok());
+        assert!(validate_origin("example.com").is_err());
+        assert!(validate_origin("https://").is_err());
+        assert!(validate_origin("https://exa mple.com").is_err());
+        assert!(validate_origin("ht tp://example.com").is_err());
+    }
+
+    #[test]
+    fn test_make_cors() {
+        let any_origin = BridgeCorsConfig::new(vec![], vec![], false, None);
+        assert!(make_cors(&any_origin).is_ok());
+        let explicit_with_credentials = BridgeCorsConfig::new(vec!["https://example.com".into()], vec![], true, None);
+        assert!(make_cors(&explicit_with_credentials).is_ok());
+        let invalid_origin = BridgeCorsConfig::new(vec!["example.com".into()], vec![], false, None);
+        assert!(make_cors(&invalid_origin).is_err());
+    }
+
+    #[test]
+    fn test_make_cors_rejects_credentials_for_any_origin() {
+        let any_origin_with_credentials = BridgeCorsConfig::new(vec![], vec![], true, None);
+        assert!(make_cors(&any_origin_with_credentials).is_err());
+    }
+}
@@ -0,0 +1,348 @@
+use crate::handler::common::{internal_server_error, make_error_response, request_conversion_error, JsonResult};
+use daml_json::error::DamlJsonReqConError;
+use daml_json::request::{
+    DamlJsonBatchCommand, DamlJsonBatchRequest, DamlJsonCreateAndExerciseRequest, DamlJsonCreateRequest,
+    DamlJsonErrorResponse, DamlJsonExerciseByKeyRequest, DamlJsonExerciseRequest, DamlJsonValidationError,
+    DamlJsonValidationReport,
+};
+use daml_json::request_converter::{DamlJsonTemplateId, JsonToGrpcRequestConverter};
+use daml_json::schema_encoder::{
+    DataDict, JsonSchemaEncoder, ReferenceMode, RenderDescription, RenderSchema, RenderTitle, SchemaEncoderConfig,
+};
+use daml_lf::element::{DamlArchive, DamlTemplate};
+use jsonschema::output::BasicOutput;
+use jsonschema::JSONSchema;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::{Arc, RwLock};
+
+/// The `$ref` prefix used for data types referenced from a bundled schema.
+const DEFS_REFERENCE_PREFIX: &str = "#/$defs/";
+
+/// Validate the JSON values of incoming requests against the JSON schema of their Daml types.
+///
+/// Schemas are generated by the [`JsonSchemaEncoder`] and compiled on first use, the compiled schemas are cached per
+/// template and choice until the cache is invalidated by a call to [`SchemaValidator::invalidate`].
+///
+/// Every violation found in a request is reported using the JSON schema "basic" output format, the instance location
+/// of each violation is a JSON pointer relative to the root of the request.
+#[derive(Debug, Default)]
+pub struct SchemaValidator {
+    cache: RwLock<HashMap<SchemaKey, Arc<JSONSchema>>>,
+}
+
+/// Identifies the schema of a JSON value within a request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SchemaKey {
+    package_id: String,
+    module: String,
+    entity: String,
+    kind: SchemaKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum SchemaKind {
+    Payload,
+    Key,
+    Choice(String),
+}
+
+impl SchemaValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate the `payload` of a [`DamlJsonCreateRequest`].
+    pub fn validate_create(&self, archive: &DamlArchive<'_>, create: &DamlJsonCreateRequest) -> JsonResult<()> {
+        self.validate(archive, &create.template_id, &[(SchemaKind::Payload, &create.payload, "/payload")])
+    }
+
+    /// Validate the `argument` of a [`DamlJsonExerciseRequest`].
+    pub fn validate_exercise(&self, archive: &DamlArchive<'_>, exercise: &DamlJsonExerciseRequest) -> JsonResult<()> {
+        self.validate(archive, &exercise.template_id, &[(
+            SchemaKind::Choice(exercise.choice.clone()),
+            &exercise.argument,
+            "/argument",
+        )])
+    }
+
+    /// Validate the `key` and `argument` of a [`DamlJsonExerciseByKeyRequest`].
+    pub fn validate_exercise_by_key(
+        &self,
+        archive: &DamlArchive<'_>,
+        exercise: &DamlJsonExerciseByKeyRequest,
+    ) -> JsonResult<()> {
+        self.validate(archive, &exercise.template_id, &[
+            (SchemaKind::Key, &exercise.key, "/key"),
+            (SchemaKind::Choice(exercise.choice.clone()), &exercise.argument, "/argument"),
+        ])
+    }
+
+    /// Validate the `payload` and `argument` of a [`DamlJsonCreateAndExerciseRequest`].
+    pub fn validate_create_and_exercise(
+        &self,
+        archive: &DamlArchive<'_>,
+        create_and_exercise: &DamlJsonCreateAndExerciseRequest,
+    ) -> JsonResult<()> {
+        self.validate(archive, &create_and_exercise.template_id, &[
+            (SchemaKind::Payload, &create_and_exercise.payload, "/payload"),
+            (SchemaKind::Choice(create_and_exercise.choice.clone()), &create_and_exercise.argument, "/argument"),
+        ])
+    }
+
+    /// Validate the JSON values of every command of a [`DamlJsonBatchRequest`].
+    ///
+    /// Violations are reported for all commands of the batch, the instance location of each violation is relative to
+    /// the root of the batch request, such as `/commands/1/exercise/argument`.
+    pub fn validate_batch(&self, archive: &DamlArchive<'_>, batch: &DamlJsonBatchRequest) -> JsonResult<()> {
+        let mut errors = vec![];
+        for (index, command) in batch.commands.iter().enumerate() {
+            let violations = match command {
+                DamlJsonBatchCommand::Create(create) => self.violations(archive, &create.template_id, &[(
+                    SchemaKind::Payload,
+                    &create.payload,
+                    &format!("/commands/{index}/create/payload"),
+                )])?,
+                DamlJsonBatchCommand::Exercise(exercise) => self.violations(archive, &exercise.template_id, &[(
+                    SchemaKind::Choice(exercise.choice.clone()),
+                    &exercise.argument,
+                    &format!("/commands/{index}/exercise/argument"),
+                )])?,
+                DamlJsonBatchCommand::ExerciseByKey(exercise) => self.violations(archive, &exercise.template_id, &[
+                    (SchemaKind::Key, &exercise.key, &format!("/commands/{index}/exerciseByKey/key")),
+                    (
+                        SchemaKind::Choice(exercise.choice.clone()),
+                        &exercise.argument,
+                        &format!("/commands/{index}/exerciseByKey/argument"),
+                    ),
+                ])?,
+            };
+            errors.extend(violations);
+        }
+        report(errors)
+    }
+
+    /// Discard all cached schemas.
+    ///
+    /// This must be called whenever the [`DamlArchive`] used for validation changes.
+    pub fn invalidate(&self) {
+        self.cache.write().unwrap_or_else(std::sync::PoisonError::into_inner).clear();
+    }
+
+    /// Validate each JSON `value` located at JSON pointer `pointer` in the request against the schema of `kind`.
+    fn validate(
+        &self,
+        archive: &DamlArchive<'_>,
+        template_id: &str,
+        values: &[(SchemaKind, &Value, &str)],
+    ) -> JsonResult<()> {
+        report(self.violations(archive, template_id, values)?)
+    }
+
+    /// Find every violation of each JSON `value` located at JSON pointer `pointer` against the schema of `kind`.
+    fn violations(
+        &self,
+        archive: &DamlArchive<'_>,
+        template_id: &str,
+        values: &[(SchemaKind, &Value, &str)],
+    ) -> JsonResult<Vec<DamlJsonValidationError>> {
+        let template_id = DamlJsonTemplateId::try_from(template_id).map_err(request_conversion_error)?;
+        let converter = JsonToGrpcRequestConverter::new(archive);
+        let (_, template) = converter.resolve_template(&template_id).map_err(request_conversion_error)?;
+        let mut errors = vec![];
+        for (kind, value, pointer) in values {
+            let schema = self.compiled_schema(archive, template, kind)?;
+            errors.extend(violations(&schema, value, pointer));
+        }
+        Ok(errors)
+    }
+
+    /// Get the compiled schema of `kind` for `template` from the cache, compiling it if required.
+    fn compiled_schema(
+        &self,
+        archive: &DamlArchive<'_>,
+        template: &DamlTemplate<'_>,
+        kind: &SchemaKind,
+    ) -> JsonResult<Arc<JSONSchema>> {
+        let key = SchemaKey {
+            package_id: template.package_id().to_owned(),
+            module: template.module_path().collect::<Vec<_>>().join("."),
+            entity: template.name().to_owned(),
+            kind: kind.clone(),
+        };
+        if let Some(schema) = self.cache.read().unwrap_or_else(std::sync::PoisonError::into_inner).get(&key) {
+            return Ok(schema.clone());
+        }
+        let schema = Arc::new(compile_schema(archive, template, kind)?);
+        self.cache.write().unwrap_or_else(std::sync::PoisonError::into_inner).insert(key, schema.clone());
+        Ok(schema)
+    }
+}
+
+/// Generate and compile a self-contained JSON schema of `kind` for `template`.
+fn compile_schema(archive: &DamlArchive<'_>, template: &DamlTemplate<'_>, kind: &SchemaKind) -> JsonResult<JSONSchema> {
+    let encoder = JsonSchemaEncoder::new_with_config(
+        archive,
+        SchemaEncoderConfig::new(
+            RenderSchema::None,
+            RenderTitle::None,
+            RenderDescription::None,
+            ReferenceMode::Reference {
+                prefix: DEFS_REFERENCE_PREFIX.to_owned(),
+            },
+            DataDict::default(),
+        ),
+    );
+    let schema = match kind {
+        SchemaKind::Payload => encoder.encode_template(template).map(|schema| schema.create_payload),
+        SchemaKind::Key => {
+            let key = template.key().ok_or_else(|| {
+                request_conversion_error(DamlJsonReqConError::TemplateNoKeyError(template.name().to_owned()))
+            })?;
+            encoder.encode_type(key.ty())
+        },
+        SchemaKind::Choice(name) => {
+            let choice = template.choices().iter().find(|choice| choice.name() == name).ok_or_else(|| {
+                make_error_response(format!("unknown choice {} for template {}", name, template.name()), 400)
+            })?;
+            encoder.encode_choice(choice).map(|schema| schema.argument)
+        },
+    }
+    .and_then(|schema| encoder.bundle(schema))
+    .map_err(internal_server_error)?;
+    JSONSchema::compile(&schema).map_err(|err| make_error_response(err.to_string(), 500))
+}
+
+/// Report the violations found in a request, if any.
+fn report(errors: Vec<DamlJsonValidationError>) -> JsonResult<()> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(DamlJsonErrorResponse::from_validation_report(400, DamlJsonValidationReport::new(errors)))
+    }
+}
+
+/// Validate `value` against `schema` and return every violation found.
+///
+/// The instance location of each violation is prefixed with `pointer`, the location of `value` within the request.
+fn violations(schema: &JSONSchema, value: &Value, pointer: &str) -> Vec<DamlJsonValidationError> {
+    match schema.apply(value).basic() {
+        BasicOutput::Valid(_) => vec![],
+        BasicOutput::Invalid(errors) => errors
+            .iter()
+            .map(|error| DamlJsonValidationError {
+                keyword_location: error.keyword_location().to_string(),
+                instance_location: format!("{}{}", pointer, error.instance_location()),
+                error: error.error_description().to_string(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use daml_lf::DarFile;
+    use serde_json::json;
+
+    static TESTING_TYPES_DAR_PATH: &str = "../resources/testing_types_sandbox/TestingTypes-latest.dar";
+
+    fn make_create(payload: Value) -> DamlJsonCreateRequest {
+        DamlJsonCreateRequest::new("Fuji.PingPong:Ping", payload)
+    }
+
+    #[test]
+    fn test_valid_create() -> anyhow::Result<()> {
+        let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+        let validator = SchemaValidator::new();
+        let create = make_create(json!({"sender": "Alice", "receiver": "Bob", "count": 0}));
+        dar.apply(|archive| {
+            assert!(validator.validate_create(archive, &create).is_ok());
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_create_reports_all_violations() -> anyhow::Result<()> {
+        let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+        let validator = SchemaValidator::new();
+        let create = make_create(json!({"sender": 1, "receiver": true, "count": 0}));
+        let response = dar.apply(|archive| validator.validate_create(archive, &create))?.unwrap_err();
+        assert_eq!(response.status, 400);
+        let report = response.validation.unwrap();
+        assert!(!report.valid);
+        assert!(report.errors.iter().any(|e| e.instance_location == "/payload/sender"));
+        assert!(report.errors.iter().any(|e| e.instance_location == "/payload/receiver"));
+        assert_eq!(response.errors.len(), report.errors.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_exercise_by_key() -> anyhow::Result<()> {
+        let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+        let validator = SchemaValidator::new();
+        let exercise = DamlJsonExerciseByKeyRequest::new(
+            "Fuji.PingPong:Ping",
+            json!({"sender": "Alice", "count": true}),
+            "RespondPong",
+            json!({}),
+        );
+        let response = dar.apply(|archive| validator.validate_exercise_by_key(archive, &exercise))?.unwrap_err();
+        let report = response.validation.unwrap();
+        assert!(report.errors.iter().all(|e| e.instance_location.starts_with("/key")));
+        assert!(!report.errors.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_batch_reports_all_violations() -> anyhow::Result<()> {
+        let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+        let validator = SchemaValidator::new();
+        let batch = DamlJsonBatchRequest::new(vec![
+            DamlJsonBatchCommand::Create(make_create(json!({"sender": 1, "receiver": "Bob", "count": 0}))),
+            DamlJsonBatchCommand::Create(make_create(json!({"sender": "Alice", "receiver": "Bob", "count": 0}))),
+            DamlJsonBatchCommand::ExerciseByKey(DamlJsonExerciseByKeyRequest::new(
+                "Fuji.PingPong:Ping",
+                json!({"sender": "Alice", "count": true}),
+                "RespondPong",
+                json!({}),
+            )),
+        ]);
+        let response = dar.apply(|archive| validator.validate_batch(archive, &batch))?.unwrap_err();
+        assert_eq!(response.status, 400);
+        let report = response.validation.unwrap();
+        assert!(report.errors.iter().any(|e| e.instance_location == "/commands/0/create/payload/sender"));
+        assert!(report.errors.iter().any(|e| e.instance_location.starts_with("/commands/2/exerciseByKey/key")));
+        assert!(report.errors.iter().all(|e| !e.instance_location.starts_with("/commands/1")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_choice() -> anyhow::Result<()> {
+        let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+        let validator = SchemaValidator::new();
+        let exercise = DamlJsonExerciseRequest::new("Fuji.PingPong:Ping", "#0:0", "Unknown", json!({}));
+        let response = dar.apply(|archive| validator.validate_exercise(archive, &exercise))?.unwrap_err();
+        assert_eq!(response.status, 400);
+        assert!(response.validation.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_invalidation() -> anyhow::Result<()> {
+        let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+        let validator = SchemaValidator::new();
+        let create = make_create(json!({"sender": "Alice", "receiver": "Bob", "count": 0}));
+        let exercise = DamlJsonExerciseRequest::new("Fuji.PingPong:Ping", "#0:0", "ResetPingCount", json!({}));
+        dar.apply(|archive| {
+            assert!(validator.validate_create(archive, &create).is_ok());
+            assert!(validator.validate_create(archive, &create).is_ok());
+            assert!(validator.validate_exercise(archive, &exercise).is_ok());
+        })?;
+        assert_eq!(validator.cache.read().unwrap().len(), 2);
+        validator.invalidate();
+        assert!(validator.cache.read().unwrap().is_empty());
+        Ok(())
+    }
+}
@@ -4,7 +4,9 @@ use daml::grpc_api::DamlGrpcClientBuilder;
 use daml::util::DamlSandboxTokenBuilder;
 use daml_bridge::{Bridge, BridgeConfigData};
 use daml_json::data::{DamlJsonCreatedEvent, DamlJsonEvent, DamlJsonParty};
-use daml_json::request::DamlJsonRequestMeta;
+use daml_json::request::{
+    DamlJsonBatchCommand, DamlJsonBatchResult, DamlJsonCreateRequest, DamlJsonExerciseByKeyRequest, DamlJsonRequestMeta,
+};
 use daml_json::service::{DamlJsonClient, DamlJsonClientBuilder};
 use daml_lf::DarFile;
 use serde_json::json;
@@ -136,6 +138,46 @@ async fn test_exercise_by_key() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_batch() -> anyhow::Result<()> {
+    let _lock = initialize().await;
+    spawn_bridge().await?;
+    let alice_client = new_client()?;
+    alice_client.create("Fuji.PingPong:Ping", json!({ "sender": "Alice", "receiver": "Bob", "count": 5 })).await?;
+    let batch_result = alice_client
+        .batch(vec![
+            DamlJsonBatchCommand::Create(DamlJsonCreateRequest::new(
+                "Fuji.PingPong:Ping",
+                json!({ "sender": "Alice", "receiver": "Bob", "count": 0 }),
+            )),
+            DamlJsonBatchCommand::ExerciseByKey(DamlJsonExerciseByKeyRequest::new(
+                "Fuji.PingPong:Ping",
+                json!({"sender": "Alice", "count": 5}),
+                "FromUserData",
+                json!({"new_count": "3", "new_data": {"name": "Alice", "new_value": 8 }}),
+            )),
+        ])
+        .await?;
+    match batch_result.as_slice() {
+        [DamlJsonBatchResult::Created(created), DamlJsonBatchResult::Exercised(exercised)] => {
+            assert_eq!(created.payload, json!({ "sender": "Alice", "receiver": "Bob", "count": "0" }));
+            match exercised.events.as_slice() {
+                [.., DamlJsonEvent::Created(DamlJsonCreatedEvent {
+                    payload,
+                    ..
+                })]
+                | [DamlJsonEvent::Created(DamlJsonCreatedEvent {
+                    payload,
+                    ..
+                }), ..] => assert_eq!(*payload, json!({ "sender": "Alice", "receiver": "Bob", "count": "11" })),
+                _ => panic!(),
+            }
+        },
+        _ => panic!(),
+    }
+    Ok(())
+}
+
 #[ignore]
 #[tokio::test]
 async fn test_fetch() -> anyhow::Result<()> {
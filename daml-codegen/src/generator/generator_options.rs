@@ -5,6 +5,8 @@ pub enum RenderMethod {
     Full,
     /// Render Rust types annotated with attributes such as `[DamlData]`.
     Intermediate,
+    /// Render Rust types which serialize to the Daml-LF JSON encoding with typed `DamlJsonClient` wrappers.
+    Json,
 }
 
 /// Render each module as a separate file or combined in a single file.
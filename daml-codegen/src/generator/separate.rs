@@ -1,6 +1,8 @@
 use crate::generator::module_matcher::ModuleMatcher;
 use crate::generator::RenderMethod;
-use crate::renderer::{quote_all_data, to_module_path, to_rust_identifier, RenderContext, RenderFilterMode};
+use crate::renderer::{
+    quote_all_data, quote_prelude, to_module_path, to_rust_identifier, RenderContext, RenderFilterMode,
+};
 use daml_lf::element::{DamlArchive, DamlModule, DamlPackage};
 use itertools::Itertools;
 use std::fs::File;
@@ -10,7 +12,6 @@ use std::path::PathBuf;
 use std::{fs, io};
 
 const DISABLE_WARNINGS: &str = "#![allow(clippy::all, warnings)]";
-const USE_DAML_PRELUDE: &str = "use ::daml::prelude::*;";
 
 pub fn generate_archive_separate(
     archive: &DamlArchive<'_>,
@@ -55,7 +56,7 @@ fn generate_module_source(
     let sub_modules: Vec<_> = module.child_modules().filter(|&m| is_interesting_module(m, module_matcher)).collect();
     let sub_module_decl: String = sub_modules.iter().map(|&m| make_pub_mod_declaration(m.local_name())).join("\n");
     let module_types_text = quote_module_data_types(ctx, module, render_method);
-    let module_body = format!("{}\n{}{}", USE_DAML_PRELUDE, sub_module_decl, module_types_text);
+    let module_body = format!("{}\n{}{}", quote_prelude(render_method), sub_module_decl, module_types_text);
     let module_path: Vec<_> = module.path().collect();
     let module_dir_path = module_path[..module_path.len() - 1].iter().map(to_rust_identifier).join("/");
     let package_module_dir_path = PathBuf::from(package_dir_path).join(module_dir_path);
@@ -24,6 +24,13 @@ fn main() {
                 .help("Sets the regex module filter to apply"),
         )
         .arg(Arg::new("intermediate").short('i').long("render-intermediate").help("Generate intermediate types"))
+        .arg(
+            Arg::new("json")
+                .short('j')
+                .long("render-json")
+                .conflicts_with("intermediate")
+                .help("Generate JSON API types and typed client wrappers"),
+        )
         .arg(Arg::new("combine").short('c').long("combine-modules").help("Combine modules as a single file"))
         .get_matches();
     let dar_file = matches.value_of("dar").unwrap();
@@ -35,6 +42,8 @@ fn main() {
     };
     let render_method = if matches.is_present("intermediate") {
         RenderMethod::Intermediate
+    } else if matches.is_present("json") {
+        RenderMethod::Json
     } else {
         RenderMethod::Full
     };
@@ -8,6 +8,7 @@ use self::intermediate::quote_daml_enum as quote_daml_enum_intermediate;
 use self::intermediate::quote_daml_record as quote_daml_record_intermediate;
 use self::intermediate::quote_daml_template as quote_daml_template_intermediate;
 use self::intermediate::quote_daml_variant as quote_daml_variant_intermediate;
+use self::json::{quote_json_enum, quote_json_record, quote_json_template, quote_json_variant};
 use crate::generator::RenderMethod;
 use daml_lf::element::DamlData;
 
@@ -33,6 +34,16 @@ pub mod full {
     pub use quote_variant::*;
 }
 
+pub mod json {
+    mod quote_json_data;
+    mod quote_json_template;
+    mod quote_json_type;
+
+    pub use quote_json_data::*;
+    pub use quote_json_template::*;
+    pub use quote_json_type::*;
+}
+
 mod intermediate {
     mod quote_intermediate_data;
 
@@ -65,5 +76,11 @@ pub fn quote_data(ctx: &RenderContext<'_>, data_type: &DamlData<'_>, render_meth
             DamlData::Variant(variant) => quote_daml_variant_intermediate(ctx, variant),
             DamlData::Enum(data_enum) => quote_daml_enum_intermediate(ctx, data_enum),
         },
+        RenderMethod::Json => match data_type {
+            DamlData::Template(template) => quote_json_template(ctx, template),
+            DamlData::Record(record) => quote_json_record(ctx, record),
+            DamlData::Variant(variant) => quote_json_variant(ctx, variant),
+            DamlData::Enum(data_enum) => quote_json_enum(ctx, data_enum),
+        },
     }
 }
@@ -0,0 +1,201 @@
+use crate::renderer::data_renderer::json::{json_params, quote_json_params, quote_json_type, quote_json_var};
+use crate::renderer::{make_ignored_ident, quote_escaped_ident, IsRenderable, RenderContext};
+use daml_lf::element::{DamlEnum, DamlField, DamlRecord, DamlType, DamlTypeVarWithKind, DamlVariant};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Quote the `derive` and `serde` attributes common to all JSON data types.
+pub fn quote_json_derive() -> TokenStream {
+    quote!(
+        #[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Debug)]
+        #[serde(crate = "::daml::json_prelude::serde")]
+    )
+}
+
+/// Generate the `Foo` struct and methods.
+pub fn quote_json_record(ctx: &RenderContext<'_>, daml_record: &DamlRecord<'_>) -> TokenStream {
+    quote_json_record_and_impl(ctx, daml_record.name(), daml_record.fields(), daml_record.type_params())
+}
+
+/// Generate the `Foo` struct and methods.
+pub fn quote_json_record_and_impl(
+    ctx: &RenderContext<'_>,
+    name: &str,
+    fields: &[DamlField<'_>],
+    params: &[DamlTypeVarWithKind<'_>],
+) -> TokenStream {
+    let supported_fields: Vec<_> =
+        fields.iter().filter(|&field| IsRenderable::new(ctx).check_type(field.ty())).collect();
+    let struct_tokens = quote_struct(name, &supported_fields, params);
+    let new_method_tokens = quote_new_method(name, &supported_fields, params);
+    quote!(
+        #struct_tokens
+        #new_method_tokens
+    )
+}
+
+/// Generate the variant `enum` with an adjacently tagged (`{"tag": ..., "value": ...}`) JSON encoding.
+pub fn quote_json_variant(ctx: &RenderContext<'_>, variant: &DamlVariant<'_>) -> TokenStream {
+    let supported_fields: Vec<_> =
+        variant.fields().iter().filter(|&field| IsRenderable::new(ctx).check_type(field.ty())).collect();
+    let derive_tokens = quote_json_derive();
+    let enum_name_tokens = quote_escaped_ident(variant.name());
+    let param_tokens = quote_json_params(variant.type_params());
+    let all_constructors: Vec<_> = supported_fields
+        .iter()
+        .map(|&field| {
+            let constructor_string = field.name();
+            let constructor_tokens = quote_escaped_ident(field.name());
+            let type_tokens = quote_json_type(field.ty());
+            quote!(
+                #[serde(rename = #constructor_string)]
+                #constructor_tokens(#type_tokens)
+            )
+        })
+        .collect();
+    let unused_params: Vec<_> = unused_params(variant.type_params(), &supported_fields)
+        .map(|param| {
+            let param_tokens = quote_json_var(param.var());
+            quote!( ::std::marker::PhantomData<#param_tokens> )
+        })
+        .collect();
+    let phantom_tokens = if unused_params.is_empty() {
+        quote!()
+    } else {
+        quote!(
+            #[serde(skip)]
+            _UnsupportedTypes( #( #unused_params ),* ),
+        )
+    };
+    quote!(
+        #derive_tokens
+        #[serde(tag = "tag", content = "value")]
+        pub enum #enum_name_tokens #param_tokens {
+            #( #all_constructors ,)*
+            #phantom_tokens
+        }
+    )
+}
+
+/// Generate the `enum` with a JSON string encoding.
+pub fn quote_json_enum(_ctx: &RenderContext<'_>, daml_enum: &DamlEnum<'_>) -> TokenStream {
+    let derive_tokens = quote_json_derive();
+    let enum_name_tokens = quote_escaped_ident(daml_enum.name());
+    let all_constructors: Vec<_> = daml_enum
+        .constructors()
+        .map(|constructor_string| {
+            let constructor_tokens = quote_escaped_ident(constructor_string);
+            quote!(
+                #[serde(rename = #constructor_string)]
+                #constructor_tokens
+            )
+        })
+        .collect();
+    quote!(
+        #derive_tokens
+        pub enum #enum_name_tokens {
+            #( #all_constructors ,)*
+        }
+    )
+}
+
+/// Quote the arguments to a method.
+pub fn quote_json_method_arguments(fields: &[&DamlField<'_>]) -> TokenStream {
+    let all_fields: Vec<_> = fields
+        .iter()
+        .map(|&field| {
+            let field_label = quote_escaped_ident(field.name());
+            let field_type_rendered = quote_json_type(field.ty());
+            quote!(#field_label: impl Into<#field_type_rendered>)
+        })
+        .collect();
+    quote!( #( #all_fields ,)* )
+}
+
+/// Generate `struct Foo {...}` struct.
+fn quote_struct(
+    struct_name: &str,
+    struct_fields: &[&DamlField<'_>],
+    params: &[DamlTypeVarWithKind<'_>],
+) -> TokenStream {
+    let derive_tokens = quote_json_derive();
+    let struct_name_tokens = quote_escaped_ident(struct_name);
+    let param_tokens = quote_json_params(params);
+    let all_fields: Vec<_> = struct_fields.iter().map(|&field| quote_struct_field(field)).collect();
+    let all_phantoms: Vec<_> = unused_params(params, struct_fields)
+        .map(|param| {
+            let name_tokens = quote_escaped_ident(make_ignored_ident(param.var()));
+            let param_tokens = quote_json_var(param.var());
+            quote!(
+                #[serde(skip)]
+                #name_tokens: ::std::marker::PhantomData<#param_tokens>
+            )
+        })
+        .collect();
+    quote!(
+        #derive_tokens
+        pub struct #struct_name_tokens #param_tokens {
+            #( #all_fields ,)*
+            #( #all_phantoms ,)*
+        }
+    )
+}
+
+/// Generate a struct field, top level `Optional` fields are omitted from the JSON encoding when `None`.
+fn quote_struct_field(field: &DamlField<'_>) -> TokenStream {
+    let field_string = field.name();
+    let field_label = quote_escaped_ident(field.name());
+    let field_type_rendered = quote_json_type(field.ty());
+    if let DamlType::Optional(_) = field.ty() {
+        quote!(
+            #[serde(rename = #field_string, skip_serializing_if = "Option::is_none")]
+            pub #field_label: #field_type_rendered
+        )
+    } else {
+        quote!(
+            #[serde(rename = #field_string)]
+            pub #field_label: #field_type_rendered
+        )
+    }
+}
+
+/// Generate the `Foo::new(...)` method.
+fn quote_new_method(
+    struct_name: &str,
+    struct_fields: &[&DamlField<'_>],
+    params: &[DamlTypeVarWithKind<'_>],
+) -> TokenStream {
+    let struct_name_tokens = quote_escaped_ident(struct_name);
+    let param_tokens = quote_json_params(params);
+    let method_arguments_tokens = quote_json_method_arguments(struct_fields);
+    let all_fields_init: Vec<_> = struct_fields
+        .iter()
+        .map(|&field| {
+            let field_label = quote_escaped_ident(field.name());
+            quote!(#field_label: #field_label.into())
+        })
+        .collect();
+    let all_phantoms_init: Vec<_> = unused_params(params, struct_fields)
+        .map(|param| {
+            let name_tokens = quote_escaped_ident(make_ignored_ident(param.var()));
+            quote!( #name_tokens: ::std::marker::PhantomData )
+        })
+        .collect();
+    quote!(
+        impl #param_tokens #struct_name_tokens #param_tokens {
+            pub fn new( #method_arguments_tokens ) -> Self {
+                Self {
+                    #( #all_fields_init ,)*
+                    #( #all_phantoms_init ,)*
+                }
+            }
+        }
+    )
+}
+
+fn unused_params<'a>(
+    params: &'a [DamlTypeVarWithKind<'a>],
+    fields: &'a [&DamlField<'_>],
+) -> impl Iterator<Item = &'a DamlTypeVarWithKind<'a>> + 'a {
+    json_params(params).filter(move |param| !fields.iter().any(|&field| field.ty().contains_type_var(param.var())))
+}
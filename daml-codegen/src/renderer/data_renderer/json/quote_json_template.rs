@@ -0,0 +1,169 @@
+use crate::renderer::data_renderer::json::{
+    quote_json_method_arguments, quote_json_record_and_impl, quote_json_type, quote_json_type_or_value,
+};
+use crate::renderer::{quote_escaped_ident, to_module_path, IsRenderable, RenderContext};
+use daml_lf::element::{DamlChoice, DamlField, DamlTemplate};
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Generate the `Foo` struct and the typed `DamlJsonClient` wrappers for creating, fetching, querying and exercising
+/// choices on `Foo` contracts.
+pub fn quote_json_template(ctx: &RenderContext<'_>, daml_template: &DamlTemplate<'_>) -> TokenStream {
+    let struct_and_impl_tokens = quote_json_record_and_impl(ctx, daml_template.name(), daml_template.fields(), &[]);
+    let template_trait_impl_tokens = quote_template_trait_impl(daml_template);
+    let template_methods_tokens = quote_template_methods(ctx, daml_template);
+    let contract_id_struct_and_impl_tokens = quote_contract_id_struct_and_impl(ctx, daml_template);
+    quote!(
+        #struct_and_impl_tokens
+        #template_trait_impl_tokens
+        #template_methods_tokens
+        #contract_id_struct_and_impl_tokens
+    )
+}
+
+/// Generate the `DamlJsonTemplate for Foo` impl.
+fn quote_template_trait_impl(daml_template: &DamlTemplate<'_>) -> TokenStream {
+    let struct_name_tokens = quote_escaped_ident(daml_template.name());
+    let template_id = format!(
+        "{}:{}:{}",
+        daml_template.package_id(),
+        to_module_path(daml_template.module_path()),
+        daml_template.name()
+    );
+    quote!(
+        impl DamlJsonTemplate for #struct_name_tokens {
+            const TEMPLATE_ID: &'static str = #template_id;
+        }
+    )
+}
+
+/// Generate the `Foo::create(...)`, `Foo::fetch(...)` & `Foo::query(...)` methods and, if `Foo` has a contract key,
+/// the `Foo::fetch_by_key(...)` and `Foo::bar_by_key(...)` choice methods.
+fn quote_template_methods(ctx: &RenderContext<'_>, daml_template: &DamlTemplate<'_>) -> TokenStream {
+    let struct_name_tokens = quote_escaped_ident(daml_template.name());
+    let key_methods_tokens = daml_template.key().map_or_else(
+        || quote!(),
+        |key| {
+            let key_type_tokens = quote_json_type_or_value(ctx, key.ty());
+            let all_choice_methods: Vec<_> = daml_template
+                .choices()
+                .iter()
+                .map(|choice| quote_choice_by_key_method(ctx, &key_type_tokens, choice))
+                .collect();
+            quote!(
+                pub async fn fetch_by_key(client: &DamlJsonClient, key: &#key_type_tokens) -> DamlJsonResult<DamlJsonContract<Self>> {
+                    client.fetch_by_key_typed(key).await
+                }
+                #( #all_choice_methods )*
+            )
+        },
+    );
+    quote!(
+        impl #struct_name_tokens {
+            pub async fn create(&self, client: &DamlJsonClient) -> DamlJsonResult<DamlJsonContract<Self>> {
+                client.create_typed(self).await
+            }
+            pub async fn fetch(client: &DamlJsonClient, contract_id: &str) -> DamlJsonResult<DamlJsonContract<Self>> {
+                client.fetch_typed(contract_id).await
+            }
+            pub async fn query(client: &DamlJsonClient, query: DamlJsonValue) -> DamlJsonResult<Vec<DamlJsonContract<Self>>> {
+                client.query_typed(query).await
+            }
+            #key_methods_tokens
+        }
+    )
+}
+
+/// Generate the `FooContractId` struct and the `FooContractId::bar(...)` choice methods.
+fn quote_contract_id_struct_and_impl(ctx: &RenderContext<'_>, daml_template: &DamlTemplate<'_>) -> TokenStream {
+    let struct_name_tokens = quote_escaped_ident(daml_template.name());
+    let contract_id_struct_name_tokens = quote_escaped_ident(format!("{}ContractId", daml_template.name()));
+    let all_choice_methods: Vec<_> =
+        daml_template.choices().iter().map(|choice| quote_choice_method(ctx, daml_template.name(), choice)).collect();
+    quote!(
+        #[derive(Eq, PartialEq, Clone, Debug)]
+        pub struct #contract_id_struct_name_tokens {
+            contract_id: DamlJsonContractId,
+        }
+        impl #contract_id_struct_name_tokens {
+            pub fn new(contract_id: impl Into<DamlJsonContractId>) -> Self {
+                Self {
+                    contract_id: contract_id.into(),
+                }
+            }
+            pub fn contract_id(&self) -> &str {
+                &self.contract_id
+            }
+            #( #all_choice_methods )*
+        }
+        impl From<&DamlJsonContract<#struct_name_tokens>> for #contract_id_struct_name_tokens {
+            fn from(contract: &DamlJsonContract<#struct_name_tokens>) -> Self {
+                Self::new(contract.contract_id.as_str())
+            }
+        }
+    )
+}
+
+/// Generate the `pub async fn bar(&self, client, ...)` choice method.
+fn quote_choice_method(ctx: &RenderContext<'_>, struct_name: &str, choice: &DamlChoice<'_>) -> TokenStream {
+    let choice_name = choice.name();
+    let struct_name_tokens = quote_escaped_ident(struct_name);
+    let method_name_tokens = quote_escaped_ident(choice.name().to_snake_case());
+    let supported_fields: Vec<_> =
+        choice.fields().iter().filter(|&field| IsRenderable::new(ctx).check_type(field.ty())).collect();
+    let method_arguments_tokens = quote_json_method_arguments(&supported_fields);
+    let argument_tokens = quote_choice_argument(&supported_fields);
+    let return_type_tokens = quote_json_type_or_value(ctx, choice.return_type());
+    quote!(
+        pub async fn #method_name_tokens(&self, client: &DamlJsonClient, #method_arguments_tokens) -> DamlJsonResult<DamlJsonTypedExerciseResult<#return_type_tokens>> {
+            #argument_tokens
+            client.exercise_typed::<#struct_name_tokens, _, _>(&self.contract_id, #choice_name, &argument).await
+        }
+    )
+}
+
+/// Generate the `pub async fn bar_by_key(client, key, ...)` choice method.
+fn quote_choice_by_key_method(
+    ctx: &RenderContext<'_>,
+    key_type_tokens: &TokenStream,
+    choice: &DamlChoice<'_>,
+) -> TokenStream {
+    let choice_name = choice.name();
+    let method_name_tokens = quote_escaped_ident(format!("{}_by_key", choice.name().to_snake_case()));
+    let supported_fields: Vec<_> =
+        choice.fields().iter().filter(|&field| IsRenderable::new(ctx).check_type(field.ty())).collect();
+    let method_arguments_tokens = quote_json_method_arguments(&supported_fields);
+    let argument_tokens = quote_choice_argument(&supported_fields);
+    let return_type_tokens = quote_json_type_or_value(ctx, choice.return_type());
+    quote!(
+        pub async fn #method_name_tokens(client: &DamlJsonClient, key: &#key_type_tokens, #method_arguments_tokens) -> DamlJsonResult<DamlJsonTypedExerciseResult<#return_type_tokens>> {
+            #argument_tokens
+            client.exercise_by_key_typed::<Self, _, _, _>(key, #choice_name, &argument).await
+        }
+    )
+}
+
+/// Generate the JSON object containing all choice fields.
+fn quote_choice_argument(supported_fields: &[&DamlField<'_>]) -> TokenStream {
+    if supported_fields.is_empty() {
+        quote!(
+            let argument = DamlJsonUnit;
+        )
+    } else {
+        let all_fields: Vec<_> = supported_fields
+            .iter()
+            .map(|&field| {
+                let field_string = field.name();
+                let field_label = quote_escaped_ident(field.name());
+                let field_type_tokens = quote_json_type(field.ty());
+                quote!(
+                    (String::from(#field_string), to_json_value(&Into::<#field_type_tokens>::into(#field_label))?)
+                )
+            })
+            .collect();
+        quote!(
+            let argument = DamlJsonValue::Object(vec![ #( #all_fields ),* ].into_iter().collect());
+        )
+    }
+}
@@ -0,0 +1,138 @@
+use crate::renderer::type_renderer::quote_tycon_path;
+use crate::renderer::{normalize_generic_param, quote_ident, IsRenderable, RenderContext};
+use daml_lf::element::{DamlKind, DamlTyCon, DamlTyConName, DamlType, DamlTypeVarWithKind};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Quote a Daml type as a Rust type which serializes to the Daml-LF JSON encoding.
+///
+/// An `Optional` which is nested directly within another `Optional` is rendered as a `DamlJsonNestedOptional` such
+/// that it is encoded as an array as described in the
+/// [Daml LF JSON Encoding documentation](https://docs.daml.com/json-api/lf-value-specification.html).
+///
+/// Note that an `Optional` which only becomes nested once a type variable is substituted (i.e. `Optional a` where `a`
+/// is itself an `Optional`) cannot be detected here and is encoded as a top level `Optional`.
+pub fn quote_json_type(daml_type: &DamlType<'_>) -> TokenStream {
+    quote_json_type_nested(daml_type, true)
+}
+
+/// Quote `<A, B>` for all type parameters which are not of kind `Nat`.
+///
+/// Type parameters of kind `Nat` only determine the scale of a `Numeric` and are not required by the JSON encoding.
+pub fn quote_json_params(params: &[DamlTypeVarWithKind<'_>]) -> TokenStream {
+    let all_params_tokens: Vec<_> = json_params(params).map(|param| quote_json_var(param.var())).collect();
+    if all_params_tokens.is_empty() {
+        quote!()
+    } else {
+        quote!( < #( #all_params_tokens ),* > )
+    }
+}
+
+/// All type parameters which are not of kind `Nat`.
+pub fn json_params<'a>(
+    params: &'a [DamlTypeVarWithKind<'a>],
+) -> impl Iterator<Item = &'a DamlTypeVarWithKind<'a>> + 'a {
+    params.iter().filter(|param| !matches!(param.kind(), DamlKind::Nat))
+}
+
+/// Quote a type variable.
+pub fn quote_json_var(var: &str) -> TokenStream {
+    quote_ident(normalize_generic_param(var).to_uppercase())
+}
+
+/// Quote a Daml type as a typed JSON type if it is renderable and refers only to data types in the current module,
+/// otherwise quote it as an untyped `DamlJsonValue`.
+///
+/// This is used for choice return types and contract key types which, unlike fields, frequently refer to data types
+/// (such as tuples) defined in other packages which may not have been generated.
+pub fn quote_json_type_or_value(ctx: &RenderContext<'_>, daml_type: &DamlType<'_>) -> TokenStream {
+    if IsRenderable::new(ctx).check_type(daml_type) && is_local_type(daml_type) {
+        quote_json_type(daml_type)
+    } else {
+        quote!(DamlJsonValue)
+    }
+}
+
+fn quote_json_type_nested(daml_type: &DamlType<'_>, top_level: bool) -> TokenStream {
+    match daml_type {
+        DamlType::Int64 => quote!(DamlJsonInt64),
+        DamlType::Numeric(_) => quote!(DamlJsonNumeric),
+        DamlType::Text => quote!(DamlJsonText),
+        DamlType::Timestamp => quote!(DamlJsonTimestamp),
+        DamlType::Party => quote!(DamlJsonParty),
+        DamlType::Bool => quote!(DamlJsonBool),
+        DamlType::Unit => quote!(DamlJsonUnit),
+        DamlType::Date => quote!(DamlJsonDate),
+        DamlType::ContractId(_) => quote!(DamlJsonContractId),
+        DamlType::List(args) => {
+            let arg_tokens = quote_single_type_argument(daml_type, args, true);
+            quote!(DamlJsonList<#arg_tokens>)
+        },
+        DamlType::TextMap(args) => {
+            let arg_tokens = quote_single_type_argument(daml_type, args, true);
+            quote!(DamlJsonTextMap<#arg_tokens>)
+        },
+        DamlType::Optional(args) => {
+            let arg_tokens = quote_single_type_argument(daml_type, args, false);
+            if top_level {
+                quote!(DamlJsonOptional<#arg_tokens>)
+            } else {
+                quote!(DamlJsonNestedOptional<#arg_tokens>)
+            }
+        },
+        DamlType::GenMap(args) => match args.as_slice() {
+            [k, v] => {
+                let key_tokens = quote_json_type(k);
+                let value_tokens = quote_json_type(v);
+                quote!(DamlJsonGenMap<#key_tokens, #value_tokens>)
+            },
+            _ => panic!("expected exactly 2 type argument for {}, found {:?}", daml_type.name(), args),
+        },
+        DamlType::TyCon(tycon) => quote_json_tycon(tycon),
+        DamlType::BoxedTyCon(tycon) => {
+            let tycon_tokens = quote_json_tycon(tycon);
+            quote!(Box<#tycon_tokens>)
+        },
+        DamlType::Var(var) => quote_json_var(var.var()),
+        DamlType::Nat(_)
+        | DamlType::Update(_)
+        | DamlType::Scenario(_)
+        | DamlType::Arrow(_)
+        | DamlType::Any
+        | DamlType::TypeRep
+        | DamlType::Bignumeric
+        | DamlType::RoundingMode
+        | DamlType::AnyException
+        | DamlType::Forall(_)
+        | DamlType::Struct(_)
+        | DamlType::Syn(_) => panic!("cannot render unsupported type: {}", daml_type.name()),
+    }
+}
+
+fn quote_single_type_argument(daml_type: &DamlType<'_>, args: &[DamlType<'_>], top_level: bool) -> TokenStream {
+    match args {
+        [arg] => quote_json_type_nested(arg, top_level),
+        _ => panic!("expected exactly 1 type argument for {}, found {:?}", daml_type.name(), args),
+    }
+}
+
+fn quote_json_tycon(tycon: &DamlTyCon<'_>) -> TokenStream {
+    let target_type_tokens = quote_tycon_path(tycon);
+    let all_type_arguments: Vec<_> =
+        tycon.type_arguments().iter().filter(|&ty| !matches!(ty, DamlType::Nat(_))).map(quote_json_type).collect();
+    if all_type_arguments.is_empty() {
+        target_type_tokens
+    } else {
+        quote!( #target_type_tokens < #( #all_type_arguments ),* > )
+    }
+}
+
+fn is_local_type(daml_type: &DamlType<'_>) -> bool {
+    match daml_type {
+        DamlType::List(args) | DamlType::TextMap(args) | DamlType::GenMap(args) | DamlType::Optional(args) =>
+            args.iter().all(is_local_type),
+        DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) =>
+            matches!(tycon.tycon(), DamlTyConName::Local(_)) && tycon.type_arguments().iter().all(is_local_type),
+        _ => true,
+    }
+}
@@ -5,7 +5,7 @@ use quote::quote;
 
 use crate::generator::ModuleMatcher;
 use crate::generator::RenderMethod;
-use crate::renderer::{quote_all_data, quote_escaped_ident, to_module_path, RenderContext};
+use crate::renderer::{quote_all_data, quote_escaped_ident, quote_prelude, to_module_path, RenderContext};
 use daml_lf::element::DamlModule;
 
 pub fn quote_module_tree(
@@ -30,9 +30,10 @@ pub fn quote_module_tree(
             quote!()
         };
         let module_name_tokens = quote_escaped_ident(name.to_snake_case());
+        let prelude_tokens = quote_prelude(render_method);
         quote!(
             pub mod #module_name_tokens {
-                use ::daml::prelude::*;
+                #prelude_tokens
                 #module_tokens
                 #( #all_children )*
             }
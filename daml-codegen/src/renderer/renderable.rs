@@ -49,9 +49,9 @@ impl<'a> IsRenderable<'a> {
             DamlType::ContractId(tycon) => tycon.as_ref().map_or(true, |ty| self.check_type(ty)),
             DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) => self.check_tycon(tycon),
             DamlType::Var(var) => var.type_arguments().iter().all(|ty| self.check_type(ty)),
-            DamlType::Arrow
-            | DamlType::Update
-            | DamlType::Scenario
+            DamlType::Arrow(_)
+            | DamlType::Update(_)
+            | DamlType::Scenario(_)
             | DamlType::Any
             | DamlType::TypeRep
             | DamlType::Bignumeric
@@ -1,9 +1,22 @@
+use crate::generator::RenderMethod;
 use heck::ToSnakeCase;
 use itertools::Itertools;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use std::convert::AsRef;
 
+/// Quote the `use` of the Daml prelude required by the generated code for a given [`RenderMethod`].
+pub fn quote_prelude(render_method: &RenderMethod) -> TokenStream {
+    match render_method {
+        RenderMethod::Full | RenderMethod::Intermediate => quote!(
+            use ::daml::prelude::*;
+        ),
+        RenderMethod::Json => quote!(
+            use ::daml::json_prelude::*;
+        ),
+    }
+}
+
 /// Quote a string as an identifier.
 pub fn quote_ident(value: impl AsRef<str>) -> TokenStream {
     let ident = Ident::new(value.as_ref(), Span::call_site());
@@ -50,9 +50,9 @@ pub fn quote_type(daml_type: &DamlType<'_>) -> TokenStream {
         | DamlType::Bool
         | DamlType::Unit
         | DamlType::Date => quote_escaped_ident(daml_type.name()),
-        DamlType::Update
-        | DamlType::Scenario
-        | DamlType::Arrow
+        DamlType::Update(_)
+        | DamlType::Scenario(_)
+        | DamlType::Arrow(_)
         | DamlType::Any
         | DamlType::TypeRep
         | DamlType::Bignumeric
@@ -65,21 +65,24 @@ pub fn quote_type(daml_type: &DamlType<'_>) -> TokenStream {
 }
 
 pub fn quote_tycon(tycon: &DamlTyCon<'_>) -> TokenStream {
+    let target_type_tokens = quote_tycon_path(tycon);
     let type_arguments_tokens = quote_generic_type_arguments(tycon.type_arguments());
+    quote!(#target_type_tokens #type_arguments_tokens)
+}
+
+/// Quote the (possibly relative) path to the target type of a `DamlTyCon`, excluding any type arguments.
+pub fn quote_tycon_path(tycon: &DamlTyCon<'_>) -> TokenStream {
     match tycon.tycon() {
-        DamlTyConName::Local(local_tycon) => {
-            let target_type_tokens = quote_escaped_ident(local_tycon.data_name());
-            quote!(#target_type_tokens #type_arguments_tokens)
-        },
+        DamlTyConName::Local(local_tycon) => quote_escaped_ident(local_tycon.data_name()),
         DamlTyConName::NonLocal(non_local_tycon) => {
             let target_type_tokens = quote_escaped_ident(non_local_tycon.data_name());
             let target_path_tokens = quote_non_local_path(non_local_tycon);
-            quote!(#target_path_tokens #target_type_tokens #type_arguments_tokens)
+            quote!(#target_path_tokens #target_type_tokens)
         },
         DamlTyConName::Absolute(abs_tycon) => {
             let target_type_tokens = quote_escaped_ident(abs_tycon.data_name());
             let target_path_tokens = quote_absolute_tycon(abs_tycon);
-            quote!(#target_path_tokens #target_type_tokens #type_arguments_tokens)
+            quote!(#target_path_tokens #target_type_tokens)
         },
     }
 }
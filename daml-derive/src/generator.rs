@@ -24,7 +24,8 @@ pub fn generate_tokens(args: AttributeArgs) -> proc_macro::TokenStream {
     let render_method = match &params.mode {
         Some(name) if name.to_ascii_lowercase() == "intermediate" => RenderMethod::Intermediate,
         Some(name) if name.to_ascii_lowercase() == "full" => RenderMethod::Full,
-        Some(name) => panic!("unknown mode: {}, expected Intermediate or Full", name),
+        Some(name) if name.to_ascii_lowercase() == "json" => RenderMethod::Json,
+        Some(name) => panic!("unknown mode: {}, expected Intermediate, Full or Json", name),
         _ => RenderMethod::Full,
     };
     let applied =
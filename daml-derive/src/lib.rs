@@ -694,6 +694,15 @@ pub fn DamlEnum(_attr: proc_macro::TokenStream, input: proc_macro::TokenStream)
 /// daml_codegen!(dar_file = r"MyApp.dar", module_filter_regex = "Fuji.*", mode = "full");
 /// ```
 ///
+/// To generate [`RenderMethod::Json`](daml_codegen::generator::RenderMethod::Json) Rust types, which serialize to the
+/// Daml-LF JSON encoding and provide typed wrappers over the `DamlJsonClient`, for a `MyApp.dar`:
+///
+/// ```ignore
+/// daml_codegen!(dar_file = r"MyApp.dar", mode = "json");
+/// ```
+///
+/// Code generated in this mode requires the `json` feature of the `daml` crate.
+///
 /// ### Panics
 ///
 /// Panics (compile-time only) if errors are detected during code generation.
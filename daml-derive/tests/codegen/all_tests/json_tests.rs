@@ -0,0 +1,120 @@
+use crate::common::test_utils::TestResult;
+use daml::json_api::typed::serde::de::DeserializeOwned;
+use daml::json_api::typed::serde::Serialize;
+use daml::json_api::typed::{DamlJsonContract, DamlJsonInt64, DamlJsonNestedOptional, DamlJsonTemplate, DamlJsonUnit};
+use daml::json_api::value_decode::JsonValueDecoder;
+use daml::json_api::value_encode::TypedJsonValueEncoder;
+use daml::lf::element::{DamlArchive, DamlType};
+use daml::lf::DarFile;
+use daml_derive::daml_codegen;
+use serde_json::{json, Value};
+use std::fmt::Debug;
+
+daml_codegen!(
+    dar_file = r"resources/testing_types_sandbox/TestingTypes-latest.dar",
+    module_filter_regex = "Fuji.JsonTest",
+    module_filter_regex = "Fuji.PingPong",
+    mode = "json"
+);
+
+const DAR_PATH: &str = "../resources/testing_types_sandbox/TestingTypes-latest.dar";
+
+#[test]
+fn test_record() -> TestResult {
+    use testing_types::fuji::json_test::Person;
+    let person = Person::new("Alice", 42_i64);
+    assert_eq!(serde_json::to_value(&person)?, json!({"name": "Alice", "age": "42"}));
+    assert_roundtrip(&person, "Fuji.JsonTest", "Person")
+}
+
+#[test]
+fn test_optional() -> TestResult {
+    use testing_types::fuji::json_test::Depth1;
+    assert_eq!(serde_json::to_value(Depth1::new(None))?, json!({}));
+    assert_eq!(serde_json::to_value(Depth1::new(Some(DamlJsonInt64(1))))?, json!({"foo": "1"}));
+    assert_eq!(serde_json::from_value::<Depth1>(json!({"foo": null}))?, Depth1::new(None));
+    assert_roundtrip(&Depth1::new(Some(DamlJsonInt64(1))), "Fuji.JsonTest", "Depth1")
+}
+
+#[test]
+fn test_nested_optional() -> TestResult {
+    use testing_types::fuji::json_test::Depth2;
+    let some_none = Depth2::new(Some(DamlJsonNestedOptional(None)));
+    let some_some = Depth2::new(Some(DamlJsonNestedOptional(Some(DamlJsonInt64(1)))));
+    assert_eq!(serde_json::to_value(&some_none)?, json!({"foo": []}));
+    assert_eq!(serde_json::to_value(&some_some)?, json!({"foo": ["1"]}));
+    assert_roundtrip(&some_none, "Fuji.JsonTest", "Depth2")?;
+    assert_roundtrip(&some_some, "Fuji.JsonTest", "Depth2")
+}
+
+#[test]
+fn test_variant() -> TestResult {
+    use testing_types::fuji::json_test::Foo;
+    assert_eq!(serde_json::to_value(Foo::Bar(DamlJsonInt64(1)))?, json!({"tag": "Bar", "value": "1"}));
+    assert_eq!(serde_json::to_value(Foo::Baz(DamlJsonUnit))?, json!({"tag": "Baz", "value": {}}));
+    assert_eq!(serde_json::to_value(Foo::Quux(None))?, json!({"tag": "Quux", "value": null}));
+    assert_roundtrip(&Foo::Bar(DamlJsonInt64(1)), "Fuji.JsonTest", "Foo")?;
+    assert_roundtrip(&Foo::Baz(DamlJsonUnit), "Fuji.JsonTest", "Foo")?;
+    assert_roundtrip(&Foo::Quux(Some(DamlJsonInt64(2))), "Fuji.JsonTest", "Foo")
+}
+
+#[test]
+fn test_enum() -> TestResult {
+    use testing_types::fuji::json_test::Status;
+    assert_eq!(serde_json::to_value(Status::Disabled)?, json!("Disabled"));
+    assert_roundtrip(&Status::Enabled, "Fuji.JsonTest", "Status")
+}
+
+#[test]
+fn test_gen_map() -> TestResult {
+    use testing_types::fuji::json_test::{Person, PersonMap};
+    let person_map = PersonMap::new(vec![(Person::new("Alice", 1_i64), String::from("a"))]);
+    assert_eq!(serde_json::to_value(&person_map)?, json!({"m": [[{"name": "Alice", "age": "1"}, "a"]]}));
+    assert_roundtrip(&person_map, "Fuji.JsonTest", "PersonMap")
+}
+
+#[test]
+fn test_generic_and_recursive() -> TestResult {
+    use testing_types::fuji::json_test::{Bottom, Middle, Top};
+    let top = Top::new(Middle::new(Bottom::new(DamlJsonInt64(3))));
+    assert_eq!(serde_json::to_value(&top)?, json!({"top": {"middle": {"bottom": "3"}}}));
+    assert_roundtrip(&top, "Fuji.JsonTest", "Top")
+}
+
+#[test]
+fn test_template() -> TestResult {
+    use testing_types::fuji::ping_pong::{Ping, PingContractId};
+    let ping = Ping::new("Alice", "Bob", 0_i64);
+    assert!(Ping::TEMPLATE_ID.ends_with(":Fuji.PingPong:Ping"));
+    let contract = DamlJsonContract {
+        contract_id: String::from("#0:0"),
+        template_id: Ping::TEMPLATE_ID.to_owned(),
+        payload: ping.clone(),
+        signatories: vec![String::from("Alice")],
+        observers: vec![String::from("Bob")],
+        agreement_text: String::new(),
+    };
+    assert_eq!(PingContractId::from(&contract).contract_id(), "#0:0");
+    assert_roundtrip(&ping, "Fuji.PingPong", "Ping")
+}
+
+/// Check the typed JSON encoding of a value matches the encoding produced by the `TypedJsonValueEncoder`.
+fn assert_roundtrip<T: Serialize + DeserializeOwned + PartialEq + Debug>(
+    value: &T,
+    module: &str,
+    entity: &str,
+) -> TestResult {
+    let typed_json = serde_json::to_value(value)?;
+    let encoded_json =
+        DarFile::from_file(DAR_PATH)?.apply(|archive| encode_json(archive, &typed_json, module, entity))??;
+    assert_eq!(typed_json, encoded_json);
+    assert_eq!(&serde_json::from_value::<T>(encoded_json)?, value);
+    Ok(())
+}
+
+fn encode_json(archive: &DamlArchive<'_>, json: &Value, module: &str, entity: &str) -> anyhow::Result<Value> {
+    let module_path: Vec<_> = module.split('.').collect();
+    let ty = DamlType::make_tycon(archive.main_package_id(), &module_path, entity);
+    let value = JsonValueDecoder::new(archive).decode(json, &ty)?;
+    Ok(TypedJsonValueEncoder::new(archive).encode(&value, &ty)?)
+}
@@ -41,6 +41,7 @@ mod codegen {
     mod all_tests {
         pub mod generic_tests;
         pub mod higher_kinded_tests;
+        pub mod json_tests;
         pub mod nested_datatypes_tests;
         pub mod nested_module_tests;
         pub mod ping_pong_tests;
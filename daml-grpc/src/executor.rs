@@ -196,6 +196,19 @@ impl<'a> DamlSimpleExecutor<'a> {
         Ok(self.client().submit_and_wait_for_transaction_tree(commands).await?.0)
     }
 
+    /// Submit several commands atomically, as a single transaction, and wait for the resulting transaction tree.
+    ///
+    /// The root events of the returned [`DamlTransactionTree`] correspond, in order, to the submitted `commands`.  A
+    /// new command id is generated if none is provided.
+    pub async fn execute_all_for_transaction_tree(
+        &self,
+        commands: Vec<DamlCommand>,
+        command_id: Option<&str>,
+    ) -> DamlResult<DamlTransactionTree> {
+        let commands = self.command_factory.make_commands(commands, command_id);
+        Ok(self.client().submit_and_wait_for_transaction_tree(commands).await?.0)
+    }
+
     fn client(&self) -> DamlCommandService<'_> {
         match self.auth_token {
             Some(token) => self.ledger_client.command_service().with_token(token),
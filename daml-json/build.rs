@@ -0,0 +1,9 @@
+use std::error;
+
+const LF_VALUE_PROTO_PATH: &str = "resources/protobuf/com/daml/lf/value.proto";
+const PROTO_ROOT_PATH: &str = "resources/protobuf";
+
+fn main() -> Result<(), Box<dyn error::Error>> {
+    prost_build::compile_protos(&[LF_VALUE_PROTO_PATH], &[PROTO_ROOT_PATH])?;
+    Ok(())
+}
@@ -1,10 +1,18 @@
-use itertools::{chain, zip};
+use std::iter::zip;
+
+use itertools::{chain, Itertools};
 use serde_json::Value;
 
-use crate::common::DataId;
-use crate::format::{format_daml_template, format_oas_template, format_path_slice_regex_safe};
+use crate::request_converter::DamlJsonTemplateId;
 
-/// Make Daml JSON API schema items.
+/// Make JSON schema items for the Daml JSON API requests and responses.
+///
+/// Template specific request and response schemas reference the schema of the template payload by `$ref` from the
+/// `reference_prefix`, such as `#/components/schemas/`, where it is assumed the schema for the template (i.e. as
+/// produced by [`JsonSchemaEncoder::encode_template`]) will be made available under the name `Module.Path.Entity`.
+///
+/// [`JsonSchemaEncoder::encode_template`]: crate::schema_encoder::JsonSchemaEncoder::encode_template
+#[derive(Debug)]
 pub struct DamlJsonApiSchema {
     reference_prefix: String,
     emit_package_id: bool,
@@ -66,27 +74,39 @@ impl DamlJsonApiSchema {
     }
 
     /// Make a JSON schema value which represents a Daml JSON API `create` request for a given `TemplateId`.
-    pub fn make_create_request(&self, template_id: &DataId) -> Value {
+    pub fn make_create_request(&self, template_id: &DamlJsonTemplateId) -> Value {
         let payload = serde_json::json!({"$ref": self.make_schema_ref_url(&format_oas_template(template_id))});
         let template_id = self.make_template_pattern_schema(template_id);
         Self::make_create_request_schema(&template_id, &payload)
     }
 
     /// Make a JSON schema value which represents a Daml JSON API `create` response for a given `TemplateId`.
-    pub fn make_create_response(&self, template_id: &DataId) -> Value {
+    pub fn make_create_response(&self, template_id: &DamlJsonTemplateId) -> Value {
         Self::make_success_response_schema(&self.make_create_event(template_id))
     }
 
-    /// DOCME
-    pub fn make_create_and_exercise_request(&self, template_id: &DataId, choice_id: &str, args: &Value) -> Value {
+    /// Make a JSON schema value which represents a Daml JSON API `create_and_exercise` request for a given
+    /// `TemplateId` and choice.
+    pub fn make_create_and_exercise_request(
+        &self,
+        template_id: &DamlJsonTemplateId,
+        choice_id: &str,
+        args: &Value,
+    ) -> Value {
         let payload = serde_json::json!({"$ref": self.make_schema_ref_url(&format_oas_template(template_id))});
         let template_id = self.make_template_pattern_schema(template_id);
         let choice = Self::make_choice_schema(choice_id);
         Self::make_create_and_exercise_request_schema(&template_id, &payload, &choice, args)
     }
 
-    /// DOCME
-    pub fn make_exercise_by_id_request(&self, template_id: &DataId, choice_id: &str, args: &Value) -> Value {
+    /// Make a JSON schema value which represents a Daml JSON API `exercise` (by contract id) request for a given
+    /// `TemplateId` and choice.
+    pub fn make_exercise_by_id_request(
+        &self,
+        template_id: &DamlJsonTemplateId,
+        choice_id: &str,
+        args: &Value,
+    ) -> Value {
         serde_json::json!(
             {
               "type": "object",
@@ -110,10 +130,11 @@ impl DamlJsonApiSchema {
         )
     }
 
-    /// DOCME
+    /// Make a JSON schema value which represents a Daml JSON API `exercise` (by contract key) request for a given
+    /// `TemplateId` and choice.
     pub fn make_exercise_by_key_request(
         &self,
-        template_id: &DataId,
+        template_id: &DamlJsonTemplateId,
         choice_id: &str,
         args: &Value,
         key: &Value,
@@ -138,8 +159,14 @@ impl DamlJsonApiSchema {
         )
     }
 
-    /// DOCME
-    pub fn make_exercise_response(&self, return_type: &Value, created: &[DataId], archived: &[DataId]) -> Value {
+    /// Make a JSON schema value which represents a Daml JSON API `exercise` response with the given choice return
+    /// type and the templates of the contracts which may be created and archived by the choice.
+    pub fn make_exercise_response(
+        &self,
+        return_type: &Value,
+        created: &[DamlJsonTemplateId],
+        archived: &[DamlJsonTemplateId],
+    ) -> Value {
         let event_items = serde_json::json!(
             {
                 "oneOf": self.make_create_and_archive_events(created, archived)
@@ -148,7 +175,7 @@ impl DamlJsonApiSchema {
         Self::make_exercise_response_schema(return_type, &event_items)
     }
 
-    /// DOCME
+    /// Make a JSON schema value which represents a Daml JSON API `fetch` (by contract id) request.
     pub fn make_fetch_by_id_request() -> Value {
         serde_json::json!(
             {
@@ -167,8 +194,9 @@ impl DamlJsonApiSchema {
         )
     }
 
-    /// DOCME
-    pub fn make_fetch_by_key_request(&self, template_id: &DataId, key: &Value) -> Value {
+    /// Make a JSON schema value which represents a Daml JSON API `fetch` (by contract key) request for a given
+    /// `TemplateId`.
+    pub fn make_fetch_by_key_request(&self, template_id: &DamlJsonTemplateId, key: &Value) -> Value {
         serde_json::json!(
             {
               "type": "object",
@@ -184,8 +212,8 @@ impl DamlJsonApiSchema {
         )
     }
 
-    /// DOCME
-    pub fn make_fetch_response(&self, template_id: &DataId) -> Value {
+    /// Make a JSON schema value which represents a Daml JSON API `fetch` response for a given `TemplateId`.
+    pub fn make_fetch_response(&self, template_id: &DamlJsonTemplateId) -> Value {
         Self::make_success_response_schema(&Self::make_optional_result_schema(&self.make_create_event(template_id)))
     }
 
@@ -204,7 +232,7 @@ impl DamlJsonApiSchema {
         Self::make_success_response_schema(&Self::make_general_create_event())
     }
 
-    /// DOCME
+    /// Make a JSON schema value which represents a Daml JSON API `create_and_exercise` request for any template.
     pub fn make_general_create_and_exercise_request() -> Value {
         let payload = Self::make_general_payload_schema();
         let template_id = Self::make_general_template_id_schema();
@@ -213,28 +241,28 @@ impl DamlJsonApiSchema {
         Self::make_create_and_exercise_request_schema(&template_id, &payload, &choice, &args)
     }
 
-    /// DOCME
+    /// Make a JSON schema value which represents a Daml JSON API `exercise` request for any template.
     pub fn make_general_exercise_request() -> Value {
         serde_json::json!({
             "oneOf": [Self::make_general_exercise_by_id_request_schema(), Self::make_general_exercise_by_key_request_schema()]
         })
     }
 
-    /// DOCME
+    /// Make a JSON schema value which represents a Daml JSON API `exercise` response for any template.
     pub fn make_general_exercise_response() -> Value {
         let return_type = Self::make_general_return_type_schema();
         let event_items = serde_json::json!({ "oneOf": Self::make_general_create_and_archive_events() });
         Self::make_exercise_response_schema(&return_type, &event_items)
     }
 
-    /// DOCME
+    /// Make a JSON schema value which represents a Daml JSON API `fetch` request for any template.
     pub fn make_general_fetch_request() -> Value {
         serde_json::json!({
             "oneOf": [Self::make_general_fetch_by_id_request_schema(), Self::make_general_fetch_by_key_request_schema()]
         })
     }
 
-    /// DOCME
+    /// Make a JSON schema value which represents a Daml JSON API `fetch` response for any template.
     pub fn make_general_fetch_response() -> Value {
         Self::make_success_response_schema(&Self::make_optional_result_schema(&Self::make_general_create_event()))
     }
@@ -245,14 +273,14 @@ impl DamlJsonApiSchema {
     /// Make a Contracts Query Stream request (single).
     ///
     /// See [Contracts Query Stream](https://docs.daml.com/json-api/index.html#contracts-query-stream)
-    pub fn make_stream_query_single_request(&self, templates: &[DataId]) -> Value {
+    pub fn make_stream_query_single_request(&self, templates: &[DamlJsonTemplateId]) -> Value {
         self.make_query_request_schema(templates)
     }
 
     /// Make a Contracts Query Stream request (multi).
     ///
     /// See [Contracts Query Stream](https://docs.daml.com/json-api/index.html#contracts-query-stream)
-    pub fn make_stream_query_multi_request(&self, templates: &[DataId]) -> Value {
+    pub fn make_stream_query_multi_request(&self, templates: &[DamlJsonTemplateId]) -> Value {
         serde_json::json!(
             {
                 "type": "array",
@@ -282,9 +310,9 @@ impl DamlJsonApiSchema {
     /// Make Fetch by Key Contracts Stream request.
     ///
     /// See [Fetch by Key Contracts Stream](https://docs.daml.com/json-api/index.html#fetch-by-key-contracts-stream)
-    pub fn make_stream_fetch_request(&self, templates: &[DataId], keys: &[Value]) -> Value {
+    pub fn make_stream_fetch_request(&self, templates: &[DamlJsonTemplateId], keys: &[Value]) -> Value {
         let template_fetch_items =
-            zip(templates, keys).into_iter().map(|(t, k)| self.make_fetch_item_schema(t, k)).collect::<Vec<_>>();
+            zip(templates, keys).map(|(t, k)| self.make_fetch_item_schema(t, k)).collect::<Vec<_>>();
         serde_json::json!(
             {
                 "type": "array",
@@ -298,7 +326,11 @@ impl DamlJsonApiSchema {
     }
 
     /// Make Fetch & Query Stream events.
-    pub fn make_stream_events_response(&self, created: &[DataId], archived: &[DataId]) -> Value {
+    pub fn make_stream_events_response(
+        &self,
+        created: &[DamlJsonTemplateId],
+        archived: &[DamlJsonTemplateId],
+    ) -> Value {
         // TODO this is technically wrong as stream events don't contain warnings
         // TODO is offset mandatory?
         Self::make_success_response_schema(&serde_json::json!(
@@ -441,15 +473,15 @@ impl DamlJsonApiSchema {
 
     fn make_create_and_archive_stream_events(
         &self,
-        create_template_ids: &[DataId],
-        archived_template_ids: &[DataId],
+        create_template_ids: &[DamlJsonTemplateId],
+        archived_template_ids: &[DamlJsonTemplateId],
     ) -> Value {
         let active = create_template_ids.iter().map(|create| self.make_stream_created_schema(create));
         let archived = archived_template_ids.iter().map(|archive| self.make_stream_archived_schema(archive));
         chain(active, archived).collect()
     }
 
-    fn make_stream_archived_schema(&self, template_id: &DataId) -> Value {
+    fn make_stream_archived_schema(&self, template_id: &DamlJsonTemplateId) -> Value {
         serde_json::json!(
             {
                 "type": "object",
@@ -463,7 +495,7 @@ impl DamlJsonApiSchema {
         )
     }
 
-    fn make_stream_created_schema(&self, template_id: &DataId) -> Value {
+    fn make_stream_created_schema(&self, template_id: &DamlJsonTemplateId) -> Value {
         serde_json::json!(
             {
                 "type": "object",
@@ -483,7 +515,7 @@ impl DamlJsonApiSchema {
         )
     }
 
-    fn make_fetch_item_schema(&self, template_id: &DataId, key: &Value) -> Value {
+    fn make_fetch_item_schema(&self, template_id: &DamlJsonTemplateId, key: &Value) -> Value {
         serde_json::json!(
             {
                 "type": "object",
@@ -498,7 +530,7 @@ impl DamlJsonApiSchema {
         )
     }
 
-    fn make_query_request_schema(&self, templates: &[DataId]) -> Value {
+    fn make_query_request_schema(&self, templates: &[DamlJsonTemplateId]) -> Value {
         let template_patterns = templates.iter().map(|t| self.make_template_pattern_schema(t)).collect::<Vec<_>>();
         serde_json::json!(
             {
@@ -557,15 +589,15 @@ impl DamlJsonApiSchema {
 
     fn make_create_and_archive_events(
         &self,
-        create_template_ids: &[DataId],
-        archived_template_ids: &[DataId],
+        create_template_ids: &[DamlJsonTemplateId],
+        archived_template_ids: &[DamlJsonTemplateId],
     ) -> Value {
         let created = create_template_ids.iter().map(|create| self.make_created_schema(create));
         let archived = archived_template_ids.iter().map(|archive| self.make_archived_schema(archive));
         chain(created, archived).collect()
     }
 
-    fn make_created_schema(&self, template_id: &DataId) -> Value {
+    fn make_created_schema(&self, template_id: &DamlJsonTemplateId) -> Value {
         serde_json::json!(
             {
                 "type": "object",
@@ -579,7 +611,7 @@ impl DamlJsonApiSchema {
         )
     }
 
-    fn make_archived_schema(&self, template_id: &DataId) -> Value {
+    fn make_archived_schema(&self, template_id: &DamlJsonTemplateId) -> Value {
         serde_json::json!(
             {
                 "type": "object",
@@ -593,12 +625,12 @@ impl DamlJsonApiSchema {
         )
     }
 
-    fn make_archive_event(&self, template_id: &DataId) -> Value {
+    fn make_archive_event(&self, template_id: &DamlJsonTemplateId) -> Value {
         let template_id = self.make_template_pattern_schema(template_id);
         Self::make_archive_event_schema(&template_id)
     }
 
-    fn make_create_event(&self, template_id: &DataId) -> Value {
+    fn make_create_event(&self, template_id: &DamlJsonTemplateId) -> Value {
         let payload = serde_json::json!({"$ref": self.make_schema_ref_url(&format_oas_template(template_id))});
         let template_id = self.make_template_pattern_schema(template_id);
         Self::make_create_event_schema(&template_id, &payload)
@@ -836,7 +868,7 @@ impl DamlJsonApiSchema {
         )
     }
 
-    fn make_template_pattern_schema(&self, template_id: &DataId) -> Value {
+    fn make_template_pattern_schema(&self, template_id: &DamlJsonTemplateId) -> Value {
         let path = format_path_slice_regex_safe(&template_id.module);
         let pattern = if self.emit_package_id {
             format!("^({}:)?{}:{}$", template_id.package_id.as_deref().unwrap_or_default(), path, &template_id.entity)
@@ -868,3 +900,20 @@ impl DamlJsonApiSchema {
         format!("{}{}", self.reference_prefix, schema_ref)
     }
 }
+
+/// Format a `TemplateId` that is suitable for use in Daml JSON API payloads.
+fn format_daml_template(template_id: &DamlJsonTemplateId) -> String {
+    format!("{}:{}", template_id.module.join("."), template_id.entity)
+}
+
+/// Format a `TemplateId` that is suitable for JSON schema references.
+///
+/// The Daml JSON separator character ':' is not legal in URLs and so we use `.` instead.
+fn format_oas_template(template_id: &DamlJsonTemplateId) -> String {
+    format!("{}.{}", template_id.module.join("."), template_id.entity)
+}
+
+/// Format a regex safe module path.
+fn format_path_slice_regex_safe(path: &[String]) -> String {
+    path.iter().join("\\.")
+}
@@ -1,5 +1,7 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 
 /// Daml JSON API representation of a ledger event.
 #[derive(Debug, Serialize, Deserialize)]
@@ -63,11 +65,60 @@ impl DamlJsonArchivedEvent {
 }
 
 /// Daml JSON API representation of a ledger exercise result.
+///
+/// The full `transaction_tree` is only provided if it was explicitly requested, see
+/// [`GrpcToJsonResponseConverter::with_verbose`].
+///
+/// [`GrpcToJsonResponseConverter::with_verbose`]: crate::response_converter::GrpcToJsonResponseConverter::with_verbose
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DamlJsonExerciseResult {
     #[serde(rename = "exerciseResult")]
     pub exercise_result: Value,
     pub events: Vec<DamlJsonEvent>,
+    #[serde(rename = "transactionTree", skip_serializing_if = "Option::is_none", default)]
+    pub transaction_tree: Option<DamlJsonTransactionTree>,
+}
+
+/// Daml JSON representation of a ledger transaction tree.
+///
+/// The `events_by_id` contains every event of the transaction, the `root_event_ids` are the ids of the events which
+/// are not the child of any other event and each exercised event lists the ids of its own children.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DamlJsonTransactionTree {
+    pub transaction_id: String,
+    pub command_id: String,
+    pub workflow_id: String,
+    pub effective_at: DateTime<Utc>,
+    pub offset: String,
+    pub events_by_id: BTreeMap<String, DamlJsonTreeEvent>,
+    pub root_event_ids: Vec<String>,
+}
+
+/// Daml JSON representation of a ledger transaction tree event.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DamlJsonTreeEvent {
+    Created(DamlJsonCreatedEvent),
+    Exercised(DamlJsonExercisedEvent),
+}
+
+/// Daml JSON representation of a ledger choice exercised event.
+///
+/// The `choice_argument` and `exercise_result` are encoded using the Daml-LF JSON encoding.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DamlJsonExercisedEvent {
+    pub event_id: String,
+    pub contract_id: String,
+    pub template_id: String,
+    pub choice: String,
+    pub choice_argument: Value,
+    pub acting_parties: Vec<String>,
+    pub consuming: bool,
+    pub witness_parties: Vec<String>,
+    pub child_event_ids: Vec<String>,
+    pub exercise_result: Value,
 }
 
 /// Daml JSON API representation of a ledger Party.
@@ -90,6 +141,83 @@ impl DamlJsonParty {
     }
 }
 
+/// Daml JSON API representation of a ledger User.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct DamlJsonUser {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+    #[serde(rename = "primaryParty", skip_serializing_if = "Option::is_none")]
+    pub primary_party: Option<String>,
+}
+
+impl DamlJsonUser {
+    pub fn new<S: Into<String>>(user_id: impl Into<String>, primary_party: Option<S>) -> Self {
+        Self {
+            user_id: user_id.into(),
+            primary_party: primary_party.map(Into::into),
+        }
+    }
+}
+
+/// Daml JSON API representation of a ledger User right.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+#[serde(tag = "type")]
+pub enum DamlJsonUserRight {
+    /// The right to administer the participant node.
+    ParticipantAdmin,
+    /// The right to act as the given party.
+    CanActAs {
+        party: String,
+    },
+    /// The right to read as the given party.
+    CanReadAs {
+        party: String,
+    },
+}
+
+impl DamlJsonUserRight {
+    /// Create a [`DamlJsonUserRight::CanActAs`] right for the given `party`.
+    pub fn can_act_as(party: impl Into<String>) -> Self {
+        Self::CanActAs {
+            party: party.into(),
+        }
+    }
+
+    /// Create a [`DamlJsonUserRight::CanReadAs`] right for the given `party`.
+    pub fn can_read_as(party: impl Into<String>) -> Self {
+        Self::CanReadAs {
+            party: party.into(),
+        }
+    }
+}
+
+/// Daml JSON API representation of a participant metering report.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct DamlJsonMeteringReport {
+    pub participant: String,
+    pub request: DamlJsonMeteringReportPeriod,
+    #[serde(rename = "final")]
+    pub is_final: bool,
+    pub applications: Vec<DamlJsonApplicationMeteringReport>,
+}
+
+/// Daml JSON API representation of the period covered by a metering report.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct DamlJsonMeteringReportPeriod {
+    pub from: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application: Option<String>,
+}
+
+/// Daml JSON API representation of the metering report for a single application.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct DamlJsonApplicationMeteringReport {
+    pub application: String,
+    pub events: i64,
+}
+
 /// Daml JSON API representation of a query.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DamlJsonQuery {
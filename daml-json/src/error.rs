@@ -21,6 +21,10 @@ pub enum DamlJsonError {
     UnhandledHttpResponse(String),
     #[error("DamlJsonError: unexpected GRPC event")]
     UnexpectedGrpcEvent,
+    #[error("DamlJsonError: typed payload error: {0}")]
+    TypedPayloadError(#[from] serde_json::Error),
+    #[error("DamlJsonError: query error: {0}")]
+    QueryError(#[from] DamlJsonQueryError),
 
     // TODO move to bridge crate
     #[error("DamlJsonError: Transaction tree did not contain an exercised event")]
@@ -51,6 +55,10 @@ pub enum DamlJsonReqConError {
     UnexpectedGrpcEvent,
     #[error("DamlJsonError: Transaction tree did not contain an exercised event")]
     MissingExercisedEvent,
+    #[error("DamlJsonError: batch request must contain at least one command")]
+    EmptyBatchRequest,
+    #[error("DamlJsonError: expected {0} root events in transaction tree but found {1}")]
+    UnexpectedRootEventCount(usize, usize),
 }
 
 /// Daml JSON Codec Result.
@@ -93,6 +101,53 @@ pub enum DamlJsonCodecError {
     UnsupportedDamlType(String),
     #[error("Data item {0} not found in archive")]
     DataNotFound(String),
+    #[error("Daml type variable '{0}' not found in type parameters")]
+    TypeVarNotFound(String),
+    #[error("expected Daml value of type {0} but found {1}")]
+    UnexpectedDamlValue(String, String),
+    #[error("record did not contain expected field {0}")]
+    MissingDamlRecordField(String),
+    #[error("numeric {0} cannot be represented as a Numeric with scale {1}")]
+    NumericOutOfRange(String, u8),
+    #[error("failed to decode protobuf value: {0}")]
+    ProtobufDecodeError(#[from] prost::DecodeError),
+    #[error("failed to encode or decode CBOR value: {0}")]
+    CborError(String),
+    #[error("unexpected encoded value of kind {0}")]
+    UnexpectedEncodedValue(String),
+    #[error("record did not contain expected field {1} at position {0}")]
+    MissingRecordField(usize, String),
+    #[error("expected a record with at most {0} fields but found {1}")]
+    UnexpectedRecordFieldCount(usize, usize),
+    #[error("date or timestamp out of range: {0}")]
+    DateTimeOutOfRange(String),
+    #[error("{}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    DecodeErrors(Vec<DamlJsonDecodeError>),
+}
+
+/// An error decoding a value at a given location.
+///
+/// The `path` is a [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) to the value which could not be
+/// decoded, i.e. `/payload/items/3/price`, and `expected` is the Daml type the value was expected to have.
+///
+/// This is shared by the JSON, protobuf and CBOR decoders, for encodings where record fields are positional the path
+/// refers to the record field by name.
+#[derive(Error, Debug)]
+#[error("{error} at '{path}' (expected {expected})")]
+pub struct DamlJsonDecodeError {
+    pub path: String,
+    pub expected: String,
+    pub error: Box<DamlJsonCodecError>,
+}
+
+impl DamlJsonDecodeError {
+    pub fn new(path: impl Into<String>, expected: impl Into<String>, error: DamlJsonCodecError) -> Self {
+        Self {
+            path: path.into(),
+            expected: expected.into(),
+            error: Box::new(error),
+        }
+    }
 }
 
 /// Daml JSON Schema Codec Result.
@@ -120,3 +175,33 @@ pub enum DamlJsonSchemaCodecError {
     #[error("Daml type variable '{0}' not found in type parameters")]
     TypeVarNotFoundInParams(String),
 }
+
+/// Daml JSON Query Result.
+pub type DamlJsonQueryResult<T> = Result<T, DamlJsonQueryError>;
+
+/// Daml JSON Query Error.
+#[derive(Error, Debug)]
+pub enum DamlJsonQueryError {
+    #[error("query template error: {0}")]
+    TemplateError(Box<DamlJsonReqConError>),
+    #[error("query must have at least one template id")]
+    NoTemplateIds,
+    #[error("query field path must not be empty")]
+    EmptyFieldPath,
+    #[error("conflicting query predicates for field {0}")]
+    ConflictingPredicates(String),
+    #[error("unknown field {0} for template {1}")]
+    UnknownField(String, String),
+    #[error("field {0} of type {1} is not a record")]
+    NotARecord(String, String),
+    #[error("operator {1} cannot be applied to field {0} of type {2}")]
+    UnsupportedComparison(String, String, String),
+    #[error("invalid value for field {0}: {1}")]
+    InvalidValue(String, Box<DamlJsonCodecError>),
+}
+
+impl From<DamlJsonReqConError> for DamlJsonQueryError {
+    fn from(err: DamlJsonReqConError) -> Self {
+        Self::TemplateError(Box::new(err))
+    }
+}
@@ -0,0 +1,10 @@
+#![allow(clippy::all, clippy::pedantic, clippy::nursery, dead_code)]
+pub mod com {
+    pub mod daml {
+        pub mod lf {
+            pub mod value {
+                include!(concat!(env!("OUT_DIR"), "/com.daml.lf.value.rs"));
+            }
+        }
+    }
+}
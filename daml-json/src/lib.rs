@@ -6,11 +6,19 @@
 //! - A Daml JSON API [client](service::DamlJsonClient) and [builder](service::DamlJsonClientBuilder)
 //! - A [`DamlValue`](`daml_grpc::data::value::DamlValue`) <> JSON [`Value`](`serde_json::Value`)
 //!   [encoder](value_encode::JsonValueEncoder) and [decoder](value_decode::JsonValueDecoder)
+//! - A type directed `DamlValue` to JSON [encoder](value_encode::TypedJsonValueEncoder) which is the inverse of the
+//!   decoder
+//! - A type directed `DamlValue` <> Daml-LF value protobuf [encoder](value_proto::ProtoValueEncoder) and
+//!   [decoder](value_proto::ProtoValueDecoder)
+//! - A type directed `DamlValue` <> CBOR [encoder](value_cbor::CborValueEncoder) and
+//!   [decoder](value_cbor::CborValueDecoder)
 //! - A Daml JSON API [`request`](request) to GRPC API [`command`](daml_grpc::data::command)
 //!   [converter](request_converter::JsonToGrpcRequestConverter)
 //! - A Daml GRPC API [`event`](daml_grpc::data::event) to JSON API [`response`](request)
 //!   [converter](response_converter::GrpcToJsonResponseConverter)
-//! - A JSON Schema [encoder](schema_encoder::JsonSchemaEncoder)
+//! - A Daml JSON API [query](query::Query) builder which may be checked against a Daml archive
+//! - A JSON Schema [encoder](schema_encoder::JsonSchemaEncoder) for Daml types, data types and templates
+//! - JSON Schema for the Daml JSON API [requests and responses](api_schema::DamlJsonApiSchema)
 
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, rust_2018_idioms)]
 #![allow(
@@ -47,6 +55,15 @@ pub mod value_decode;
 /// Daml JSON value encoder.
 pub mod value_encode;
 
+/// Daml-LF protobuf value encoder and decoder.
+pub mod value_proto;
+
+/// Daml CBOR value encoder and decoder.
+pub mod value_cbor;
+
+/// Daml JSON API query builder.
+pub mod query;
+
 /// Daml JSON request converter.
 pub mod request_converter;
 
@@ -59,7 +76,15 @@ mod schema_data;
 /// Daml JSON schema encoder.
 pub mod schema_encoder;
 
+/// Daml JSON API request & response schema.
+pub mod api_schema;
+
+/// Typed Daml JSON API support for generated code.
+pub mod typed;
+
 mod util;
 
+mod lf_value;
+
 #[cfg(test)]
 mod test_util;
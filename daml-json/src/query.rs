@@ -0,0 +1,504 @@
+use crate::data::DamlJsonQuery;
+use crate::error::{DamlJsonQueryError, DamlJsonQueryResult};
+use crate::request_converter::{DamlJsonTemplateId, JsonToGrpcRequestConverter};
+use crate::util::{describe_type, TypeArgs};
+use crate::value_decode::JsonValueDecoder;
+use daml_lf::element::{DamlArchive, DamlData, DamlField, DamlType};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Build a Daml JSON API [`DamlJsonQuery`].
+///
+/// A `Query` is built from one or more template ids and a sequence of predicates, each of which constrains a single,
+/// possibly nested, field of the contract payload.  All predicates must hold for a contract to match.
+///
+/// Nested fields are specified using a `.` separated path, such as `data.name`, and several comparison predicates may
+/// be combined for the same field to match a range of values.
+///
+/// # Examples
+///
+/// ```
+/// # use daml_json::query::Query;
+/// # use serde_json::json;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let query = Query::template("Fuji.PingPong:Ping")
+///     .field("count")
+///     .gte(5)
+///     .and("count")
+///     .lt(10)
+///     .and("sender")
+///     .eq("Alice")
+///     .build()?;
+/// assert_eq!(query.template_ids, vec!["Fuji.PingPong:Ping"]);
+/// assert_eq!(query.query, json!({ "count": { "%gte": 5, "%lt": 10 }, "sender": "Alice" }));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// A query may be checked against a [`DamlArchive`] before it is sent to ensure that every field exists and that every
+/// value and comparison is compatible with the type of the field, see [`Query::check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    template_ids: Vec<String>,
+    predicates: Vec<QueryPredicate>,
+}
+
+impl Query {
+    /// Create a `Query` for contracts of a single template.
+    pub fn template(template_id: impl Into<String>) -> Self {
+        Self::templates(vec![template_id])
+    }
+
+    /// Create a `Query` for contracts of any of several templates.
+    ///
+    /// The predicates of the query apply to the contracts of every template.
+    pub fn templates<S: Into<String>>(template_ids: Vec<S>) -> Self {
+        Self {
+            template_ids: template_ids.into_iter().map(Into::into).collect(),
+            predicates: vec![],
+        }
+    }
+
+    /// Constrain the contract payload field with the given `.` separated path.
+    pub fn field(self, path: impl AsRef<str>) -> QueryField {
+        QueryField {
+            query: self,
+            path: path.as_ref().split('.').map(ToOwned::to_owned).collect(),
+        }
+    }
+
+    /// Add a further constraint on the contract payload field with the given `.` separated path.
+    ///
+    /// This is equivalent to [`Query::field`] and is provided for readability when chaining predicates.
+    pub fn and(self, path: impl AsRef<str>) -> QueryField {
+        self.field(path)
+    }
+
+    /// The predicates of this query.
+    pub fn predicates(&self) -> &[QueryPredicate] {
+        &self.predicates
+    }
+
+    /// Render the Daml JSON API query language JSON representation of the predicates of this query.
+    ///
+    /// Returns an error if any two predicates conflict, such as an equality and a comparison on the same field.
+    pub fn to_json(&self) -> DamlJsonQueryResult<Value> {
+        let mut root = QueryNode::Fields(BTreeMap::new());
+        for predicate in &self.predicates {
+            root.insert(predicate, &predicate.path)?;
+        }
+        Ok(root.into_json())
+    }
+
+    /// Build the [`DamlJsonQuery`].
+    pub fn build(self) -> DamlJsonQueryResult<DamlJsonQuery> {
+        if self.template_ids.is_empty() {
+            return Err(DamlJsonQueryError::NoTemplateIds);
+        }
+        let query = self.to_json()?;
+        Ok(DamlJsonQuery::new(self.template_ids, query))
+    }
+
+    /// Check the query against a [`DamlArchive`] and build the [`DamlJsonQuery`].
+    pub fn build_checked(self, arc: &DamlArchive<'_>) -> DamlJsonQueryResult<DamlJsonQuery> {
+        self.check(arc)?;
+        self.build()
+    }
+
+    /// Check the query against a [`DamlArchive`].
+    ///
+    /// For every template of the query this checks that each field path exists, that every intermediate field of a
+    /// path is a record, that comparisons are only applied to fields of comparable types (`Int64`, `Numeric`, `Text`,
+    /// `Date`, `Time` and `Party`) and that every value is a valid JSON encoding of the type of its field.
+    pub fn check(&self, arc: &DamlArchive<'_>) -> DamlJsonQueryResult<()> {
+        self.to_json()?;
+        let converter = JsonToGrpcRequestConverter::new(arc);
+        let checker = QueryChecker {
+            arc,
+            decoder: JsonValueDecoder::new(arc),
+        };
+        for template_id in &self.template_ids {
+            let template_id = DamlJsonTemplateId::try_from(template_id.as_str())?;
+            let (_, template) = converter.resolve_template(&template_id)?;
+            for predicate in &self.predicates {
+                checker.check_predicate(&template_id, template.fields(), None, predicate, 0)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn with_predicate(mut self, predicate: QueryPredicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+}
+
+/// A field of a [`Query`] which is being constrained.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryField {
+    query: Query,
+    path: Vec<String>,
+}
+
+impl QueryField {
+    /// Match contracts where the field is equal to `value`.
+    ///
+    /// The `value` must be the Daml JSON encoding of a value of the type of the field.
+    pub fn eq(self, value: impl Into<Value>) -> Query {
+        self.predicate(QueryOperator::Eq, value.into())
+    }
+
+    /// Match contracts where the field is less than `value`.
+    pub fn lt(self, value: impl Into<Value>) -> Query {
+        self.predicate(QueryOperator::Lt, value.into())
+    }
+
+    /// Match contracts where the field is less than or equal to `value`.
+    pub fn lte(self, value: impl Into<Value>) -> Query {
+        self.predicate(QueryOperator::Lte, value.into())
+    }
+
+    /// Match contracts where the field is greater than `value`.
+    pub fn gt(self, value: impl Into<Value>) -> Query {
+        self.predicate(QueryOperator::Gt, value.into())
+    }
+
+    /// Match contracts where the field is greater than or equal to `value`.
+    pub fn gte(self, value: impl Into<Value>) -> Query {
+        self.predicate(QueryOperator::Gte, value.into())
+    }
+
+    /// Match contracts where the field is greater than or equal to `from` and less than or equal to `to`.
+    pub fn between(self, from: impl Into<Value>, to: impl Into<Value>) -> Query {
+        let path = self.path.clone();
+        Self {
+            query: self.gte(from),
+            path,
+        }
+        .lte(to)
+    }
+
+    fn predicate(self, operator: QueryOperator, value: Value) -> Query {
+        self.query.with_predicate(QueryPredicate {
+            path: self.path,
+            operator,
+            value,
+        })
+    }
+}
+
+/// A single predicate of a [`Query`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPredicate {
+    path: Vec<String>,
+    operator: QueryOperator,
+    value: Value,
+}
+
+impl QueryPredicate {
+    /// The path of the field constrained by this predicate.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    pub const fn operator(&self) -> QueryOperator {
+        self.operator
+    }
+
+    pub const fn value(&self) -> &Value {
+        &self.value
+    }
+
+    fn field_name(&self) -> String {
+        self.path.join(".")
+    }
+}
+
+/// A Daml JSON API query language operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryOperator {
+    Eq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+impl QueryOperator {
+    /// The query language keyword of a comparison operator, `None` for equality.
+    pub const fn keyword(self) -> Option<&'static str> {
+        match self {
+            Self::Eq => None,
+            Self::Lt => Some("%lt"),
+            Self::Lte => Some("%lte"),
+            Self::Gt => Some("%gt"),
+            Self::Gte => Some("%gte"),
+        }
+    }
+}
+
+impl Display for QueryOperator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.keyword().unwrap_or("="))
+    }
+}
+
+/// The predicates of a query arranged by field.
+enum QueryNode {
+    Fields(BTreeMap<String, Self>),
+    Eq(Value),
+    Range(Map<String, Value>),
+}
+
+impl QueryNode {
+    fn insert(&mut self, predicate: &QueryPredicate, path: &[String]) -> DamlJsonQueryResult<()> {
+        let conflict = || DamlJsonQueryError::ConflictingPredicates(predicate.field_name());
+        match (self, path) {
+            (_, []) => Err(DamlJsonQueryError::EmptyFieldPath),
+            (Self::Fields(fields), [name]) => match (fields.get_mut(name), predicate.operator.keyword()) {
+                (None, None) => {
+                    fields.insert(name.clone(), Self::Eq(predicate.value.clone()));
+                    Ok(())
+                },
+                (None, Some(keyword)) => {
+                    let range = std::iter::once((keyword.to_owned(), predicate.value.clone())).collect();
+                    fields.insert(name.clone(), Self::Range(range));
+                    Ok(())
+                },
+                (Some(Self::Range(range)), Some(keyword)) if !range.contains_key(keyword) => {
+                    range.insert(keyword.to_owned(), predicate.value.clone());
+                    Ok(())
+                },
+                _ => Err(conflict()),
+            },
+            (Self::Fields(fields), [name, rest @ ..]) =>
+                fields.entry(name.clone()).or_insert_with(|| Self::Fields(BTreeMap::new())).insert(predicate, rest),
+            _ => Err(conflict()),
+        }
+    }
+
+    fn into_json(self) -> Value {
+        match self {
+            Self::Fields(fields) =>
+                Value::Object(fields.into_iter().map(|(name, node)| (name, node.into_json())).collect()),
+            Self::Eq(value) => value,
+            Self::Range(range) => Value::Object(range),
+        }
+    }
+}
+
+/// Check the predicates of a query against the fields of a template.
+struct QueryChecker<'a> {
+    arc: &'a DamlArchive<'a>,
+    decoder: JsonValueDecoder<'a>,
+}
+
+impl QueryChecker<'_> {
+    /// Check the part of the `predicate` path from `depth` onwards against `fields`.
+    fn check_predicate<'t>(
+        &self,
+        template_id: &DamlJsonTemplateId,
+        fields: &'t [DamlField<'t>],
+        type_args: Option<&'t TypeArgs<'t>>,
+        predicate: &QueryPredicate,
+        depth: usize,
+    ) -> DamlJsonQueryResult<()> {
+        let name = predicate.path.get(depth).ok_or(DamlJsonQueryError::EmptyFieldPath)?;
+        let field = fields.iter().find(|field| field.name() == name).ok_or_else(|| {
+            DamlJsonQueryError::UnknownField(predicate.path[..=depth].join("."), template_id.to_string())
+        })?;
+        let (ty, type_args) = resolve_var(field.ty(), type_args);
+        if depth + 1 < predicate.path.len() {
+            let record = match ty {
+                DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) => match self.arc.data_by_tycon(tycon) {
+                    Some(data @ DamlData::Record(record)) => Some((data, tycon, record.fields())),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let (data, tycon, fields) = record.ok_or_else(|| {
+                DamlJsonQueryError::NotARecord(predicate.path[..=depth].join("."), describe_type(ty, type_args))
+            })?;
+            let type_args = TypeArgs::new(data.type_params(), tycon.type_arguments(), type_args);
+            self.check_predicate(template_id, fields, Some(&type_args), predicate, depth + 1)
+        } else {
+            self.check_value(ty, type_args, predicate)
+        }
+    }
+
+    /// Check that the `predicate` operator and value are compatible with the type `ty` of the field.
+    fn check_value<'t>(
+        &self,
+        ty: &'t DamlType<'t>,
+        type_args: Option<&'t TypeArgs<'t>>,
+        predicate: &QueryPredicate,
+    ) -> DamlJsonQueryResult<()> {
+        let comparable = matches!(
+            ty,
+            DamlType::Int64
+                | DamlType::Numeric(_)
+                | DamlType::Text
+                | DamlType::Date
+                | DamlType::Timestamp
+                | DamlType::Party
+        );
+        if predicate.operator != QueryOperator::Eq && !comparable {
+            return Err(DamlJsonQueryError::UnsupportedComparison(
+                predicate.field_name(),
+                predicate.operator.to_string(),
+                describe_type(ty, type_args),
+            ));
+        }
+        let pointer = format!("/query/{}", predicate.path.join("/"));
+        self.decoder
+            .decode_with_type_args(&predicate.value, ty, type_args, &pointer)
+            .map(|_| ())
+            .map_err(|err| DamlJsonQueryError::InvalidValue(predicate.field_name(), Box::new(err)))
+    }
+}
+
+/// Resolve a `DamlType` which may be a type variable to the type it refers to and the `TypeArgs` of that type.
+fn resolve_var<'t>(
+    ty: &'t DamlType<'t>,
+    type_args: Option<&'t TypeArgs<'t>>,
+) -> (&'t DamlType<'t>, Option<&'t TypeArgs<'t>>) {
+    match ty {
+        DamlType::Var(var) => match type_args.and_then(|type_args| type_args.resolve(var)) {
+            Some((resolved, parent)) => resolve_var(resolved, parent),
+            None => (ty, type_args),
+        },
+        _ => (ty, type_args),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    static TESTING_TYPES_DAR_PATH: &str = "../resources/testing_types_sandbox/TestingTypes-latest.dar";
+
+    #[test]
+    fn test_build_query() -> Result<()> {
+        let query = Query::template("Fuji.PingPong:Ping").field("count").gte(5).and("sender").eq("Alice").build()?;
+        assert_eq!(query.template_ids, vec!["Fuji.PingPong:Ping"]);
+        assert_eq!(query.query, json!({ "count": { "%gte": 5 }, "sender": "Alice" }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_empty_query() -> Result<()> {
+        let query = Query::templates(vec!["Fuji.PingPong:Ping", "Fuji.PingPong:Pong"]).build()?;
+        assert_eq!(query.template_ids, vec!["Fuji.PingPong:Ping", "Fuji.PingPong:Pong"]);
+        assert_eq!(query.query, json!({}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_nested_and_range() -> Result<()> {
+        let query = Query::template("Fuji.GenericTypes:TemplateWithGeneric")
+            .field("data_y.first")
+            .between(1, 10)
+            .and("data_y.middle")
+            .eq("boo")
+            .and("party")
+            .eq("Alice")
+            .build()?;
+        assert_eq!(
+            query.query,
+            json!({ "data_y": { "first": { "%gte": 1, "%lte": 10 }, "middle": "boo" }, "party": "Alice" })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_no_templates_err() {
+        let query = Query::templates::<String>(vec![]).build();
+        assert!(matches!(query, Err(DamlJsonQueryError::NoTemplateIds)));
+    }
+
+    #[test]
+    fn test_build_conflicting_predicates_err() {
+        let eq_and_range = Query::template("Fuji.PingPong:Ping").field("count").gte(5).and("count").eq(6).build();
+        assert!(matches!(eq_and_range, Err(DamlJsonQueryError::ConflictingPredicates(f)) if f == "count"));
+        let duplicate = Query::template("Fuji.PingPong:Ping").field("count").gte(5).and("count").gte(6).build();
+        assert!(matches!(duplicate, Err(DamlJsonQueryError::ConflictingPredicates(f)) if f == "count"));
+        let nested = Query::template("Fuji.PingPong:Ping").field("data").eq(json!({})).and("data.foo").eq(1).build();
+        assert!(matches!(nested, Err(DamlJsonQueryError::ConflictingPredicates(f)) if f == "data.foo"));
+    }
+
+    #[test]
+    fn test_check_valid() -> Result<()> {
+        let arc = daml_archive();
+        Query::template("Fuji.PingPong:Ping").field("count").gte(5).and("sender").eq("Alice").check(arc)?;
+        Query::template("Fuji.GenericTypes:TemplateWithGeneric")
+            .field("data_y.first")
+            .between(1, 10)
+            .and("data_y.middle")
+            .lt("z")
+            .and("data_z.beta.first")
+            .eq(json!([1, 2]))
+            .check(arc)?;
+        Query::template("Fuji.Vehicle:Car")
+            .field("reg_year")
+            .gt("2020-01-01")
+            .and("purchase_time")
+            .lte("2020-01-01T00:00:00Z")
+            .and("color")
+            .eq("Red")
+            .check(arc)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_unknown_field_err() {
+        let arc = daml_archive();
+        let result = Query::template("Fuji.PingPong:Ping").field("cont").gte(5).check(arc);
+        assert!(matches!(result, Err(DamlJsonQueryError::UnknownField(f, _)) if f == "cont"));
+        let result = Query::templates(vec!["Fuji.PingPong:Ping", "Fuji.Vehicle:Car"]).field("count").gte(5).check(arc);
+        assert!(
+            matches!(result, Err(DamlJsonQueryError::UnknownField(f, t)) if f == "count" && t == "Fuji.Vehicle:Car")
+        );
+    }
+
+    #[test]
+    fn test_check_not_a_record_err() {
+        let arc = daml_archive();
+        let result = Query::template("Fuji.PingPong:Ping").field("count.value").eq(1).check(arc);
+        assert!(matches!(result, Err(DamlJsonQueryError::NotARecord(f, ty)) if f == "count" && ty == "Int"));
+    }
+
+    #[test]
+    fn test_check_unsupported_comparison_err() {
+        let arc = daml_archive();
+        let result = Query::template("Fuji.Vehicle:Car").field("color").gt("Red").check(arc);
+        assert!(
+            matches!(result, Err(DamlJsonQueryError::UnsupportedComparison(f, op, _)) if f == "color" && op == "%gt")
+        );
+    }
+
+    #[test]
+    fn test_check_invalid_value_err() {
+        let arc = daml_archive();
+        let result =
+            Query::template("Fuji.GenericTypes:TemplateWithGeneric").field("data_y.first").gte("ten").check(arc);
+        assert!(matches!(result, Err(DamlJsonQueryError::InvalidValue(f, _)) if f == "data_y.first"));
+        let result = Query::template("Fuji.Vehicle:Car").field("color").eq("Purple").check(arc);
+        assert!(matches!(result, Err(DamlJsonQueryError::InvalidValue(f, _)) if f == "color"));
+    }
+
+    #[test]
+    fn test_check_unknown_template_err() {
+        let arc = daml_archive();
+        let result = Query::template("Fuji.PingPong:Unknown").field("count").eq(1).check(arc);
+        assert!(matches!(result, Err(DamlJsonQueryError::TemplateError(_))));
+    }
+
+    fn daml_archive() -> &'static DamlArchive<'static> {
+        crate::test_util::daml_archive(TESTING_TYPES_DAR_PATH)
+    }
+}
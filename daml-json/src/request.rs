@@ -1,7 +1,14 @@
-use crate::data::{DamlJsonCreatedEvent, DamlJsonExerciseResult, DamlJsonParty};
+use crate::data::{
+    DamlJsonCreatedEvent, DamlJsonExerciseResult, DamlJsonMeteringReport, DamlJsonParty, DamlJsonUser,
+    DamlJsonUserRight,
+};
+use crate::error::DamlJsonDecodeError;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use std::iter::once;
 
 /// Daml JSON API request metadata.
@@ -189,6 +196,69 @@ pub struct DamlJsonCreateAndExerciseResponse {
     pub warnings: Option<HashMap<String, Vec<String>>>,
 }
 
+/// Daml JSON API batch request.
+///
+/// The `commands` are submitted, in order, as a single atomic transaction.  Either every command succeeds or the
+/// whole batch is rejected.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonBatchRequest {
+    pub commands: Vec<DamlJsonBatchCommand>,
+    pub meta: Option<DamlJsonRequestMeta>,
+}
+
+impl DamlJsonBatchRequest {
+    /// Create a new `DamlJsonBatchRequest` for a given list of commands.
+    pub fn new(commands: Vec<DamlJsonBatchCommand>) -> Self {
+        Self {
+            commands,
+            meta: None,
+        }
+    }
+
+    /// Create a new `DamlJsonBatchRequest` with metadata for a given list of commands.
+    pub fn new_with_meta(commands: Vec<DamlJsonBatchCommand>, meta: DamlJsonRequestMeta) -> Self {
+        Self {
+            commands,
+            meta: Some(meta),
+        }
+    }
+}
+
+/// A single command within a [`DamlJsonBatchRequest`].
+///
+/// Each command is represented as a JSON object with a single `create`, `exercise` or `exerciseByKey` field whose
+/// value has the same structure as the body of the corresponding Daml JSON API request.  Any `meta` of a nested
+/// create request is ignored, the `meta` of the enclosing [`DamlJsonBatchRequest`] applies to the whole batch.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DamlJsonBatchCommand {
+    Create(DamlJsonCreateRequest),
+    Exercise(DamlJsonExerciseRequest),
+    ExerciseByKey(DamlJsonExerciseByKeyRequest),
+}
+
+/// Daml JSON API batch response.
+///
+/// The `result` contains one [`DamlJsonBatchResult`] for each command of the [`DamlJsonBatchRequest`], in order.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonBatchResponse {
+    pub status: u16,
+    pub result: Vec<DamlJsonBatchResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<HashMap<String, Vec<String>>>,
+}
+
+/// The result of a single command within a [`DamlJsonBatchRequest`].
+///
+/// A `create` command results in a `created` contract and an `exercise` or `exerciseByKey` command results in an
+/// `exercised` choice.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DamlJsonBatchResult {
+    Created(DamlJsonCreatedEvent),
+    Exercised(DamlJsonExerciseResult),
+}
+
 /// Daml JSON API fetch contract by id request.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DamlJsonFetchRequest {
@@ -316,11 +386,202 @@ pub struct DamlJsonUploadDarResponse {
     pub warnings: Option<HashMap<String, Vec<String>>>,
 }
 
+/// Daml JSON API create user request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonCreateUserRequest {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+    #[serde(rename = "primaryParty", skip_serializing_if = "Option::is_none")]
+    pub primary_party: Option<String>,
+    pub rights: Vec<DamlJsonUserRight>,
+}
+
+impl DamlJsonCreateUserRequest {
+    /// Create a new `DamlJsonCreateUserRequest` with an optional primary party and initial rights.
+    pub fn new<S: Into<String>>(
+        user_id: impl Into<String>,
+        primary_party: Option<S>,
+        rights: Vec<DamlJsonUserRight>,
+    ) -> Self {
+        Self {
+            user_id: user_id.into(),
+            primary_party: primary_party.map(Into::into),
+            rights,
+        }
+    }
+}
+
+/// Daml JSON API create user response.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonCreateUserResponse {
+    pub status: u16,
+    pub result: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<HashMap<String, Vec<String>>>,
+}
+
+/// Daml JSON API fetch user request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonFetchUserRequest {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+}
+
+impl DamlJsonFetchUserRequest {
+    /// Create a new `DamlJsonFetchUserRequest` for a given user identifier.
+    pub fn new(user_id: impl Into<String>) -> Self {
+        Self {
+            user_id: user_id.into(),
+        }
+    }
+}
+
+/// Daml JSON API fetch user response.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonFetchUserResponse {
+    pub status: u16,
+    pub result: DamlJsonUser,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<HashMap<String, Vec<String>>>,
+}
+
+/// Daml JSON API delete user request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonDeleteUserRequest {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+}
+
+impl DamlJsonDeleteUserRequest {
+    /// Create a new `DamlJsonDeleteUserRequest` for a given user identifier.
+    pub fn new(user_id: impl Into<String>) -> Self {
+        Self {
+            user_id: user_id.into(),
+        }
+    }
+}
+
+/// Daml JSON API delete user response.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonDeleteUserResponse {
+    pub status: u16,
+    pub result: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<HashMap<String, Vec<String>>>,
+}
+
+/// Daml JSON API list users response.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonListUsersResponse {
+    pub status: u16,
+    pub result: Vec<DamlJsonUser>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<HashMap<String, Vec<String>>>,
+}
+
+/// Daml JSON API grant user rights request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonGrantUserRightsRequest {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+    pub rights: Vec<DamlJsonUserRight>,
+}
+
+impl DamlJsonGrantUserRightsRequest {
+    /// Create a new `DamlJsonGrantUserRightsRequest` for a given user identifier and rights.
+    pub fn new(user_id: impl Into<String>, rights: Vec<DamlJsonUserRight>) -> Self {
+        Self {
+            user_id: user_id.into(),
+            rights,
+        }
+    }
+}
+
+/// Daml JSON API revoke user rights request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonRevokeUserRightsRequest {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+    pub rights: Vec<DamlJsonUserRight>,
+}
+
+impl DamlJsonRevokeUserRightsRequest {
+    /// Create a new `DamlJsonRevokeUserRightsRequest` for a given user identifier and rights.
+    pub fn new(user_id: impl Into<String>, rights: Vec<DamlJsonUserRight>) -> Self {
+        Self {
+            user_id: user_id.into(),
+            rights,
+        }
+    }
+}
+
+/// Daml JSON API list user rights request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonListUserRightsRequest {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+}
+
+impl DamlJsonListUserRightsRequest {
+    /// Create a new `DamlJsonListUserRightsRequest` for a given user identifier.
+    pub fn new(user_id: impl Into<String>) -> Self {
+        Self {
+            user_id: user_id.into(),
+        }
+    }
+}
+
+/// Daml JSON API grant, revoke & list user rights response.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonUserRightsResponse {
+    pub status: u16,
+    pub result: Vec<DamlJsonUserRight>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<HashMap<String, Vec<String>>>,
+}
+
+/// Daml JSON API metering report request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonMeteringReportRequest {
+    pub from: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application: Option<String>,
+}
+
+impl DamlJsonMeteringReportRequest {
+    /// Create a new `DamlJsonMeteringReportRequest` with an optional end date and application.
+    pub fn new<S: Into<String>>(from: NaiveDate, to: Option<NaiveDate>, application: Option<S>) -> Self {
+        Self {
+            from,
+            to,
+            application: application.map(Into::into),
+        }
+    }
+}
+
+/// Daml JSON API metering report response.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonMeteringReportResponse {
+    pub status: u16,
+    pub result: DamlJsonMeteringReport,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<HashMap<String, Vec<String>>>,
+}
+
 /// Daml JSON API generic error response.
+///
+/// The `error_details` are provided if the request contained JSON values which could not be decoded and the
+/// `validation` report is provided if the request contained JSON values which do not conform to their JSON schema.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DamlJsonErrorResponse {
     pub status: u16,
     pub errors: Vec<String>,
+    #[serde(rename = "errorDetails", skip_serializing_if = "Option::is_none", default)]
+    pub error_details: Option<Vec<DamlJsonErrorDetail>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub validation: Option<Box<DamlJsonValidationReport>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub warnings: Option<HashMap<String, Vec<String>>>,
 }
@@ -330,11 +591,96 @@ impl DamlJsonErrorResponse {
         Self {
             status,
             errors: vec![error],
+            error_details: None,
+            validation: None,
+            warnings: None,
+        }
+    }
+
+    /// Create an error response with an error and error detail for each [`DamlJsonDecodeError`].
+    pub fn from_decode_errors(status: u16, decode_errors: &[DamlJsonDecodeError]) -> Self {
+        Self {
+            status,
+            errors: decode_errors.iter().map(ToString::to_string).collect(),
+            error_details: Some(decode_errors.iter().map(DamlJsonErrorDetail::from).collect()),
+            validation: None,
+            warnings: None,
+        }
+    }
+
+    /// Create an error response for a request which failed JSON schema validation, reporting every violation.
+    pub fn from_validation_report(status: u16, report: DamlJsonValidationReport) -> Self {
+        Self {
+            status,
+            errors: report.errors.iter().map(ToString::to_string).collect(),
+            error_details: None,
+            validation: Some(Box::new(report)),
             warnings: None,
         }
     }
 }
 
+/// Daml JSON API error detail for a JSON value which could not be decoded.
+///
+/// The `path` is a JSON pointer to the offending value within the request and `expected` is the Daml type that value
+/// was expected to have.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamlJsonErrorDetail {
+    pub path: String,
+    pub expected: String,
+    pub message: String,
+}
+
+impl From<&DamlJsonDecodeError> for DamlJsonErrorDetail {
+    fn from(err: &DamlJsonDecodeError) -> Self {
+        Self {
+            path: err.path.clone(),
+            expected: err.expected.clone(),
+            message: err.error.to_string(),
+        }
+    }
+}
+
+/// A JSON schema validation report.
+///
+/// The report uses the standard JSON schema
+/// ["basic" output format](https://json-schema.org/draft/2020-12/json-schema-core.html#name-basic) and so lists every
+/// violation as a flat sequence of errors.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DamlJsonValidationReport {
+    pub valid: bool,
+    #[serde(default)]
+    pub errors: Vec<DamlJsonValidationError>,
+}
+
+impl DamlJsonValidationReport {
+    /// Create a report from a list of violations, the report is valid if there are no violations.
+    pub fn new(errors: Vec<DamlJsonValidationError>) -> Self {
+        Self {
+            valid: errors.is_empty(),
+            errors,
+        }
+    }
+}
+
+/// A single JSON schema violation.
+///
+/// The `keyword_location` is a JSON pointer to the schema keyword which failed and the `instance_location` is a JSON
+/// pointer to the offending value within the request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DamlJsonValidationError {
+    pub keyword_location: String,
+    pub instance_location: String,
+    pub error: String,
+}
+
+impl Display for DamlJsonValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.instance_location, self.error)
+    }
+}
+
 /// Make a warnings map with a single entry.
 pub fn make_single_warning(name: impl Into<String>, data: Vec<String>) -> HashMap<String, Vec<String>> {
     once((name.into(), data)).collect::<HashMap<_, _>>()
@@ -1,11 +1,12 @@
 use crate::error::{DamlJsonReqConError, DamlJsonReqConResult};
 use crate::request::{
-    DamlJsonCreateAndExerciseRequest, DamlJsonCreateRequest, DamlJsonExerciseByKeyRequest, DamlJsonExerciseRequest,
+    DamlJsonBatchCommand, DamlJsonBatchRequest, DamlJsonCreateAndExerciseRequest, DamlJsonCreateRequest,
+    DamlJsonExerciseByKeyRequest, DamlJsonExerciseRequest,
 };
 use crate::util::fst;
 use crate::value_decode::JsonValueDecoder;
 use daml_grpc::data::command::{
-    DamlCreateAndExerciseCommand, DamlCreateCommand, DamlExerciseByKeyCommand, DamlExerciseCommand,
+    DamlCommand, DamlCreateAndExerciseCommand, DamlCreateCommand, DamlExerciseByKeyCommand, DamlExerciseCommand,
 };
 use daml_grpc::data::value::{DamlRecord, DamlValue};
 use daml_grpc::data::DamlIdentifier;
@@ -31,14 +32,20 @@ impl<'a> JsonToGrpcRequestConverter<'a> {
         }
     }
 
+    /// Sets whether all errors are collected when decoding JSON values rather than failing on the first error.
+    ///
+    /// See [`JsonValueDecoder::with_validate_all`].
+    #[must_use]
+    pub const fn with_validate_all(self, validate_all: bool) -> Self {
+        Self {
+            arc: self.arc,
+            decoder: self.decoder.with_validate_all(validate_all),
+        }
+    }
+
     /// Convert a JSON API [`DamlJsonCreateRequest`] to a GRPC [`DamlCreateCommand`].
     pub fn convert_create_request(&self, create: &DamlJsonCreateRequest) -> DamlJsonReqConResult<DamlCreateCommand> {
-        let template_id = DamlJsonTemplateId::try_from(create.template_id.as_str())?;
-        let package_id = self.resolve_template(&template_id).map(fst)?;
-        let grpc_identifier = make_grpc_identifier(package_id, &template_id.module, &template_id.entity);
-        let decoded_value = self.decode_data(package_id, &template_id.module, &template_id.entity, &create.payload)?;
-        let grpc_create_arguments = DamlRecord::try_from(decoded_value)?;
-        Ok(DamlCreateCommand::new(grpc_identifier, grpc_create_arguments))
+        self.create_command(create, "")
     }
 
     /// Convert a JSON API [`DamlJsonExerciseRequest`] to a GRPC [`DamlExerciseCommand`].
@@ -46,11 +53,7 @@ impl<'a> JsonToGrpcRequestConverter<'a> {
         &self,
         exercise: &DamlJsonExerciseRequest,
     ) -> DamlJsonReqConResult<DamlExerciseCommand> {
-        let template_id = DamlJsonTemplateId::try_from(exercise.template_id.as_str())?;
-        let package_id = self.resolve_template(&template_id).map(fst)?;
-        let grpc_identifier = make_grpc_identifier(package_id, &template_id.module, &template_id.entity);
-        let choice_args = self.decode_data(package_id, &template_id.module, &exercise.choice, &exercise.argument)?;
-        Ok(DamlExerciseCommand::new(grpc_identifier, &exercise.contract_id, &exercise.choice, choice_args))
+        self.exercise_command(exercise, "")
     }
 
     /// Convert a JSON API [`DamlJsonExerciseByKeyRequest`] to a GRPC [`DamlExerciseByKeyCommand`].
@@ -58,12 +61,7 @@ impl<'a> JsonToGrpcRequestConverter<'a> {
         &self,
         exercise: &DamlJsonExerciseByKeyRequest,
     ) -> DamlJsonReqConResult<DamlExerciseByKeyCommand> {
-        let template_id = DamlJsonTemplateId::try_from(exercise.template_id.as_str())?;
-        let (package_id, template) = self.resolve_template(&template_id)?;
-        let grpc_identifier = make_grpc_identifier(package_id, &template_id.module, &template_id.entity);
-        let choice_key = self.decode_template_key(template, &exercise.key)?;
-        let choice_args = self.decode_data(package_id, &template_id.module, &exercise.choice, &exercise.argument)?;
-        Ok(DamlExerciseByKeyCommand::new(grpc_identifier, choice_key, &exercise.choice, choice_args))
+        self.exercise_by_key_command(exercise, "")
     }
 
     /// Convert a JSON API [`DamlJsonCreateAndExerciseRequest`] to a GRPC [`DamlCreateAndExerciseCommand`].
@@ -74,14 +72,20 @@ impl<'a> JsonToGrpcRequestConverter<'a> {
         let template_id = DamlJsonTemplateId::try_from(create_and_exercise.template_id.as_str())?;
         let package_id = self.resolve_template(&template_id).map(fst)?;
         let grpc_identifier = make_grpc_identifier(package_id, &template_id.module, &template_id.entity);
-        let decoded_value =
-            self.decode_data(package_id, &template_id.module, &template_id.entity, &create_and_exercise.payload)?;
+        let decoded_value = self.decode_data(
+            package_id,
+            &template_id.module,
+            &template_id.entity,
+            &create_and_exercise.payload,
+            "/payload",
+        )?;
         let grpc_create_arguments = DamlRecord::try_from(decoded_value)?;
         let choice_args = self.decode_data(
             package_id,
             &template_id.module,
             &create_and_exercise.choice,
             &create_and_exercise.argument,
+            "/argument",
         )?;
         Ok(DamlCreateAndExerciseCommand::new(
             grpc_identifier,
@@ -91,6 +95,31 @@ impl<'a> JsonToGrpcRequestConverter<'a> {
         ))
     }
 
+    /// Convert a JSON API [`DamlJsonBatchRequest`] to a list of GRPC [`DamlCommand`].
+    ///
+    /// The commands are converted in order and conversion stops at the first command which cannot be converted.  The
+    /// path of any value which cannot be decoded is a JSON pointer relative to the root of the batch request, such as
+    /// `/commands/1/exercise/argument`.
+    pub fn convert_batch_request(&self, batch: &DamlJsonBatchRequest) -> DamlJsonReqConResult<Vec<DamlCommand>> {
+        if batch.commands.is_empty() {
+            return Err(DamlJsonReqConError::EmptyBatchRequest);
+        }
+        batch
+            .commands
+            .iter()
+            .enumerate()
+            .map(|(index, command)| match command {
+                DamlJsonBatchCommand::Create(create) =>
+                    self.create_command(create, &format!("/commands/{index}/create")).map(DamlCommand::Create),
+                DamlJsonBatchCommand::Exercise(exercise) =>
+                    self.exercise_command(exercise, &format!("/commands/{index}/exercise")).map(DamlCommand::Exercise),
+                DamlJsonBatchCommand::ExerciseByKey(exercise) => self
+                    .exercise_by_key_command(exercise, &format!("/commands/{index}/exerciseByKey"))
+                    .map(DamlCommand::ExerciseByKeyCommand),
+            })
+            .collect()
+    }
+
     /// Attempt to resolve a `DamlJsonTemplateId` to a `DamlTemplate` and containing package id.
     ///
     /// If the given `DamlJsonTemplateId` contains a package id then this is used to locate the required
@@ -100,7 +129,7 @@ impl<'a> JsonToGrpcRequestConverter<'a> {
     /// unique template which matches the required module path and entity name.
     ///
     /// If no unique `DamlTemplate` is found then an error is returned.
-    fn resolve_template(
+    pub fn resolve_template(
         &'a self,
         template_id: &'a DamlJsonTemplateId,
     ) -> DamlJsonReqConResult<(&'a str, &'a DamlTemplate<'_>)> {
@@ -148,26 +177,83 @@ impl<'a> JsonToGrpcRequestConverter<'a> {
         }
     }
 
-    /// Decode a JSON encoded Daml `data` type.
+    /// Convert a [`DamlJsonCreateRequest`] located at the JSON pointer `pointer` within the request.
+    fn create_command(&self, create: &DamlJsonCreateRequest, pointer: &str) -> DamlJsonReqConResult<DamlCreateCommand> {
+        let template_id = DamlJsonTemplateId::try_from(create.template_id.as_str())?;
+        let package_id = self.resolve_template(&template_id).map(fst)?;
+        let grpc_identifier = make_grpc_identifier(package_id, &template_id.module, &template_id.entity);
+        let decoded_value = self.decode_data(
+            package_id,
+            &template_id.module,
+            &template_id.entity,
+            &create.payload,
+            &format!("{pointer}/payload"),
+        )?;
+        let grpc_create_arguments = DamlRecord::try_from(decoded_value)?;
+        Ok(DamlCreateCommand::new(grpc_identifier, grpc_create_arguments))
+    }
+
+    /// Convert a [`DamlJsonExerciseRequest`] located at the JSON pointer `pointer` within the request.
+    fn exercise_command(
+        &self,
+        exercise: &DamlJsonExerciseRequest,
+        pointer: &str,
+    ) -> DamlJsonReqConResult<DamlExerciseCommand> {
+        let template_id = DamlJsonTemplateId::try_from(exercise.template_id.as_str())?;
+        let package_id = self.resolve_template(&template_id).map(fst)?;
+        let grpc_identifier = make_grpc_identifier(package_id, &template_id.module, &template_id.entity);
+        let choice_args = self.decode_data(
+            package_id,
+            &template_id.module,
+            &exercise.choice,
+            &exercise.argument,
+            &format!("{pointer}/argument"),
+        )?;
+        Ok(DamlExerciseCommand::new(grpc_identifier, &exercise.contract_id, &exercise.choice, choice_args))
+    }
+
+    /// Convert a [`DamlJsonExerciseByKeyRequest`] located at the JSON pointer `pointer` within the request.
+    fn exercise_by_key_command(
+        &self,
+        exercise: &DamlJsonExerciseByKeyRequest,
+        pointer: &str,
+    ) -> DamlJsonReqConResult<DamlExerciseByKeyCommand> {
+        let template_id = DamlJsonTemplateId::try_from(exercise.template_id.as_str())?;
+        let (package_id, template) = self.resolve_template(&template_id)?;
+        let grpc_identifier = make_grpc_identifier(package_id, &template_id.module, &template_id.entity);
+        let choice_key = self.decode_template_key(template, &exercise.key, &format!("{pointer}/key"))?;
+        let choice_args = self.decode_data(
+            package_id,
+            &template_id.module,
+            &exercise.choice,
+            &exercise.argument,
+            &format!("{pointer}/argument"),
+        )?;
+        Ok(DamlExerciseByKeyCommand::new(grpc_identifier, choice_key, &exercise.choice, choice_args))
+    }
+
+    /// Decode a JSON encoded Daml `data` type located at the JSON pointer `pointer` within the request.
     fn decode_data(
         &self,
         package_id: &str,
         module: &[String],
         entity: &str,
         json_value: &Value,
+        pointer: &str,
     ) -> DamlJsonReqConResult<DamlValue> {
-        Ok(self.decoder.decode(json_value, &DamlType::make_tycon(package_id, module, entity))?)
+        Ok(self.decoder.decode_at(json_value, &DamlType::make_tycon(package_id, module, entity), pointer)?)
     }
 
-    /// Decode a JSON encoded Daml template key.
+    /// Decode a JSON encoded Daml template key located at the JSON pointer `pointer` within the request.
     fn decode_template_key(
         &self,
         template: &DamlTemplate<'_>,
         json_key_value: &Value,
+        pointer: &str,
     ) -> DamlJsonReqConResult<DamlValue> {
         let key_ty =
             template.key().ok_or_else(|| DamlJsonReqConError::TemplateNoKeyError(template.name().to_string()))?.ty();
-        Ok(self.decoder.decode(json_key_value, key_ty)?)
+        Ok(self.decoder.decode_at(json_key_value, key_ty, pointer)?)
     }
 }
 
@@ -434,7 +520,12 @@ mod tests {
         let request_converter = JsonToGrpcRequestConverter::new(arc);
         let request = DamlJsonExerciseRequest::new("Fuji.RentDemo:RentalAgreement", "#0:0", "UnknownChoice", json!({}));
         match request_converter.convert_exercise_request(&request) {
-            Err(DamlJsonReqConError::CodecError(DamlJsonCodecError::DataNotFound(_))) => Ok(()),
+            Err(DamlJsonReqConError::CodecError(DamlJsonCodecError::DecodeErrors(errors))) => {
+                assert!(
+                    matches!(errors.as_slice(), [err] if matches!(*err.error, DamlJsonCodecError::DataNotFound(_)))
+                );
+                Ok(())
+            },
             Err(e) => panic!("{}", e.to_string()),
             _ => panic!("test should fail"),
         }
@@ -466,7 +557,12 @@ mod tests {
             ),
         );
         match request_converter.convert_create_request(&request) {
-            Err(DamlJsonReqConError::CodecError(DamlJsonCodecError::MissingJsonRecordObjectField(_))) => Ok(()),
+            Err(DamlJsonReqConError::CodecError(DamlJsonCodecError::DecodeErrors(errors))) => {
+                assert!(
+                    matches!(errors.as_slice(), [err] if matches!(*err.error, DamlJsonCodecError::MissingJsonRecordObjectField(_)) && err.path == "/payload/terms")
+                );
+                Ok(())
+            },
             Err(e) => panic!("{}", e.to_string()),
             _ => panic!("test should fail"),
         }
@@ -492,12 +588,92 @@ mod tests {
             ),
         );
         match request_converter.convert_create_and_exercise_request(&request) {
-            Err(DamlJsonReqConError::CodecError(DamlJsonCodecError::MissingJsonRecordObjectField(_))) => Ok(()),
+            Err(DamlJsonReqConError::CodecError(DamlJsonCodecError::DecodeErrors(errors))) => {
+                assert!(
+                    matches!(errors.as_slice(), [err] if matches!(*err.error, DamlJsonCodecError::MissingJsonRecordObjectField(_)) && err.path == "/argument/bar")
+                );
+                Ok(())
+            },
             Err(e) => panic!("{}", e.to_string()),
             _ => panic!("test should fail"),
         }
     }
 
+    #[test]
+    fn test_convert_batch_request() -> Result<()> {
+        let arc = daml_archive();
+        let request_converter = JsonToGrpcRequestConverter::new(arc);
+        let request = DamlJsonBatchRequest::new(vec![
+            DamlJsonBatchCommand::Create(DamlJsonCreateRequest::new(
+                "Fuji.RentDemo:RentalAgreement",
+                json!({ "landlord": "Alice", "tenant": "Bob", "terms": "test terms" }),
+            )),
+            DamlJsonBatchCommand::Exercise(DamlJsonExerciseRequest::new(
+                "Fuji.RentDemo:RentalAgreement",
+                "#0:0",
+                "Accept",
+                json!({ "foo": "this is foo", "bar": 100 }),
+            )),
+            DamlJsonBatchCommand::ExerciseByKey(DamlJsonExerciseByKeyRequest::new(
+                "Fuji.PingPong:Ping",
+                json!({ "sender" : "Alice", "count": 99 }),
+                "ResetPingCount",
+                json!({}),
+            )),
+        ]);
+        let commands = request_converter.convert_batch_request(&request)?;
+        match commands.as_slice() {
+            [DamlCommand::Create(create), DamlCommand::Exercise(exercise), DamlCommand::ExerciseByKeyCommand(by_key)] =>
+            {
+                assert_eq!("RentalAgreement", create.template_id().entity_name());
+                assert_eq!("Accept", exercise.choice());
+                assert_eq!("ResetPingCount", by_key.choice());
+                assert_eq!(&99, by_key.contract_key().extract(daml_path![count::i])?);
+                Ok(())
+            },
+            _ => panic!("unexpected commands: {commands:?}"),
+        }
+    }
+
+    #[test]
+    fn test_convert_batch_request_missing_field_err() -> Result<()> {
+        let arc = daml_archive();
+        let request_converter = JsonToGrpcRequestConverter::new(arc);
+        let request = DamlJsonBatchRequest::new(vec![
+            DamlJsonBatchCommand::Create(DamlJsonCreateRequest::new(
+                "Fuji.RentDemo:RentalAgreement",
+                json!({ "landlord": "Alice", "tenant": "Bob", "terms": "test terms" }),
+            )),
+            DamlJsonBatchCommand::Exercise(DamlJsonExerciseRequest::new(
+                "Fuji.RentDemo:RentalAgreement",
+                "#0:0",
+                "Accept",
+                json!({ "foo": "this is foo" }),
+            )),
+        ]);
+        match request_converter.convert_batch_request(&request) {
+            Err(DamlJsonReqConError::CodecError(DamlJsonCodecError::DecodeErrors(errors))) => {
+                assert!(
+                    matches!(errors.as_slice(), [err] if matches!(*err.error, DamlJsonCodecError::MissingJsonRecordObjectField(_)) && err.path == "/commands/1/exercise/argument/bar")
+                );
+                Ok(())
+            },
+            Err(e) => panic!("{}", e.to_string()),
+            _ => panic!("test should fail"),
+        }
+    }
+
+    #[test]
+    fn test_convert_batch_request_empty_err() {
+        let arc = daml_archive();
+        let request_converter = JsonToGrpcRequestConverter::new(arc);
+        let request = DamlJsonBatchRequest::new(vec![]);
+        assert!(matches!(
+            request_converter.convert_batch_request(&request),
+            Err(DamlJsonReqConError::EmptyBatchRequest)
+        ));
+    }
+
     fn daml_archive() -> &'static DamlArchive<'static> {
         crate::test_util::daml_archive(TESTING_TYPES_DAR_PATH)
     }
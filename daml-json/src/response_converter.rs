@@ -1,7 +1,11 @@
-use crate::data::{DamlJsonArchivedEvent, DamlJsonCreatedEvent, DamlJsonEvent, DamlJsonExerciseResult};
+use crate::data::{
+    DamlJsonArchivedEvent, DamlJsonCreatedEvent, DamlJsonEvent, DamlJsonExerciseResult, DamlJsonExercisedEvent,
+    DamlJsonTransactionTree, DamlJsonTreeEvent,
+};
 use crate::error::{DamlJsonReqConError, DamlJsonReqConResult};
 use crate::request::{
-    DamlJsonCreateAndExerciseResponse, DamlJsonCreateResponse, DamlJsonExerciseByKeyResponse, DamlJsonExerciseResponse,
+    DamlJsonBatchResponse, DamlJsonBatchResult, DamlJsonCreateAndExerciseResponse, DamlJsonCreateResponse,
+    DamlJsonExerciseByKeyResponse, DamlJsonExerciseResponse,
 };
 use crate::value_encode::JsonValueEncoder;
 use daml_grpc::data::event::{DamlCreatedEvent, DamlEvent, DamlExercisedEvent, DamlTreeEvent};
@@ -11,12 +15,26 @@ use daml_grpc::data::{DamlTransaction, DamlTransactionTree};
 #[derive(Debug)]
 pub struct GrpcToJsonResponseConverter {
     encoder: JsonValueEncoder,
+    verbose: bool,
 }
 
 impl GrpcToJsonResponseConverter {
     pub const fn new(encoder: JsonValueEncoder) -> Self {
         Self {
             encoder,
+            verbose: false,
+        }
+    }
+
+    /// Include the full [`DamlJsonTransactionTree`] in exercise results.
+    ///
+    /// By default exercise results contain only the Created and Archived events of the transaction, in verbose mode the
+    /// `transaction_tree` of each [`DamlJsonExerciseResult`] is also populated.
+    #[must_use]
+    pub const fn with_verbose(self, verbose: bool) -> Self {
+        Self {
+            verbose,
+            ..self
         }
     }
 
@@ -74,6 +92,59 @@ impl GrpcToJsonResponseConverter {
         })
     }
 
+    /// Convert a [`DamlTransactionTree`] to a [`DamlJsonBatchResponse`].
+    ///
+    /// The transaction tree is expected to contain exactly one root event for each of the `command_count` commands of
+    /// the batch, in the order the commands were submitted.  Each root event is converted to a [`DamlJsonBatchResult`]
+    /// which contains only those events which are descendants of that root event.
+    pub fn convert_batch_response(
+        &self,
+        transaction: &DamlTransactionTree,
+        command_count: usize,
+    ) -> DamlJsonReqConResult<DamlJsonBatchResponse> {
+        let root_event_ids = transaction.root_event_ids();
+        if root_event_ids.len() != command_count {
+            return Err(DamlJsonReqConError::UnexpectedRootEventCount(command_count, root_event_ids.len()));
+        }
+        let result = root_event_ids
+            .iter()
+            .map(|event_id| match transaction.events_by_id().get(event_id) {
+                Some(DamlTreeEvent::Created(created)) => self.created_event(created).map(DamlJsonBatchResult::Created),
+                Some(DamlTreeEvent::Exercised(exercised)) =>
+                    self.batch_exercise_result(transaction, exercised).map(DamlJsonBatchResult::Exercised),
+                None => Err(DamlJsonReqConError::UnexpectedGrpcEvent),
+            })
+            .collect::<DamlJsonReqConResult<Vec<_>>>()?;
+        Ok(DamlJsonBatchResponse {
+            status: 200,
+            result,
+            warnings: None,
+        })
+    }
+
+    /// Convert a [`DamlTransactionTree`] to a [`DamlJsonTransactionTree`].
+    ///
+    /// All choice arguments, exercise results and contract payloads are encoded with the [`JsonValueEncoder`].
+    pub fn convert_transaction_tree(
+        &self,
+        transaction: &DamlTransactionTree,
+    ) -> DamlJsonReqConResult<DamlJsonTransactionTree> {
+        let events_by_id = transaction
+            .events_by_id()
+            .iter()
+            .map(|(event_id, event)| Ok((event_id.clone(), self.tree_event(event)?)))
+            .collect::<DamlJsonReqConResult<_>>()?;
+        Ok(DamlJsonTransactionTree {
+            transaction_id: transaction.transaction_id().to_owned(),
+            command_id: transaction.command_id().to_owned(),
+            workflow_id: transaction.workflow_id().to_owned(),
+            effective_at: *transaction.effective_at(),
+            offset: transaction.offset().to_owned(),
+            events_by_id,
+            root_event_ids: transaction.root_event_ids().to_vec(),
+        })
+    }
+
     /// Convert a [`DamlTransactionTree`] to a [`DamlJsonExerciseResult`].
     ///
     /// Note that the [`DamlTransactionTree`] contains only Created and Exercised events, it does not contain Archived
@@ -90,9 +161,11 @@ impl GrpcToJsonResponseConverter {
         let exercise_result = self.encoder.encode_value(exercise_event.exercise_result())?;
         let archive_event = derive_archived_event(exercise_event);
         let events = self.extract_created_events(transaction, archive_event)?;
+        let transaction_tree = self.verbose.then(|| self.convert_transaction_tree(transaction)).transpose()?;
         Ok(DamlJsonExerciseResult {
             exercise_result,
             events,
+            transaction_tree,
         })
     }
 
@@ -116,6 +189,67 @@ impl GrpcToJsonResponseConverter {
             .collect::<DamlJsonReqConResult<Vec<_>>>()
     }
 
+    /// Convert a root [`DamlExercisedEvent`] of a batch to a [`DamlJsonExerciseResult`].
+    ///
+    /// The result includes the Created events of all descendants of `exercised`, in depth-first order, and an Archived
+    /// event if the choice is consuming, see [`GrpcToJsonResponseConverter::exercise_result`].
+    fn batch_exercise_result(
+        &self,
+        transaction: &DamlTransactionTree,
+        exercised: &DamlExercisedEvent,
+    ) -> DamlJsonReqConResult<DamlJsonExerciseResult> {
+        let exercise_result = self.encoder.encode_value(exercised.exercise_result())?;
+        let mut events = vec![];
+        self.collect_created_events(transaction, exercised.child_event_ids(), &mut events)?;
+        events.extend(derive_archived_event(exercised));
+        Ok(DamlJsonExerciseResult {
+            exercise_result,
+            events,
+            transaction_tree: None,
+        })
+    }
+
+    /// Collect the Created events of the `event_ids` events, and their descendants, in depth-first order.
+    fn collect_created_events(
+        &self,
+        transaction: &DamlTransactionTree,
+        event_ids: &[String],
+        events: &mut Vec<DamlJsonEvent>,
+    ) -> DamlJsonReqConResult<()> {
+        for event_id in event_ids {
+            match transaction.events_by_id().get(event_id) {
+                Some(DamlTreeEvent::Created(created)) =>
+                    events.push(DamlJsonEvent::Created(self.created_event(created)?)),
+                Some(DamlTreeEvent::Exercised(exercised)) =>
+                    self.collect_created_events(transaction, exercised.child_event_ids(), events)?,
+                None => return Err(DamlJsonReqConError::UnexpectedGrpcEvent),
+            }
+        }
+        Ok(())
+    }
+
+    fn tree_event(&self, event: &DamlTreeEvent) -> DamlJsonReqConResult<DamlJsonTreeEvent> {
+        match event {
+            DamlTreeEvent::Created(created) => self.created_event(created).map(DamlJsonTreeEvent::Created),
+            DamlTreeEvent::Exercised(exercised) => self.exercised_event(exercised).map(DamlJsonTreeEvent::Exercised),
+        }
+    }
+
+    fn exercised_event(&self, exercised: &DamlExercisedEvent) -> DamlJsonReqConResult<DamlJsonExercisedEvent> {
+        Ok(DamlJsonExercisedEvent {
+            event_id: exercised.event_id().to_owned(),
+            contract_id: exercised.contract_id().to_owned(),
+            template_id: exercised.template_id().to_string(),
+            choice: exercised.choice().to_owned(),
+            choice_argument: self.encoder.encode_value(exercised.choice_argument())?,
+            acting_parties: exercised.acting_parties().to_vec(),
+            consuming: exercised.consuming(),
+            witness_parties: exercised.witness_parties().to_vec(),
+            child_event_ids: exercised.child_event_ids().to_vec(),
+            exercise_result: self.encoder.encode_value(exercised.exercise_result())?,
+        })
+    }
+
     fn created_event(&self, created: &DamlCreatedEvent) -> DamlJsonReqConResult<DamlJsonCreatedEvent> {
         let observers = created.observers().to_vec();
         let agreement_text = created.agreement_text().to_owned();
@@ -200,6 +334,35 @@ mod tests {
         assert_eq!(json!("package:module:entity"), *ser.pointer("/result/events/1/archived/templateId").req()?);
         assert_eq!(json!(false), *ser.pointer("/result/exerciseResult/foo").req()?);
         assert_eq!(json!("something"), *ser.pointer("/result/exerciseResult/bar").req()?);
+        assert!(ser.pointer("/result/transactionTree").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_exercise_response_verbose() -> Result<()> {
+        let value_encoder = JsonValueEncoder::new(false, false);
+        let converter = GrpcToJsonResponseConverter::new(value_encoder).with_verbose(true);
+        let dummy_tx_tree = make_dummy_batch_transaction_tree()?;
+        let converted = converter.convert_exercise_response(&dummy_tx_tree)?;
+        let ser = serde_json::to_value(converted)?;
+        let tree = ser.pointer("/result/transactionTree").req()?;
+        assert_eq!(json!("tx_id"), *tree.pointer("/transactionId").req()?);
+        assert_eq!(json!("cmd_id"), *tree.pointer("/commandId").req()?);
+        assert_eq!(json!("wf_id"), *tree.pointer("/workflowId").req()?);
+        assert_eq!(json!("2019-01-02T03:45:56Z"), *tree.pointer("/effectiveAt").req()?);
+        assert_eq!(json!("offset"), *tree.pointer("/offset").req()?);
+        assert_eq!(json!(["#1", "#2"]), *tree.pointer("/rootEventIds").req()?);
+        assert_eq!(json!("contract_id_1"), *tree.pointer("/eventsById/#1/created/contractId").req()?);
+        assert_eq!(json!("choice_name"), *tree.pointer("/eventsById/#4/exercised/choice").req()?);
+        assert_eq!(json!("contract_id_3"), *tree.pointer("/eventsById/#4/exercised/contractId").req()?);
+        assert_eq!(json!("package:module:entity"), *tree.pointer("/eventsById/#4/exercised/templateId").req()?);
+        assert_eq!(json!({}), *tree.pointer("/eventsById/#4/exercised/choiceArgument").req()?);
+        assert_eq!(json!(["ActingParty"]), *tree.pointer("/eventsById/#4/exercised/actingParties").req()?);
+        assert_eq!(json!(true), *tree.pointer("/eventsById/#4/exercised/consuming").req()?);
+        assert_eq!(json!(["#5"]), *tree.pointer("/eventsById/#4/exercised/childEventIds").req()?);
+        assert_eq!(json!(false), *tree.pointer("/eventsById/#4/exercised/exerciseResult/foo").req()?);
+        assert_eq!(json!(["#3", "#4"]), *tree.pointer("/eventsById/#2/exercised/childEventIds").req()?);
+        assert_eq!(json!("contract_id_4"), *tree.pointer("/eventsById/#5/created/contractId").req()?);
         Ok(())
     }
 
@@ -286,6 +449,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_convert_batch_response() -> Result<()> {
+        let value_encoder = JsonValueEncoder::new(false, false);
+        let converter = GrpcToJsonResponseConverter::new(value_encoder);
+        let dummy_tx_tree = make_dummy_batch_transaction_tree()?;
+        let converted = converter.convert_batch_response(&dummy_tx_tree, 2)?;
+        let ser = serde_json::to_value(converted)?;
+        assert_eq!(json!(200), *ser.pointer("/status").req()?);
+        assert_eq!(json!("contract_id_1"), *ser.pointer("/result/0/created/contractId").req()?);
+        assert_eq!(json!("something"), *ser.pointer("/result/0/created/payload/bar").req()?);
+        assert_eq!(json!("contract_id_3"), *ser.pointer("/result/1/exercised/events/0/created/contractId").req()?);
+        assert_eq!(json!("contract_id_4"), *ser.pointer("/result/1/exercised/events/1/created/contractId").req()?);
+        assert_eq!(json!("contract_id_2"), *ser.pointer("/result/1/exercised/events/2/archived/contractId").req()?);
+        assert_eq!(json!(false), *ser.pointer("/result/1/exercised/exerciseResult/foo").req()?);
+        assert!(ser.pointer("/result/1/exercised/events/3").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_batch_response_root_event_count_err() -> Result<()> {
+        let value_encoder = JsonValueEncoder::new(false, false);
+        let converter = GrpcToJsonResponseConverter::new(value_encoder);
+        let dummy_tx_tree = make_dummy_batch_transaction_tree()?;
+        match converter.convert_batch_response(&dummy_tx_tree, 3) {
+            Err(DamlJsonReqConError::UnexpectedRootEventCount(3, 2)) => Ok(()),
+            Err(e) => panic!("{}", e.to_string()),
+            _ => panic!("test should fail"),
+        }
+    }
+
     fn make_dummy_grpc_record() -> DamlRecord {
         DamlRecordBuilder::new()
             .add_field("foo", DamlValue::new_bool(false))
@@ -370,6 +563,52 @@ mod tests {
         created_event.event_id().into() => created_event))
     }
 
+    /// A batch of a create and an exercise whose choice creates a contract and exercises a choice which does the same.
+    fn make_dummy_batch_transaction_tree() -> Result<DamlTransactionTree> {
+        let created = |event_id: &str, contract_id: &str| {
+            DamlTreeEvent::Created(DamlCreatedEvent::new(
+                event_id,
+                contract_id,
+                DamlIdentifier::new("package", "module", "entity"),
+                None,
+                make_dummy_grpc_record(),
+                vec![],
+                vec!["SigParty1".into()],
+                vec![],
+                "",
+            ))
+        };
+        let exercised = |event_id: &str, contract_id: &str, children: Vec<String>| {
+            DamlTreeEvent::Exercised(DamlExercisedEvent::new(
+                event_id,
+                contract_id,
+                DamlIdentifier::new("package", "module", "entity"),
+                "choice_name",
+                DamlValue::Unit,
+                vec!["ActingParty".into()],
+                true,
+                vec![],
+                children,
+                DamlValue::new_record(make_dummy_grpc_record()),
+            ))
+        };
+        Ok(DamlTransactionTree::new(
+            "tx_id",
+            "cmd_id",
+            "wf_id",
+            "2019-01-02T03:45:56Z".parse::<DateTime<Utc>>()?,
+            "offset",
+            hashmap!(
+                String::from("#1") => created("#1", "contract_id_1"),
+                String::from("#2") => exercised("#2", "contract_id_2", vec!["#3".into(), "#4".into()]),
+                String::from("#3") => created("#3", "contract_id_3"),
+                String::from("#4") => exercised("#4", "contract_id_3", vec!["#5".into()]),
+                String::from("#5") => created("#5", "contract_id_4")
+            ),
+            vec!["#1".into(), "#2".into()],
+        ))
+    }
+
     fn make_dummy_transaction_tree_no_exercised_event() -> Result<DamlTransactionTree> {
         let record = make_dummy_grpc_record();
         let created_event = make_dummy_grpc_created_tree_event(record);
@@ -1,16 +1,17 @@
 #![allow(clippy::or_fun_call)]
 
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::ops::Not;
 
 use itertools::Itertools;
 use serde::Deserialize;
 use serde_json::json;
-use serde_json::Value;
+use serde_json::{Map, Number, Value};
 
 use daml_lf::element::{
-    DamlArchive, DamlData, DamlEnum, DamlField, DamlTyCon, DamlTyConName, DamlType, DamlTypeVarWithKind, DamlVar,
-    DamlVariant,
+    DamlArchive, DamlChoice, DamlData, DamlEnum, DamlField, DamlModule, DamlPackage, DamlTemplate, DamlTyCon,
+    DamlTyConName, DamlType, DamlTypeVarWithKind, DamlVar, DamlVariant,
 };
 
 use crate::error::DamlJsonSchemaCodecError::NotSerializableDamlType;
@@ -26,17 +27,125 @@ use crate::schema_data::{
 use crate::util::AsSingleSliceExt;
 use crate::util::Required;
 
-/// A data dictionary for augmenting the generated JSON Schema with `title` and `description` attributes.
-#[derive(Debug, Deserialize, Default)]
+/// A data dictionary for augmenting the generated JSON Schema with documentation and validation attributes.
+#[derive(Debug, Clone, Deserialize, Default)]
 pub struct DataDict(BTreeMap<String, DataDictEntry>);
 
 /// A data item in the data dictionary.
-#[derive(Debug, Deserialize, Default)]
+///
+/// The `items` are the record fields, variant constructors or enum constructors of the data item.
+#[derive(Debug, Clone, Deserialize, Default)]
 pub struct DataDictEntry {
     title: Option<String>,
     description: Option<String>,
     #[serde(default)]
-    items: BTreeMap<String, String>,
+    items: BTreeMap<String, DataDictItem>,
+}
+
+/// A record field, variant constructor or enum constructor in the data dictionary.
+///
+/// An item may be given either as a plain description or as an object with any of the following optional attributes,
+/// each of which is emitted as the JSON Schema keyword of the same name:
+///
+/// - `title`: the display name of the item, i.e. for enum constructors
+/// - `description`: the description of the item
+/// - `examples`: a list of example JSON values
+/// - `pattern`: a regular expression which `Text` values must match
+/// - `minimum` & `maximum`: the inclusive bounds of `Int64` and `Numeric` values
+/// - `deprecated`: whether the item is deprecated
+///
+/// For example:
+///
+/// ```yaml
+/// Fuji.JsonTest:Person:
+///   items:
+///     name: the full name of the person
+///     age:
+///       description: the age of the person
+///       examples: [ 42 ]
+///       minimum: 0
+///       maximum: 150
+/// ```
+///
+/// > ⓘ Note: `Int64` and `Numeric` values may also be encoded as JSON strings, the `minimum` and `maximum` keywords
+/// > only apply to values encoded as JSON numbers.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(from = "DataDictItemRepr")]
+pub struct DataDictItem {
+    title: Option<String>,
+    description: Option<String>,
+    examples: Vec<Value>,
+    pattern: Option<String>,
+    minimum: Option<Number>,
+    maximum: Option<Number>,
+    deprecated: bool,
+}
+
+impl DataDictItem {
+    /// Add the `examples`, `pattern`, `minimum`, `maximum` and `deprecated` keywords to a JSON schema object.
+    fn annotate(&self, schema: &mut Map<String, Value>) {
+        if !self.examples.is_empty() {
+            schema.insert(String::from("examples"), json!(self.examples));
+        }
+        if let Some(pattern) = &self.pattern {
+            schema.insert(String::from("pattern"), json!(pattern));
+        }
+        if let Some(minimum) = &self.minimum {
+            schema.insert(String::from("minimum"), json!(minimum));
+        }
+        if let Some(maximum) = &self.maximum {
+            schema.insert(String::from("maximum"), json!(maximum));
+        }
+        if self.deprecated {
+            schema.insert(String::from("deprecated"), json!(true));
+        }
+    }
+}
+
+/// The serialized forms of a `DataDictItem`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DataDictItemRepr {
+    Description(String),
+    Item {
+        title: Option<String>,
+        description: Option<String>,
+        #[serde(default)]
+        examples: Vec<Value>,
+        pattern: Option<String>,
+        minimum: Option<Number>,
+        maximum: Option<Number>,
+        #[serde(default)]
+        deprecated: bool,
+    },
+}
+
+impl From<DataDictItemRepr> for DataDictItem {
+    fn from(repr: DataDictItemRepr) -> Self {
+        match repr {
+            DataDictItemRepr::Description(description) => Self {
+                description: Some(description),
+                ..Self::default()
+            },
+            DataDictItemRepr::Item {
+                title,
+                description,
+                examples,
+                pattern,
+                minimum,
+                maximum,
+                deprecated,
+            } => Self {
+                title,
+                description,
+                examples,
+                pattern,
+                minimum,
+                maximum,
+                deprecated,
+            },
+        }
+    }
 }
 
 /// The JSON schema version.
@@ -229,6 +338,24 @@ impl SchemaEncoderConfig {
     }
 }
 
+/// The JSON schemas for a Daml template.
+///
+/// This comprises the schemas for the `create` payload of the template, the contract key, if the template has one,
+/// and the argument and return type of each template choice, keyed by choice name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamlJsonTemplateSchema {
+    pub create_payload: Value,
+    pub key: Option<Value>,
+    pub choices: BTreeMap<String, DamlJsonChoiceSchema>,
+}
+
+/// The JSON schemas for the argument and return type of a Daml template choice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamlJsonChoiceSchema {
+    pub argument: Value,
+    pub return_type: Value,
+}
+
 /// Encode a `DamlArchive` as a JSON schema.
 ///
 /// Generate [JSON Schema](https://json-schema.org/) from Daml LF using the `draft/2020-12/schema` version of the
@@ -268,6 +395,70 @@ impl<'a> JsonSchemaEncoder<'a> {
             .unwrap_or_else(|| Err(NotSerializableDamlType(data.name().to_owned())))
     }
 
+    /// Encode the `create` payload, contract key and choice argument and return type of a `DamlTemplate` as JSON
+    /// schema.
+    pub fn encode_template(&self, template: &DamlTemplate<'_>) -> DamlJsonSchemaCodecResult<DamlJsonTemplateSchema> {
+        let create_payload =
+            self.do_encode_record(template.name(), template.module_path(), template.fields(), &[], &[])?;
+        let key = template.key().map(|key| self.encode_type(key.ty())).transpose()?;
+        let choices = template
+            .choices()
+            .iter()
+            .map(|choice| Ok((choice.name().to_owned(), self.encode_choice(choice)?)))
+            .collect::<DamlJsonSchemaCodecResult<BTreeMap<_, _>>>()?;
+        Ok(DamlJsonTemplateSchema {
+            create_payload,
+            key,
+            choices,
+        })
+    }
+
+    /// Encode the argument and return type of a `DamlChoice` as JSON schema.
+    ///
+    /// The choice argument is a record with the same name as the choice which contains the choice fields.
+    pub fn encode_choice(&self, choice: &DamlChoice<'_>) -> DamlJsonSchemaCodecResult<DamlJsonChoiceSchema> {
+        Ok(DamlJsonChoiceSchema {
+            argument: self.do_encode_record(choice.name(), choice.module_path(), choice.fields(), &[], &[])?,
+            return_type: self.encode_type(choice.return_type())?,
+        })
+    }
+
+    /// Bundle the schemas of all data types referenced from a JSON schema as `$defs`.
+    ///
+    /// In `ReferenceMode::Reference` mode nested `DamlTyCon` are encoded as a `$ref` to a schema which is assumed to
+    /// be made available elsewhere under the configured `prefix`.  This encodes the schema of every data type which is
+    /// referenced, directly or indirectly, from `schema` and adds them to the `$defs` of `schema` such that it is
+    /// self-contained.  For this to be valid the `prefix` must be `#/$defs/`.
+    ///
+    /// In `ReferenceMode::Inline` mode there are no references and so the `schema` is returned unchanged.
+    pub fn bundle(&self, schema: Value) -> DamlJsonSchemaCodecResult<Value> {
+        match &self.config.reference_mode {
+            ReferenceMode::Inline => Ok(schema),
+            ReferenceMode::Reference {
+                prefix,
+            } => {
+                let mut defs = BTreeMap::new();
+                let mut pending = vec![];
+                Self::collect_references(&schema, prefix, &mut pending);
+                while let Some(name) = pending.pop() {
+                    if let Entry::Vacant(entry) = defs.entry(name) {
+                        let data = self
+                            .find_referenced_data(entry.key())
+                            .ok_or_else(|| DamlJsonSchemaCodecError::DataNotFound(entry.key().clone()))?;
+                        let def = self.encode_data(data)?;
+                        Self::collect_references(&def, prefix, &mut pending);
+                        entry.insert(def);
+                    }
+                }
+                let mut schema = schema;
+                if let Some(obj) = schema.as_object_mut().filter(|_| !defs.is_empty()) {
+                    obj.insert(String::from("$defs"), json!(defs));
+                }
+                Ok(schema)
+            },
+        }
+    }
+
     /// Encode a Daml `Unit` type as JSON schema.
     ///
     /// A Daml LF `Unit` type is [encoded](https://docs.daml.com/json-api/lf-value-specification.html#unit) as an empty
@@ -870,11 +1061,11 @@ impl<'a> JsonSchemaEncoder<'a> {
                 type_args,
             ),
             DamlType::Nat(_)
-            | DamlType::Arrow
+            | DamlType::Arrow(_)
             | DamlType::Any
             | DamlType::TypeRep
-            | DamlType::Update
-            | DamlType::Scenario
+            | DamlType::Update(_)
+            | DamlType::Scenario(_)
             | DamlType::Forall(_)
             | DamlType::Struct(_)
             | DamlType::Syn(_)
@@ -964,7 +1155,7 @@ impl<'a> JsonSchemaEncoder<'a> {
             .iter()
             .map(|field| {
                 self.do_encode_type(field.ty(), true, type_params, type_args)
-                    .map(|json_val| self.update_desc_from_data_dict(json_val, data_item_path, field.name()))
+                    .map(|json_val| self.annotate_from_data_dict(json_val, data_item_path, field.name()))
             })
             .collect::<DamlJsonSchemaCodecResult<BTreeMap<&str, Value>>>()?;
         let required = fields
@@ -1014,19 +1205,11 @@ impl<'a> JsonSchemaEncoder<'a> {
         type_params: &[DamlTypeVarWithKind<'a>],
         type_args: &[DamlType<'_>],
     ) -> DamlJsonSchemaCodecResult<Value> {
-        let description = if let Some(DataDictEntry {
-            items: fields,
-            ..
-        }) = self.config.data_dict.0.get(data_item_path)
-        {
-            fields.get(daml_field.name()).map(AsRef::as_ref)
-        } else {
-            None
-        };
-        Ok(serde_json::to_value(DamlJsonSchemaVariantArm {
+        let item = self.get_data_dict_item(data_item_path, daml_field.name());
+        let arm = serde_json::to_value(DamlJsonSchemaVariantArm {
             ty: "object",
-            title: Some(daml_field.name()),
-            description: description.or(self.description_if_all(&format!(
+            title: item.and_then(|item| item.title.as_deref()).or(Some(daml_field.name())),
+            description: item.and_then(|item| item.description.as_deref()).or(self.description_if_all(&format!(
                 "Variant ({}, tag={})",
                 name,
                 daml_field.name()
@@ -1039,25 +1222,21 @@ impl<'a> JsonSchemaEncoder<'a> {
             ),
             required: vec!["tag", "value"],
             additional_properties: false,
-        })?)
+        })?;
+        Ok(Self::annotate_item(arm, item))
     }
 
     fn encode_enum_entry(&self, name: &str, data_item_path: &str, entry: &str) -> DamlJsonSchemaCodecResult<Value> {
-        let description = if let Some(DataDictEntry {
-            items: fields,
-            ..
-        }) = self.config.data_dict.0.get(data_item_path)
-        {
-            fields.get(entry).map(AsRef::as_ref)
-        } else {
-            None
-        };
-        Ok(serde_json::to_value(DamlJsonSchemaEnumEntry {
+        let item = self.get_data_dict_item(data_item_path, entry);
+        let enum_entry = serde_json::to_value(DamlJsonSchemaEnumEntry {
             ty: "string",
-            title: Some(entry),
-            description: description.or(self.description_if_all(&format!("Enum ({}, tag={})", name, entry))),
+            title: item.and_then(|item| item.title.as_deref()).or(Some(entry)),
+            description: item
+                .and_then(|item| item.description.as_deref())
+                .or(self.description_if_all(&format!("Enum ({}, tag={})", name, entry))),
             data_enum: vec![entry],
-        })?)
+        })?;
+        Ok(Self::annotate_item(enum_entry, item))
     }
 
     ///
@@ -1086,6 +1265,40 @@ impl<'a> JsonSchemaEncoder<'a> {
         )
     }
 
+    /// Collect the names of all data types referenced by `$ref` from `prefix` in a JSON schema.
+    fn collect_references(schema: &Value, prefix: &str, references: &mut Vec<String>) {
+        match schema {
+            Value::Object(obj) => {
+                if let Some(name) = obj.get("$ref").and_then(Value::as_str).and_then(|r| r.strip_prefix(prefix)) {
+                    references.push(name.to_owned());
+                }
+                obj.values().for_each(|value| Self::collect_references(value, prefix, references));
+            },
+            Value::Array(items) => items.iter().for_each(|value| Self::collect_references(value, prefix, references)),
+            _ => {},
+        }
+    }
+
+    /// Find the `DamlData` for a reference name of the form `Module.Path.Entity`, preferring the main package.
+    fn find_referenced_data(&self, name: &str) -> Option<&DamlData<'_>> {
+        fn find_in_module<'m>(module: &'m DamlModule<'m>, name: &str) -> Option<&'m DamlData<'m>> {
+            let module_path = module.path().join(".");
+            module
+                .data_types()
+                .find(|data| format!("{}.{}", module_path, data.name()) == name)
+                .or_else(|| module.child_modules().find_map(|child| find_in_module(child, name)))
+        }
+        let main_package = self.arc.main_package();
+        main_package
+            .into_iter()
+            .chain(
+                self.arc
+                    .packages()
+                    .filter(|package| Some(package.package_id()) != main_package.map(DamlPackage::package_id)),
+            )
+            .find_map(|package| find_in_module(package.root_module(), name))
+    }
+
     /// Resolve a `DamlTyCon` to a `DamlData` from the archive.
     fn resolve_tycon(&self, tycon: &DamlTyCon<'_>) -> DamlJsonSchemaCodecResult<&DamlData<'_>> {
         self.arc.data_by_tycon(tycon).ok_or_else(|| DamlJsonSchemaCodecError::DataNotFound(tycon.tycon().to_string()))
@@ -1120,26 +1333,42 @@ impl<'a> JsonSchemaEncoder<'a> {
         }
     }
 
-    /// Update the `description` in a `json_val` if this data item and field is defined in the `DataDict`.
-    fn update_desc_from_data_dict<'f>(
+    /// Update the `title`, `description` and validation keywords in a `json_val` if this data item and field is
+    /// defined in the `DataDict`.
+    fn annotate_from_data_dict<'f>(
         &self,
         json_val: Value,
         data_item_path: &str,
         field_name: &'f str,
     ) -> (&'f str, Value) {
         let mut json_val = json_val;
-        if let Some(DataDictEntry {
-            items: fields,
-            ..
-        }) = self.config.data_dict.0.get(data_item_path)
-        {
-            if let Some(desc) = fields.get(field_name) {
-                json_val.as_object_mut().unwrap().insert(String::from("description"), json!(desc));
+        if let Some(item) = self.get_data_dict_item(data_item_path, field_name) {
+            let obj = json_val.as_object_mut().unwrap();
+            if let Some(title) = &item.title {
+                obj.insert(String::from("title"), json!(title));
+            }
+            if let Some(desc) = &item.description {
+                obj.insert(String::from("description"), json!(desc));
             }
+            item.annotate(obj);
         }
         (field_name, json_val)
     }
 
+    /// Add the validation keywords of a `DataDictItem`, if any, to a variant arm or enum entry `json_val`.
+    fn annotate_item(json_val: Value, item: Option<&DataDictItem>) -> Value {
+        let mut json_val = json_val;
+        if let (Some(item), Some(obj)) = (item, json_val.as_object_mut()) {
+            item.annotate(obj);
+        }
+        json_val
+    }
+
+    /// Lookup the `DataDictItem` for a given item (field or constructor) of a data item in the data dictionary.
+    fn get_data_dict_item(&self, data_item_path: &str, item_name: &str) -> Option<&DataDictItem> {
+        self.config.data_dict.0.get(data_item_path).and_then(|entry| entry.items.get(item_name))
+    }
+
     /// Lookup the data dictionary for a given key and resolve `title` and `description` if they are defined.
     fn get_title_and_description(&self, key: &str) -> (Option<&str>, Option<&str>) {
         match self.config.data_dict.0.get(key).as_ref() {
@@ -1462,6 +1691,61 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_encode_template() -> DamlJsonSchemaCodecResult<()> {
+        let arc = daml_archive();
+        let template = find_template(arc, &["Fuji", "PingPong"], "Ping");
+        let actual = JsonSchemaEncoder::new(arc).encode_template(template)?;
+        assert_json_eq!(actual.create_payload, get_expected!("test_template.json")?);
+        assert_eq!(actual.choices.keys().map(String::as_str).collect::<Vec<_>>(), vec![
+            "Archive",
+            "FromUserData",
+            "ResetPingCount",
+            "RespondPong"
+        ]);
+        let key = actual.key.unwrap_or_default();
+        assert_eq!(key["title"], json!("Fuji.PingPong:PingPongKey"));
+        assert_eq!(key["oneOf"][0]["required"], json!(["sender", "count"]));
+        let from_user_data = &actual.choices["FromUserData"];
+        assert_eq!(from_user_data.argument["title"], json!("Fuji.PingPong:FromUserData"));
+        assert_eq!(from_user_data.argument["oneOf"][0]["required"], json!(["new_count", "new_data"]));
+        assert_json_eq!(from_user_data.return_type, get_expected!("test_unit.json")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundle_template_choice() -> Result<()> {
+        let arc = daml_archive();
+        let template = find_template(arc, &["Fuji", "PingPong"], "Ping");
+        let config = get_schema_config(
+            ReferenceMode::Reference {
+                prefix: "#/$defs/".to_string(),
+            },
+            DataDict::default(),
+        );
+        let encoder = JsonSchemaEncoder::new_with_config(arc, config);
+        let argument = encoder.encode_template(template)?.choices["FromUserData"].argument.clone();
+        let bundled = encoder.bundle(argument)?;
+        assert_eq!(
+            bundled["$defs"].as_object().map(|defs| defs.keys().cloned().collect::<Vec<_>>()),
+            Some(vec![String::from("Fuji.PingPong.UserData")])
+        );
+        let compiled = JSONSchema::compile(&bundled).map_err(|e| anyhow!("failed to compile schema: {e}"))?;
+        assert!(compiled.is_valid(&json!({"new_count": 1, "new_data": {"name": "Alice", "new_value": 2}})));
+        assert!(!compiled.is_valid(&json!({"new_count": 1, "new_data": {"name": "Alice", "new_value": true}})));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundle_inline() -> DamlJsonSchemaCodecResult<()> {
+        let arc = daml_archive();
+        let template = find_template(arc, &["Fuji", "PingPong"], "Ping");
+        let encoder = JsonSchemaEncoder::new_with_config(arc, get_schema_config_inline());
+        let argument = encoder.encode_template(template)?.choices["FromUserData"].argument.clone();
+        assert_json_eq!(encoder.bundle(argument.clone())?, argument);
+        Ok(())
+    }
+
     /// Covers case 2 from `ReferenceMode` (inline, non-recursive, with type parameters)
     #[test]
     fn test_reference_mode_case_2() -> DamlJsonSchemaCodecResult<()> {
@@ -1594,6 +1878,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_record_datadict_extended() -> DamlJsonSchemaCodecResult<()> {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Person");
+        let expected = get_expected!("test_record_datadict_extended.json")?;
+        let datadict = get_datadict!("datadict_extended.yaml").unwrap();
+        let config = get_schema_config(ReferenceMode::Inline, datadict);
+        let actual = JsonSchemaEncoder::new_with_config(arc, config).encode_type(&ty)?;
+        assert_json_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_enum_datadict_extended() -> DamlJsonSchemaCodecResult<()> {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "Vehicle"], "SimpleColor");
+        let expected = get_expected!("test_enum_datadict_extended.json")?;
+        let datadict = get_datadict!("datadict_extended.yaml").unwrap();
+        let config = get_schema_config(ReferenceMode::Inline, datadict);
+        let actual = JsonSchemaEncoder::new_with_config(arc, config).encode_type(&ty)?;
+        assert_json_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_variant_datadict_extended() -> DamlJsonSchemaCodecResult<()> {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "Shape"], "Color");
+        let expected = get_expected!("test_variant_datadict_extended.json")?;
+        let datadict = get_datadict!("datadict_extended.yaml").unwrap();
+        let config = get_schema_config(ReferenceMode::Inline, datadict);
+        let actual = JsonSchemaEncoder::new_with_config(arc, config).encode_type(&ty)?;
+        assert_json_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_datadict_extended_validation() -> Result<()> {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Person");
+        let datadict = get_datadict!("datadict_extended.yaml")?;
+        let config = get_schema_config(ReferenceMode::Inline, datadict);
+        let schema = JsonSchemaEncoder::new_with_config(arc, config).encode_type(&ty)?;
+        let compiled = JSONSchema::compile(&schema).map_err(|e| anyhow!("failed to compile schema: {e}"))?;
+        assert!(compiled.is_valid(&json!({"name": "Alice Smith", "age": 42})));
+        assert!(compiled.is_valid(&json!({"name": "Alice Smith", "age": "200"})));
+        assert!(!compiled.is_valid(&json!({"name": "alice", "age": 42})));
+        assert!(!compiled.is_valid(&json!({"name": "Alice Smith", "age": 200})));
+        assert!(!compiled.is_valid(&json!({"name": "Alice Smith", "age": -1})));
+        Ok(())
+    }
+
     #[test]
     fn test_fail_for_non_serializable_record() -> Result<()> {
         let arc = daml_archive();
@@ -2024,6 +2360,17 @@ mod tests {
         )
     }
 
+    fn find_template<'a>(arc: &'a DamlArchive<'a>, module_path: &[&str], name: &str) -> &'a DamlTemplate<'a> {
+        let ty = DamlType::make_tycon(arc.main_package_id(), module_path, name);
+        match ty {
+            DamlType::TyCon(tycon) => match arc.data_by_tycon(&tycon) {
+                Some(DamlData::Template(template)) => template,
+                _ => panic!("expected template {name}"),
+            },
+            _ => unreachable!(),
+        }
+    }
+
     fn daml_archive() -> &'static DamlArchive<'static> {
         crate::test_util::daml_archive(TESTING_TYPES_DAR_PATH)
     }
@@ -1,19 +1,30 @@
-use crate::data::{DamlJsonCreatedEvent, DamlJsonExerciseResult, DamlJsonParty, DamlJsonQuery};
+use crate::data::{
+    DamlJsonCreatedEvent, DamlJsonExerciseResult, DamlJsonMeteringReport, DamlJsonParty, DamlJsonQuery, DamlJsonUser,
+    DamlJsonUserRight,
+};
 use crate::error::{DamlJsonError, DamlJsonResult};
+use crate::query::Query;
 use crate::request::{
-    DamlJsonAllocatePartyRequest, DamlJsonAllocatePartyResponse, DamlJsonCreateAndExerciseRequest,
-    DamlJsonCreateAndExerciseResponse, DamlJsonCreateRequest, DamlJsonCreateResponse, DamlJsonErrorResponse,
-    DamlJsonExerciseByKeyRequest, DamlJsonExerciseByKeyResponse, DamlJsonExerciseRequest, DamlJsonExerciseResponse,
-    DamlJsonFetchByKeyRequest, DamlJsonFetchPartiesRequest, DamlJsonFetchPartiesResponse, DamlJsonFetchRequest,
-    DamlJsonFetchResponse, DamlJsonListPackagesResponse, DamlJsonQueryResponse, DamlJsonRequestMeta,
-    DamlJsonUploadDarResponse,
+    DamlJsonAllocatePartyRequest, DamlJsonAllocatePartyResponse, DamlJsonBatchCommand, DamlJsonBatchRequest,
+    DamlJsonBatchResponse, DamlJsonBatchResult, DamlJsonCreateAndExerciseRequest, DamlJsonCreateAndExerciseResponse,
+    DamlJsonCreateRequest, DamlJsonCreateResponse, DamlJsonCreateUserRequest, DamlJsonCreateUserResponse,
+    DamlJsonDeleteUserRequest, DamlJsonDeleteUserResponse, DamlJsonErrorResponse, DamlJsonExerciseByKeyRequest,
+    DamlJsonExerciseByKeyResponse, DamlJsonExerciseRequest, DamlJsonExerciseResponse, DamlJsonFetchByKeyRequest,
+    DamlJsonFetchPartiesRequest, DamlJsonFetchPartiesResponse, DamlJsonFetchRequest, DamlJsonFetchResponse,
+    DamlJsonFetchUserRequest, DamlJsonFetchUserResponse, DamlJsonGrantUserRightsRequest, DamlJsonListPackagesResponse,
+    DamlJsonListUserRightsRequest, DamlJsonListUsersResponse, DamlJsonMeteringReportRequest,
+    DamlJsonMeteringReportResponse, DamlJsonQueryResponse, DamlJsonRequestMeta, DamlJsonRevokeUserRightsRequest,
+    DamlJsonUploadDarResponse, DamlJsonUserRightsResponse,
 };
+use crate::typed::{DamlJsonContract, DamlJsonTemplate, DamlJsonTypedExerciseResult};
 use crate::util::Required;
 use bytes::Bytes;
+use chrono::NaiveDate;
 use reqwest::{Certificate, Client, ClientBuilder, RequestBuilder, Response};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::Value;
+use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::time::{Duration, Instant};
 use tracing::{instrument, trace};
@@ -22,11 +33,20 @@ use url::Url;
 static CREATE_REST: &str = "/v1/create";
 static EXERCISE_REST: &str = "/v1/exercise";
 static CREATE_AND_EXERCISE_REST: &str = "/v1/create-and-exercise";
+static BATCH_REST: &str = "/v1/batch";
 static FETCH_REST: &str = "/v1/fetch";
 static QUERY_REST: &str = "/v1/query";
 static PARTIES_REST: &str = "/v1/parties";
 static ALLOCATE_PARTY_REST: &str = "/v1/parties/allocate";
 static PACKAGES_REST: &str = "/v1/packages";
+static USER_REST: &str = "/v1/user";
+static CREATE_USER_REST: &str = "/v1/user/create";
+static DELETE_USER_REST: &str = "/v1/user/delete";
+static USERS_REST: &str = "/v1/users";
+static USER_RIGHTS_REST: &str = "/v1/user/rights";
+static GRANT_USER_RIGHTS_REST: &str = "/v1/user/rights/grant";
+static REVOKE_USER_RIGHTS_REST: &str = "/v1/user/rights/revoke";
+static METERING_REPORT_REST: &str = "/v1/metering-report";
 
 const DEFAULT_TIMEOUT_SECS: u64 = 5;
 
@@ -41,6 +61,7 @@ pub struct DamlJsonClientConfig {
     max_idle_connection_per_host: usize,
     tls_config: Option<DamlJsonTlsConfig>,
     auth_token: Option<String>,
+    verbose: bool,
 }
 
 /// Daml JSON client TLS configuration.
@@ -139,6 +160,18 @@ impl DamlJsonClientBuilder {
         }
     }
 
+    /// Request the full transaction tree for all exercise results.
+    ///
+    /// Note that this is an extension of the Daml JSON API which is only supported by `daml-bridge`.
+    pub fn verbose(self) -> Self {
+        Self {
+            config: DamlJsonClientConfig {
+                verbose: true,
+                ..self.config
+            },
+        }
+    }
+
     /// Build the [`DamlJsonClient`] from the configuration.
     pub fn build(self) -> DamlJsonResult<DamlJsonClient> {
         DamlJsonClient::new_from_config(self.config)
@@ -271,6 +304,14 @@ impl DamlJsonClient {
             .result)
     }
 
+    /// Submit a batch of commands as a single atomic transaction and return the result of each command, in order.
+    ///
+    /// Note that the `/v1/batch` endpoint is provided by `daml-bridge` and is not part of the Daml JSON API.
+    #[instrument(skip(self))]
+    pub async fn batch(&self, commands: Vec<DamlJsonBatchCommand>) -> DamlJsonResult<Vec<DamlJsonBatchResult>> {
+        Ok(self.batch_request(&DamlJsonBatchRequest::new(commands)).await?.result)
+    }
+
     /// Fetch a `Daml` contract by id.
     #[instrument(skip(self))]
     pub async fn fetch(&self, contract_id: &str) -> DamlJsonResult<DamlJsonCreatedEvent> {
@@ -300,6 +341,72 @@ impl DamlJsonClient {
         Ok(self.query_request(&DamlJsonQuery::new(templates, query)).await?.result)
     }
 
+    /// List currently active contracts that match a [`Query`].
+    ///
+    /// The query may be checked against a Daml archive before it is executed, see [`Query::check`].
+    #[instrument(skip(self))]
+    pub async fn execute_query(&self, query: Query) -> DamlJsonResult<Vec<DamlJsonCreatedEvent>> {
+        Ok(self.query_request(&query.build()?).await?.result)
+    }
+
+    /// Create a new `Daml` contract from a typed template payload.
+    #[instrument(skip(self, payload))]
+    pub async fn create_typed<T: DamlJsonTemplate>(&self, payload: &T) -> DamlJsonResult<DamlJsonContract<T>> {
+        let created = self.create(T::TEMPLATE_ID, serde_json::to_value(payload)?).await?;
+        Ok(DamlJsonContract::try_from(created)?)
+    }
+
+    /// Exercise a `Daml` choice by contract id with a typed argument and result.
+    #[instrument(skip(self, argument))]
+    pub async fn exercise_typed<T: DamlJsonTemplate, A: Serialize, R: DeserializeOwned>(
+        &self,
+        contract_id: &str,
+        choice: &str,
+        argument: &A,
+    ) -> DamlJsonResult<DamlJsonTypedExerciseResult<R>> {
+        let result = self.exercise(T::TEMPLATE_ID, contract_id, choice, serde_json::to_value(argument)?).await?;
+        Ok(DamlJsonTypedExerciseResult::try_from(result)?)
+    }
+
+    /// Exercise a `Daml` choice by contract key with a typed key, argument and result.
+    #[instrument(skip(self, key, argument))]
+    pub async fn exercise_by_key_typed<T: DamlJsonTemplate, K: Serialize, A: Serialize, R: DeserializeOwned>(
+        &self,
+        key: &K,
+        choice: &str,
+        argument: &A,
+    ) -> DamlJsonResult<DamlJsonTypedExerciseResult<R>> {
+        let result = self
+            .exercise_by_key(T::TEMPLATE_ID, serde_json::to_value(key)?, choice, serde_json::to_value(argument)?)
+            .await?;
+        Ok(DamlJsonTypedExerciseResult::try_from(result)?)
+    }
+
+    /// Fetch a typed `Daml` contract by id.
+    #[instrument(skip(self))]
+    pub async fn fetch_typed<T: DamlJsonTemplate>(&self, contract_id: &str) -> DamlJsonResult<DamlJsonContract<T>> {
+        Ok(DamlJsonContract::try_from(self.fetch(contract_id).await?)?)
+    }
+
+    /// Fetch a typed `Daml` contract by key.
+    #[instrument(skip(self, key))]
+    pub async fn fetch_by_key_typed<T: DamlJsonTemplate, K: Serialize>(
+        &self,
+        key: &K,
+    ) -> DamlJsonResult<DamlJsonContract<T>> {
+        Ok(DamlJsonContract::try_from(self.fetch_by_key(T::TEMPLATE_ID, serde_json::to_value(key)?).await?)?)
+    }
+
+    /// List currently active typed contracts of a template that match a given query.
+    #[instrument(skip(self))]
+    pub async fn query_typed<T: DamlJsonTemplate>(&self, query: Value) -> DamlJsonResult<Vec<DamlJsonContract<T>>> {
+        self.query(vec![T::TEMPLATE_ID], query)
+            .await?
+            .into_iter()
+            .map(|created| Ok(DamlJsonContract::try_from(created)?))
+            .collect()
+    }
+
     /// Fetch `Daml` Parties by identifiers.
     ///
     /// Retrieve the [`DamlJsonParty`] entries for the given `parties` identifiers.  Unknown parties are silently
@@ -363,6 +470,93 @@ impl DamlJsonClient {
         Ok(())
     }
 
+    /// Create a User with an optional primary party and initial rights.
+    #[instrument(skip(self))]
+    pub async fn create_user(
+        &self,
+        user_id: &str,
+        primary_party: Option<&str>,
+        rights: Vec<DamlJsonUserRight>,
+    ) -> DamlJsonResult<()> {
+        self.create_user_request(&DamlJsonCreateUserRequest::new(user_id, primary_party, rights)).await?;
+        Ok(())
+    }
+
+    /// Fetch the User associated with the authorization token of this client.
+    #[instrument(skip(self))]
+    pub async fn fetch_current_user(&self) -> DamlJsonResult<DamlJsonUser> {
+        Ok(self.fetch_current_user_request().await?.result)
+    }
+
+    /// Fetch a User by identifier.
+    #[instrument(skip(self))]
+    pub async fn fetch_user(&self, user_id: &str) -> DamlJsonResult<DamlJsonUser> {
+        Ok(self.fetch_user_request(&DamlJsonFetchUserRequest::new(user_id)).await?.result)
+    }
+
+    /// Delete a User by identifier.
+    #[instrument(skip(self))]
+    pub async fn delete_user(&self, user_id: &str) -> DamlJsonResult<()> {
+        self.delete_user_request(&DamlJsonDeleteUserRequest::new(user_id)).await?;
+        Ok(())
+    }
+
+    /// List all Users.
+    #[instrument(skip(self))]
+    pub async fn list_users(&self) -> DamlJsonResult<Vec<DamlJsonUser>> {
+        Ok(self.list_users_request().await?.result)
+    }
+
+    /// Grant rights to a User.
+    ///
+    /// Returns the rights which were newly granted, rights which the User already held are not included.
+    #[instrument(skip(self))]
+    pub async fn grant_user_rights(
+        &self,
+        user_id: &str,
+        rights: Vec<DamlJsonUserRight>,
+    ) -> DamlJsonResult<Vec<DamlJsonUserRight>> {
+        Ok(self.grant_user_rights_request(&DamlJsonGrantUserRightsRequest::new(user_id, rights)).await?.result)
+    }
+
+    /// Revoke rights from a User.
+    ///
+    /// Returns the rights which were revoked, rights which the User did not hold are not included.
+    #[instrument(skip(self))]
+    pub async fn revoke_user_rights(
+        &self,
+        user_id: &str,
+        rights: Vec<DamlJsonUserRight>,
+    ) -> DamlJsonResult<Vec<DamlJsonUserRight>> {
+        Ok(self.revoke_user_rights_request(&DamlJsonRevokeUserRightsRequest::new(user_id, rights)).await?.result)
+    }
+
+    /// List the rights of the User associated with the authorization token of this client.
+    #[instrument(skip(self))]
+    pub async fn list_current_user_rights(&self) -> DamlJsonResult<Vec<DamlJsonUserRight>> {
+        Ok(self.list_current_user_rights_request().await?.result)
+    }
+
+    /// List the rights of a User.
+    #[instrument(skip(self))]
+    pub async fn list_user_rights(&self, user_id: &str) -> DamlJsonResult<Vec<DamlJsonUserRight>> {
+        Ok(self.list_user_rights_request(&DamlJsonListUserRightsRequest::new(user_id)).await?.result)
+    }
+
+    /// Generate a participant metering report.
+    ///
+    /// The report covers the period starting at `from` (inclusive) and, if provided, ending at `to` (exclusive).  If
+    /// an `application` is provided then the report is restricted to that application.
+    #[instrument(skip(self))]
+    pub async fn metering_report(
+        &self,
+        from: NaiveDate,
+        to: Option<NaiveDate>,
+        application: Option<&str>,
+    ) -> DamlJsonResult<DamlJsonMeteringReport> {
+        Ok(self.metering_report_request(&DamlJsonMeteringReportRequest::new(from, to, application)).await?.result)
+    }
+
     #[instrument(skip(self))]
     async fn create_request(&self, request: &DamlJsonCreateRequest) -> DamlJsonResult<DamlJsonCreateResponse> {
         self.post_json(Self::url(&self.config.url, CREATE_REST)?, request).await
@@ -370,7 +564,7 @@ impl DamlJsonClient {
 
     #[instrument(skip(self))]
     async fn exercise_request(&self, request: &DamlJsonExerciseRequest) -> DamlJsonResult<DamlJsonExerciseResponse> {
-        self.post_json(Self::url(&self.config.url, EXERCISE_REST)?, request).await
+        self.post_json(self.exercise_url(EXERCISE_REST)?, request).await
     }
 
     #[instrument(skip(self))]
@@ -378,7 +572,7 @@ impl DamlJsonClient {
         &self,
         request: &DamlJsonExerciseByKeyRequest,
     ) -> DamlJsonResult<DamlJsonExerciseByKeyResponse> {
-        self.post_json(Self::url(&self.config.url, EXERCISE_REST)?, request).await
+        self.post_json(self.exercise_url(EXERCISE_REST)?, request).await
     }
 
     #[instrument(skip(self))]
@@ -386,7 +580,12 @@ impl DamlJsonClient {
         &self,
         request: &DamlJsonCreateAndExerciseRequest,
     ) -> DamlJsonResult<DamlJsonCreateAndExerciseResponse> {
-        self.post_json(Self::url(&self.config.url, CREATE_AND_EXERCISE_REST)?, request).await
+        self.post_json(self.exercise_url(CREATE_AND_EXERCISE_REST)?, request).await
+    }
+
+    #[instrument(skip(self))]
+    async fn batch_request(&self, request: &DamlJsonBatchRequest) -> DamlJsonResult<DamlJsonBatchResponse> {
+        self.post_json(Self::url(&self.config.url, BATCH_REST)?, request).await
     }
 
     #[instrument(skip(self))]
@@ -445,6 +644,77 @@ impl DamlJsonClient {
         self.post_bytes(Self::url(&self.config.url, PACKAGES_REST)?, bytes).await
     }
 
+    #[instrument(skip(self))]
+    async fn create_user_request(
+        &self,
+        request: &DamlJsonCreateUserRequest,
+    ) -> DamlJsonResult<DamlJsonCreateUserResponse> {
+        self.post_json(Self::url(&self.config.url, CREATE_USER_REST)?, request).await
+    }
+
+    #[instrument(skip(self))]
+    async fn fetch_current_user_request(&self) -> DamlJsonResult<DamlJsonFetchUserResponse> {
+        self.get_json(Self::url(&self.config.url, USER_REST)?).await
+    }
+
+    #[instrument(skip(self))]
+    async fn fetch_user_request(
+        &self,
+        request: &DamlJsonFetchUserRequest,
+    ) -> DamlJsonResult<DamlJsonFetchUserResponse> {
+        self.post_json(Self::url(&self.config.url, USER_REST)?, request).await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_user_request(
+        &self,
+        request: &DamlJsonDeleteUserRequest,
+    ) -> DamlJsonResult<DamlJsonDeleteUserResponse> {
+        self.post_json(Self::url(&self.config.url, DELETE_USER_REST)?, request).await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_users_request(&self) -> DamlJsonResult<DamlJsonListUsersResponse> {
+        self.get_json(Self::url(&self.config.url, USERS_REST)?).await
+    }
+
+    #[instrument(skip(self))]
+    async fn grant_user_rights_request(
+        &self,
+        request: &DamlJsonGrantUserRightsRequest,
+    ) -> DamlJsonResult<DamlJsonUserRightsResponse> {
+        self.post_json(Self::url(&self.config.url, GRANT_USER_RIGHTS_REST)?, request).await
+    }
+
+    #[instrument(skip(self))]
+    async fn revoke_user_rights_request(
+        &self,
+        request: &DamlJsonRevokeUserRightsRequest,
+    ) -> DamlJsonResult<DamlJsonUserRightsResponse> {
+        self.post_json(Self::url(&self.config.url, REVOKE_USER_RIGHTS_REST)?, request).await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_current_user_rights_request(&self) -> DamlJsonResult<DamlJsonUserRightsResponse> {
+        self.get_json(Self::url(&self.config.url, USER_RIGHTS_REST)?).await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_user_rights_request(
+        &self,
+        request: &DamlJsonListUserRightsRequest,
+    ) -> DamlJsonResult<DamlJsonUserRightsResponse> {
+        self.post_json(Self::url(&self.config.url, USER_RIGHTS_REST)?, request).await
+    }
+
+    #[instrument(skip(self))]
+    async fn metering_report_request(
+        &self,
+        request: &DamlJsonMeteringReportRequest,
+    ) -> DamlJsonResult<DamlJsonMeteringReportResponse> {
+        self.post_json(Self::url(&self.config.url, METERING_REPORT_REST)?, request).await
+    }
+
     #[instrument(skip(self))]
     async fn get_json<R: DeserializeOwned>(&self, url: Url) -> DamlJsonResult<R> {
         let request = self.make_get_request(&url);
@@ -550,4 +820,12 @@ impl DamlJsonClient {
     fn url(base: &str, path: &str) -> DamlJsonResult<Url> {
         Ok(Url::parse(base)?.join(path)?)
     }
+
+    fn exercise_url(&self, path: &str) -> DamlJsonResult<Url> {
+        let mut url = Self::url(&self.config.url, path)?;
+        if self.config.verbose {
+            url.query_pairs_mut().append_pair("verbose", "true");
+        }
+        Ok(url)
+    }
 }
@@ -1,7 +1,23 @@
 use once_cell::sync::OnceCell;
 
-use daml_lf::element::DamlArchive;
+use crate::util::{date_from_days, resolve_scale, timestamp_from_micros, TypeArgs};
+use daml_grpc::data::value::{DamlEnum, DamlRecord, DamlRecordField, DamlValue, DamlVariant};
+use daml_grpc::data::DamlIdentifier;
+use daml_grpc::primitive_types::{DamlGenMap, DamlNumeric, DamlTextMap};
+use daml_lf::element::{DamlArchive, DamlData, DamlField, DamlType};
 use daml_lf::DarFile;
+use proptest::prelude::{prop, Rng, Strategy};
+use proptest::test_runner::{RngAlgorithm, TestRng};
+use std::convert::{TryFrom, TryInto};
+
+/// The days since epoch of the earliest (0001-01-01) and latest (9999-12-31) Daml `Date`.
+const MIN_DATE_DAYS: i32 = -719_162;
+const MAX_DATE_DAYS: i32 = 2_932_896;
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// The maximum number of digits of generated numerics.
+const NUMERIC_MAX_DIGITS: u32 = 12;
 
 /// Load a dar and convert to an owned `DamlArchive`
 pub fn daml_archive(path: &str) -> &'static DamlArchive<'static> {
@@ -11,3 +27,182 @@ pub fn daml_archive(path: &str) -> &'static DamlArchive<'static> {
         dar.to_owned_archive().expect("failed to convert dar to owned archive")
     })
 }
+
+/// Generate an arbitrary `DamlValue` of a given `DamlType` from `rng`.
+///
+/// Timestamps are generated with millisecond precision and numerics within the precision of their scale such that
+/// every generated value can be represented by all value codecs.
+///
+/// Once `depth` is exhausted only the smallest values are generated for types which may recurse, that is empty lists
+/// and maps, `None` for optionals and the first constructor of variants.
+pub fn arbitrary_value<'t>(
+    rng: &mut impl Rng,
+    arc: &'t DamlArchive<'t>,
+    ty: &'t DamlType<'t>,
+    type_args: Option<&'t TypeArgs<'t>>,
+    depth: usize,
+) -> DamlValue {
+    match ty {
+        DamlType::Unit => DamlValue::Unit,
+        DamlType::Bool => DamlValue::new_bool(rng.next_u32() % 2 == 0),
+        DamlType::Int64 => DamlValue::new_int64(i64::from_ne_bytes(rng.next_u64().to_ne_bytes())),
+        DamlType::Numeric(args) =>
+            DamlValue::new_numeric(arbitrary_numeric(rng, resolve_scale(args, type_args).expect("numeric scale"))),
+        DamlType::Text => DamlValue::new_text(arbitrary_text(rng)),
+        DamlType::Party => DamlValue::new_party(format!("party-{}", rng.next_u32() % 100)),
+        DamlType::ContractId(_) =>
+            DamlValue::new_contract_id(format!("#{}:{}", rng.next_u32() % 100, rng.next_u32() % 10)),
+        DamlType::Date => DamlValue::new_date(
+            date_from_days(MIN_DATE_DAYS + arbitrary_below(rng, MAX_DATE_DAYS - MIN_DATE_DAYS)).expect("date in range"),
+        ),
+        DamlType::Timestamp => {
+            let millis = i64::from(MIN_DATE_DAYS) * MILLIS_PER_DAY
+                + arbitrary_below(rng, i64::from(MAX_DATE_DAYS - MIN_DATE_DAYS) * MILLIS_PER_DAY);
+            DamlValue::new_timestamp(timestamp_from_micros(millis * 1000).expect("timestamp in range"))
+        },
+        DamlType::List(tys) => DamlValue::new_list(
+            (0..arbitrary_size(rng, depth))
+                .map(|_| arbitrary_value(rng, arc, &tys[0], type_args, depth - 1))
+                .collect::<Vec<_>>(),
+        ),
+        DamlType::TextMap(tys) => DamlValue::new_map(
+            (0..arbitrary_size(rng, depth))
+                .map(|_| (arbitrary_text(rng), arbitrary_value(rng, arc, &tys[0], type_args, depth - 1)))
+                .collect::<DamlTextMap<_>>(),
+        ),
+        DamlType::GenMap(tys) => DamlValue::new_genmap(
+            (0..arbitrary_size(rng, depth))
+                .map(|_| {
+                    (
+                        arbitrary_value(rng, arc, &tys[0], type_args, depth - 1),
+                        arbitrary_value(rng, arc, &tys[1], type_args, depth - 1),
+                    )
+                })
+                .collect::<DamlGenMap<_, _>>(),
+        ),
+        DamlType::Optional(tys) =>
+            if arbitrary_size(rng, depth) == 0 {
+                DamlValue::new_optional(None)
+            } else {
+                DamlValue::new_optional(Some(arbitrary_value(rng, arc, &tys[0], type_args, depth - 1)))
+            },
+        DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) => {
+            let data = arc.data_by_tycon(tycon).expect("data type");
+            let type_args = TypeArgs::new(data.type_params(), tycon.type_arguments(), type_args);
+            match data {
+                DamlData::Template(template) => arbitrary_record(rng, arc, template.fields(), &type_args, depth),
+                DamlData::Record(record) => arbitrary_record(rng, arc, record.fields(), &type_args, depth),
+                DamlData::Variant(variant) => {
+                    let constructor = if depth == 0 {
+                        &variant.fields()[0]
+                    } else {
+                        &variant.fields()[arbitrary_below(rng, variant.fields().len())]
+                    };
+                    DamlValue::new_variant(DamlVariant::new(
+                        constructor.name(),
+                        Box::new(arbitrary_value(
+                            rng,
+                            arc,
+                            constructor.ty(),
+                            Some(&type_args),
+                            depth.saturating_sub(1),
+                        )),
+                        None::<DamlIdentifier>,
+                    ))
+                },
+                DamlData::Enum(data_enum) => {
+                    let constructors: Vec<_> = data_enum.constructors().collect();
+                    DamlValue::new_enum(DamlEnum::new(
+                        constructors[arbitrary_below(rng, constructors.len())],
+                        None::<DamlIdentifier>,
+                    ))
+                },
+            }
+        },
+        DamlType::Var(var) => {
+            let (resolved, parent) = type_args.and_then(|type_args| type_args.resolve(var)).expect("type variable");
+            arbitrary_value(rng, arc, resolved, parent, depth)
+        },
+        _ => panic!("unsupported type {}", ty.name()),
+    }
+}
+
+fn arbitrary_record<'t>(
+    rng: &mut impl Rng,
+    arc: &'t DamlArchive<'t>,
+    fields: &'t [DamlField<'t>],
+    type_args: &'t TypeArgs<'t>,
+    depth: usize,
+) -> DamlValue {
+    DamlValue::new_record(DamlRecord::new(
+        fields
+            .iter()
+            .map(|field| {
+                DamlRecordField::new(
+                    Some(field.name()),
+                    arbitrary_value(rng, arc, field.ty(), Some(type_args), depth.saturating_sub(1)),
+                )
+            })
+            .collect::<Vec<_>>(),
+        None::<DamlIdentifier>,
+    ))
+}
+
+/// Generate a numeric which can be represented with the given `scale`.
+fn arbitrary_numeric(rng: &mut impl Rng, scale: u8) -> DamlNumeric {
+    let fractional_digits = arbitrary_below(rng, u32::from(scale) + 1);
+    let digits = NUMERIC_MAX_DIGITS.min(38 - u32::from(scale) + fractional_digits);
+    let mantissa = arbitrary_below(rng, 10_i64.pow(digits));
+    let sign = if rng.next_u32() % 2 == 0 {
+        1
+    } else {
+        -1
+    };
+    DamlNumeric::new((sign * mantissa).into(), i64::from(fractional_digits))
+}
+
+/// Generate the size of a list or map, which is always zero once `depth` is exhausted.
+fn arbitrary_size(rng: &mut impl Rng, depth: usize) -> usize {
+    if depth == 0 {
+        0
+    } else {
+        arbitrary_below(rng, 4)
+    }
+}
+
+fn arbitrary_text(rng: &mut impl Rng) -> String {
+    const CHARS: &[char] = &['a', 'Z', '0', ' ', '/', '~', '"', 'é', '😀'];
+    (0..arbitrary_below(rng, 8)).map(|_| CHARS[arbitrary_below(rng, CHARS.len())]).collect()
+}
+
+/// Generate a non-negative number below `bound`.
+fn arbitrary_below<T: TryFrom<u64> + TryInto<u64>>(rng: &mut impl Rng, bound: T) -> T {
+    let bound = bound.try_into().ok().expect("positive bound");
+    T::try_from(rng.next_u64() % bound).ok().expect("number below bound")
+}
+
+/// A strategy which selects one of the data types of the testing types archive for which values can be generated.
+///
+/// Returns the module (within `Fuji`) and name of the data type.
+pub fn arbitrary_data_type() -> impl Strategy<Value = (&'static str, &'static str)> {
+    prop::sample::select(vec![
+        ("VariantExamples", "VariantTemplate"),
+        ("Nested", "NestedTemplate"),
+        ("NumericTest", "Foo"),
+        ("MapTest", "Foo"),
+        ("MapTest", "Bar"),
+        ("JsonTest", "Depth2"),
+        ("JsonTest", "OPerson"),
+        ("JsonTest", "PersonMap"),
+        ("JsonTest", "Foo"),
+        ("JsonTest", "Status"),
+        ("GenericTypes", "TemplateWithGeneric"),
+        ("GenericTypes", "ConcreteVariant"),
+        ("RentDemo", "RentalAgreement"),
+    ])
+}
+
+/// Make a deterministic random number generator from a `seed`.
+pub fn make_rng(seed: u64) -> TestRng {
+    TestRng::from_seed(RngAlgorithm::ChaCha, &[seed.to_le_bytes(), seed.to_be_bytes(), [0; 8], [0; 8]].concat())
+}
@@ -0,0 +1,292 @@
+use crate::data::{DamlJsonCreatedEvent, DamlJsonEvent, DamlJsonExerciseResult, DamlJsonTransactionTree};
+use crate::error::DamlJsonResult;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::de::{DeserializeOwned, Error, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+
+#[doc(hidden)]
+pub use serde;
+
+/// A Daml `Text` in the Daml-LF JSON encoding.
+pub type DamlJsonText = String;
+
+/// A Daml `Party` in the Daml-LF JSON encoding.
+pub type DamlJsonParty = String;
+
+/// A Daml `ContractId` in the Daml-LF JSON encoding.
+pub type DamlJsonContractId = String;
+
+/// A Daml `Bool` in the Daml-LF JSON encoding.
+pub type DamlJsonBool = bool;
+
+/// A Daml `Numeric` in the Daml-LF JSON encoding, encoded as a JSON string.
+pub type DamlJsonNumeric = BigDecimal;
+
+/// A Daml `Date` in the Daml-LF JSON encoding, encoded as a JSON string of the form `YYYY-MM-DD`.
+pub type DamlJsonDate = NaiveDate;
+
+/// A Daml `Timestamp` in the Daml-LF JSON encoding, encoded as an RFC 3339 JSON string.
+pub type DamlJsonTimestamp = DateTime<Utc>;
+
+/// A Daml `List` in the Daml-LF JSON encoding.
+pub type DamlJsonList<T> = Vec<T>;
+
+/// A Daml `TextMap` in the Daml-LF JSON encoding.
+pub type DamlJsonTextMap<T> = BTreeMap<String, T>;
+
+/// A Daml `GenMap` in the Daml-LF JSON encoding, encoded as a JSON array of `[key, value]` arrays.
+pub type DamlJsonGenMap<K, V> = Vec<(K, V)>;
+
+/// A top-level Daml `Optional` in the Daml-LF JSON encoding, encoded as `null` or the value.
+pub type DamlJsonOptional<T> = Option<T>;
+
+/// An untyped value in the Daml-LF JSON encoding.
+pub type DamlJsonValue = Value;
+
+/// A Daml `Int64` in the Daml-LF JSON encoding.
+///
+/// Encoded as a JSON string and decoded from either a JSON string or a JSON number.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DamlJsonInt64(pub i64);
+
+impl From<i64> for DamlJsonInt64 {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<DamlJsonInt64> for i64 {
+    fn from(value: DamlJsonInt64) -> Self {
+        value.0
+    }
+}
+
+impl Serialize for DamlJsonInt64 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for DamlJsonInt64 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Int64Visitor;
+        impl Visitor<'_> for Int64Visitor {
+            type Value = DamlJsonInt64;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("an Int64 as a JSON number or string")
+            }
+
+            fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(DamlJsonInt64(v))
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+                i64::try_from(v).map(DamlJsonInt64).map_err(E::custom)
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map(DamlJsonInt64).map_err(E::custom)
+            }
+        }
+        deserializer.deserialize_any(Int64Visitor)
+    }
+}
+
+/// A Daml `Unit` in the Daml-LF JSON encoding, encoded as an empty JSON object.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DamlJsonUnit;
+
+impl Serialize for DamlJsonUnit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_map(Some(0)).and_then(serde::ser::SerializeMap::end)
+    }
+}
+
+impl<'de> Deserialize<'de> for DamlJsonUnit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct UnitVisitor;
+        impl<'de> Visitor<'de> for UnitVisitor {
+            type Value = DamlJsonUnit;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("a Unit as an empty JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                match map.next_entry::<IgnoredAny, IgnoredAny>()? {
+                    None => Ok(DamlJsonUnit),
+                    Some(_) => Err(A::Error::custom("expected empty record for Unit type")),
+                }
+            }
+        }
+        deserializer.deserialize_map(UnitVisitor)
+    }
+}
+
+/// A nested Daml `Optional` in the Daml-LF JSON encoding.
+///
+/// An `Optional` which is nested within another `Optional` is encoded as an empty JSON array for `None` and as a
+/// single element JSON array for `Some`.  See the
+/// [Daml LF JSON Encoding documentation](https://docs.daml.com/json-api/lf-value-specification.html) for details.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DamlJsonNestedOptional<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for DamlJsonNestedOptional<T> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> From<DamlJsonNestedOptional<T>> for Option<T> {
+    fn from(value: DamlJsonNestedOptional<T>) -> Self {
+        value.0
+    }
+}
+
+impl<T: Serialize> Serialize for DamlJsonNestedOptional<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for DamlJsonNestedOptional<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NestedOptionalVisitor<T>(PhantomData<T>);
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for NestedOptionalVisitor<T> {
+            type Value = DamlJsonNestedOptional<T>;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("a nested Optional as a JSON array with either zero or one entry")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let value = seq.next_element()?;
+                match seq.next_element::<IgnoredAny>()? {
+                    None => Ok(DamlJsonNestedOptional(value)),
+                    Some(_) => Err(A::Error::custom("expected an array with either zero or one entry")),
+                }
+            }
+        }
+        deserializer.deserialize_seq(NestedOptionalVisitor(PhantomData))
+    }
+}
+
+/// Encode a typed value in the Daml-LF JSON encoding.
+pub fn to_json_value<T: Serialize>(value: &T) -> DamlJsonResult<DamlJsonValue> {
+    Ok(serde_json::to_value(value)?)
+}
+
+/// A Daml template with a typed representation in the Daml-LF JSON encoding.
+///
+/// This trait is implemented by the code generator for all Daml templates when rendering JSON types.
+pub trait DamlJsonTemplate: Serialize + DeserializeOwned {
+    /// The fully qualified template id of the form `package_id:module:entity`.
+    const TEMPLATE_ID: &'static str;
+}
+
+/// A typed Daml contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamlJsonContract<T> {
+    pub contract_id: String,
+    pub template_id: String,
+    pub payload: T,
+    pub signatories: Vec<String>,
+    pub observers: Vec<String>,
+    pub agreement_text: String,
+}
+
+impl<T: DeserializeOwned> TryFrom<DamlJsonCreatedEvent> for DamlJsonContract<T> {
+    type Error = serde_json::Error;
+
+    fn try_from(event: DamlJsonCreatedEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            contract_id: event.contract_id,
+            template_id: event.template_id,
+            payload: serde_json::from_value(event.payload)?,
+            signatories: event.signatories,
+            observers: event.observers,
+            agreement_text: event.agreement_text,
+        })
+    }
+}
+
+/// The typed result of exercising a Daml choice.
+#[derive(Debug)]
+pub struct DamlJsonTypedExerciseResult<R> {
+    pub exercise_result: R,
+    pub events: Vec<DamlJsonEvent>,
+    pub transaction_tree: Option<DamlJsonTransactionTree>,
+}
+
+impl<R: DeserializeOwned> TryFrom<DamlJsonExerciseResult> for DamlJsonTypedExerciseResult<R> {
+    type Error = serde_json::Error;
+
+    fn try_from(result: DamlJsonExerciseResult) -> Result<Self, Self::Error> {
+        Ok(Self {
+            exercise_result: serde_json::from_value(result.exercise_result)?,
+            events: result.events,
+            transaction_tree: result.transaction_tree,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_int64() -> DamlJsonResult<()> {
+        assert_eq!(serde_json::to_value(DamlJsonInt64(-42))?, json!("-42"));
+        assert_eq!(serde_json::from_value::<DamlJsonInt64>(json!("42"))?, DamlJsonInt64(42));
+        assert_eq!(serde_json::from_value::<DamlJsonInt64>(json!(42))?, DamlJsonInt64(42));
+        assert!(serde_json::from_value::<DamlJsonInt64>(json!("4.2")).is_err());
+        assert!(serde_json::from_value::<DamlJsonInt64>(json!(u64::MAX)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unit() -> DamlJsonResult<()> {
+        assert_eq!(serde_json::to_value(DamlJsonUnit)?, json!({}));
+        assert_eq!(serde_json::from_value::<DamlJsonUnit>(json!({}))?, DamlJsonUnit);
+        assert!(serde_json::from_value::<DamlJsonUnit>(json!({"foo": 1})).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_optional() -> DamlJsonResult<()> {
+        let value: DamlJsonOptional<DamlJsonNestedOptional<DamlJsonInt64>> =
+            Some(DamlJsonNestedOptional(Some(DamlJsonInt64(1))));
+        assert_eq!(serde_json::to_value(&value)?, json!(["1"]));
+        let value: DamlJsonOptional<DamlJsonNestedOptional<DamlJsonInt64>> = Some(DamlJsonNestedOptional(None));
+        assert_eq!(serde_json::to_value(&value)?, json!([]));
+        let value: DamlJsonOptional<DamlJsonNestedOptional<DamlJsonInt64>> = None;
+        assert_eq!(serde_json::to_value(&value)?, json!(null));
+        let decoded: DamlJsonOptional<DamlJsonNestedOptional<DamlJsonNestedOptional<DamlJsonText>>> =
+            serde_json::from_value(json!([[]]))?;
+        assert_eq!(decoded, Some(DamlJsonNestedOptional(Some(DamlJsonNestedOptional(None)))));
+        assert!(serde_json::from_value::<DamlJsonNestedOptional<DamlJsonText>>(json!(["a", "b"])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_numeric() -> DamlJsonResult<()> {
+        let numeric: DamlJsonNumeric = serde_json::from_value(json!("1.50"))?;
+        assert_eq!(serde_json::to_value(&numeric)?, json!("1.50"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_gen_map() -> DamlJsonResult<()> {
+        let map: DamlJsonGenMap<DamlJsonText, DamlJsonInt64> = vec![(String::from("a"), DamlJsonInt64(1))];
+        assert_eq!(serde_json::to_value(&map)?, json!([["a", "1"]]));
+        Ok(())
+    }
+}
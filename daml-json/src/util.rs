@@ -1,5 +1,23 @@
+use crate::error::{DamlJsonCodecError, DamlJsonCodecResult, DamlJsonDecodeError};
+use bigdecimal::Zero;
+use chrono::{Date, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use daml_grpc::primitive_types::{DamlDate, DamlNumeric, DamlTimestamp};
+use daml_lf::element::{DamlType, DamlTypeVarWithKind, DamlVar};
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::iter::FromIterator;
 use thiserror::Error;
 
+/// The maximum number of significant digits in a Daml `Numeric`.
+const NUMERIC_PRECISION: u64 = 38;
+
+/// The number of days from 0001-01-01 to the unix epoch.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// The number of microseconds in a second.
+const MICROS_PER_SECOND: i64 = 1_000_000;
+
 #[derive(Error, Debug)]
 #[error("required value was not supplied")]
 pub struct RequiredError;
@@ -37,3 +55,210 @@ impl<T> AsSingleSliceExt<T> for Vec<T> {
 pub fn fst<A, B>((a, _): (A, B)) -> A {
     a
 }
+
+/// The type arguments applied to the type parameters of a data type.
+///
+/// A type argument may itself refer to the type parameters of the enclosing data type and so each `TypeArgs` holds a
+/// reference to the `TypeArgs` of the enclosing data type, if any, in which such type arguments must be resolved.
+#[derive(Debug)]
+pub struct TypeArgs<'a> {
+    params: &'a [DamlTypeVarWithKind<'a>],
+    args: &'a [DamlType<'a>],
+    parent: Option<&'a Self>,
+}
+
+impl<'a> TypeArgs<'a> {
+    pub const fn new(
+        params: &'a [DamlTypeVarWithKind<'a>],
+        args: &'a [DamlType<'a>],
+        parent: Option<&'a Self>,
+    ) -> Self {
+        Self {
+            params,
+            args,
+            parent,
+        }
+    }
+
+    /// Resolve a `DamlVar` to the `DamlType` argument applied to the matching type parameter.
+    ///
+    /// Returns the resolved `DamlType` along with the `TypeArgs` in which it must itself be resolved.
+    pub fn resolve(&self, var: &DamlVar<'_>) -> Option<(&'a DamlType<'a>, Option<&'a Self>)> {
+        let index = self.params.iter().position(|param| param.var() == var.var())?;
+        self.args.get(index).map(|arg| (arg, self.parent))
+    }
+}
+
+/// Resolve a `DamlType` which may be a `DamlVar` to the `DamlType` argument it refers to, if any.
+pub fn resolve_type<'a>(ty: &'a DamlType<'a>, type_args: Option<&'a TypeArgs<'a>>) -> &'a DamlType<'a> {
+    match ty {
+        DamlType::Var(var) => match type_args.and_then(|type_args| type_args.resolve(var)) {
+            Some((resolved, parent)) => resolve_type(resolved, parent),
+            None => ty,
+        },
+        _ => ty,
+    }
+}
+
+/// A JSON pointer to the value being decoded.
+///
+/// The `Root` holds the JSON pointer of the decoded value within some enclosing document, if any.
+///
+/// This is shared by all decoders such that errors are reported in the same way regardless of the encoding, for
+/// encodings where record fields are positional the path refers to the field by name.
+#[derive(Debug)]
+pub enum ValuePath<'p> {
+    Root(&'p str),
+    Key(&'p Self, &'p str),
+    Index(&'p Self, usize),
+}
+
+impl Display for ValuePath<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Root(pointer) => write!(f, "{pointer}"),
+            Self::Key(parent, key) => write!(f, "{parent}/{}", key.replace('~', "~0").replace('/', "~1")),
+            Self::Index(parent, index) => write!(f, "{parent}/{index}"),
+        }
+    }
+}
+
+/// Record an error against the given `path` unless it has already been located.
+pub fn locate(err: DamlJsonCodecError, path: &ValuePath<'_>, expected: impl FnOnce() -> String) -> DamlJsonCodecError {
+    match err {
+        DamlJsonCodecError::DecodeErrors(_) => err,
+        _ => DamlJsonCodecError::DecodeErrors(vec![DamlJsonDecodeError::new(path.to_string(), expected(), err)]),
+    }
+}
+
+/// Describe a `DamlType` using Daml syntax, i.e. `Optional [Int]`, resolving any type variables from `type_args`.
+pub fn describe_type(ty: &DamlType<'_>, type_args: Option<&TypeArgs<'_>>) -> String {
+    match ty {
+        DamlType::Unit => String::from("()"),
+        DamlType::Bool => String::from("Bool"),
+        DamlType::Int64 => String::from("Int"),
+        DamlType::Text => String::from("Text"),
+        DamlType::Party => String::from("Party"),
+        DamlType::Date => String::from("Date"),
+        DamlType::Timestamp => String::from("Time"),
+        DamlType::Numeric(tys) => describe_applied("Numeric", tys, type_args),
+        DamlType::ContractId(Some(ty)) => describe_applied("ContractId", std::slice::from_ref(ty), type_args),
+        DamlType::ContractId(None) => String::from("ContractId"),
+        DamlType::List(tys) => format!("[{}]", tys.iter().map(|ty| describe_type(ty, type_args)).join(", ")),
+        DamlType::TextMap(tys) => describe_applied("TextMap", tys, type_args),
+        DamlType::GenMap(tys) => describe_applied("Map", tys, type_args),
+        DamlType::Optional(tys) => describe_applied("Optional", tys, type_args),
+        DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) => describe_applied(
+            &format!("{}:{}", tycon.tycon().module_path().join("."), tycon.tycon().data_name()),
+            tycon.type_arguments(),
+            type_args,
+        ),
+        DamlType::Var(var) => match type_args.and_then(|type_args| type_args.resolve(var)) {
+            Some((resolved, parent)) => describe_type(resolved, parent),
+            None => var.var().to_string(),
+        },
+        DamlType::Nat(nat) => nat.to_string(),
+        _ => ty.name().to_owned(),
+    }
+}
+
+/// Describe a type constructor `name` applied to the types `tys`.
+fn describe_applied(name: &str, tys: &[DamlType<'_>], type_args: Option<&TypeArgs<'_>>) -> String {
+    tys.iter().fold(name.to_owned(), |acc, ty| {
+        let arg = describe_type(ty, type_args);
+        if arg.contains(' ') {
+            format!("{acc} ({arg})")
+        } else {
+            format!("{acc} {arg}")
+        }
+    })
+}
+
+/// Collect the results of decoding the children of a value.
+///
+/// If `validate_all` is set then the errors from all children are collected, otherwise the first error is returned.
+pub fn collect_results<T, C: FromIterator<T>>(
+    validate_all: bool,
+    results: impl Iterator<Item = DamlJsonCodecResult<T>>,
+) -> DamlJsonCodecResult<C> {
+    if validate_all {
+        let mut errors = vec![];
+        let collected = results
+            .filter_map(|res| match res {
+                Ok(value) => Some(value),
+                Err(DamlJsonCodecError::DecodeErrors(errs)) => {
+                    errors.extend(errs);
+                    None
+                },
+                Err(err) => {
+                    errors.push(DamlJsonDecodeError::new("", "", err));
+                    None
+                },
+            })
+            .collect::<C>();
+        if errors.is_empty() {
+            Ok(collected)
+        } else {
+            Err(DamlJsonCodecError::DecodeErrors(errors))
+        }
+    } else {
+        results.collect()
+    }
+}
+
+/// Check that a `DamlNumeric` can be represented as a `Numeric` with the given `scale`.
+///
+/// A `Numeric` with scale `s` may have at most `s` digits after the decimal point and at most `38 - s` digits before
+/// it.
+pub fn check_numeric(numeric: &DamlNumeric, scale: u8) -> DamlJsonCodecResult<()> {
+    let (_, fractional_digits) = numeric.normalized().as_bigint_and_exponent();
+    let integer_part = numeric.with_scale(0).abs();
+    let integer_digits = if integer_part.is_zero() {
+        0
+    } else {
+        integer_part.digits()
+    };
+    if fractional_digits > i64::from(scale) || integer_digits > NUMERIC_PRECISION - u64::from(scale) {
+        Err(DamlJsonCodecError::NumericOutOfRange(numeric.to_string(), scale))
+    } else {
+        Ok(())
+    }
+}
+
+/// Resolve the scale of a `Numeric` from its (possibly variable) `Nat` type argument.
+pub fn resolve_scale<'t>(args: &'t [DamlType<'t>], type_args: Option<&'t TypeArgs<'t>>) -> DamlJsonCodecResult<u8> {
+    match args {
+        [arg] => match resolve_type(arg, type_args) {
+            DamlType::Nat(scale) => Ok(*scale),
+            DamlType::Var(var) => Err(DamlJsonCodecError::TypeVarNotFound(var.var().to_string())),
+            ty => Err(DamlJsonCodecError::UnsupportedDamlType(ty.name().to_owned())),
+        },
+        _ => Err(DamlJsonCodecError::UnexpectedListEntries(NotSingleError)),
+    }
+}
+
+/// The number of days since the unix epoch of a `DamlDate`.
+pub fn days_from_date(date: DamlDate) -> i32 {
+    date.naive_utc().num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE
+}
+
+/// The `DamlDate` which is `days` days after the unix epoch.
+pub fn date_from_days(days: i32) -> DamlJsonCodecResult<DamlDate> {
+    days.checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+        .map(|naive| Date::from_utc(naive, Utc))
+        .ok_or_else(|| DamlJsonCodecError::DateTimeOutOfRange(format!("{days} days since epoch")))
+}
+
+/// The number of microseconds since the unix epoch of a `DamlTimestamp`, any sub-microsecond precision is truncated.
+pub fn micros_from_timestamp(timestamp: &DamlTimestamp) -> i64 {
+    timestamp.timestamp() * MICROS_PER_SECOND + i64::from(timestamp.timestamp_subsec_micros())
+}
+
+/// The `DamlTimestamp` which is `micros` microseconds after the unix epoch.
+pub fn timestamp_from_micros(micros: i64) -> DamlJsonCodecResult<DamlTimestamp> {
+    Utc.timestamp_opt(micros.div_euclid(MICROS_PER_SECOND), 0)
+        .single()
+        .and_then(|seconds| seconds.checked_add_signed(Duration::microseconds(micros.rem_euclid(MICROS_PER_SECOND))))
+        .ok_or_else(|| DamlJsonCodecError::DateTimeOutOfRange(format!("{micros} microseconds since epoch")))
+}
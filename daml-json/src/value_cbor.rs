@@ -0,0 +1,661 @@
+use crate::error::{DamlJsonCodecError, DamlJsonCodecResult};
+use crate::util::{
+    check_numeric, collect_results, date_from_days, days_from_date, describe_type, locate, resolve_scale, resolve_type,
+    AsSingleSliceExt, Required, TypeArgs, ValuePath,
+};
+use chrono::{DateTime, SecondsFormat};
+use ciborium::value::{Integer, Value};
+use daml_grpc::data::value::{DamlEnum, DamlRecord, DamlRecordField, DamlValue, DamlVariant};
+use daml_grpc::data::DamlIdentifier;
+use daml_grpc::primitive_types::{DamlGenMap, DamlNumeric, DamlTextMap};
+use daml_lf::element;
+use daml_lf::element::{DamlArchive, DamlData, DamlField, DamlType};
+use std::convert::TryFrom;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+/// The CBOR tag for a standard date/time string (RFC 8949).
+const DATE_TIME_STRING_TAG: u64 = 0;
+
+/// The CBOR tag for a number of days since the unix epoch (RFC 8943).
+const EPOCH_DAYS_TAG: u64 = 100;
+
+/// Encode a `DamlValue` as CBOR.
+///
+/// The encoding is directed by the `DamlType` of the value and so requires a `DamlArchive`.  Values are encoded as
+/// follows:
+///
+/// - `Unit` as an empty map
+/// - `Bool` as a boolean and `Int64` as an integer
+/// - `Numeric` as a text string which must be representable with the scale of the type
+/// - `Text`, `Party` and `ContractId` as text strings
+/// - `Date` as the number of days since the unix epoch (tag 100)
+/// - `Timestamp` as an RFC 3339 date/time string with microsecond precision (tag 0)
+/// - `Optional` as an array of zero or one entry and `List` as an array
+/// - `TextMap` as a map with text keys and `GenMap` as a map with arbitrary keys
+/// - records as an array of fields, positionally
+/// - variants as a map with a single entry from the constructor to the value
+/// - enums as a text string
+///
+/// This is the inverse of the [`CborValueDecoder`].
+#[derive(Debug)]
+pub struct CborValueEncoder<'a> {
+    arc: &'a DamlArchive<'a>,
+}
+
+impl<'a> CborValueEncoder<'a> {
+    pub const fn new(arc: &'a DamlArchive<'a>) -> Self {
+        Self {
+            arc,
+        }
+    }
+
+    /// Recursively encode a GRPC `DamlValue` of a given `DamlType` as CBOR bytes.
+    pub fn encode(&self, value: &DamlValue, ty: &DamlType<'_>) -> DamlJsonCodecResult<Vec<u8>> {
+        let mut bytes = vec![];
+        ciborium::ser::into_writer(&self.do_encode(value, ty, None)?, &mut bytes)
+            .map_err(|err| DamlJsonCodecError::CborError(err.to_string()))?;
+        Ok(bytes)
+    }
+
+    fn do_encode<'t>(
+        &self,
+        value: &DamlValue,
+        ty: &'t DamlType<'t>,
+        type_args: Option<&'t TypeArgs<'t>>,
+    ) -> DamlJsonCodecResult<Value> {
+        match (ty, value) {
+            (DamlType::Unit, DamlValue::Unit) => Ok(Value::Map(vec![])),
+            (DamlType::Bool, DamlValue::Bool(b)) => Ok(Value::Bool(*b)),
+            (DamlType::Int64, DamlValue::Int64(i)) => Ok(Value::Integer(Integer::from(*i))),
+            (DamlType::Numeric(args), DamlValue::Numeric(n)) => {
+                check_numeric(n, resolve_scale(args, type_args)?)?;
+                Ok(Value::Text(n.to_string()))
+            },
+            (DamlType::Text, DamlValue::Text(text)) => Ok(Value::Text(text.clone())),
+            (DamlType::Party, DamlValue::Party(party)) => Ok(Value::Text(party.party.clone())),
+            (DamlType::ContractId(_), DamlValue::ContractId(id)) => Ok(Value::Text(id.contract_id.clone())),
+            (DamlType::Date, DamlValue::Date(date)) =>
+                Ok(Value::Tag(EPOCH_DAYS_TAG, Box::new(Value::Integer(Integer::from(days_from_date(*date)))))),
+            (DamlType::Timestamp, DamlValue::Timestamp(timestamp)) => Ok(Value::Tag(
+                DATE_TIME_STRING_TAG,
+                Box::new(Value::Text(timestamp.to_rfc3339_opts(SecondsFormat::Micros, true))),
+            )),
+            (DamlType::List(tys), DamlValue::List(list)) => {
+                let ty = tys.as_single()?;
+                Ok(Value::Array(
+                    list.iter()
+                        .map(|item| self.do_encode(item, ty, type_args))
+                        .collect::<DamlJsonCodecResult<Vec<_>>>()?,
+                ))
+            },
+            (DamlType::TextMap(tys), DamlValue::Map(map)) => {
+                let ty = tys.as_single()?;
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_unstable_by_key(|(k, _)| *k);
+                Ok(Value::Map(
+                    entries
+                        .into_iter()
+                        .map(|(k, v)| Ok((Value::Text(k.clone()), self.do_encode(v, ty, type_args)?)))
+                        .collect::<DamlJsonCodecResult<Vec<_>>>()?,
+                ))
+            },
+            (DamlType::GenMap(tys), DamlValue::GenMap(map)) => match tys.as_slice() {
+                [key_ty, value_ty] => Ok(Value::Map(
+                    map.iter()
+                        .map(|(k, v)| {
+                            Ok((self.do_encode(k, key_ty, type_args)?, self.do_encode(v, value_ty, type_args)?))
+                        })
+                        .collect::<DamlJsonCodecResult<Vec<_>>>()?,
+                )),
+                _ => Err(DamlJsonCodecError::UnexpectedGenMapTypes),
+            },
+            (DamlType::Optional(tys), DamlValue::Optional(opt)) => {
+                let ty = tys.as_single()?;
+                Ok(Value::Array(
+                    opt.iter().map(|inner| self.do_encode(inner, ty, type_args)).collect::<DamlJsonCodecResult<_>>()?,
+                ))
+            },
+            (DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon), _) => {
+                let data = self
+                    .arc
+                    .data_by_tycon(tycon)
+                    .ok_or_else(|| DamlJsonCodecError::DataNotFound(tycon.tycon().to_string()))?;
+                self.encode_data(value, data, &TypeArgs::new(data.type_params(), tycon.type_arguments(), type_args))
+            },
+            (DamlType::Var(var), _) => {
+                let (resolved, parent) = type_args
+                    .and_then(|type_args| type_args.resolve(var))
+                    .ok_or_else(|| DamlJsonCodecError::TypeVarNotFound(var.var().to_string()))?;
+                self.do_encode(value, resolved, parent)
+            },
+            (
+                DamlType::Nat(_)
+                | DamlType::Arrow(_)
+                | DamlType::Any
+                | DamlType::TypeRep
+                | DamlType::Bignumeric
+                | DamlType::RoundingMode
+                | DamlType::AnyException
+                | DamlType::Update(_)
+                | DamlType::Scenario(_)
+                | DamlType::Forall(_)
+                | DamlType::Struct(_)
+                | DamlType::Syn(_),
+                _,
+            ) => Err(DamlJsonCodecError::UnsupportedDamlType(ty.name().to_owned())),
+            _ => Err(DamlJsonCodecError::UnexpectedDamlValue(ty.name().to_owned(), value.variant_name().to_owned())),
+        }
+    }
+
+    /// Encode a `DamlValue` for a given `DamlData`.
+    fn encode_data<'t>(
+        &self,
+        value: &DamlValue,
+        data: &'t DamlData<'t>,
+        type_args: &'t TypeArgs<'t>,
+    ) -> DamlJsonCodecResult<Value> {
+        match (data, value) {
+            (DamlData::Template(template), DamlValue::Record(record)) =>
+                self.encode_record(record, template.fields(), type_args),
+            (DamlData::Record(data_record), DamlValue::Record(record)) =>
+                self.encode_record(record, data_record.fields(), type_args),
+            (DamlData::Variant(data_variant), DamlValue::Variant(variant)) =>
+                self.encode_variant(variant, data_variant.fields(), type_args),
+            (DamlData::Enum(data_enum), DamlValue::Enum(value_enum)) => Self::encode_enum(value_enum, data_enum),
+            _ => Err(DamlJsonCodecError::UnexpectedDamlValue(data.name().to_owned(), value.variant_name().to_owned())),
+        }
+    }
+
+    /// Encode a `DamlRecord` as a CBOR array in the order of the given `DamlRecord` / `DamlTemplate` fields.
+    ///
+    /// If every field of the `DamlRecord` is labelled then fields are matched by label, otherwise they are matched by
+    /// position.
+    fn encode_record<'t>(
+        &self,
+        record: &DamlRecord,
+        lf_fields: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+    ) -> DamlJsonCodecResult<Value> {
+        let has_labels = record.fields().iter().all(|f| f.label().is_some());
+        Ok(Value::Array(
+            lf_fields
+                .iter()
+                .enumerate()
+                .map(|(i, lf_field)| {
+                    let field_value = if has_labels {
+                        record.fields().iter().find(|f| f.label().as_deref() == Some(lf_field.name()))
+                    } else {
+                        record.fields().get(i)
+                    }
+                    .map(DamlRecordField::value)
+                    .ok_or_else(|| DamlJsonCodecError::MissingDamlRecordField(lf_field.name().to_owned()))?;
+                    self.do_encode(field_value, lf_field.ty(), Some(type_args))
+                })
+                .collect::<DamlJsonCodecResult<Vec<_>>>()?,
+        ))
+    }
+
+    /// Encode a `DamlVariant` as a single entry CBOR map for the given `DamlVariant` constructors.
+    fn encode_variant<'t>(
+        &self,
+        variant: &DamlVariant,
+        constructors: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+    ) -> DamlJsonCodecResult<Value> {
+        let constructor = constructors
+            .iter()
+            .find(|&field| field.name() == variant.constructor())
+            .ok_or_else(|| DamlJsonCodecError::UnknownVariantConstructor(variant.constructor().to_owned()))?;
+        let value = self.do_encode(variant.value(), constructor.ty(), Some(type_args))?;
+        Ok(Value::Map(vec![(Value::Text(constructor.name().to_owned()), value)]))
+    }
+
+    /// Encode a `DamlEnum` as a CBOR text string for the given `DamlEnum` constructors.
+    fn encode_enum(value_enum: &DamlEnum, data_enum: &element::DamlEnum<'_>) -> DamlJsonCodecResult<Value> {
+        let constructor = value_enum.constructor();
+        if data_enum.constructors().any(|c| c == constructor) {
+            Ok(Value::Text(constructor.to_owned()))
+        } else {
+            Err(DamlJsonCodecError::UnknownEnumConstructor(constructor.to_owned()))
+        }
+    }
+}
+
+/// Decode a `DamlValue` from CBOR.
+///
+/// See [`CborValueEncoder`] for details of the encoding.
+///
+/// Decoding failures are reported as [`DamlJsonCodecError::DecodeErrors`] in the same way as for the
+/// [`JsonValueDecoder`], the path of each error refers to record fields by name.
+///
+/// Trailing record fields of `Optional` type may be omitted and are decoded as `None`.
+///
+/// By default decoding stops at the first error, use [`CborValueDecoder::with_validate_all`] to collect all errors.
+///
+/// [`JsonValueDecoder`]: crate::value_decode::JsonValueDecoder
+#[derive(Debug)]
+pub struct CborValueDecoder<'a> {
+    arc: &'a DamlArchive<'a>,
+    validate_all: bool,
+}
+
+impl<'a> CborValueDecoder<'a> {
+    pub const fn new(arc: &'a DamlArchive<'a>) -> Self {
+        Self {
+            arc,
+            validate_all: false,
+        }
+    }
+
+    /// Sets whether all errors are collected rather than failing on the first error.
+    #[must_use]
+    pub const fn with_validate_all(self, validate_all: bool) -> Self {
+        Self {
+            validate_all,
+            ..self
+        }
+    }
+
+    /// Recursively decode a GRPC `DamlValue` from CBOR bytes for a given `DamlType`.
+    pub fn decode(&self, bytes: &[u8], ty: &DamlType<'_>) -> DamlJsonCodecResult<DamlValue> {
+        self.decode_at(bytes, ty, "")
+    }
+
+    /// Recursively decode a GRPC `DamlValue` from CBOR bytes which is located at the JSON pointer `pointer` within
+    /// some enclosing document.
+    ///
+    /// The `pointer` is used as the prefix of the path of all [`DamlJsonDecodeError`] returned.
+    ///
+    /// [`DamlJsonDecodeError`]: crate::error::DamlJsonDecodeError
+    pub fn decode_at(&self, bytes: &[u8], ty: &DamlType<'_>, pointer: &str) -> DamlJsonCodecResult<DamlValue> {
+        let value: Value =
+            ciborium::de::from_reader(bytes).map_err(|err| DamlJsonCodecError::CborError(err.to_string()))?;
+        self.do_decode(&value, ty, None, &ValuePath::Root(pointer))
+    }
+
+    fn do_decode<'t>(
+        &self,
+        value: &Value,
+        ty: &'t DamlType<'t>,
+        type_args: Option<&'t TypeArgs<'t>>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        self.decode_type(value, ty, type_args, path).map_err(|err| locate(err, path, || describe_type(ty, type_args)))
+    }
+
+    fn decode_type<'t>(
+        &self,
+        value: &Value,
+        ty: &'t DamlType<'t>,
+        type_args: Option<&'t TypeArgs<'t>>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        match (ty, value) {
+            (DamlType::Unit, Value::Map(entries)) =>
+                if entries.is_empty() {
+                    Ok(DamlValue::Unit)
+                } else {
+                    Err(DamlJsonCodecError::UnexpectedUnitData)
+                },
+            (DamlType::Bool, Value::Bool(b)) => Ok(DamlValue::Bool(*b)),
+            (DamlType::Int64, Value::Integer(i)) => Ok(DamlValue::new_int64(
+                i64::try_from(*i).map_err(|err| DamlJsonCodecError::CborError(err.to_string()))?,
+            )),
+            (DamlType::Numeric(args), Value::Text(s)) => {
+                let numeric = DamlNumeric::from_str(s)?;
+                check_numeric(&numeric, resolve_scale(args, type_args)?)?;
+                Ok(DamlValue::new_numeric(numeric))
+            },
+            (DamlType::Text, Value::Text(text)) => Ok(DamlValue::new_text(text)),
+            (DamlType::Party, Value::Text(party)) => Ok(DamlValue::new_party(party.as_str())),
+            (DamlType::ContractId(_), Value::Text(id)) => Ok(DamlValue::new_contract_id(id.as_str())),
+            (DamlType::Date, Value::Tag(EPOCH_DAYS_TAG, days)) => match days.as_ref() {
+                Value::Integer(days) => Ok(DamlValue::new_date(date_from_days(
+                    i32::try_from(*days).map_err(|err| DamlJsonCodecError::DateTimeOutOfRange(err.to_string()))?,
+                )?)),
+                other => Err(unexpected_value(other)),
+            },
+            (DamlType::Timestamp, Value::Tag(DATE_TIME_STRING_TAG, timestamp)) => match timestamp.as_ref() {
+                Value::Text(timestamp) => Ok(DamlValue::new_timestamp(DateTime::parse_from_rfc3339(timestamp)?)),
+                other => Err(unexpected_value(other)),
+            },
+            (DamlType::List(tys), Value::Array(items)) => {
+                let item_ty = tys.as_single()?;
+                Ok(DamlValue::List(
+                    self.collect(
+                        items
+                            .iter()
+                            .enumerate()
+                            .map(|(i, item)| self.do_decode(item, item_ty, type_args, &ValuePath::Index(path, i))),
+                    )?,
+                ))
+            },
+            (DamlType::TextMap(tys), Value::Map(entries)) => {
+                let value_ty = tys.as_single()?;
+                Ok(DamlValue::Map(self.collect::<_, DamlTextMap<DamlValue>>(entries.iter().enumerate().map(
+                    |(i, (k, v))| match k {
+                        Value::Text(key) =>
+                            Ok((key.clone(), self.do_decode(v, value_ty, type_args, &ValuePath::Key(path, key))?)),
+                        other =>
+                            Err(locate(unexpected_value(other), &ValuePath::Index(path, i), || String::from("Text"))),
+                    },
+                ))?))
+            },
+            (DamlType::GenMap(tys), Value::Map(entries)) => self.decode_genmap(entries, tys, type_args, path),
+            (DamlType::Optional(tys), Value::Array(items)) => {
+                let single = tys.as_single()?;
+                match items.as_slice() {
+                    [] => Ok(DamlValue::Optional(None)),
+                    [inner] => Ok(DamlValue::Optional(Some(Box::new(self.do_decode(
+                        inner,
+                        single,
+                        type_args,
+                        &ValuePath::Index(path, 0),
+                    )?)))),
+                    _ => Err(DamlJsonCodecError::UnexpectedOptionalArrayLength),
+                }
+            },
+            (DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon), _) => {
+                let data = self
+                    .arc
+                    .data_by_tycon(tycon)
+                    .ok_or_else(|| DamlJsonCodecError::DataNotFound(tycon.tycon().to_string()))?;
+                self.decode_data(
+                    value,
+                    data,
+                    &TypeArgs::new(data.type_params(), tycon.type_arguments(), type_args),
+                    path,
+                )
+            },
+            (DamlType::Var(var), _) => {
+                let (resolved, parent) = type_args
+                    .and_then(|type_args| type_args.resolve(var))
+                    .ok_or_else(|| DamlJsonCodecError::TypeVarNotFound(var.var().to_string()))?;
+                self.do_decode(value, resolved, parent, path)
+            },
+            (
+                DamlType::Nat(_)
+                | DamlType::Arrow(_)
+                | DamlType::Any
+                | DamlType::TypeRep
+                | DamlType::Bignumeric
+                | DamlType::RoundingMode
+                | DamlType::AnyException
+                | DamlType::Update(_)
+                | DamlType::Scenario(_)
+                | DamlType::Forall(_)
+                | DamlType::Struct(_)
+                | DamlType::Syn(_),
+                _,
+            ) => Err(DamlJsonCodecError::UnsupportedDamlType(ty.name().to_owned())),
+            (_, other) => Err(unexpected_value(other)),
+        }
+    }
+
+    /// Decode a `DamlValue::GenMap` from the entries of a CBOR map.
+    fn decode_genmap<'t>(
+        &self,
+        entries: &[(Value, Value)],
+        tys: &'t [DamlType<'t>],
+        type_args: Option<&'t TypeArgs<'t>>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        let key_ty = tys.first().req()?;
+        let value_ty = tys.last().req()?;
+        let genmap =
+            self.collect::<_, DamlGenMap<DamlValue, DamlValue>>(entries.iter().enumerate().map(|(i, (k, v))| {
+                let entry_path = ValuePath::Index(path, i);
+                Ok((
+                    self.do_decode(k, key_ty, type_args, &ValuePath::Index(&entry_path, 0))?,
+                    self.do_decode(v, value_ty, type_args, &ValuePath::Index(&entry_path, 1))?,
+                ))
+            }))?;
+        if entries.len() == genmap.len() {
+            Ok(DamlValue::GenMap(genmap))
+        } else {
+            Err(DamlJsonCodecError::DuplicateGenMapKeys)
+        }
+    }
+
+    /// Collect the results of decoding the children of a CBOR value.
+    fn collect<T, C: FromIterator<T>>(
+        &self,
+        results: impl Iterator<Item = DamlJsonCodecResult<T>>,
+    ) -> DamlJsonCodecResult<C> {
+        collect_results(self.validate_all, results)
+    }
+
+    /// Decode a `DamlValue` from a CBOR value and `DamlData`.
+    fn decode_data<'t>(
+        &self,
+        value: &Value,
+        data: &'t DamlData<'t>,
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        match (data, value) {
+            (DamlData::Template(template), Value::Array(fields)) =>
+                self.decode_record(fields, template.fields(), type_args, path),
+            (DamlData::Record(data_record), Value::Array(fields)) =>
+                self.decode_record(fields, data_record.fields(), type_args, path),
+            (DamlData::Variant(data_variant), Value::Map(entries)) => match entries.as_slice() {
+                [(Value::Text(constructor), value)] =>
+                    self.decode_variant(constructor, value, data_variant.fields(), type_args, path),
+                _ => Err(DamlJsonCodecError::UnexpectedEncodedValue(String::from("Map"))),
+            },
+            (DamlData::Enum(data_enum), Value::Text(constructor)) => Self::decode_enum(constructor, data_enum),
+            (_, other) => Err(unexpected_value(other)),
+        }
+    }
+
+    /// Decode a `DamlValue::Enum` from a CBOR text string and `DamlEnum` constructors.
+    fn decode_enum(constructor: &str, data_enum: &element::DamlEnum<'_>) -> DamlJsonCodecResult<DamlValue> {
+        if data_enum.constructors().any(|c| c == constructor) {
+            Ok(DamlValue::Enum(DamlEnum::new(constructor, None::<DamlIdentifier>)))
+        } else {
+            Err(DamlJsonCodecError::UnknownEnumConstructor(constructor.to_owned()))
+        }
+    }
+
+    /// Decode a `DamlValue::Variant` from a CBOR constructor and value and `DamlVariant` constructors.
+    fn decode_variant<'t>(
+        &self,
+        constructor: &str,
+        value: &Value,
+        constructors: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        let constructor = constructors
+            .iter()
+            .find(|&field| field.name() == constructor)
+            .ok_or_else(|| DamlJsonCodecError::UnknownVariantConstructor(constructor.to_owned()))?;
+        Ok(DamlValue::Variant(DamlVariant::new(
+            constructor.name(),
+            Box::new(self.do_decode(value, constructor.ty(), Some(type_args), &ValuePath::Key(path, "value"))?),
+            None::<DamlIdentifier>,
+        )))
+    }
+
+    /// Decode a `DamlValue::Record` from a positional CBOR array and `DamlRecord` / `DamlTemplate` fields.
+    fn decode_record<'t>(
+        &self,
+        values: &[Value],
+        lf_fields: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        if values.len() > lf_fields.len() {
+            return Err(DamlJsonCodecError::UnexpectedRecordFieldCount(lf_fields.len(), values.len()));
+        }
+        let fields: Vec<_> = self.collect(lf_fields.iter().enumerate().map(|(i, field)| {
+            let field_path = ValuePath::Key(path, field.name());
+            match (resolve_type(field.ty(), Some(type_args)), values.get(i)) {
+                (_, Some(value)) => Ok(DamlRecordField::new(
+                    Some(field.name()),
+                    self.do_decode(value, field.ty(), Some(type_args), &field_path)?,
+                )),
+                (DamlType::Optional(_), None) =>
+                    Ok(DamlRecordField::new(Some(field.name()), DamlValue::Optional(None))),
+                (_, None) =>
+                    Err(locate(DamlJsonCodecError::MissingRecordField(i, field.name().to_owned()), &field_path, || {
+                        describe_type(field.ty(), Some(type_args))
+                    })),
+            }
+        }))?;
+        Ok(DamlValue::Record(DamlRecord::new(fields, None::<DamlIdentifier>)))
+    }
+}
+
+/// Make an error for an unexpected CBOR value.
+fn unexpected_value(value: &Value) -> DamlJsonCodecError {
+    DamlJsonCodecError::UnexpectedEncodedValue(cbor_value_name(value).to_owned())
+}
+
+/// The name of the kind of a CBOR value.
+const fn cbor_value_name(value: &Value) -> &'static str {
+    match value {
+        Value::Integer(_) => "Integer",
+        Value::Bytes(_) => "Bytes",
+        Value::Float(_) => "Float",
+        Value::Text(_) => "Text",
+        Value::Bool(_) => "Bool",
+        Value::Null => "Null",
+        Value::Tag(..) => "Tag",
+        Value::Array(_) => "Array",
+        Value::Map(_) => "Map",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CborValueDecoder, CborValueEncoder};
+    use crate::error::{DamlJsonCodecError, DamlJsonCodecResult};
+    use crate::test_util::{arbitrary_data_type, arbitrary_value, make_rng};
+    use crate::value_decode::JsonValueDecoder;
+    use crate::value_encode::TypedJsonValueEncoder;
+    use ciborium::value::{Integer, Value};
+    use daml_grpc::data::value::DamlValue;
+    use daml_lf::element::{DamlArchive, DamlType};
+    use proptest::prelude::*;
+
+    static TESTING_TYPES_DAR_PATH: &str = "../resources/testing_types_sandbox/TestingTypes-latest.dar";
+
+    /// The maximum depth of generated values.
+    const MAX_DEPTH: usize = 4;
+
+    #[test]
+    fn test_date_tag() -> DamlJsonCodecResult<()> {
+        let value = DamlValue::new_date(crate::util::date_from_days(18_000)?);
+        let arc = DamlArchive::default();
+        let bytes = CborValueEncoder::new(&arc).encode(&value, &DamlType::Date)?;
+        assert_eq!(decode_cbor(&bytes), Value::Tag(100, Box::new(Value::Integer(Integer::from(18_000)))));
+        assert_eq!(CborValueDecoder::new(&arc).decode(&bytes, &DamlType::Date)?, value);
+        Ok(())
+    }
+
+    #[test]
+    fn test_timestamp_tag() -> anyhow::Result<()> {
+        let value = DamlValue::new_timestamp("2019-01-02T03:45:56.123456Z".parse::<chrono::DateTime<chrono::Utc>>()?);
+        let arc = DamlArchive::default();
+        let bytes = CborValueEncoder::new(&arc).encode(&value, &DamlType::Timestamp)?;
+        assert_eq!(
+            decode_cbor(&bytes),
+            Value::Tag(0, Box::new(Value::Text(String::from("2019-01-02T03:45:56.123456Z"))))
+        );
+        assert_eq!(CborValueDecoder::new(&arc).decode(&bytes, &DamlType::Timestamp)?, value);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_optional() -> DamlJsonCodecResult<()> {
+        let ty = DamlType::Optional(vec![DamlType::Optional(vec![DamlType::Int64])]);
+        let value = DamlValue::new_optional(Some(DamlValue::new_optional(None)));
+        let arc = DamlArchive::default();
+        let bytes = CborValueEncoder::new(&arc).encode(&value, &ty)?;
+        assert_eq!(decode_cbor(&bytes), Value::Array(vec![Value::Array(vec![])]));
+        assert_eq!(CborValueDecoder::new(&arc).decode(&bytes, &ty)?, value);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_errors_are_located() {
+        let arc = daml_archive();
+        let module_path = ["Fuji", "JsonTest"];
+        let ty = DamlType::make_tycon(arc.main_package_id(), &module_path, "OPerson");
+        let bytes =
+            encode_cbor(&Value::Array(vec![Value::Array(vec![Value::Array(vec![Value::Array(vec![Value::Bool(
+                true,
+            )])])])]));
+        let result = CborValueDecoder::new(arc).with_validate_all(true).decode_at(&bytes, &ty, "/payload");
+        match result {
+            Err(DamlJsonCodecError::DecodeErrors(errors)) => {
+                let paths: Vec<_> = errors.iter().map(|e| (e.path.as_str(), e.expected.as_str())).collect();
+                assert_eq!(paths, vec![("/payload/bar/foo/0/name", "Text"), ("/payload/bar/foo/0/age", "Int")]);
+            },
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_variant_constructor() {
+        let arc = daml_archive();
+        let module_path = ["Fuji", "JsonTest"];
+        let ty = DamlType::make_tycon(arc.main_package_id(), &module_path, "Foo");
+        let bytes = encode_cbor(&Value::Map(vec![(Value::Text(String::from("Unknown")), Value::Map(vec![]))]));
+        let result = CborValueDecoder::new(arc).decode(&bytes, &ty);
+        assert!(
+            matches!(result, Err(DamlJsonCodecError::DecodeErrors(errors)) if matches!(*errors[0].error, DamlJsonCodecError::UnknownVariantConstructor(_)))
+        );
+    }
+
+    #[test]
+    fn test_invalid_bytes() {
+        let result = CborValueDecoder::new(&DamlArchive::default()).decode(&[0xff], &DamlType::Int64);
+        assert!(matches!(result, Err(DamlJsonCodecError::CborError(_))));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_round_trip(seed in any::<u64>(), (module, entity) in arbitrary_data_type()) {
+            let arc = daml_archive();
+            let module_path = ["Fuji", module];
+            let ty = DamlType::make_tycon(arc.main_package_id(), &module_path, entity);
+            let value = arbitrary_value(&mut make_rng(seed), arc, &ty, None, MAX_DEPTH);
+            let bytes = CborValueEncoder::new(arc).encode(&value, &ty).unwrap();
+            prop_assert_eq!(CborValueDecoder::new(arc).decode(&bytes, &ty).unwrap(), value);
+        }
+
+        #[test]
+        fn prop_agrees_with_json(seed in any::<u64>(), (module, entity) in arbitrary_data_type()) {
+            let arc = daml_archive();
+            let module_path = ["Fuji", module];
+            let ty = DamlType::make_tycon(arc.main_package_id(), &module_path, entity);
+            let value = arbitrary_value(&mut make_rng(seed), arc, &ty, None, MAX_DEPTH);
+            let json = TypedJsonValueEncoder::new(arc).encode(&value, &ty).unwrap();
+            let bytes = CborValueEncoder::new(arc).encode(&value, &ty).unwrap();
+            prop_assert_eq!(
+                CborValueDecoder::new(arc).decode(&bytes, &ty).unwrap(),
+                JsonValueDecoder::new(arc).decode(&json, &ty).unwrap()
+            );
+        }
+    }
+
+    fn encode_cbor(value: &Value) -> Vec<u8> {
+        let mut bytes = vec![];
+        ciborium::ser::into_writer(value, &mut bytes).unwrap();
+        bytes
+    }
+
+    fn decode_cbor(bytes: &[u8]) -> Value {
+        ciborium::de::from_reader(bytes).unwrap()
+    }
+
+    fn daml_archive() -> &'static DamlArchive<'static> {
+        crate::test_util::daml_archive(TESTING_TYPES_DAR_PATH)
+    }
+}
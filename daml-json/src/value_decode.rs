@@ -1,5 +1,7 @@
 use crate::error::{DamlJsonCodecError, DamlJsonCodecResult};
-use crate::util::{AsSingleSliceExt, Required};
+use crate::util::{
+    collect_results, describe_type, locate, resolve_type, AsSingleSliceExt, Required, TypeArgs, ValuePath,
+};
 use chrono::{offset, Date, DateTime, NaiveDate};
 use daml_grpc::data::value::{DamlEnum, DamlRecord, DamlRecordField, DamlValue, DamlVariant};
 use daml_grpc::data::DamlIdentifier;
@@ -8,18 +10,36 @@ use daml_lf::element;
 use daml_lf::element::{DamlArchive, DamlData, DamlField, DamlType};
 use serde_json::{Map, Value};
 use std::convert::TryFrom;
+use std::iter::FromIterator;
 use std::str::FromStr;
 
 /// Decode a `DamlValue` from JSON.
+///
+/// All decoding failures are reported as [`DamlJsonCodecError::DecodeErrors`] which record the location of the
+/// offending JSON value as a [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) along with the Daml type it
+/// was expected to have.
+///
+/// By default decoding stops at the first error, use [`JsonValueDecoder::with_validate_all`] to collect all errors.
 #[derive(Debug)]
 pub struct JsonValueDecoder<'a> {
     arc: &'a DamlArchive<'a>,
+    validate_all: bool,
 }
 
 impl<'a> JsonValueDecoder<'a> {
     pub const fn new(arc: &'a DamlArchive<'a>) -> Self {
         Self {
             arc,
+            validate_all: false,
+        }
+    }
+
+    /// Sets whether all errors are collected rather than failing on the first error.
+    #[must_use]
+    pub const fn with_validate_all(self, validate_all: bool) -> Self {
+        Self {
+            validate_all,
+            ..self
         }
     }
 
@@ -30,14 +50,60 @@ impl<'a> JsonValueDecoder<'a> {
     ///
     /// [`JsonValueEncoder`]: crate::value_encode::JsonValueEncoder
     pub fn decode(&self, json: &Value, ty: &DamlType<'_>) -> DamlJsonCodecResult<DamlValue> {
-        self.do_decode(json, ty, true)
+        self.decode_at(json, ty, "")
+    }
+
+    /// Recursively decode a GRPC `DamlValue` from a JSON `Value` which is located at the JSON pointer `pointer` within
+    /// some enclosing JSON document.
+    ///
+    /// The `pointer` is used as the prefix of the path of all [`DamlJsonDecodeError`] returned.
+    ///
+    /// [`DamlJsonDecodeError`]: crate::error::DamlJsonDecodeError
+    pub fn decode_at(&self, json: &Value, ty: &DamlType<'_>, pointer: &str) -> DamlJsonCodecResult<DamlValue> {
+        self.do_decode(json, ty, true, None, &ValuePath::Root(pointer))
+    }
+
+    /// Decode a JSON `Value` located at the JSON pointer `pointer` for a `DamlType` which may refer to type parameters
+    /// of some enclosing data types, which are resolved using `type_args`.
+    pub(crate) fn decode_with_type_args<'t>(
+        &self,
+        json: &Value,
+        ty: &'t DamlType<'t>,
+        type_args: Option<&'t TypeArgs<'t>>,
+        pointer: &str,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        self.do_decode(json, ty, true, type_args, &ValuePath::Root(pointer))
     }
 
     /// Perform the decode.
     ///
     /// Here `top_level` refers to whether we are processing a value corresponding to the "top level" of a type or a
     /// nested types.  This is required to support the "shortcut" decoding for optional fields.
-    fn do_decode(&self, json: &Value, ty: &DamlType<'_>, top_level: bool) -> DamlJsonCodecResult<DamlValue> {
+    ///
+    /// The `type_args` are used to resolve any type variables in `ty` which refer to the type parameters of the
+    /// enclosing data type.
+    ///
+    /// Any error which has not already been located is recorded against the `path` of the value being decoded.
+    fn do_decode<'t>(
+        &self,
+        json: &Value,
+        ty: &'t DamlType<'t>,
+        top_level: bool,
+        type_args: Option<&'t TypeArgs<'t>>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        self.decode_type(json, ty, top_level, type_args, path)
+            .map_err(|err| locate(err, path, || describe_type(ty, type_args)))
+    }
+
+    fn decode_type<'t>(
+        &self,
+        json: &Value,
+        ty: &'t DamlType<'t>,
+        top_level: bool,
+        type_args: Option<&'t TypeArgs<'t>>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
         match ty {
             DamlType::Unit =>
                 if json.try_object()?.is_empty() {
@@ -53,78 +119,124 @@ impl<'a> JsonValueDecoder<'a> {
             DamlType::Numeric(_) => Self::decode_numeric(json),
             DamlType::Date => Self::decode_date(json),
             DamlType::Timestamp => Self::decode_timestamp(json),
-            DamlType::List(tys) => Ok(DamlValue::List(
-                json.try_array()?
-                    .iter()
-                    .map(|item| self.do_decode(item, tys.as_single()?, true))
-                    .collect::<DamlJsonCodecResult<Vec<_>>>()?,
-            )),
-            DamlType::TextMap(tys) => Ok(DamlValue::Map(
-                json.try_object()?
-                    .iter()
-                    .map(|(k, v)| Ok((k.clone(), self.do_decode(v, tys.as_single()?, true)?)))
-                    .collect::<DamlJsonCodecResult<DamlTextMap<DamlValue>>>()?,
-            )),
-            DamlType::GenMap(tys) => {
-                let array = json.try_array()?;
-                let genmap = array
-                    .iter()
-                    .map(|item| match item.try_array()?.as_slice() {
-                        [k, v] => Ok((
-                            self.do_decode(k, tys.first().req()?, true)?,
-                            self.do_decode(v, tys.last().req()?, true)?,
-                        )),
-                        _ => Err(DamlJsonCodecError::UnexpectedGenMapTypes),
-                    })
-                    .collect::<DamlJsonCodecResult<DamlGenMap<DamlValue, DamlValue>>>()?;
-
-                // If the resulting GenMap containers fewer entries that the input array then we know that the input
-                // array must have contained duplicate keys and should therefore be rejected.
-                if array.len() == genmap.len() {
-                    Ok(DamlValue::GenMap(genmap))
-                } else {
-                    Err(DamlJsonCodecError::DuplicateGenMapKeys)
-                }
+            DamlType::List(tys) => {
+                let item_ty = tys.as_single()?;
+                Ok(DamlValue::List(
+                    self.collect(
+                        json.try_array()?.iter().enumerate().map(|(i, item)| {
+                            self.do_decode(item, item_ty, true, type_args, &ValuePath::Index(path, i))
+                        }),
+                    )?,
+                ))
+            },
+            DamlType::TextMap(tys) => {
+                let value_ty = tys.as_single()?;
+                Ok(DamlValue::Map(self.collect::<_, DamlTextMap<DamlValue>>(json.try_object()?.iter().map(
+                    |(k, v)| Ok((k.clone(), self.do_decode(v, value_ty, true, type_args, &ValuePath::Key(path, k))?)),
+                ))?))
             },
-            DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) => self.decode_data(
-                json,
-                self.arc
+            DamlType::GenMap(tys) => self.decode_genmap(json, tys, type_args, path),
+            DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) => {
+                let data = self
+                    .arc
                     .data_by_tycon(tycon)
-                    .ok_or_else(|| DamlJsonCodecError::DataNotFound(tycon.tycon().to_string()))?,
-            ),
+                    .ok_or_else(|| DamlJsonCodecError::DataNotFound(tycon.tycon().to_string()))?;
+                self.decode_data(
+                    json,
+                    data,
+                    &TypeArgs::new(data.type_params(), tycon.type_arguments(), type_args),
+                    path,
+                )
+            },
             DamlType::Optional(nested) => {
                 let single = nested.as_single()?;
                 if top_level {
                     if json.is_null() {
                         Ok(DamlValue::Optional(None))
                     } else {
-                        Ok(DamlValue::Optional(Some(Box::new(self.do_decode(json, single, false)?))))
+                        Ok(DamlValue::Optional(Some(Box::new(self.do_decode(json, single, false, type_args, path)?))))
                     }
                 } else {
                     match json.try_array()?.as_slice() {
                         [] => Ok(DamlValue::Optional(None)),
-                        [inner_json] =>
-                            Ok(DamlValue::Optional(Some(Box::new(self.do_decode(inner_json, single, false)?)))),
+                        [inner_json] => Ok(DamlValue::Optional(Some(Box::new(self.do_decode(
+                            inner_json,
+                            single,
+                            false,
+                            type_args,
+                            &ValuePath::Index(path, 0),
+                        )?)))),
                         _ => Err(DamlJsonCodecError::UnexpectedOptionalArrayLength),
                     }
                 }
             },
-            DamlType::Var(_)
-            | DamlType::Nat(_)
-            | DamlType::Arrow
+            DamlType::Var(var) => {
+                let (resolved, parent) = type_args
+                    .and_then(|type_args| type_args.resolve(var))
+                    .ok_or_else(|| DamlJsonCodecError::TypeVarNotFound(var.var().to_string()))?;
+                self.do_decode(json, resolved, top_level, parent, path)
+            },
+            DamlType::Nat(_)
+            | DamlType::Arrow(_)
             | DamlType::Any
             | DamlType::TypeRep
             | DamlType::Bignumeric
             | DamlType::RoundingMode
             | DamlType::AnyException
-            | DamlType::Update
-            | DamlType::Scenario
+            | DamlType::Update(_)
+            | DamlType::Scenario(_)
             | DamlType::Forall(_)
             | DamlType::Struct(_)
             | DamlType::Syn(_) => Err(DamlJsonCodecError::UnsupportedDamlType(ty.name().to_owned())),
         }
     }
 
+    /// Decode a `DamlValue::GenMap` from a JSON array of `[key, value]` entries.
+    fn decode_genmap<'t>(
+        &self,
+        json: &Value,
+        tys: &'t [DamlType<'t>],
+        type_args: Option<&'t TypeArgs<'t>>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        let key_ty = tys.first().req()?;
+        let value_ty = tys.last().req()?;
+        let array = json.try_array()?;
+        let genmap =
+            self.collect::<_, DamlGenMap<DamlValue, DamlValue>>(array.iter().enumerate().map(|(i, item)| {
+                let entry_path = ValuePath::Index(path, i);
+                item.try_array()
+                    .and_then(|entry| match entry.as_slice() {
+                        [k, v] => Ok((
+                            self.do_decode(k, key_ty, true, type_args, &ValuePath::Index(&entry_path, 0))?,
+                            self.do_decode(v, value_ty, true, type_args, &ValuePath::Index(&entry_path, 1))?,
+                        )),
+                        _ => Err(DamlJsonCodecError::UnexpectedGenMapTypes),
+                    })
+                    .map_err(|err| {
+                        locate(err, &entry_path, || {
+                            format!("({}, {})", describe_type(key_ty, type_args), describe_type(value_ty, type_args))
+                        })
+                    })
+            }))?;
+
+        // If the resulting GenMap containers fewer entries that the input array then we know that the input
+        // array must have contained duplicate keys and should therefore be rejected.
+        if array.len() == genmap.len() {
+            Ok(DamlValue::GenMap(genmap))
+        } else {
+            Err(DamlJsonCodecError::DuplicateGenMapKeys)
+        }
+    }
+
+    /// Collect the results of decoding the children of a JSON value.
+    fn collect<T, C: FromIterator<T>>(
+        &self,
+        results: impl Iterator<Item = DamlJsonCodecResult<T>>,
+    ) -> DamlJsonCodecResult<C> {
+        collect_results(self.validate_all, results)
+    }
+
     fn decode_bool(json: &Value) -> DamlJsonCodecResult<DamlValue> {
         Ok(DamlValue::Bool(json.try_bool()?))
     }
@@ -172,11 +284,17 @@ impl<'a> JsonValueDecoder<'a> {
     }
 
     /// Decode a `DamlValue` from a JSON `Value` and `DamlData`.
-    fn decode_data(&self, json: &Value, data: &DamlData<'_>) -> DamlJsonCodecResult<DamlValue> {
+    fn decode_data<'t>(
+        &self,
+        json: &Value,
+        data: &'t DamlData<'t>,
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
         match data {
-            DamlData::Template(template) => self.decode_record(json, template.fields()),
-            DamlData::Record(record) => self.decode_record(json, record.fields()),
-            DamlData::Variant(variant) => self.decode_variant(json, variant.fields()),
+            DamlData::Template(template) => self.decode_record(json, template.fields(), type_args, path),
+            DamlData::Record(record) => self.decode_record(json, record.fields(), type_args, path),
+            DamlData::Variant(variant) => self.decode_variant(json, variant.fields(), type_args, path),
             DamlData::Enum(data_enum) => Self::decode_enum(json, data_enum),
         }
     }
@@ -192,7 +310,13 @@ impl<'a> JsonValueDecoder<'a> {
     }
 
     /// Decode a `DamlValue::Variant` from a JSON `Value` and `DamlVariant` fields.
-    fn decode_variant(&self, json: &Value, constructors: &[DamlField<'_>]) -> DamlJsonCodecResult<DamlValue> {
+    fn decode_variant<'t>(
+        &self,
+        json: &Value,
+        constructors: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
         let object = json.try_object()?;
         let tag = object.get("tag").req()?.try_string()?;
         let value = object.get("value").req()?;
@@ -202,16 +326,22 @@ impl<'a> JsonValueDecoder<'a> {
             .ok_or_else(|| DamlJsonCodecError::UnknownVariantConstructor(tag.to_owned()))?;
         Ok(DamlValue::Variant(DamlVariant::new(
             constructor.name(),
-            Box::new(self.decode(value, constructor.ty())?),
+            Box::new(self.do_decode(value, constructor.ty(), true, Some(type_args), &ValuePath::Key(path, "value"))?),
             None::<DamlIdentifier>,
         )))
     }
 
     /// Decode a `DamlValue::Record` from a JSON `Value` and `DamlRecord` / `DamlTemplate` fields.
-    fn decode_record(&self, json: &Value, lf_fields: &[DamlField<'_>]) -> DamlJsonCodecResult<DamlValue> {
+    fn decode_record<'t>(
+        &self,
+        json: &Value,
+        lf_fields: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
         let fields = match (json.as_object(), json.as_array()) {
-            (Some(obj), None) => self.decode_record_object(obj, lf_fields)?,
-            (None, Some(arr)) => self.decode_record_array(arr, lf_fields)?,
+            (Some(obj), None) => self.decode_record_object(obj, lf_fields, type_args, path)?,
+            (None, Some(arr)) => self.decode_record_array(arr, lf_fields, type_args, path)?,
             _ =>
                 return Err(DamlJsonCodecError::UnexpectedJsonType(
                     "Object or Array".to_owned(),
@@ -221,42 +351,52 @@ impl<'a> JsonValueDecoder<'a> {
         Ok(DamlValue::Record(DamlRecord::new(fields, None::<DamlIdentifier>)))
     }
 
-    fn decode_record_object(
+    fn decode_record_object<'t>(
         &self,
         obj: &Map<String, Value>,
-        lf_fields: &[DamlField<'_>],
+        lf_fields: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
     ) -> DamlJsonCodecResult<Vec<DamlRecordField>> {
-        lf_fields
-            .iter()
-            .map(|field| {
-                let field_name = field.name();
-                let field_ty = field.ty();
-                let field_json = obj.get(field_name);
-                match (field_ty, field_json) {
-                    (DamlType::Optional(_), None) =>
-                        Ok(DamlRecordField::new(Some(field_name), DamlValue::Optional(None))),
-                    (_, Some(json)) => Ok(DamlRecordField::new(Some(field_name), self.decode(json, field_ty)?)),
-                    (_, None) => Err(DamlJsonCodecError::MissingJsonRecordObjectField(field_name.to_owned())),
-                }
-            })
-            .collect::<DamlJsonCodecResult<Vec<DamlRecordField>>>()
+        self.collect(lf_fields.iter().map(|field| {
+            let field_name = field.name();
+            let field_ty = field.ty();
+            let field_json = obj.get(field_name);
+            let field_path = ValuePath::Key(path, field_name);
+            match (resolve_type(field_ty, Some(type_args)), field_json) {
+                (DamlType::Optional(_), None) => Ok(DamlRecordField::new(Some(field_name), DamlValue::Optional(None))),
+                (_, Some(json)) => Ok(DamlRecordField::new(
+                    Some(field_name),
+                    self.do_decode(json, field_ty, true, Some(type_args), &field_path)?,
+                )),
+                (_, None) => Err(locate(
+                    DamlJsonCodecError::MissingJsonRecordObjectField(field_name.to_owned()),
+                    &field_path,
+                    || describe_type(field_ty, Some(type_args)),
+                )),
+            }
+        }))
     }
 
-    fn decode_record_array(
+    fn decode_record_array<'t>(
         &self,
         arr: &[Value],
-        lf_fields: &[DamlField<'_>],
+        lf_fields: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
     ) -> DamlJsonCodecResult<Vec<DamlRecordField>> {
-        lf_fields
-            .iter()
-            .enumerate()
-            .map(|(i, field)| {
-                let json = arr
-                    .get(i)
-                    .ok_or_else(|| DamlJsonCodecError::MissingJsonRecordArrayField(i, field.name().to_owned()))?;
-                Ok(DamlRecordField::new(Some(field.name()), self.decode(json, field.ty())?))
-            })
-            .collect::<DamlJsonCodecResult<Vec<DamlRecordField>>>()
+        self.collect(lf_fields.iter().enumerate().map(|(i, field)| {
+            let field_path = ValuePath::Index(path, i);
+            let json = arr.get(i).ok_or_else(|| {
+                locate(DamlJsonCodecError::MissingJsonRecordArrayField(i, field.name().to_owned()), &field_path, || {
+                    describe_type(field.ty(), Some(type_args))
+                })
+            })?;
+            Ok(DamlRecordField::new(
+                Some(field.name()),
+                self.do_decode(json, field.ty(), true, Some(type_args), &field_path)?,
+            ))
+        }))
     }
 }
 
@@ -925,6 +1065,32 @@ mod tests {
         Ok(())
     }
 
+    /// `{"foo": []}` -> `Oa with foo = Some (None) : data Oa a = Oa with foo: Optional a` where `a` is `Optional Int64`
+    #[test]
+    fn test_record_generic_nested_optional() -> DamlJsonCodecResult<()> {
+        let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+        let json_value = json!({ "foo": [] });
+        let ty = DamlType::make_tycon_with_args(&dar.main.hash, &["Fuji", "JsonTest"], "Oa", vec![DamlType::Optional(
+            vec![DamlType::Int64],
+        )]);
+        let expected = daml_value!({foo: {?={?!}}});
+        let actual = decode_apply(&dar, &json_value, &ty)?;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    /// `{"top": {"middle": {"bottom": 3}}}` -> `Top with top = Middle with middle = Bottom with bottom = 3 : Top`
+    #[test]
+    fn test_record_generic_nested() -> DamlJsonCodecResult<()> {
+        let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+        let json_value = json!({"top": {"middle": {"bottom": 3}}});
+        let ty = DamlType::make_tycon(&dar.main.hash, &["Fuji", "JsonTest"], "Top");
+        let expected = daml_value!({top: {middle: {bottom: 3}}});
+        let actual = decode_apply(&dar, &json_value, &ty)?;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
     /// `[]` -> `[] : [Text]`
     #[test]
     fn test_list_bool_empty() -> DamlJsonCodecResult<()> {
@@ -1225,6 +1391,92 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_error_path_list() {
+        let json_value = json!([1, "x", 3, true]);
+        let ty = DamlType::List(vec![DamlType::Int64]);
+        let actual = JsonValueDecoder::new(&DamlArchive::default()).decode(&json_value, &ty);
+        assert_eq!(error_locations(actual), vec![(String::from("/1"), String::from("Int"))]);
+    }
+
+    #[test]
+    fn test_error_path_validate_all_list() {
+        let json_value = json!([1, "x", 3, true]);
+        let ty = DamlType::List(vec![DamlType::Int64]);
+        let actual = JsonValueDecoder::new(&DamlArchive::default()).with_validate_all(true).decode(&json_value, &ty);
+        assert_eq!(error_locations(actual), vec![
+            (String::from("/1"), String::from("Int")),
+            (String::from("/3"), String::from("Int"))
+        ]);
+    }
+
+    #[test]
+    fn test_error_path_escaped_textmap_key() {
+        let json_value = json!({"a/b": 1, "c~d": [true]});
+        let ty = DamlType::TextMap(vec![DamlType::Optional(vec![DamlType::Optional(vec![DamlType::Int64])])]);
+        let actual = JsonValueDecoder::new(&DamlArchive::default()).with_validate_all(true).decode(&json_value, &ty);
+        assert_eq!(error_locations(actual), vec![
+            (String::from("/a~1b"), String::from("Optional Int")),
+            (String::from("/c~0d/0"), String::from("Int"))
+        ]);
+    }
+
+    #[test]
+    fn test_error_path_genmap_entry() {
+        let json_value = json!([[1, "foo"], [2], [true, "bar"]]);
+        let ty = DamlType::GenMap(vec![DamlType::Int64, DamlType::Text]);
+        let actual = JsonValueDecoder::new(&DamlArchive::default()).with_validate_all(true).decode(&json_value, &ty);
+        assert_eq!(error_locations(actual), vec![
+            (String::from("/1"), String::from("(Int, Text)")),
+            (String::from("/2/0"), String::from("Int"))
+        ]);
+    }
+
+    #[test]
+    fn test_error_path_record_validate_all() -> DamlJsonCodecResult<()> {
+        let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+        let json_value = json!({"name": 1});
+        let ty = DamlType::make_tycon(&dar.main.hash, &["Fuji", "JsonTest"], "Person");
+        let actual = dar
+            .apply(|arc| JsonValueDecoder::new(arc).with_validate_all(true).decode_at(&json_value, &ty, "/payload"))?;
+        assert_eq!(error_locations(actual), vec![
+            (String::from("/payload/name"), String::from("Text")),
+            (String::from("/payload/age"), String::from("Int"))
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_path_generic_record() -> DamlJsonCodecResult<()> {
+        let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+        let json_value = json!({"foo": [["x"]]});
+        let ty = DamlType::make_tycon_with_args(&dar.main.hash, &["Fuji", "JsonTest"], "Oa", vec![DamlType::Optional(
+            vec![DamlType::List(vec![DamlType::Int64])],
+        )]);
+        let actual = decode_apply(&dar, &json_value, &ty);
+        assert_eq!(error_locations(actual), vec![(String::from("/foo/0/0"), String::from("Int"))]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_message() {
+        let json_value = json!({"a": true});
+        let ty = DamlType::TextMap(vec![DamlType::Text]);
+        let actual = JsonValueDecoder::new(&DamlArchive::default()).decode_at(&json_value, &ty, "/payload");
+        assert_eq!(
+            actual.map_err(|err| err.to_string()),
+            Err(String::from("expected JSON type String but found type Bool at '/payload/a' (expected Text)"))
+        );
+    }
+
+    fn error_locations(result: DamlJsonCodecResult<DamlValue>) -> Vec<(String, String)> {
+        match result {
+            Err(DamlJsonCodecError::DecodeErrors(errors)) =>
+                errors.into_iter().map(|err| (err.path, err.expected)).collect(),
+            other => panic!("expected decode errors, found {other:?}"),
+        }
+    }
+
     fn decode_apply(dar: &DarFile, json_value: &Value, ty: &DamlType<'_>) -> DamlJsonCodecResult<DamlValue> {
         dar.apply(|arc| {
             let decoded_value = JsonValueDecoder::new(arc).decode(json_value, ty)?;
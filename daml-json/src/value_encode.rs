@@ -1,9 +1,12 @@
-use crate::error::DamlJsonCodecResult;
-use crate::util::Required;
+use crate::error::{DamlJsonCodecError, DamlJsonCodecResult};
+use crate::util::{check_numeric, resolve_scale, resolve_type, AsSingleSliceExt, Required, TypeArgs};
 use bigdecimal::ToPrimitive;
 use chrono::SecondsFormat;
-use daml_grpc::data::value::{DamlRecord, DamlValue};
-use serde_json::{json, Value};
+use daml_grpc::data::value::{DamlEnum, DamlRecord, DamlRecordField, DamlValue, DamlVariant};
+use daml_grpc::primitive_types::DamlNumeric;
+use daml_lf::element;
+use daml_lf::element::{DamlArchive, DamlData, DamlField, DamlType};
+use serde_json::{json, Map, Value};
 use std::collections::HashMap;
 
 /// Encode a `DamlValue` as JSON.
@@ -123,17 +126,234 @@ impl JsonValueEncoder {
     }
 }
 
+/// Encode a `DamlValue` as JSON for a given `DamlType`.
+///
+/// Unlike the [`JsonValueEncoder`], which works purely from the `DamlValue`, this encoder is directed by the
+/// `DamlType` and the data types of the `DamlArchive` and is the inverse of the [`JsonValueDecoder`].  The `DamlValue`
+/// is checked against the `DamlType` and is encoded exactly as described in the
+/// [Daml LF JSON Encoding specification](https://docs.daml.com/json-api/lf-value-specification.html):
+///
+/// - `Int64` and `Numeric` values are always encoded as JSON strings and so no precision is lost
+/// - `Numeric` values are checked against the scale of the `Numeric` type
+/// - `Optional` values nested within another `Optional` are encoded as JSON arrays, including where the nesting arises
+///   from a type argument
+/// - records are always encoded as JSON objects with `None` optional fields omitted
+///
+/// [`JsonValueDecoder`]: crate::value_decode::JsonValueDecoder
+#[derive(Debug)]
+pub struct TypedJsonValueEncoder<'a> {
+    arc: &'a DamlArchive<'a>,
+}
+
+impl<'a> TypedJsonValueEncoder<'a> {
+    pub const fn new(arc: &'a DamlArchive<'a>) -> Self {
+        Self {
+            arc,
+        }
+    }
+
+    /// Recursively encode a GRPC `DamlValue` of a given `DamlType` as a JSON `Value`.
+    pub fn encode(&self, value: &DamlValue, ty: &DamlType<'_>) -> DamlJsonCodecResult<Value> {
+        self.do_encode(value, ty, true, None)
+    }
+
+    /// Perform the encode.
+    ///
+    /// See [`JsonValueEncoder`] for a description of `top_level`.  The `type_args` are used to resolve any type
+    /// variables in `ty` which refer to the type parameters of the enclosing data type.
+    fn do_encode<'t>(
+        &self,
+        value: &DamlValue,
+        ty: &'t DamlType<'t>,
+        top_level: bool,
+        type_args: Option<&'t TypeArgs<'t>>,
+    ) -> DamlJsonCodecResult<Value> {
+        match (ty, value) {
+            (DamlType::Unit, DamlValue::Unit) => Ok(json!({})),
+            (DamlType::Bool, DamlValue::Bool(b)) => Ok(json!(b)),
+            (DamlType::Int64, DamlValue::Int64(i)) => Ok(json!(i.to_string())),
+            (DamlType::Numeric(args), DamlValue::Numeric(n)) =>
+                Self::encode_numeric(n, resolve_scale(args, type_args)?),
+            (DamlType::Text, DamlValue::Text(text)) => Ok(json!(text)),
+            (DamlType::Party, DamlValue::Party(party)) => Ok(json!(party.party)),
+            (DamlType::ContractId(_), DamlValue::ContractId(id)) => Ok(json!(id.contract_id)),
+            (DamlType::Date, DamlValue::Date(date)) => Ok(json!(date.naive_utc().to_string())),
+            (DamlType::Timestamp, DamlValue::Timestamp(timestamp)) =>
+                Ok(json!(timestamp.to_rfc3339_opts(SecondsFormat::Millis, true))),
+            (DamlType::List(tys), DamlValue::List(list)) => {
+                let ty = tys.as_single()?;
+                Ok(Value::Array(
+                    list.iter()
+                        .map(|item| self.do_encode(item, ty, true, type_args))
+                        .collect::<DamlJsonCodecResult<Vec<_>>>()?,
+                ))
+            },
+            (DamlType::TextMap(tys), DamlValue::Map(map)) => {
+                let ty = tys.as_single()?;
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_unstable_by_key(|(k, _)| *k);
+                Ok(Value::Object(
+                    entries
+                        .into_iter()
+                        .map(|(k, v)| Ok((k.clone(), self.do_encode(v, ty, true, type_args)?)))
+                        .collect::<DamlJsonCodecResult<Map<_, _>>>()?,
+                ))
+            },
+            (DamlType::GenMap(tys), DamlValue::GenMap(map)) => match tys.as_slice() {
+                [key_ty, value_ty] => Ok(Value::Array(
+                    map.iter()
+                        .map(|(k, v)| {
+                            Ok(json!([
+                                self.do_encode(k, key_ty, true, type_args)?,
+                                self.do_encode(v, value_ty, true, type_args)?
+                            ]))
+                        })
+                        .collect::<DamlJsonCodecResult<Vec<_>>>()?,
+                )),
+                _ => Err(DamlJsonCodecError::UnexpectedGenMapTypes),
+            },
+            (DamlType::Optional(tys), DamlValue::Optional(opt)) => {
+                let ty = tys.as_single()?;
+                match opt {
+                    None if top_level => Ok(json!(null)),
+                    None => Ok(json!([])),
+                    Some(inner) if top_level => self.do_encode(inner, ty, false, type_args),
+                    Some(inner) => Ok(json!([self.do_encode(inner, ty, false, type_args)?])),
+                }
+            },
+            (DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon), _) => {
+                let data = self
+                    .arc
+                    .data_by_tycon(tycon)
+                    .ok_or_else(|| DamlJsonCodecError::DataNotFound(tycon.tycon().to_string()))?;
+                self.encode_data(value, data, &TypeArgs::new(data.type_params(), tycon.type_arguments(), type_args))
+            },
+            (DamlType::Var(var), _) => {
+                let (resolved, parent) = type_args
+                    .and_then(|type_args| type_args.resolve(var))
+                    .ok_or_else(|| DamlJsonCodecError::TypeVarNotFound(var.var().to_string()))?;
+                self.do_encode(value, resolved, top_level, parent)
+            },
+            (
+                DamlType::Nat(_)
+                | DamlType::Arrow(_)
+                | DamlType::Any
+                | DamlType::TypeRep
+                | DamlType::Bignumeric
+                | DamlType::RoundingMode
+                | DamlType::AnyException
+                | DamlType::Update(_)
+                | DamlType::Scenario(_)
+                | DamlType::Forall(_)
+                | DamlType::Struct(_)
+                | DamlType::Syn(_),
+                _,
+            ) => Err(DamlJsonCodecError::UnsupportedDamlType(ty.name().to_owned())),
+            _ => Err(DamlJsonCodecError::UnexpectedDamlValue(ty.name().to_owned(), value.variant_name().to_owned())),
+        }
+    }
+
+    /// Encode a `DamlValue` for a given `DamlData`.
+    fn encode_data<'t>(
+        &self,
+        value: &DamlValue,
+        data: &'t DamlData<'t>,
+        type_args: &'t TypeArgs<'t>,
+    ) -> DamlJsonCodecResult<Value> {
+        match (data, value) {
+            (DamlData::Template(template), DamlValue::Record(record)) =>
+                self.encode_record(record, template.fields(), type_args),
+            (DamlData::Record(data_record), DamlValue::Record(record)) =>
+                self.encode_record(record, data_record.fields(), type_args),
+            (DamlData::Variant(data_variant), DamlValue::Variant(variant)) =>
+                self.encode_variant(variant, data_variant.fields(), type_args),
+            (DamlData::Enum(data_enum), DamlValue::Enum(value_enum)) => Self::encode_enum(value_enum, data_enum),
+            _ => Err(DamlJsonCodecError::UnexpectedDamlValue(data.name().to_owned(), value.variant_name().to_owned())),
+        }
+    }
+
+    /// Encode a `DamlRecord` as a JSON object for the given `DamlRecord` / `DamlTemplate` fields.
+    ///
+    /// If every field of the `DamlRecord` is labelled then fields are matched by label, otherwise they are matched by
+    /// position.  Fields of `Optional` type which are `None` are omitted.
+    fn encode_record<'t>(
+        &self,
+        record: &DamlRecord,
+        lf_fields: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+    ) -> DamlJsonCodecResult<Value> {
+        let has_labels = record.fields().iter().all(|f| f.label().is_some());
+        let mut fields = Map::new();
+        for (i, lf_field) in lf_fields.iter().enumerate() {
+            let field_value = if has_labels {
+                record.fields().iter().find(|f| f.label().as_deref() == Some(lf_field.name()))
+            } else {
+                record.fields().get(i)
+            }
+            .map(DamlRecordField::value)
+            .ok_or_else(|| DamlJsonCodecError::MissingDamlRecordField(lf_field.name().to_owned()))?;
+            let is_omitted = matches!(
+                (resolve_type(lf_field.ty(), Some(type_args)), field_value),
+                (DamlType::Optional(_), DamlValue::Optional(None))
+            );
+            if !is_omitted {
+                fields.insert(
+                    lf_field.name().to_owned(),
+                    self.do_encode(field_value, lf_field.ty(), true, Some(type_args))?,
+                );
+            }
+        }
+        Ok(Value::Object(fields))
+    }
+
+    /// Encode a `DamlVariant` as a JSON object with `tag` and `value` for the given `DamlVariant` constructors.
+    fn encode_variant<'t>(
+        &self,
+        variant: &DamlVariant,
+        constructors: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+    ) -> DamlJsonCodecResult<Value> {
+        let constructor = constructors
+            .iter()
+            .find(|&field| field.name() == variant.constructor())
+            .ok_or_else(|| DamlJsonCodecError::UnknownVariantConstructor(variant.constructor().to_owned()))?;
+        let value = self.do_encode(variant.value(), constructor.ty(), true, Some(type_args))?;
+        Ok(json!({"tag": constructor.name(), "value": value}))
+    }
+
+    /// Encode a `DamlEnum` as a JSON string for the given `DamlEnum` constructors.
+    fn encode_enum(value_enum: &DamlEnum, data_enum: &element::DamlEnum<'_>) -> DamlJsonCodecResult<Value> {
+        let constructor = value_enum.constructor();
+        if data_enum.constructors().any(|c| c == constructor) {
+            Ok(json!(constructor))
+        } else {
+            Err(DamlJsonCodecError::UnknownEnumConstructor(constructor.to_owned()))
+        }
+    }
+
+    /// Encode a `DamlNumeric` as a JSON string, checking that it can be represented with the given `scale`.
+    fn encode_numeric(numeric: &DamlNumeric, scale: u8) -> DamlJsonCodecResult<Value> {
+        check_numeric(numeric, scale)?;
+        Ok(json!(numeric.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{DamlJsonCodecResult, DamlValue, JsonValueEncoder};
+    use super::{DamlJsonCodecResult, DamlValue, JsonValueEncoder, TypedJsonValueEncoder};
+    use crate::error::DamlJsonCodecError;
     use crate::util::Required;
-    use crate::value_decode::JsonTryAsExt;
+    use crate::value_decode::{JsonTryAsExt, JsonValueDecoder};
     use daml::macros::daml_value;
     use daml_grpc::data::value::{DamlEnum, DamlRecord, DamlRecordField};
     use daml_grpc::data::DamlIdentifier;
-    use daml_grpc::primitive_types::DamlTextMap;
+    use daml_grpc::primitive_types::{DamlNumeric, DamlTextMap};
+    use daml_lf::element::{DamlArchive, DamlType};
     use maplit::hashmap;
     use serde_json::{json, Value};
+    use std::str::FromStr;
+
+    static TESTING_TYPES_DAR_PATH: &str = "../resources/testing_types_sandbox/TestingTypes-latest.dar";
 
     #[test]
     fn test_unit() -> DamlJsonCodecResult<()> {
@@ -730,6 +950,194 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_typed_int64() -> DamlJsonCodecResult<()> {
+        let grpc_value = daml_value!(-42);
+        let expected = json!("-42");
+        let actual = TypedJsonValueEncoder::new(&DamlArchive::default()).encode(&grpc_value, &DamlType::Int64)?;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_numeric_lossless() -> DamlJsonCodecResult<()> {
+        let grpc_value = DamlValue::new_numeric(DamlNumeric::from_str("0.1234567890123456789012345678901234567")?);
+        let ty = DamlType::Numeric(vec![DamlType::Nat(37)]);
+        let expected = json!("0.1234567890123456789012345678901234567");
+        let actual = TypedJsonValueEncoder::new(&DamlArchive::default()).encode(&grpc_value, &ty)?;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_numeric_scale_exceeded() -> DamlJsonCodecResult<()> {
+        let grpc_value = DamlValue::new_numeric(DamlNumeric::from_str("1.12345678901")?);
+        let ty = DamlType::Numeric(vec![DamlType::Nat(10)]);
+        let actual = TypedJsonValueEncoder::new(&DamlArchive::default()).encode(&grpc_value, &ty);
+        assert!(matches!(actual, Err(DamlJsonCodecError::NumericOutOfRange(_, 10))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_numeric_precision_exceeded() -> DamlJsonCodecResult<()> {
+        let grpc_value = DamlValue::new_numeric(DamlNumeric::from_str("12345678901234567890123456789")?);
+        let ty = DamlType::Numeric(vec![DamlType::Nat(10)]);
+        let actual = TypedJsonValueEncoder::new(&DamlArchive::default()).encode(&grpc_value, &ty);
+        assert!(matches!(actual, Err(DamlJsonCodecError::NumericOutOfRange(_, 10))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_numeric_trailing_zeros() -> DamlJsonCodecResult<()> {
+        let grpc_value = DamlValue::new_numeric(DamlNumeric::from_str("1.50000000000000")?);
+        let ty = DamlType::Numeric(vec![DamlType::Nat(10)]);
+        let actual = TypedJsonValueEncoder::new(&DamlArchive::default()).encode(&grpc_value, &ty)?;
+        assert_eq!(actual, json!("1.50000000000000"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_unexpected_value() {
+        let grpc_value = daml_value!(true);
+        let actual = TypedJsonValueEncoder::new(&DamlArchive::default()).encode(&grpc_value, &DamlType::Int64);
+        assert!(matches!(actual, Err(DamlJsonCodecError::UnexpectedDamlValue(_, _))));
+    }
+
+    #[test]
+    fn test_typed_list_of_optional() -> DamlJsonCodecResult<()> {
+        let grpc_value = daml_value![[{?=1}, {?!}]];
+        let ty = DamlType::List(vec![DamlType::Optional(vec![DamlType::Int64])]);
+        let expected = json!(["1", null]);
+        let actual = TypedJsonValueEncoder::new(&DamlArchive::default()).encode(&grpc_value, &ty)?;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_record_depth2() -> DamlJsonCodecResult<()> {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Depth2");
+        let encoder = TypedJsonValueEncoder::new(arc);
+        assert_eq!(encoder.encode(&daml_value!({foo: {?!}}), &ty)?, json!({}));
+        assert_eq!(encoder.encode(&daml_value!({foo: {?={?!}}}), &ty)?, json!({"foo": []}));
+        assert_eq!(encoder.encode(&daml_value!({foo: {?={?=42}}}), &ty)?, json!({"foo": ["42"]}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_record_unlabelled() -> DamlJsonCodecResult<()> {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Person");
+        let grpc_value = DamlValue::Record(DamlRecord::new(
+            vec![DamlRecordField::new(None::<String>, daml_value!("Alice")), DamlRecordField::new(None::<String>, 42)],
+            None::<DamlIdentifier>,
+        ));
+        let actual = TypedJsonValueEncoder::new(arc).encode(&grpc_value, &ty)?;
+        assert_eq!(actual, json!({"name": "Alice", "age": "42"}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_record_missing_field() {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Person");
+        let actual = TypedJsonValueEncoder::new(arc).encode(&daml_value!({name: "Alice"}), &ty);
+        assert!(matches!(actual, Err(DamlJsonCodecError::MissingDamlRecordField(_))));
+    }
+
+    /// `Oa (Optional Int64)` where `data Oa a = Oa with foo: Optional a`
+    #[test]
+    fn test_typed_nested_optional_from_type_argument() -> DamlJsonCodecResult<()> {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon_with_args(arc.main_package_id(), &["Fuji", "JsonTest"], "Oa", vec![
+            DamlType::Optional(vec![DamlType::Int64]),
+        ]);
+        let encoder = TypedJsonValueEncoder::new(arc);
+        assert_eq!(encoder.encode(&daml_value!({foo: {?!}}), &ty)?, json!({}));
+        assert_eq!(encoder.encode(&daml_value!({foo: {?={?!}}}), &ty)?, json!({"foo": []}));
+        assert_eq!(encoder.encode(&daml_value!({foo: {?={?=42}}}), &ty)?, json!({"foo": ["42"]}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_generic_record() -> DamlJsonCodecResult<()> {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Top");
+        let grpc_value = daml_value!({top: {middle: {bottom: 3}}});
+        let actual = TypedJsonValueEncoder::new(arc).encode(&grpc_value, &ty)?;
+        assert_eq!(actual, json!({"top": {"middle": {"bottom": "3"}}}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_variant_and_enum() -> DamlJsonCodecResult<()> {
+        let arc = daml_archive();
+        let foo_ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Foo");
+        let status_ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Status");
+        let encoder = TypedJsonValueEncoder::new(arc);
+        assert_eq!(encoder.encode(&daml_value![{=>Bar 42}], &foo_ty)?, json!({"tag": "Bar", "value": "42"}));
+        assert_eq!(encoder.encode(&daml_value![{=>Baz}], &foo_ty)?, json!({"tag": "Baz", "value": {}}));
+        assert_eq!(encoder.encode(&daml_value![{=>Quux {?!}}], &foo_ty)?, json!({"tag": "Quux", "value": null}));
+        assert!(matches!(
+            encoder.encode(&daml_value![{=>Unknown 1}], &foo_ty),
+            Err(DamlJsonCodecError::UnknownVariantConstructor(_))
+        ));
+        let enabled = DamlValue::Enum(DamlEnum::new("Enabled", None));
+        let unknown = DamlValue::Enum(DamlEnum::new("Unknown", None));
+        assert_eq!(encoder.encode(&enabled, &status_ty)?, json!("Enabled"));
+        assert!(matches!(encoder.encode(&unknown, &status_ty), Err(DamlJsonCodecError::UnknownEnumConstructor(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_genmap() -> DamlJsonCodecResult<()> {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "PersonMap");
+        let grpc_value = DamlValue::Record(DamlRecord::new(
+            vec![DamlRecordField::new(
+                Some("m"),
+                DamlValue::GenMap(
+                    vec![(daml_value![{name: "John", age: 29}], daml_value!["foo"])].into_iter().collect(),
+                ),
+            )],
+            None::<DamlIdentifier>,
+        ));
+        let actual = TypedJsonValueEncoder::new(arc).encode(&grpc_value, &ty)?;
+        assert_eq!(actual, json!({"m": [[{"name": "John", "age": "29"}, "foo"]]}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_roundtrip() -> DamlJsonCodecResult<()> {
+        let arc = daml_archive();
+        let package_id = arc.main_package_id();
+        let all_cases = vec![
+            (DamlType::make_tycon(package_id, &["Fuji", "JsonTest"], "Depth2"), daml_value!({foo: {?={?!}}})),
+            (DamlType::make_tycon(package_id, &["Fuji", "JsonTest"], "Top"), daml_value!({top: {middle: {bottom: 3}}})),
+            (DamlType::make_tycon(package_id, &["Fuji", "JsonTest"], "Foo"), daml_value![{=>Quux {?=1}}]),
+            (
+                DamlType::make_tycon_with_args(package_id, &["Fuji", "JsonTest"], "Oa", vec![DamlType::Optional(
+                    vec![DamlType::Int64],
+                )]),
+                daml_value!({foo: {?={?=42}}}),
+            ),
+            (
+                DamlType::Numeric(vec![DamlType::Nat(10)]),
+                DamlValue::new_numeric(DamlNumeric::from_str("9999999999999999999999999999.9999999999")?),
+            ),
+        ];
+        for (ty, value) in all_cases {
+            let json = TypedJsonValueEncoder::new(arc).encode(&value, &ty)?;
+            let decoded = JsonValueDecoder::new(arc).decode(&json, &ty)?;
+            assert_eq!(decoded, value);
+        }
+        Ok(())
+    }
+
+    fn daml_archive() -> &'static DamlArchive<'static> {
+        crate::test_util::daml_archive(TESTING_TYPES_DAR_PATH)
+    }
+
     fn find_genmap_value<'a>(genmap: &'a Value, key: &Value) -> DamlJsonCodecResult<&'a Value> {
         Ok(genmap
             .try_array()?
@@ -0,0 +1,649 @@
+use crate::error::{DamlJsonCodecError, DamlJsonCodecResult};
+use crate::lf_value::com::daml::lf::value as lf;
+use crate::lf_value::com::daml::lf::value::value::{gen_map, text_map, Sum};
+use crate::util::{
+    check_numeric, collect_results, date_from_days, days_from_date, describe_type, locate, micros_from_timestamp,
+    resolve_scale, resolve_type, timestamp_from_micros, AsSingleSliceExt, Required, TypeArgs, ValuePath,
+};
+use daml_grpc::data::value::{DamlEnum, DamlRecord, DamlRecordField, DamlValue, DamlVariant};
+use daml_grpc::data::DamlIdentifier;
+use daml_grpc::primitive_types::{DamlGenMap, DamlNumeric, DamlTextMap};
+use daml_lf::element;
+use daml_lf::element::{DamlArchive, DamlData, DamlField, DamlType};
+use prost::Message;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+/// Encode a `DamlValue` as a Daml-LF value protobuf (`com.daml.lf.value.Value`).
+///
+/// The encoding is directed by the `DamlType` of the value and so requires a `DamlArchive`.  Note that:
+///
+/// - records are encoded positionally, without field labels or type identifiers
+/// - `Numeric` values are encoded as strings and must be representable with the scale of their type
+/// - `Date` values are encoded as days since the unix epoch
+/// - `Timestamp` values are encoded as microseconds since the unix epoch, any sub-microsecond precision is truncated
+///
+/// This is the inverse of the [`ProtoValueDecoder`].
+#[derive(Debug)]
+pub struct ProtoValueEncoder<'a> {
+    arc: &'a DamlArchive<'a>,
+}
+
+impl<'a> ProtoValueEncoder<'a> {
+    pub const fn new(arc: &'a DamlArchive<'a>) -> Self {
+        Self {
+            arc,
+        }
+    }
+
+    /// Recursively encode a GRPC `DamlValue` of a given `DamlType` as Daml-LF value protobuf bytes.
+    pub fn encode(&self, value: &DamlValue, ty: &DamlType<'_>) -> DamlJsonCodecResult<Vec<u8>> {
+        Ok(self.do_encode(value, ty, None)?.encode_to_vec())
+    }
+
+    fn do_encode<'t>(
+        &self,
+        value: &DamlValue,
+        ty: &'t DamlType<'t>,
+        type_args: Option<&'t TypeArgs<'t>>,
+    ) -> DamlJsonCodecResult<lf::Value> {
+        let sum = match (ty, value) {
+            (DamlType::Unit, DamlValue::Unit) => Sum::Unit(()),
+            (DamlType::Bool, DamlValue::Bool(b)) => Sum::Bool(*b),
+            (DamlType::Int64, DamlValue::Int64(i)) => Sum::Int64(*i),
+            (DamlType::Numeric(args), DamlValue::Numeric(n)) => {
+                check_numeric(n, resolve_scale(args, type_args)?)?;
+                Sum::Numeric(n.to_string())
+            },
+            (DamlType::Text, DamlValue::Text(text)) => Sum::Text(text.clone()),
+            (DamlType::Party, DamlValue::Party(party)) => Sum::Party(party.party.clone()),
+            (DamlType::ContractId(_), DamlValue::ContractId(id)) => Sum::ContractId(id.contract_id.clone()),
+            (DamlType::Date, DamlValue::Date(date)) => Sum::Date(days_from_date(*date)),
+            (DamlType::Timestamp, DamlValue::Timestamp(timestamp)) => Sum::Timestamp(micros_from_timestamp(timestamp)),
+            (DamlType::List(tys), DamlValue::List(list)) => {
+                let ty = tys.as_single()?;
+                Sum::List(lf::value::List {
+                    elements: list
+                        .iter()
+                        .map(|item| self.do_encode(item, ty, type_args))
+                        .collect::<DamlJsonCodecResult<Vec<_>>>()?,
+                })
+            },
+            (DamlType::TextMap(tys), DamlValue::Map(map)) => {
+                let ty = tys.as_single()?;
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_unstable_by_key(|(k, _)| *k);
+                Sum::TextMap(lf::value::TextMap {
+                    entries: entries
+                        .into_iter()
+                        .map(|(k, v)| {
+                            Ok(text_map::Entry {
+                                key: k.clone(),
+                                value: Some(self.do_encode(v, ty, type_args)?),
+                            })
+                        })
+                        .collect::<DamlJsonCodecResult<Vec<_>>>()?,
+                })
+            },
+            (DamlType::GenMap(tys), DamlValue::GenMap(map)) => match tys.as_slice() {
+                [key_ty, value_ty] => Sum::GenMap(lf::value::GenMap {
+                    entries: map
+                        .iter()
+                        .map(|(k, v)| {
+                            Ok(gen_map::Entry {
+                                key: Some(self.do_encode(k, key_ty, type_args)?),
+                                value: Some(self.do_encode(v, value_ty, type_args)?),
+                            })
+                        })
+                        .collect::<DamlJsonCodecResult<Vec<_>>>()?,
+                }),
+                _ => return Err(DamlJsonCodecError::UnexpectedGenMapTypes),
+            },
+            (DamlType::Optional(tys), DamlValue::Optional(opt)) => {
+                let ty = tys.as_single()?;
+                Sum::Optional(Box::new(lf::value::Optional {
+                    value: opt.as_ref().map(|inner| self.do_encode(inner, ty, type_args).map(Box::new)).transpose()?,
+                }))
+            },
+            (DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon), _) => {
+                let data = self
+                    .arc
+                    .data_by_tycon(tycon)
+                    .ok_or_else(|| DamlJsonCodecError::DataNotFound(tycon.tycon().to_string()))?;
+                self.encode_data(value, data, &TypeArgs::new(data.type_params(), tycon.type_arguments(), type_args))?
+            },
+            (DamlType::Var(var), _) => {
+                let (resolved, parent) = type_args
+                    .and_then(|type_args| type_args.resolve(var))
+                    .ok_or_else(|| DamlJsonCodecError::TypeVarNotFound(var.var().to_string()))?;
+                return self.do_encode(value, resolved, parent);
+            },
+            (
+                DamlType::Nat(_)
+                | DamlType::Arrow(_)
+                | DamlType::Any
+                | DamlType::TypeRep
+                | DamlType::Bignumeric
+                | DamlType::RoundingMode
+                | DamlType::AnyException
+                | DamlType::Update(_)
+                | DamlType::Scenario(_)
+                | DamlType::Forall(_)
+                | DamlType::Struct(_)
+                | DamlType::Syn(_),
+                _,
+            ) => return Err(DamlJsonCodecError::UnsupportedDamlType(ty.name().to_owned())),
+            _ =>
+                return Err(DamlJsonCodecError::UnexpectedDamlValue(
+                    ty.name().to_owned(),
+                    value.variant_name().to_owned(),
+                )),
+        };
+        Ok(lf::Value {
+            sum: Some(sum),
+        })
+    }
+
+    /// Encode a `DamlValue` for a given `DamlData`.
+    fn encode_data<'t>(
+        &self,
+        value: &DamlValue,
+        data: &'t DamlData<'t>,
+        type_args: &'t TypeArgs<'t>,
+    ) -> DamlJsonCodecResult<Sum> {
+        match (data, value) {
+            (DamlData::Template(template), DamlValue::Record(record)) =>
+                self.encode_record(record, template.fields(), type_args),
+            (DamlData::Record(data_record), DamlValue::Record(record)) =>
+                self.encode_record(record, data_record.fields(), type_args),
+            (DamlData::Variant(data_variant), DamlValue::Variant(variant)) =>
+                self.encode_variant(variant, data_variant.fields(), type_args),
+            (DamlData::Enum(data_enum), DamlValue::Enum(value_enum)) => Self::encode_enum(value_enum, data_enum),
+            _ => Err(DamlJsonCodecError::UnexpectedDamlValue(data.name().to_owned(), value.variant_name().to_owned())),
+        }
+    }
+
+    /// Encode a `DamlRecord` positionally in the order of the given `DamlRecord` / `DamlTemplate` fields.
+    ///
+    /// If every field of the `DamlRecord` is labelled then fields are matched by label, otherwise they are matched by
+    /// position.
+    fn encode_record<'t>(
+        &self,
+        record: &DamlRecord,
+        lf_fields: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+    ) -> DamlJsonCodecResult<Sum> {
+        let has_labels = record.fields().iter().all(|f| f.label().is_some());
+        let fields = lf_fields
+            .iter()
+            .enumerate()
+            .map(|(i, lf_field)| {
+                let field_value = if has_labels {
+                    record.fields().iter().find(|f| f.label().as_deref() == Some(lf_field.name()))
+                } else {
+                    record.fields().get(i)
+                }
+                .map(DamlRecordField::value)
+                .ok_or_else(|| DamlJsonCodecError::MissingDamlRecordField(lf_field.name().to_owned()))?;
+                self.do_encode(field_value, lf_field.ty(), Some(type_args))
+            })
+            .collect::<DamlJsonCodecResult<Vec<_>>>()?;
+        Ok(Sum::Record(lf::value::Record {
+            fields,
+        }))
+    }
+
+    /// Encode a `DamlVariant` for the given `DamlVariant` constructors.
+    fn encode_variant<'t>(
+        &self,
+        variant: &DamlVariant,
+        constructors: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+    ) -> DamlJsonCodecResult<Sum> {
+        let constructor = constructors
+            .iter()
+            .find(|&field| field.name() == variant.constructor())
+            .ok_or_else(|| DamlJsonCodecError::UnknownVariantConstructor(variant.constructor().to_owned()))?;
+        let value = self.do_encode(variant.value(), constructor.ty(), Some(type_args))?;
+        Ok(Sum::Variant(Box::new(lf::value::Variant {
+            constructor: constructor.name().to_owned(),
+            value: Some(Box::new(value)),
+        })))
+    }
+
+    /// Encode a `DamlEnum` for the given `DamlEnum` constructors.
+    fn encode_enum(value_enum: &DamlEnum, data_enum: &element::DamlEnum<'_>) -> DamlJsonCodecResult<Sum> {
+        let constructor = value_enum.constructor();
+        if data_enum.constructors().any(|c| c == constructor) {
+            Ok(Sum::Enum(lf::value::Enum {
+                value: constructor.to_owned(),
+            }))
+        } else {
+            Err(DamlJsonCodecError::UnknownEnumConstructor(constructor.to_owned()))
+        }
+    }
+}
+
+/// Decode a `DamlValue` from a Daml-LF value protobuf (`com.daml.lf.value.Value`).
+///
+/// Decoding failures are reported as [`DamlJsonCodecError::DecodeErrors`] in the same way as for the
+/// [`JsonValueDecoder`], the path of each error refers to record fields by name.
+///
+/// Trailing record fields of `Optional` type may be omitted and are decoded as `None`.
+///
+/// By default decoding stops at the first error, use [`ProtoValueDecoder::with_validate_all`] to collect all errors.
+///
+/// [`JsonValueDecoder`]: crate::value_decode::JsonValueDecoder
+#[derive(Debug)]
+pub struct ProtoValueDecoder<'a> {
+    arc: &'a DamlArchive<'a>,
+    validate_all: bool,
+}
+
+impl<'a> ProtoValueDecoder<'a> {
+    pub const fn new(arc: &'a DamlArchive<'a>) -> Self {
+        Self {
+            arc,
+            validate_all: false,
+        }
+    }
+
+    /// Sets whether all errors are collected rather than failing on the first error.
+    #[must_use]
+    pub const fn with_validate_all(self, validate_all: bool) -> Self {
+        Self {
+            validate_all,
+            ..self
+        }
+    }
+
+    /// Recursively decode a GRPC `DamlValue` from Daml-LF value protobuf bytes for a given `DamlType`.
+    pub fn decode(&self, bytes: &[u8], ty: &DamlType<'_>) -> DamlJsonCodecResult<DamlValue> {
+        self.decode_at(bytes, ty, "")
+    }
+
+    /// Recursively decode a GRPC `DamlValue` from Daml-LF value protobuf bytes which is located at the JSON pointer
+    /// `pointer` within some enclosing document.
+    ///
+    /// The `pointer` is used as the prefix of the path of all [`DamlJsonDecodeError`] returned.
+    ///
+    /// [`DamlJsonDecodeError`]: crate::error::DamlJsonDecodeError
+    pub fn decode_at(&self, bytes: &[u8], ty: &DamlType<'_>, pointer: &str) -> DamlJsonCodecResult<DamlValue> {
+        let value = lf::Value::decode(bytes)?;
+        self.do_decode(&value, ty, None, &ValuePath::Root(pointer))
+    }
+
+    fn do_decode<'t>(
+        &self,
+        value: &lf::Value,
+        ty: &'t DamlType<'t>,
+        type_args: Option<&'t TypeArgs<'t>>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        self.decode_type(value, ty, type_args, path).map_err(|err| locate(err, path, || describe_type(ty, type_args)))
+    }
+
+    fn decode_type<'t>(
+        &self,
+        value: &lf::Value,
+        ty: &'t DamlType<'t>,
+        type_args: Option<&'t TypeArgs<'t>>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        match (ty, value.sum.as_ref()) {
+            (DamlType::Unit, Some(Sum::Unit(()))) => Ok(DamlValue::Unit),
+            (DamlType::Bool, Some(Sum::Bool(b))) => Ok(DamlValue::Bool(*b)),
+            (DamlType::Int64, Some(Sum::Int64(i))) => Ok(DamlValue::new_int64(*i)),
+            (DamlType::Numeric(args), Some(Sum::Numeric(s))) => {
+                let numeric = DamlNumeric::from_str(s)?;
+                check_numeric(&numeric, resolve_scale(args, type_args)?)?;
+                Ok(DamlValue::new_numeric(numeric))
+            },
+            (DamlType::Text, Some(Sum::Text(text))) => Ok(DamlValue::new_text(text)),
+            (DamlType::Party, Some(Sum::Party(party))) => Ok(DamlValue::new_party(party.as_str())),
+            (DamlType::ContractId(_), Some(Sum::ContractId(id))) => Ok(DamlValue::new_contract_id(id.as_str())),
+            (DamlType::Date, Some(Sum::Date(days))) => Ok(DamlValue::new_date(date_from_days(*days)?)),
+            (DamlType::Timestamp, Some(Sum::Timestamp(micros))) =>
+                Ok(DamlValue::new_timestamp(timestamp_from_micros(*micros)?)),
+            (DamlType::List(tys), Some(Sum::List(list))) => {
+                let item_ty = tys.as_single()?;
+                Ok(DamlValue::List(
+                    self.collect(
+                        list.elements
+                            .iter()
+                            .enumerate()
+                            .map(|(i, item)| self.do_decode(item, item_ty, type_args, &ValuePath::Index(path, i))),
+                    )?,
+                ))
+            },
+            (DamlType::TextMap(tys), Some(Sum::TextMap(map))) => {
+                let value_ty = tys.as_single()?;
+                Ok(DamlValue::Map(self.collect::<_, DamlTextMap<DamlValue>>(map.entries.iter().map(|entry| {
+                    let entry_path = ValuePath::Key(path, &entry.key);
+                    let entry_value = entry
+                        .value
+                        .as_ref()
+                        .req()
+                        .map_err(|err| locate(err.into(), &entry_path, || describe_type(value_ty, type_args)))?;
+                    Ok((entry.key.clone(), self.do_decode(entry_value, value_ty, type_args, &entry_path)?))
+                }))?))
+            },
+            (DamlType::GenMap(tys), Some(Sum::GenMap(map))) => self.decode_genmap(map, tys, type_args, path),
+            (DamlType::Optional(tys), Some(Sum::Optional(opt))) => {
+                let single = tys.as_single()?;
+                match &opt.value {
+                    None => Ok(DamlValue::Optional(None)),
+                    Some(inner) =>
+                        Ok(DamlValue::Optional(Some(Box::new(self.do_decode(inner, single, type_args, path)?)))),
+                }
+            },
+            (DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon), _) => {
+                let data = self
+                    .arc
+                    .data_by_tycon(tycon)
+                    .ok_or_else(|| DamlJsonCodecError::DataNotFound(tycon.tycon().to_string()))?;
+                self.decode_data(
+                    value,
+                    data,
+                    &TypeArgs::new(data.type_params(), tycon.type_arguments(), type_args),
+                    path,
+                )
+            },
+            (DamlType::Var(var), _) => {
+                let (resolved, parent) = type_args
+                    .and_then(|type_args| type_args.resolve(var))
+                    .ok_or_else(|| DamlJsonCodecError::TypeVarNotFound(var.var().to_string()))?;
+                self.do_decode(value, resolved, parent, path)
+            },
+            (
+                DamlType::Nat(_)
+                | DamlType::Arrow(_)
+                | DamlType::Any
+                | DamlType::TypeRep
+                | DamlType::Bignumeric
+                | DamlType::RoundingMode
+                | DamlType::AnyException
+                | DamlType::Update(_)
+                | DamlType::Scenario(_)
+                | DamlType::Forall(_)
+                | DamlType::Struct(_)
+                | DamlType::Syn(_),
+                _,
+            ) => Err(DamlJsonCodecError::UnsupportedDamlType(ty.name().to_owned())),
+            (_, sum) => Err(unexpected_value(sum)),
+        }
+    }
+
+    /// Decode a `DamlValue::GenMap` from a protobuf `GenMap`.
+    fn decode_genmap<'t>(
+        &self,
+        map: &lf::value::GenMap,
+        tys: &'t [DamlType<'t>],
+        type_args: Option<&'t TypeArgs<'t>>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        let key_ty = tys.first().req()?;
+        let value_ty = tys.last().req()?;
+        let genmap =
+            self.collect::<_, DamlGenMap<DamlValue, DamlValue>>(map.entries.iter().enumerate().map(|(i, entry)| {
+                let entry_path = ValuePath::Index(path, i);
+                match (&entry.key, &entry.value) {
+                    (Some(k), Some(v)) => Ok((
+                        self.do_decode(k, key_ty, type_args, &ValuePath::Index(&entry_path, 0))?,
+                        self.do_decode(v, value_ty, type_args, &ValuePath::Index(&entry_path, 1))?,
+                    )),
+                    _ => Err(locate(DamlJsonCodecError::UnexpectedGenMapTypes, &entry_path, || {
+                        format!("({}, {})", describe_type(key_ty, type_args), describe_type(value_ty, type_args))
+                    })),
+                }
+            }))?;
+        if map.entries.len() == genmap.len() {
+            Ok(DamlValue::GenMap(genmap))
+        } else {
+            Err(DamlJsonCodecError::DuplicateGenMapKeys)
+        }
+    }
+
+    /// Collect the results of decoding the children of a protobuf value.
+    fn collect<T, C: FromIterator<T>>(
+        &self,
+        results: impl Iterator<Item = DamlJsonCodecResult<T>>,
+    ) -> DamlJsonCodecResult<C> {
+        collect_results(self.validate_all, results)
+    }
+
+    /// Decode a `DamlValue` from a protobuf value and `DamlData`.
+    fn decode_data<'t>(
+        &self,
+        value: &lf::Value,
+        data: &'t DamlData<'t>,
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        match (data, value.sum.as_ref()) {
+            (DamlData::Template(template), Some(Sum::Record(record))) =>
+                self.decode_record(record, template.fields(), type_args, path),
+            (DamlData::Record(data_record), Some(Sum::Record(record))) =>
+                self.decode_record(record, data_record.fields(), type_args, path),
+            (DamlData::Variant(data_variant), Some(Sum::Variant(variant))) =>
+                self.decode_variant(variant, data_variant.fields(), type_args, path),
+            (DamlData::Enum(data_enum), Some(Sum::Enum(value_enum))) => Self::decode_enum(value_enum, data_enum),
+            (_, sum) => Err(unexpected_value(sum)),
+        }
+    }
+
+    /// Decode a `DamlValue::Enum` from a protobuf `Enum` and `DamlEnum` constructors.
+    fn decode_enum(value_enum: &lf::value::Enum, data_enum: &element::DamlEnum<'_>) -> DamlJsonCodecResult<DamlValue> {
+        if data_enum.constructors().any(|c| c == value_enum.value) {
+            Ok(DamlValue::Enum(DamlEnum::new(&value_enum.value, None::<DamlIdentifier>)))
+        } else {
+            Err(DamlJsonCodecError::UnknownEnumConstructor(value_enum.value.clone()))
+        }
+    }
+
+    /// Decode a `DamlValue::Variant` from a protobuf `Variant` and `DamlVariant` constructors.
+    fn decode_variant<'t>(
+        &self,
+        variant: &lf::value::Variant,
+        constructors: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        let constructor = constructors
+            .iter()
+            .find(|&field| field.name() == variant.constructor)
+            .ok_or_else(|| DamlJsonCodecError::UnknownVariantConstructor(variant.constructor.clone()))?;
+        let value = variant.value.as_deref().req()?;
+        Ok(DamlValue::Variant(DamlVariant::new(
+            constructor.name(),
+            Box::new(self.do_decode(value, constructor.ty(), Some(type_args), &ValuePath::Key(path, "value"))?),
+            None::<DamlIdentifier>,
+        )))
+    }
+
+    /// Decode a `DamlValue::Record` from a positional protobuf `Record` and `DamlRecord` / `DamlTemplate` fields.
+    fn decode_record<'t>(
+        &self,
+        record: &lf::value::Record,
+        lf_fields: &'t [DamlField<'t>],
+        type_args: &'t TypeArgs<'t>,
+        path: &ValuePath<'_>,
+    ) -> DamlJsonCodecResult<DamlValue> {
+        if record.fields.len() > lf_fields.len() {
+            return Err(DamlJsonCodecError::UnexpectedRecordFieldCount(lf_fields.len(), record.fields.len()));
+        }
+        let fields: Vec<_> = self.collect(lf_fields.iter().enumerate().map(|(i, field)| {
+            let field_path = ValuePath::Key(path, field.name());
+            match (resolve_type(field.ty(), Some(type_args)), record.fields.get(i)) {
+                (_, Some(value)) => Ok(DamlRecordField::new(
+                    Some(field.name()),
+                    self.do_decode(value, field.ty(), Some(type_args), &field_path)?,
+                )),
+                (DamlType::Optional(_), None) =>
+                    Ok(DamlRecordField::new(Some(field.name()), DamlValue::Optional(None))),
+                (_, None) =>
+                    Err(locate(DamlJsonCodecError::MissingRecordField(i, field.name().to_owned()), &field_path, || {
+                        describe_type(field.ty(), Some(type_args))
+                    })),
+            }
+        }))?;
+        Ok(DamlValue::Record(DamlRecord::new(fields, None::<DamlIdentifier>)))
+    }
+}
+
+/// Make an error for an unexpected protobuf value.
+fn unexpected_value(sum: Option<&Sum>) -> DamlJsonCodecError {
+    DamlJsonCodecError::UnexpectedEncodedValue(sum_name(sum).to_owned())
+}
+
+/// The name of the kind of a protobuf value.
+const fn sum_name(sum: Option<&Sum>) -> &'static str {
+    match sum {
+        None => "None",
+        Some(Sum::Unit(())) => "Unit",
+        Some(Sum::Bool(_)) => "Bool",
+        Some(Sum::Int64(_)) => "Int64",
+        Some(Sum::Date(_)) => "Date",
+        Some(Sum::Timestamp(_)) => "Timestamp",
+        Some(Sum::Numeric(_)) => "Numeric",
+        Some(Sum::Party(_)) => "Party",
+        Some(Sum::Text(_)) => "Text",
+        Some(Sum::ContractId(_)) => "ContractId",
+        Some(Sum::Optional(_)) => "Optional",
+        Some(Sum::List(_)) => "List",
+        Some(Sum::TextMap(_)) => "TextMap",
+        Some(Sum::GenMap(_)) => "GenMap",
+        Some(Sum::Record(_)) => "Record",
+        Some(Sum::Variant(_)) => "Variant",
+        Some(Sum::Enum(_)) => "Enum",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lf, ProtoValueDecoder, ProtoValueEncoder, Sum};
+    use crate::error::{DamlJsonCodecError, DamlJsonCodecResult};
+    use crate::test_util::{arbitrary_data_type, arbitrary_value, make_rng};
+    use crate::value_decode::JsonValueDecoder;
+    use crate::value_encode::TypedJsonValueEncoder;
+    use daml_grpc::data::value::DamlValue;
+    use daml_lf::element::{DamlArchive, DamlType};
+    use proptest::prelude::*;
+    use prost::Message;
+
+    static TESTING_TYPES_DAR_PATH: &str = "../resources/testing_types_sandbox/TestingTypes-latest.dar";
+
+    /// The maximum depth of generated values.
+    const MAX_DEPTH: usize = 4;
+
+    #[test]
+    fn test_timestamp_micros() -> anyhow::Result<()> {
+        let value =
+            DamlValue::new_timestamp("2019-01-02T03:45:56.123456789Z".parse::<chrono::DateTime<chrono::Utc>>()?);
+        let arc = DamlArchive::default();
+        let bytes = ProtoValueEncoder::new(&arc).encode(&value, &DamlType::Timestamp)?;
+        let decoded = ProtoValueDecoder::new(&arc).decode(&bytes, &DamlType::Timestamp)?;
+        assert_eq!(decoded.try_timestamp()?.to_rfc3339(), "2019-01-02T03:45:56.123456+00:00");
+        Ok(())
+    }
+
+    #[test]
+    fn test_date_before_epoch() -> DamlJsonCodecResult<()> {
+        let value = DamlValue::new_date(crate::util::date_from_days(-1)?);
+        let arc = DamlArchive::default();
+        let bytes = ProtoValueEncoder::new(&arc).encode(&value, &DamlType::Date)?;
+        assert_eq!(lf::Value::decode(bytes.as_slice())?.sum, Some(Sum::Date(-1)));
+        assert_eq!(ProtoValueDecoder::new(&arc).decode(&bytes, &DamlType::Date)?, value);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_optional_field() -> anyhow::Result<()> {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Depth1");
+        let bytes = encode_value(Sum::Record(lf::value::Record {
+            fields: vec![],
+        }));
+        let decoded = ProtoValueDecoder::new(arc).decode(&bytes, &ty)?;
+        assert_eq!(decoded.try_record()?.fields()[0].value(), &DamlValue::new_optional(None));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_errors_are_located() {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Person");
+        let bytes = encode_value(Sum::Record(lf::value::Record {
+            fields: vec![proto_value(Sum::Int64(1)), proto_value(Sum::Text(String::from("old")))],
+        }));
+        let result = ProtoValueDecoder::new(arc).with_validate_all(true).decode_at(&bytes, &ty, "/payload");
+        match result {
+            Err(DamlJsonCodecError::DecodeErrors(errors)) => {
+                let paths: Vec<_> = errors.iter().map(|e| (e.path.as_str(), e.expected.as_str())).collect();
+                assert_eq!(paths, vec![("/payload/name", "Text"), ("/payload/age", "Int")]);
+            },
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_too_many_fields() {
+        let arc = daml_archive();
+        let ty = DamlType::make_tycon(arc.main_package_id(), &["Fuji", "JsonTest"], "Depth1");
+        let bytes = encode_value(Sum::Record(lf::value::Record {
+            fields: vec![proto_value(Sum::Unit(())), proto_value(Sum::Unit(()))],
+        }));
+        let result = ProtoValueDecoder::new(arc).decode(&bytes, &ty);
+        assert!(
+            matches!(result, Err(DamlJsonCodecError::DecodeErrors(errors)) if matches!(*errors[0].error, DamlJsonCodecError::UnexpectedRecordFieldCount(1, 2)))
+        );
+    }
+
+    #[test]
+    fn test_invalid_bytes() {
+        let result = ProtoValueDecoder::new(&DamlArchive::default()).decode(&[0xff, 0xff], &DamlType::Int64);
+        assert!(matches!(result, Err(DamlJsonCodecError::ProtobufDecodeError(_))));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_round_trip(seed in any::<u64>(), (module, entity) in arbitrary_data_type()) {
+            let arc = daml_archive();
+            let module_path = ["Fuji", module];
+            let ty = DamlType::make_tycon(arc.main_package_id(), &module_path, entity);
+            let value = arbitrary_value(&mut make_rng(seed), arc, &ty, None, MAX_DEPTH);
+            let bytes = ProtoValueEncoder::new(arc).encode(&value, &ty).unwrap();
+            prop_assert_eq!(ProtoValueDecoder::new(arc).decode(&bytes, &ty).unwrap(), value);
+        }
+
+        #[test]
+        fn prop_agrees_with_json(seed in any::<u64>(), (module, entity) in arbitrary_data_type()) {
+            let arc = daml_archive();
+            let module_path = ["Fuji", module];
+            let ty = DamlType::make_tycon(arc.main_package_id(), &module_path, entity);
+            let value = arbitrary_value(&mut make_rng(seed), arc, &ty, None, MAX_DEPTH);
+            let json = TypedJsonValueEncoder::new(arc).encode(&value, &ty).unwrap();
+            let bytes = ProtoValueEncoder::new(arc).encode(&value, &ty).unwrap();
+            prop_assert_eq!(
+                ProtoValueDecoder::new(arc).decode(&bytes, &ty).unwrap(),
+                JsonValueDecoder::new(arc).decode(&json, &ty).unwrap()
+            );
+        }
+    }
+
+    fn proto_value(sum: Sum) -> lf::Value {
+        lf::Value {
+            sum: Some(sum),
+        }
+    }
+
+    fn encode_value(sum: Sum) -> Vec<u8> {
+        proto_value(sum).encode_to_vec()
+    }
+
+    fn daml_archive() -> &'static DamlArchive<'static> {
+        crate::test_util::daml_archive(TESTING_TYPES_DAR_PATH)
+    }
+}
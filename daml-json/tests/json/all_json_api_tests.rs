@@ -1,8 +1,10 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::similar_names, clippy::missing_errors_doc, clippy::used_underscore_binding)]
+use chrono::NaiveDate;
 use daml::util::DamlSandboxTokenBuilder;
 use daml_grpc::DamlGrpcClientBuilder;
-use daml_json::data::{DamlJsonCreatedEvent, DamlJsonEvent, DamlJsonParty};
+use daml_json::data::{DamlJsonCreatedEvent, DamlJsonEvent, DamlJsonParty, DamlJsonUser, DamlJsonUserRight};
+use daml_json::query::Query;
 use daml_json::request::DamlJsonRequestMeta;
 use daml_json::service::{DamlJsonClient, DamlJsonClientBuilder};
 use daml_lf::DarFile;
@@ -181,6 +183,24 @@ async fn test_query() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_execute_query() -> anyhow::Result<()> {
+    let _lock = initialize().await;
+    let alice_client = new_client().await?;
+    let _event =
+        alice_client.create("Fuji.PingPong:Ping", json!({ "sender": "Alice", "receiver": "Bob", "count": 5 })).await?;
+    let _event =
+        alice_client.create("Fuji.PingPong:Ping", json!({ "sender": "Alice", "receiver": "Bob", "count": 1 })).await?;
+    let query = Query::template("Fuji.PingPong:Ping").field("count").gte(5).and("receiver").eq("Bob");
+    let active_contracts = alice_client.execute_query(query).await?;
+    assert_eq!(active_contracts.len(), 1);
+    assert_eq!(
+        active_contracts.first().unwrap().payload,
+        json!({ "sender": "Alice", "receiver": "Bob", "count": "5" })
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_fetch_parties() -> anyhow::Result<()> {
     let _lock = initialize().await;
@@ -295,6 +315,85 @@ async fn test_upload_dar() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_create_and_fetch_user() -> anyhow::Result<()> {
+    let _lock = initialize().await;
+    let alice_client = new_client().await?;
+    let alice_party = alice_client.allocate_party(None, Some("Alice")).await?;
+    alice_client
+        .create_user("alice", Some(&alice_party.identifier), vec![DamlJsonUserRight::can_act_as(
+            &alice_party.identifier,
+        )])
+        .await?;
+    let fetch_user_response = alice_client.fetch_user("alice").await?;
+    assert_eq!(fetch_user_response, DamlJsonUser::new("alice", Some(alice_party.identifier)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fetch_unknown_user() -> anyhow::Result<()> {
+    let _lock = initialize().await;
+    let alice_client = new_client().await?;
+    let fetch_user_response = alice_client.fetch_user("unknown").await;
+    assert!(fetch_user_response.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_and_delete_users() -> anyhow::Result<()> {
+    let _lock = initialize().await;
+    let alice_client = new_client().await?;
+    alice_client.create_user("bob", None, vec![]).await?;
+    let list_users_response = alice_client.list_users().await?;
+    assert!(list_users_response.contains(&DamlJsonUser::new::<String>("bob", None)));
+    alice_client.delete_user("bob").await?;
+    let list_users_response = alice_client.list_users().await?;
+    assert!(!list_users_response.iter().any(|user| user.user_id == "bob"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_grant_list_and_revoke_user_rights() -> anyhow::Result<()> {
+    let _lock = initialize().await;
+    let alice_client = new_client().await?;
+    let alice_party = alice_client.allocate_party(None, Some("Alice")).await?;
+    let act_as = DamlJsonUserRight::can_act_as(&alice_party.identifier);
+    let read_as = DamlJsonUserRight::can_read_as(&alice_party.identifier);
+    alice_client.create_user("carol", None, vec![act_as.clone()]).await?;
+    let granted = alice_client.grant_user_rights("carol", vec![act_as.clone(), read_as.clone()]).await?;
+    assert_eq!(granted, vec![read_as.clone()]);
+    let rights = alice_client.list_user_rights("carol").await?;
+    assert_eq!(rights.len(), 2);
+    assert!(rights.contains(&act_as) && rights.contains(&read_as));
+    let revoked = alice_client.revoke_user_rights("carol", vec![act_as, DamlJsonUserRight::ParticipantAdmin]).await?;
+    assert_eq!(revoked, vec![DamlJsonUserRight::can_act_as(&alice_party.identifier)]);
+    assert_eq!(alice_client.list_user_rights("carol").await?, vec![read_as]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metering_report() -> anyhow::Result<()> {
+    let _lock = initialize().await;
+    let alice_client = new_client().await?;
+    let from = "2022-01-01".parse::<NaiveDate>()?;
+    let metering_report_response = alice_client.metering_report(from, None, None).await?;
+    assert_eq!(metering_report_response.request.from.naive_utc().date(), from);
+    assert!(!metering_report_response.is_final);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metering_report_for_application() -> anyhow::Result<()> {
+    let _lock = initialize().await;
+    let alice_client = new_client().await?;
+    let from = "2022-01-01".parse::<NaiveDate>()?;
+    let to = "2099-01-01".parse::<NaiveDate>()?;
+    let metering_report_response = alice_client.metering_report(from, Some(to), Some("demo")).await?;
+    assert_eq!(metering_report_response.request.application.as_deref(), Some("demo"));
+    assert!(metering_report_response.applications.iter().all(|app| app.application == "demo"));
+    Ok(())
+}
+
 lazy_static! {
     pub static ref SANDBOX_LOCK: Mutex<()> = Mutex::new(());
 }
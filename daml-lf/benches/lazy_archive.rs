@@ -0,0 +1,71 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use daml_lf::{DarFile, LazyDarFile};
+
+/// The benchmarked DARs, with an increasing number of packages, and the module which contains the `Ping` template.
+const DARS: [(&str, &str, &[&str]); 3] = [
+    ("lf_1_6", "test_resources/TestingTypes-1_0_0-sdk_0_13_36-lf_1_6.dar", &["DA", "PingPong"]),
+    ("lf_1_8", "test_resources/TestingTypes-1_0_0-sdk_0_13_55-lf_1_8.dar", &["DA", "PingPong"]),
+    ("lf_1_14", "test_resources/TestingTypes-1_9_0-sdk_1_18_1-lf_1_14.dar", &["Fuji", "PingPong"]),
+];
+
+fn eager_to_owned_archive(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eager to_owned_archive");
+    for (name, path, _) in DARS {
+        group.bench_with_input(BenchmarkId::from_parameter(name), path, |b, path| {
+            b.iter(|| {
+                let dar = DarFile::from_file(path).unwrap();
+                dar.to_owned_archive().unwrap()
+            });
+        });
+    }
+    group.finish();
+}
+
+fn eager_data_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eager data lookup");
+    for (name, path, module) in DARS {
+        group.bench_with_input(BenchmarkId::from_parameter(name), path, |b, path| {
+            b.iter(|| {
+                let dar = DarFile::from_file(path).unwrap();
+                dar.apply(|archive| {
+                    archive.data(archive.main_package_id(), module, "Ping").map(|data| data.fields().len())
+                })
+                .unwrap()
+            });
+        });
+    }
+    group.finish();
+}
+
+fn lazy_data_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lazy data lookup");
+    for (name, path, module) in DARS {
+        group.bench_with_input(BenchmarkId::from_parameter(name), path, |b, path| {
+            b.iter(|| {
+                let dar = LazyDarFile::from_file(path).unwrap();
+                dar.apply(|archive| {
+                    archive.data(archive.main_package_id(), module, "Ping").unwrap().map(|data| data.fields().len())
+                })
+            });
+        });
+    }
+    group.finish();
+}
+
+fn lazy_package_by_name(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lazy package_by_name");
+    for (name, path, _) in DARS {
+        group.bench_with_input(BenchmarkId::from_parameter(name), path, |b, path| {
+            b.iter(|| {
+                let dar = LazyDarFile::from_file(path).unwrap();
+                dar.apply(|archive| {
+                    archive.package_by_name("daml-prim").unwrap().map(|package| package.name().to_owned())
+                })
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, eager_to_owned_archive, eager_data_lookup, lazy_data_lookup, lazy_package_by_name);
+criterion_main!(benches);
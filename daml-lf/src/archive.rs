@@ -1,9 +1,12 @@
 use crate::element::DamlPackage;
-use crate::lf_protobuf::com::daml::daml_lf::Archive;
+use crate::error::DamlLfConvertError;
+use crate::lf_protobuf::com::daml::daml_lf::{Archive, HashFunction};
 use crate::DamlLfResult;
 use crate::{convert, DamlLfArchivePayload};
 use bytes::Bytes;
 use prost::Message;
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
 use std::ffi::OsStr;
 use std::fs::File;
 use std::io::Read;
@@ -16,19 +19,26 @@ pub const DEFAULT_ARCHIVE_NAME: &str = "Unnamed";
 ///
 /// A `DamlLfArchive` contains a `name`, a `payload` (aka "package"), a `hash` (aka "package id") of that `payload` for
 /// a given `hash_function`.
+///
+/// The serialized bytes of the `payload`, from which the `hash` was computed, are retained if available such that the
+/// archive can be serialized again without changing the `payload` bytes and thus invalidating the `hash`.  The retained
+/// bytes are discarded if the `payload` is accessed mutably via [`DamlLfArchive::payload_mut`].
 #[derive(Debug, Clone)]
 pub struct DamlLfArchive {
     pub name: String,
-    pub payload: DamlLfArchivePayload,
+    payload: DamlLfArchivePayload,
     pub hash_function: DamlLfHashFunction,
     pub hash: String,
+    payload_bytes: Option<Bytes>,
 }
 
 impl DamlLfArchive {
     /// Create an archive from an existing `payload`, `hash_function` and `hash`.
     ///
     /// Note that this method does not validate that the supplied `hash` is valid for the supplied `payload` and
-    /// `hash_function` and thus could create an invalid archive.
+    /// `hash_function` and thus could create an invalid archive.  As the serialized bytes of the `payload` are not
+    /// known, the `payload` will be re-serialized by [`DamlLfArchive::to_bytes`], which may not match the `hash`.  Use
+    /// [`DamlLfArchive::from_payload_bytes`] if the serialized bytes of the `payload` are available.
     pub fn new(
         name: impl Into<String>,
         payload: impl Into<DamlLfArchivePayload>,
@@ -40,9 +50,54 @@ impl DamlLfArchive {
             payload: payload.into(),
             hash_function: hash_function.into(),
             hash: hash.into(),
+            payload_bytes: None,
         }
     }
 
+    /// Create an archive from the serialized `payload_bytes`, `hash_function` and `hash`.
+    ///
+    /// The `payload_bytes` are deserialized into a [`DamlLfArchivePayload`] and retained such that they are written
+    /// unchanged by [`DamlLfArchive::to_bytes`].
+    ///
+    /// Note that this method does not validate that the supplied `hash` is valid for the supplied `payload_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// If the `payload_bytes` cannot be deserialized then the error from [`DamlLfArchivePayload::from_bytes`] will
+    /// be returned.
+    pub fn from_payload_bytes(
+        name: impl Into<String>,
+        payload_bytes: impl Into<Bytes>,
+        hash_function: impl Into<DamlLfHashFunction>,
+        hash: impl Into<String>,
+    ) -> DamlLfResult<Self> {
+        let payload_bytes = payload_bytes.into();
+        let payload = DamlLfArchivePayload::from_bytes(payload_bytes.clone())?;
+        Ok(Self {
+            payload_bytes: Some(payload_bytes),
+            ..Self::new(name, payload, hash_function, hash)
+        })
+    }
+
+    /// Create a named archive from a `payload`, computing the `hash` (aka "package id") of the serialized `payload`.
+    ///
+    /// This is typically used to create an archive from a payload which was encoded with
+    /// `DamlLfArchivePayload::from_package`.  The `hash` is computed with [`DamlLfHashFunction::Sha256`].
+    ///
+    /// # Errors
+    ///
+    /// Payloads of `Daml LF` `v0` are not supported and will result in a [`UnsupportedVersion`] being returned.
+    ///
+    /// [`UnsupportedVersion`]: crate::DamlLfError::UnsupportedVersion
+    pub fn from_payload(name: impl Into<String>, payload: DamlLfArchivePayload) -> DamlLfResult<Self> {
+        let payload_bytes = Bytes::from(payload.to_bytes()?);
+        let hash = format!("{:x}", Sha256::digest(&payload_bytes));
+        Ok(Self {
+            payload_bytes: Some(payload_bytes),
+            ..Self::new(name, payload, DamlLfHashFunction::Sha256, hash)
+        })
+    }
+
     /// Deserialize an archive from the protobuf binary representation with a default name.
     ///
     /// Deserialize the supplied protobuf `bytes` into a `DamlLfArchive`.  The embedded `payload` (bytes) will also
@@ -89,6 +144,8 @@ impl DamlLfArchive {
     ///
     /// If the embedded `payload` is not of a known version then [`UnknownVersion`] will be returned.
     ///
+    /// If the archive `hash_function` is not known then [`DamlLfConvertError`] will be returned.
+    ///
     /// Archives of `Daml LF` `v0` are not supported and will result in a [`UnsupportedVersion`] being returned.
     ///
     /// # Examples
@@ -107,13 +164,17 @@ impl DamlLfArchive {
     /// [`DamlLfParseError`]: crate::DamlLfError::DamlLfParseError
     /// [`UnknownVersion`]: crate::DamlLfError::UnknownVersion
     /// [`UnsupportedVersion`]: crate::DamlLfError::UnsupportedVersion
+    /// [`DamlLfConvertError`]: crate::DamlLfError::DamlLfConvertError
     /// [`DamlLfArchivePayload`]: DamlLfArchivePayload
     pub fn from_bytes_named(name: impl Into<String>, bytes: impl Into<Bytes>) -> DamlLfResult<Self> {
         let archive: Archive = Archive::decode(bytes.into())?;
-        let payload = DamlLfArchivePayload::from_bytes(archive.payload)?;
         let archive_name = name.into();
-        let sanitized_name = archive_name.rfind(&archive.hash).map_or(&archive_name[..], |i| &archive_name[..i - 1]);
-        Ok(Self::new(sanitized_name, payload, DamlLfHashFunction::Sha256, archive.hash))
+        Self::from_payload_bytes(
+            sanitize_name(&archive_name, &archive.hash),
+            archive.payload,
+            DamlLfHashFunction::try_from(archive.hash_function)?,
+            archive.hash,
+        )
     }
 
     /// Read and parse an archive from a `dalf` file.
@@ -154,6 +215,82 @@ impl DamlLfArchive {
         Self::from_bytes_named(archive_name_stem, buffer)
     }
 
+    /// Read and parse an archive from a `reader` with a default name.
+    ///
+    /// The entire contents of the `reader` are read into memory before being deserialized as per
+    /// [`DamlLfArchive::from_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// If the `reader` cannot be read an [`IOError`] will be returned which contains the underlying IO error.
+    ///
+    /// If the contents of the `reader` cannot be deserialized into an archive (or the embedded `payload` cannot be
+    /// deserialized into a [`DamlLfArchivePayload`]) then [`DamlLfParseError`] will be returned.
+    ///
+    /// If the embedded `payload` is not of a known version then [`UnknownVersion`] will be returned.
+    ///
+    /// Archives of `Daml LF` `v0` are not supported and will result in a [`UnsupportedVersion`] being returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use daml_lf::{DamlLfArchive, DamlLfHashFunction};
+    /// # use daml_lf::DamlLfResult;
+    /// # use std::fs::File;
+    /// # fn main() -> DamlLfResult<()> {
+    /// let archive = DamlLfArchive::from_reader(File::open("Example.dalf")?)?;
+    /// assert_eq!(&DamlLfHashFunction::Sha256, archive.hash_function());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`IOError`]: crate::DamlLfError::IoError
+    /// [`DamlLfParseError`]: crate::DamlLfError::DamlLfParseError
+    /// [`UnknownVersion`]: crate::DamlLfError::UnknownVersion
+    /// [`UnsupportedVersion`]: crate::DamlLfError::UnsupportedVersion
+    /// [`DamlLfArchivePayload`]: DamlLfArchivePayload
+    pub fn from_reader(mut reader: impl Read) -> DamlLfResult<Self> {
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        Self::from_bytes(buffer)
+    }
+
+    /// Serialize this archive to the protobuf binary representation (i.e. the contents of a `dalf` file).
+    ///
+    /// The serialized bytes of the embedded `payload` are written unchanged if they were retained when this archive was
+    /// created, otherwise the `payload` is serialized with [`DamlLfArchivePayload::to_bytes`].  The existing `hash` of
+    /// this archive is retained in either case.
+    ///
+    /// # Errors
+    ///
+    /// If the embedded `payload` cannot be serialized then the error from [`DamlLfArchivePayload::to_bytes`] will be
+    /// returned.
+    pub fn to_bytes(&self) -> DamlLfResult<Vec<u8>> {
+        let hash_function = match self.hash_function {
+            DamlLfHashFunction::Sha256 => HashFunction::Sha256,
+        };
+        let archive = Archive {
+            hash_function: hash_function as i32,
+            payload: match &self.payload_bytes {
+                Some(payload_bytes) => payload_bytes.to_vec(),
+                None => self.payload.to_bytes()?,
+            },
+            hash: self.hash.clone(),
+        };
+        Ok(archive.encode_to_vec())
+    }
+
+    /// The file name of this archive when stored as a `dalf` file, i.e. `name-hash.dalf`.
+    ///
+    /// If this archive has an empty `name` then [`DEFAULT_ARCHIVE_NAME`] is used instead.
+    pub fn dalf_file_name(&self) -> String {
+        let name = if self.name.is_empty() {
+            DEFAULT_ARCHIVE_NAME
+        } else {
+            &self.name
+        };
+        format!("{}-{}.dalf", name, self.hash)
+    }
+
     /// Create a [`DamlArchive`] from a [`DamlLfArchive`] and apply it to `f`.
     ///
     /// See [`DarFile::apply`] for details.
@@ -177,6 +314,26 @@ impl DamlLfArchive {
         &self.payload
     }
 
+    /// Mutable access to the payload (aka "package") contained within this archive.
+    ///
+    /// As the payload may be changed, any retained serialized bytes of the payload are discarded and the payload will
+    /// be re-serialized by [`DamlLfArchive::to_bytes`].  Note that the `hash` of this archive is not recomputed, use
+    /// [`DamlLfArchive::from_payload`] to create an archive with a new `hash` for a modified payload.
+    pub fn payload_mut(&mut self) -> &mut DamlLfArchivePayload {
+        self.payload_bytes = None;
+        &mut self.payload
+    }
+
+    /// Consume this archive and return the payload (aka "package") contained within it.
+    pub fn into_payload(self) -> DamlLfArchivePayload {
+        self.payload
+    }
+
+    /// The serialized bytes of the payload from which the `hash` was computed, if known.
+    pub fn payload_bytes(&self) -> Option<&[u8]> {
+        self.payload_bytes.as_deref()
+    }
+
     /// The hashing function used to generate this archives `hash`.
     pub const fn hash_function(&self) -> &DamlLfHashFunction {
         &self.hash_function
@@ -193,3 +350,57 @@ impl DamlLfArchive {
 pub enum DamlLfHashFunction {
     Sha256,
 }
+
+impl TryFrom<i32> for DamlLfHashFunction {
+    type Error = DamlLfConvertError;
+
+    fn try_from(hash_function: i32) -> Result<Self, Self::Error> {
+        match HashFunction::from_i32(hash_function) {
+            Some(HashFunction::Sha256) => Ok(Self::Sha256),
+            None => Err(DamlLfConvertError::UnknownHashFunction(hash_function)),
+        }
+    }
+}
+
+/// Strip the `-hash` suffix, and anything after it, from an archive `name`.
+pub fn sanitize_name<'a>(name: &'a str, hash: &str) -> &'a str {
+    name.rfind(hash).map_or(name, |i| &name[..i - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DamlLfError, DamlLfPackage};
+
+    #[test]
+    fn test_hash_function_from_archive() {
+        assert_eq!(DamlLfHashFunction::Sha256, DamlLfHashFunction::try_from(HashFunction::Sha256 as i32).unwrap());
+        assert!(matches!(DamlLfHashFunction::try_from(99), Err(DamlLfConvertError::UnknownHashFunction(99))));
+    }
+
+    #[test]
+    fn test_from_bytes_unknown_hash_function() {
+        let archive = Archive {
+            hash_function: 99,
+            payload: vec![],
+            hash: String::new(),
+        };
+        let result = DamlLfArchive::from_bytes(archive.encode_to_vec());
+        assert!(matches!(result, Err(DamlLfError::DamlLfConvertError(DamlLfConvertError::UnknownHashFunction(99)))));
+    }
+
+    #[test]
+    fn test_payload_mut_discards_payload_bytes() -> DamlLfResult<()> {
+        let mut archive = DamlLfArchive::from_file("../resources/testing_types_sandbox/archive/legacy/Legacy.dalf")?;
+        let original = archive.payload_bytes().map(<[u8]>::to_vec);
+        assert_eq!(original, Some(Archive::decode(archive.to_bytes()?.as_slice())?.payload));
+        let DamlLfPackage::V1(package) = &mut archive.payload_mut().package;
+        package.interned_strings.push(String::from("modified"));
+        assert!(archive.payload_bytes().is_none());
+        let written = DamlLfArchive::from_bytes(archive.to_bytes()?)?;
+        let DamlLfPackage::V1(package) = &written.payload().package;
+        assert_eq!(Some("modified"), package.interned_strings.last().map(String::as_str));
+        assert_ne!(original.as_deref(), written.payload_bytes());
+        Ok(())
+    }
+}
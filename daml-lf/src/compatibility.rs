@@ -0,0 +1,655 @@
+use crate::element::{
+    DamlArchive, DamlChoice, DamlData, DamlField, DamlModule, DamlPackage, DamlTemplate, DamlTyConName, DamlType,
+};
+use crate::{DamlLfError, DamlLfResult, DarFile, PackageInfo, PrettyPrinter};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::mem;
+
+/// A change between two versions of a package which breaks compatibility with existing contracts or clients.
+///
+/// Data types are identified by their qualified name (i.e. `Module.Path:Name`) and templates by their qualified
+/// name and choices by the qualified name of the template followed by the choice name.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum DamlBreakingChange {
+    /// A template was removed.
+    TemplateRemoved(String),
+    /// A serializable data type was removed.
+    DataRemoved(String),
+    /// A data type changed kind, for example from a record to a variant.
+    DataKindChanged(String, String, String),
+    /// The number of type parameters of a data type changed.
+    TypeParamsChanged(String, usize, usize),
+    /// A field was removed from a record or template.
+    FieldRemoved(String, String),
+    /// A field which is not `Optional` was added to a record or template.
+    FieldAdded(String, String),
+    /// The type of a field of a record or template changed.
+    FieldTypeChanged(String, String, String, String),
+    /// The fields of a record or template were reordered or a new field was not added last.
+    FieldsReordered(String),
+    /// A constructor was removed from a variant or enum.
+    ConstructorRemoved(String, String),
+    /// The type of a variant constructor changed.
+    ConstructorTypeChanged(String, String, String, String),
+    /// The constructors of a variant or enum were reordered or a new constructor was not added last.
+    ConstructorsReordered(String),
+    /// A choice was removed from a template.
+    ChoiceRemoved(String, String),
+    /// A choice changed from consuming to non-consuming or vice versa.
+    ChoiceConsumingChanged(String, String),
+    /// The argument type of a choice changed.
+    ChoiceArgumentTypeChanged(String, String, String, String),
+    /// The return type of a choice changed.
+    ChoiceReturnTypeChanged(String, String, String, String),
+    /// A contract key was added to a template.
+    KeyAdded(String),
+    /// The contract key was removed from a template.
+    KeyRemoved(String),
+    /// The type of the contract key of a template changed.
+    KeyTypeChanged(String, String, String),
+}
+
+impl Display for DamlBreakingChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DamlBreakingChange::TemplateRemoved(template) => write!(f, "template {template} was removed"),
+            DamlBreakingChange::DataRemoved(data) => write!(f, "data type {data} was removed"),
+            DamlBreakingChange::DataKindChanged(data, old, new) =>
+                write!(f, "data type {data} changed from {old} to {new}"),
+            DamlBreakingChange::TypeParamsChanged(data, old, new) =>
+                write!(f, "data type {data} changed from {old} to {new} type parameters"),
+            DamlBreakingChange::FieldRemoved(data, field) => write!(f, "field {field} of {data} was removed"),
+            DamlBreakingChange::FieldAdded(data, field) =>
+                write!(f, "field {field} of {data} was added but is not Optional"),
+            DamlBreakingChange::FieldTypeChanged(data, field, old, new) =>
+                write!(f, "field {field} of {data} changed type from {old} to {new}"),
+            DamlBreakingChange::FieldsReordered(data) => write!(f, "fields of {data} were reordered"),
+            DamlBreakingChange::ConstructorRemoved(data, constructor) =>
+                write!(f, "constructor {constructor} of {data} was removed"),
+            DamlBreakingChange::ConstructorTypeChanged(data, constructor, old, new) =>
+                write!(f, "constructor {constructor} of {data} changed type from {old} to {new}"),
+            DamlBreakingChange::ConstructorsReordered(data) => write!(f, "constructors of {data} were reordered"),
+            DamlBreakingChange::ChoiceRemoved(template, choice) =>
+                write!(f, "choice {choice} of {template} was removed"),
+            DamlBreakingChange::ChoiceConsumingChanged(template, choice) =>
+                write!(f, "choice {choice} of {template} changed consuming behaviour"),
+            DamlBreakingChange::ChoiceArgumentTypeChanged(template, choice, old, new) =>
+                write!(f, "choice {choice} of {template} changed argument type from {old} to {new}"),
+            DamlBreakingChange::ChoiceReturnTypeChanged(template, choice, old, new) =>
+                write!(f, "choice {choice} of {template} changed return type from {old} to {new}"),
+            DamlBreakingChange::KeyAdded(template) => write!(f, "a key was added to {template}"),
+            DamlBreakingChange::KeyRemoved(template) => write!(f, "the key of {template} was removed"),
+            DamlBreakingChange::KeyTypeChanged(template, old, new) =>
+                write!(f, "the key of {template} changed type from {old} to {new}"),
+        }
+    }
+}
+
+/// The result of comparing two versions of a package.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DamlCompatibilityReport {
+    old: PackageInfo,
+    new: PackageInfo,
+    changes: Vec<DamlBreakingChange>,
+}
+
+impl DamlCompatibilityReport {
+    /// The old version of the package.
+    pub const fn old_package(&self) -> &PackageInfo {
+        &self.old
+    }
+
+    /// The new version of the package.
+    pub const fn new_package(&self) -> &PackageInfo {
+        &self.new
+    }
+
+    /// The breaking changes found, ordered by the qualified name of the data type.
+    pub fn changes(&self) -> &[DamlBreakingChange] {
+        &self.changes
+    }
+
+    /// Is the new version of the package compatible with the old version?
+    pub fn is_compatible(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Compare two versions of a package and report changes which break compatibility.
+///
+/// Every serializable data type of the old package is compared with the data type with the same qualified name in the
+/// new package.  The following are considered breaking:
+///
+/// - removing a template or serializable data type, or changing its kind or number of type parameters
+/// - removing a field, changing the type of a field or adding a field which is not `Optional` or not last
+/// - removing a variant or enum constructor, changing the type of a variant constructor or adding a constructor which
+///   is not last
+/// - removing a choice or changing whether it is consuming, its argument type or its return type
+/// - adding, removing or changing the type of a contract key
+///
+/// Type constructors are compared by package name, module and data type name such that references to data types
+/// within the package being compared, or to a new version of a dependency of the same name, are considered equal.
+///
+/// Choice arguments are compared by type if the `full` feature is enabled and by their fields otherwise.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use daml_lf::{DamlCompatibilityChecker, DamlLfResult, DarFile};
+/// # fn main() -> DamlLfResult<()> {
+/// let old = DarFile::from_file("Example-1.0.0.dar")?;
+/// let new = DarFile::from_file("Example-1.1.0.dar")?;
+/// let report = DamlCompatibilityChecker::new().check_dars(&old, &new)?;
+/// for change in report.changes() {
+///     println!("{}", change);
+/// }
+/// assert!(report.is_compatible());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DamlCompatibilityChecker {
+    printer: PrettyPrinter,
+}
+
+impl Default for DamlCompatibilityChecker {
+    fn default() -> Self {
+        Self {
+            printer: PrettyPrinter::new().elide_package_ids(true),
+        }
+    }
+}
+
+impl DamlCompatibilityChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compare the main packages of two `DarFile`.
+    pub fn check_dars(&self, old: &DarFile, new: &DarFile) -> DamlLfResult<DamlCompatibilityReport> {
+        old.apply(|old| new.apply(|new| self.check_archives(old, new)))??
+    }
+
+    /// Compare the main packages of two `DamlArchive`.
+    pub fn check_archives(
+        &self,
+        old: &DamlArchive<'_>,
+        new: &DamlArchive<'_>,
+    ) -> DamlLfResult<DamlCompatibilityReport> {
+        Ok(self.check_packages(main_package(old)?, main_package(new)?))
+    }
+
+    /// Compare two `DamlPackage`.
+    pub fn check_packages(&self, old: &DamlPackage<'_>, new: &DamlPackage<'_>) -> DamlCompatibilityReport {
+        let new_data = serializable_data(new.root_module());
+        let changes = serializable_data(old.root_module())
+            .into_iter()
+            .flat_map(|(name, old_data)| match new_data.get(&name) {
+                Some(new_data) => self.check_data(&name, old_data, new_data),
+                None => vec![match old_data {
+                    DamlData::Template(_) => DamlBreakingChange::TemplateRemoved(name),
+                    _ => DamlBreakingChange::DataRemoved(name),
+                }],
+            })
+            .collect();
+        DamlCompatibilityReport {
+            old: PackageInfo::extract_from_package(old),
+            new: PackageInfo::extract_from_package(new),
+            changes,
+        }
+    }
+
+    fn check_data(&self, name: &str, old: &DamlData<'_>, new: &DamlData<'_>) -> Vec<DamlBreakingChange> {
+        let mut changes = vec![];
+        if mem::discriminant(old) != mem::discriminant(new) {
+            changes.push(DamlBreakingChange::DataKindChanged(
+                name.to_owned(),
+                data_kind(old).to_owned(),
+                data_kind(new).to_owned(),
+            ));
+            return changes;
+        }
+        if old.type_params().len() != new.type_params().len() {
+            changes.push(DamlBreakingChange::TypeParamsChanged(
+                name.to_owned(),
+                old.type_params().len(),
+                new.type_params().len(),
+            ));
+        }
+        match (old, new) {
+            (DamlData::Template(old), DamlData::Template(new)) => {
+                self.check_fields(name, old.fields(), new.fields(), &mut changes);
+                self.check_template(name, old, new, &mut changes);
+            },
+            (DamlData::Record(old), DamlData::Record(new)) =>
+                self.check_fields(name, old.fields(), new.fields(), &mut changes),
+            (DamlData::Variant(old), DamlData::Variant(new)) =>
+                self.check_constructors(name, old.fields(), new.fields(), &mut changes),
+            (DamlData::Enum(old), DamlData::Enum(new)) => {
+                let old_constructors = old.constructors().collect::<Vec<_>>();
+                let new_constructors = new.constructors().collect::<Vec<_>>();
+                let removed = old_constructors.iter().filter(|&c| !new_constructors.contains(c)).collect::<Vec<_>>();
+                changes.extend(
+                    removed.iter().map(|&&c| DamlBreakingChange::ConstructorRemoved(name.to_owned(), c.to_owned())),
+                );
+                if removed.is_empty() && !new_constructors.starts_with(&old_constructors) {
+                    changes.push(DamlBreakingChange::ConstructorsReordered(name.to_owned()));
+                }
+            },
+            _ => {},
+        }
+        changes
+    }
+
+    fn check_fields(
+        &self,
+        name: &str,
+        old: &[DamlField<'_>],
+        new: &[DamlField<'_>],
+        changes: &mut Vec<DamlBreakingChange>,
+    ) {
+        let mut removed = false;
+        for old_field in old {
+            if let Some(new_field) = new.iter().find(|f| f.name() == old_field.name()) {
+                if !same_type(old_field.ty(), new_field.ty()) {
+                    changes.push(DamlBreakingChange::FieldTypeChanged(
+                        name.to_owned(),
+                        old_field.name().to_owned(),
+                        self.printer.render_type(old_field.ty()),
+                        self.printer.render_type(new_field.ty()),
+                    ));
+                }
+            } else {
+                removed = true;
+                changes.push(DamlBreakingChange::FieldRemoved(name.to_owned(), old_field.name().to_owned()));
+            }
+        }
+        for new_field in new.iter().filter(|f| !old.iter().any(|o| o.name() == f.name())) {
+            if !matches!(new_field.ty(), DamlType::Optional(_)) {
+                changes.push(DamlBreakingChange::FieldAdded(name.to_owned(), new_field.name().to_owned()));
+            }
+        }
+        if !removed && !is_prefix(old, new) {
+            changes.push(DamlBreakingChange::FieldsReordered(name.to_owned()));
+        }
+    }
+
+    fn check_constructors(
+        &self,
+        name: &str,
+        old: &[DamlField<'_>],
+        new: &[DamlField<'_>],
+        changes: &mut Vec<DamlBreakingChange>,
+    ) {
+        let mut removed = false;
+        for old_constructor in old {
+            if let Some(new_constructor) = new.iter().find(|f| f.name() == old_constructor.name()) {
+                if !same_type(old_constructor.ty(), new_constructor.ty()) {
+                    changes.push(DamlBreakingChange::ConstructorTypeChanged(
+                        name.to_owned(),
+                        old_constructor.name().to_owned(),
+                        self.printer.render_type(old_constructor.ty()),
+                        self.printer.render_type(new_constructor.ty()),
+                    ));
+                }
+            } else {
+                removed = true;
+                changes
+                    .push(DamlBreakingChange::ConstructorRemoved(name.to_owned(), old_constructor.name().to_owned()));
+            }
+        }
+        if !removed && !is_prefix(old, new) {
+            changes.push(DamlBreakingChange::ConstructorsReordered(name.to_owned()));
+        }
+    }
+
+    fn check_template(
+        &self,
+        name: &str,
+        old: &DamlTemplate<'_>,
+        new: &DamlTemplate<'_>,
+        changes: &mut Vec<DamlBreakingChange>,
+    ) {
+        for old_choice in old.choices() {
+            match new.choices().iter().find(|c| c.name() == old_choice.name()) {
+                Some(new_choice) => self.check_choice(name, old_choice, new_choice, changes),
+                None => changes.push(DamlBreakingChange::ChoiceRemoved(name.to_owned(), old_choice.name().to_owned())),
+            }
+        }
+        match (old.key(), new.key()) {
+            (Some(old_key), Some(new_key)) =>
+                if !same_type(old_key.ty(), new_key.ty()) {
+                    changes.push(DamlBreakingChange::KeyTypeChanged(
+                        name.to_owned(),
+                        self.printer.render_type(old_key.ty()),
+                        self.printer.render_type(new_key.ty()),
+                    ));
+                },
+            (Some(_), None) => changes.push(DamlBreakingChange::KeyRemoved(name.to_owned())),
+            (None, Some(_)) => changes.push(DamlBreakingChange::KeyAdded(name.to_owned())),
+            (None, None) => {},
+        }
+    }
+
+    fn check_choice(
+        &self,
+        name: &str,
+        old: &DamlChoice<'_>,
+        new: &DamlChoice<'_>,
+        changes: &mut Vec<DamlBreakingChange>,
+    ) {
+        if old.consuming() != new.consuming() {
+            changes.push(DamlBreakingChange::ChoiceConsumingChanged(name.to_owned(), old.name().to_owned()));
+        }
+        if !same_argument(old, new) {
+            changes.push(DamlBreakingChange::ChoiceArgumentTypeChanged(
+                name.to_owned(),
+                old.name().to_owned(),
+                self.render_argument(old),
+                self.render_argument(new),
+            ));
+        }
+        if !same_type(old.return_type(), new.return_type()) {
+            changes.push(DamlBreakingChange::ChoiceReturnTypeChanged(
+                name.to_owned(),
+                old.name().to_owned(),
+                self.printer.render_type(old.return_type()),
+                self.printer.render_type(new.return_type()),
+            ));
+        }
+    }
+
+    #[cfg(feature = "full")]
+    fn render_argument(&self, choice: &DamlChoice<'_>) -> String {
+        self.printer.render_type(choice.argument_type())
+    }
+
+    #[cfg(not(feature = "full"))]
+    fn render_argument(&self, choice: &DamlChoice<'_>) -> String {
+        let fields =
+            choice.fields().iter().map(|field| format!("{} : {}", field.name(), self.printer.render_type(field.ty())));
+        format!("{{ {} }}", fields.format(", "))
+    }
+}
+
+fn main_package<'a>(archive: &'a DamlArchive<'_>) -> DamlLfResult<&'a DamlPackage<'a>> {
+    archive.main_package().ok_or_else(|| {
+        DamlLfError::new_dar_parse_error(format!("main package {} not found", archive.main_package_id()))
+    })
+}
+
+/// Collect all serializable data types of a module and its children, keyed by qualified name.
+fn serializable_data<'a>(module: &'a DamlModule<'a>) -> BTreeMap<String, &'a DamlData<'a>> {
+    let mut data = module
+        .data_types()
+        .filter(|data| data.serializable())
+        .map(|data| (format!("{}:{}", data.module_path().join("."), data.name()), data))
+        .collect::<BTreeMap<_, _>>();
+    for child in module.child_modules() {
+        data.extend(serializable_data(child));
+    }
+    data
+}
+
+fn data_kind(data: &DamlData<'_>) -> &'static str {
+    match data {
+        DamlData::Template(_) => "template",
+        DamlData::Record(_) => "record",
+        DamlData::Variant(_) => "variant",
+        DamlData::Enum(_) => "enum",
+    }
+}
+
+/// Are the names of the `old` fields a prefix of the names of the `new` fields?
+fn is_prefix(old: &[DamlField<'_>], new: &[DamlField<'_>]) -> bool {
+    old.len() <= new.len() && old.iter().zip(new).all(|(o, n)| o.name() == n.name())
+}
+
+/// Compare two types, ignoring the package ids of type constructors.
+fn same_type(old: &DamlType<'_>, new: &DamlType<'_>) -> bool {
+    match (old, new) {
+        (DamlType::ContractId(old), DamlType::ContractId(new)) => match (old, new) {
+            (Some(old), Some(new)) => same_type(old, new),
+            (None, None) => true,
+            _ => false,
+        },
+        (DamlType::Numeric(old), DamlType::Numeric(new))
+        | (DamlType::List(old), DamlType::List(new))
+        | (DamlType::TextMap(old), DamlType::TextMap(new))
+        | (DamlType::GenMap(old), DamlType::GenMap(new))
+        | (DamlType::Optional(old), DamlType::Optional(new))
+        | (DamlType::Arrow(old), DamlType::Arrow(new))
+        | (DamlType::Update(old), DamlType::Update(new))
+        | (DamlType::Scenario(old), DamlType::Scenario(new)) => same_types(old, new),
+        (DamlType::TyCon(old) | DamlType::BoxedTyCon(old), DamlType::TyCon(new) | DamlType::BoxedTyCon(new)) =>
+            same_tycon_name(old.tycon(), new.tycon()) && same_types(old.type_arguments(), new.type_arguments()),
+        (DamlType::Var(old), DamlType::Var(new)) =>
+            old.var() == new.var() && same_types(old.type_arguments(), new.type_arguments()),
+        (DamlType::Nat(old), DamlType::Nat(new)) => old == new,
+        (DamlType::Forall(_), DamlType::Forall(_))
+        | (DamlType::Struct(_), DamlType::Struct(_))
+        | (DamlType::Syn(_), DamlType::Syn(_)) => {
+            let printer = PrettyPrinter::new().elide_package_ids(true);
+            printer.render_type(old) == printer.render_type(new)
+        },
+        _ => mem::discriminant(old) == mem::discriminant(new),
+    }
+}
+
+/// Compare the argument types of two choices.
+#[cfg(feature = "full")]
+fn same_argument(old: &DamlChoice<'_>, new: &DamlChoice<'_>) -> bool {
+    same_type(old.argument_type(), new.argument_type())
+}
+
+/// Compare the argument fields of two choices.
+#[cfg(not(feature = "full"))]
+fn same_argument(old: &DamlChoice<'_>, new: &DamlChoice<'_>) -> bool {
+    old.fields().len() == new.fields().len()
+        && old.fields().iter().zip(new.fields()).all(|(o, n)| o.name() == n.name() && same_type(o.ty(), n.ty()))
+}
+
+fn same_types(old: &[DamlType<'_>], new: &[DamlType<'_>]) -> bool {
+    old.len() == new.len() && old.iter().zip(new).all(|(o, n)| same_type(o, n))
+}
+
+fn same_tycon_name(old: &DamlTyConName<'_>, new: &DamlTyConName<'_>) -> bool {
+    old.package_name() == new.package_name()
+        && old.data_name() == new.data_name()
+        && old.module_path().eq(new.module_path())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compatibility::DamlBreakingChange;
+    #[cfg(feature = "full")]
+    use crate::element::DamlExpr;
+    use crate::element::{
+        DamlChoice, DamlData, DamlEnum, DamlFeatureFlags, DamlField, DamlModule, DamlPackage, DamlRecord, DamlTemplate,
+        DamlTyCon, DamlType, DamlVariant,
+    };
+    use crate::{DamlCompatibilityChecker, LanguageVersion};
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+
+    fn package(data: Vec<DamlData<'static>>) -> DamlPackage<'static> {
+        let path = vec![Cow::from("Test")];
+        let data = data.into_iter().map(|d| (Cow::from(d.name().to_owned()), d)).collect();
+        #[cfg(feature = "full")]
+        let module = DamlModule::new_leaf(
+            path,
+            DamlFeatureFlags::default(),
+            vec![],
+            data,
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+        );
+        #[cfg(not(feature = "full"))]
+        let module = DamlModule::new_leaf(path, DamlFeatureFlags::default(), vec![], data, HashMap::new());
+        DamlPackage::new(Cow::from("Test"), Cow::from("pkg"), None, LanguageVersion::V1_14, module)
+    }
+
+    fn field(name: &'static str, ty: DamlType<'static>) -> DamlField<'static> {
+        DamlField::new(Cow::from(name), ty)
+    }
+
+    fn record(fields: Vec<DamlField<'static>>) -> DamlData<'static> {
+        DamlData::Record(DamlRecord::new(
+            Cow::from("Rec"),
+            Cow::from("pkg"),
+            vec![Cow::from("Test")],
+            fields,
+            vec![],
+            true,
+        ))
+    }
+
+    fn template(choices: Vec<DamlChoice<'static>>) -> DamlData<'static> {
+        DamlData::Template(Box::new(DamlTemplate::new(
+            Cow::from("Tmpl"),
+            Cow::from("pkg"),
+            vec![Cow::from("Test")],
+            vec![field("owner", DamlType::Party)],
+            choices,
+            Cow::from("this"),
+            #[cfg(feature = "full")]
+            None,
+            #[cfg(feature = "full")]
+            DamlExpr::Nil(DamlType::Party),
+            #[cfg(feature = "full")]
+            DamlExpr::Nil(DamlType::Party),
+            #[cfg(feature = "full")]
+            DamlExpr::Nil(DamlType::Party),
+            None,
+            vec![],
+            true,
+        )))
+    }
+
+    #[cfg_attr(not(feature = "full"), allow(unused_variables))]
+    fn choice(argument: &'static str, fields: Vec<DamlField<'static>>) -> DamlChoice<'static> {
+        DamlChoice::new(
+            Cow::from("Go"),
+            Cow::from("pkg"),
+            vec![Cow::from("Test")],
+            fields,
+            DamlType::Unit,
+            true,
+            Cow::from("self"),
+            #[cfg(feature = "full")]
+            Cow::from("arg"),
+            #[cfg(feature = "full")]
+            DamlType::TyCon(DamlTyCon::new_absolute("pkg", &["Test"], argument)),
+            #[cfg(feature = "full")]
+            DamlExpr::Nil(DamlType::Party),
+            #[cfg(feature = "full")]
+            DamlExpr::Nil(DamlType::Party),
+            #[cfg(feature = "full")]
+            DamlExpr::Nil(DamlType::Party),
+        )
+    }
+
+    #[test]
+    fn test_compatible_record() {
+        let old = package(vec![record(vec![field("a", DamlType::Int64)])]);
+        let new = package(vec![record(vec![
+            field("a", DamlType::Int64),
+            field("b", DamlType::Optional(vec![DamlType::Text])),
+        ])]);
+        assert!(DamlCompatibilityChecker::new().check_packages(&old, &new).is_compatible());
+    }
+
+    #[test]
+    fn test_incompatible_record() {
+        let old = package(vec![record(vec![field("a", DamlType::Int64), field("b", DamlType::Text)])]);
+        let new = package(vec![record(vec![
+            field("b", DamlType::Text),
+            field("a", DamlType::Party),
+            field("c", DamlType::Text),
+        ])]);
+        let report = DamlCompatibilityChecker::new().check_packages(&old, &new);
+        assert_eq!(
+            &[
+                DamlBreakingChange::FieldTypeChanged(
+                    "Test:Rec".to_owned(),
+                    "a".to_owned(),
+                    "Int64".to_owned(),
+                    "Party".to_owned()
+                ),
+                DamlBreakingChange::FieldAdded("Test:Rec".to_owned(), "c".to_owned()),
+                DamlBreakingChange::FieldsReordered("Test:Rec".to_owned()),
+            ],
+            report.changes()
+        );
+    }
+
+    #[test]
+    fn test_removed_and_changed_data() {
+        let variant = DamlData::Variant(DamlVariant::new(
+            Cow::from("Var"),
+            Cow::from("pkg"),
+            vec![Cow::from("Test")],
+            vec![field("A", DamlType::Unit)],
+            vec![],
+            true,
+        ));
+        let enum_data = |name: &'static str, constructors: Vec<&'static str>| {
+            DamlData::Enum(DamlEnum::new(
+                Cow::from(name),
+                Cow::from("pkg"),
+                vec![Cow::from("Test")],
+                constructors.into_iter().map(Cow::from).collect(),
+                vec![],
+                true,
+            ))
+        };
+        let old = package(vec![variant, enum_data("Enum", vec!["X", "Y"]), record(vec![])]);
+        let new = package(vec![
+            enum_data("Var", vec!["A"]),
+            enum_data("Enum", vec!["Z", "X", "Y"]),
+            DamlData::Record(DamlRecord::new(
+                Cow::from("Other"),
+                Cow::from("pkg"),
+                vec![Cow::from("Test")],
+                vec![field("r", DamlType::TyCon(DamlTyCon::new_absolute("other", &["Test"], "Rec")))],
+                vec![],
+                true,
+            )),
+        ]);
+        let report = DamlCompatibilityChecker::new().check_packages(&old, &new);
+        assert_eq!(
+            &[
+                DamlBreakingChange::ConstructorsReordered("Test:Enum".to_owned()),
+                DamlBreakingChange::DataRemoved("Test:Rec".to_owned()),
+                DamlBreakingChange::DataKindChanged("Test:Var".to_owned(), "variant".to_owned(), "enum".to_owned()),
+            ],
+            report.changes()
+        );
+    }
+
+    #[test]
+    fn test_choice_argument_type_changed() {
+        let old = package(vec![template(vec![choice("Go", vec![field("a", DamlType::Int64)])])]);
+        let same = package(vec![template(vec![choice("Go", vec![field("a", DamlType::Int64)])])]);
+        assert!(DamlCompatibilityChecker::new().check_packages(&old, &same).is_compatible());
+        let new = package(vec![template(vec![choice("GoAgain", vec![field("a", DamlType::Text)])])]);
+        let report = DamlCompatibilityChecker::new().check_packages(&old, &new);
+        #[cfg(feature = "full")]
+        let (old_argument, new_argument) = ("Test:Go", "Test:GoAgain");
+        #[cfg(not(feature = "full"))]
+        let (old_argument, new_argument) = ("{ a : Int64 }", "{ a : Text }");
+        assert_eq!(
+            &[DamlBreakingChange::ChoiceArgumentTypeChanged(
+                "Test:Tmpl".to_owned(),
+                "Go".to_owned(),
+                old_argument.to_owned(),
+                new_argument.to_owned()
+            )],
+            report.changes()
+        );
+    }
+}
@@ -3,6 +3,7 @@ mod archive_payload;
 mod data_box_checker;
 mod data_payload;
 mod field_payload;
+mod interface_payload;
 mod interned;
 mod module_payload;
 mod package_payload;
@@ -19,27 +20,48 @@ mod expr_converter;
 #[cfg(feature = "full")]
 mod expr_payload;
 
-use crate::convert::archive_payload::{DamlArchivePayload, DamlArchiveWrapper};
-use crate::convert::package_payload::DamlPackagePayload;
+pub use crate::convert::archive_payload::DamlArchivePayload;
+
+use crate::convert::archive_payload::DamlArchiveWrapper;
 use crate::convert::util::Required;
-use crate::element::{DamlArchive, DamlPackage};
-use crate::{DamlLfArchive, DamlLfArchivePayload, DamlLfHashFunction, DamlLfResult, DarFile};
+use crate::element::{DamlArchive, DamlModule, DamlPackage};
+use crate::error::{DamlLfConvertError, DamlLfConvertResult};
+use crate::{
+    DamlLazyArchive, DamlLfArchive, DamlLfArchivePayload, DamlLfHashFunction, DamlLfResult, DarFile, LazyDarFile,
+};
 use bounded_static::ToBoundedStatic;
 use std::convert::TryFrom;
 
 /// Create an owned [`DamlArchive`] from a [`DarFile`].
 pub fn to_owned_archive(dar: &DarFile) -> DamlLfResult<DamlArchive<'static>> {
-    let archive_payload = DamlArchivePayload::try_from(dar)?;
+    let archive_payload = DamlArchivePayload::from(dar);
     let archive = DamlArchive::try_from(DamlArchiveWrapper::new(&archive_payload))?;
     Ok(archive.to_static())
 }
 
+/// Create owned [`DamlPackage`] for the given package ids from a [`DarFile`].
+///
+/// Only the requested packages are converted, all other packages in the [`DarFile`] are used to resolve references.
+pub fn to_owned_packages<S: AsRef<str>>(dar: &DarFile, package_ids: &[S]) -> DamlLfResult<Vec<DamlPackage<'static>>> {
+    let archive_payload = DamlArchivePayload::from(dar);
+    let archive_wrapper = DamlArchiveWrapper::new(&archive_payload);
+    package_ids
+        .iter()
+        .map(|package_id| {
+            let package_payload = archive_payload
+                .package_by_id(package_id.as_ref())?
+                .ok_or_else(|| DamlLfConvertError::UnknownPackage(package_id.as_ref().to_owned()))?;
+            Ok(DamlPackage::try_from(archive_wrapper.with_package(package_payload))?.to_static())
+        })
+        .collect()
+}
+
 /// Convert a [`DarFile`] to a [`DamlArchive`] and map function `f` over it.
 pub fn apply_dar<R, F>(dar: &DarFile, f: F) -> DamlLfResult<R>
 where
     F: FnOnce(&DamlArchive<'_>) -> R,
 {
-    let archive_payload = DamlArchivePayload::try_from(dar)?;
+    let archive_payload = DamlArchivePayload::from(dar);
     let archive_wrapper = DamlArchiveWrapper::new(&archive_payload);
     let archive = DamlArchive::try_from(archive_wrapper)?;
     Ok(f(&archive))
@@ -50,8 +72,7 @@ pub fn apply_dalf<R, F>(dalf: &DamlLfArchive, f: F) -> DamlLfResult<R>
 where
     F: FnOnce(&DamlPackage<'_>) -> R,
 {
-    let package_payload = DamlPackagePayload::try_from(dalf)?;
-    let archive_payload = DamlArchivePayload::from_single_package(package_payload);
+    let archive_payload = DamlArchivePayload::from_single_archive(dalf);
     let archive_wrapper = DamlArchiveWrapper::new(&archive_payload);
     let archive = DamlArchive::try_from(archive_wrapper)?;
     let package = archive.packages().next().req()?;
@@ -64,10 +85,54 @@ where
     F: FnOnce(&DamlPackage<'_>) -> R,
 {
     let dalf = DamlLfArchive::new("unnamed", payload, DamlLfHashFunction::Sha256, "");
-    let package_payload = DamlPackagePayload::try_from(&dalf)?;
-    let archive_payload = DamlArchivePayload::from_single_package(package_payload);
+    let archive_payload = DamlArchivePayload::from_single_archive(&dalf);
     let archive_wrapper = DamlArchiveWrapper::new(&archive_payload);
     let archive = DamlArchive::try_from(archive_wrapper)?;
     let package = archive.packages().next().req()?;
     Ok(f(package))
 }
+
+/// Create a [`DamlLazyArchive`] from a [`DarFile`] and apply it to `f`.
+pub fn apply_lazy<R, F>(dar: &DarFile, f: F) -> R
+where
+    F: FnOnce(&DamlLazyArchive<'_>) -> R,
+{
+    let archive_payload = DamlArchivePayload::from(dar);
+    f(&DamlLazyArchive::new(&archive_payload))
+}
+
+/// Create a [`DamlLazyArchive`] from a [`LazyDarFile`] and apply it to `f`.
+pub fn apply_lazy_dar<R, F>(dar: &LazyDarFile, f: F) -> R
+where
+    F: FnOnce(&DamlLazyArchive<'_>) -> R,
+{
+    let archive_payload = DamlArchivePayload::from(dar);
+    f(&DamlLazyArchive::new(&archive_payload))
+}
+
+/// Convert a single [`DamlPackage`] from a [`DamlArchivePayload`].
+pub fn convert_package<'a>(
+    archive: &'a DamlArchivePayload<'a>,
+    package_id: &str,
+) -> DamlLfConvertResult<Option<DamlPackage<'a>>> {
+    archive
+        .package_by_id(package_id)?
+        .map(|package| DamlPackage::try_from(DamlArchiveWrapper::new(archive).with_package(package)))
+        .transpose()
+}
+
+/// Convert a single leaf [`DamlModule`] from a [`DamlArchivePayload`].
+pub fn convert_module<'a>(
+    archive: &'a DamlArchivePayload<'a>,
+    package_id: &str,
+    module_path: &str,
+) -> DamlLfConvertResult<Option<DamlModule<'a>>> {
+    let package = match archive.package_by_id(package_id)? {
+        Some(package) => package,
+        None => return Ok(None),
+    };
+    package
+        .module_by_name(module_path)?
+        .map(|module| DamlModule::try_from(&DamlArchiveWrapper::new(archive).with_package(package).with_module(module)))
+        .transpose()
+}
@@ -6,6 +6,9 @@ use crate::convert::data_payload::{
 #[cfg(feature = "full")]
 use crate::convert::expr_payload::DamlKeyExprPayload;
 use crate::convert::field_payload::{DamlFieldPayload, DamlFieldWrapper};
+use crate::convert::interface_payload::{
+    DamlInterfaceInstanceMethodWrapper, DamlInterfaceInstanceWrapper, DamlInterfaceMethodWrapper, DamlInterfaceWrapper,
+};
 use crate::convert::interned::{InternableDottedName, PackageInternedResolver};
 use crate::convert::module_payload::{DamlDefTypeSynWrapper, DamlFeatureFlagsPayload, DamlModuleWrapper};
 use crate::convert::package_payload::{DamlPackagePayload, DamlPackageWrapper};
@@ -19,14 +22,15 @@ use crate::convert::typevar_payload::{DamlKindPayload, DamlTypeVarWithKindPayloa
 use crate::convert::util::Required;
 use crate::convert::wrapper::{DamlPayloadParentContext, DamlPayloadParentContextType, PayloadElementWrapper};
 #[cfg(feature = "full")]
-use crate::element::DamlDefValue;
-#[cfg(feature = "full")]
 use crate::element::DamlExpr;
 use crate::element::{
     DamlArchive, DamlArrow, DamlChoice, DamlData, DamlDefKey, DamlDefTypeSyn, DamlEnum, DamlFeatureFlags, DamlField,
-    DamlForall, DamlKind, DamlLocalTyCon, DamlModule, DamlNonLocalTyCon, DamlPackage, DamlRecord, DamlStruct, DamlSyn,
-    DamlTemplate, DamlTyCon, DamlTyConName, DamlType, DamlTypeSynName, DamlTypeVarWithKind, DamlVar, DamlVariant,
+    DamlForall, DamlInterface, DamlInterfaceInstance, DamlInterfaceInstanceMethod, DamlInterfaceMethod, DamlKind,
+    DamlLocalTyCon, DamlModule, DamlNonLocalTyCon, DamlPackage, DamlRecord, DamlStruct, DamlSyn, DamlTemplate,
+    DamlTyCon, DamlTyConName, DamlType, DamlTypeSynName, DamlTypeVarWithKind, DamlVar, DamlVariant,
 };
+#[cfg(feature = "full")]
+use crate::element::{DamlDefException, DamlDefValue};
 use crate::error::{DamlLfConvertError, DamlLfConvertResult};
 use crate::LanguageFeatureVersion;
 use std::borrow::Cow;
@@ -40,9 +44,11 @@ impl<'a> TryFrom<DamlArchiveWrapper<'a>> for DamlArchive<'a> {
     fn try_from(archive: DamlArchiveWrapper<'a>) -> DamlLfConvertResult<Self> {
         let packages: HashMap<_, _> = archive
             .archive
-            .packages
-            .values()
-            .map(|package| Ok((Cow::from(package.package_id), DamlPackage::try_from(archive.with_package(package))?)))
+            .packages()
+            .map(|package| {
+                let package = package?;
+                Ok((Cow::from(package.package_id), DamlPackage::try_from(archive.with_package(package))?))
+            })
             .collect::<DamlLfConvertResult<_>>()?;
         Ok(DamlArchive::new(Cow::from(archive.archive.name), Cow::from(archive.archive.main_package_id), packages))
     }
@@ -53,12 +59,17 @@ impl<'a> TryFrom<DamlPackageWrapper<'a>> for DamlPackage<'a> {
     type Error = DamlLfConvertError;
 
     fn try_from(package: DamlPackageWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let modules = package
+            .package
+            .modules()
+            .map(|module| module.map(|module| package.with_module(module)))
+            .collect::<DamlLfConvertResult<Vec<_>>>()?;
         Ok(DamlPackage::new(
             Cow::from(&package.package.name),
             Cow::from(package.package.package_id),
             package.package.version.as_ref().map(Cow::from),
             package.package.language_version,
-            from_modules(package.package.modules.values().map(|module| package.with_module(module)))?,
+            from_modules(modules.into_iter())?,
         ))
     }
 }
@@ -80,8 +91,24 @@ impl<'a> TryFrom<&DamlModuleWrapper<'a>> for DamlModule<'a> {
             .module
             .data_types
             .iter()
+            .filter(|dt| !matches!(dt, DamlDataPayload::Interface(_)))
             .map(|dt| DamlData::try_from(enriched_data(module.wrap_data(dt))?))
             .collect::<DamlLfConvertResult<_>>()?;
+        if !module.module.interfaces.is_empty()
+            && !module.package.language_version.supports_feature(&LanguageFeatureVersion::INTERFACES)
+        {
+            return Err(DamlLfConvertError::UnsupportedFeatureUsed(
+                module.package.language_version.to_string(),
+                LanguageFeatureVersion::INTERFACES.name.to_string(),
+                LanguageFeatureVersion::INTERFACES.min_version.to_string(),
+            ));
+        }
+        let interfaces: Vec<_> = module
+            .module
+            .interfaces
+            .values()
+            .map(|interface| DamlInterface::try_from(module.wrap_interface(interface)))
+            .collect::<DamlLfConvertResult<_>>()?;
         #[cfg(feature = "full")]
         let values: Vec<_> = module
             .module
@@ -89,13 +116,23 @@ impl<'a> TryFrom<&DamlModuleWrapper<'a>> for DamlModule<'a> {
             .iter()
             .map(|val| DamlDefValue::try_from(&module.wrap_value(val)))
             .collect::<DamlLfConvertResult<_>>()?;
+        #[cfg(feature = "full")]
+        let exceptions: Vec<_> = module
+            .module
+            .exceptions
+            .iter()
+            .map(|exception| DamlDefException::try_from(&module.wrap_exception(exception)))
+            .collect::<DamlLfConvertResult<_>>()?;
         Ok(DamlModule::new_leaf(
             path,
             flags,
             synonyms,
             data_types.into_iter().map(|dt| (dt.name_clone(), dt)).collect(),
+            interfaces.into_iter().map(|interface| (interface.name_clone(), interface)).collect(),
             #[cfg(feature = "full")]
             values.into_iter().map(|val| (val.name_clone(), val)).collect(),
+            #[cfg(feature = "full")]
+            exceptions.into_iter().map(|exception| (exception.name_clone(), exception)).collect(),
         ))
     }
 }
@@ -174,6 +211,11 @@ impl<'a> TryFrom<DamlDataWrapper<'a>> for DamlData<'a> {
                 #[cfg(feature = "full")]
                 let observers = DamlExpr::try_from(&data.wrap(&template.observers))?;
                 let key = template.key.as_ref().map(|k| DamlDefKey::try_from(&data.wrap(k))).transpose()?;
+                let implements = template
+                    .implements
+                    .iter()
+                    .map(|instance| DamlInterfaceInstance::try_from(&data.wrap(instance)))
+                    .collect::<DamlLfConvertResult<_>>()?;
                 let serializable = parent_data.serializable;
                 DamlData::Template(Box::new(DamlTemplate::new(
                     name,
@@ -191,6 +233,7 @@ impl<'a> TryFrom<DamlDataWrapper<'a>> for DamlData<'a> {
                     #[cfg(feature = "full")]
                     observers,
                     key,
+                    implements,
                     serializable,
                 )))
             },
@@ -238,10 +281,102 @@ impl<'a> TryFrom<DamlDataWrapper<'a>> for DamlData<'a> {
                     serializable,
                 ))
             },
+            DamlDataEnrichedPayload::Interface(_) => return Err(DamlLfConvertError::UnexpectedData),
         })
     }
 }
 
+/// Convert from `DamlInterfaceWrapper` to `DamlInterface`.
+impl<'a> TryFrom<DamlInterfaceWrapper<'a>> for DamlInterface<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(interface: DamlInterfaceWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let resolver = interface.context.package;
+        let name = interface.payload.name.resolve_last(resolver)?;
+        let module_path = interface.context.module.path.resolve(resolver)?;
+        let param = interface.payload.param.resolve(resolver)?;
+        let choices = interface
+            .payload
+            .choices
+            .iter()
+            .map(|choice| DamlChoice::try_from(interface.wrap(choice)))
+            .collect::<DamlLfConvertResult<_>>()?;
+        let methods = interface
+            .payload
+            .methods
+            .iter()
+            .map(|method| DamlInterfaceMethod::try_from(&interface.wrap(method)))
+            .collect::<DamlLfConvertResult<_>>()?;
+        let requires = interface
+            .payload
+            .requires
+            .iter()
+            .map(|required| DamlTyConName::try_from(&interface.wrap(required)))
+            .collect::<DamlLfConvertResult<_>>()?;
+        let view = DamlType::try_from(&interface.wrap(&interface.payload.view))?;
+        Ok(DamlInterface::new(
+            name,
+            Cow::from(interface.context.package.package_id),
+            module_path,
+            param,
+            choices,
+            methods,
+            requires,
+            view,
+        ))
+    }
+}
+
+/// Convert from `DamlInterfaceMethodWrapper` to `DamlInterfaceMethod`.
+impl<'a> TryFrom<&DamlInterfaceMethodWrapper<'a>> for DamlInterfaceMethod<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(method: &DamlInterfaceMethodWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let name = method.payload.name.resolve(method.context.package)?;
+        let ty = DamlType::try_from(&method.wrap(&method.payload.ty))?;
+        Ok(DamlInterfaceMethod::new(name, ty))
+    }
+}
+
+/// Convert from `DamlInterfaceInstanceWrapper` to `DamlInterfaceInstance`.
+impl<'a> TryFrom<&DamlInterfaceInstanceWrapper<'a>> for DamlInterfaceInstance<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(instance: &DamlInterfaceInstanceWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let interface = DamlTyConName::try_from(&instance.wrap(&instance.payload.interface))?;
+        let methods = instance
+            .payload
+            .methods
+            .iter()
+            .map(|method| DamlInterfaceInstanceMethod::try_from(&instance.wrap(method)))
+            .collect::<DamlLfConvertResult<_>>()?;
+        #[cfg(feature = "full")]
+        let view = DamlExpr::try_from(&instance.wrap(&instance.payload.view))?;
+        Ok(DamlInterfaceInstance::new(
+            interface,
+            methods,
+            #[cfg(feature = "full")]
+            view,
+        ))
+    }
+}
+
+/// Convert from `DamlInterfaceInstanceMethodWrapper` to `DamlInterfaceInstanceMethod`.
+impl<'a> TryFrom<&DamlInterfaceInstanceMethodWrapper<'a>> for DamlInterfaceInstanceMethod<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(method: &DamlInterfaceInstanceMethodWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let name = method.payload.name.resolve(method.context.package)?;
+        #[cfg(feature = "full")]
+        let value = DamlExpr::try_from(&method.wrap(&method.payload.value))?;
+        Ok(DamlInterfaceInstanceMethod::new(
+            name,
+            #[cfg(feature = "full")]
+            value,
+        ))
+    }
+}
+
 /// Convert from `DamlChoiceWrapper` to `DamlChoice`.
 impl<'a> TryFrom<DamlChoiceWrapper<'a>> for DamlChoice<'a> {
     type Error = DamlLfConvertError;
@@ -272,6 +407,10 @@ impl<'a> TryFrom<DamlChoiceWrapper<'a>> for DamlChoice<'a> {
         let consuming = choice.payload.consuming;
         let self_binder = choice.payload.self_binder.resolve(choice.context.package)?;
         #[cfg(feature = "full")]
+        let argument_binder = choice.payload.argument_name.resolve(choice.context.package)?;
+        #[cfg(feature = "full")]
+        let argument_type = DamlType::try_from(&choice.wrap(&choice.payload.argument_type))?;
+        #[cfg(feature = "full")]
         let update = DamlExpr::try_from(&choice.wrap(&choice.payload.update))?;
         #[cfg(feature = "full")]
         let controllers = DamlExpr::try_from(&choice.wrap(&choice.payload.controllers))?;
@@ -280,7 +419,7 @@ impl<'a> TryFrom<DamlChoiceWrapper<'a>> for DamlChoice<'a> {
             if choice.context.package.language_version.supports_feature(&LanguageFeatureVersion::CHOICE_OBSERVERS) {
                 DamlExpr::try_from(&choice.wrap(choice.payload.observers.as_ref().req()?))?
             } else {
-                DamlExpr::Nil(DamlType::List(vec![DamlType::Party]))
+                DamlExpr::Nil(DamlType::Party)
             };
         Ok(DamlChoice::new(
             name,
@@ -291,6 +430,10 @@ impl<'a> TryFrom<DamlChoiceWrapper<'a>> for DamlChoice<'a> {
             consuming,
             self_binder,
             #[cfg(feature = "full")]
+            argument_binder,
+            #[cfg(feature = "full")]
+            argument_type,
+            #[cfg(feature = "full")]
             update,
             #[cfg(feature = "full")]
             controllers,
@@ -367,8 +510,8 @@ impl<'a> TryFrom<&DamlTypeWrapper<'a>> for DamlType<'a> {
             DamlTypePayload::Unit => DamlType::Unit,
             DamlTypePayload::Date => DamlType::Date,
             DamlTypePayload::List(args) => DamlType::List(wrap_type_args(daml_type, args)?),
-            DamlTypePayload::Update => DamlType::Update,
-            DamlTypePayload::Scenario => DamlType::Scenario,
+            DamlTypePayload::Update(args) => DamlType::Update(wrap_type_args(daml_type, args)?),
+            DamlTypePayload::Scenario(args) => DamlType::Scenario(wrap_type_args(daml_type, args)?),
             DamlTypePayload::TextMap(args) => DamlType::TextMap(wrap_type_args(daml_type, args)?),
             DamlTypePayload::GenMap(args) => DamlType::GenMap(wrap_type_args(daml_type, args)?),
             DamlTypePayload::Optional(args) => DamlType::Optional(wrap_type_args(daml_type, args)?),
@@ -385,13 +528,15 @@ impl<'a> TryFrom<&DamlTypeWrapper<'a>> for DamlType<'a> {
                         }
                     },
                     // We are not in a context with a DamlDataPayload and so we do not need to Box this reference
-                    DamlPayloadParentContextType::DefTypeSyn(_) => DamlType::TyCon(tycon),
+                    DamlPayloadParentContextType::DefTypeSyn(_) | DamlPayloadParentContextType::Interface(_) =>
+                        DamlType::TyCon(tycon),
                     #[cfg(feature = "full")]
-                    DamlPayloadParentContextType::Value(_) => DamlType::TyCon(tycon),
+                    DamlPayloadParentContextType::Value(_) | DamlPayloadParentContextType::Exception(_) =>
+                        DamlType::TyCon(tycon),
                 }
             },
             DamlTypePayload::Var(var) => DamlType::Var(DamlVar::try_from(&daml_type.wrap(var))?),
-            DamlTypePayload::Arrow => DamlType::Arrow,
+            DamlTypePayload::Arrow(args) => DamlType::Arrow(wrap_type_args(daml_type, args)?),
             DamlTypePayload::Any => DamlType::Any,
             DamlTypePayload::TypeRep => DamlType::TypeRep,
             DamlTypePayload::Bignumeric => DamlType::Bignumeric,
@@ -564,7 +709,7 @@ fn from_modules<'a, T: Iterator<Item = DamlModuleWrapper<'a>>>(modules: T) -> Da
 /// go, and adds the `node_to_add` node as the leaf node.
 fn add_module_to_tree<'a>(node: &mut DamlModule<'a>, node_to_add: DamlModule<'a>, remaining_path: &[&'a str]) {
     if let Some(&child_mod_name) = remaining_path.first() {
-        add_module_to_tree(node.child_module_or_new(child_mod_name), node_to_add, &remaining_path[1..]);
+        add_module_to_tree(node.child_module_or_new(Cow::from(child_mod_name)), node_to_add, &remaining_path[1..]);
     } else {
         node.take_from(node_to_add);
     }
@@ -583,7 +728,7 @@ fn make_tycon_name<'a>(
     let target_package_id = package_ref.resolve(source_resolver)?;
     let target_package: &DamlPackagePayload<'_> = context
         .archive
-        .package_by_id(&target_package_id)
+        .package_by_id(&target_package_id)?
         .ok_or_else(|| DamlLfConvertError::UnknownPackage(target_package_id.to_string()))?;
     let target_package_name = Cow::from(target_package.name.as_str());
     let target_module_path = module_path.resolve(source_resolver)?;
@@ -3,7 +3,9 @@ use std::collections::HashMap;
 
 use crate::convert::package_payload::{DamlPackagePayload, DamlPackageWrapper};
 use crate::error::{DamlLfConvertError, DamlLfConvertResult};
-use crate::DarFile;
+use crate::lazy::LazyDalf;
+use crate::{DamlLfArchive, DarFile, LazyDarFile};
+use once_cell::sync::OnceCell;
 use std::convert::TryFrom;
 
 ///
@@ -13,13 +15,13 @@ pub struct DamlArchiveWrapper<'a> {
 }
 
 impl<'a> DamlArchiveWrapper<'a> {
-    pub const fn new(archive: &'a DamlArchivePayload<'_>) -> Self {
+    pub const fn new(archive: &'a DamlArchivePayload<'a>) -> Self {
         Self {
             archive,
         }
     }
 
-    pub const fn with_package(self, package: &'a DamlPackagePayload<'_>) -> DamlPackageWrapper<'a> {
+    pub const fn with_package(self, package: &'a DamlPackagePayload<'a>) -> DamlPackageWrapper<'a> {
         DamlPackageWrapper {
             archive: self.archive,
             package,
@@ -27,43 +29,114 @@ impl<'a> DamlArchiveWrapper<'a> {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct DamlArchivePayload<'a> {
     pub name: &'a str,
     pub main_package_id: &'a str,
-    pub packages: HashMap<&'a str, DamlPackagePayload<'a>>,
+    pub packages: HashMap<&'a str, DamlLazyPackagePayload<'a>>,
 }
 
 impl<'a> DamlArchivePayload<'a> {
-    pub fn from_single_package(package: DamlPackagePayload<'a>) -> Self {
+    pub fn from_single_archive(archive: &'a DamlLfArchive) -> Self {
         Self {
             name: "",
-            main_package_id: package.package_id,
-            packages: vec![("", package)].into_iter().collect(),
+            main_package_id: &archive.hash,
+            packages: iter::once((archive.hash.as_str(), DamlLazyPackagePayload::from(archive))).collect(),
         }
     }
 
-    pub fn package_by_id(&self, name: &str) -> Option<&DamlPackagePayload<'_>> {
-        self.packages.get(name)
+    /// Get the `DamlPackagePayload` with the given id, creating it if it has not yet been accessed.
+    pub fn package_by_id(&'a self, package_id: &str) -> DamlLfConvertResult<Option<&'a DamlPackagePayload<'a>>> {
+        self.packages.get(package_id).map(DamlLazyPackagePayload::payload).transpose()
     }
-}
 
-impl<'a> TryFrom<&'a DarFile> for DamlArchivePayload<'a> {
-    type Error = DamlLfConvertError;
+    /// Get all `DamlPackagePayload` in this archive, creating any which have not yet been accessed.
+    pub fn packages(&'a self) -> impl Iterator<Item = DamlLfConvertResult<&'a DamlPackagePayload<'a>>> {
+        self.packages.values().map(DamlLazyPackagePayload::payload)
+    }
+}
 
-    fn try_from(dar_file: &'a DarFile) -> DamlLfConvertResult<Self> {
-        let name = &dar_file.main.name;
-        let main_package_id = &dar_file.main.hash;
-        let packages: HashMap<_, _> = dar_file
+impl<'a> From<&'a DarFile> for DamlArchivePayload<'a> {
+    fn from(dar_file: &'a DarFile) -> Self {
+        let packages = dar_file
             .dependencies
             .iter()
             .chain(iter::once(&dar_file.main))
-            .map(|p| Ok((p.hash.as_str(), DamlPackagePayload::try_from(p)?)))
-            .collect::<DamlLfConvertResult<_>>()?;
-        Ok(Self {
-            name,
-            main_package_id,
+            .map(|archive| (archive.hash.as_str(), DamlLazyPackagePayload::from(archive)))
+            .collect();
+        Self {
+            name: &dar_file.main.name,
+            main_package_id: &dar_file.main.hash,
+            packages,
+        }
+    }
+}
+
+impl<'a> From<&'a LazyDarFile> for DamlArchivePayload<'a> {
+    fn from(dar_file: &'a LazyDarFile) -> Self {
+        let packages = dar_file.dalfs().map(|dalf| (dalf.hash(), DamlLazyPackagePayload::from(dalf))).collect();
+        Self {
+            name: dar_file.name(),
+            main_package_id: dar_file.main_package_id(),
             packages,
+        }
+    }
+}
+
+/// A `DamlPackagePayload` which is created from its source on first access.
+#[derive(Debug)]
+pub struct DamlLazyPackagePayload<'a> {
+    source: DamlPackageSource<'a>,
+    payload: OnceCell<DamlPackagePayload<'a>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DamlPackageSource<'a> {
+    Archive(&'a DamlLfArchive),
+    Dalf(&'a LazyDalf),
+}
+
+impl<'a> DamlLazyPackagePayload<'a> {
+    const fn new(source: DamlPackageSource<'a>) -> Self {
+        Self {
+            source,
+            payload: OnceCell::new(),
+        }
+    }
+
+    /// Get the `DamlPackagePayload`, decoding the underlying `DamlLfArchive` if required.
+    pub fn payload(&'a self) -> DamlLfConvertResult<&'a DamlPackagePayload<'a>> {
+        self.payload.get_or_try_init(|| match self.source {
+            DamlPackageSource::Archive(archive) => DamlPackagePayload::try_from(archive),
+            DamlPackageSource::Dalf(dalf) => {
+                let archive = dalf
+                    .archive()
+                    .map_err(|err| DamlLfConvertError::DecodePackage(dalf.hash().to_owned(), err.to_string()))?;
+                DamlPackagePayload::try_from(archive)
+            },
         })
     }
+
+    /// Get the name of the package without creating the `DamlPackagePayload` where possible.
+    pub fn name(&'a self) -> DamlLfConvertResult<&'a str> {
+        match self.source {
+            DamlPackageSource::Archive(_) => Ok(self.payload()?.name.as_str()),
+            DamlPackageSource::Dalf(dalf) => dalf
+                .info()
+                .map(|info| info.package_name.as_str())
+                .map_err(|err| DamlLfConvertError::DecodePackage(dalf.hash().to_owned(), err.to_string())),
+        }
+    }
+}
+
+impl<'a> From<&'a DamlLfArchive> for DamlLazyPackagePayload<'a> {
+    fn from(archive: &'a DamlLfArchive) -> Self {
+        Self::new(DamlPackageSource::Archive(archive))
+    }
+}
+
+impl<'a> From<&'a LazyDalf> for DamlLazyPackagePayload<'a> {
+    fn from(dalf: &'a LazyDalf) -> Self {
+        Self::new(DamlPackageSource::Dalf(dalf))
+    }
 }
@@ -83,12 +83,12 @@ impl<'a> DamlDataBoxChecker<'a> {
             | DamlTypePayload::Unit
             | DamlTypePayload::Date
             | DamlTypePayload::List(_)
-            | DamlTypePayload::Update
-            | DamlTypePayload::Scenario
+            | DamlTypePayload::Update(_)
+            | DamlTypePayload::Scenario(_)
             | DamlTypePayload::TextMap(_)
             | DamlTypePayload::GenMap(_)
             | DamlTypePayload::Var(_)
-            | DamlTypePayload::Arrow
+            | DamlTypePayload::Arrow(_)
             | DamlTypePayload::Any
             | DamlTypePayload::TypeRep
             | DamlTypePayload::Bignumeric
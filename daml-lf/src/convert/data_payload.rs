@@ -1,6 +1,7 @@
 #[cfg(feature = "full")]
 use crate::convert::expr_payload::{DamlExprPayload, DamlKeyExprPayload};
 use crate::convert::field_payload::DamlFieldPayload;
+use crate::convert::interface_payload::{DamlInterfaceInstancePayload, DamlInterfacePayload};
 use crate::convert::interned::{InternableDottedName, InternableString};
 use crate::convert::type_payload::DamlTypePayload;
 use crate::convert::typevar_payload::DamlTypeVarWithKindPayload;
@@ -20,6 +21,7 @@ pub enum DamlDataPayload<'a> {
     Record(DamlRecordPayload<'a>),
     Variant(DamlVariantPayload<'a>),
     Enum(DamlEnumPayload<'a>),
+    Interface(InternableDottedName<'a>),
 }
 
 impl<'a> PartialEq for DamlDataPayload<'a> {
@@ -46,6 +48,7 @@ impl<'a> DamlDataPayload<'a> {
             DamlDataPayload::Record(record) => &record.name,
             DamlDataPayload::Variant(variant) => &variant.name,
             DamlDataPayload::Enum(daml_enum) => &daml_enum.name,
+            DamlDataPayload::Interface(name) => name,
         }
     }
 }
@@ -81,6 +84,7 @@ impl<'a> TryFrom<&'a DefDataType> for DamlDataPayload<'a> {
                 type_params,
                 serializable,
             )),
+            DataCons::Interface(_) => DamlDataPayload::Interface(name),
         })
     }
 }
@@ -91,6 +95,7 @@ pub enum DamlDataEnrichedPayload<'a> {
     Template(&'a DamlTemplatePayload<'a>),
     Variant(&'a DamlVariantPayload<'a>),
     Enum(&'a DamlEnumPayload<'a>),
+    Interface(&'a DamlInterfacePayload<'a>),
 }
 
 impl<'a> DamlDataEnrichedPayload<'a> {
@@ -109,6 +114,11 @@ impl<'a> DamlDataEnrichedPayload<'a> {
                 Ok(DamlDataEnrichedPayload::Variant(variant)),
             DamlPayloadParentContextType::Data(DamlDataPayload::Enum(data_enum)) =>
                 Ok(DamlDataEnrichedPayload::Enum(data_enum)),
+            DamlPayloadParentContextType::Data(DamlDataPayload::Interface(name)) =>
+                data.module
+                    .interface(name)
+                    .map(DamlDataEnrichedPayload::Interface)
+                    .ok_or(DamlLfConvertError::UnexpectedData),
             _ => Err(DamlLfConvertError::InternalError(
                 "DamlPayloadParentContext.parent not of expected type".to_owned(),
             )),
@@ -122,6 +132,7 @@ impl<'a> DamlDataEnrichedPayload<'a> {
             DamlDataEnrichedPayload::Template(template) => template.name,
             DamlDataEnrichedPayload::Variant(variant) => variant.name,
             DamlDataEnrichedPayload::Enum(data_enum) => data_enum.name,
+            DamlDataEnrichedPayload::Interface(interface) => interface.name,
         }
     }
 }
@@ -140,6 +151,7 @@ pub struct DamlTemplatePayload<'a> {
     #[cfg(feature = "full")]
     pub observers: DamlExprPayload<'a>,
     pub key: Option<DamlDefKeyPayload<'a>>,
+    pub implements: Vec<DamlInterfaceInstancePayload<'a>>,
 }
 
 impl<'a> DamlTemplatePayload<'a> {
@@ -153,6 +165,7 @@ impl<'a> DamlTemplatePayload<'a> {
         #[cfg(feature = "full")] agreement: DamlExprPayload<'a>,
         #[cfg(feature = "full")] observers: DamlExprPayload<'a>,
         key: Option<DamlDefKeyPayload<'a>>,
+        implements: Vec<DamlInterfaceInstancePayload<'a>>,
     ) -> Self {
         Self {
             name,
@@ -167,6 +180,7 @@ impl<'a> DamlTemplatePayload<'a> {
             #[cfg(feature = "full")]
             observers,
             key,
+            implements,
         }
     }
 }
@@ -188,6 +202,11 @@ impl<'a> TryFrom<&'a DefTemplate> for DamlTemplatePayload<'a> {
             #[cfg(feature = "full")]
             DamlExprPayload::try_from(def_template.observers.as_ref().req()?)?,
             def_template.key.as_ref().map(DamlDefKeyPayload::try_from).transpose()?,
+            def_template
+                .implements
+                .iter()
+                .map(DamlInterfaceInstancePayload::try_from)
+                .collect::<DamlLfConvertResult<_>>()?,
         ))
     }
 }
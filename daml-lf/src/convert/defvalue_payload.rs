@@ -4,7 +4,7 @@ use crate::convert::type_payload::DamlTypePayload;
 use crate::convert::util::Required;
 use crate::convert::wrapper::PayloadElementWrapper;
 use crate::error::{DamlLfConvertError, DamlLfConvertResult};
-use crate::lf_protobuf::com::daml::daml_lf_1::DefValue;
+use crate::lf_protobuf::com::daml::daml_lf_1::{DefException, DefValue};
 use std::convert::TryFrom;
 
 ///
@@ -51,3 +51,32 @@ impl<'a> TryFrom<&'a DefValue> for DamlDefValuePayload<'a> {
         ))
     }
 }
+
+/// A `DamlDefExceptionPayload` within the context of its module.
+pub type DamlDefExceptionWrapper<'a> = PayloadElementWrapper<'a, &'a DamlDefExceptionPayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlDefExceptionPayload<'a> {
+    pub name: InternableDottedName<'a>,
+    pub message: DamlExprPayload<'a>,
+}
+
+impl<'a> DamlDefExceptionPayload<'a> {
+    pub const fn new(name: InternableDottedName<'a>, message: DamlExprPayload<'a>) -> Self {
+        Self {
+            name,
+            message,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a DefException> for DamlDefExceptionPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(def_exception: &'a DefException) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            InternableDottedName::InternedDottedName(def_exception.name_interned_dname),
+            DamlExprPayload::try_from(def_exception.message.as_ref().req()?)?,
+        ))
+    }
+}
@@ -1,27 +1,32 @@
-use crate::convert::defvalue_payload::DamlDefValueWrapper;
+use crate::convert::defvalue_payload::{DamlDefExceptionWrapper, DamlDefValueWrapper};
 use crate::convert::expr_payload::{
     DamlAbsWrapper, DamlAppWrapper, DamlBindingWrapper, DamlBlockWrapper, DamlBuiltinFunctionPayload,
     DamlCaseAltConsWrapper, DamlCaseAltEnumWrapper, DamlCaseAltOptionalSomeWrapper, DamlCaseAltSumPayload,
-    DamlCaseAltSumWrapper, DamlCaseAltVariantWrapper, DamlCaseAltWrapper, DamlCaseWrapper, DamlCommitWrapper,
-    DamlConsWrapper, DamlCreateWrapper, DamlEnumConWrapper, DamlExerciseByKeyWrapper, DamlExerciseWrapper,
-    DamlExprPayload, DamlExprWrapper, DamlFetchWrapper, DamlFieldWithExprWrapper, DamlFromAnyExceptionWrapper,
-    DamlFromAnyWrapper, DamlOptionalSomeWrapper, DamlPrimConPayload, DamlPrimLitPayload, DamlPrimLitWrapper,
-    DamlPureWrapper, DamlRecConWrapper, DamlRecProjWrapper, DamlRecUpdWrapper, DamlRetrieveByKeyWrapper,
-    DamlScenarioEmbedExprWrapper, DamlScenarioPayload, DamlScenarioWrapper, DamlStructConWrapper,
-    DamlStructProjWrapper, DamlStructUpdWrapper, DamlThrowWrapper, DamlToAnyExceptionWrapper, DamlToAnyWrapper,
-    DamlTryCatchWrapper, DamlTyAbsWrapper, DamlTyAppWrapper, DamlUpdateEmbedExprWrapper, DamlUpdatePayload,
-    DamlUpdateWrapper, DamlValueNameWrapper, DamlVarWithTypeWrapper, DamlVariantConWrapper, RoundingModePayload,
+    DamlCaseAltSumWrapper, DamlCaseAltVariantWrapper, DamlCaseAltWrapper, DamlCaseWrapper, DamlCallInterfaceWrapper,
+    DamlCommitWrapper, DamlConsWrapper, DamlCreateWrapper, DamlEnumConWrapper, DamlExerciseByKeyWrapper,
+    DamlExerciseInterfaceWrapper, DamlExerciseWrapper, DamlExprPayload, DamlExprWrapper, DamlFetchInterfaceWrapper,
+    DamlFetchWrapper, DamlFieldWithExprWrapper, DamlFromAnyExceptionWrapper, DamlFromAnyWrapper,
+    DamlInterfaceCastWrapper, DamlInterfaceExprWrapper, DamlOptionalSomeWrapper, DamlPrimConPayload,
+    DamlPrimLitPayload, DamlPrimLitWrapper, DamlPureWrapper, DamlRecConWrapper, DamlRecProjWrapper, DamlRecUpdWrapper,
+    DamlRequiredInterfaceCastWrapper, DamlRetrieveByKeyWrapper, DamlScenarioEmbedExprWrapper, DamlScenarioPayload,
+    DamlScenarioWrapper, DamlStructConWrapper, DamlStructProjWrapper, DamlStructUpdWrapper, DamlThrowWrapper,
+    DamlToAnyExceptionWrapper, DamlToAnyWrapper, DamlTryCatchWrapper, DamlTyAbsWrapper, DamlTyAppWrapper,
+    DamlUnsafeFromInterfaceWrapper, DamlUnsafeFromRequiredInterfaceWrapper, DamlUpdateEmbedExprWrapper,
+    DamlUpdatePayload, DamlUpdateWrapper, DamlValueNameWrapper, DamlVarWithTypeWrapper, DamlVariantConWrapper,
+    RoundingModePayload,
 };
 use crate::convert::package_payload::DamlPackagePayload;
 use crate::element::{
-    DamlAbs, DamlApp, DamlBinding, DamlBlock, DamlBuiltinFunction, DamlCase, DamlCaseAlt, DamlCaseAltCons,
-    DamlCaseAltEnum, DamlCaseAltOptionalSome, DamlCaseAltSum, DamlCaseAltVariant, DamlCommit, DamlCons, DamlCreate,
-    DamlDefValue, DamlEnumCon, DamlExercise, DamlExerciseByKey, DamlExpr, DamlFetch, DamlFieldWithExpr, DamlFromAny,
-    DamlFromAnyException, DamlLocalValueName, DamlNonLocalValueName, DamlOptionalSome, DamlPrimCon, DamlPrimLit,
-    DamlPure, DamlRecCon, DamlRecProj, DamlRecUpd, DamlRetrieveByKey, DamlScenario, DamlScenarioEmbedExpr,
-    DamlStructCon, DamlStructProj, DamlStructUpd, DamlThrow, DamlToAny, DamlToAnyException, DamlTryCatch, DamlTyAbs,
-    DamlTyApp, DamlTyCon, DamlTyConName, DamlType, DamlTypeVarWithKind, DamlUpdate, DamlUpdateEmbedExpr, DamlValueName,
-    DamlVarWithType, DamlVariantCon, RoundingMode,
+    DamlAbs, DamlApp, DamlBinding, DamlBlock, DamlBuiltinFunction, DamlCallInterface, DamlCase, DamlCaseAlt,
+    DamlCaseAltCons, DamlCaseAltEnum, DamlCaseAltOptionalSome, DamlCaseAltSum, DamlCaseAltVariant, DamlCommit,
+    DamlCons, DamlCreate, DamlDefException, DamlDefValue, DamlEnumCon, DamlExercise, DamlExerciseByKey,
+    DamlExerciseInterface, DamlExpr, DamlFetch, DamlFetchInterface, DamlFieldWithExpr, DamlFromAny,
+    DamlFromAnyException, DamlInterfaceCast, DamlInterfaceExpr, DamlLocalValueName, DamlNonLocalValueName,
+    DamlOptionalSome, DamlPrimCon, DamlPrimLit, DamlPure, DamlRecCon, DamlRecProj, DamlRecUpd,
+    DamlRequiredInterfaceCast, DamlRetrieveByKey, DamlScenario, DamlScenarioEmbedExpr, DamlStructCon, DamlStructProj,
+    DamlStructUpd, DamlThrow, DamlToAny, DamlToAnyException, DamlTryCatch, DamlTyAbs, DamlTyApp, DamlTyCon,
+    DamlTyConName, DamlType, DamlTypeVarWithKind, DamlUnsafeFromInterface, DamlUnsafeFromRequiredInterface, DamlUpdate,
+    DamlUpdateEmbedExpr, DamlValueName, DamlVarWithType, DamlVariantCon, RoundingMode,
 };
 use crate::error::{DamlLfConvertError, DamlLfConvertResult};
 use std::borrow::Cow;
@@ -42,6 +47,18 @@ impl<'a> TryFrom<&DamlDefValueWrapper<'a>> for DamlDefValue<'a> {
     }
 }
 
+/// Convert from `DamlDefExceptionWrapper` to `DamlDefException`.
+impl<'a> TryFrom<&DamlDefExceptionWrapper<'a>> for DamlDefException<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(def_exception: &DamlDefExceptionWrapper<'a>) -> DamlLfConvertResult<Self> {
+        Ok(DamlDefException::new(
+            def_exception.payload.name.resolve_last(def_exception.context.package)?,
+            DamlExpr::try_from(&def_exception.wrap(&def_exception.payload.message))?,
+        ))
+    }
+}
+
 /// Convert from `DamlExprWrapper` to `DamlExpr`.
 impl<'a> TryFrom<&DamlExprWrapper<'a>> for DamlExpr<'a> {
     type Error = DamlLfConvertError;
@@ -86,6 +103,32 @@ impl<'a> TryFrom<&DamlExprWrapper<'a>> for DamlExpr<'a> {
             DamlExprPayload::FromAnyException(from_any_exception) =>
                 DamlExpr::FromAnyException(DamlFromAnyException::try_from(&expr.wrap(from_any_exception))?),
             DamlExprPayload::Throw(throw) => DamlExpr::Throw(DamlThrow::try_from(&expr.wrap(throw))?),
+            DamlExprPayload::ToInterface(to_interface) =>
+                DamlExpr::ToInterface(DamlInterfaceCast::try_from(&expr.wrap(to_interface))?),
+            DamlExprPayload::FromInterface(from_interface) =>
+                DamlExpr::FromInterface(DamlInterfaceCast::try_from(&expr.wrap(from_interface))?),
+            DamlExprPayload::UnsafeFromInterface(unsafe_from_interface) =>
+                DamlExpr::UnsafeFromInterface(DamlUnsafeFromInterface::try_from(&expr.wrap(unsafe_from_interface))?),
+            DamlExprPayload::CallInterface(call_interface) =>
+                DamlExpr::CallInterface(DamlCallInterface::try_from(&expr.wrap(call_interface))?),
+            DamlExprPayload::ToRequiredInterface(to_required_interface) => DamlExpr::ToRequiredInterface(
+                DamlRequiredInterfaceCast::try_from(&expr.wrap(to_required_interface))?,
+            ),
+            DamlExprPayload::FromRequiredInterface(from_required_interface) => DamlExpr::FromRequiredInterface(
+                DamlRequiredInterfaceCast::try_from(&expr.wrap(from_required_interface))?,
+            ),
+            DamlExprPayload::UnsafeFromRequiredInterface(unsafe_from_required_interface) =>
+                DamlExpr::UnsafeFromRequiredInterface(DamlUnsafeFromRequiredInterface::try_from(
+                    &expr.wrap(unsafe_from_required_interface),
+                )?),
+            DamlExprPayload::InterfaceTemplateTypeRep(interface_expr) =>
+                DamlExpr::InterfaceTemplateTypeRep(DamlInterfaceExpr::try_from(&expr.wrap(interface_expr))?),
+            DamlExprPayload::SignatoryInterface(interface_expr) =>
+                DamlExpr::SignatoryInterface(DamlInterfaceExpr::try_from(&expr.wrap(interface_expr))?),
+            DamlExprPayload::ObserverInterface(interface_expr) =>
+                DamlExpr::ObserverInterface(DamlInterfaceExpr::try_from(&expr.wrap(interface_expr))?),
+            DamlExprPayload::ViewInterface(interface_expr) =>
+                DamlExpr::ViewInterface(DamlInterfaceExpr::try_from(&expr.wrap(interface_expr))?),
         })
     }
 }
@@ -613,6 +656,10 @@ impl<'a> TryFrom<&DamlUpdateWrapper<'a>> for DamlUpdate<'a> {
                 DamlUpdate::EmbedExpr(DamlUpdateEmbedExpr::try_from(&update.wrap(embed_expr))?),
             DamlUpdatePayload::TryCatch(try_catch) =>
                 DamlUpdate::TryCatch(DamlTryCatch::try_from(&update.wrap(try_catch))?),
+            DamlUpdatePayload::ExerciseInterface(exercise_interface) =>
+                DamlUpdate::ExerciseInterface(DamlExerciseInterface::try_from(&update.wrap(exercise_interface))?),
+            DamlUpdatePayload::FetchInterface(fetch_interface) =>
+                DamlUpdate::FetchInterface(DamlFetchInterface::try_from(&update.wrap(fetch_interface))?),
         })
     }
 }
@@ -745,7 +792,7 @@ impl<'a> TryFrom<&DamlValueNameWrapper<'a>> for DamlValueName<'a> {
         let target_package: &DamlPackagePayload<'_> = value_name
             .context
             .archive
-            .package_by_id(&target_package_id)
+            .package_by_id(&target_package_id)?
             .ok_or_else(|| DamlLfConvertError::UnknownPackage(target_package_id.to_string()))?;
         let target_package_name = Cow::from(target_package.name.as_str());
         let target_module_path = value_name.payload.module_path.resolve(source_resolver)?;
@@ -813,3 +860,105 @@ impl<'a> TryFrom<&DamlTryCatchWrapper<'a>> for DamlTryCatch<'a> {
         Ok(DamlTryCatch::new(return_type, Box::new(try_expr), var, Box::new(catch_expr)))
     }
 }
+
+impl<'a> TryFrom<&DamlInterfaceCastWrapper<'a>> for DamlInterfaceCast<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(interface_cast: &DamlInterfaceCastWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let interface_type = DamlTyConName::try_from(&interface_cast.wrap(&interface_cast.payload.interface_type))?;
+        let template_type = DamlTyConName::try_from(&interface_cast.wrap(&interface_cast.payload.template_type))?;
+        let expr = DamlExpr::try_from(&interface_cast.wrap(interface_cast.payload.expr.as_ref()))?;
+        Ok(DamlInterfaceCast::new(Box::new(interface_type), Box::new(template_type), Box::new(expr)))
+    }
+}
+
+impl<'a> TryFrom<&DamlUnsafeFromInterfaceWrapper<'a>> for DamlUnsafeFromInterface<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(unsafe_from_interface: &DamlUnsafeFromInterfaceWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let payload = unsafe_from_interface.payload;
+        let interface_type = DamlTyConName::try_from(&unsafe_from_interface.wrap(&payload.interface_type))?;
+        let template_type = DamlTyConName::try_from(&unsafe_from_interface.wrap(&payload.template_type))?;
+        let contract_id_expr = DamlExpr::try_from(&unsafe_from_interface.wrap(payload.contract_id_expr.as_ref()))?;
+        let interface_expr = DamlExpr::try_from(&unsafe_from_interface.wrap(payload.interface_expr.as_ref()))?;
+        Ok(DamlUnsafeFromInterface::new(
+            Box::new(interface_type),
+            Box::new(template_type),
+            Box::new(contract_id_expr),
+            Box::new(interface_expr),
+        ))
+    }
+}
+
+impl<'a> TryFrom<&DamlCallInterfaceWrapper<'a>> for DamlCallInterface<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(call_interface: &DamlCallInterfaceWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let interface_type = DamlTyConName::try_from(&call_interface.wrap(&call_interface.payload.interface_type))?;
+        let method = call_interface.payload.method.resolve(call_interface.context.package)?;
+        let interface_expr = DamlExpr::try_from(&call_interface.wrap(call_interface.payload.interface_expr.as_ref()))?;
+        Ok(DamlCallInterface::new(Box::new(interface_type), method, Box::new(interface_expr)))
+    }
+}
+
+impl<'a> TryFrom<&DamlRequiredInterfaceCastWrapper<'a>> for DamlRequiredInterfaceCast<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(required_interface_cast: &DamlRequiredInterfaceCastWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let payload = required_interface_cast.payload;
+        let required_interface = DamlTyConName::try_from(&required_interface_cast.wrap(&payload.required_interface))?;
+        let requiring_interface = DamlTyConName::try_from(&required_interface_cast.wrap(&payload.requiring_interface))?;
+        let expr = DamlExpr::try_from(&required_interface_cast.wrap(payload.expr.as_ref()))?;
+        Ok(DamlRequiredInterfaceCast::new(Box::new(required_interface), Box::new(requiring_interface), Box::new(expr)))
+    }
+}
+
+impl<'a> TryFrom<&DamlUnsafeFromRequiredInterfaceWrapper<'a>> for DamlUnsafeFromRequiredInterface<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(unsafe_from_required: &DamlUnsafeFromRequiredInterfaceWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let payload = unsafe_from_required.payload;
+        let required_interface = DamlTyConName::try_from(&unsafe_from_required.wrap(&payload.required_interface))?;
+        let requiring_interface = DamlTyConName::try_from(&unsafe_from_required.wrap(&payload.requiring_interface))?;
+        let contract_id_expr = DamlExpr::try_from(&unsafe_from_required.wrap(payload.contract_id_expr.as_ref()))?;
+        let interface_expr = DamlExpr::try_from(&unsafe_from_required.wrap(payload.interface_expr.as_ref()))?;
+        Ok(DamlUnsafeFromRequiredInterface::new(
+            Box::new(required_interface),
+            Box::new(requiring_interface),
+            Box::new(contract_id_expr),
+            Box::new(interface_expr),
+        ))
+    }
+}
+
+impl<'a> TryFrom<&DamlInterfaceExprWrapper<'a>> for DamlInterfaceExpr<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(interface_expr: &DamlInterfaceExprWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let interface = DamlTyConName::try_from(&interface_expr.wrap(&interface_expr.payload.interface))?;
+        let expr = DamlExpr::try_from(&interface_expr.wrap(interface_expr.payload.expr.as_ref()))?;
+        Ok(DamlInterfaceExpr::new(Box::new(interface), Box::new(expr)))
+    }
+}
+
+impl<'a> TryFrom<&DamlExerciseInterfaceWrapper<'a>> for DamlExerciseInterface<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(exercise_interface: &DamlExerciseInterfaceWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let interface = DamlTyConName::try_from(&exercise_interface.wrap(&exercise_interface.payload.interface))?;
+        let choice = exercise_interface.payload.choice.resolve(exercise_interface.context.package)?;
+        let cid = DamlExpr::try_from(&exercise_interface.wrap(exercise_interface.payload.cid.as_ref()))?;
+        let arg = DamlExpr::try_from(&exercise_interface.wrap(exercise_interface.payload.arg.as_ref()))?;
+        Ok(DamlExerciseInterface::new(Box::new(interface), choice, Box::new(cid), Box::new(arg)))
+    }
+}
+
+impl<'a> TryFrom<&DamlFetchInterfaceWrapper<'a>> for DamlFetchInterface<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(fetch_interface: &DamlFetchInterfaceWrapper<'a>) -> DamlLfConvertResult<Self> {
+        let interface = DamlTyConName::try_from(&fetch_interface.wrap(&fetch_interface.payload.interface))?;
+        let cid = DamlExpr::try_from(&fetch_interface.wrap(fetch_interface.payload.cid.as_ref()))?;
+        Ok(DamlFetchInterface::new(Box::new(interface), Box::new(cid)))
+    }
+}
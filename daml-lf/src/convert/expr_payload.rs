@@ -9,14 +9,16 @@ use crate::error::{DamlLfConvertError, DamlLfConvertResult};
 use crate::lf_protobuf::com::daml::daml_lf_1::case_alt::Sum;
 use crate::lf_protobuf::com::daml::daml_lf_1::def_template::def_key;
 use crate::lf_protobuf::com::daml::daml_lf_1::expr::{
-    Abs, App, Cons, EnumCon, FromAny, FromAnyException, OptionalSome, RecCon, RecProj, RecUpd, StructCon, StructProj,
-    StructUpd, Throw, ToAny, ToAnyException, TyAbs, TyApp, VariantCon,
+    Abs, App, CallInterface, Cons, EnumCon, FromAny, FromAnyException, FromInterface, FromRequiredInterface,
+    InterfaceTemplateTypeRep, ObserverInterface, OptionalSome, RecCon, RecProj, RecUpd, SignatoryInterface, StructCon,
+    StructProj, StructUpd, Throw, ToAny, ToAnyException, ToInterface, ToRequiredInterface, TyAbs, TyApp,
+    UnsafeFromInterface, UnsafeFromRequiredInterface, VariantCon, ViewInterface,
 };
 use crate::lf_protobuf::com::daml::daml_lf_1::prim_lit::RoundingMode;
 use crate::lf_protobuf::com::daml::daml_lf_1::scenario::Commit;
 use crate::lf_protobuf::com::daml::daml_lf_1::update;
 use crate::lf_protobuf::com::daml::daml_lf_1::update::{
-    Create, Exercise, ExerciseByKey, Fetch, RetrieveByKey, TryCatch,
+    Create, Exercise, ExerciseByKey, ExerciseInterface, Fetch, FetchInterface, RetrieveByKey, TryCatch,
 };
 use crate::lf_protobuf::com::daml::daml_lf_1::{case_alt, scenario, CaseAlt};
 use crate::lf_protobuf::com::daml::daml_lf_1::{
@@ -60,6 +62,17 @@ pub enum DamlExprPayload<'a> {
     ToAnyException(DamlToAnyExceptionPayload<'a>),
     FromAnyException(DamlFromAnyExceptionPayload<'a>),
     Throw(DamlThrowPayload<'a>),
+    ToInterface(DamlInterfaceCastPayload<'a>),
+    FromInterface(DamlInterfaceCastPayload<'a>),
+    UnsafeFromInterface(DamlUnsafeFromInterfacePayload<'a>),
+    CallInterface(DamlCallInterfacePayload<'a>),
+    ToRequiredInterface(DamlRequiredInterfaceCastPayload<'a>),
+    FromRequiredInterface(DamlRequiredInterfaceCastPayload<'a>),
+    UnsafeFromRequiredInterface(DamlUnsafeFromRequiredInterfacePayload<'a>),
+    InterfaceTemplateTypeRep(DamlInterfaceExprPayload<'a>),
+    SignatoryInterface(DamlInterfaceExprPayload<'a>),
+    ObserverInterface(DamlInterfaceExprPayload<'a>),
+    ViewInterface(DamlInterfaceExprPayload<'a>),
 }
 
 impl<'a> TryFrom<&'a Expr> for DamlExprPayload<'a> {
@@ -107,6 +120,34 @@ impl<'a> TryFrom<&'a Expr> for DamlExprPayload<'a> {
             expr::Sum::FromAnyException(from_any_exception) =>
                 DamlExprPayload::FromAnyException(DamlFromAnyExceptionPayload::try_from(from_any_exception.as_ref())?),
             expr::Sum::Throw(throw) => DamlExprPayload::Throw(DamlThrowPayload::try_from(throw.as_ref())?),
+            expr::Sum::ToInterface(to_interface) =>
+                DamlExprPayload::ToInterface(DamlInterfaceCastPayload::try_from(to_interface.as_ref())?),
+            expr::Sum::FromInterface(from_interface) =>
+                DamlExprPayload::FromInterface(DamlInterfaceCastPayload::try_from(from_interface.as_ref())?),
+            expr::Sum::UnsafeFromInterface(unsafe_from_interface) => DamlExprPayload::UnsafeFromInterface(
+                DamlUnsafeFromInterfacePayload::try_from(unsafe_from_interface.as_ref())?,
+            ),
+            expr::Sum::CallInterface(call_interface) =>
+                DamlExprPayload::CallInterface(DamlCallInterfacePayload::try_from(call_interface.as_ref())?),
+            expr::Sum::ToRequiredInterface(to_required_interface) => DamlExprPayload::ToRequiredInterface(
+                DamlRequiredInterfaceCastPayload::try_from(to_required_interface.as_ref())?,
+            ),
+            expr::Sum::FromRequiredInterface(from_required_interface) => DamlExprPayload::FromRequiredInterface(
+                DamlRequiredInterfaceCastPayload::try_from(from_required_interface.as_ref())?,
+            ),
+            expr::Sum::UnsafeFromRequiredInterface(unsafe_from_required_interface) =>
+                DamlExprPayload::UnsafeFromRequiredInterface(DamlUnsafeFromRequiredInterfacePayload::try_from(
+                    unsafe_from_required_interface.as_ref(),
+                )?),
+            expr::Sum::InterfaceTemplateTypeRep(interface_template_type_rep) => DamlExprPayload::InterfaceTemplateTypeRep(
+                DamlInterfaceExprPayload::try_from(interface_template_type_rep.as_ref())?,
+            ),
+            expr::Sum::SignatoryInterface(signatory_interface) =>
+                DamlExprPayload::SignatoryInterface(DamlInterfaceExprPayload::try_from(signatory_interface.as_ref())?),
+            expr::Sum::ObserverInterface(observer_interface) =>
+                DamlExprPayload::ObserverInterface(DamlInterfaceExprPayload::try_from(observer_interface.as_ref())?),
+            expr::Sum::ViewInterface(view_interface) =>
+                DamlExprPayload::ViewInterface(DamlInterfaceExprPayload::try_from(view_interface.as_ref())?),
             expr::Sum::Experimental(_) => panic!("Experimental Expr not supported"),
         })
     }
@@ -1328,6 +1369,8 @@ pub enum DamlUpdatePayload<'a> {
     FetchByKey(DamlRetrieveByKeyPayload<'a>),
     EmbedExpr(DamlUpdateEmbedExprPayload<'a>),
     TryCatch(DamlTryCatchPayload<'a>),
+    ExerciseInterface(DamlExerciseInterfacePayload<'a>),
+    FetchInterface(DamlFetchInterfacePayload<'a>),
 }
 
 impl<'a> TryFrom<&'a Update> for DamlUpdatePayload<'a> {
@@ -1352,6 +1395,10 @@ impl<'a> TryFrom<&'a Update> for DamlUpdatePayload<'a> {
                 DamlUpdatePayload::EmbedExpr(DamlUpdateEmbedExprPayload::try_from(embed_expr.as_ref())?),
             update::Sum::TryCatch(try_catch) =>
                 DamlUpdatePayload::TryCatch(DamlTryCatchPayload::try_from(try_catch.as_ref())?),
+            update::Sum::ExerciseInterface(exercise_interface) =>
+                DamlUpdatePayload::ExerciseInterface(DamlExerciseInterfacePayload::try_from(exercise_interface.as_ref())?),
+            update::Sum::FetchInterface(fetch_interface) =>
+                DamlUpdatePayload::FetchInterface(DamlFetchInterfacePayload::try_from(fetch_interface.as_ref())?),
         })
     }
 }
@@ -1568,8 +1615,9 @@ impl<'a> TryFrom<&'a Scenario> for DamlScenarioPayload<'a> {
         Ok(match scenario.sum.as_ref().req()? {
             scenario::Sum::Pure(pure) => DamlScenarioPayload::Pure(DamlPurePayload::try_from(pure.as_ref())?),
             scenario::Sum::Block(block) => DamlScenarioPayload::Block(DamlBlockPayload::try_from(block.as_ref())?),
-            scenario::Sum::Commit(commit) | scenario::Sum::MustFailAt(commit) =>
-                DamlScenarioPayload::Commit(DamlCommitPayload::try_from(commit.as_ref())?),
+            scenario::Sum::Commit(commit) => DamlScenarioPayload::Commit(DamlCommitPayload::try_from(commit.as_ref())?),
+            scenario::Sum::MustFailAt(commit) =>
+                DamlScenarioPayload::MustFailAt(DamlCommitPayload::try_from(commit.as_ref())?),
             scenario::Sum::Pass(embed_expr) =>
                 DamlScenarioPayload::Pass(Box::new(DamlExprPayload::try_from(embed_expr.as_ref())?)),
             scenario::Sum::GetTime(_) => DamlScenarioPayload::GetTime,
@@ -1787,3 +1835,339 @@ impl<'a> TryFrom<&'a TryCatch> for DamlTryCatchPayload<'a> {
         ))
     }
 }
+
+pub type DamlInterfaceCastWrapper<'a> = PayloadElementWrapper<'a, &'a DamlInterfaceCastPayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlInterfaceCastPayload<'a> {
+    pub interface_type: DamlTyConNamePayload<'a>,
+    pub template_type: DamlTyConNamePayload<'a>,
+    pub expr: Box<DamlExprPayload<'a>>,
+}
+
+impl<'a> DamlInterfaceCastPayload<'a> {
+    pub fn new(
+        interface_type: DamlTyConNamePayload<'a>,
+        template_type: DamlTyConNamePayload<'a>,
+        expr: Box<DamlExprPayload<'a>>,
+    ) -> Self {
+        Self {
+            interface_type,
+            template_type,
+            expr,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ToInterface> for DamlInterfaceCastPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(to_interface: &'a ToInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(to_interface.interface_type.as_ref().req()?)?,
+            DamlTyConNamePayload::try_from(to_interface.template_type.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(to_interface.template_expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+impl<'a> TryFrom<&'a FromInterface> for DamlInterfaceCastPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(from_interface: &'a FromInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(from_interface.interface_type.as_ref().req()?)?,
+            DamlTyConNamePayload::try_from(from_interface.template_type.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(from_interface.interface_expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+pub type DamlUnsafeFromInterfaceWrapper<'a> = PayloadElementWrapper<'a, &'a DamlUnsafeFromInterfacePayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlUnsafeFromInterfacePayload<'a> {
+    pub interface_type: DamlTyConNamePayload<'a>,
+    pub template_type: DamlTyConNamePayload<'a>,
+    pub contract_id_expr: Box<DamlExprPayload<'a>>,
+    pub interface_expr: Box<DamlExprPayload<'a>>,
+}
+
+impl<'a> DamlUnsafeFromInterfacePayload<'a> {
+    pub fn new(
+        interface_type: DamlTyConNamePayload<'a>,
+        template_type: DamlTyConNamePayload<'a>,
+        contract_id_expr: Box<DamlExprPayload<'a>>,
+        interface_expr: Box<DamlExprPayload<'a>>,
+    ) -> Self {
+        Self {
+            interface_type,
+            template_type,
+            contract_id_expr,
+            interface_expr,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a UnsafeFromInterface> for DamlUnsafeFromInterfacePayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(unsafe_from_interface: &'a UnsafeFromInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(unsafe_from_interface.interface_type.as_ref().req()?)?,
+            DamlTyConNamePayload::try_from(unsafe_from_interface.template_type.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(unsafe_from_interface.contract_id_expr.as_ref().req()?.as_ref())?),
+            Box::new(DamlExprPayload::try_from(unsafe_from_interface.interface_expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+pub type DamlCallInterfaceWrapper<'a> = PayloadElementWrapper<'a, &'a DamlCallInterfacePayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlCallInterfacePayload<'a> {
+    pub interface_type: DamlTyConNamePayload<'a>,
+    pub method: InternableString<'a>,
+    pub interface_expr: Box<DamlExprPayload<'a>>,
+}
+
+impl<'a> DamlCallInterfacePayload<'a> {
+    pub fn new(
+        interface_type: DamlTyConNamePayload<'a>,
+        method: InternableString<'a>,
+        interface_expr: Box<DamlExprPayload<'a>>,
+    ) -> Self {
+        Self {
+            interface_type,
+            method,
+            interface_expr,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a CallInterface> for DamlCallInterfacePayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(call_interface: &'a CallInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(call_interface.interface_type.as_ref().req()?)?,
+            InternableString::InternedString(call_interface.method_interned_name),
+            Box::new(DamlExprPayload::try_from(call_interface.interface_expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+pub type DamlRequiredInterfaceCastWrapper<'a> = PayloadElementWrapper<'a, &'a DamlRequiredInterfaceCastPayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlRequiredInterfaceCastPayload<'a> {
+    pub required_interface: DamlTyConNamePayload<'a>,
+    pub requiring_interface: DamlTyConNamePayload<'a>,
+    pub expr: Box<DamlExprPayload<'a>>,
+}
+
+impl<'a> DamlRequiredInterfaceCastPayload<'a> {
+    pub fn new(
+        required_interface: DamlTyConNamePayload<'a>,
+        requiring_interface: DamlTyConNamePayload<'a>,
+        expr: Box<DamlExprPayload<'a>>,
+    ) -> Self {
+        Self {
+            required_interface,
+            requiring_interface,
+            expr,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ToRequiredInterface> for DamlRequiredInterfaceCastPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(to_required_interface: &'a ToRequiredInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(to_required_interface.required_interface.as_ref().req()?)?,
+            DamlTyConNamePayload::try_from(to_required_interface.requiring_interface.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(to_required_interface.expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+impl<'a> TryFrom<&'a FromRequiredInterface> for DamlRequiredInterfaceCastPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(from_required_interface: &'a FromRequiredInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(from_required_interface.required_interface.as_ref().req()?)?,
+            DamlTyConNamePayload::try_from(from_required_interface.requiring_interface.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(from_required_interface.expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+pub type DamlUnsafeFromRequiredInterfaceWrapper<'a> =
+    PayloadElementWrapper<'a, &'a DamlUnsafeFromRequiredInterfacePayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlUnsafeFromRequiredInterfacePayload<'a> {
+    pub required_interface: DamlTyConNamePayload<'a>,
+    pub requiring_interface: DamlTyConNamePayload<'a>,
+    pub contract_id_expr: Box<DamlExprPayload<'a>>,
+    pub interface_expr: Box<DamlExprPayload<'a>>,
+}
+
+impl<'a> DamlUnsafeFromRequiredInterfacePayload<'a> {
+    pub fn new(
+        required_interface: DamlTyConNamePayload<'a>,
+        requiring_interface: DamlTyConNamePayload<'a>,
+        contract_id_expr: Box<DamlExprPayload<'a>>,
+        interface_expr: Box<DamlExprPayload<'a>>,
+    ) -> Self {
+        Self {
+            required_interface,
+            requiring_interface,
+            contract_id_expr,
+            interface_expr,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a UnsafeFromRequiredInterface> for DamlUnsafeFromRequiredInterfacePayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(unsafe_from_required: &'a UnsafeFromRequiredInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(unsafe_from_required.required_interface.as_ref().req()?)?,
+            DamlTyConNamePayload::try_from(unsafe_from_required.requiring_interface.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(unsafe_from_required.contract_id_expr.as_ref().req()?.as_ref())?),
+            Box::new(DamlExprPayload::try_from(unsafe_from_required.interface_expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+pub type DamlInterfaceExprWrapper<'a> = PayloadElementWrapper<'a, &'a DamlInterfaceExprPayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlInterfaceExprPayload<'a> {
+    pub interface: DamlTyConNamePayload<'a>,
+    pub expr: Box<DamlExprPayload<'a>>,
+}
+
+impl<'a> DamlInterfaceExprPayload<'a> {
+    pub fn new(interface: DamlTyConNamePayload<'a>, expr: Box<DamlExprPayload<'a>>) -> Self {
+        Self {
+            interface,
+            expr,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a SignatoryInterface> for DamlInterfaceExprPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(signatory_interface: &'a SignatoryInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(signatory_interface.interface.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(signatory_interface.expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+impl<'a> TryFrom<&'a ObserverInterface> for DamlInterfaceExprPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(observer_interface: &'a ObserverInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(observer_interface.interface.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(observer_interface.expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+impl<'a> TryFrom<&'a ViewInterface> for DamlInterfaceExprPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(view_interface: &'a ViewInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(view_interface.interface.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(view_interface.expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+impl<'a> TryFrom<&'a InterfaceTemplateTypeRep> for DamlInterfaceExprPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(interface_template_type_rep: &'a InterfaceTemplateTypeRep) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(interface_template_type_rep.interface.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(interface_template_type_rep.expr.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+pub type DamlExerciseInterfaceWrapper<'a> = PayloadElementWrapper<'a, &'a DamlExerciseInterfacePayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlExerciseInterfacePayload<'a> {
+    pub interface: DamlTyConNamePayload<'a>,
+    pub choice: InternableString<'a>,
+    pub cid: Box<DamlExprPayload<'a>>,
+    pub arg: Box<DamlExprPayload<'a>>,
+}
+
+impl<'a> DamlExerciseInterfacePayload<'a> {
+    pub fn new(
+        interface: DamlTyConNamePayload<'a>,
+        choice: InternableString<'a>,
+        cid: Box<DamlExprPayload<'a>>,
+        arg: Box<DamlExprPayload<'a>>,
+    ) -> Self {
+        Self {
+            interface,
+            choice,
+            cid,
+            arg,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ExerciseInterface> for DamlExerciseInterfacePayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(exercise_interface: &'a ExerciseInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(exercise_interface.interface.as_ref().req()?)?,
+            InternableString::InternedString(exercise_interface.choice_interned_str),
+            Box::new(DamlExprPayload::try_from(exercise_interface.cid.as_ref().req()?.as_ref())?),
+            Box::new(DamlExprPayload::try_from(exercise_interface.arg.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
+
+pub type DamlFetchInterfaceWrapper<'a> = PayloadElementWrapper<'a, &'a DamlFetchInterfacePayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlFetchInterfacePayload<'a> {
+    pub interface: DamlTyConNamePayload<'a>,
+    pub cid: Box<DamlExprPayload<'a>>,
+}
+
+impl<'a> DamlFetchInterfacePayload<'a> {
+    pub fn new(interface: DamlTyConNamePayload<'a>, cid: Box<DamlExprPayload<'a>>) -> Self {
+        Self {
+            interface,
+            cid,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a FetchInterface> for DamlFetchInterfacePayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(fetch_interface: &'a FetchInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(fetch_interface.interface.as_ref().req()?)?,
+            Box::new(DamlExprPayload::try_from(fetch_interface.cid.as_ref().req()?.as_ref())?),
+        ))
+    }
+}
@@ -0,0 +1,172 @@
+use crate::convert::data_payload::DamlChoicePayload;
+#[cfg(feature = "full")]
+use crate::convert::expr_payload::DamlExprPayload;
+use crate::convert::interned::{InternableDottedName, InternableString};
+use crate::convert::type_payload::{DamlTyConNamePayload, DamlTypePayload};
+use crate::convert::util::Required;
+use crate::convert::wrapper::PayloadElementWrapper;
+use crate::error::{DamlLfConvertError, DamlLfConvertResult};
+use crate::lf_protobuf::com::daml::daml_lf_1::def_template::Implements;
+use crate::lf_protobuf::com::daml::daml_lf_1::interface_instance_body::InterfaceInstanceMethod;
+use crate::lf_protobuf::com::daml::daml_lf_1::{DefInterface, InterfaceMethod};
+use std::convert::TryFrom;
+
+///
+pub type DamlInterfaceWrapper<'a> = PayloadElementWrapper<'a, &'a DamlInterfacePayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlInterfacePayload<'a> {
+    pub name: InternableDottedName<'a>,
+    pub param: InternableString<'a>,
+    pub choices: Vec<DamlChoicePayload<'a>>,
+    pub methods: Vec<DamlInterfaceMethodPayload<'a>>,
+    pub requires: Vec<DamlTyConNamePayload<'a>>,
+    pub view: DamlTypePayload<'a>,
+}
+
+impl<'a> DamlInterfacePayload<'a> {
+    pub fn new(
+        name: InternableDottedName<'a>,
+        param: InternableString<'a>,
+        choices: Vec<DamlChoicePayload<'a>>,
+        methods: Vec<DamlInterfaceMethodPayload<'a>>,
+        requires: Vec<DamlTyConNamePayload<'a>>,
+        view: DamlTypePayload<'a>,
+    ) -> Self {
+        Self {
+            name,
+            param,
+            choices,
+            methods,
+            requires,
+            view,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a DefInterface> for DamlInterfacePayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(def_interface: &'a DefInterface) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            InternableDottedName::InternedDottedName(def_interface.tycon_interned_dname),
+            InternableString::InternedString(def_interface.param_interned_str),
+            def_interface.choices.iter().map(DamlChoicePayload::try_from).collect::<DamlLfConvertResult<_>>()?,
+            def_interface
+                .methods
+                .iter()
+                .map(DamlInterfaceMethodPayload::try_from)
+                .collect::<DamlLfConvertResult<_>>()?,
+            def_interface.requires.iter().map(DamlTyConNamePayload::try_from).collect::<DamlLfConvertResult<_>>()?,
+            DamlTypePayload::try_from(def_interface.view.as_ref().req()?)?,
+        ))
+    }
+}
+
+impl<'a> PartialEq for DamlInterfacePayload<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+///
+pub type DamlInterfaceMethodWrapper<'a> = PayloadElementWrapper<'a, &'a DamlInterfaceMethodPayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlInterfaceMethodPayload<'a> {
+    pub name: InternableString<'a>,
+    pub ty: DamlTypePayload<'a>,
+}
+
+impl<'a> DamlInterfaceMethodPayload<'a> {
+    pub fn new(name: InternableString<'a>, ty: DamlTypePayload<'a>) -> Self {
+        Self {
+            name,
+            ty,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a InterfaceMethod> for DamlInterfaceMethodPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(interface_method: &'a InterfaceMethod) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            InternableString::InternedString(interface_method.method_interned_name),
+            DamlTypePayload::try_from(interface_method.r#type.as_ref().req()?)?,
+        ))
+    }
+}
+
+///
+pub type DamlInterfaceInstanceWrapper<'a> = PayloadElementWrapper<'a, &'a DamlInterfaceInstancePayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlInterfaceInstancePayload<'a> {
+    pub interface: DamlTyConNamePayload<'a>,
+    pub methods: Vec<DamlInterfaceInstanceMethodPayload<'a>>,
+    #[cfg(feature = "full")]
+    pub view: DamlExprPayload<'a>,
+}
+
+impl<'a> DamlInterfaceInstancePayload<'a> {
+    pub fn new(
+        interface: DamlTyConNamePayload<'a>,
+        methods: Vec<DamlInterfaceInstanceMethodPayload<'a>>,
+        #[cfg(feature = "full")] view: DamlExprPayload<'a>,
+    ) -> Self {
+        Self {
+            interface,
+            methods,
+            #[cfg(feature = "full")]
+            view,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Implements> for DamlInterfaceInstancePayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(implements: &'a Implements) -> DamlLfConvertResult<Self> {
+        let body = implements.body.as_ref().req()?;
+        Ok(Self::new(
+            DamlTyConNamePayload::try_from(implements.interface.as_ref().req()?)?,
+            body.methods.iter().map(DamlInterfaceInstanceMethodPayload::try_from).collect::<DamlLfConvertResult<_>>()?,
+            #[cfg(feature = "full")]
+            DamlExprPayload::try_from(body.view.as_ref().req()?)?,
+        ))
+    }
+}
+
+///
+pub type DamlInterfaceInstanceMethodWrapper<'a> =
+    PayloadElementWrapper<'a, &'a DamlInterfaceInstanceMethodPayload<'a>>;
+
+#[derive(Debug)]
+pub struct DamlInterfaceInstanceMethodPayload<'a> {
+    pub name: InternableString<'a>,
+    #[cfg(feature = "full")]
+    pub value: DamlExprPayload<'a>,
+}
+
+impl<'a> DamlInterfaceInstanceMethodPayload<'a> {
+    pub fn new(name: InternableString<'a>, #[cfg(feature = "full")] value: DamlExprPayload<'a>) -> Self {
+        Self {
+            name,
+            #[cfg(feature = "full")]
+            value,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a InterfaceInstanceMethod> for DamlInterfaceInstanceMethodPayload<'a> {
+    type Error = DamlLfConvertError;
+
+    fn try_from(method: &'a InterfaceInstanceMethod) -> DamlLfConvertResult<Self> {
+        Ok(Self::new(
+            InternableString::InternedString(method.method_interned_name),
+            #[cfg(feature = "full")]
+            DamlExprPayload::try_from(method.value.as_ref().req()?)?,
+        ))
+    }
+}
@@ -4,7 +4,10 @@ use std::convert::TryFrom;
 use crate::convert::archive_payload::DamlArchivePayload;
 use crate::convert::data_payload::{DamlDataPayload, DamlTemplatePayload};
 #[cfg(feature = "full")]
-use crate::convert::defvalue_payload::{DamlDefValuePayload, DamlDefValueWrapper};
+use crate::convert::defvalue_payload::{
+    DamlDefExceptionPayload, DamlDefExceptionWrapper, DamlDefValuePayload, DamlDefValueWrapper,
+};
+use crate::convert::interface_payload::{DamlInterfacePayload, DamlInterfaceWrapper};
 use crate::convert::interned::InternableDottedName;
 use crate::convert::package_payload::DamlPackagePayload;
 use crate::convert::type_payload::DamlTypePayload;
@@ -13,6 +16,7 @@ use crate::convert::util::Required;
 use crate::convert::wrapper::{DamlPayloadParentContext, DamlPayloadParentContextType, PayloadElementWrapper};
 use crate::error::{DamlLfConvertError, DamlLfConvertResult};
 use crate::lf_protobuf::com::daml::daml_lf_1::{DefTypeSyn, FeatureFlags, Module};
+use once_cell::sync::OnceCell;
 
 ///
 #[derive(Debug, Clone, Copy)]
@@ -46,6 +50,19 @@ impl<'a> DamlModuleWrapper<'a> {
         }
     }
 
+    /// DOCME
+    pub const fn wrap_interface(self, interface: &'a DamlInterfacePayload<'_>) -> DamlInterfaceWrapper<'a> {
+        DamlInterfaceWrapper {
+            context: DamlPayloadParentContext {
+                archive: self.archive,
+                package: self.package,
+                module: self.module,
+                parent: DamlPayloadParentContextType::Interface(interface),
+            },
+            payload: interface,
+        }
+    }
+
     #[cfg(feature = "full")]
     pub fn wrap_value(self, def_value: &'a DamlDefValuePayload<'_>) -> DamlDefValueWrapper<'a> {
         DamlDefValueWrapper {
@@ -58,6 +75,19 @@ impl<'a> DamlModuleWrapper<'a> {
             payload: def_value,
         }
     }
+
+    #[cfg(feature = "full")]
+    pub fn wrap_exception(self, def_exception: &'a DamlDefExceptionPayload<'_>) -> DamlDefExceptionWrapper<'a> {
+        DamlDefExceptionWrapper {
+            context: DamlPayloadParentContext {
+                archive: self.archive,
+                package: self.package,
+                module: self.module,
+                parent: DamlPayloadParentContextType::Exception(def_exception),
+            },
+            payload: def_exception,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -66,8 +96,11 @@ pub struct DamlModulePayload<'a> {
     pub synonyms: Vec<DamlDefTypeSynPayload<'a>>,
     pub data_types: Vec<DamlDataPayload<'a>>,
     pub templates: HashMap<InternableDottedName<'a>, DamlTemplatePayload<'a>>,
+    pub interfaces: HashMap<InternableDottedName<'a>, DamlInterfacePayload<'a>>,
     #[cfg(feature = "full")]
     pub values: Vec<DamlDefValuePayload<'a>>,
+    #[cfg(feature = "full")]
+    pub exceptions: Vec<DamlDefExceptionPayload<'a>>,
     pub path: InternableDottedName<'a>,
 }
 
@@ -91,28 +124,66 @@ impl<'a> TryFrom<&'a Module> for DamlModulePayload<'a> {
             .map(DamlTemplatePayload::try_from)
             .map(|tr| tr.map(|t| (t.name, t)))
             .collect::<DamlLfConvertResult<_>>()?;
+        let interfaces = module
+            .interfaces
+            .iter()
+            .map(DamlInterfacePayload::try_from)
+            .map(|ir| ir.map(|i| (i.name, i)))
+            .collect::<DamlLfConvertResult<_>>()?;
         let data_types =
             module.data_types.iter().map(DamlDataPayload::try_from).collect::<DamlLfConvertResult<Vec<_>>>()?;
         #[cfg(feature = "full")]
         let values =
             module.values.iter().map(DamlDefValuePayload::try_from).collect::<DamlLfConvertResult<Vec<_>>>()?;
+        #[cfg(feature = "full")]
+        let exceptions =
+            module.exceptions.iter().map(DamlDefExceptionPayload::try_from).collect::<DamlLfConvertResult<Vec<_>>>()?;
         Ok(Self {
             flags,
             synonyms,
             data_types,
             templates,
+            interfaces,
             #[cfg(feature = "full")]
             values,
+            #[cfg(feature = "full")]
+            exceptions,
             path,
         })
     }
 }
 
+/// A `DamlModulePayload` which is created from the underlying `Module` on first access.
+#[derive(Debug)]
+pub struct DamlLazyModulePayload<'a> {
+    module: &'a Module,
+    payload: OnceCell<DamlModulePayload<'a>>,
+}
+
+impl<'a> DamlLazyModulePayload<'a> {
+    pub const fn new(module: &'a Module) -> Self {
+        Self {
+            module,
+            payload: OnceCell::new(),
+        }
+    }
+
+    /// Get the `DamlModulePayload`, creating it if required.
+    pub fn payload(&'a self) -> DamlLfConvertResult<&'a DamlModulePayload<'a>> {
+        self.payload.get_or_try_init(|| DamlModulePayload::try_from(self.module))
+    }
+}
+
 impl<'a> DamlModulePayload<'a> {
     /// Get a named template from this module.
     pub fn template(&self, name: &InternableDottedName<'a>) -> Option<&DamlTemplatePayload<'_>> {
         self.templates.get(name)
     }
+
+    /// Get a named interface from this module.
+    pub fn interface(&self, name: &InternableDottedName<'a>) -> Option<&DamlInterfacePayload<'_>> {
+        self.interfaces.get(name)
+    }
 }
 
 pub type DamlDefTypeSynWrapper<'a> = PayloadElementWrapper<'a, &'a DamlDefTypeSynPayload<'a>>;
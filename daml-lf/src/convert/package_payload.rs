@@ -1,6 +1,6 @@
 use crate::convert::archive_payload::DamlArchivePayload;
-use crate::convert::interned::PackageInternedResolver;
-use crate::convert::module_payload::{DamlModulePayload, DamlModuleWrapper};
+use crate::convert::interned::{InternableDottedName, PackageInternedResolver};
+use crate::convert::module_payload::{DamlLazyModulePayload, DamlModulePayload, DamlModuleWrapper};
 use crate::convert::type_payload::DamlTypePayload;
 use crate::convert::util::Required;
 use crate::error::{DamlLfConvertError, DamlLfConvertResult};
@@ -16,7 +16,7 @@ pub struct DamlPackageWrapper<'a> {
 }
 
 impl<'a> DamlPackageWrapper<'a> {
-    pub const fn with_module(self, module: &'a DamlModulePayload<'_>) -> DamlModuleWrapper<'a> {
+    pub const fn with_module(self, module: &'a DamlModulePayload<'a>) -> DamlModuleWrapper<'a> {
         DamlModuleWrapper {
             archive: self.archive,
             package: self.package,
@@ -34,7 +34,7 @@ pub struct DamlPackagePayload<'a> {
     pub interned_strings: &'a [String],
     pub interned_dotted_names: Vec<&'a [i32]>,
     pub interned_types: Vec<DamlTypePayload<'a>>,
-    pub modules: HashMap<String, DamlModulePayload<'a>>,
+    pub modules: HashMap<String, DamlLazyModulePayload<'a>>,
 }
 
 impl<'a> PackageInternedResolver for DamlPackagePayload<'_> {
@@ -66,8 +66,14 @@ impl<'a> PartialEq for DamlPackagePayload<'a> {
 }
 
 impl<'a> DamlPackagePayload<'a> {
-    pub fn module_by_name(&self, module_path: &str) -> Option<&DamlModulePayload<'_>> {
-        self.modules.get(module_path)
+    /// Get the `DamlModulePayload` with the given dotted path, creating it if it has not yet been accessed.
+    pub fn module_by_name(&'a self, module_path: &str) -> DamlLfConvertResult<Option<&'a DamlModulePayload<'a>>> {
+        self.modules.get(module_path).map(DamlLazyModulePayload::payload).transpose()
+    }
+
+    /// Get all `DamlModulePayload` in this package, creating any which have not yet been accessed.
+    pub fn modules(&'a self) -> impl Iterator<Item = DamlLfConvertResult<&'a DamlModulePayload<'a>>> {
+        self.modules.values().map(DamlLazyModulePayload::payload)
     }
 }
 
@@ -121,9 +127,9 @@ impl<'a> TryFrom<&'a DamlLfArchive> for DamlPackagePayload<'a> {
             }
         }
 
-        Ok(match &daml_lf_archive.payload.package {
+        Ok(match &daml_lf_archive.payload().package {
             DamlLfPackage::V1(package) => {
-                let language_version = daml_lf_archive.payload.language_version;
+                let language_version = daml_lf_archive.payload().language_version;
                 let package_id = daml_lf_archive.hash.as_str();
                 let interned_strings = package.interned_strings.as_slice();
                 let interned_dotted_names: Vec<_> =
@@ -144,8 +150,10 @@ impl<'a> TryFrom<&'a DamlLfArchive> for DamlPackagePayload<'a> {
                 let modules = package
                     .modules
                     .iter()
-                    .map(DamlModulePayload::try_from)
-                    .map(|mr| mr.and_then(|m| Ok((m.path.resolve(&self_resolver)?.join("."), m))))
+                    .map(|module| {
+                        let path = InternableDottedName::from(module.name.as_ref().req()?);
+                        Ok((path.resolve(&self_resolver)?.join("."), DamlLazyModulePayload::new(module)))
+                    })
                     .collect::<DamlLfConvertResult<_>>()?;
                 Self {
                     name,
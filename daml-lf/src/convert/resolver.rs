@@ -48,12 +48,12 @@ fn resolve<'a, R: PackageInternedResolver>(
 
     // Extract the target package from the parent archive
     let target_package: &DamlPackagePayload<'_> = archive
-        .package_by_id(&target_package_id)
+        .package_by_id(&target_package_id)?
         .ok_or_else(|| DamlLfConvertError::UnknownPackage(target_package_id.to_string()))?;
 
     // Extract the target module from the target package
     let target_module = target_package
-        .module_by_name(&target_module_path)
+        .module_by_name(&target_module_path)?
         .ok_or(DamlLfConvertError::UnknownModule(target_module_path))?;
 
     // Find the target data from the target module
@@ -26,14 +26,14 @@ pub enum DamlTypePayload<'a> {
     Unit,
     Date,
     List(Vec<DamlTypePayload<'a>>),
-    Update,
-    Scenario,
+    Update(Vec<DamlTypePayload<'a>>),
+    Scenario(Vec<DamlTypePayload<'a>>),
     TextMap(Vec<DamlTypePayload<'a>>),
     GenMap(Vec<DamlTypePayload<'a>>),
     Optional(Vec<DamlTypePayload<'a>>),
     TyCon(DamlTyConPayload<'a>),
     Var(DamlVarPayload<'a>),
-    Arrow,
+    Arrow(Vec<DamlTypePayload<'a>>),
     Any,
     TypeRep,
     Bignumeric,
@@ -59,14 +59,14 @@ impl<'a> DamlTypePayload<'a> {
             DamlTypePayload::Unit => "Unit",
             DamlTypePayload::Date => "Date",
             DamlTypePayload::List(_) => "List",
-            DamlTypePayload::Update => "Update",
-            DamlTypePayload::Scenario => "Scenario",
+            DamlTypePayload::Update(_) => "Update",
+            DamlTypePayload::Scenario(_) => "Scenario",
             DamlTypePayload::TextMap(_) => "TextMap",
             DamlTypePayload::GenMap(_) => "GenMap",
             DamlTypePayload::Optional(_) => "Optional",
             DamlTypePayload::TyCon(_) => "TyCon",
             DamlTypePayload::Var(_) => "Var",
-            DamlTypePayload::Arrow => "Arrow",
+            DamlTypePayload::Arrow(_) => "Arrow",
             DamlTypePayload::Any => "Any",
             DamlTypePayload::TypeRep => "TypeRep",
             DamlTypePayload::Bignumeric => "Bignumeric",
@@ -99,8 +99,8 @@ impl<'a> TryFrom<&'a Type> for DamlTypePayload<'a> {
                 PrimType::Timestamp => Ok(DamlTypePayload::Timestamp),
                 PrimType::Party => Ok(DamlTypePayload::Party),
                 PrimType::List => Ok(DamlTypePayload::List(try_from_type_args(&prim.args)?)),
-                PrimType::Update => Ok(DamlTypePayload::Update),
-                PrimType::Scenario => Ok(DamlTypePayload::Scenario),
+                PrimType::Update => Ok(DamlTypePayload::Update(try_from_type_args(&prim.args)?)),
+                PrimType::Scenario => Ok(DamlTypePayload::Scenario(try_from_type_args(&prim.args)?)),
                 PrimType::Date => Ok(DamlTypePayload::Date),
                 PrimType::ContractId => match prim.args.as_slice() {
                     [ty] => Ok(DamlTypePayload::ContractId(Some(Box::new(DamlTypePayload::try_from(ty)?)))),
@@ -108,7 +108,7 @@ impl<'a> TryFrom<&'a Type> for DamlTypePayload<'a> {
                     _ => Ok(DamlTypePayload::ContractId(None)),
                 },
                 PrimType::Optional => Ok(DamlTypePayload::Optional(try_from_type_args(&prim.args)?)),
-                PrimType::Arrow => Ok(DamlTypePayload::Arrow),
+                PrimType::Arrow => Ok(DamlTypePayload::Arrow(try_from_type_args(&prim.args)?)),
                 PrimType::Textmap => Ok(DamlTypePayload::TextMap(try_from_type_args(&prim.args)?)),
                 PrimType::Genmap => Ok(DamlTypePayload::GenMap(try_from_type_args(&prim.args)?)),
                 PrimType::Any => Ok(DamlTypePayload::Any),
@@ -1,7 +1,8 @@
 use crate::convert::archive_payload::DamlArchivePayload;
 use crate::convert::data_payload::DamlDataPayload;
 #[cfg(feature = "full")]
-use crate::convert::defvalue_payload::DamlDefValuePayload;
+use crate::convert::defvalue_payload::{DamlDefExceptionPayload, DamlDefValuePayload};
+use crate::convert::interface_payload::DamlInterfacePayload;
 use crate::convert::module_payload::{DamlDefTypeSynPayload, DamlModulePayload};
 use crate::convert::package_payload::DamlPackagePayload;
 
@@ -45,6 +46,9 @@ pub struct DamlPayloadParentContext<'a> {
 pub enum DamlPayloadParentContextType<'a> {
     Data(&'a DamlDataPayload<'a>),
     DefTypeSyn(&'a DamlDefTypeSynPayload<'a>),
+    Interface(&'a DamlInterfacePayload<'a>),
     #[cfg(feature = "full")]
     Value(&'a DamlDefValuePayload<'a>),
+    #[cfg(feature = "full")]
+    Exception(&'a DamlDefExceptionPayload<'a>),
 }
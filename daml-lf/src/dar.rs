@@ -1,18 +1,27 @@
 use crate::archive::DamlLfArchive;
 use crate::convert;
-use crate::element::DamlArchive;
+use crate::element::{DamlArchive, DamlPackage};
 use crate::error::{DamlLfError, DamlLfResult};
-use crate::manifest::DarManifest;
-use crate::DEFAULT_ARCHIVE_NAME;
+use crate::manifest::{DarEncryptionType, DarManifest, DarManifestFormat, DarManifestVersion};
+use crate::{DamlLazyArchive, DEFAULT_ARCHIVE_NAME};
+use bytes::Bytes;
+#[cfg(feature = "async")]
+use futures::io::{AsyncRead, AsyncReadExt};
+use itertools::Itertools;
+use std::collections::BTreeMap;
 use std::ffi::OsStr;
 use std::fs::File;
-use std::io::Read;
+use std::io::{Cursor, Read, Seek, Write};
 use std::path::{Path, PathBuf};
-use zip::ZipArchive;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, DateTime, ZipArchive, ZipWriter};
 
 const MANIFEST_FILE_PATH: &str = "META-INF/MANIFEST.MF";
 const DALF_FILE_EXTENSION: &str = "dalf";
+const DALF_FILE_SUFFIX: &str = ".dalf";
 const DALF_PRIM_FILE_SUFFIX: &str = "-prim";
+const DAR_ENTRY_PERMISSIONS: u32 = 0o644;
+const DAR_CREATED_BY: &str = "daml-lf";
 
 /// A collection of `Daml LF` archives combined with a manifest file (aka a `dar` file).
 ///
@@ -87,17 +96,99 @@ impl DarFile {
     /// [`IoError`]: DamlLfError::IoError
     /// [`DarParseError`]: DamlLfError::DarParseError
     pub fn from_file(path: impl AsRef<Path>) -> DamlLfResult<Self> {
-        let dar_file = std::fs::File::open(path)?;
-        let mut zip_archive = zip::ZipArchive::new(dar_file)?;
-        let manifest = match Self::parse_dar_manifest_from_file(&mut zip_archive) {
-            Ok(manifest) => Ok(manifest),
-            Err(_) => Self::make_manifest_from_archive(&mut zip_archive),
-        }?;
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Create a `DarFile` from the supplied `dar` file `bytes`.
+    ///
+    /// This is useful for `dar` files which are held in memory, such as those downloaded over a network or embedded
+    /// with `include_bytes!`.  See [`DarFile::from_file`] for details of the supported `dar` formats.
+    ///
+    /// # Errors
+    ///
+    /// If the `bytes` cannot be interpreted as a `zip` archive then a [`DarParseError`] will be returned.
+    ///
+    /// Should both `fat` and `legacy` parsing attempts fail then a [`DarParseError`] will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use daml_lf::DarFile;
+    /// # use daml_lf::DamlLfResult;
+    /// # use daml_lf::DamlLfHashFunction;
+    /// # fn main() -> DamlLfResult<()> {
+    /// let bytes = std::fs::read("Example.dar")?;
+    /// let dar = DarFile::from_bytes(bytes)?;
+    /// assert_eq!(&DamlLfHashFunction::Sha256, dar.main().hash_function());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`DarParseError`]: DamlLfError::DarParseError
+    pub fn from_bytes(bytes: impl Into<Bytes>) -> DamlLfResult<Self> {
+        Self::from_reader(Cursor::new(bytes.into()))
+    }
+
+    /// Create a `DarFile` from the supplied `dar` file `reader`.
+    ///
+    /// See [`DarFile::from_file`] for details of the supported `dar` formats.
+    ///
+    /// # Errors
+    ///
+    /// If the `reader` cannot be read then an [`IoError`] will be returned.
+    ///
+    /// If the contents of the `reader` cannot be interpreted as a `zip` archive then a [`DarParseError`] will be
+    /// returned.
+    ///
+    /// Should both `fat` and `legacy` parsing attempts fail then a [`DarParseError`] will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use daml_lf::DarFile;
+    /// # use daml_lf::DamlLfResult;
+    /// # use daml_lf::DamlLfHashFunction;
+    /// # use std::io::BufReader;
+    /// # use std::fs::File;
+    /// # fn main() -> DamlLfResult<()> {
+    /// let dar = DarFile::from_reader(BufReader::new(File::open("Example.dar")?))?;
+    /// assert_eq!(&DamlLfHashFunction::Sha256, dar.main().hash_function());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`IoError`]: DamlLfError::IoError
+    /// [`DarParseError`]: DamlLfError::DarParseError
+    pub fn from_reader<R: Read + Seek>(reader: R) -> DamlLfResult<Self> {
+        let mut zip_archive = ZipArchive::new(reader)?;
+        let manifest = Self::read_manifest(&mut zip_archive)?;
         let dalf_main = Self::parse_dalf_from_archive(&mut zip_archive, manifest.dalf_main())?;
         let dalf_dependencies = Self::parse_dalfs_from_archive(&mut zip_archive, manifest.dalf_dependencies())?;
         Ok(Self::new(manifest, dalf_main, dalf_dependencies))
     }
 
+    /// Create a `DarFile` from the supplied asynchronous `dar` file `reader`.
+    ///
+    /// The entire contents of the `reader` are read into memory before being parsed as per [`DarFile::from_bytes`].
+    ///
+    /// This method requires the `async` feature.
+    ///
+    /// # Errors
+    ///
+    /// If the `reader` cannot be read then an [`IoError`] will be returned.
+    ///
+    /// If the contents of the `reader` cannot be interpreted as a `zip` archive then a [`DarParseError`] will be
+    /// returned.
+    ///
+    /// Should both `fat` and `legacy` parsing attempts fail then a [`DarParseError`] will be returned.
+    ///
+    /// [`IoError`]: DamlLfError::IoError
+    /// [`DarParseError`]: DamlLfError::DarParseError
+    #[cfg(feature = "async")]
+    pub async fn from_async_reader<R: AsyncRead + Unpin>(mut reader: R) -> DamlLfResult<Self> {
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer).await?;
+        Self::from_bytes(buffer)
+    }
+
     /// Create a [`DamlArchive`] from this [`DarFile`] and apply it to `f`.
     ///
     /// The created [`DamlArchive`] borrows all interned string data from this [`DarFile`] and is therefore tied to the
@@ -127,6 +218,33 @@ impl DarFile {
         convert::apply_dar(self, f)
     }
 
+    /// Create a [`DamlLazyArchive`] from this [`DarFile`] and apply it to `f`.
+    ///
+    /// Unlike [`DarFile::apply`], which converts every package up front, the [`DamlLazyArchive`] converts packages
+    /// and modules only when they are first accessed.  See [`LazyDarFile`] to also defer decoding the packages.
+    ///
+    /// [`LazyDarFile`]: crate::LazyDarFile
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use daml_lf::DarFile;
+    /// # use daml_lf::DamlLfResult;
+    /// # fn main() -> DamlLfResult<()> {
+    /// let dar = DarFile::from_file("Example.dar")?;
+    /// let name = dar.apply_lazy(|archive| -> DamlLfResult<Option<String>> {
+    ///     Ok(archive.package_by_name("daml-prim")?.map(|package| package.package_id().to_owned()))
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_lazy<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&DamlLazyArchive<'_>) -> R,
+    {
+        convert::apply_lazy(self, f)
+    }
+
     /// Create an owned [`DamlArchive`] from this [`DarFile`].
     ///
     /// This is an expensive operation as it involves both a conversion of the [`DarFile`] to a [`DamlArchive`] (which
@@ -152,6 +270,118 @@ impl DarFile {
         convert::to_owned_archive(self)
     }
 
+    /// Create owned [`DamlPackage`] for the given package ids from this [`DarFile`].
+    ///
+    /// Only the requested packages are converted, the remaining packages in this [`DarFile`] are used to resolve any
+    /// references from the requested packages.  This is useful for incrementally adding packages to an existing
+    /// [`DamlArchive`] without converting all packages again.
+    ///
+    /// # Errors
+    ///
+    /// If any of the requested package ids is not contained in this [`DarFile`] then a [`DamlLfConvertError`] will be
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use daml_lf::DarFile;
+    /// # use daml_lf::DamlLfResult;
+    /// # fn main() -> DamlLfResult<()> {
+    /// let dar = DarFile::from_file("Example.dar")?;
+    /// let packages = dar.to_owned_packages(&[dar.main().hash()])?;
+    /// assert_eq!("TestingTypes", packages[0].name());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`DamlLfConvertError`]: DamlLfError::DamlLfConvertError
+    pub fn to_owned_packages<S: AsRef<str>>(&self, package_ids: &[S]) -> DamlLfResult<Vec<DamlPackage<'static>>> {
+        convert::to_owned_packages(self, package_ids)
+    }
+
+    /// Serialize this `DarFile` to the bytes of a `dar` (zip) file.
+    ///
+    /// The `main` and `dependencies` [`DamlLfArchive`] are stored as `dalf` entries named `name-hash.dalf` within a
+    /// top level directory named after the `main` archive, and a `META-INF/MANIFEST.MF` is rendered (see
+    /// [`DarManifest::render`]) which lists these entries.  The `version`, `created_by`, `format` and `encryption` of
+    /// the rendered manifest are taken from the `manifest` of this `DarFile` whereas the `dalf` entries are always
+    /// derived from the contained archives, any `dalf` names in the existing `manifest` are ignored.
+    ///
+    /// The output is deterministic: the manifest is written first, followed by the `main` `dalf` and then the
+    /// `dependencies` `dalf` entries ordered by name (duplicates are omitted) and all entries share a fixed timestamp.
+    ///
+    /// # Errors
+    ///
+    /// If any contained [`DamlLfArchive`] cannot be serialized or the `zip` archive cannot be written then an error will
+    /// be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use daml_lf::DarFile;
+    /// # use daml_lf::DamlLfResult;
+    /// # fn main() -> DamlLfResult<()> {
+    /// let dar = DarFile::from_file("Example.dar")?;
+    /// let bytes = dar.to_bytes()?;
+    /// assert!(!bytes.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_bytes(&self) -> DamlLfResult<Vec<u8>> {
+        let main_path = Self::dalf_entry_path(&self.main, &self.main);
+        let dependencies: BTreeMap<String, &DamlLfArchive> = self
+            .dependencies
+            .iter()
+            .map(|archive| (Self::dalf_entry_path(&self.main, archive), archive))
+            .filter(|(path, _)| *path != main_path)
+            .collect();
+        let manifest = DarManifest::new(
+            self.manifest.version(),
+            self.manifest.created_by(),
+            main_path.clone(),
+            dependencies.keys().cloned().collect(),
+            self.manifest.format(),
+            self.manifest.encryption(),
+        );
+        let options = FileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .last_modified_time(DateTime::default())
+            .unix_permissions(DAR_ENTRY_PERMISSIONS);
+        let mut zip_writer = ZipWriter::new(Cursor::new(Vec::new()));
+        zip_writer.start_file(MANIFEST_FILE_PATH, options)?;
+        zip_writer.write_all(format!("{}\n", manifest.render()).as_bytes())?;
+        zip_writer.start_file(main_path, options)?;
+        zip_writer.write_all(&self.main.to_bytes()?)?;
+        for (path, archive) in dependencies {
+            zip_writer.start_file(path, options)?;
+            zip_writer.write_all(&archive.to_bytes()?)?;
+        }
+        Ok(zip_writer.finish()?.into_inner())
+    }
+
+    /// Write this `DarFile` to a `dar` file at the given `path`.
+    ///
+    /// See [`DarFile::to_bytes`] for details of the generated `dar` file.
+    ///
+    /// # Errors
+    ///
+    /// If this `DarFile` cannot be serialized or the file cannot be written then an error will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use daml_lf::DarFile;
+    /// # use daml_lf::DamlLfResult;
+    /// # fn main() -> DamlLfResult<()> {
+    /// let dar = DarFile::from_file("Example.dar")?;
+    /// dar.write_to_file("Copy.dar")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> DamlLfResult<()> {
+        Ok(std::fs::write(path, self.to_bytes()?)?)
+    }
+
     /// The `manifest` information contained within this `DarFile`.
     pub const fn manifest(&self) -> &DarManifest {
         &self.manifest
@@ -167,6 +397,11 @@ impl DarFile {
         &self.dependencies
     }
 
+    fn dalf_entry_path(main: &DamlLfArchive, archive: &DamlLfArchive) -> String {
+        let main_file_name = main.dalf_file_name();
+        format!("{}/{}", main_file_name.trim_end_matches(DALF_FILE_SUFFIX), archive.dalf_file_name())
+    }
+
     fn is_dalf(path: &Path) -> bool {
         path.extension().and_then(OsStr::to_str).map(str::to_lowercase).map_or(false, |q| q == DALF_FILE_EXTENSION)
     }
@@ -178,7 +413,7 @@ impl DarFile {
             .map_or(false, |p| p.ends_with(DALF_PRIM_FILE_SUFFIX))
     }
 
-    fn make_manifest_from_archive(zip_archive: &mut ZipArchive<File>) -> DamlLfResult<DarManifest> {
+    fn make_manifest_from_archive<R: Read + Seek>(zip_archive: &mut ZipArchive<R>) -> DamlLfResult<DarManifest> {
         let dalf_paths = zip_archive.paths();
         let (prim, main): (Vec<PathBuf>, Vec<PathBuf>) =
             dalf_paths.into_iter().filter(|d| Self::is_dalf(d)).partition(|d| Self::is_prim_dalf(d));
@@ -196,8 +431,8 @@ impl DarFile {
         Ok(manifest)
     }
 
-    fn parse_dalfs_from_archive(
-        zip_archive: &mut ZipArchive<File>,
+    fn parse_dalfs_from_archive<R: Read + Seek>(
+        zip_archive: &mut ZipArchive<R>,
         paths: &[String],
     ) -> DamlLfResult<Vec<DamlLfArchive>> {
         paths
@@ -206,17 +441,34 @@ impl DarFile {
             .collect::<DamlLfResult<Vec<DamlLfArchive>>>()
     }
 
+    fn parse_dalf_from_archive<R: Read + Seek>(
+        zip_archive: &mut ZipArchive<R>,
+        location: &str,
+    ) -> DamlLfResult<DamlLfArchive> {
+        let (archive_name_stem, buf) = Self::read_dalf_from_archive(zip_archive, location)?;
+        DamlLfArchive::from_bytes_named(archive_name_stem, buf)
+    }
+
+    /// Read the manifest of a `dar`, inferring it from the `dalf` entries for `legacy` `dar` files.
+    pub(crate) fn read_manifest<R: Read + Seek>(zip_archive: &mut ZipArchive<R>) -> DamlLfResult<DarManifest> {
+        Self::parse_dar_manifest_from_file(zip_archive).or_else(|_| Self::make_manifest_from_archive(zip_archive))
+    }
+
+    /// Read the name and undecoded bytes of the `dalf` entry at `location`.
     #[allow(clippy::cast_possible_truncation)]
-    fn parse_dalf_from_archive(zip_archive: &mut ZipArchive<File>, location: &str) -> DamlLfResult<DamlLfArchive> {
+    pub(crate) fn read_dalf_from_archive<R: Read + Seek>(
+        zip_archive: &mut ZipArchive<R>,
+        location: &str,
+    ) -> DamlLfResult<(String, Vec<u8>)> {
         let mut file = zip_archive.by_name(location)?;
         let mut buf = Vec::with_capacity(file.size() as usize);
         file.read_to_end(&mut buf)?;
         let archive_name_buffer = PathBuf::from(location);
         let archive_name_stem = archive_name_buffer.file_stem().and_then(OsStr::to_str).unwrap_or(DEFAULT_ARCHIVE_NAME);
-        DamlLfArchive::from_bytes_named(archive_name_stem, buf)
+        Ok((archive_name_stem.to_owned(), buf))
     }
 
-    fn parse_dar_manifest_from_file(zip_archive: &mut ZipArchive<File>) -> DamlLfResult<DarManifest> {
+    fn parse_dar_manifest_from_file<R: Read + Seek>(zip_archive: &mut ZipArchive<R>) -> DamlLfResult<DarManifest> {
         let mut file = zip_archive.by_name(MANIFEST_FILE_PATH)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
@@ -224,12 +476,88 @@ impl DarFile {
     }
 }
 
+/// Helper for building a [`DarFile`] from a `main` and `dependencies` [`DamlLfArchive`].
+///
+/// The [`DarManifest`] of the built [`DarFile`] is generated such that it lists the `dalf` entries of the `dar` file
+/// produced by [`DarFile::to_bytes`] and [`DarFile::write_to_file`].  Any `dependencies` which have the same `hash` as
+/// the `main` archive or as a previously added dependency are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use daml_lf::{DamlLfArchive, DarBuilder};
+/// # use daml_lf::DamlLfResult;
+/// # fn main() -> DamlLfResult<()> {
+/// let main = DamlLfArchive::from_file("Main.dalf")?;
+/// let prim = DamlLfArchive::from_file("daml-prim.dalf")?;
+/// let dar = DarBuilder::new(main).created_by("my-tool").add_dependency(prim).build();
+/// dar.write_to_file("Main.dar")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DarBuilder {
+    main: DamlLfArchive,
+    dependencies: Vec<DamlLfArchive>,
+    created_by: String,
+}
+
+impl DarBuilder {
+    /// Create a `DarBuilder` for the given `main` [`DamlLfArchive`].
+    pub fn new(main: impl Into<DamlLfArchive>) -> Self {
+        Self {
+            main: main.into(),
+            dependencies: vec![],
+            created_by: DAR_CREATED_BY.to_owned(),
+        }
+    }
+
+    /// Set the value of the `Created-By` attribute of the manifest.
+    pub fn created_by(mut self, created_by: impl Into<String>) -> Self {
+        self.created_by = created_by.into();
+        self
+    }
+
+    /// Add a `dependency` [`DamlLfArchive`].
+    pub fn add_dependency(mut self, dependency: impl Into<DamlLfArchive>) -> Self {
+        self.dependencies.push(dependency.into());
+        self
+    }
+
+    /// Add all `dependencies` [`DamlLfArchive`].
+    pub fn add_dependencies(mut self, dependencies: impl IntoIterator<Item = DamlLfArchive>) -> Self {
+        self.dependencies.extend(dependencies);
+        self
+    }
+
+    /// Build the [`DarFile`].
+    pub fn build(self) -> DarFile {
+        let main_hash = self.main.hash().to_owned();
+        let dependencies: Vec<DamlLfArchive> = self
+            .dependencies
+            .into_iter()
+            .filter(|archive| archive.hash() != main_hash)
+            .unique_by(|archive| archive.hash().to_owned())
+            .sorted_by_key(DamlLfArchive::dalf_file_name)
+            .collect();
+        let manifest = DarManifest::new(
+            DarManifestVersion::V1,
+            self.created_by,
+            DarFile::dalf_entry_path(&self.main, &self.main),
+            dependencies.iter().map(|archive| DarFile::dalf_entry_path(&self.main, archive)).collect(),
+            DarManifestFormat::DamlLf,
+            DarEncryptionType::NotEncrypted,
+        );
+        DarFile::new(manifest, self.main, dependencies)
+    }
+}
+
 trait ZipArchiveEx<T> {
     fn paths(&mut self) -> Vec<PathBuf>;
     fn contains(&mut self, path: &str) -> bool;
 }
 
-impl ZipArchiveEx<File> for ZipArchive<File> {
+impl<R: Read + Seek> ZipArchiveEx<R> for ZipArchive<R> {
     fn paths(&mut self) -> Vec<PathBuf> {
         let mut paths = Vec::with_capacity(self.len());
         for i in 0..self.len() {
@@ -0,0 +1,352 @@
+use crate::element::{
+    DamlAbsoluteTyCon, DamlArchive, DamlElementVisitor, DamlModule, DamlNonLocalTyCon, DamlPackage,
+    DamlVisitableElement,
+};
+use crate::{DamlLfError, DamlLfResult, DarFile, PackageInfo};
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Identifies a module within a `DamlPackage`.
+#[derive(Debug, Serialize, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct DamlModuleId {
+    pub package_id: String,
+    pub module_path: String,
+}
+
+impl DamlModuleId {
+    pub fn new(package_id: impl Into<String>, module_path: impl Into<String>) -> Self {
+        Self {
+            package_id: package_id.into(),
+            module_path: module_path.into(),
+        }
+    }
+}
+
+impl Display for DamlModuleId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.package_id, self.module_path)
+    }
+}
+
+/// A reference from a module to a data type defined in a different module.
+#[derive(Debug, Serialize, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct DamlDataReference {
+    pub source: DamlModuleId,
+    pub target: DamlModuleId,
+    pub data_name: String,
+}
+
+impl DamlDataReference {
+    pub fn new(source: DamlModuleId, target: DamlModuleId, data_name: impl Into<String>) -> Self {
+        Self {
+            source,
+            target,
+            data_name: data_name.into(),
+        }
+    }
+}
+
+impl Display for DamlDataReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}:{}", self.source, self.target, self.data_name)
+    }
+}
+
+/// The graph of references between the packages and modules of a `DamlArchive`.
+///
+/// Every [`DamlNonLocalTyCon`] and [`DamlAbsoluteTyCon`] found in the archive is recorded as a [`DamlDataReference`]
+/// from the module in which it appears to the module which defines the referenced data type.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use daml_lf::{DamlDependencyGraph, DamlLfResult, DarFile};
+/// # fn main() -> DamlLfResult<()> {
+/// let dar = DarFile::from_file("Example.dar")?;
+/// let graph = DamlDependencyGraph::extract_from_dar(&dar)?;
+/// for package_id in graph.package_order()? {
+///     println!("{}", package_id);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DamlDependencyGraph {
+    packages: BTreeMap<String, PackageInfo>,
+    modules: BTreeSet<DamlModuleId>,
+    references: BTreeSet<DamlDataReference>,
+}
+
+impl DamlDependencyGraph {
+    /// Extract the dependency graph of the packages in a `DarFile`.
+    pub fn extract_from_dar(dar: &DarFile) -> DamlLfResult<Self> {
+        dar.apply(Self::extract_from_archive)
+    }
+
+    /// Extract the dependency graph of the packages in a `DamlArchive`.
+    pub fn extract_from_archive(archive: &DamlArchive<'_>) -> Self {
+        let mut visitor = DependencyVisitor::default();
+        archive.accept(&mut visitor);
+        visitor.graph
+    }
+
+    /// The packages in the graph.
+    pub fn packages(&self) -> impl Iterator<Item = &PackageInfo> {
+        self.packages.values()
+    }
+
+    /// The modules, of all packages, in the graph.
+    pub fn modules(&self) -> impl Iterator<Item = &DamlModuleId> {
+        self.modules.iter()
+    }
+
+    /// All references between modules.
+    pub fn references(&self) -> impl Iterator<Item = &DamlDataReference> {
+        self.references.iter()
+    }
+
+    /// The ids of the packages which are directly referenced by the given package.
+    pub fn package_dependencies(&self, package_id: &str) -> BTreeSet<&str> {
+        self.package_edges().remove(package_id).unwrap_or_default()
+    }
+
+    /// The ids of the packages which directly reference the given package.
+    pub fn package_dependents(&self, package_id: &str) -> BTreeSet<&str> {
+        self.package_edges()
+            .into_iter()
+            .filter_map(|(source, targets)| targets.contains(package_id).then(|| source))
+            .collect()
+    }
+
+    /// The modules which are directly referenced by the given module.
+    pub fn module_dependencies(&self, module: &DamlModuleId) -> BTreeSet<&DamlModuleId> {
+        self.module_edges().remove(module).unwrap_or_default()
+    }
+
+    /// The modules which directly reference the given module.
+    pub fn module_dependents(&self, module: &DamlModuleId) -> BTreeSet<&DamlModuleId> {
+        self.module_edges()
+            .into_iter()
+            .filter_map(|(source, targets)| targets.contains(module).then(|| source))
+            .collect()
+    }
+
+    /// The ids of all packages ordered such that every package appears after all of the packages it depends on.
+    ///
+    /// Returns [`DamlLfError::DependencyCycle`] if the packages cannot be ordered.
+    pub fn package_order(&self) -> DamlLfResult<Vec<&str>> {
+        topological_order(&self.package_edges())
+    }
+
+    /// All modules ordered such that every module appears after all of the modules it depends on.
+    ///
+    /// Returns [`DamlLfError::DependencyCycle`] if the modules cannot be ordered.
+    pub fn module_order(&self) -> DamlLfResult<Vec<&DamlModuleId>> {
+        topological_order(&self.module_edges())
+    }
+
+    /// The sets of packages which depend on each other cyclically.
+    pub fn package_cycles(&self) -> Vec<Vec<&str>> {
+        cycles(&self.package_edges())
+    }
+
+    /// The sets of modules which depend on each other cyclically.
+    pub fn module_cycles(&self) -> Vec<Vec<&DamlModuleId>> {
+        cycles(&self.module_edges())
+    }
+
+    fn package_edges(&self) -> BTreeMap<&str, BTreeSet<&str>> {
+        let mut edges: BTreeMap<&str, BTreeSet<&str>> =
+            self.packages.keys().map(|package_id| (package_id.as_str(), BTreeSet::new())).collect();
+        for reference in &self.references {
+            edges.entry(reference.target.package_id.as_str()).or_default();
+            if reference.source.package_id != reference.target.package_id {
+                edges
+                    .entry(reference.source.package_id.as_str())
+                    .or_default()
+                    .insert(reference.target.package_id.as_str());
+            }
+        }
+        edges
+    }
+
+    fn module_edges(&self) -> BTreeMap<&DamlModuleId, BTreeSet<&DamlModuleId>> {
+        let mut edges: BTreeMap<&DamlModuleId, BTreeSet<&DamlModuleId>> =
+            self.modules.iter().map(|module| (module, BTreeSet::new())).collect();
+        for reference in &self.references {
+            edges.entry(&reference.target).or_default();
+            edges.entry(&reference.source).or_default().insert(&reference.target);
+        }
+        edges
+    }
+}
+
+/// Order the nodes of a graph such that every node appears after all nodes it has edges to.
+fn topological_order<T: Ord + Copy + Display>(edges: &BTreeMap<T, BTreeSet<T>>) -> DamlLfResult<Vec<T>> {
+    let mut remaining: BTreeMap<T, usize> = edges.iter().map(|(&node, targets)| (node, targets.len())).collect();
+    let mut ready: BTreeSet<T> = remaining.iter().filter(|(_, &count)| count == 0).map(|(&node, _)| node).collect();
+    let mut order = Vec::with_capacity(edges.len());
+    while let Some(node) = ready.iter().next().copied() {
+        ready.remove(&node);
+        remaining.remove(&node);
+        order.push(node);
+        for (&dependent, targets) in edges {
+            if targets.contains(&node) {
+                if let Some(count) = remaining.get_mut(&dependent) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.insert(dependent);
+                    }
+                }
+            }
+        }
+    }
+    match cycles(edges).first() {
+        Some(cycle) if order.len() < edges.len() =>
+            Err(DamlLfError::DependencyCycle(cycle.iter().map(ToString::to_string).collect())),
+        _ => Ok(order),
+    }
+}
+
+/// Find the strongly connected components of a graph which contain a cycle.
+fn cycles<T: Ord + Copy>(edges: &BTreeMap<T, BTreeSet<T>>) -> Vec<Vec<T>> {
+    struct Tarjan<'e, T> {
+        edges: &'e BTreeMap<T, BTreeSet<T>>,
+        index: BTreeMap<T, usize>,
+        low_link: BTreeMap<T, usize>,
+        stack: Vec<T>,
+        on_stack: BTreeSet<T>,
+        components: Vec<Vec<T>>,
+    }
+
+    impl<T: Ord + Copy> Tarjan<'_, T> {
+        fn visit(&mut self, node: T) {
+            let index = self.index.len();
+            self.index.insert(node, index);
+            self.low_link.insert(node, index);
+            self.stack.push(node);
+            self.on_stack.insert(node);
+            for &target in self.edges.get(&node).into_iter().flatten() {
+                if !self.index.contains_key(&target) {
+                    self.visit(target);
+                    let low = self.low_link[&node].min(self.low_link[&target]);
+                    self.low_link.insert(node, low);
+                } else if self.on_stack.contains(&target) {
+                    let low = self.low_link[&node].min(self.index[&target]);
+                    self.low_link.insert(node, low);
+                }
+            }
+            if self.low_link[&node] == index {
+                let mut component = vec![];
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(&member);
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                if component.len() > 1 || self.edges.get(&node).map_or(false, |targets| targets.contains(&node)) {
+                    component.sort();
+                    self.components.push(component);
+                }
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges,
+        index: BTreeMap::new(),
+        low_link: BTreeMap::new(),
+        stack: vec![],
+        on_stack: BTreeSet::new(),
+        components: vec![],
+    };
+    for &node in edges.keys() {
+        if !tarjan.index.contains_key(&node) {
+            tarjan.visit(node);
+        }
+    }
+    tarjan.components.sort();
+    tarjan.components
+}
+
+/// Record the references of every non-local and absolute type constructor.
+#[derive(Default)]
+struct DependencyVisitor {
+    graph: DamlDependencyGraph,
+    package_id: String,
+    modules: Vec<DamlModuleId>,
+}
+
+impl DamlElementVisitor for DependencyVisitor {
+    fn pre_visit_package<'a>(&mut self, package: &'a DamlPackage<'a>) {
+        self.package_id = package.package_id().to_owned();
+        self.graph.packages.insert(self.package_id.clone(), PackageInfo::extract_from_package(package));
+    }
+
+    fn pre_visit_module<'a>(&mut self, module: &'a DamlModule<'a>) {
+        let module_id = DamlModuleId::new(&self.package_id, module.path().join("."));
+        if !module.is_root() {
+            self.graph.modules.insert(module_id.clone());
+        }
+        self.modules.push(module_id);
+    }
+
+    fn post_visit_module<'a>(&mut self, _module: &'a DamlModule<'a>) {
+        self.modules.pop();
+    }
+
+    fn pre_visit_non_local_tycon<'a>(&mut self, non_local_tycon: &'a DamlNonLocalTyCon<'a>) {
+        let source =
+            DamlModuleId::new(non_local_tycon.source_package_id(), non_local_tycon.source_module_path().join("."));
+        let target =
+            DamlModuleId::new(non_local_tycon.target_package_id(), non_local_tycon.target_module_path().join("."));
+        self.add_reference(source, target, non_local_tycon.data_name());
+    }
+
+    fn pre_visit_absolute_tycon<'a>(&mut self, absolute_tycon: &'a DamlAbsoluteTyCon<'a>) {
+        if let Some(source) = self.modules.last().cloned() {
+            let target = DamlModuleId::new(absolute_tycon.package_id(), absolute_tycon.module_path().join("."));
+            self.add_reference(source, target, absolute_tycon.data_name());
+        }
+    }
+}
+
+impl DependencyVisitor {
+    fn add_reference(&mut self, source: DamlModuleId, target: DamlModuleId, data_name: &str) {
+        if source != target {
+            self.graph.references.insert(DamlDataReference::new(source, target, data_name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cycles, topological_order};
+    use crate::DamlLfError;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn graph<'a>(edges: &[(&'a str, &[&'a str])]) -> BTreeMap<&'a str, BTreeSet<&'a str>> {
+        edges.iter().map(|(node, targets)| (*node, targets.iter().copied().collect())).collect()
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let edges = graph(&[("a", &["b", "c"]), ("b", &["d"]), ("c", &["d"]), ("d", &[])]);
+        let order = topological_order(&edges).unwrap();
+        assert_eq!(vec!["d", "b", "c", "a"], order);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let edges = graph(&[("a", &["b"]), ("b", &["c"]), ("c", &["a"]), ("d", &["a"]), ("e", &["e"])]);
+        assert_eq!(vec![vec!["a", "b", "c"], vec!["e"]], cycles(&edges));
+        match topological_order(&edges) {
+            Err(DamlLfError::DependencyCycle(cycle)) => assert_eq!(vec!["a", "b", "c"], cycle),
+            other => panic!("expected a dependency cycle, found {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,466 @@
+use crate::element::{
+    DamlArchive, DamlChoice, DamlData, DamlElementVisitor, DamlField, DamlInterface, DamlModule, DamlPackage,
+    DamlVisitableElement,
+};
+use crate::{DamlLfResult, DarFile, PackageInfo, PrettyPrinter};
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// The kind of change made to an item.
+#[derive(Debug, Serialize, Clone, Copy, Eq, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DamlChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl DamlChangeKind {
+    fn symbol(self) -> char {
+        match self {
+            DamlChangeKind::Added => '+',
+            DamlChangeKind::Removed => '-',
+            DamlChangeKind::Changed => '~',
+        }
+    }
+}
+
+/// A change to a named property, such as a feature flag, field type or contract key.
+///
+/// The `old` value is `None` if the property was added and the `new` value is `None` if it was removed.
+#[derive(Debug, Serialize, Clone, Eq, PartialEq, Hash)]
+pub struct DamlPropertyDiff {
+    pub name: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl DamlPropertyDiff {
+    pub fn new(name: impl Into<String>, old: Option<String>, new: Option<String>) -> Self {
+        Self {
+            name: name.into(),
+            old,
+            new,
+        }
+    }
+
+    /// The kind of change made to this property.
+    pub fn change(&self) -> DamlChangeKind {
+        match (&self.old, &self.new) {
+            (None, _) => DamlChangeKind::Added,
+            (_, None) => DamlChangeKind::Removed,
+            _ => DamlChangeKind::Changed,
+        }
+    }
+}
+
+/// A change to a choice of a template or interface.
+#[derive(Debug, Serialize, Clone, Eq, PartialEq, Hash)]
+pub struct DamlChoiceDiff {
+    pub name: String,
+    pub change: DamlChangeKind,
+    pub properties: Vec<DamlPropertyDiff>,
+    pub fields: Vec<DamlPropertyDiff>,
+}
+
+/// A change to a data type or interface.
+///
+/// The `fields` of a variant or enum are its constructors.
+#[derive(Debug, Serialize, Clone, Eq, PartialEq, Hash)]
+pub struct DamlDataDiff {
+    pub name: String,
+    pub kind: String,
+    pub change: DamlChangeKind,
+    pub properties: Vec<DamlPropertyDiff>,
+    pub fields: Vec<DamlPropertyDiff>,
+    pub choices: Vec<DamlChoiceDiff>,
+}
+
+/// A change to a module.
+#[derive(Debug, Serialize, Clone, Eq, PartialEq, Hash)]
+pub struct DamlModuleDiff {
+    pub name: String,
+    pub change: DamlChangeKind,
+    pub flags: Vec<DamlPropertyDiff>,
+    pub data: Vec<DamlDataDiff>,
+}
+
+/// A structural diff between the main packages of two `DamlArchive`.
+///
+/// Modules, data types, interfaces, fields, choices, keys and module feature flags are compared by name.  Types are
+/// compared as rendered by the [`PrettyPrinter`] with package ids elided, such that a change of package id alone is not
+/// reported.
+///
+/// The diff can be rendered as text with [`Display`] or serialized, for example as JSON.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use daml_lf::{DamlArchiveDiff, DamlLfResult, DarFile};
+/// # fn main() -> DamlLfResult<()> {
+/// let old = DarFile::from_file("Example-1.0.0.dar")?;
+/// let new = DarFile::from_file("Example-1.1.0.dar")?;
+/// let diff = DamlArchiveDiff::from_dars(&old, &new)?;
+/// println!("{}", diff);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Serialize, Clone, Eq, PartialEq, Hash)]
+pub struct DamlArchiveDiff {
+    pub old_package: Option<PackageInfo>,
+    pub new_package: Option<PackageInfo>,
+    pub modules: Vec<DamlModuleDiff>,
+}
+
+impl DamlArchiveDiff {
+    /// Diff the main packages of two `DarFile`.
+    pub fn from_dars(old: &DarFile, new: &DarFile) -> DamlLfResult<Self> {
+        old.apply(|old| new.apply(|new| Self::from_archives(old, new)))?
+    }
+
+    /// Diff the main packages of two `DamlArchive`.
+    pub fn from_archives(old: &DamlArchive<'_>, new: &DamlArchive<'_>) -> Self {
+        let old = Snapshot::from_archive(old);
+        let new = Snapshot::from_archive(new);
+        let modules = merge(&old.modules, &new.modules)
+            .filter_map(|(name, old_module, new_module)| diff_module(name, old_module, new_module))
+            .collect();
+        Self {
+            old_package: old.package,
+            new_package: new.package,
+            modules,
+        }
+    }
+
+    /// Are the main packages of the two archives structurally equal?
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+}
+
+impl Display for DamlArchiveDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let package = |package: &Option<PackageInfo>| match package {
+            Some(PackageInfo {
+                package_id,
+                package_name,
+                version: Some(version),
+                ..
+            }) => format!("{}-{} ({})", package_name, version, package_id),
+            Some(PackageInfo {
+                package_id,
+                package_name,
+                ..
+            }) => format!("{} ({})", package_name, package_id),
+            None => String::from("none"),
+        };
+        writeln!(f, "--- {}", package(&self.old_package))?;
+        writeln!(f, "+++ {}", package(&self.new_package))?;
+        for module in &self.modules {
+            writeln!(f, "{} module {}", module.change.symbol(), module.name)?;
+            write_properties(f, 1, "", &module.flags)?;
+            for data in &module.data {
+                writeln!(f, "  {} {} {}", data.change.symbol(), data.kind, data.name)?;
+                write_properties(f, 2, "", &data.properties)?;
+                let label = match data.kind.as_str() {
+                    "variant" | "enum" => "constructor ",
+                    _ => "field ",
+                };
+                write_properties(f, 2, label, &data.fields)?;
+                for choice in &data.choices {
+                    writeln!(f, "    {} choice {}", choice.change.symbol(), choice.name)?;
+                    write_properties(f, 3, "", &choice.properties)?;
+                    write_properties(f, 3, "field ", &choice.fields)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_properties(f: &mut Formatter<'_>, depth: usize, label: &str, properties: &[DamlPropertyDiff]) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    for property in properties {
+        let symbol = property.change().symbol();
+        match (&property.old, &property.new) {
+            (Some(old), Some(new)) =>
+                writeln!(f, "{}{} {}{}: {} -> {}", indent, symbol, label, property.name, old, new)?,
+            (Some(value), None) | (None, Some(value)) if value.is_empty() =>
+                writeln!(f, "{}{} {}{}", indent, symbol, label, property.name)?,
+            (Some(value), None) | (None, Some(value)) =>
+                writeln!(f, "{}{} {}{}: {}", indent, symbol, label, property.name, value)?,
+            (None, None) => {},
+        }
+    }
+    Ok(())
+}
+
+fn diff_module(name: &str, old: Option<&ModuleSnapshot>, new: Option<&ModuleSnapshot>) -> Option<DamlModuleDiff> {
+    let module = |change, flags, data| DamlModuleDiff {
+        name: name.to_owned(),
+        change,
+        flags,
+        data,
+    };
+    match (old, new) {
+        (Some(old), Some(new)) => {
+            let flags = diff_properties(&old.flags, &new.flags);
+            let data = merge(&old.data, &new.data)
+                .filter_map(|(name, old_data, new_data)| diff_data(name, old_data, new_data))
+                .collect::<Vec<_>>();
+            (!flags.is_empty() || !data.is_empty()).then(|| module(DamlChangeKind::Changed, flags, data))
+        },
+        (None, Some(_)) => Some(module(DamlChangeKind::Added, vec![], vec![])),
+        (Some(_), None) => Some(module(DamlChangeKind::Removed, vec![], vec![])),
+        (None, None) => None,
+    }
+}
+
+fn diff_data(name: &str, old: Option<&DataSnapshot>, new: Option<&DataSnapshot>) -> Option<DamlDataDiff> {
+    let data = |kind: &str, change, properties, fields, choices| DamlDataDiff {
+        name: name.to_owned(),
+        kind: kind.to_owned(),
+        change,
+        properties,
+        fields,
+        choices,
+    };
+    match (old, new) {
+        (Some(old), Some(new)) => {
+            let properties = diff_properties(&old.properties, &new.properties);
+            let fields = diff_properties(&old.fields, &new.fields);
+            let choices = merge(&old.choices, &new.choices)
+                .filter_map(|(name, old_choice, new_choice)| diff_choice(name, old_choice, new_choice))
+                .collect::<Vec<_>>();
+            (!properties.is_empty() || !fields.is_empty() || !choices.is_empty())
+                .then(|| data(new.kind, DamlChangeKind::Changed, properties, fields, choices))
+        },
+        (None, Some(new)) => Some(data(new.kind, DamlChangeKind::Added, vec![], vec![], vec![])),
+        (Some(old), None) => Some(data(old.kind, DamlChangeKind::Removed, vec![], vec![], vec![])),
+        (None, None) => None,
+    }
+}
+
+fn diff_choice(name: &str, old: Option<&ChoiceSnapshot>, new: Option<&ChoiceSnapshot>) -> Option<DamlChoiceDiff> {
+    let choice = |change, properties, fields| DamlChoiceDiff {
+        name: name.to_owned(),
+        change,
+        properties,
+        fields,
+    };
+    match (old, new) {
+        (Some(old), Some(new)) => {
+            let properties = diff_properties(&old.properties, &new.properties);
+            let fields = diff_properties(&old.fields, &new.fields);
+            (!properties.is_empty() || !fields.is_empty()).then(|| choice(DamlChangeKind::Changed, properties, fields))
+        },
+        (None, Some(_)) => Some(choice(DamlChangeKind::Added, vec![], vec![])),
+        (Some(_), None) => Some(choice(DamlChangeKind::Removed, vec![], vec![])),
+        (None, None) => None,
+    }
+}
+
+/// Diff two ordered lists of named properties, ordered by their position in `old` followed by any new properties.
+fn diff_properties(old: &[(String, String)], new: &[(String, String)]) -> Vec<DamlPropertyDiff> {
+    let find = |properties: &[(String, String)], name: &str| {
+        properties.iter().find(|(n, _)| n == name).map(|(_, value)| value.clone())
+    };
+    let changed = old.iter().filter_map(|(name, old_value)| match find(new, name) {
+        Some(new_value) if &new_value == old_value => None,
+        new_value => Some(DamlPropertyDiff::new(name, Some(old_value.clone()), new_value)),
+    });
+    let added = new
+        .iter()
+        .filter(|(name, _)| find(old, name).is_none())
+        .map(|(name, new_value)| DamlPropertyDiff::new(name, None, Some(new_value.clone())));
+    changed.chain(added).collect()
+}
+
+/// Merge two maps by key.
+fn merge<'m, T>(
+    old: &'m BTreeMap<String, T>,
+    new: &'m BTreeMap<String, T>,
+) -> impl Iterator<Item = (&'m str, Option<&'m T>, Option<&'m T>)> {
+    old.keys().chain(new.keys()).unique().sorted().map(move |name| (name.as_str(), old.get(name), new.get(name)))
+}
+
+/// A snapshot of the items of the main package of an archive which are compared.
+struct Snapshot {
+    package: Option<PackageInfo>,
+    modules: BTreeMap<String, ModuleSnapshot>,
+}
+
+impl Snapshot {
+    fn from_archive(archive: &DamlArchive<'_>) -> Self {
+        let mut visitor = SnapshotVisitor {
+            printer: PrettyPrinter::new().elide_package_ids(true),
+            main_package_id: String::new(),
+            active: false,
+            snapshot: Snapshot {
+                package: None,
+                modules: BTreeMap::new(),
+            },
+        };
+        archive.accept(&mut visitor);
+        visitor.snapshot
+    }
+}
+
+#[derive(Default)]
+struct ModuleSnapshot {
+    flags: Vec<(String, String)>,
+    data: BTreeMap<String, DataSnapshot>,
+}
+
+struct DataSnapshot {
+    kind: &'static str,
+    properties: Vec<(String, String)>,
+    fields: Vec<(String, String)>,
+    choices: BTreeMap<String, ChoiceSnapshot>,
+}
+
+struct ChoiceSnapshot {
+    properties: Vec<(String, String)>,
+    fields: Vec<(String, String)>,
+}
+
+/// Record a snapshot of each module, data type and interface of the main package.
+struct SnapshotVisitor {
+    printer: PrettyPrinter,
+    main_package_id: String,
+    active: bool,
+    snapshot: Snapshot,
+}
+
+impl SnapshotVisitor {
+    fn fields(&self, fields: &[DamlField<'_>]) -> Vec<(String, String)> {
+        fields.iter().map(|field| (field.name().to_owned(), self.printer.render_type(field.ty()))).collect()
+    }
+
+    fn choices(&self, choices: &[DamlChoice<'_>]) -> BTreeMap<String, ChoiceSnapshot> {
+        choices
+            .iter()
+            .map(|choice| {
+                let properties = vec![
+                    (String::from("consuming"), choice.consuming().to_string()),
+                    (String::from("returns"), self.printer.render_type(choice.return_type())),
+                ];
+                (choice.name().to_owned(), ChoiceSnapshot {
+                    properties,
+                    fields: self.fields(choice.fields()),
+                })
+            })
+            .collect()
+    }
+
+    fn add_data(&mut self, module_path: String, name: &str, data: DataSnapshot) {
+        self.snapshot.modules.entry(module_path).or_default().data.insert(name.to_owned(), data);
+    }
+}
+
+impl DamlElementVisitor for SnapshotVisitor {
+    fn pre_visit_archive<'a>(&mut self, archive: &'a DamlArchive<'a>) {
+        self.main_package_id = archive.main_package_id().to_owned();
+    }
+
+    fn pre_visit_package<'a>(&mut self, package: &'a DamlPackage<'a>) {
+        self.active = package.package_id() == self.main_package_id;
+        if self.active {
+            self.snapshot.package = Some(PackageInfo::extract_from_package(package));
+        }
+    }
+
+    fn pre_visit_module<'a>(&mut self, module: &'a DamlModule<'a>) {
+        if self.active && !module.is_root() {
+            let flags = module.flags();
+            self.snapshot.modules.entry(module.path().join(".")).or_default().flags = vec![
+                (String::from("forbid_party_literals"), flags.forbid_party_literals().to_string()),
+                (
+                    String::from("dont_divulge_contract_ids_in_create_arguments"),
+                    flags.dont_divulge_contract_ids_in_create_arguments().to_string(),
+                ),
+                (
+                    String::from("dont_disclose_non_consuming_choices_to_observers"),
+                    flags.dont_disclose_non_consuming_choices_to_observers().to_string(),
+                ),
+            ];
+        }
+    }
+
+    fn pre_visit_data<'a>(&mut self, data: &'a DamlData<'a>) {
+        if !self.active {
+            return;
+        }
+        let mut properties = vec![];
+        if !data.type_params().is_empty() {
+            let type_params = data.type_params().iter().map(|param| param.var().to_owned()).join(" ");
+            properties.push((String::from("type parameters"), type_params));
+        }
+        let (kind, fields, choices) = match data {
+            DamlData::Template(template) => {
+                if let Some(key) = template.key() {
+                    properties.push((String::from("key"), self.printer.render_type(key.ty())));
+                }
+                ("template", self.fields(template.fields()), self.choices(template.choices()))
+            },
+            DamlData::Record(record) => ("record", self.fields(record.fields()), BTreeMap::new()),
+            DamlData::Variant(variant) => ("variant", self.fields(variant.fields()), BTreeMap::new()),
+            DamlData::Enum(data_enum) => {
+                let constructors = data_enum.constructors().map(|c| (c.to_owned(), String::new())).collect();
+                ("enum", constructors, BTreeMap::new())
+            },
+        };
+        let snapshot = DataSnapshot {
+            kind,
+            properties,
+            fields,
+            choices,
+        };
+        self.add_data(data.module_path().join("."), data.name(), snapshot);
+    }
+
+    fn pre_visit_interface<'a>(&mut self, interface: &'a DamlInterface<'a>) {
+        if !self.active {
+            return;
+        }
+        let snapshot = DataSnapshot {
+            kind: "interface",
+            properties: vec![(String::from("view"), self.printer.render_type(interface.view()))],
+            fields: vec![],
+            choices: self.choices(interface.choices()),
+        };
+        self.add_data(interface.module_path().join("."), interface.name(), snapshot);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::diff_properties;
+    use crate::{DamlChangeKind, DamlPropertyDiff};
+
+    fn properties(properties: &[(&str, &str)]) -> Vec<(String, String)> {
+        properties.iter().map(|(name, value)| ((*name).to_owned(), (*value).to_owned())).collect()
+    }
+
+    #[test]
+    fn test_diff_properties() {
+        let old = properties(&[("a", "Int64"), ("b", "Text"), ("c", "Party")]);
+        let new = properties(&[("d", "Date"), ("a", "Int64"), ("c", "Text")]);
+        let diff = diff_properties(&old, &new);
+        assert_eq!(
+            vec![
+                DamlPropertyDiff::new("b", Some("Text".to_owned()), None),
+                DamlPropertyDiff::new("c", Some("Party".to_owned()), Some("Text".to_owned())),
+                DamlPropertyDiff::new("d", None, Some("Date".to_owned())),
+            ],
+            diff
+        );
+        assert_eq!(
+            vec![DamlChangeKind::Removed, DamlChangeKind::Changed, DamlChangeKind::Added],
+            diff.iter().map(DamlPropertyDiff::change).collect::<Vec<_>>()
+        );
+    }
+}
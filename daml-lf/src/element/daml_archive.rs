@@ -46,6 +46,12 @@ impl<'a> DamlArchive<'a> {
         self.packages.values()
     }
 
+    /// Return the [`DamlPackage`] in this [`DamlArchive`] with the given `package_id` or `None` if no such package
+    /// exists.
+    pub fn package_by_id(&self, package_id: &str) -> Option<&DamlPackage<'_>> {
+        self.packages.get(package_id)
+    }
+
     /// Return the first [`DamlPackage`] in this [`DamlArchive`] which has the given `name` or `None` if no such
     /// package exists.
     pub fn package_by_name(&self, name: &str) -> Option<&DamlPackage<'_>> {
@@ -57,6 +63,23 @@ impl<'a> DamlArchive<'a> {
         self.packages.get(&self.main_package_id)
     }
 
+    /// Return a mutable reference to the [`DamlPackage`] in this [`DamlArchive`] with the given `package_id` or `None`
+    /// if no such package exists.
+    pub fn package_by_id_mut(&mut self, package_id: &str) -> Option<&mut DamlPackage<'a>> {
+        self.packages.get_mut(package_id)
+    }
+
+    /// Return a mutable reference to the main [`DamlPackage`] in this [`DamlArchive`] or `None` if no such package
+    /// exists.
+    pub fn main_package_mut(&mut self) -> Option<&mut DamlPackage<'a>> {
+        self.packages.get_mut(&self.main_package_id)
+    }
+
+    /// Remove the [`DamlPackage`] with the given `package_id` from this [`DamlArchive`], returning it if it existed.
+    pub fn remove_package(&mut self, package_id: &str) -> Option<DamlPackage<'a>> {
+        self.packages.remove(package_id)
+    }
+
     /// Retrieve a `DamlData` contained within this `DamlArchive` referred to by the supplied `DamlTyCon` or `None` if
     /// not such data item exists.
     ///
@@ -115,6 +138,13 @@ impl<'a> DamlArchive<'a> {
     }
 }
 
+/// Add [`DamlPackage`] to a [`DamlArchive`], replacing any existing package with the same package id.
+impl<'a> Extend<DamlPackage<'a>> for DamlArchive<'a> {
+    fn extend<T: IntoIterator<Item = DamlPackage<'a>>>(&mut self, packages: T) {
+        self.packages.extend(packages.into_iter().map(|package| (Cow::from(package.package_id().to_owned()), package)));
+    }
+}
+
 impl<'a> DamlVisitableElement<'a> for DamlArchive<'a> {
     fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
         visitor.pre_visit_archive(self);
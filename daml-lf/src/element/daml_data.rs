@@ -2,7 +2,7 @@ use crate::element::daml_field::DamlField;
 use crate::element::visitor::DamlElementVisitor;
 #[cfg(feature = "full")]
 use crate::element::{DamlExpr, DamlPrimLit};
-use crate::element::{DamlTyConName, DamlType, DamlTypeVarWithKind, DamlVisitableElement};
+use crate::element::{DamlInterfaceInstance, DamlTyConName, DamlType, DamlTypeVarWithKind, DamlVisitableElement};
 use bounded_static::ToStatic;
 use serde::Serialize;
 use std::borrow::Cow;
@@ -48,7 +48,7 @@ impl<'a> DamlData<'a> {
     }
 
     /// The fields of this data type.
-    pub fn fields(&self) -> &[DamlField<'_>] {
+    pub fn fields(&self) -> &[DamlField<'a>] {
         match self {
             DamlData::Record(record) => &record.fields,
             DamlData::Template(template) => &template.fields,
@@ -58,7 +58,7 @@ impl<'a> DamlData<'a> {
     }
 
     /// The type parameters applied to this data type.
-    pub fn type_params(&self) -> &[DamlTypeVarWithKind<'_>] {
+    pub fn type_params(&self) -> &[DamlTypeVarWithKind<'a>] {
         match self {
             DamlData::Record(record) => &record.type_params,
             DamlData::Template(_) => &[],
@@ -179,6 +179,7 @@ pub struct DamlTemplate<'a> {
     #[cfg(feature = "full")]
     observers: DamlExpr<'a>,
     key: Option<DamlDefKey<'a>>,
+    implements: Vec<DamlInterfaceInstance<'a>>,
     serializable: bool,
 }
 
@@ -196,6 +197,7 @@ impl<'a> DamlTemplate<'a> {
         #[cfg(feature = "full")] agreement: DamlExpr<'a>,
         #[cfg(feature = "full")] observers: DamlExpr<'a>,
         key: Option<DamlDefKey<'a>>,
+        implements: Vec<DamlInterfaceInstance<'a>>,
         serializable: bool,
     ) -> Self {
         Self {
@@ -214,6 +216,7 @@ impl<'a> DamlTemplate<'a> {
             #[cfg(feature = "full")]
             observers,
             key,
+            implements,
             serializable,
         }
     }
@@ -234,12 +237,13 @@ impl<'a> DamlTemplate<'a> {
             #[cfg(feature = "full")]
             precond: None,
             #[cfg(feature = "full")]
-            signatories: DamlExpr::Nil(DamlType::List(vec![DamlType::Party])),
+            signatories: DamlExpr::Nil(DamlType::Party),
             #[cfg(feature = "full")]
             agreement: DamlExpr::PrimLit(DamlPrimLit::Text(Cow::default())),
             #[cfg(feature = "full")]
-            observers: DamlExpr::Nil(DamlType::List(vec![DamlType::Party])),
+            observers: DamlExpr::Nil(DamlType::Party),
             key: None,
+            implements: vec![],
             serializable: true,
         }
     }
@@ -292,6 +296,10 @@ impl<'a> DamlTemplate<'a> {
         self.key.as_ref()
     }
 
+    pub fn implements(&self) -> &[DamlInterfaceInstance<'a>] {
+        &self.implements
+    }
+
     pub const fn serializable(&self) -> bool {
         self.serializable
     }
@@ -311,6 +319,7 @@ impl<'a> DamlVisitableElement<'a> for DamlTemplate<'a> {
         #[cfg(feature = "full")]
         self.observers.accept(visitor);
         self.key.iter().for_each(|k| k.accept(visitor));
+        self.implements.iter().for_each(|instance| instance.accept(visitor));
         visitor.post_visit_template(self);
     }
 }
@@ -343,6 +352,10 @@ pub struct DamlChoice<'a> {
     consuming: bool,
     self_binder: Cow<'a, str>,
     #[cfg(feature = "full")]
+    argument_binder: Cow<'a, str>,
+    #[cfg(feature = "full")]
+    argument_type: DamlType<'a>,
+    #[cfg(feature = "full")]
     update: DamlExpr<'a>,
     #[cfg(feature = "full")]
     controllers: DamlExpr<'a>,
@@ -360,6 +373,8 @@ impl<'a> DamlChoice<'a> {
         return_type: DamlType<'a>,
         consuming: bool,
         self_binder: Cow<'a, str>,
+        #[cfg(feature = "full")] argument_binder: Cow<'a, str>,
+        #[cfg(feature = "full")] argument_type: DamlType<'a>,
         #[cfg(feature = "full")] update: DamlExpr<'a>,
         #[cfg(feature = "full")] controllers: DamlExpr<'a>,
         #[cfg(feature = "full")] observers: DamlExpr<'a>,
@@ -373,6 +388,10 @@ impl<'a> DamlChoice<'a> {
             consuming,
             self_binder,
             #[cfg(feature = "full")]
+            argument_binder,
+            #[cfg(feature = "full")]
+            argument_type,
+            #[cfg(feature = "full")]
             update,
             #[cfg(feature = "full")]
             controllers,
@@ -397,11 +416,15 @@ impl<'a> DamlChoice<'a> {
             consuming: false,
             self_binder: Cow::default(),
             #[cfg(feature = "full")]
+            argument_binder: Cow::default(),
+            #[cfg(feature = "full")]
+            argument_type: DamlType::Unit,
+            #[cfg(feature = "full")]
             update: DamlExpr::Nil(DamlType::List(vec![DamlType::Party])),
             #[cfg(feature = "full")]
-            controllers: DamlExpr::Nil(DamlType::List(vec![DamlType::Party])),
+            controllers: DamlExpr::Nil(DamlType::Party),
             #[cfg(feature = "full")]
-            observers: DamlExpr::Nil(DamlType::List(vec![DamlType::Party])),
+            observers: DamlExpr::Nil(DamlType::Party),
         }
     }
 
@@ -433,6 +456,18 @@ impl<'a> DamlChoice<'a> {
         &self.self_binder
     }
 
+    /// The name of the variable bound to the choice argument within the choice body.
+    #[cfg(feature = "full")]
+    pub fn argument_binder(&self) -> &str {
+        &self.argument_binder
+    }
+
+    /// The type of the choice argument.
+    #[cfg(feature = "full")]
+    pub fn argument_type(&self) -> &DamlType<'a> {
+        &self.argument_type
+    }
+
     #[cfg(feature = "full")]
     pub fn update(&self) -> &DamlExpr<'a> {
         &self.update
@@ -455,6 +490,8 @@ impl<'a> DamlVisitableElement<'a> for DamlChoice<'a> {
         self.fields.iter().for_each(|field| field.accept(visitor));
         self.return_type.accept(visitor);
         #[cfg(feature = "full")]
+        self.argument_type.accept(visitor);
+        #[cfg(feature = "full")]
         self.update.accept(visitor);
         #[cfg(feature = "full")]
         self.controllers.accept(visitor);
@@ -69,3 +69,48 @@ impl<'a> DamlVisitableElement<'a> for DamlDefValue<'a> {
         visitor.post_visit_def_value(self);
     }
 }
+
+/// A Daml exception definition.
+///
+/// The `name` of an exception is the name of the record [`DamlData`](crate::element::DamlData) which holds the
+/// exception payload.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlDefException<'a> {
+    pub name: Cow<'a, str>,
+    pub message: DamlExpr<'a>,
+}
+
+impl<'a> DamlDefException<'a> {
+    pub const fn new(name: Cow<'a, str>, message: DamlExpr<'a>) -> Self {
+        Self {
+            name,
+            message,
+        }
+    }
+
+    /// The name of this exception.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name of this exception.
+    ///
+    /// This is a clone of a `Cow<str>` which is cheap for the borrowed case used within the library.
+    #[doc(hidden)]
+    pub fn name_clone(&self) -> Cow<'a, str> {
+        self.name.clone()
+    }
+
+    /// The expression which computes the message of this exception from the exception payload.
+    pub const fn message(&self) -> &DamlExpr<'a> {
+        &self.message
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlDefException<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_def_exception(self);
+        self.message.accept(visitor);
+        visitor.post_visit_def_exception(self);
+    }
+}
@@ -41,6 +41,17 @@ pub enum DamlExpr<'a> {
     ToAnyException(DamlToAnyException<'a>),
     FromAnyException(DamlFromAnyException<'a>),
     Throw(DamlThrow<'a>),
+    ToInterface(DamlInterfaceCast<'a>),
+    FromInterface(DamlInterfaceCast<'a>),
+    UnsafeFromInterface(DamlUnsafeFromInterface<'a>),
+    CallInterface(DamlCallInterface<'a>),
+    ToRequiredInterface(DamlRequiredInterfaceCast<'a>),
+    FromRequiredInterface(DamlRequiredInterfaceCast<'a>),
+    UnsafeFromRequiredInterface(DamlUnsafeFromRequiredInterface<'a>),
+    InterfaceTemplateTypeRep(DamlInterfaceExpr<'a>),
+    SignatoryInterface(DamlInterfaceExpr<'a>),
+    ObserverInterface(DamlInterfaceExpr<'a>),
+    ViewInterface(DamlInterfaceExpr<'a>),
 }
 
 impl<'a> DamlVisitableElement<'a> for DamlExpr<'a> {
@@ -75,6 +86,18 @@ impl<'a> DamlVisitableElement<'a> for DamlExpr<'a> {
             DamlExpr::ToAnyException(to_any_exception) => to_any_exception.accept(visitor),
             DamlExpr::FromAnyException(from_any_exception) => from_any_exception.accept(visitor),
             DamlExpr::Throw(throw) => throw.accept(visitor),
+            DamlExpr::ToInterface(interface_cast) | DamlExpr::FromInterface(interface_cast) =>
+                interface_cast.accept(visitor),
+            DamlExpr::UnsafeFromInterface(unsafe_from_interface) => unsafe_from_interface.accept(visitor),
+            DamlExpr::CallInterface(call_interface) => call_interface.accept(visitor),
+            DamlExpr::ToRequiredInterface(required_interface_cast)
+            | DamlExpr::FromRequiredInterface(required_interface_cast) => required_interface_cast.accept(visitor),
+            DamlExpr::UnsafeFromRequiredInterface(unsafe_from_required_interface) =>
+                unsafe_from_required_interface.accept(visitor),
+            DamlExpr::InterfaceTemplateTypeRep(interface_expr)
+            | DamlExpr::SignatoryInterface(interface_expr)
+            | DamlExpr::ObserverInterface(interface_expr)
+            | DamlExpr::ViewInterface(interface_expr) => interface_expr.accept(visitor),
             DamlExpr::Var(_) => {},
         }
         visitor.post_visit_expr(self);
@@ -1396,6 +1419,8 @@ pub enum DamlUpdate<'a> {
     FetchByKey(DamlRetrieveByKey<'a>),
     EmbedExpr(DamlUpdateEmbedExpr<'a>),
     TryCatch(DamlTryCatch<'a>),
+    ExerciseInterface(DamlExerciseInterface<'a>),
+    FetchInterface(DamlFetchInterface<'a>),
 }
 
 impl<'a> DamlVisitableElement<'a> for DamlUpdate<'a> {
@@ -1412,6 +1437,8 @@ impl<'a> DamlVisitableElement<'a> for DamlUpdate<'a> {
                 retrieve_by_key.accept(visitor),
             DamlUpdate::EmbedExpr(embed_expr) => embed_expr.accept(visitor),
             DamlUpdate::TryCatch(try_catch) => try_catch.accept(visitor),
+            DamlUpdate::ExerciseInterface(exercise_interface) => exercise_interface.accept(visitor),
+            DamlUpdate::FetchInterface(fetch_interface) => fetch_interface.accept(visitor),
             DamlUpdate::GetTime => {},
         }
         visitor.post_visit_update(self);
@@ -1942,3 +1969,351 @@ impl<'a> DamlVisitableElement<'a> for DamlTryCatch<'a> {
         visitor.post_visit_try_catch(self);
     }
 }
+
+/// A Daml expression interface upcast (`to_interface`) or downcast (`from_interface`).
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlInterfaceCast<'a> {
+    interface_type: Box<DamlTyConName<'a>>,
+    template_type: Box<DamlTyConName<'a>>,
+    expr: Box<DamlExpr<'a>>,
+}
+
+impl<'a> DamlInterfaceCast<'a> {
+    pub fn new(
+        interface_type: Box<DamlTyConName<'a>>,
+        template_type: Box<DamlTyConName<'a>>,
+        expr: Box<DamlExpr<'a>>,
+    ) -> Self {
+        Self {
+            interface_type,
+            template_type,
+            expr,
+        }
+    }
+
+    pub fn interface_type(&self) -> &DamlTyConName<'a> {
+        &self.interface_type
+    }
+
+    pub fn template_type(&self) -> &DamlTyConName<'a> {
+        &self.template_type
+    }
+
+    pub fn expr(&self) -> &DamlExpr<'a> {
+        self.expr.as_ref()
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlInterfaceCast<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_interface_cast(self);
+        self.interface_type.accept(visitor);
+        self.template_type.accept(visitor);
+        self.expr.accept(visitor);
+        visitor.post_visit_interface_cast(self);
+    }
+}
+
+/// A Daml expression interface downcast which throws on type mismatch.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlUnsafeFromInterface<'a> {
+    interface_type: Box<DamlTyConName<'a>>,
+    template_type: Box<DamlTyConName<'a>>,
+    contract_id_expr: Box<DamlExpr<'a>>,
+    interface_expr: Box<DamlExpr<'a>>,
+}
+
+impl<'a> DamlUnsafeFromInterface<'a> {
+    pub fn new(
+        interface_type: Box<DamlTyConName<'a>>,
+        template_type: Box<DamlTyConName<'a>>,
+        contract_id_expr: Box<DamlExpr<'a>>,
+        interface_expr: Box<DamlExpr<'a>>,
+    ) -> Self {
+        Self {
+            interface_type,
+            template_type,
+            contract_id_expr,
+            interface_expr,
+        }
+    }
+
+    pub fn interface_type(&self) -> &DamlTyConName<'a> {
+        &self.interface_type
+    }
+
+    pub fn template_type(&self) -> &DamlTyConName<'a> {
+        &self.template_type
+    }
+
+    pub fn contract_id_expr(&self) -> &DamlExpr<'a> {
+        self.contract_id_expr.as_ref()
+    }
+
+    pub fn interface_expr(&self) -> &DamlExpr<'a> {
+        self.interface_expr.as_ref()
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlUnsafeFromInterface<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_unsafe_from_interface(self);
+        self.interface_type.accept(visitor);
+        self.template_type.accept(visitor);
+        self.contract_id_expr.accept(visitor);
+        self.interface_expr.accept(visitor);
+        visitor.post_visit_unsafe_from_interface(self);
+    }
+}
+
+/// A Daml expression interface method call.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlCallInterface<'a> {
+    interface_type: Box<DamlTyConName<'a>>,
+    method: Cow<'a, str>,
+    interface_expr: Box<DamlExpr<'a>>,
+}
+
+impl<'a> DamlCallInterface<'a> {
+    pub fn new(interface_type: Box<DamlTyConName<'a>>, method: Cow<'a, str>, interface_expr: Box<DamlExpr<'a>>) -> Self {
+        Self {
+            interface_type,
+            method,
+            interface_expr,
+        }
+    }
+
+    pub fn interface_type(&self) -> &DamlTyConName<'a> {
+        &self.interface_type
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn interface_expr(&self) -> &DamlExpr<'a> {
+        self.interface_expr.as_ref()
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlCallInterface<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_call_interface(self);
+        self.interface_type.accept(visitor);
+        self.interface_expr.accept(visitor);
+        visitor.post_visit_call_interface(self);
+    }
+}
+
+/// A Daml expression upcast (`to_required_interface`) or downcast (`from_required_interface`) between interfaces.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlRequiredInterfaceCast<'a> {
+    required_interface: Box<DamlTyConName<'a>>,
+    requiring_interface: Box<DamlTyConName<'a>>,
+    expr: Box<DamlExpr<'a>>,
+}
+
+impl<'a> DamlRequiredInterfaceCast<'a> {
+    pub fn new(
+        required_interface: Box<DamlTyConName<'a>>,
+        requiring_interface: Box<DamlTyConName<'a>>,
+        expr: Box<DamlExpr<'a>>,
+    ) -> Self {
+        Self {
+            required_interface,
+            requiring_interface,
+            expr,
+        }
+    }
+
+    pub fn required_interface(&self) -> &DamlTyConName<'a> {
+        &self.required_interface
+    }
+
+    pub fn requiring_interface(&self) -> &DamlTyConName<'a> {
+        &self.requiring_interface
+    }
+
+    pub fn expr(&self) -> &DamlExpr<'a> {
+        self.expr.as_ref()
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlRequiredInterfaceCast<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_required_interface_cast(self);
+        self.required_interface.accept(visitor);
+        self.requiring_interface.accept(visitor);
+        self.expr.accept(visitor);
+        visitor.post_visit_required_interface_cast(self);
+    }
+}
+
+/// A Daml expression downcast to a requiring interface which throws on type mismatch.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlUnsafeFromRequiredInterface<'a> {
+    required_interface: Box<DamlTyConName<'a>>,
+    requiring_interface: Box<DamlTyConName<'a>>,
+    contract_id_expr: Box<DamlExpr<'a>>,
+    interface_expr: Box<DamlExpr<'a>>,
+}
+
+impl<'a> DamlUnsafeFromRequiredInterface<'a> {
+    pub fn new(
+        required_interface: Box<DamlTyConName<'a>>,
+        requiring_interface: Box<DamlTyConName<'a>>,
+        contract_id_expr: Box<DamlExpr<'a>>,
+        interface_expr: Box<DamlExpr<'a>>,
+    ) -> Self {
+        Self {
+            required_interface,
+            requiring_interface,
+            contract_id_expr,
+            interface_expr,
+        }
+    }
+
+    pub fn required_interface(&self) -> &DamlTyConName<'a> {
+        &self.required_interface
+    }
+
+    pub fn requiring_interface(&self) -> &DamlTyConName<'a> {
+        &self.requiring_interface
+    }
+
+    pub fn contract_id_expr(&self) -> &DamlExpr<'a> {
+        self.contract_id_expr.as_ref()
+    }
+
+    pub fn interface_expr(&self) -> &DamlExpr<'a> {
+        self.interface_expr.as_ref()
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlUnsafeFromRequiredInterface<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_unsafe_from_required_interface(self);
+        self.required_interface.accept(visitor);
+        self.requiring_interface.accept(visitor);
+        self.contract_id_expr.accept(visitor);
+        self.interface_expr.accept(visitor);
+        visitor.post_visit_unsafe_from_required_interface(self);
+    }
+}
+
+/// A Daml expression which operates on an interface value (signatories, observers, view or template type rep).
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlInterfaceExpr<'a> {
+    interface: Box<DamlTyConName<'a>>,
+    expr: Box<DamlExpr<'a>>,
+}
+
+impl<'a> DamlInterfaceExpr<'a> {
+    pub fn new(interface: Box<DamlTyConName<'a>>, expr: Box<DamlExpr<'a>>) -> Self {
+        Self {
+            interface,
+            expr,
+        }
+    }
+
+    pub fn interface(&self) -> &DamlTyConName<'a> {
+        &self.interface
+    }
+
+    pub fn expr(&self) -> &DamlExpr<'a> {
+        self.expr.as_ref()
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlInterfaceExpr<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_interface_expr(self);
+        self.interface.accept(visitor);
+        self.expr.accept(visitor);
+        visitor.post_visit_interface_expr(self);
+    }
+}
+
+/// A Daml expression update effect exercise interface choice action.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlExerciseInterface<'a> {
+    interface: Box<DamlTyConName<'a>>,
+    choice: Cow<'a, str>,
+    cid: Box<DamlExpr<'a>>,
+    arg: Box<DamlExpr<'a>>,
+}
+
+impl<'a> DamlExerciseInterface<'a> {
+    pub fn new(
+        interface: Box<DamlTyConName<'a>>,
+        choice: Cow<'a, str>,
+        cid: Box<DamlExpr<'a>>,
+        arg: Box<DamlExpr<'a>>,
+    ) -> Self {
+        Self {
+            interface,
+            choice,
+            cid,
+            arg,
+        }
+    }
+
+    pub fn interface(&self) -> &DamlTyConName<'a> {
+        &self.interface
+    }
+
+    pub fn choice(&self) -> &str {
+        &self.choice
+    }
+
+    pub fn cid(&self) -> &DamlExpr<'a> {
+        self.cid.as_ref()
+    }
+
+    pub fn arg(&self) -> &DamlExpr<'a> {
+        self.arg.as_ref()
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlExerciseInterface<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_exercise_interface(self);
+        self.interface.accept(visitor);
+        self.cid.accept(visitor);
+        self.arg.accept(visitor);
+        visitor.post_visit_exercise_interface(self);
+    }
+}
+
+/// A Daml expression update effect fetch by interface action.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlFetchInterface<'a> {
+    interface: Box<DamlTyConName<'a>>,
+    cid: Box<DamlExpr<'a>>,
+}
+
+impl<'a> DamlFetchInterface<'a> {
+    pub fn new(interface: Box<DamlTyConName<'a>>, cid: Box<DamlExpr<'a>>) -> Self {
+        Self {
+            interface,
+            cid,
+        }
+    }
+
+    pub fn interface(&self) -> &DamlTyConName<'a> {
+        &self.interface
+    }
+
+    pub fn cid(&self) -> &DamlExpr<'a> {
+        self.cid.as_ref()
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlFetchInterface<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_fetch_interface(self);
+        self.interface.accept(visitor);
+        self.cid.accept(visitor);
+        visitor.post_visit_fetch_interface(self);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::element::visitor::DamlElementVisitor;
+#[cfg(feature = "full")]
+use crate::element::DamlExpr;
+use crate::element::{DamlChoice, DamlTyConName, DamlType, DamlVisitableElement};
+use bounded_static::ToStatic;
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// A Daml interface.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlInterface<'a> {
+    name: Cow<'a, str>,
+    package_id: Cow<'a, str>,
+    module_path: Vec<Cow<'a, str>>,
+    param: Cow<'a, str>,
+    choices: Vec<DamlChoice<'a>>,
+    methods: Vec<DamlInterfaceMethod<'a>>,
+    requires: Vec<DamlTyConName<'a>>,
+    view: DamlType<'a>,
+}
+
+impl<'a> DamlInterface<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: Cow<'a, str>,
+        package_id: Cow<'a, str>,
+        module_path: Vec<Cow<'a, str>>,
+        param: Cow<'a, str>,
+        choices: Vec<DamlChoice<'a>>,
+        methods: Vec<DamlInterfaceMethod<'a>>,
+        requires: Vec<DamlTyConName<'a>>,
+        view: DamlType<'a>,
+    ) -> Self {
+        Self {
+            name,
+            package_id,
+            module_path,
+            param,
+            choices,
+            methods,
+            requires,
+            view,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn package_id(&self) -> &str {
+        &self.package_id
+    }
+
+    pub fn module_path(&self) -> impl Iterator<Item = &str> {
+        self.module_path.iter().map(AsRef::as_ref)
+    }
+
+    pub fn param(&self) -> &str {
+        &self.param
+    }
+
+    pub fn choices(&self) -> &[DamlChoice<'a>] {
+        &self.choices
+    }
+
+    pub fn methods(&self) -> &[DamlInterfaceMethod<'a>] {
+        &self.methods
+    }
+
+    pub fn requires(&self) -> &[DamlTyConName<'a>] {
+        &self.requires
+    }
+
+    pub fn view(&self) -> &DamlType<'a> {
+        &self.view
+    }
+
+    /// The name of this interface.
+    ///
+    /// This is a clone of a `Cow<str>` which is cheap for the borrowed case used within the library.
+    #[doc(hidden)]
+    pub(crate) fn name_clone(&self) -> Cow<'a, str> {
+        self.name.clone()
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlInterface<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_interface(self);
+        self.choices.iter().for_each(|choice| choice.accept(visitor));
+        self.methods.iter().for_each(|method| method.accept(visitor));
+        self.requires.iter().for_each(|required| required.accept(visitor));
+        self.view.accept(visitor);
+        visitor.post_visit_interface(self);
+    }
+}
+
+/// A Daml interface method declaration.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlInterfaceMethod<'a> {
+    name: Cow<'a, str>,
+    ty: DamlType<'a>,
+}
+
+impl<'a> DamlInterfaceMethod<'a> {
+    pub fn new(name: Cow<'a, str>, ty: DamlType<'a>) -> Self {
+        Self {
+            name,
+            ty,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn ty(&self) -> &DamlType<'a> {
+        &self.ty
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlInterfaceMethod<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_interface_method(self);
+        self.ty.accept(visitor);
+        visitor.post_visit_interface_method(self);
+    }
+}
+
+/// A Daml interface instance, the implementation of an interface by a template.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlInterfaceInstance<'a> {
+    interface: DamlTyConName<'a>,
+    methods: Vec<DamlInterfaceInstanceMethod<'a>>,
+    #[cfg(feature = "full")]
+    view: DamlExpr<'a>,
+}
+
+impl<'a> DamlInterfaceInstance<'a> {
+    pub fn new(
+        interface: DamlTyConName<'a>,
+        methods: Vec<DamlInterfaceInstanceMethod<'a>>,
+        #[cfg(feature = "full")] view: DamlExpr<'a>,
+    ) -> Self {
+        Self {
+            interface,
+            methods,
+            #[cfg(feature = "full")]
+            view,
+        }
+    }
+
+    pub fn interface(&self) -> &DamlTyConName<'a> {
+        &self.interface
+    }
+
+    pub fn methods(&self) -> &[DamlInterfaceInstanceMethod<'a>] {
+        &self.methods
+    }
+
+    #[cfg(feature = "full")]
+    pub fn view(&self) -> &DamlExpr<'a> {
+        &self.view
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlInterfaceInstance<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_interface_instance(self);
+        self.interface.accept(visitor);
+        self.methods.iter().for_each(|method| method.accept(visitor));
+        #[cfg(feature = "full")]
+        self.view.accept(visitor);
+        visitor.post_visit_interface_instance(self);
+    }
+}
+
+/// A Daml interface instance method implementation.
+#[derive(Debug, Serialize, Clone, ToStatic)]
+pub struct DamlInterfaceInstanceMethod<'a> {
+    name: Cow<'a, str>,
+    #[cfg(feature = "full")]
+    value: DamlExpr<'a>,
+}
+
+impl<'a> DamlInterfaceInstanceMethod<'a> {
+    pub fn new(name: Cow<'a, str>, #[cfg(feature = "full")] value: DamlExpr<'a>) -> Self {
+        Self {
+            name,
+            #[cfg(feature = "full")]
+            value,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[cfg(feature = "full")]
+    pub fn value(&self) -> &DamlExpr<'a> {
+        &self.value
+    }
+}
+
+impl<'a> DamlVisitableElement<'a> for DamlInterfaceInstanceMethod<'a> {
+    fn accept(&'a self, visitor: &'a mut impl DamlElementVisitor) {
+        visitor.pre_visit_interface_instance_method(self);
+        #[cfg(feature = "full")]
+        self.value.accept(visitor);
+        visitor.post_visit_interface_instance_method(self);
+    }
+}
@@ -1,9 +1,9 @@
 use crate::element::daml_data::DamlData;
 use crate::element::visitor::DamlElementVisitor;
-#[cfg(feature = "full")]
-use crate::element::DamlDefValue;
-use crate::element::DamlVisitableElement;
 use crate::element::{serialize, DamlType, DamlTypeVarWithKind};
+#[cfg(feature = "full")]
+use crate::element::{DamlDefException, DamlDefValue};
+use crate::element::{DamlInterface, DamlVisitableElement};
 use bounded_static::ToStatic;
 use itertools::Itertools;
 use serde::Serialize;
@@ -23,8 +23,12 @@ pub struct DamlModule<'a> {
     child_modules: HashMap<Cow<'a, str>, DamlModule<'a>>,
     #[serde(serialize_with = "serialize::serialize_map")]
     data_types: HashMap<Cow<'a, str>, DamlData<'a>>,
+    #[serde(serialize_with = "serialize::serialize_map")]
+    interfaces: HashMap<Cow<'a, str>, DamlInterface<'a>>,
     #[cfg(feature = "full")]
     values: HashMap<Cow<'a, str>, DamlDefValue<'a>>,
+    #[cfg(feature = "full")]
+    exceptions: HashMap<Cow<'a, str>, DamlDefException<'a>>,
 }
 
 impl<'a> DamlModule<'a> {
@@ -39,7 +43,9 @@ impl<'a> DamlModule<'a> {
         flags: DamlFeatureFlags,
         synonyms: Vec<DamlDefTypeSyn<'a>>,
         data_types: HashMap<Cow<'a, str>, DamlData<'a>>,
+        interfaces: HashMap<Cow<'a, str>, DamlInterface<'a>>,
         #[cfg(feature = "full")] values: HashMap<Cow<'a, str>, DamlDefValue<'a>>,
+        #[cfg(feature = "full")] exceptions: HashMap<Cow<'a, str>, DamlDefException<'a>>,
     ) -> Self {
         Self {
             path,
@@ -47,8 +53,11 @@ impl<'a> DamlModule<'a> {
             synonyms,
             child_modules: HashMap::default(),
             data_types,
+            interfaces,
             #[cfg(feature = "full")]
             values,
+            #[cfg(feature = "full")]
+            exceptions,
         }
     }
 
@@ -58,7 +67,7 @@ impl<'a> DamlModule<'a> {
     }
 
     /// The `DamlDefTypeSyn` of the module.
-    pub fn synonyms(&self) -> &[DamlDefTypeSyn<'_>] {
+    pub fn synonyms(&self) -> &[DamlDefTypeSyn<'a>] {
         &self.synonyms
     }
 
@@ -77,12 +86,23 @@ impl<'a> DamlModule<'a> {
         self.data_types.values()
     }
 
+    /// The `DamlInterface` declared in the module.
+    pub fn interfaces(&self) -> impl Iterator<Item = &DamlInterface<'a>> {
+        self.interfaces.values()
+    }
+
     /// The `DamlDefValue` declared in the module.
     #[cfg(feature = "full")]
     pub fn values(&self) -> impl Iterator<Item = &DamlDefValue<'a>> {
         self.values.values()
     }
 
+    /// The `DamlDefException` declared in the module.
+    #[cfg(feature = "full")]
+    pub fn exceptions(&self) -> impl Iterator<Item = &DamlDefException<'a>> {
+        self.exceptions.values()
+    }
+
     /// Returns `true` if this is a root module, `false` otherwise.
     pub fn is_root(&self) -> bool {
         self.path.is_empty()
@@ -119,19 +139,113 @@ impl<'a> DamlModule<'a> {
         self.data_types.get(name.as_ref())
     }
 
+    /// Retrieve a [`DamlInterface`] by name or `None` if no such interface exists.
+    pub fn interface<S: AsRef<str>>(&self, name: S) -> Option<&DamlInterface<'a>> {
+        self.interfaces.get(name.as_ref())
+    }
+
     /// Retrieve a [`DamlDefValue`] by name or `None` if no such value exists.
     #[cfg(feature = "full")]
     pub fn value<S: AsRef<str>>(&self, name: S) -> Option<&DamlDefValue<'a>> {
         self.values.get(name.as_ref())
     }
 
+    /// Retrieve a [`DamlDefException`] by name or `None` if no such exception exists.
+    #[cfg(feature = "full")]
+    pub fn exception<S: AsRef<str>>(&self, name: S) -> Option<&DamlDefException<'a>> {
+        self.exceptions.get(name.as_ref())
+    }
+
+    /// Retrieve a mutable child [`DamlModule`] by name or `None` if no such module exists.
+    pub fn child_module_mut<S: AsRef<str>>(&mut self, name: S) -> Option<&mut DamlModule<'a>> {
+        self.child_modules.get_mut(name.as_ref())
+    }
+
+    /// Retrieve a mutable descendant [`DamlModule`] by relative path or `None` if no such module exists.
+    pub fn child_module_path_mut<S: AsRef<str>>(&mut self, relative_path: &[S]) -> Option<&mut DamlModule<'a>> {
+        match relative_path {
+            [] => Some(self),
+            [head, tail @ ..] => self.child_module_mut(head.as_ref())?.child_module_path_mut(tail),
+        }
+    }
+
+    /// Remove the child [`DamlModule`] with `name`, returning it if it existed.
+    pub fn remove_child_module<S: AsRef<str>>(&mut self, name: S) -> Option<DamlModule<'a>> {
+        self.child_modules.remove(name.as_ref())
+    }
+
+    /// Set the `DamlFeatureFlags` of the module.
+    pub fn set_flags(&mut self, flags: DamlFeatureFlags) {
+        self.flags = flags;
+    }
+
+    /// A mutable reference to the `DamlDefTypeSyn` of the module.
+    pub fn synonyms_mut(&mut self) -> &mut Vec<DamlDefTypeSyn<'a>> {
+        &mut self.synonyms
+    }
+
+    /// Add a [`DamlData`] to the module, returning any existing data type with the same name.
+    pub fn insert_data_type(&mut self, data: DamlData<'a>) -> Option<DamlData<'a>> {
+        self.data_types.insert(data.name_clone(), data)
+    }
+
+    /// Remove the [`DamlData`] with `name`, returning it if it existed.
+    pub fn remove_data_type<S: AsRef<str>>(&mut self, name: S) -> Option<DamlData<'a>> {
+        self.data_types.remove(name.as_ref())
+    }
+
+    /// Add a [`DamlInterface`] to the module, returning any existing interface with the same name.
+    pub fn insert_interface(&mut self, interface: DamlInterface<'a>) -> Option<DamlInterface<'a>> {
+        self.interfaces.insert(interface.name_clone(), interface)
+    }
+
+    /// Remove the [`DamlInterface`] with `name`, returning it if it existed.
+    pub fn remove_interface<S: AsRef<str>>(&mut self, name: S) -> Option<DamlInterface<'a>> {
+        self.interfaces.remove(name.as_ref())
+    }
+
+    /// Add a [`DamlDefValue`] to the module, returning any existing value with the same name.
+    #[cfg(feature = "full")]
+    pub fn insert_value(&mut self, value: DamlDefValue<'a>) -> Option<DamlDefValue<'a>> {
+        self.values.insert(value.name_clone(), value)
+    }
+
+    /// Remove the [`DamlDefValue`] with `name`, returning it if it existed.
+    #[cfg(feature = "full")]
+    pub fn remove_value<S: AsRef<str>>(&mut self, name: S) -> Option<DamlDefValue<'a>> {
+        self.values.remove(name.as_ref())
+    }
+
+    /// Add a [`DamlDefException`] to the module, returning any existing exception with the same name.
+    #[cfg(feature = "full")]
+    pub fn insert_exception(&mut self, exception: DamlDefException<'a>) -> Option<DamlDefException<'a>> {
+        self.exceptions.insert(exception.name_clone(), exception)
+    }
+
+    /// Remove the [`DamlDefException`] with `name`, returning it if it existed.
+    #[cfg(feature = "full")]
+    pub fn remove_exception<S: AsRef<str>>(&mut self, name: S) -> Option<DamlDefException<'a>> {
+        self.exceptions.remove(name.as_ref())
+    }
+
     /// Retrieve or create a child [`DamlModule`] with `name`.
     #[doc(hidden)]
-    pub(crate) fn child_module_or_new(&mut self, name: &'a str) -> &mut Self {
+    pub(crate) fn child_module_or_new(&mut self, name: Cow<'a, str>) -> &mut Self {
         let path = &self.path;
-        self.child_modules.entry(Cow::from(name)).or_insert_with(|| {
-            DamlModule::new_empty(path.iter().map(ToOwned::to_owned).chain(once(Cow::from(name))).collect())
-        })
+        self.child_modules
+            .entry(name.clone())
+            .or_insert_with(|| DamlModule::new_empty(path.iter().map(ToOwned::to_owned).chain(once(name)).collect()))
+    }
+
+    /// Add `module` beneath this module at the position given by its path, creating intermediate modules as required.
+    ///
+    /// The path of `module` must be prefixed by the path of this module.
+    #[doc(hidden)]
+    pub(crate) fn insert_descendant(&mut self, module: Self) {
+        debug_assert!(module.path.starts_with(&self.path));
+        let relative_path = module.path[self.path.len()..].to_vec();
+        let node = relative_path.into_iter().fold(self, DamlModule::child_module_or_new);
+        node.take_from(module);
     }
 
     /// Populate this module with data taken from another module.
@@ -143,10 +257,12 @@ impl<'a> DamlModule<'a> {
         debug_assert_eq!(self.path, other.path);
         self.flags = other.flags;
         self.data_types = other.data_types;
+        self.interfaces = other.interfaces;
         self.synonyms = other.synonyms;
         #[cfg(feature = "full")]
         {
             self.values = other.values;
+            self.exceptions = other.exceptions;
         }
     }
 
@@ -158,8 +274,11 @@ impl<'a> DamlModule<'a> {
             synonyms: Vec::default(),
             child_modules: HashMap::default(),
             data_types: HashMap::default(),
+            interfaces: HashMap::default(),
             #[cfg(feature = "full")]
             values: HashMap::default(),
+            #[cfg(feature = "full")]
+            exceptions: HashMap::default(),
         }
     }
 }
@@ -170,13 +289,18 @@ impl<'a> DamlVisitableElement<'a> for DamlModule<'a> {
         self.synonyms.iter().for_each(|syn| syn.accept(visitor));
         if visitor.sort_elements() {
             self.data_types.values().sorted_by_key(|ty| ty.name()).for_each(|data| data.accept(visitor));
+            self.interfaces.values().sorted_by_key(|i| i.name()).for_each(|interface| interface.accept(visitor));
             self.child_modules.values().sorted_by_key(|&m| m.path.clone()).for_each(|module| module.accept(visitor));
         } else {
             self.data_types.values().for_each(|data| data.accept(visitor));
+            self.interfaces.values().for_each(|interface| interface.accept(visitor));
             self.child_modules.values().for_each(|module| module.accept(visitor));
         }
         #[cfg(feature = "full")]
-        self.values.values().for_each(|value| value.accept(visitor));
+        {
+            self.values.values().for_each(|value| value.accept(visitor));
+            self.exceptions.values().for_each(|exception| exception.accept(visitor));
+        }
         visitor.post_visit_module(self);
     }
 }
@@ -200,12 +324,12 @@ impl<'a> DamlDefTypeSyn<'a> {
     }
 
     ///
-    pub fn params(&self) -> &[DamlTypeVarWithKind<'_>] {
+    pub fn params(&self) -> &[DamlTypeVarWithKind<'a>] {
         &self.params
     }
 
     /// Type of the defined type synonym.
-    pub fn ty(&self) -> &DamlType<'_> {
+    pub fn ty(&self) -> &DamlType<'a> {
         &self.ty
     }
 
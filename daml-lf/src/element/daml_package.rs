@@ -54,6 +54,37 @@ impl<'a> DamlPackage<'a> {
     pub const fn root_module(&self) -> &DamlModule<'a> {
         &self.root_module
     }
+
+    /// Set the package name.
+    pub fn set_name(&mut self, name: impl Into<Cow<'a, str>>) {
+        self.name = name.into();
+    }
+
+    /// Set the package version.
+    pub fn set_version(&mut self, version: Option<Cow<'a, str>>) {
+        self.version = version;
+    }
+
+    /// A mutable reference to the root [`DamlModule`] of the package.
+    pub fn root_module_mut(&mut self) -> &mut DamlModule<'a> {
+        &mut self.root_module
+    }
+
+    /// Add a [`DamlModule`] to the package at the position given by its path.
+    ///
+    /// Any intermediate modules are created as required.  If a module already exists at the given path then its
+    /// content is replaced but its child modules are retained.
+    pub fn insert_module(&mut self, module: DamlModule<'a>) {
+        self.root_module.insert_descendant(module);
+    }
+
+    /// Remove the [`DamlModule`] with the given `path`, and all of its child modules, returning it if it existed.
+    pub fn remove_module<S: AsRef<str>>(&mut self, path: &[S]) -> Option<DamlModule<'a>> {
+        match path {
+            [] => None,
+            [parent @ .., name] => self.root_module.child_module_path_mut(parent)?.remove_child_module(name),
+        }
+    }
 }
 
 impl<'a> DamlVisitableElement<'a> for DamlPackage<'a> {
@@ -47,7 +47,7 @@ pub enum DamlType<'a> {
     /// A natural number.
     Nat(u8),
     /// A function.
-    Arrow,
+    Arrow(Vec<DamlType<'a>>),
     /// Any type.
     Any,
     /// A type rep.
@@ -59,9 +59,9 @@ pub enum DamlType<'a> {
     /// An exception.
     AnyException,
     /// An update effect.
-    Update,
+    Update(Vec<DamlType<'a>>),
     /// A scenario effect.
-    Scenario,
+    Scenario(Vec<DamlType<'a>>),
     /// Universal qualifier.
     Forall(DamlForall<'a>),
     /// A struct type.
@@ -86,12 +86,12 @@ impl<'a> DamlType<'a> {
             DamlType::TextMap(_) => "DamlTextMap",
             DamlType::GenMap(_) => "DamlGenMap",
             DamlType::Optional(_) => "DamlOptional",
-            DamlType::Update => "None (Update)",
-            DamlType::Scenario => "None (Scenario)",
+            DamlType::Update(_) => "None (Update)",
+            DamlType::Scenario(_) => "None (Scenario)",
             DamlType::TyCon(_) => "None (TyCon)",
             DamlType::BoxedTyCon(_) => "None (BoxedTyCon)",
             DamlType::Var(_) => "None (Var)",
-            DamlType::Arrow => "None (Arrow)",
+            DamlType::Arrow(_) => "None (Arrow)",
             DamlType::Any => "None (Any)",
             DamlType::TypeRep => "None (TypeRep)",
             DamlType::RoundingMode => "None (RoundingMode)",
@@ -115,7 +115,10 @@ impl<'a> DamlType<'a> {
             | DamlType::Optional(args)
             | DamlType::TextMap(args)
             | DamlType::GenMap(args)
-            | DamlType::Numeric(args) => args.iter().any(|arg| arg.contains_type_var(type_var)),
+            | DamlType::Numeric(args)
+            | DamlType::Update(args)
+            | DamlType::Scenario(args)
+            | DamlType::Arrow(args) => args.iter().any(|arg| arg.contains_type_var(type_var)),
             DamlType::ContractId(inner) => inner.as_ref().map_or(false, |ty| ty.contains_type_var(type_var)),
             DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) =>
                 tycon.type_arguments.iter().any(|f| f.contains_type_var(type_var)),
@@ -129,9 +132,6 @@ impl<'a> DamlType<'a> {
             | DamlType::Bool
             | DamlType::Unit
             | DamlType::Date
-            | DamlType::Update
-            | DamlType::Scenario
-            | DamlType::Arrow
             | DamlType::Any
             | DamlType::TypeRep
             | DamlType::Bignumeric
@@ -166,7 +166,10 @@ impl<'a> DamlVisitableElement<'a> for DamlType<'a> {
             | DamlType::Optional(args)
             | DamlType::TextMap(args)
             | DamlType::GenMap(args)
-            | DamlType::Numeric(args) => args.iter().for_each(|arg| arg.accept(visitor)),
+            | DamlType::Numeric(args)
+            | DamlType::Update(args)
+            | DamlType::Scenario(args)
+            | DamlType::Arrow(args) => args.iter().for_each(|arg| arg.accept(visitor)),
             DamlType::ContractId(tycon) => tycon.as_ref().map_or_else(|| {}, |dr| dr.accept(visitor)),
             DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) => tycon.accept(visitor),
             DamlType::Forall(forall) => forall.accept(visitor),
@@ -179,9 +182,6 @@ impl<'a> DamlVisitableElement<'a> for DamlType<'a> {
             | DamlType::Bool
             | DamlType::Unit
             | DamlType::Date
-            | DamlType::Update
-            | DamlType::Scenario
-            | DamlType::Arrow
             | DamlType::Any
             | DamlType::TypeRep
             | DamlType::Bignumeric
@@ -215,7 +215,7 @@ impl<'a> DamlSyn<'a> {
         &self.tysyn
     }
 
-    pub fn args(&self) -> &[DamlType<'_>] {
+    pub fn args(&self) -> &[DamlType<'a>] {
         &self.args
     }
 }
@@ -242,7 +242,7 @@ impl<'a> DamlStruct<'a> {
         }
     }
 
-    pub fn fields(&self) -> &[DamlField<'_>] {
+    pub fn fields(&self) -> &[DamlField<'a>] {
         &self.fields
     }
 }
@@ -270,7 +270,7 @@ impl<'a> DamlForall<'a> {
         }
     }
 
-    pub fn vars(&self) -> &[DamlTypeVarWithKind<'_>] {
+    pub fn vars(&self) -> &[DamlTypeVarWithKind<'a>] {
         &self.vars
     }
 
@@ -320,11 +320,11 @@ impl<'a> DamlTyCon<'a> {
         DamlTyCon::new(Box::new(DamlTyConName::new_absolute(package_id, module, entity)), type_arguments)
     }
 
-    pub fn type_arguments(&self) -> &[DamlType<'_>] {
+    pub fn type_arguments(&self) -> &[DamlType<'a>] {
         &self.type_arguments
     }
 
-    pub fn tycon(&self) -> &DamlTyConName<'_> {
+    pub fn tycon(&self) -> &DamlTyConName<'a> {
         &self.tycon
     }
 }
@@ -2,18 +2,22 @@
 use crate::element::daml_expr::DamlScenario;
 #[cfg(feature = "full")]
 use crate::element::{
-    DamlAbs, DamlApp, DamlBinding, DamlBlock, DamlBuiltinFunction, DamlCase, DamlCaseAlt, DamlCaseAltCons,
-    DamlCaseAltEnum, DamlCaseAltOptionalSome, DamlCaseAltSum, DamlCaseAltVariant, DamlCommit, DamlCons, DamlCreate,
-    DamlDefValue, DamlEnumCon, DamlExercise, DamlExerciseByKey, DamlExpr, DamlFetch, DamlFieldWithExpr, DamlFromAny,
-    DamlFromAnyException, DamlLocalValueName, DamlNonLocalValueName, DamlOptionalSome, DamlPrimCon, DamlPrimLit,
-    DamlPure, DamlRecCon, DamlRecProj, DamlRecUpd, DamlRetrieveByKey, DamlScenarioEmbedExpr, DamlStructCon,
-    DamlStructProj, DamlStructUpd, DamlThrow, DamlToAny, DamlToAnyException, DamlTryCatch, DamlTyAbs, DamlTyApp,
-    DamlUpdate, DamlUpdateEmbedExpr, DamlValueName, DamlVarWithType, DamlVariantCon, RoundingMode,
+    DamlAbs, DamlApp, DamlBinding, DamlBlock, DamlBuiltinFunction, DamlCallInterface, DamlCase, DamlCaseAlt,
+    DamlCaseAltCons, DamlCaseAltEnum, DamlCaseAltOptionalSome, DamlCaseAltSum, DamlCaseAltVariant, DamlCommit,
+    DamlCons, DamlCreate, DamlDefException, DamlDefValue, DamlEnumCon, DamlExercise, DamlExerciseByKey,
+    DamlExerciseInterface, DamlExpr, DamlFetch, DamlFetchInterface, DamlFieldWithExpr, DamlFromAny,
+    DamlFromAnyException, DamlInterfaceCast, DamlInterfaceExpr, DamlLocalValueName, DamlNonLocalValueName,
+    DamlOptionalSome, DamlPrimCon, DamlPrimLit, DamlPure, DamlRecCon, DamlRecProj, DamlRecUpd,
+    DamlRequiredInterfaceCast, DamlRetrieveByKey, DamlScenarioEmbedExpr, DamlStructCon, DamlStructProj, DamlStructUpd,
+    DamlThrow, DamlToAny, DamlToAnyException, DamlTryCatch, DamlTyAbs, DamlTyApp, DamlUnsafeFromInterface,
+    DamlUnsafeFromRequiredInterface, DamlUpdate, DamlUpdateEmbedExpr, DamlValueName, DamlVarWithType, DamlVariantCon,
+    RoundingMode,
 };
 use crate::element::{
     DamlAbsoluteTyCon, DamlArchive, DamlArrow, DamlChoice, DamlData, DamlDefKey, DamlDefTypeSyn, DamlEnum, DamlField,
-    DamlForall, DamlKind, DamlLocalTyCon, DamlModule, DamlNonLocalTyCon, DamlPackage, DamlRecord, DamlStruct, DamlSyn,
-    DamlTemplate, DamlTyCon, DamlTyConName, DamlType, DamlTypeVarWithKind, DamlVar, DamlVariant,
+    DamlForall, DamlInterface, DamlInterfaceInstance, DamlInterfaceInstanceMethod, DamlInterfaceMethod, DamlKind,
+    DamlLocalTyCon, DamlModule, DamlNonLocalTyCon, DamlPackage, DamlRecord, DamlStruct, DamlSyn, DamlTemplate,
+    DamlTyCon, DamlTyConName, DamlType, DamlTypeVarWithKind, DamlVar, DamlVariant,
 };
 
 /// A Daml [element](`crate::element`) that can be visited by a [`DamlElementVisitor`].
@@ -107,12 +111,24 @@ pub trait DamlElementVisitor {
     fn post_visit_absolute_tycon<'a>(&mut self, absolute_tycon: &'a DamlAbsoluteTyCon<'a>) {}
     fn pre_visit_def_key<'a>(&mut self, def_key: &DamlDefKey<'a>) {}
     fn post_visit_def_key<'a>(&mut self, def_key: &DamlDefKey<'a>) {}
+    fn pre_visit_interface<'a>(&mut self, interface: &'a DamlInterface<'a>) {}
+    fn post_visit_interface<'a>(&mut self, interface: &'a DamlInterface<'a>) {}
+    fn pre_visit_interface_method<'a>(&mut self, interface_method: &'a DamlInterfaceMethod<'a>) {}
+    fn post_visit_interface_method<'a>(&mut self, interface_method: &'a DamlInterfaceMethod<'a>) {}
+    fn pre_visit_interface_instance<'a>(&mut self, interface_instance: &'a DamlInterfaceInstance<'a>) {}
+    fn post_visit_interface_instance<'a>(&mut self, interface_instance: &'a DamlInterfaceInstance<'a>) {}
+    fn pre_visit_interface_instance_method<'a>(&mut self, instance_method: &'a DamlInterfaceInstanceMethod<'a>) {}
+    fn post_visit_interface_instance_method<'a>(&mut self, instance_method: &'a DamlInterfaceInstanceMethod<'a>) {}
 
     #[cfg(feature = "full")]
     fn pre_visit_def_value<'a>(&mut self, def_value: &'a DamlDefValue<'a>) {}
     #[cfg(feature = "full")]
     fn post_visit_def_value<'a>(&mut self, def_value: &'a DamlDefValue<'a>) {}
     #[cfg(feature = "full")]
+    fn pre_visit_def_exception<'a>(&mut self, def_exception: &'a DamlDefException<'a>) {}
+    #[cfg(feature = "full")]
+    fn post_visit_def_exception<'a>(&mut self, def_exception: &'a DamlDefException<'a>) {}
+    #[cfg(feature = "full")]
     fn pre_visit_expr<'a>(&mut self, expr: &'a DamlExpr<'a>) {}
     #[cfg(feature = "full")]
     fn post_visit_expr<'a>(&mut self, expr: &'a DamlExpr<'a>) {}
@@ -313,4 +329,37 @@ pub trait DamlElementVisitor {
     fn pre_visit_throw<'a>(&mut self, throw: &DamlThrow<'a>) {}
     #[cfg(feature = "full")]
     fn post_visit_throw<'a>(&mut self, throw: &DamlThrow<'a>) {}
+
+    #[cfg(feature = "full")]
+    fn pre_visit_interface_cast<'a>(&mut self, interface_cast: &DamlInterfaceCast<'a>) {}
+    #[cfg(feature = "full")]
+    fn post_visit_interface_cast<'a>(&mut self, interface_cast: &DamlInterfaceCast<'a>) {}
+    #[cfg(feature = "full")]
+    fn pre_visit_unsafe_from_interface<'a>(&mut self, unsafe_from_interface: &DamlUnsafeFromInterface<'a>) {}
+    #[cfg(feature = "full")]
+    fn post_visit_unsafe_from_interface<'a>(&mut self, unsafe_from_interface: &DamlUnsafeFromInterface<'a>) {}
+    #[cfg(feature = "full")]
+    fn pre_visit_call_interface<'a>(&mut self, call_interface: &DamlCallInterface<'a>) {}
+    #[cfg(feature = "full")]
+    fn post_visit_call_interface<'a>(&mut self, call_interface: &DamlCallInterface<'a>) {}
+    #[cfg(feature = "full")]
+    fn pre_visit_required_interface_cast<'a>(&mut self, required_interface_cast: &DamlRequiredInterfaceCast<'a>) {}
+    #[cfg(feature = "full")]
+    fn post_visit_required_interface_cast<'a>(&mut self, required_interface_cast: &DamlRequiredInterfaceCast<'a>) {}
+    #[cfg(feature = "full")]
+    fn pre_visit_unsafe_from_required_interface<'a>(&mut self, unsafe_from: &DamlUnsafeFromRequiredInterface<'a>) {}
+    #[cfg(feature = "full")]
+    fn post_visit_unsafe_from_required_interface<'a>(&mut self, unsafe_from: &DamlUnsafeFromRequiredInterface<'a>) {}
+    #[cfg(feature = "full")]
+    fn pre_visit_interface_expr<'a>(&mut self, interface_expr: &DamlInterfaceExpr<'a>) {}
+    #[cfg(feature = "full")]
+    fn post_visit_interface_expr<'a>(&mut self, interface_expr: &DamlInterfaceExpr<'a>) {}
+    #[cfg(feature = "full")]
+    fn pre_visit_exercise_interface<'a>(&mut self, exercise_interface: &DamlExerciseInterface<'a>) {}
+    #[cfg(feature = "full")]
+    fn post_visit_exercise_interface<'a>(&mut self, exercise_interface: &DamlExerciseInterface<'a>) {}
+    #[cfg(feature = "full")]
+    fn pre_visit_fetch_interface<'a>(&mut self, fetch_interface: &DamlFetchInterface<'a>) {}
+    #[cfg(feature = "full")]
+    fn post_visit_fetch_interface<'a>(&mut self, fetch_interface: &DamlFetchInterface<'a>) {}
 }
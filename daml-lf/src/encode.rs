@@ -0,0 +1,582 @@
+mod expr_encoder;
+mod interner;
+
+use crate::element::{
+    DamlChoice, DamlData, DamlDefException, DamlDefKey, DamlDefTypeSyn, DamlDefValue, DamlField, DamlInterface,
+    DamlInterfaceInstance, DamlKind, DamlModule, DamlPackage, DamlTemplate, DamlTyCon, DamlTyConName, DamlType,
+    DamlTypeVarWithKind,
+};
+use crate::encode::interner::PackageInterner;
+use crate::error::{DamlLfConvertError, DamlLfConvertResult};
+use crate::lf_protobuf::com::daml::daml_lf_1::def_data_type::{DataCons, EnumConstructors, Fields};
+use crate::lf_protobuf::com::daml::daml_lf_1::def_template::{def_key, DefKey, Implements};
+use crate::lf_protobuf::com::daml::daml_lf_1::interface_instance_body::InterfaceInstanceMethod;
+use crate::lf_protobuf::com::daml::daml_lf_1::{
+    def_data_type, def_template, def_type_syn, def_value, field_with_type, kind, module, module_ref, package_ref,
+    r#type, template_choice, type_con_name, type_syn_name, type_var_with_kind, var_with_type, DefDataType,
+    DefException, DefInterface, DefTemplate, DefTypeSyn, DefValue, DottedName, FeatureFlags, FieldWithType,
+    InterfaceInstanceBody, InterfaceMethod, Kind, Module, ModuleRef, Package, PackageMetadata, PackageRef, PrimType,
+    TemplateChoice, Type, TypeConName, TypeSynName, TypeVarWithKind, Unit, VarWithType,
+};
+use crate::{DamlLfArchivePayload, DamlLfPackage, LanguageFeatureVersion, LanguageVersion};
+use itertools::Itertools;
+
+/// Encode a [`DamlPackage`] as a [`DamlLfArchivePayload`].
+///
+/// Strings and dotted names are interned, or written literally, as required by the language version of the package.
+/// Modules, data types, interfaces, values and exceptions are emitted in name order such that the encoding of a given
+/// package is deterministic.
+pub fn encode_package(package: &DamlPackage<'_>) -> DamlLfConvertResult<DamlLfArchivePayload> {
+    let mut encoder = PackageEncoder::new(package.package_id(), package.language_version());
+    let modules = encodable_modules(package.root_module())
+        .into_iter()
+        .map(|module| encoder.encode_module(module))
+        .collect::<DamlLfConvertResult<_>>()?;
+    let metadata = if encoder.supports(&LanguageFeatureVersion::PACKAGE_METADATA) {
+        let version = package.version().ok_or_else(|| {
+            DamlLfConvertError::UnencodableElement(format!(
+                "package {} has no version which is required by Daml LF {}",
+                package.name(),
+                package.language_version()
+            ))
+        })?;
+        Some(PackageMetadata {
+            name_interned_str: encoder.interner.intern_string(package.name()),
+            version_interned_str: encoder.interner.intern_string(version),
+        })
+    } else {
+        None
+    };
+    let (interned_strings, interned_dotted_names) = encoder.interner.into_tables();
+    Ok(DamlLfArchivePayload::new(
+        package.language_version(),
+        DamlLfPackage::V1(Package {
+            modules,
+            interned_strings,
+            interned_dotted_names,
+            metadata,
+            interned_types: vec![],
+        }),
+    ))
+}
+
+/// Encode the elements of a single package.
+struct PackageEncoder<'e> {
+    package_id: &'e str,
+    language_version: LanguageVersion,
+    interner: PackageInterner,
+}
+
+impl<'e> PackageEncoder<'e> {
+    fn new(package_id: &'e str, language_version: LanguageVersion) -> Self {
+        Self {
+            package_id,
+            language_version,
+            interner: PackageInterner::default(),
+        }
+    }
+
+    fn supports(&self, feature: &LanguageFeatureVersion) -> bool {
+        self.language_version.supports_feature(feature)
+    }
+
+    /// Encode a string as either `interned` or `literal` depending on the language version.
+    fn string<T>(&mut self, s: &str, literal: impl FnOnce(String) -> T, interned: impl FnOnce(i32) -> T) -> T {
+        if self.supports(&LanguageFeatureVersion::INTERNED_STRINGS) {
+            interned(self.interner.intern_string(s))
+        } else {
+            literal(s.to_owned())
+        }
+    }
+
+    /// Encode a dotted name as either `interned` or `literal` depending on the language version.
+    fn dotted<T, S: AsRef<str>>(
+        &mut self,
+        segments: &[S],
+        literal: impl FnOnce(DottedName) -> T,
+        interned: impl FnOnce(i32) -> T,
+    ) -> T {
+        if self.supports(&LanguageFeatureVersion::INTERNED_DOTTED_NAMES) {
+            interned(self.interner.intern_dotted(segments))
+        } else {
+            literal(DottedName {
+                segments: segments.iter().map(|s| s.as_ref().to_owned()).collect(),
+            })
+        }
+    }
+
+    /// Encode a dotted name for fields which hold both the literal and interned forms.
+    fn dotted_pair<S: AsRef<str>>(&mut self, segments: &[S]) -> (Vec<String>, i32) {
+        self.dotted(segments, |dn| (dn.segments, 0), |i| (vec![], i))
+    }
+
+    fn encode_module(&mut self, module: &DamlModule<'_>) -> DamlLfConvertResult<Module> {
+        if module.interfaces().next().is_some() && !self.supports(&LanguageFeatureVersion::INTERFACES) {
+            return Err(DamlLfConvertError::UnsupportedFeatureUsed(
+                self.language_version.to_string(),
+                LanguageFeatureVersion::INTERFACES.name.to_string(),
+                LanguageFeatureVersion::INTERFACES.min_version.to_string(),
+            ));
+        }
+        if module.exceptions().next().is_some() && !self.supports(&LanguageFeatureVersion::EXCEPTIONS) {
+            return Err(DamlLfConvertError::UnsupportedFeatureUsed(
+                self.language_version.to_string(),
+                LanguageFeatureVersion::EXCEPTIONS.name.to_string(),
+                LanguageFeatureVersion::EXCEPTIONS.min_version.to_string(),
+            ));
+        }
+        let path: Vec<_> = module.path().collect();
+        let name = self.dotted(&path, module::Name::NameDname, module::Name::NameInternedDname);
+        let flags = module.flags();
+        let synonyms =
+            module.synonyms().iter().map(|syn| self.encode_def_type_syn(syn)).collect::<DamlLfConvertResult<_>>()?;
+        let mut data_types = vec![];
+        let mut templates = vec![];
+        for data in module.data_types().sorted_by(|a, b| a.name().cmp(b.name())) {
+            data_types.push(self.encode_data(data)?);
+            if let DamlData::Template(template) = data {
+                templates.push(self.encode_template(template)?);
+            }
+        }
+        let mut interfaces = vec![];
+        for interface in module.interfaces().sorted_by(|a, b| a.name().cmp(b.name())) {
+            data_types.push(self.encode_def_data_type(interface.name(), &[], false, DataCons::Interface(Unit {})));
+            interfaces.push(self.encode_interface(interface)?);
+        }
+        let values = module
+            .values()
+            .sorted_by(|a, b| a.name().cmp(b.name()))
+            .map(|value| self.encode_def_value(value))
+            .collect::<DamlLfConvertResult<_>>()?;
+        let exceptions = module
+            .exceptions()
+            .sorted_by(|a, b| a.name().cmp(b.name()))
+            .map(|exception| self.encode_def_exception(exception))
+            .collect::<DamlLfConvertResult<_>>()?;
+        Ok(Module {
+            flags: Some(FeatureFlags {
+                forbid_party_literals: flags.forbid_party_literals(),
+                dont_divulge_contract_ids_in_create_arguments: flags.dont_divulge_contract_ids_in_create_arguments(),
+                dont_disclose_non_consuming_choices_to_observers: flags
+                    .dont_disclose_non_consuming_choices_to_observers(),
+            }),
+            synonyms,
+            data_types,
+            values,
+            templates,
+            exceptions,
+            interfaces,
+            name: Some(name),
+        })
+    }
+
+    fn encode_def_type_syn(&mut self, syn: &DamlDefTypeSyn<'_>) -> DamlLfConvertResult<DefTypeSyn> {
+        let params = self.encode_type_vars(syn.params());
+        let name: Vec<_> = syn.name().collect();
+        Ok(DefTypeSyn {
+            params,
+            r#type: Some(self.encode_type(syn.ty())?),
+            location: None,
+            name: Some(self.dotted(&name, def_type_syn::Name::NameDname, def_type_syn::Name::NameInternedDname)),
+        })
+    }
+
+    fn encode_data(&mut self, data: &DamlData<'_>) -> DamlLfConvertResult<DefDataType> {
+        match data {
+            DamlData::Template(template) => {
+                let fields = self.encode_fields(template.fields())?;
+                Ok(self.encode_def_data_type(template.name(), &[], template.serializable(), DataCons::Record(fields)))
+            },
+            DamlData::Record(record) => {
+                let fields = self.encode_fields(record.fields())?;
+                Ok(self.encode_def_data_type(
+                    record.name(),
+                    record.type_params(),
+                    record.serializable(),
+                    DataCons::Record(fields),
+                ))
+            },
+            DamlData::Variant(variant) => {
+                let fields = self.encode_fields(variant.fields())?;
+                Ok(self.encode_def_data_type(
+                    variant.name(),
+                    variant.type_params(),
+                    variant.serializable(),
+                    DataCons::Variant(fields),
+                ))
+            },
+            DamlData::Enum(data_enum) => {
+                let constructors = if self.supports(&LanguageFeatureVersion::INTERNED_STRINGS) {
+                    EnumConstructors {
+                        constructors_str: vec![],
+                        constructors_interned_str: data_enum
+                            .constructors()
+                            .map(|constructor| self.interner.intern_string(constructor))
+                            .collect(),
+                    }
+                } else {
+                    EnumConstructors {
+                        constructors_str: data_enum.constructors().map(ToOwned::to_owned).collect(),
+                        constructors_interned_str: vec![],
+                    }
+                };
+                Ok(self.encode_def_data_type(
+                    data_enum.name(),
+                    data_enum.type_params(),
+                    data_enum.serializable(),
+                    DataCons::Enum(constructors),
+                ))
+            },
+        }
+    }
+
+    fn encode_def_data_type(
+        &mut self,
+        name: &str,
+        params: &[DamlTypeVarWithKind<'_>],
+        serializable: bool,
+        data_cons: DataCons,
+    ) -> DefDataType {
+        DefDataType {
+            params: self.encode_type_vars(params),
+            serializable,
+            location: None,
+            name: Some(self.dotted(&[name], def_data_type::Name::NameDname, def_data_type::Name::NameInternedDname)),
+            data_cons: Some(data_cons),
+        }
+    }
+
+    fn encode_fields(&mut self, fields: &[DamlField<'_>]) -> DamlLfConvertResult<Fields> {
+        Ok(Fields {
+            fields: fields.iter().map(|field| self.encode_field(field)).collect::<DamlLfConvertResult<_>>()?,
+        })
+    }
+
+    fn encode_field(&mut self, field: &DamlField<'_>) -> DamlLfConvertResult<FieldWithType> {
+        Ok(FieldWithType {
+            r#type: Some(self.encode_type(field.ty())?),
+            field: Some(self.string(
+                field.name(),
+                field_with_type::Field::FieldStr,
+                field_with_type::Field::FieldInternedStr,
+            )),
+        })
+    }
+
+    fn encode_template(&mut self, template: &DamlTemplate<'_>) -> DamlLfConvertResult<DefTemplate> {
+        Ok(DefTemplate {
+            tycon: Some(self.dotted(
+                &[template.name()],
+                def_template::Tycon::TyconDname,
+                def_template::Tycon::TyconInternedDname,
+            )),
+            param: Some(self.string(
+                template.param(),
+                def_template::Param::ParamStr,
+                def_template::Param::ParamInternedStr,
+            )),
+            precond: template.precond().map(|precond| self.encode_expr(precond)).transpose()?,
+            signatories: Some(self.encode_expr(template.signatories())?),
+            agreement: Some(self.encode_expr(template.agreement())?),
+            choices: template
+                .choices()
+                .iter()
+                .map(|choice| self.encode_choice(choice))
+                .collect::<DamlLfConvertResult<_>>()?,
+            observers: Some(self.encode_expr(template.observers())?),
+            location: None,
+            key: template.key().map(|key| self.encode_def_key(key)).transpose()?,
+            implements: template
+                .implements()
+                .iter()
+                .map(|instance| self.encode_interface_instance(instance))
+                .collect::<DamlLfConvertResult<_>>()?,
+        })
+    }
+
+    fn encode_choice(&mut self, choice: &DamlChoice<'_>) -> DamlLfConvertResult<TemplateChoice> {
+        let name = self.string(choice.name(), template_choice::Name::NameStr, template_choice::Name::NameInternedStr);
+        let self_binder = self.string(
+            choice.self_binder(),
+            template_choice::SelfBinder::SelfBinderStr,
+            template_choice::SelfBinder::SelfBinderInternedStr,
+        );
+        let arg_binder = VarWithType {
+            r#type: Some(self.encode_type(choice.argument_type())?),
+            var: Some(self.string(
+                choice.argument_binder(),
+                var_with_type::Var::VarStr,
+                var_with_type::Var::VarInternedStr,
+            )),
+        };
+        let observers = if self.supports(&LanguageFeatureVersion::CHOICE_OBSERVERS) {
+            Some(self.encode_expr(choice.observers())?)
+        } else {
+            None
+        };
+        Ok(TemplateChoice {
+            consuming: choice.consuming(),
+            controllers: Some(self.encode_expr(choice.controllers())?),
+            observers,
+            arg_binder: Some(arg_binder),
+            ret_type: Some(self.encode_type(choice.return_type())?),
+            update: Some(self.encode_expr(choice.update())?),
+            location: None,
+            name: Some(name),
+            self_binder: Some(self_binder),
+        })
+    }
+
+    fn encode_def_key(&mut self, key: &DamlDefKey<'_>) -> DamlLfConvertResult<DefKey> {
+        Ok(DefKey {
+            r#type: Some(self.encode_type(key.ty())?),
+            maintainers: Some(self.encode_expr(key.maintainers())?),
+            key_expr: Some(def_key::KeyExpr::ComplexKey(self.encode_expr(key.key_expr())?)),
+        })
+    }
+
+    fn encode_interface_instance(&mut self, instance: &DamlInterfaceInstance<'_>) -> DamlLfConvertResult<Implements> {
+        let interface = self.encode_tycon_name(instance.interface());
+        let methods = instance
+            .methods()
+            .iter()
+            .map(|method| {
+                Ok(InterfaceInstanceMethod {
+                    method_interned_name: self.interner.intern_string(method.name()),
+                    value: Some(self.encode_expr(method.value())?),
+                })
+            })
+            .collect::<DamlLfConvertResult<_>>()?;
+        Ok(Implements {
+            interface: Some(interface),
+            body: Some(InterfaceInstanceBody {
+                methods,
+                view: Some(self.encode_expr(instance.view())?),
+            }),
+            location: None,
+        })
+    }
+
+    fn encode_interface(&mut self, interface: &DamlInterface<'_>) -> DamlLfConvertResult<DefInterface> {
+        let tycon_interned_dname = self.interner.intern_dotted(&[interface.name()]);
+        let param_interned_str = self.interner.intern_string(interface.param());
+        let methods = interface
+            .methods()
+            .iter()
+            .map(|method| {
+                Ok(InterfaceMethod {
+                    location: None,
+                    method_interned_name: self.interner.intern_string(method.name()),
+                    r#type: Some(self.encode_type(method.ty())?),
+                })
+            })
+            .collect::<DamlLfConvertResult<_>>()?;
+        let choices =
+            interface.choices().iter().map(|choice| self.encode_choice(choice)).collect::<DamlLfConvertResult<_>>()?;
+        let requires = interface.requires().iter().map(|required| self.encode_tycon_name(required)).collect();
+        Ok(DefInterface {
+            location: None,
+            tycon_interned_dname,
+            methods,
+            param_interned_str,
+            choices,
+            requires,
+            view: Some(self.encode_type(interface.view())?),
+        })
+    }
+
+    fn encode_def_value(&mut self, value: &DamlDefValue<'_>) -> DamlLfConvertResult<DefValue> {
+        let (name_dname, name_interned_dname) = self.dotted_pair(&[value.name()]);
+        Ok(DefValue {
+            name_with_type: Some(def_value::NameWithType {
+                name_dname,
+                name_interned_dname,
+                r#type: Some(self.encode_type(value.ty())?),
+            }),
+            expr: Some(self.encode_expr(value.expr())?),
+            no_party_literals: value.no_party_literals(),
+            is_test: value.is_test(),
+            location: None,
+        })
+    }
+
+    fn encode_def_exception(&mut self, exception: &DamlDefException<'_>) -> DamlLfConvertResult<DefException> {
+        Ok(DefException {
+            name_interned_dname: self.interner.intern_dotted(&[exception.name()]),
+            location: None,
+            message: Some(self.encode_expr(exception.message())?),
+        })
+    }
+
+    fn encode_package_ref(&mut self, package_id: &str) -> PackageRef {
+        let sum = if package_id == self.package_id {
+            package_ref::Sum::Self_(Unit {})
+        } else if self.supports(&LanguageFeatureVersion::INTERNED_PACKAGE_ID) {
+            package_ref::Sum::PackageIdInternedStr(self.interner.intern_string(package_id))
+        } else {
+            package_ref::Sum::PackageIdStr(package_id.to_owned())
+        };
+        PackageRef {
+            sum: Some(sum),
+        }
+    }
+
+    fn encode_module_ref<'b>(&mut self, package_id: &str, module_path: impl Iterator<Item = &'b str>) -> ModuleRef {
+        let module_path: Vec<_> = module_path.collect();
+        ModuleRef {
+            package_ref: Some(self.encode_package_ref(package_id)),
+            module_name: Some(self.dotted(
+                &module_path,
+                module_ref::ModuleName::ModuleNameDname,
+                module_ref::ModuleName::ModuleNameInternedDname,
+            )),
+        }
+    }
+
+    fn encode_tycon_name(&mut self, tycon_name: &DamlTyConName<'_>) -> TypeConName {
+        TypeConName {
+            module: Some(self.encode_module_ref(tycon_name.package_id(), tycon_name.module_path())),
+            name: Some(self.dotted(
+                &[tycon_name.data_name()],
+                type_con_name::Name::NameDname,
+                type_con_name::Name::NameInternedDname,
+            )),
+        }
+    }
+
+    fn encode_tysyn_name(&mut self, tysyn_name: &DamlTyConName<'_>) -> TypeSynName {
+        TypeSynName {
+            module: Some(self.encode_module_ref(tysyn_name.package_id(), tysyn_name.module_path())),
+            name: Some(self.dotted(
+                &[tysyn_name.data_name()],
+                type_syn_name::Name::NameDname,
+                type_syn_name::Name::NameInternedDname,
+            )),
+        }
+    }
+
+    fn encode_tycon(&mut self, tycon: &DamlTyCon<'_>) -> DamlLfConvertResult<r#type::Con> {
+        Ok(r#type::Con {
+            tycon: Some(self.encode_tycon_name(tycon.tycon())),
+            args: self.encode_types(tycon.type_arguments())?,
+        })
+    }
+
+    fn encode_types(&mut self, types: &[DamlType<'_>]) -> DamlLfConvertResult<Vec<Type>> {
+        types.iter().map(|ty| self.encode_type(ty)).collect()
+    }
+
+    fn encode_type(&mut self, ty: &DamlType<'_>) -> DamlLfConvertResult<Type> {
+        let sum = match ty {
+            DamlType::ContractId(Some(inner)) => prim(PrimType::ContractId, vec![self.encode_type(inner)?]),
+            DamlType::ContractId(None) => prim(PrimType::ContractId, vec![]),
+            DamlType::Int64 => prim(PrimType::Int64, vec![]),
+            DamlType::Numeric(_) if !self.supports(&LanguageFeatureVersion::NUMERIC) => prim(PrimType::Decimal, vec![]),
+            DamlType::Numeric(args) => prim(PrimType::Numeric, self.encode_types(args)?),
+            DamlType::Text => prim(PrimType::Text, vec![]),
+            DamlType::Timestamp => prim(PrimType::Timestamp, vec![]),
+            DamlType::Party => prim(PrimType::Party, vec![]),
+            DamlType::Bool => prim(PrimType::Bool, vec![]),
+            DamlType::Unit => prim(PrimType::Unit, vec![]),
+            DamlType::Date => prim(PrimType::Date, vec![]),
+            DamlType::List(args) => prim(PrimType::List, self.encode_types(args)?),
+            DamlType::TextMap(args) => prim(PrimType::Textmap, self.encode_types(args)?),
+            DamlType::GenMap(args) => prim(PrimType::Genmap, self.encode_types(args)?),
+            DamlType::Optional(args) => prim(PrimType::Optional, self.encode_types(args)?),
+            DamlType::Update(args) => prim(PrimType::Update, self.encode_types(args)?),
+            DamlType::Scenario(args) => prim(PrimType::Scenario, self.encode_types(args)?),
+            DamlType::Arrow(args) => prim(PrimType::Arrow, self.encode_types(args)?),
+            DamlType::Any => prim(PrimType::Any, vec![]),
+            DamlType::TypeRep => prim(PrimType::TypeRep, vec![]),
+            DamlType::Bignumeric => prim(PrimType::Bignumeric, vec![]),
+            DamlType::RoundingMode => prim(PrimType::RoundingMode, vec![]),
+            DamlType::AnyException => prim(PrimType::AnyException, vec![]),
+            DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) => r#type::Sum::Con(self.encode_tycon(tycon)?),
+            DamlType::Var(var) => r#type::Sum::Var(r#type::Var {
+                args: self.encode_types(var.type_arguments())?,
+                var: Some(self.string(var.var(), r#type::var::Var::VarStr, r#type::var::Var::VarInternedStr)),
+            }),
+            DamlType::Nat(n) if *n > MAX_NAT => return Err(DamlLfConvertError::NatOutOfRange(i64::from(*n))),
+            DamlType::Nat(n) => r#type::Sum::Nat(i64::from(*n)),
+            DamlType::Forall(forall) => r#type::Sum::Forall(Box::new(r#type::Forall {
+                vars: self.encode_type_vars(forall.vars()),
+                body: Some(Box::new(self.encode_type(forall.body())?)),
+            })),
+            DamlType::Struct(tuple) => r#type::Sum::Struct(r#type::Struct {
+                fields: tuple
+                    .fields()
+                    .iter()
+                    .map(|field| self.encode_field(field))
+                    .collect::<DamlLfConvertResult<_>>()?,
+            }),
+            DamlType::Syn(syn) => r#type::Sum::Syn(r#type::Syn {
+                tysyn: Some(self.encode_tysyn_name(syn.tysyn())),
+                args: self.encode_types(syn.args())?,
+            }),
+        };
+        Ok(Type {
+            sum: Some(sum),
+        })
+    }
+
+    fn encode_type_vars(&mut self, vars: &[DamlTypeVarWithKind<'_>]) -> Vec<TypeVarWithKind> {
+        vars.iter().map(|var| self.encode_type_var(var)).collect()
+    }
+
+    fn encode_type_var(&mut self, var: &DamlTypeVarWithKind<'_>) -> TypeVarWithKind {
+        TypeVarWithKind {
+            kind: Some(encode_kind(var.kind())),
+            var: Some(self.string(var.var(), type_var_with_kind::Var::VarStr, type_var_with_kind::Var::VarInternedStr)),
+        }
+    }
+}
+
+/// The maximum `Nat` value.
+const MAX_NAT: u8 = 37;
+
+fn prim(prim: PrimType, args: Vec<Type>) -> r#type::Sum {
+    r#type::Sum::Prim(r#type::Prim {
+        prim: prim as i32,
+        args,
+    })
+}
+
+fn encode_kind(kind: &DamlKind) -> Kind {
+    let sum = match kind {
+        DamlKind::Star => kind::Sum::Star(Unit {}),
+        DamlKind::Arrow(arrow) => kind::Sum::Arrow(Box::new(kind::Arrow {
+            params: arrow.params().iter().map(encode_kind).collect(),
+            result: Some(Box::new(encode_kind(arrow.result()))),
+        })),
+        DamlKind::Nat => kind::Sum::Nat(Unit {}),
+    };
+    Kind {
+        sum: Some(sum),
+    }
+}
+
+/// Collect the modules of a package which should be encoded, ordered by path.
+///
+/// Intermediate modules which exist only to hold child modules are not encoded.
+fn encodable_modules<'a>(root: &'a DamlModule<'a>) -> Vec<&'a DamlModule<'a>> {
+    fn is_encodable(module: &DamlModule<'_>) -> bool {
+        !module.is_root()
+            && (module.is_leaf()
+                || !module.synonyms().is_empty()
+                || module.data_types().next().is_some()
+                || module.interfaces().next().is_some()
+                || module.values().next().is_some()
+                || module.exceptions().next().is_some())
+    }
+    fn collect<'a>(module: &'a DamlModule<'a>, modules: &mut Vec<&'a DamlModule<'a>>) {
+        if is_encodable(module) {
+            modules.push(module);
+        }
+        for child in module.child_modules().sorted_by(|a, b| a.local_name().cmp(b.local_name())) {
+            collect(child, modules);
+        }
+    }
+    let mut modules = vec![];
+    collect(root, &mut modules);
+    modules
+}
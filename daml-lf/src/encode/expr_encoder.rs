@@ -0,0 +1,719 @@
+use crate::element::{
+    DamlAbs, DamlApp, DamlBinding, DamlBlock, DamlBuiltinFunction, DamlCallInterface, DamlCase, DamlCaseAlt,
+    DamlCaseAltSum, DamlCommit, DamlCons, DamlExpr, DamlFieldWithExpr, DamlFromAny, DamlFromAnyException,
+    DamlInterfaceCast, DamlInterfaceExpr, DamlOptionalSome, DamlPrimCon, DamlPrimLit, DamlPure, DamlRecCon,
+    DamlRecProj, DamlRecUpd, DamlRequiredInterfaceCast, DamlScenario, DamlStructCon, DamlStructProj, DamlStructUpd,
+    DamlThrow, DamlToAny, DamlToAnyException, DamlTyAbs, DamlTyApp, DamlUnsafeFromInterface,
+    DamlUnsafeFromRequiredInterface, DamlUpdate, DamlValueName, DamlVarWithType, DamlVariantCon, RoundingMode,
+};
+use crate::encode::PackageEncoder;
+use crate::error::{DamlLfConvertError, DamlLfConvertResult};
+use crate::lf_protobuf::com::daml::daml_lf_1::case_alt::{cons, optional_some, r#enum, variant};
+use crate::lf_protobuf::com::daml::daml_lf_1::expr::{
+    enum_con, rec_proj, rec_upd, struct_proj, struct_upd, variant_con,
+};
+use crate::lf_protobuf::com::daml::daml_lf_1::field_with_expr;
+use crate::lf_protobuf::com::daml::daml_lf_1::update::exercise;
+use crate::lf_protobuf::com::daml::daml_lf_1::{
+    case_alt, expr, prim_lit, scenario, update, var_with_type, Binding, Block, BuiltinFunction, Case, CaseAlt, Expr,
+    FieldWithExpr, PrimCon, PrimLit, Pure, Scenario, Unit, Update, ValName, VarWithType,
+};
+
+type BoxedExpr = Option<Box<Expr>>;
+
+impl PackageEncoder<'_> {
+    pub fn encode_expr(&mut self, daml_expr: &DamlExpr<'_>) -> DamlLfConvertResult<Expr> {
+        let sum = match daml_expr {
+            DamlExpr::Var(var) => self.string(var, expr::Sum::VarStr, expr::Sum::VarInternedStr),
+            DamlExpr::Val(val) => expr::Sum::Val(self.encode_val_name(val)),
+            DamlExpr::Builtin(builtin) => expr::Sum::Builtin(encode_builtin(builtin)? as i32),
+            DamlExpr::PrimCon(prim_con) => expr::Sum::PrimCon(encode_prim_con(*prim_con) as i32),
+            DamlExpr::PrimLit(prim_lit) => expr::Sum::PrimLit(self.encode_prim_lit(prim_lit)),
+            DamlExpr::RecCon(rec_con) => self.encode_rec_con(rec_con)?,
+            DamlExpr::RecProj(rec_proj) => self.encode_rec_proj(rec_proj)?,
+            DamlExpr::RecUpd(rec_upd) => self.encode_rec_upd(rec_upd)?,
+            DamlExpr::VariantCon(variant_con) => self.encode_variant_con(variant_con)?,
+            DamlExpr::EnumCon(enum_con) => expr::Sum::EnumCon(expr::EnumCon {
+                tycon: Some(self.encode_tycon_name(enum_con.tycon())),
+                enum_con: Some(self.string(
+                    enum_con.enum_con(),
+                    enum_con::EnumCon::EnumConStr,
+                    enum_con::EnumCon::EnumConInternedStr,
+                )),
+            }),
+            DamlExpr::StructCon(struct_con) => self.encode_struct_con(struct_con)?,
+            DamlExpr::StructProj(struct_proj) => self.encode_struct_proj(struct_proj)?,
+            DamlExpr::StructUpd(struct_upd) => self.encode_struct_upd(struct_upd)?,
+            DamlExpr::App(app) => self.encode_app(app)?,
+            DamlExpr::TyApp(ty_app) => self.encode_ty_app(ty_app)?,
+            DamlExpr::Abs(abs) => self.encode_abs(abs)?,
+            DamlExpr::TyAbs(ty_abs) => self.encode_ty_abs(ty_abs)?,
+            DamlExpr::Case(case) => self.encode_case(case)?,
+            DamlExpr::Let(block) => expr::Sum::Let(Box::new(self.encode_block(block)?)),
+            DamlExpr::Nil(ty) => expr::Sum::Nil(expr::Nil {
+                r#type: Some(self.encode_type(ty)?),
+            }),
+            DamlExpr::Cons(cons) => self.encode_cons(cons)?,
+            DamlExpr::Update(update) => expr::Sum::Update(Box::new(self.encode_update(update)?)),
+            DamlExpr::Scenario(scenario) => expr::Sum::Scenario(Box::new(self.encode_scenario(scenario)?)),
+            DamlExpr::OptionalNone(ty) => expr::Sum::OptionalNone(expr::OptionalNone {
+                r#type: Some(self.encode_type(ty)?),
+            }),
+            DamlExpr::OptionalSome(opt_some) => self.encode_optional_some(opt_some)?,
+            DamlExpr::ToAny(to_any) => self.encode_to_any(to_any)?,
+            DamlExpr::FromAny(from_any) => self.encode_from_any(from_any)?,
+            DamlExpr::TypeRep(ty) => expr::Sum::TypeRep(self.encode_type(ty)?),
+            DamlExpr::ToAnyException(to_any_exception) => self.encode_to_any_exception(to_any_exception)?,
+            DamlExpr::FromAnyException(from_any_exception) => self.encode_from_any_exception(from_any_exception)?,
+            DamlExpr::Throw(throw) => self.encode_throw(throw)?,
+            DamlExpr::ToInterface(to_interface) => self.encode_to_interface(to_interface)?,
+            DamlExpr::FromInterface(from_interface) => self.encode_from_interface(from_interface)?,
+            DamlExpr::UnsafeFromInterface(unsafe_from_interface) =>
+                self.encode_unsafe_from_interface(unsafe_from_interface)?,
+            DamlExpr::CallInterface(call_interface) => self.encode_call_interface(call_interface)?,
+            DamlExpr::ToRequiredInterface(cast) => self.encode_to_required_interface(cast)?,
+            DamlExpr::FromRequiredInterface(cast) => self.encode_from_required_interface(cast)?,
+            DamlExpr::UnsafeFromRequiredInterface(cast) => self.encode_unsafe_from_required_interface(cast)?,
+            DamlExpr::InterfaceTemplateTypeRep(interface_expr) =>
+                self.encode_interface_template_type_rep(interface_expr)?,
+            DamlExpr::SignatoryInterface(interface_expr) => self.encode_signatory_interface(interface_expr)?,
+            DamlExpr::ObserverInterface(interface_expr) => self.encode_observer_interface(interface_expr)?,
+            DamlExpr::ViewInterface(interface_expr) => self.encode_view_interface(interface_expr)?,
+        };
+        Ok(Expr {
+            location: None,
+            sum: Some(sum),
+        })
+    }
+
+    fn encode_rec_con(&mut self, rec_con: &DamlRecCon<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::RecCon(expr::RecCon {
+            tycon: Some(self.encode_tycon(rec_con.tycon())?),
+            fields: self.encode_fields_with_expr(rec_con.fields())?,
+        }))
+    }
+
+    fn encode_rec_proj(&mut self, rec_proj: &DamlRecProj<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::RecProj(Box::new(expr::RecProj {
+            tycon: Some(self.encode_tycon(rec_proj.tycon())?),
+            record: self.encode_boxed_expr(rec_proj.record())?,
+            field: Some(self.string(rec_proj.field(), rec_proj::Field::FieldStr, rec_proj::Field::FieldInternedStr)),
+        })))
+    }
+
+    fn encode_rec_upd(&mut self, rec_upd: &DamlRecUpd<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::RecUpd(Box::new(expr::RecUpd {
+            tycon: Some(self.encode_tycon(rec_upd.tycon())?),
+            record: self.encode_boxed_expr(rec_upd.record())?,
+            update: self.encode_boxed_expr(rec_upd.update())?,
+            field: Some(self.string(rec_upd.field(), rec_upd::Field::FieldStr, rec_upd::Field::FieldInternedStr)),
+        })))
+    }
+
+    fn encode_variant_con(&mut self, variant_con: &DamlVariantCon<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::VariantCon(Box::new(expr::VariantCon {
+            tycon: Some(self.encode_tycon(variant_con.tycon())?),
+            variant_arg: self.encode_boxed_expr(variant_con.variant_arg())?,
+            variant_con: Some(self.string(
+                variant_con.variant_con(),
+                variant_con::VariantCon::VariantConStr,
+                variant_con::VariantCon::VariantConInternedStr,
+            )),
+        })))
+    }
+
+    fn encode_struct_con(&mut self, struct_con: &DamlStructCon<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::StructCon(expr::StructCon {
+            fields: self.encode_fields_with_expr(struct_con.fields())?,
+        }))
+    }
+
+    fn encode_struct_proj(&mut self, struct_proj: &DamlStructProj<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::StructProj(Box::new(expr::StructProj {
+            r#struct: self.encode_boxed_expr(struct_proj.struct_expr())?,
+            field: Some(self.string(
+                struct_proj.field(),
+                struct_proj::Field::FieldStr,
+                struct_proj::Field::FieldInternedStr,
+            )),
+        })))
+    }
+
+    fn encode_struct_upd(&mut self, struct_upd: &DamlStructUpd<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::StructUpd(Box::new(expr::StructUpd {
+            r#struct: self.encode_boxed_expr(struct_upd.struct_expr())?,
+            update: self.encode_boxed_expr(struct_upd.update())?,
+            field: Some(self.string(
+                struct_upd.field(),
+                struct_upd::Field::FieldStr,
+                struct_upd::Field::FieldInternedStr,
+            )),
+        })))
+    }
+
+    fn encode_app(&mut self, app: &DamlApp<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::App(Box::new(expr::App {
+            fun: self.encode_boxed_expr(app.fun())?,
+            args: self.encode_exprs(app.args())?,
+        })))
+    }
+
+    fn encode_ty_app(&mut self, ty_app: &DamlTyApp<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::TyApp(Box::new(expr::TyApp {
+            expr: self.encode_boxed_expr(ty_app.expr())?,
+            types: self.encode_types(ty_app.types())?,
+        })))
+    }
+
+    fn encode_abs(&mut self, abs: &DamlAbs<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::Abs(Box::new(expr::Abs {
+            param: abs
+                .params()
+                .iter()
+                .map(|param| self.encode_var_with_type(param))
+                .collect::<DamlLfConvertResult<_>>()?,
+            body: self.encode_boxed_expr(abs.body())?,
+        })))
+    }
+
+    fn encode_ty_abs(&mut self, ty_abs: &DamlTyAbs<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::TyAbs(Box::new(expr::TyAbs {
+            param: self.encode_type_vars(ty_abs.params()),
+            body: self.encode_boxed_expr(ty_abs.body())?,
+        })))
+    }
+
+    fn encode_case(&mut self, case: &DamlCase<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::Case(Box::new(Case {
+            scrut: self.encode_boxed_expr(case.scrut())?,
+            alts: case.alts().iter().map(|alt| self.encode_case_alt(alt)).collect::<DamlLfConvertResult<_>>()?,
+        })))
+    }
+
+    fn encode_cons(&mut self, cons: &DamlCons<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::Cons(Box::new(expr::Cons {
+            r#type: Some(self.encode_type(cons.ty())?),
+            front: self.encode_exprs(cons.front())?,
+            tail: self.encode_boxed_expr(cons.tail())?,
+        })))
+    }
+
+    fn encode_optional_some(&mut self, opt_some: &DamlOptionalSome<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::OptionalSome(Box::new(expr::OptionalSome {
+            r#type: Some(self.encode_type(opt_some.ty())?),
+            body: self.encode_boxed_expr(opt_some.body())?,
+        })))
+    }
+
+    fn encode_to_any(&mut self, to_any: &DamlToAny<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::ToAny(Box::new(expr::ToAny {
+            r#type: Some(self.encode_type(to_any.ty())?),
+            expr: self.encode_boxed_expr(to_any.expr())?,
+        })))
+    }
+
+    fn encode_from_any(&mut self, from_any: &DamlFromAny<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::FromAny(Box::new(expr::FromAny {
+            r#type: Some(self.encode_type(from_any.ty())?),
+            expr: self.encode_boxed_expr(from_any.expr())?,
+        })))
+    }
+
+    fn encode_to_any_exception(&mut self, to_any_exception: &DamlToAnyException<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::ToAnyException(Box::new(expr::ToAnyException {
+            r#type: Some(self.encode_type(to_any_exception.ty())?),
+            expr: self.encode_boxed_expr(to_any_exception.expr())?,
+        })))
+    }
+
+    fn encode_from_any_exception(
+        &mut self,
+        from_any_exception: &DamlFromAnyException<'_>,
+    ) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::FromAnyException(Box::new(expr::FromAnyException {
+            r#type: Some(self.encode_type(from_any_exception.ty())?),
+            expr: self.encode_boxed_expr(from_any_exception.expr())?,
+        })))
+    }
+
+    fn encode_throw(&mut self, throw: &DamlThrow<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::Throw(Box::new(expr::Throw {
+            return_type: Some(self.encode_type(throw.return_type())?),
+            exception_type: Some(self.encode_type(throw.exception_type())?),
+            exception_expr: self.encode_boxed_expr(throw.exception_expr())?,
+        })))
+    }
+
+    fn encode_to_interface(&mut self, to_interface: &DamlInterfaceCast<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::ToInterface(Box::new(expr::ToInterface {
+            interface_type: Some(self.encode_tycon_name(to_interface.interface_type())),
+            template_type: Some(self.encode_tycon_name(to_interface.template_type())),
+            template_expr: self.encode_boxed_expr(to_interface.expr())?,
+        })))
+    }
+
+    fn encode_from_interface(&mut self, from_interface: &DamlInterfaceCast<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::FromInterface(Box::new(expr::FromInterface {
+            interface_type: Some(self.encode_tycon_name(from_interface.interface_type())),
+            template_type: Some(self.encode_tycon_name(from_interface.template_type())),
+            interface_expr: self.encode_boxed_expr(from_interface.expr())?,
+        })))
+    }
+
+    fn encode_unsafe_from_interface(
+        &mut self,
+        unsafe_from_interface: &DamlUnsafeFromInterface<'_>,
+    ) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::UnsafeFromInterface(Box::new(expr::UnsafeFromInterface {
+            interface_type: Some(self.encode_tycon_name(unsafe_from_interface.interface_type())),
+            template_type: Some(self.encode_tycon_name(unsafe_from_interface.template_type())),
+            contract_id_expr: self.encode_boxed_expr(unsafe_from_interface.contract_id_expr())?,
+            interface_expr: self.encode_boxed_expr(unsafe_from_interface.interface_expr())?,
+        })))
+    }
+
+    fn encode_call_interface(&mut self, call_interface: &DamlCallInterface<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::CallInterface(Box::new(expr::CallInterface {
+            interface_type: Some(self.encode_tycon_name(call_interface.interface_type())),
+            method_interned_name: self.interner.intern_string(call_interface.method()),
+            interface_expr: self.encode_boxed_expr(call_interface.interface_expr())?,
+        })))
+    }
+
+    fn encode_to_required_interface(&mut self, cast: &DamlRequiredInterfaceCast<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::ToRequiredInterface(Box::new(expr::ToRequiredInterface {
+            required_interface: Some(self.encode_tycon_name(cast.required_interface())),
+            requiring_interface: Some(self.encode_tycon_name(cast.requiring_interface())),
+            expr: self.encode_boxed_expr(cast.expr())?,
+        })))
+    }
+
+    fn encode_from_required_interface(
+        &mut self,
+        cast: &DamlRequiredInterfaceCast<'_>,
+    ) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::FromRequiredInterface(Box::new(expr::FromRequiredInterface {
+            required_interface: Some(self.encode_tycon_name(cast.required_interface())),
+            requiring_interface: Some(self.encode_tycon_name(cast.requiring_interface())),
+            expr: self.encode_boxed_expr(cast.expr())?,
+        })))
+    }
+
+    fn encode_unsafe_from_required_interface(
+        &mut self,
+        cast: &DamlUnsafeFromRequiredInterface<'_>,
+    ) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::UnsafeFromRequiredInterface(Box::new(expr::UnsafeFromRequiredInterface {
+            required_interface: Some(self.encode_tycon_name(cast.required_interface())),
+            requiring_interface: Some(self.encode_tycon_name(cast.requiring_interface())),
+            contract_id_expr: self.encode_boxed_expr(cast.contract_id_expr())?,
+            interface_expr: self.encode_boxed_expr(cast.interface_expr())?,
+        })))
+    }
+
+    fn encode_interface_template_type_rep(
+        &mut self,
+        interface_expr: &DamlInterfaceExpr<'_>,
+    ) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::InterfaceTemplateTypeRep(Box::new(expr::InterfaceTemplateTypeRep {
+            interface: Some(self.encode_tycon_name(interface_expr.interface())),
+            expr: self.encode_boxed_expr(interface_expr.expr())?,
+        })))
+    }
+
+    fn encode_signatory_interface(&mut self, interface_expr: &DamlInterfaceExpr<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::SignatoryInterface(Box::new(expr::SignatoryInterface {
+            interface: Some(self.encode_tycon_name(interface_expr.interface())),
+            expr: self.encode_boxed_expr(interface_expr.expr())?,
+        })))
+    }
+
+    fn encode_observer_interface(&mut self, interface_expr: &DamlInterfaceExpr<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::ObserverInterface(Box::new(expr::ObserverInterface {
+            interface: Some(self.encode_tycon_name(interface_expr.interface())),
+            expr: self.encode_boxed_expr(interface_expr.expr())?,
+        })))
+    }
+
+    fn encode_view_interface(&mut self, interface_expr: &DamlInterfaceExpr<'_>) -> DamlLfConvertResult<expr::Sum> {
+        Ok(expr::Sum::ViewInterface(Box::new(expr::ViewInterface {
+            interface: Some(self.encode_tycon_name(interface_expr.interface())),
+            expr: self.encode_boxed_expr(interface_expr.expr())?,
+        })))
+    }
+
+    fn encode_boxed_expr(&mut self, daml_expr: &DamlExpr<'_>) -> DamlLfConvertResult<BoxedExpr> {
+        Ok(Some(Box::new(self.encode_expr(daml_expr)?)))
+    }
+
+    fn encode_exprs(&mut self, exprs: &[DamlExpr<'_>]) -> DamlLfConvertResult<Vec<Expr>> {
+        exprs.iter().map(|daml_expr| self.encode_expr(daml_expr)).collect()
+    }
+
+    fn encode_val_name(&mut self, val: &DamlValueName<'_>) -> ValName {
+        let module = self.encode_module_ref(val.package_id(), val.module_path());
+        let (name_dname, name_interned_dname) = self.dotted_pair(&[val.name()]);
+        ValName {
+            module: Some(module),
+            name_dname,
+            name_interned_dname,
+        }
+    }
+
+    fn encode_prim_lit(&mut self, prim_lit: &DamlPrimLit<'_>) -> PrimLit {
+        let sum = match prim_lit {
+            DamlPrimLit::Int64(i) => prim_lit::Sum::Int64(*i),
+            DamlPrimLit::Text(text) => self.string(text, prim_lit::Sum::TextStr, prim_lit::Sum::TextInternedStr),
+            DamlPrimLit::Party(party) => self.string(party, prim_lit::Sum::PartyStr, prim_lit::Sum::PartyInternedStr),
+            DamlPrimLit::Date(date) => prim_lit::Sum::Date(*date),
+            DamlPrimLit::Timestamp(timestamp) => prim_lit::Sum::Timestamp(*timestamp),
+            DamlPrimLit::Numeric(numeric) =>
+                self.string(numeric, prim_lit::Sum::DecimalStr, prim_lit::Sum::NumericInternedStr),
+            DamlPrimLit::RoundingMode(mode) => prim_lit::Sum::RoundingMode(encode_rounding_mode(mode) as i32),
+        };
+        PrimLit {
+            sum: Some(sum),
+        }
+    }
+
+    fn encode_fields_with_expr(&mut self, fields: &[DamlFieldWithExpr<'_>]) -> DamlLfConvertResult<Vec<FieldWithExpr>> {
+        fields
+            .iter()
+            .map(|field| {
+                Ok(FieldWithExpr {
+                    expr: Some(self.encode_expr(field.expr())?),
+                    field: Some(self.string(
+                        field.field(),
+                        field_with_expr::Field::FieldStr,
+                        field_with_expr::Field::FieldInternedStr,
+                    )),
+                })
+            })
+            .collect()
+    }
+
+    fn encode_var_with_type(&mut self, var_with_type: &DamlVarWithType<'_>) -> DamlLfConvertResult<VarWithType> {
+        Ok(VarWithType {
+            r#type: Some(self.encode_type(var_with_type.ty())?),
+            var: Some(self.string(var_with_type.var(), var_with_type::Var::VarStr, var_with_type::Var::VarInternedStr)),
+        })
+    }
+
+    fn encode_block(&mut self, block: &DamlBlock<'_>) -> DamlLfConvertResult<Block> {
+        Ok(Block {
+            bindings: block
+                .bindings()
+                .iter()
+                .map(|binding| self.encode_binding(binding))
+                .collect::<DamlLfConvertResult<_>>()?,
+            body: self.encode_boxed_expr(block.body())?,
+        })
+    }
+
+    fn encode_binding(&mut self, binding: &DamlBinding<'_>) -> DamlLfConvertResult<Binding> {
+        Ok(Binding {
+            binder: Some(self.encode_var_with_type(binding.binder())?),
+            bound: Some(self.encode_expr(binding.bound())?),
+        })
+    }
+
+    fn encode_pure(&mut self, pure: &DamlPure<'_>) -> DamlLfConvertResult<Pure> {
+        Ok(Pure {
+            r#type: Some(self.encode_type(pure.ty())?),
+            expr: self.encode_boxed_expr(pure.expr())?,
+        })
+    }
+
+    fn encode_case_alt(&mut self, case_alt: &DamlCaseAlt<'_>) -> DamlLfConvertResult<CaseAlt> {
+        let sum = match case_alt.sum() {
+            DamlCaseAltSum::Default => case_alt::Sum::Default(Unit {}),
+            DamlCaseAltSum::Variant(alt) => case_alt::Sum::Variant(case_alt::Variant {
+                con: Some(self.encode_tycon_name(alt.con())),
+                variant: Some(self.string(
+                    alt.variant(),
+                    variant::Variant::VariantStr,
+                    variant::Variant::VariantInternedStr,
+                )),
+                binder: Some(self.string(alt.binder(), variant::Binder::BinderStr, variant::Binder::BinderInternedStr)),
+            }),
+            DamlCaseAltSum::PrimCon(prim_con) => case_alt::Sum::PrimCon(encode_prim_con(*prim_con) as i32),
+            DamlCaseAltSum::Nil => case_alt::Sum::Nil(Unit {}),
+            DamlCaseAltSum::Cons(alt) => case_alt::Sum::Cons(case_alt::Cons {
+                var_head: Some(self.string(
+                    alt.var_head(),
+                    cons::VarHead::VarHeadStr,
+                    cons::VarHead::VarHeadInternedStr,
+                )),
+                var_tail: Some(self.string(
+                    alt.var_tail(),
+                    cons::VarTail::VarTailStr,
+                    cons::VarTail::VarTailInternedStr,
+                )),
+            }),
+            DamlCaseAltSum::OptionalNone => case_alt::Sum::OptionalNone(Unit {}),
+            DamlCaseAltSum::OptionalSome(alt) => case_alt::Sum::OptionalSome(case_alt::OptionalSome {
+                var_body: Some(self.string(
+                    alt.var_body(),
+                    optional_some::VarBody::VarBodyStr,
+                    optional_some::VarBody::VarBodyInternedStr,
+                )),
+            }),
+            DamlCaseAltSum::Enum(alt) => case_alt::Sum::Enum(case_alt::Enum {
+                con: Some(self.encode_tycon_name(alt.con())),
+                constructor: Some(self.string(
+                    alt.constructor(),
+                    r#enum::Constructor::ConstructorStr,
+                    r#enum::Constructor::ConstructorInternedStr,
+                )),
+            }),
+        };
+        Ok(CaseAlt {
+            body: Some(self.encode_expr(case_alt.body())?),
+            sum: Some(sum),
+        })
+    }
+
+    fn encode_update(&mut self, daml_update: &DamlUpdate<'_>) -> DamlLfConvertResult<Update> {
+        let sum = match daml_update {
+            DamlUpdate::Pure(pure) => update::Sum::Pure(Box::new(self.encode_pure(pure)?)),
+            DamlUpdate::Block(block) => update::Sum::Block(Box::new(self.encode_block(block)?)),
+            DamlUpdate::Create(create) => update::Sum::Create(Box::new(update::Create {
+                template: Some(self.encode_tycon_name(create.template())),
+                expr: self.encode_boxed_expr(create.expr())?,
+            })),
+            DamlUpdate::Exercise(exercise) => update::Sum::Exercise(Box::new(update::Exercise {
+                template: Some(self.encode_tycon_name(exercise.template())),
+                cid: self.encode_boxed_expr(exercise.cid())?,
+                arg: self.encode_boxed_expr(exercise.arg())?,
+                choice: Some(self.string(
+                    exercise.choice(),
+                    exercise::Choice::ChoiceStr,
+                    exercise::Choice::ChoiceInternedStr,
+                )),
+            })),
+            DamlUpdate::ExerciseByKey(exercise_by_key) => update::Sum::ExerciseByKey(Box::new(update::ExerciseByKey {
+                template: Some(self.encode_tycon_name(exercise_by_key.template())),
+                choice_interned_str: self.interner.intern_string(exercise_by_key.choice()),
+                key: self.encode_boxed_expr(exercise_by_key.key())?,
+                arg: self.encode_boxed_expr(exercise_by_key.arg())?,
+            })),
+            DamlUpdate::Fetch(fetch) => update::Sum::Fetch(Box::new(update::Fetch {
+                template: Some(self.encode_tycon_name(fetch.template())),
+                cid: self.encode_boxed_expr(fetch.cid())?,
+            })),
+            DamlUpdate::GetTime => update::Sum::GetTime(Unit {}),
+            DamlUpdate::LookupByKey(retrieve_by_key) => update::Sum::LookupByKey(Box::new(update::RetrieveByKey {
+                template: Some(self.encode_tycon_name(retrieve_by_key.template())),
+                key: self.encode_boxed_expr(retrieve_by_key.key())?,
+            })),
+            DamlUpdate::FetchByKey(retrieve_by_key) => update::Sum::FetchByKey(Box::new(update::RetrieveByKey {
+                template: Some(self.encode_tycon_name(retrieve_by_key.template())),
+                key: self.encode_boxed_expr(retrieve_by_key.key())?,
+            })),
+            DamlUpdate::EmbedExpr(embed_expr) => update::Sum::EmbedExpr(Box::new(update::EmbedExpr {
+                r#type: Some(self.encode_type(embed_expr.ty())?),
+                body: self.encode_boxed_expr(embed_expr.body())?,
+            })),
+            DamlUpdate::TryCatch(try_catch) => update::Sum::TryCatch(Box::new(update::TryCatch {
+                return_type: Some(self.encode_type(try_catch.return_type())?),
+                try_expr: self.encode_boxed_expr(try_catch.try_expr())?,
+                var_interned_str: self.interner.intern_string(try_catch.var()),
+                catch_expr: self.encode_boxed_expr(try_catch.catch_expr())?,
+            })),
+            DamlUpdate::ExerciseInterface(exercise_interface) =>
+                update::Sum::ExerciseInterface(Box::new(update::ExerciseInterface {
+                    interface: Some(self.encode_tycon_name(exercise_interface.interface())),
+                    choice_interned_str: self.interner.intern_string(exercise_interface.choice()),
+                    cid: self.encode_boxed_expr(exercise_interface.cid())?,
+                    arg: self.encode_boxed_expr(exercise_interface.arg())?,
+                })),
+            DamlUpdate::FetchInterface(fetch_interface) =>
+                update::Sum::FetchInterface(Box::new(update::FetchInterface {
+                    interface: Some(self.encode_tycon_name(fetch_interface.interface())),
+                    cid: self.encode_boxed_expr(fetch_interface.cid())?,
+                })),
+        };
+        Ok(Update {
+            sum: Some(sum),
+        })
+    }
+
+    fn encode_scenario(&mut self, daml_scenario: &DamlScenario<'_>) -> DamlLfConvertResult<Scenario> {
+        let sum = match daml_scenario {
+            DamlScenario::Pure(pure) => scenario::Sum::Pure(Box::new(self.encode_pure(pure)?)),
+            DamlScenario::Block(block) => scenario::Sum::Block(Box::new(self.encode_block(block)?)),
+            DamlScenario::Commit(commit) => scenario::Sum::Commit(Box::new(self.encode_commit(commit)?)),
+            DamlScenario::MustFailAt(commit) => scenario::Sum::MustFailAt(Box::new(self.encode_commit(commit)?)),
+            DamlScenario::Pass(daml_expr) => scenario::Sum::Pass(Box::new(self.encode_expr(daml_expr)?)),
+            DamlScenario::GetTime => scenario::Sum::GetTime(Unit {}),
+            DamlScenario::GetParty(daml_expr) => scenario::Sum::GetParty(Box::new(self.encode_expr(daml_expr)?)),
+            DamlScenario::EmbedExpr(embed_expr) => scenario::Sum::EmbedExpr(Box::new(scenario::EmbedExpr {
+                r#type: Some(self.encode_type(embed_expr.ty())?),
+                body: self.encode_boxed_expr(embed_expr.body())?,
+            })),
+        };
+        Ok(Scenario {
+            sum: Some(sum),
+        })
+    }
+
+    fn encode_commit(&mut self, commit: &DamlCommit<'_>) -> DamlLfConvertResult<scenario::Commit> {
+        Ok(scenario::Commit {
+            party: self.encode_boxed_expr(commit.party())?,
+            expr: self.encode_boxed_expr(commit.expr())?,
+            ret_type: Some(self.encode_type(commit.ret_type())?),
+        })
+    }
+}
+
+const fn encode_prim_con(prim_con: DamlPrimCon) -> PrimCon {
+    match prim_con {
+        DamlPrimCon::Unit => PrimCon::ConUnit,
+        DamlPrimCon::False => PrimCon::ConFalse,
+        DamlPrimCon::True => PrimCon::ConTrue,
+    }
+}
+
+const fn encode_rounding_mode(rounding_mode: &RoundingMode) -> prim_lit::RoundingMode {
+    match rounding_mode {
+        RoundingMode::Up => prim_lit::RoundingMode::Up,
+        RoundingMode::Down => prim_lit::RoundingMode::Down,
+        RoundingMode::Ceiling => prim_lit::RoundingMode::Ceiling,
+        RoundingMode::Floor => prim_lit::RoundingMode::Floor,
+        RoundingMode::HalfUp => prim_lit::RoundingMode::HalfUp,
+        RoundingMode::HalfDown => prim_lit::RoundingMode::HalfDown,
+        RoundingMode::HalfEven => prim_lit::RoundingMode::HalfEven,
+        RoundingMode::Unnecessary => prim_lit::RoundingMode::Unnecessary,
+    }
+}
+
+/// Encode a [`DamlBuiltinFunction`].
+///
+/// `ShiftBignumeric` has no Daml-LF representation and so cannot be encoded.
+#[allow(clippy::too_many_lines)]
+fn encode_builtin(builtin: &DamlBuiltinFunction) -> DamlLfConvertResult<BuiltinFunction> {
+    Ok(match builtin {
+        DamlBuiltinFunction::AddDecimal => BuiltinFunction::AddDecimal,
+        DamlBuiltinFunction::SubDecimal => BuiltinFunction::SubDecimal,
+        DamlBuiltinFunction::MulDecimal => BuiltinFunction::MulDecimal,
+        DamlBuiltinFunction::DivDecimal => BuiltinFunction::DivDecimal,
+        DamlBuiltinFunction::RoundDecimal => BuiltinFunction::RoundDecimal,
+        DamlBuiltinFunction::AddNumeric => BuiltinFunction::AddNumeric,
+        DamlBuiltinFunction::SubNumeric => BuiltinFunction::SubNumeric,
+        DamlBuiltinFunction::MulNumeric => BuiltinFunction::MulNumeric,
+        DamlBuiltinFunction::DivNumeric => BuiltinFunction::DivNumeric,
+        DamlBuiltinFunction::RoundNumeric => BuiltinFunction::RoundNumeric,
+        DamlBuiltinFunction::CastNumeric => BuiltinFunction::CastNumeric,
+        DamlBuiltinFunction::ShiftNumeric => BuiltinFunction::ShiftNumeric,
+        DamlBuiltinFunction::AddInt64 => BuiltinFunction::AddInt64,
+        DamlBuiltinFunction::SubInt64 => BuiltinFunction::SubInt64,
+        DamlBuiltinFunction::MulInt64 => BuiltinFunction::MulInt64,
+        DamlBuiltinFunction::DivInt64 => BuiltinFunction::DivInt64,
+        DamlBuiltinFunction::ModInt64 => BuiltinFunction::ModInt64,
+        DamlBuiltinFunction::ExpInt64 => BuiltinFunction::ExpInt64,
+        DamlBuiltinFunction::Foldl => BuiltinFunction::Foldl,
+        DamlBuiltinFunction::Foldr => BuiltinFunction::Foldr,
+        DamlBuiltinFunction::TextmapEmpty => BuiltinFunction::TextmapEmpty,
+        DamlBuiltinFunction::TextmapInsert => BuiltinFunction::TextmapInsert,
+        DamlBuiltinFunction::TextmapLookup => BuiltinFunction::TextmapLookup,
+        DamlBuiltinFunction::TextmapDelete => BuiltinFunction::TextmapDelete,
+        DamlBuiltinFunction::TextmapToList => BuiltinFunction::TextmapToList,
+        DamlBuiltinFunction::TextmapSize => BuiltinFunction::TextmapSize,
+        DamlBuiltinFunction::ExplodeText => BuiltinFunction::ExplodeText,
+        DamlBuiltinFunction::AppendText => BuiltinFunction::AppendText,
+        DamlBuiltinFunction::Error => BuiltinFunction::Error,
+        DamlBuiltinFunction::AnyExceptionMessage => BuiltinFunction::AnyExceptionMessage,
+        DamlBuiltinFunction::LeqInt64 => BuiltinFunction::LeqInt64,
+        DamlBuiltinFunction::LeqDecimal => BuiltinFunction::LeqDecimal,
+        DamlBuiltinFunction::LeqNumeric => BuiltinFunction::LeqNumeric,
+        DamlBuiltinFunction::LeqText => BuiltinFunction::LeqText,
+        DamlBuiltinFunction::LeqTimestamp => BuiltinFunction::LeqTimestamp,
+        DamlBuiltinFunction::LeqDate => BuiltinFunction::LeqDate,
+        DamlBuiltinFunction::LeqParty => BuiltinFunction::LeqParty,
+        DamlBuiltinFunction::LessInt64 => BuiltinFunction::LessInt64,
+        DamlBuiltinFunction::LessDecimal => BuiltinFunction::LessDecimal,
+        DamlBuiltinFunction::LessNumeric => BuiltinFunction::LessNumeric,
+        DamlBuiltinFunction::LessText => BuiltinFunction::LessText,
+        DamlBuiltinFunction::LessTimestamp => BuiltinFunction::LessTimestamp,
+        DamlBuiltinFunction::LessDate => BuiltinFunction::LessDate,
+        DamlBuiltinFunction::LessParty => BuiltinFunction::LessParty,
+        DamlBuiltinFunction::GeqInt64 => BuiltinFunction::GeqInt64,
+        DamlBuiltinFunction::GeqDecimal => BuiltinFunction::GeqDecimal,
+        DamlBuiltinFunction::GeqNumeric => BuiltinFunction::GeqNumeric,
+        DamlBuiltinFunction::GeqText => BuiltinFunction::GeqText,
+        DamlBuiltinFunction::GeqTimestamp => BuiltinFunction::GeqTimestamp,
+        DamlBuiltinFunction::GeqDate => BuiltinFunction::GeqDate,
+        DamlBuiltinFunction::GeqParty => BuiltinFunction::GeqParty,
+        DamlBuiltinFunction::GreaterInt64 => BuiltinFunction::GreaterInt64,
+        DamlBuiltinFunction::GreaterDecimal => BuiltinFunction::GreaterDecimal,
+        DamlBuiltinFunction::GreaterNumeric => BuiltinFunction::GreaterNumeric,
+        DamlBuiltinFunction::GreaterText => BuiltinFunction::GreaterText,
+        DamlBuiltinFunction::GreaterTimestamp => BuiltinFunction::GreaterTimestamp,
+        DamlBuiltinFunction::GreaterDate => BuiltinFunction::GreaterDate,
+        DamlBuiltinFunction::GreaterParty => BuiltinFunction::GreaterParty,
+        DamlBuiltinFunction::Int64ToText => BuiltinFunction::Int64ToText,
+        DamlBuiltinFunction::DecimalToText => BuiltinFunction::DecimalToText,
+        DamlBuiltinFunction::NumericToText => BuiltinFunction::NumericToText,
+        DamlBuiltinFunction::TextToText => BuiltinFunction::TextToText,
+        DamlBuiltinFunction::TimestampToText => BuiltinFunction::TimestampToText,
+        DamlBuiltinFunction::DateToText => BuiltinFunction::DateToText,
+        DamlBuiltinFunction::PartyToQuotedText => BuiltinFunction::PartyToQuotedText,
+        DamlBuiltinFunction::PartyToText => BuiltinFunction::PartyToText,
+        DamlBuiltinFunction::TextToParty => BuiltinFunction::TextToParty,
+        DamlBuiltinFunction::TextToInt64 => BuiltinFunction::TextToInt64,
+        DamlBuiltinFunction::TextToDecimal => BuiltinFunction::TextToDecimal,
+        DamlBuiltinFunction::TextToNumeric => BuiltinFunction::TextToNumeric,
+        DamlBuiltinFunction::ContractIdToText => BuiltinFunction::ContractIdToText,
+        DamlBuiltinFunction::Sha256Text => BuiltinFunction::Sha256Text,
+        DamlBuiltinFunction::DateToUnixDays => BuiltinFunction::DateToUnixDays,
+        DamlBuiltinFunction::UnixDaysToDate => BuiltinFunction::UnixDaysToDate,
+        DamlBuiltinFunction::TimestampToUnixMicroseconds => BuiltinFunction::TimestampToUnixMicroseconds,
+        DamlBuiltinFunction::UnixMicrosecondsToTimestamp => BuiltinFunction::UnixMicrosecondsToTimestamp,
+        DamlBuiltinFunction::Int64ToDecimal => BuiltinFunction::Int64ToDecimal,
+        DamlBuiltinFunction::DecimalToInt64 => BuiltinFunction::DecimalToInt64,
+        DamlBuiltinFunction::Int64ToNumeric => BuiltinFunction::Int64ToNumeric,
+        DamlBuiltinFunction::NumericToInt64 => BuiltinFunction::NumericToInt64,
+        DamlBuiltinFunction::ImplodeText => BuiltinFunction::ImplodeText,
+        DamlBuiltinFunction::EqualInt64 => BuiltinFunction::EqualInt64,
+        DamlBuiltinFunction::EqualDecimal => BuiltinFunction::EqualDecimal,
+        DamlBuiltinFunction::EqualNumeric => BuiltinFunction::EqualNumeric,
+        DamlBuiltinFunction::EqualText => BuiltinFunction::EqualText,
+        DamlBuiltinFunction::EqualTimestamp => BuiltinFunction::EqualTimestamp,
+        DamlBuiltinFunction::EqualDate => BuiltinFunction::EqualDate,
+        DamlBuiltinFunction::EqualParty => BuiltinFunction::EqualParty,
+        DamlBuiltinFunction::EqualBool => BuiltinFunction::EqualBool,
+        DamlBuiltinFunction::EqualContractId => BuiltinFunction::EqualContractId,
+        DamlBuiltinFunction::EqualList => BuiltinFunction::EqualList,
+        DamlBuiltinFunction::EqualTypeRep => BuiltinFunction::EqualTypeRep,
+        DamlBuiltinFunction::Trace => BuiltinFunction::Trace,
+        DamlBuiltinFunction::CoerceContractId => BuiltinFunction::CoerceContractId,
+        DamlBuiltinFunction::CodePointsToText => BuiltinFunction::CodePointsToText,
+        DamlBuiltinFunction::TextPointsToCode => BuiltinFunction::TextPointsToCode,
+        DamlBuiltinFunction::ScaleBignumeric => BuiltinFunction::ScaleBignumeric,
+        DamlBuiltinFunction::PrecisionBignumeric => BuiltinFunction::PrecisionBignumeric,
+        DamlBuiltinFunction::AddBignumeric => BuiltinFunction::AddBignumeric,
+        DamlBuiltinFunction::SubBignumeric => BuiltinFunction::SubBignumeric,
+        DamlBuiltinFunction::MulBignumeric => BuiltinFunction::MulBignumeric,
+        DamlBuiltinFunction::DivBignumeric => BuiltinFunction::DivBignumeric,
+        DamlBuiltinFunction::ShiftRightBignumeric => BuiltinFunction::ShiftRightBignumeric,
+        DamlBuiltinFunction::BigNumericToNumeric => BuiltinFunction::BignumericToNumeric,
+        DamlBuiltinFunction::NumericToBigNumeric => BuiltinFunction::NumericToBignumeric,
+        DamlBuiltinFunction::BigNumericToText => BuiltinFunction::BignumericToText,
+        DamlBuiltinFunction::GenmapEmpty => BuiltinFunction::GenmapEmpty,
+        DamlBuiltinFunction::GenmapInsert => BuiltinFunction::GenmapInsert,
+        DamlBuiltinFunction::GenmapLookup => BuiltinFunction::GenmapLookup,
+        DamlBuiltinFunction::GenmapDelete => BuiltinFunction::GenmapDelete,
+        DamlBuiltinFunction::GenmapKeys => BuiltinFunction::GenmapKeys,
+        DamlBuiltinFunction::GenmapValues => BuiltinFunction::GenmapValues,
+        DamlBuiltinFunction::GenmapSize => BuiltinFunction::GenmapSize,
+        DamlBuiltinFunction::Equal => BuiltinFunction::Equal,
+        DamlBuiltinFunction::LessEq => BuiltinFunction::LessEq,
+        DamlBuiltinFunction::Less => BuiltinFunction::Less,
+        DamlBuiltinFunction::GreaterEq => BuiltinFunction::GreaterEq,
+        DamlBuiltinFunction::Greater => BuiltinFunction::Greater,
+        DamlBuiltinFunction::ShiftBignumeric =>
+            return Err(DamlLfConvertError::UnencodableElement(String::from(
+                "builtin function ShiftBignumeric has no Daml-LF representation",
+            ))),
+    })
+}
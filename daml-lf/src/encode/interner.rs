@@ -0,0 +1,81 @@
+use crate::lf_protobuf::com::daml::daml_lf_1::InternedDottedName;
+use std::collections::HashMap;
+
+/// Build the interned string and dotted name tables of a package.
+///
+/// Each distinct string and dotted name is assigned the next free index the first time it is interned, such that the
+/// tables are deterministic for a given order of interning.
+#[derive(Debug, Default)]
+pub struct PackageInterner {
+    strings: Vec<String>,
+    string_indices: HashMap<String, i32>,
+    dotted_names: Vec<InternedDottedName>,
+    dotted_name_indices: HashMap<Vec<i32>, i32>,
+}
+
+impl PackageInterner {
+    /// Intern a string and return its index.
+    pub fn intern_string(&mut self, s: &str) -> i32 {
+        if let Some(&index) = self.string_indices.get(s) {
+            index
+        } else {
+            let index = next_index(self.strings.len());
+            self.strings.push(s.to_owned());
+            self.string_indices.insert(s.to_owned(), index);
+            index
+        }
+    }
+
+    /// Intern a dotted name, and each of its segments, and return its index.
+    pub fn intern_dotted<S: AsRef<str>>(&mut self, segments: &[S]) -> i32 {
+        let segments: Vec<_> = segments.iter().map(|s| self.intern_string(s.as_ref())).collect();
+        if let Some(&index) = self.dotted_name_indices.get(&segments) {
+            index
+        } else {
+            let index = next_index(self.dotted_names.len());
+            self.dotted_names.push(InternedDottedName {
+                segments_interned_str: segments.clone(),
+            });
+            self.dotted_name_indices.insert(segments, index);
+            index
+        }
+    }
+
+    /// Consume the interner and return the interned string and dotted name tables.
+    pub fn into_tables(self) -> (Vec<String>, Vec<InternedDottedName>) {
+        (self.strings, self.dotted_names)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+const fn next_index(len: usize) -> i32 {
+    len as i32
+}
+
+#[cfg(test)]
+mod test {
+    use super::PackageInterner;
+
+    #[test]
+    fn test_intern_strings() {
+        let mut interner = PackageInterner::default();
+        assert_eq!(0, interner.intern_string("foo"));
+        assert_eq!(1, interner.intern_string("bar"));
+        assert_eq!(0, interner.intern_string("foo"));
+        let (strings, dotted_names) = interner.into_tables();
+        assert_eq!(vec!["foo", "bar"], strings);
+        assert!(dotted_names.is_empty());
+    }
+
+    #[test]
+    fn test_intern_dotted_names() {
+        let mut interner = PackageInterner::default();
+        assert_eq!(0, interner.intern_dotted(&["Fuji", "PingPong"]));
+        assert_eq!(1, interner.intern_dotted(&["PingPong"]));
+        assert_eq!(0, interner.intern_dotted(&["Fuji", "PingPong"]));
+        let (strings, dotted_names) = interner.into_tables();
+        assert_eq!(vec!["Fuji", "PingPong"], strings);
+        assert_eq!(vec![0, 1], dotted_names[0].segments_interned_str);
+        assert_eq!(vec![1], dotted_names[1].segments_interned_str);
+    }
+}
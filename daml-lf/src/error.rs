@@ -16,6 +16,8 @@ pub enum DamlLfError {
     UnknownVersion(String),
     #[error("unsupported Daml LF version: {0}")]
     UnsupportedVersion(String),
+    #[error("dependency cycle between {}", .0.join(", "))]
+    DependencyCycle(Vec<String>),
 }
 
 impl DamlLfError {
@@ -77,6 +79,9 @@ pub enum DamlLfConvertError {
     /// Failed to lookup a `DamlPackagePayload` by id.
     #[error("failed to lookup a DamlPackagePayload with id {0}")]
     UnknownPackage(String),
+    /// Failed to decode the `DamlLfArchivePayload` of a lazily loaded package.
+    #[error("failed to decode package {0}: {1}")]
+    DecodePackage(String, String),
     /// Failed to lookup a `DamlModulePayload` by id.
     #[error("failed to lookup a DamlModulePayload with id {0}")]
     UnknownModule(String),
@@ -92,9 +97,15 @@ pub enum DamlLfConvertError {
     /// Unknown RoundingMode enum variant.
     #[error("unknown RoundingMode enum variant {0}")]
     UnknownRoundingMode(i32),
+    /// Unknown HashFunction enum variant.
+    #[error("unknown HashFunction enum variant {0}")]
+    UnknownHashFunction(i32),
     /// Nat out of range (0..37 inclusive).
     #[error("Nat {0} out of range (0..37 inclusive)")]
     NatOutOfRange(i64),
+    /// Element cannot be encoded.
+    #[error("cannot encode element: {0}")]
+    UnencodableElement(String),
     /// Internal error.
     #[error("Internal error: {0}")]
     InternalError(String),
@@ -102,3 +113,111 @@ pub enum DamlLfConvertError {
 
 /// Represents `Daml-LF` convert results.
 pub type DamlLfConvertResult<T> = Result<T, DamlLfConvertError>;
+
+/// A `Daml-LF` type checking error together with the location at which it was found.
+#[cfg(feature = "full")]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{package_id}:{module}:{definition}: {kind}")]
+pub struct DamlTypeCheckError {
+    package_id: String,
+    module: String,
+    definition: String,
+    kind: DamlTypeCheckErrorKind,
+}
+
+#[cfg(feature = "full")]
+impl DamlTypeCheckError {
+    pub fn new(
+        package_id: impl Into<String>,
+        module: impl Into<String>,
+        definition: impl Into<String>,
+        kind: DamlTypeCheckErrorKind,
+    ) -> Self {
+        Self {
+            package_id: package_id.into(),
+            module: module.into(),
+            definition: definition.into(),
+            kind,
+        }
+    }
+
+    /// The id of the package in which the error was found.
+    pub fn package_id(&self) -> &str {
+        &self.package_id
+    }
+
+    /// The dotted path of the module in which the error was found.
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    /// The definition in which the error was found, such as `MyTemplate` or `MyTemplate.MyChoice.controller`.
+    pub fn definition(&self) -> &str {
+        &self.definition
+    }
+
+    /// The kind of type checking error.
+    pub fn kind(&self) -> &DamlTypeCheckErrorKind {
+        &self.kind
+    }
+}
+
+/// The kinds of `Daml-LF` type checking errors.
+#[cfg(feature = "full")]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DamlTypeCheckErrorKind {
+    /// An expression did not have the expected type.
+    #[error("expected type {0} but found type {1}")]
+    TypeMismatch(String, String),
+    /// A type did not have the expected shape, such as a function or record type.
+    #[error("expected {0} but found type {1}")]
+    UnexpectedType(String, String),
+    /// A type did not have the expected kind.
+    #[error("expected kind {0} but found kind {1} for type {2}")]
+    KindMismatch(String, String, String),
+    /// A type synonym was defined in terms of itself.
+    #[error("type synonym {0} is cyclic")]
+    CyclicSynonym(String),
+    /// A type was applied to too many type arguments or a synonym was not fully applied.
+    #[error("type {0} expects {1} type arguments but was given {2}")]
+    TypeArgumentCount(String, usize, usize),
+    /// A `Nat` was outside the range permitted for a `Numeric` scale.
+    #[error("nat {0} is out of range (0..37 inclusive)")]
+    NatOutOfRange(u8),
+    /// A type which must be serializable is not.
+    #[error("{0} type {1} is not serializable")]
+    NotSerializable(String, String),
+    /// A term variable was not in scope.
+    #[error("unknown variable {0}")]
+    UnknownVariable(String),
+    /// A type variable was not in scope.
+    #[error("unknown type variable {0}")]
+    UnknownTypeVariable(String),
+    /// A type constructor, type synonym, template or interface could not be found.
+    #[error("unknown type {0}")]
+    UnknownType(String),
+    /// A value could not be found.
+    #[error("unknown value {0}")]
+    UnknownValue(String),
+    /// A record or struct field did not exist.
+    #[error("unknown field {0} of type {1}")]
+    UnknownField(String, String),
+    /// The fields of a record construction do not match the record definition.
+    #[error("fields do not match the definition of record {0}")]
+    FieldMismatch(String),
+    /// A variant or enum constructor did not exist.
+    #[error("unknown constructor {0} of type {1}")]
+    UnknownConstructor(String, String),
+    /// A choice did not exist.
+    #[error("unknown choice {0} of {1}")]
+    UnknownChoice(String, String),
+    /// An interface method did not exist.
+    #[error("unknown method {0} of interface {1}")]
+    UnknownMethod(String, String),
+    /// A key based operation was used on a template without a key.
+    #[error("template {0} does not have a key")]
+    MissingKey(String),
+    /// A case expression had no alternatives.
+    #[error("case expression has no alternatives")]
+    EmptyCase,
+}
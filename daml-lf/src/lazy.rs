@@ -0,0 +1,380 @@
+use crate::archive::sanitize_name;
+use crate::convert;
+use crate::convert::DamlArchivePayload;
+use crate::element::{DamlData, DamlModule, DamlPackage, DamlTyCon, DamlTyConName};
+#[cfg(feature = "full")]
+use crate::element::{DamlDefValue, DamlValueName};
+use crate::error::DamlLfConvertError;
+use crate::lf_protobuf::com::daml::daml_lf::Archive;
+use crate::lf_protobuf::com::daml::daml_lf_1::PackageMetadata;
+use crate::{
+    DamlLfArchive, DamlLfError, DamlLfHashFunction, DamlLfResult, DarFile, DarManifest, LanguageFeatureVersion,
+    LanguageV1MinorVersion, LanguageVersion, PackageInfo,
+};
+use bytes::Bytes;
+use itertools::Itertools;
+use once_cell::sync::OnceCell;
+use prost::Message;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{Cursor, Read, Seek};
+use std::iter;
+use std::path::Path;
+use zip::ZipArchive;
+
+/// A `dar` file which decodes the `Daml LF` archives it contains on first access.
+///
+/// Loading a [`DarFile`] decodes every package of the `dar` up front.  A `LazyDarFile` instead reads the manifest and
+/// the undecoded bytes of each `dalf` and decodes a package only when it is first required, after which the decoded
+/// [`DamlLfArchive`] is cached for the lifetime of the `LazyDarFile`.
+///
+/// Use [`LazyDarFile::apply`] to access a [`DamlLazyArchive`] which converts only the packages and modules which are
+/// requested from it.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use daml_lf::{LazyDarFile, DamlLfResult};
+/// # fn main() -> DamlLfResult<()> {
+/// let dar = LazyDarFile::from_file("Example.dar")?;
+/// let version = dar.package_info(dar.main_package_id())?.and_then(|info| info.version.clone());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct LazyDarFile {
+    manifest: DarManifest,
+    main: LazyDalf,
+    dependencies: Vec<LazyDalf>,
+}
+
+impl LazyDarFile {
+    /// Create a `LazyDarFile` from the supplied `dar` file.
+    ///
+    /// See [`DarFile::from_file`] for details of the supported `dar` formats.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or cannot be interpreted as a `dar` file then an error will be returned.  The
+    /// packages of the `dar` are not decoded and so any error in their content is reported only when they are
+    /// accessed.
+    pub fn from_file(path: impl AsRef<Path>) -> DamlLfResult<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Create a `LazyDarFile` from the supplied `dar` file `bytes`.
+    pub fn from_bytes(bytes: impl Into<Bytes>) -> DamlLfResult<Self> {
+        Self::from_reader(Cursor::new(bytes.into()))
+    }
+
+    /// Create a `LazyDarFile` from the supplied `dar` file `reader`.
+    pub fn from_reader<R: Read + Seek>(reader: R) -> DamlLfResult<Self> {
+        let mut zip_archive = ZipArchive::new(reader)?;
+        let manifest = DarFile::read_manifest(&mut zip_archive)?;
+        let main = LazyDalf::from_archive(&mut zip_archive, manifest.dalf_main())?;
+        let dependencies = manifest
+            .dalf_dependencies()
+            .iter()
+            .map(|location| LazyDalf::from_archive(&mut zip_archive, location))
+            .collect::<DamlLfResult<Vec<_>>>()?;
+        Ok(Self {
+            manifest,
+            main,
+            dependencies,
+        })
+    }
+
+    /// The `manifest` information contained within this `LazyDarFile`.
+    pub const fn manifest(&self) -> &DarManifest {
+        &self.manifest
+    }
+
+    /// The name of the `main` archive of this `LazyDarFile`.
+    pub fn name(&self) -> &str {
+        &self.main.name
+    }
+
+    /// The package id of the `main` archive of this `LazyDarFile`.
+    pub fn main_package_id(&self) -> &str {
+        &self.main.hash
+    }
+
+    /// Return an Iterator of the ids of all packages in this `LazyDarFile`.
+    pub fn package_ids(&self) -> impl Iterator<Item = &str> {
+        self.dalfs().map(LazyDalf::hash).unique()
+    }
+
+    /// Return the [`PackageInfo`] of the package with the given `package_id` or `None` if no such package exists.
+    ///
+    /// Only the package metadata is decoded and so this is considerably cheaper than decoding the package.
+    pub fn package_info(&self, package_id: &str) -> DamlLfResult<Option<&PackageInfo>> {
+        self.dalf(package_id).map(LazyDalf::info).transpose()
+    }
+
+    /// Return the decoded [`DamlLfArchive`] with the given `package_id` or `None` if no such package exists.
+    ///
+    /// The package is decoded on first access.
+    pub fn archive(&self, package_id: &str) -> DamlLfResult<Option<&DamlLfArchive>> {
+        self.dalf(package_id).map(LazyDalf::archive).transpose()
+    }
+
+    /// Create a [`DamlLazyArchive`] from this `LazyDarFile` and apply it to `f`.
+    ///
+    /// The [`DamlLazyArchive`] borrows from this `LazyDarFile` and so cannot be returned from this scope.  Packages
+    /// decoded by the [`DamlLazyArchive`] remain cached in this `LazyDarFile` whereas converted elements are dropped
+    /// at the end of the scope.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use daml_lf::{LazyDarFile, DamlLfResult};
+    /// # fn main() -> DamlLfResult<()> {
+    /// let dar = LazyDarFile::from_file("Example.dar")?;
+    /// let fields = dar.apply(|archive| -> DamlLfResult<usize> {
+    ///     let data = archive.data(archive.main_package_id(), &["Fuji", "PingPong"], "Ping")?;
+    ///     Ok(data.map_or(0, |data| data.fields().len()))
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&DamlLazyArchive<'_>) -> R,
+    {
+        convert::apply_lazy_dar(self, f)
+    }
+
+    pub(crate) fn dalfs(&self) -> impl Iterator<Item = &LazyDalf> {
+        self.dependencies.iter().chain(iter::once(&self.main))
+    }
+
+    fn dalf(&self, package_id: &str) -> Option<&LazyDalf> {
+        self.dalfs().find(|dalf| dalf.hash == package_id)
+    }
+}
+
+/// A `dalf` within a [`LazyDarFile`] which is decoded on first access.
+#[derive(Debug)]
+pub struct LazyDalf {
+    name: String,
+    hash: String,
+    hash_function: DamlLfHashFunction,
+    payload: Bytes,
+    info: OnceCell<PackageInfo>,
+    archive: OnceCell<DamlLfArchive>,
+}
+
+impl LazyDalf {
+    fn from_archive<R: Read + Seek>(zip_archive: &mut ZipArchive<R>, location: &str) -> DamlLfResult<Self> {
+        let (name, bytes) = DarFile::read_dalf_from_archive(zip_archive, location)?;
+        let archive = Archive::decode(Bytes::from(bytes))?;
+        Ok(Self {
+            name: sanitize_name(&name, &archive.hash).to_owned(),
+            hash_function: DamlLfHashFunction::try_from(archive.hash_function)?,
+            hash: archive.hash,
+            payload: Bytes::from(archive.payload),
+            info: OnceCell::new(),
+            archive: OnceCell::new(),
+        })
+    }
+
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// The [`PackageInfo`] of this `dalf`, decoding only the package metadata.
+    pub fn info(&self) -> DamlLfResult<&PackageInfo> {
+        self.info.get_or_try_init(|| self.decode_info())
+    }
+
+    /// The [`DamlLfArchive`] of this `dalf`, decoding the package.
+    pub fn archive(&self) -> DamlLfResult<&DamlLfArchive> {
+        self.archive.get_or_try_init(|| {
+            DamlLfArchive::from_payload_bytes(
+                self.name.clone(),
+                self.payload.clone(),
+                self.hash_function.clone(),
+                self.hash.clone(),
+            )
+        })
+    }
+
+    fn decode_info(&self) -> DamlLfResult<PackageInfo> {
+        let header = ArchivePayloadHeader::decode(self.payload.clone())?;
+        let language_version = LanguageVersion::new_v1(LanguageV1MinorVersion::try_from(header.minor.as_str())?);
+        let package = header.daml_lf_1.ok_or_else(|| DamlLfError::new_unknown_version("none"))?;
+        if language_version.supports_feature(&LanguageFeatureVersion::PACKAGE_METADATA) {
+            let metadata = package.metadata.ok_or(DamlLfConvertError::MissingRequiredField)?;
+            let interned = |index: i32| {
+                package.interned_strings.get(index as usize).cloned().ok_or(DamlLfConvertError::MissingRequiredField)
+            };
+            let name = interned(metadata.name_interned_str)?;
+            let version = interned(metadata.version_interned_str)?;
+            Ok(PackageInfo::new(&self.hash, name, version, language_version))
+        } else {
+            Ok(PackageInfo::new(&self.hash, &self.name, None, language_version))
+        }
+    }
+}
+
+/// The subset of an `ArchivePayload` required to determine the name and version of the package.
+///
+/// All other fields, notably the modules of the package, are skipped without being decoded.
+#[derive(Clone, PartialEq, Message)]
+struct ArchivePayloadHeader {
+    #[prost(string, tag = "3")]
+    minor: String,
+    #[prost(message, optional, tag = "2")]
+    daml_lf_1: Option<PackageHeader>,
+}
+
+/// The subset of a `Package` required to determine the name and version of the package.
+#[derive(Clone, PartialEq, Message)]
+struct PackageHeader {
+    #[prost(string, repeated, tag = "2")]
+    interned_strings: Vec<String>,
+    #[prost(message, optional, tag = "4")]
+    metadata: Option<PackageMetadata>,
+}
+
+/// A view of the packages of a `dar` which converts packages and modules on first access.
+///
+/// Unlike a [`DamlArchive`](crate::element::DamlArchive), which is converted in full, a `DamlLazyArchive` converts a
+/// [`DamlPackage`] or a [`DamlModule`] only when it is first requested and caches the result.  Converting a module also
+/// decodes those modules of other packages which it refers to, but no others.
+///
+/// Every method which may trigger a conversion returns a [`DamlLfResult`] as any failure to decode or convert a package
+/// is only detected on first access.
+///
+/// Modules returned from a `DamlLazyArchive` are leaf modules, they do not include any child modules.
+///
+/// A `DamlLazyArchive` is created by [`LazyDarFile::apply`] or [`DarFile::apply_lazy`].
+pub struct DamlLazyArchive<'a> {
+    archive: &'a DamlArchivePayload<'a>,
+    packages: HashMap<&'a str, DamlLazyPackage<'a>>,
+}
+
+#[derive(Default)]
+struct DamlLazyPackage<'a> {
+    package: OnceCell<DamlPackage<'a>>,
+    modules: OnceCell<HashMap<&'a str, OnceCell<DamlModule<'a>>>>,
+}
+
+impl<'a> DamlLazyArchive<'a> {
+    pub(crate) fn new(archive: &'a DamlArchivePayload<'a>) -> Self {
+        Self {
+            archive,
+            packages: archive.packages.keys().map(|&package_id| (package_id, DamlLazyPackage::default())).collect(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.archive.name
+    }
+
+    /// Return the package id of the main `DamlPackage` contained in this `DamlLazyArchive`.
+    pub fn main_package_id(&self) -> &str {
+        self.archive.main_package_id
+    }
+
+    /// Return an Iterator of the ids of all packages in this `DamlLazyArchive`.
+    ///
+    /// No packages are decoded or converted.
+    pub fn package_ids(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.packages.keys().copied()
+    }
+
+    /// Return the [`DamlPackage`] with the given `package_id` or `None` if no such package exists.
+    ///
+    /// All modules of the package are converted on first access.
+    pub fn package_by_id(&self, package_id: &str) -> DamlLfResult<Option<&DamlPackage<'a>>> {
+        match self.packages.get(package_id) {
+            Some(lazy) => Ok(Some(lazy.package.get_or_try_init(|| {
+                convert::convert_package(self.archive, package_id)?.ok_or_else(|| unknown_package(package_id))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Return the first [`DamlPackage`] which has the given `name` or `None` if no such package exists.
+    ///
+    /// Only the package metadata is decoded to find the package and only the matching package is converted.
+    pub fn package_by_name(&self, name: &str) -> DamlLfResult<Option<&DamlPackage<'a>>> {
+        for (&package_id, package) in &self.archive.packages {
+            if package.name()? == name {
+                return self.package_by_id(package_id);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Return the main [`DamlPackage`] or `None` if no such package exists.
+    pub fn main_package(&self) -> DamlLfResult<Option<&DamlPackage<'a>>> {
+        self.package_by_id(self.archive.main_package_id)
+    }
+
+    /// Return the leaf [`DamlModule`] with the given package id and module path or `None` if no such module exists.
+    ///
+    /// Only the requested module, and those modules it refers to, are converted on first access.
+    pub fn module<M: AsRef<str>>(&self, package_id: &str, module_path: &[M]) -> DamlLfResult<Option<&DamlModule<'a>>> {
+        let lazy = match self.packages.get(package_id) {
+            Some(lazy) => lazy,
+            None => return Ok(None),
+        };
+        let modules = lazy.modules.get_or_try_init(|| -> DamlLfResult<_> {
+            let package = self.archive.package_by_id(package_id)?.ok_or_else(|| unknown_package(package_id))?;
+            Ok(package.modules.keys().map(|path| (path.as_str(), OnceCell::new())).collect())
+        })?;
+        let path = module_path.iter().map(AsRef::as_ref).join(".");
+        match modules.get(path.as_str()) {
+            Some(module) => Ok(Some(module.get_or_try_init(|| {
+                convert::convert_module(self.archive, package_id, &path)?
+                    .ok_or_else(|| DamlLfConvertError::UnknownModule(path.clone()))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieve the [`DamlData`] referred to by the supplied [`DamlTyCon`] or `None` if no such data item exists.
+    pub fn data_by_tycon(&self, tycon: &DamlTyCon<'_>) -> DamlLfResult<Option<&DamlData<'a>>> {
+        self.data_by_tycon_name(tycon.tycon())
+    }
+
+    /// Retrieve the [`DamlData`] referred to by the supplied [`DamlTyConName`] or `None` if no such data item exists.
+    pub fn data_by_tycon_name(&self, tycon_name: &DamlTyConName<'_>) -> DamlLfResult<Option<&DamlData<'a>>> {
+        let (package_id, module_path, data_name) = tycon_name.reference_parts();
+        self.data(package_id, module_path, data_name)
+    }
+
+    /// Retrieve the [`DamlData`] for the supplied package id, module path & name or `None` if no such data item exists.
+    pub fn data<M, D>(&self, package_id: &str, module_path: &[M], data_name: D) -> DamlLfResult<Option<&DamlData<'a>>>
+    where
+        M: AsRef<str>,
+        D: AsRef<str>,
+    {
+        Ok(self.module(package_id, module_path)?.and_then(|module| module.data_type(data_name)))
+    }
+
+    /// Retrieve the [`DamlDefValue`] for a given [`DamlValueName`] or `None` if no such value exists.
+    #[cfg(feature = "full")]
+    pub fn value_by_name(&self, name: &DamlValueName<'_>) -> DamlLfResult<Option<&DamlDefValue<'a>>> {
+        let (package_id, module_path, name) = name.reference_parts();
+        self.value(package_id, module_path, name)
+    }
+
+    /// Retrieve the [`DamlDefValue`] for the supplied package id, module path & name or `None` if no such value
+    /// exists.
+    #[cfg(feature = "full")]
+    pub fn value<M, D>(&self, package_id: &str, module_path: &[M], name: D) -> DamlLfResult<Option<&DamlDefValue<'a>>>
+    where
+        M: AsRef<str>,
+        D: AsRef<str>,
+    {
+        Ok(self.module(package_id, module_path)?.and_then(|module| module.value(name)))
+    }
+}
+
+fn unknown_package(package_id: &str) -> DamlLfConvertError {
+    DamlLfConvertError::UnknownPackage(package_id.to_owned())
+}
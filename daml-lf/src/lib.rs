@@ -52,12 +52,74 @@
 //! # }
 //! ```
 //!
+//! # Lazy Loading
+//!
+//! Converting a [`DarFile`] to a [`DamlArchive`](`element::DamlArchive`) decodes every package and converts every
+//! module, which can be expensive for large `dar` files, especially with the `full` feature enabled.  A
+//! [`LazyDarFile`] instead retains the raw `dalf` bytes and a [`DamlLazyArchive`] decodes and converts packages and
+//! modules on first access, caching the results.
+//!
+//! The following example loads `Example.dar` from a file and converts only the package and module required to look up
+//! a single data type:
+//!
+//! ```no_run
+//! # use daml_lf::LazyDarFile;
+//! # use daml_lf::DamlLfResult;
+//! # fn main() -> DamlLfResult<()> {
+//! let dar = LazyDarFile::from_file("Example.dar")?;
+//! let field_count = dar.apply(|archive| {
+//!     let data = archive.data(archive.main_package_id(), &["Fuji", "PingPong"], "Ping")?;
+//!     DamlLfResult::Ok(data.map(|data| data.fields().len()))
+//! })?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Pretty Printing
+//!
+//! The [`PrettyPrinter`] renders [`element`] items, such as types, templates, modules and packages, in a readable
+//! Daml-like syntax.  Expressions, such as template signatories and choice bodies, are rendered if the `full` feature is
+//! enabled.
+//!
+//! # Type Checking
+//!
+//! The [`DamlTypeChecker`] infers and verifies the types of all expressions in a
+//! [`DamlArchive`](`element::DamlArchive`) and reports any errors along with the module and definition in which they
+//! were found.  This can be used to vet hand crafted or transformed archives before they are uploaded to a ledger.
+//! Type checking requires the `full` feature.
+//!
+//! # Encoding
+//!
+//! The [`element`] items of a package may be modified, such as with [`DamlPackage::set_name`],
+//! [`DamlPackage::remove_module`] or [`DamlModule::insert_data_type`], and then encoded back to a
+//! [`DamlLfArchivePayload`] with [`DamlLfArchivePayload::from_package`].  Strings and dotted names are interned as
+//! required by the language version of the package.  A [`DamlLfArchive`] with a new `hash` (package id) can then be
+//! created with [`DamlLfArchive::from_payload`].  Encoding requires the `full` feature.
+//!
+//! Note that exception definitions and source locations are not represented by [`element`] items and so are omitted
+//! from the encoded package.
+//!
+//! [`DamlPackage::set_name`]: element::DamlPackage::set_name
+//! [`DamlPackage::remove_module`]: element::DamlPackage::remove_module
+//! [`DamlModule::insert_data_type`]: element::DamlModule::insert_data_type
+//!
+//! # Dependencies, Compatibility & Diff
+//!
+//! The [`DamlDependencyGraph`] records the references between the packages and modules of a
+//! [`DamlArchive`](`element::DamlArchive`) and provides a topological ordering of them.  The
+//! [`DamlCompatibilityChecker`] compares two versions of a package, such as the main packages of an old and new
+//! [`DarFile`], and reports any [`DamlBreakingChange`] to templates, choices, fields, variants and keys.
+//!
+//! The [`DamlArchiveDiff`] reports all structural changes between the main packages of two archives and can be
+//! rendered as text or serialized.
+//!
 //! # Features
 //!
 //! The following features are defined:
 //!
 //! - `default` Includes all `Daml-LF` types _except_ Daml expressions.
 //! - `full` Includes all `Daml-LF` types.
+//! - `async` Includes support for loading `dar` files from asynchronous readers.
 //!
 //! # Downloading Daml Packages
 //!
@@ -71,7 +133,7 @@
 //! # Versions
 //!
 //! This library supports all Daml-LF [`LanguageVersion`] from [`LanguageVersion::V1_0`] up to
-//! [`LanguageVersion::V1_14`].
+//! [`LanguageVersion::V1_15`].
 //!
 //! [`daml-util`]: https://docs.rs/daml-util/0.2.2/daml_util/
 //! [`DamlPackages`]: https://docs.rs/daml-util/0.2.2/daml_util/package/struct.DamlPackages.html
@@ -91,13 +153,22 @@
 #![doc(html_root_url = "https://docs.rs/daml-lf/0.2.2")]
 
 mod archive;
+mod compatibility;
 mod convert;
 mod dar;
+mod dependency;
+mod diff;
+#[cfg(feature = "full")]
+mod encode;
 mod error;
+mod lazy;
 mod lf_protobuf;
 mod manifest;
 mod package_info;
 mod payload;
+mod pretty;
+#[cfg(feature = "full")]
+mod typecheck;
 mod version;
 
 /// Representation of Daml types.
@@ -105,9 +176,18 @@ pub mod element;
 
 // reexport types
 pub use archive::{DamlLfArchive, DamlLfHashFunction, DEFAULT_ARCHIVE_NAME};
-pub use dar::DarFile;
-pub use error::{DamlLfError, DamlLfResult};
+pub use compatibility::{DamlBreakingChange, DamlCompatibilityChecker, DamlCompatibilityReport};
+pub use dar::{DarBuilder, DarFile};
+pub use dependency::{DamlDataReference, DamlDependencyGraph, DamlModuleId};
+pub use diff::{DamlArchiveDiff, DamlChangeKind, DamlChoiceDiff, DamlDataDiff, DamlModuleDiff, DamlPropertyDiff};
+pub use error::{DamlLfConvertError, DamlLfError, DamlLfResult};
+pub use lazy::{DamlLazyArchive, LazyDarFile};
+#[cfg(feature = "full")]
+pub use error::{DamlTypeCheckError, DamlTypeCheckErrorKind};
 pub use manifest::{DarEncryptionType, DarManifest, DarManifestFormat, DarManifestVersion};
 pub use package_info::PackageInfo;
 pub use payload::{DamlLfArchivePayload, DamlLfPackage};
+pub use pretty::PrettyPrinter;
+#[cfg(feature = "full")]
+pub use typecheck::{DamlTypeChecker, TypeCheckResult};
 pub use version::{LanguageFeatureVersion, LanguageV1MinorVersion, LanguageVersion};
@@ -203,12 +203,15 @@ impl DarManifest {
     }
 
     /// Render this `DarManifest`
+    ///
+    /// Note that the `main` `dalf` is always included as the first entry of the rendered `Dalfs` attribute.
     pub fn render(&self) -> String {
+        let mut all_dalfs = std::iter::once(self.dalf_main()).chain(self.dalf_dependencies().iter().map(String::as_str));
         vec![
             make_manifest_entry(MANIFEST_VERSION_KEY, self.version().to_string()),
             make_manifest_entry(CREATED_BY_KEY, self.created_by()),
             make_manifest_entry(DALF_MAIN_KEY, self.dalf_main()),
-            make_manifest_entry(DALFS_KEY, self.dalf_dependencies().iter().join(", ")),
+            make_manifest_entry(DALFS_KEY, all_dalfs.join(", ")),
             make_manifest_entry(FORMAT_KEY, self.format().to_string()),
             make_manifest_entry(ENCRYPTION_KEY, self.encryption().to_string()),
         ]
@@ -387,6 +390,28 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    pub fn test_render_round_trip() -> DamlLfResult<()> {
+        let manifest = DarManifest::new(
+            DarManifestVersion::V1,
+            "daml-lf",
+            "A-1234.dalf",
+            vec!["B-5678.dalf".to_owned(), "C-9abc.dalf".to_owned()],
+            DarManifestFormat::DamlLf,
+            DarEncryptionType::NotEncrypted,
+        );
+        let rendered = manifest.render();
+        assert!(rendered.contains("Dalfs: A-1234.dalf, B-5678.dalf, C-9abc.dalf"));
+        let parsed = DarManifest::parse(&rendered)?;
+        assert_eq!(DarManifestVersion::V1, parsed.version());
+        assert_eq!("daml-lf", parsed.created_by());
+        assert_eq!("A-1234.dalf", parsed.dalf_main());
+        assert_eq!(&vec!["B-5678.dalf", "C-9abc.dalf"], parsed.dalf_dependencies());
+        assert_eq!(DarManifestFormat::DamlLf, parsed.format());
+        assert_eq!(DarEncryptionType::NotEncrypted, parsed.encryption());
+        Ok(())
+    }
+
     #[test]
     pub fn test_invalid_format() {
         let manifest_str = "
@@ -1,8 +1,9 @@
 use crate::element::{DamlArchive, DamlPackage};
 use crate::{DamlLfResult, DarFile, LanguageVersion};
+use serde::Serialize;
 
 /// Information about a `DamlPackage`.
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize)]
 pub struct PackageInfo {
     pub package_id: String,
     pub package_name: String,
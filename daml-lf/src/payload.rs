@@ -4,6 +4,8 @@ use crate::lf_protobuf::com::daml::daml_lf::archive_payload::Sum;
 use crate::lf_protobuf::com::daml::daml_lf::ArchivePayload;
 use crate::lf_protobuf::com::daml::daml_lf_1;
 use crate::lf_protobuf::com::daml::daml_lf_1::module::Name;
+#[cfg(feature = "full")]
+use crate::encode;
 use crate::{convert, LanguageV1MinorVersion, LanguageVersion};
 use bytes::Bytes;
 use itertools::Itertools;
@@ -30,6 +32,26 @@ impl DamlLfArchivePayload {
         }
     }
 
+    /// Encode a (possibly modified) [`DamlPackage`] as a `DamlLfArchivePayload`.
+    ///
+    /// Strings and dotted names are interned as required by the language version of the `package`.  Source locations
+    /// are not represented by [`DamlPackage`] and so are omitted from the payload.
+    ///
+    /// Use [`DamlLfArchive::from_payload`] to create an archive, and hence a new package id, for the payload.
+    ///
+    /// # Errors
+    ///
+    /// If the `package` contains an element which cannot be encoded for its language version, such as an interface
+    /// prior to `Daml LF` `1.15` or an exception prior to `Daml LF` `1.14`, then [`DamlLfConvertError`] will be
+    /// returned.
+    ///
+    /// [`DamlLfArchive::from_payload`]: crate::DamlLfArchive::from_payload
+    /// [`DamlLfConvertError`]: DamlLfError::DamlLfConvertError
+    #[cfg(feature = "full")]
+    pub fn from_package(package: &DamlPackage<'_>) -> DamlLfResult<Self> {
+        Ok(encode::encode_package(package)?)
+    }
+
     /// Create a `DamlLfArchivePayload` from a serialized protobuf byte buffer.
     ///
     /// This method is suitable for use with the bytes returned by the [`payload()`] method of [`DamlPackage`] which is
@@ -76,6 +98,34 @@ impl DamlLfArchivePayload {
         }
     }
 
+    /// Serialize this `DamlLfArchivePayload` to the protobuf binary representation.
+    ///
+    /// Note that the serialized bytes are not guaranteed to be identical to the bytes this payload was originally
+    /// deserialized from and so may not match the `hash` of the archive which contains this payload.  The
+    /// [`DamlLfArchive`] retains the original bytes, where available, and writes them unchanged.
+    ///
+    /// [`DamlLfArchive`]: crate::DamlLfArchive
+    ///
+    /// # Errors
+    ///
+    /// Payloads of `Daml LF` `v0` are not supported and will result in a [`UnsupportedVersion`] being returned.
+    ///
+    /// [`UnsupportedVersion`]: DamlLfError::UnsupportedVersion
+    pub fn to_bytes(&self) -> DamlLfResult<Vec<u8>> {
+        let minor = match self.language_version {
+            LanguageVersion::Lv1(minor) => Ok(minor.to_string()),
+            LanguageVersion::Lv0 => Err(DamlLfError::new_unsupported_version(self.language_version.to_string())),
+        }?;
+        let sum = match &self.package {
+            DamlLfPackage::V1(package) => Sum::DamlLf1(package.clone()),
+        };
+        let payload = ArchivePayload {
+            minor,
+            sum: Some(sum),
+        };
+        Ok(payload.encode_to_vec())
+    }
+
     /// Create a [`DamlArchive`] from this [`DamlLfArchivePayload`] and apply it to `f`.
     ///
     /// See [`DarFile::apply`] for details.
@@ -0,0 +1,986 @@
+mod doc;
+
+use crate::element::{
+    DamlArrow, DamlChoice, DamlData, DamlDefKey, DamlDefTypeSyn, DamlEnum, DamlField, DamlInterface,
+    DamlInterfaceInstance, DamlInterfaceMethod, DamlKind, DamlModule, DamlPackage, DamlRecord, DamlTemplate,
+    DamlTyConName, DamlType, DamlTypeVarWithKind, DamlVariant,
+};
+#[cfg(feature = "full")]
+use crate::element::{
+    DamlBinding, DamlBlock, DamlCase, DamlCaseAlt, DamlCaseAltSum, DamlCons, DamlDefValue, DamlExpr, DamlFieldWithExpr,
+    DamlInterfaceCast, DamlInterfaceExpr, DamlPrimCon, DamlPrimLit, DamlRecCon, DamlRequiredInterfaceCast,
+    DamlScenario, DamlUnsafeFromInterface, DamlUnsafeFromRequiredInterface, DamlUpdate, DamlValueName, DamlVarWithType,
+    DamlVariantCon,
+};
+use doc::Doc;
+use itertools::Itertools;
+
+const DEFAULT_WIDTH: usize = 100;
+const DEFAULT_INDENT: usize = 2;
+
+/// Render `Daml-LF` elements in a readable, Daml-like, surface syntax.
+///
+/// Types, data types, templates, interfaces, modules and packages can always be rendered.  With the `full` feature
+/// enabled the expressions contained within definitions, template bodies (precondition, signatories, observers, key and
+/// choice updates) and interface instances are also rendered.
+///
+/// Note that the rendered output is intended to aid reading and auditing `Daml-LF` and is not valid Daml source.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use daml_lf::{DarFile, PrettyPrinter};
+/// # use daml_lf::DamlLfResult;
+/// # fn main() -> DamlLfResult<()> {
+/// let dar = DarFile::from_file("Example.dar")?;
+/// let printer = PrettyPrinter::new().width(80).elide_package_ids(true);
+/// let rendered =
+///     dar.apply(|archive| archive.main_package().map(|package| printer.render_package(package)))?;
+/// println!("{}", rendered.unwrap_or_default());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PrettyPrinter {
+    width: usize,
+    indent: usize,
+    elide_package_ids: bool,
+}
+
+impl Default for PrettyPrinter {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_WIDTH,
+            indent: DEFAULT_INDENT,
+            elide_package_ids: false,
+        }
+    }
+}
+
+impl PrettyPrinter {
+    /// Create a `PrettyPrinter` with the default width (100), indent (2) and with package ids shown.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum line width.
+    ///
+    /// Lines are broken such that they do not exceed this width where possible.
+    #[must_use]
+    pub fn width(self, width: usize) -> Self {
+        Self {
+            width,
+            ..self
+        }
+    }
+
+    /// Set the number of spaces used for each level of indentation.
+    #[must_use]
+    pub fn indent(self, indent: usize) -> Self {
+        Self {
+            indent,
+            ..self
+        }
+    }
+
+    /// Omit package ids when rendering qualified names, i.e. render `Module:Name` rather than `package_id:Module:Name`.
+    #[must_use]
+    pub fn elide_package_ids(self, elide_package_ids: bool) -> Self {
+        Self {
+            elide_package_ids,
+            ..self
+        }
+    }
+
+    /// Render a [`DamlType`].
+    pub fn render_type(&self, ty: &DamlType<'_>) -> String {
+        self.type_doc(ty).render(self.width)
+    }
+
+    /// Render a [`DamlData`] definition.
+    pub fn render_data(&self, data: &DamlData<'_>) -> String {
+        self.data_doc(data).render(self.width)
+    }
+
+    /// Render a [`DamlInterface`] definition.
+    pub fn render_interface(&self, interface: &DamlInterface<'_>) -> String {
+        self.interface_doc(interface).render(self.width)
+    }
+
+    /// Render all definitions of a [`DamlModule`], excluding any child modules.
+    pub fn render_module(&self, module: &DamlModule<'_>) -> String {
+        self.module_doc(module).render(self.width)
+    }
+
+    /// Render all modules of a [`DamlPackage`] which contain at least one definition.
+    pub fn render_package(&self, package: &DamlPackage<'_>) -> String {
+        let id = if self.elide_package_ids {
+            String::new()
+        } else {
+            format!(" ({})", package.package_id())
+        };
+        let header = package.version().map_or_else(
+            || format!("package {}{id}", package.name()),
+            |version| format!("package {}-{version}{id}", package.name()),
+        );
+        let modules = all_modules(package.root_module())
+            .into_iter()
+            .filter(|module| has_definitions(module))
+            .map(|module| self.module_doc(module));
+        Doc::join(std::iter::once(Doc::text(header)).chain(modules), &blank_line()).render(self.width)
+    }
+
+    /// Render a [`DamlDefValue`] definition.
+    #[cfg(feature = "full")]
+    pub fn render_def_value(&self, def_value: &DamlDefValue<'_>) -> String {
+        self.def_value_doc(def_value).render(self.width)
+    }
+
+    /// Render a [`DamlExpr`].
+    #[cfg(feature = "full")]
+    pub fn render_expr(&self, expr: &DamlExpr<'_>) -> String {
+        self.expr_doc(expr).render(self.width)
+    }
+
+    fn module_doc(&self, module: &DamlModule<'_>) -> Doc {
+        let header = Doc::text(format!("module {} where", module.path().join(".")));
+        let synonyms =
+            module.synonyms().iter().sorted_by_key(|syn| syn.name().join(".")).map(|syn| self.synonym_doc(syn));
+        let data = module.data_types().sorted_by(|a, b| a.name().cmp(b.name())).map(|data| self.data_doc(data));
+        let interfaces =
+            module.interfaces().sorted_by(|a, b| a.name().cmp(b.name())).map(|interface| self.interface_doc(interface));
+        let definitions = synonyms.chain(data).chain(interfaces);
+        #[cfg(feature = "full")]
+        let definitions = definitions
+            .chain(module.values().sorted_by(|a, b| a.name().cmp(b.name())).map(|value| self.def_value_doc(value)));
+        Doc::join(std::iter::once(header).chain(definitions), &blank_line())
+    }
+
+    fn synonym_doc(&self, syn: &DamlDefTypeSyn<'_>) -> Doc {
+        let header = format!("synonym {}{} =", syn.name().join("."), type_params(syn.params()));
+        Doc::group(Doc::concat(vec![
+            Doc::text(header),
+            Doc::nest(self.indent, Doc::concat(vec![Doc::Line, self.type_doc(syn.ty())])),
+        ]))
+    }
+
+    fn data_doc(&self, data: &DamlData<'_>) -> Doc {
+        match data {
+            DamlData::Template(template) => self.template_doc(template),
+            DamlData::Record(record) => self.record_doc(record),
+            DamlData::Variant(variant) => self.variant_doc(variant),
+            DamlData::Enum(data_enum) => self.enum_doc(data_enum),
+        }
+    }
+
+    fn record_doc(&self, record: &DamlRecord<'_>) -> Doc {
+        let header = format!("record {}{} =", record.name(), type_params(record.type_params()));
+        let fields = record.fields().iter().map(|field| self.field_doc(field));
+        let body = Doc::concat(vec![
+            Doc::Line,
+            Doc::text("{ "),
+            Doc::join(fields, &Doc::concat(vec![Doc::SoftLine, Doc::text(", ")])),
+            Doc::Line,
+            Doc::text("}"),
+        ]);
+        Doc::group(Doc::concat(vec![Doc::text(header), Doc::nest(self.indent, body)]))
+    }
+
+    fn variant_doc(&self, variant: &DamlVariant<'_>) -> Doc {
+        let header = format!("variant {}{} =", variant.name(), type_params(variant.type_params()));
+        let constructors = variant
+            .fields()
+            .iter()
+            .map(|field| Doc::concat(vec![Doc::text(format!("{} ", field.name())), self.type_atom(field.ty())]));
+        let body =
+            Doc::concat(vec![Doc::Line, Doc::join(constructors, &Doc::concat(vec![Doc::Line, Doc::text("| ")]))]);
+        Doc::group(Doc::concat(vec![Doc::text(header), Doc::nest(self.indent, body)]))
+    }
+
+    fn enum_doc(&self, data_enum: &DamlEnum<'_>) -> Doc {
+        let header = format!("enum {}{} =", data_enum.name(), type_params(data_enum.type_params()));
+        let constructors = data_enum.constructors().map(Doc::text);
+        let body =
+            Doc::concat(vec![Doc::Line, Doc::join(constructors, &Doc::concat(vec![Doc::Line, Doc::text("| ")]))]);
+        Doc::group(Doc::concat(vec![Doc::text(header), Doc::nest(self.indent, body)]))
+    }
+
+    fn template_doc(&self, template: &DamlTemplate<'_>) -> Doc {
+        let header = Doc::text(format!("template ({} : {}) with", template.param(), template.name()));
+        let fields = self.fields_block(template.fields(), self.indent * 2);
+        let mut items = vec![];
+        #[cfg(feature = "full")]
+        {
+            items.push(self.keyword_item("signatory", self.expr_doc(template.signatories())));
+            items.push(self.keyword_item("observer", self.expr_doc(template.observers())));
+            if let Some(precond) = template.precond() {
+                items.push(self.keyword_item("ensure", self.expr_doc(precond)));
+            }
+            items.push(self.keyword_item("agreement", self.expr_doc(template.agreement())));
+        }
+        if let Some(key) = template.key() {
+            items.extend(self.key_docs(key));
+        }
+        items.extend(template.choices().iter().map(|choice| self.choice_doc(choice)));
+        items.extend(template.implements().iter().map(|instance| self.interface_instance_doc(instance)));
+        Doc::concat(vec![
+            header,
+            fields,
+            Doc::nest(self.indent, Doc::concat(vec![Doc::HardLine, Doc::text("where")])),
+            Doc::nest(
+                self.indent * 2,
+                Doc::concat(items.into_iter().map(|item| Doc::concat(vec![Doc::HardLine, item]))),
+            ),
+        ])
+    }
+
+    #[cfg(feature = "full")]
+    fn key_docs(&self, key: &DamlDefKey<'_>) -> Vec<Doc> {
+        vec![
+            self.keyword_item(
+                "key",
+                Doc::concat(vec![self.expr_atom(key.key_expr()), Doc::text(" : "), self.type_doc(key.ty())]),
+            ),
+            self.keyword_item("maintainer", self.expr_doc(key.maintainers())),
+        ]
+    }
+
+    #[cfg(not(feature = "full"))]
+    fn key_docs(&self, key: &DamlDefKey<'_>) -> Vec<Doc> {
+        vec![self.keyword_item("key", Doc::concat(vec![Doc::text(": "), self.type_doc(key.ty())]))]
+    }
+
+    fn choice_doc(&self, choice: &DamlChoice<'_>) -> Doc {
+        let consuming = if choice.consuming() {
+            ""
+        } else {
+            "nonconsuming "
+        };
+        let header = Doc::concat(vec![
+            Doc::text(format!("choice {}{} ({}) : ", consuming, choice.name(), choice.self_binder())),
+            self.type_doc(choice.return_type()),
+            Doc::text(if choice.fields().is_empty() {
+                ""
+            } else {
+                " with"
+            }),
+        ]);
+        let fields = self.fields_block(choice.fields(), self.indent * 2);
+        #[cfg(feature = "full")]
+        let body = Doc::nest(
+            self.indent,
+            Doc::concat(vec![
+                Doc::HardLine,
+                self.keyword_item("controller", self.expr_doc(choice.controllers())),
+                Doc::HardLine,
+                self.keyword_item("observer", self.expr_doc(choice.observers())),
+                Doc::HardLine,
+                self.keyword_item("do", self.expr_doc(choice.update())),
+            ]),
+        );
+        #[cfg(not(feature = "full"))]
+        let body = Doc::concat(vec![]);
+        Doc::concat(vec![header, fields, body])
+    }
+
+    fn interface_doc(&self, interface: &DamlInterface<'_>) -> Doc {
+        let header = Doc::text(format!("interface ({} : {}) where", interface.param(), interface.name()));
+        let mut items = vec![];
+        if !interface.requires().is_empty() {
+            let requires = interface.requires().iter().map(|name| self.tycon_name(name)).join(", ");
+            items.push(Doc::text(format!("requires {requires}")));
+        }
+        items.push(self.keyword_item("viewtype", self.type_doc(interface.view())));
+        items.extend(interface.methods().iter().map(|method| self.interface_method_doc(method)));
+        items.extend(interface.choices().iter().map(|choice| self.choice_doc(choice)));
+        Doc::concat(vec![
+            header,
+            Doc::nest(
+                self.indent * 2,
+                Doc::concat(items.into_iter().map(|item| Doc::concat(vec![Doc::HardLine, item]))),
+            ),
+        ])
+    }
+
+    fn interface_method_doc(&self, method: &DamlInterfaceMethod<'_>) -> Doc {
+        Doc::concat(vec![Doc::text(format!("method {} : ", method.name())), self.type_doc(method.ty())])
+    }
+
+    fn interface_instance_doc(&self, instance: &DamlInterfaceInstance<'_>) -> Doc {
+        let header = Doc::text(format!("interface instance {}", self.tycon_name(instance.interface())));
+        #[cfg(feature = "full")]
+        let items = std::iter::once(self.keyword_item("view =", self.expr_doc(instance.view()))).chain(
+            instance
+                .methods()
+                .iter()
+                .map(|method| self.keyword_item(&format!("method {} =", method.name()), self.expr_doc(method.value()))),
+        );
+        #[cfg(not(feature = "full"))]
+        let items = instance.methods().iter().map(|method| Doc::text(format!("method {}", method.name())));
+        Doc::concat(vec![
+            header,
+            Doc::nest(self.indent, Doc::concat(items.map(|item| Doc::concat(vec![Doc::HardLine, item])))),
+        ])
+    }
+
+    /// Render each field on its own line, nested by `indent`.
+    fn fields_block(&self, fields: &[DamlField<'_>], indent: usize) -> Doc {
+        Doc::nest(
+            indent,
+            Doc::concat(fields.iter().map(|field| Doc::concat(vec![Doc::HardLine, self.field_doc(field)]))),
+        )
+    }
+
+    fn field_doc(&self, field: &DamlField<'_>) -> Doc {
+        Doc::concat(vec![Doc::text(format!("{} : ", field.name())), self.type_doc(field.ty())])
+    }
+
+    /// A `keyword` followed by `body` on the same line if it fits, or on the next (nested) line otherwise.
+    fn keyword_item(&self, keyword: &str, body: Doc) -> Doc {
+        Doc::group(Doc::concat(vec![Doc::text(keyword), Doc::nest(self.indent, Doc::concat(vec![Doc::Line, body]))]))
+    }
+
+    fn tycon_name(&self, name: &DamlTyConName<'_>) -> String {
+        self.qualified(name.package_id(), &name.module_path().join("."), name.data_name())
+    }
+
+    fn qualified(&self, package_id: &str, module: &str, name: &str) -> String {
+        if self.elide_package_ids {
+            format!("{module}:{name}")
+        } else {
+            format!("{package_id}:{module}:{name}")
+        }
+    }
+
+    fn type_doc(&self, ty: &DamlType<'_>) -> Doc {
+        match ty {
+            DamlType::ContractId(Some(inner)) => self.type_app("ContractId", std::slice::from_ref(inner.as_ref())),
+            DamlType::ContractId(None) => Doc::text("ContractId"),
+            DamlType::Numeric(args) => self.type_app("Numeric", args),
+            DamlType::List(args) => match args.as_slice() {
+                [elem] => Doc::concat(vec![Doc::text("["), self.type_doc(elem), Doc::text("]")]),
+                _ => self.type_app("List", args),
+            },
+            DamlType::TextMap(args) => self.type_app("TextMap", args),
+            DamlType::GenMap(args) => self.type_app("GenMap", args),
+            DamlType::Optional(args) => self.type_app("Optional", args),
+            DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) =>
+                self.type_app(&self.tycon_name(tycon.tycon()), tycon.type_arguments()),
+            DamlType::Var(var) => self.type_app(var.var(), var.type_arguments()),
+            DamlType::Syn(syn) => self.type_app(&self.tycon_name(syn.tysyn()), syn.args()),
+            DamlType::Nat(n) => Doc::text(n.to_string()),
+            DamlType::Forall(forall) => {
+                let vars = forall.vars().iter().map(type_var_with_kind).join(" ");
+                Doc::group(Doc::concat(vec![
+                    Doc::text(format!("forall {vars}.")),
+                    Doc::nest(self.indent, Doc::concat(vec![Doc::Line, self.type_doc(forall.body())])),
+                ]))
+            },
+            DamlType::Struct(daml_struct) => {
+                let fields = daml_struct.fields().iter().map(|field| self.field_doc(field));
+                Doc::group(Doc::concat(vec![
+                    Doc::text("<"),
+                    Doc::join(fields, &Doc::concat(vec![Doc::text(","), Doc::Line])),
+                    Doc::text(">"),
+                ]))
+            },
+            DamlType::Int64 => Doc::text("Int64"),
+            DamlType::Text => Doc::text("Text"),
+            DamlType::Timestamp => Doc::text("Timestamp"),
+            DamlType::Party => Doc::text("Party"),
+            DamlType::Bool => Doc::text("Bool"),
+            DamlType::Unit => Doc::text("Unit"),
+            DamlType::Date => Doc::text("Date"),
+            DamlType::Arrow(args) => match args.as_slice() {
+                [param, result] => {
+                    let parens = matches!(param, DamlType::Forall(_))
+                        || matches!(param, DamlType::Arrow(inner) if inner.len() == 2);
+                    let param = parens_if(parens, self.type_doc(param));
+                    Doc::group(Doc::concat(vec![param, Doc::text(" ->"), Doc::Line, self.type_doc(result)]))
+                },
+                _ => self.type_app("(->)", args),
+            },
+            DamlType::Any => Doc::text("Any"),
+            DamlType::TypeRep => Doc::text("TypeRep"),
+            DamlType::Bignumeric => Doc::text("BigNumeric"),
+            DamlType::RoundingMode => Doc::text("RoundingMode"),
+            DamlType::AnyException => Doc::text("AnyException"),
+            DamlType::Update(args) => self.type_app("Update", args),
+            DamlType::Scenario(args) => self.type_app("Scenario", args),
+        }
+    }
+
+    /// Render a type in argument position, parenthesized if required.
+    fn type_atom(&self, ty: &DamlType<'_>) -> Doc {
+        let needs_parens = match ty {
+            DamlType::ContractId(inner) => inner.is_some(),
+            DamlType::Numeric(args)
+            | DamlType::TextMap(args)
+            | DamlType::GenMap(args)
+            | DamlType::Optional(args)
+            | DamlType::Update(args)
+            | DamlType::Scenario(args)
+            | DamlType::Arrow(args) => !args.is_empty(),
+            DamlType::List(args) => args.len() != 1,
+            DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) => !tycon.type_arguments().is_empty(),
+            DamlType::Var(var) => !var.type_arguments().is_empty(),
+            DamlType::Syn(syn) => !syn.args().is_empty(),
+            DamlType::Forall(_) => true,
+            _ => false,
+        };
+        parens_if(needs_parens, self.type_doc(ty))
+    }
+
+    fn type_app(&self, head: &str, args: &[DamlType<'_>]) -> Doc {
+        self.apply(Doc::text(head), args.iter().map(|arg| self.type_atom(arg)).collect())
+    }
+
+    /// Render `head` applied to `args`, breaking before each argument if the application does not fit.
+    fn apply(&self, head: Doc, args: Vec<Doc>) -> Doc {
+        if args.is_empty() {
+            head
+        } else {
+            Doc::group(Doc::concat(vec![
+                head,
+                Doc::nest(self.indent, Doc::concat(args.into_iter().map(|arg| Doc::concat(vec![Doc::Line, arg])))),
+            ]))
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl PrettyPrinter {
+    fn def_value_doc(&self, def_value: &DamlDefValue<'_>) -> Doc {
+        Doc::concat(vec![
+            Doc::text(format!("{} : ", def_value.name())),
+            self.type_doc(def_value.ty()),
+            Doc::HardLine,
+            self.keyword_item(&format!("{} =", def_value.name()), self.expr_doc(def_value.expr())),
+        ])
+    }
+
+    fn value_name(&self, name: &DamlValueName<'_>) -> String {
+        self.qualified(name.package_id(), &name.module_path().join("."), name.name())
+    }
+
+    fn type_arg(&self, ty: &DamlType<'_>) -> Doc {
+        Doc::concat(vec![Doc::text("@"), self.type_atom(ty)])
+    }
+
+    fn tycon_arg(&self, name: &DamlTyConName<'_>) -> Doc {
+        Doc::text(format!("@{}", self.tycon_name(name)))
+    }
+
+    /// Render `keyword` applied to type arguments `types` and expression arguments `exprs`.
+    fn keyword_app(&self, keyword: &str, types: Vec<Doc>, exprs: &[&DamlExpr<'_>]) -> Doc {
+        let args = types.into_iter().chain(exprs.iter().map(|expr| self.expr_atom(expr))).collect();
+        self.apply(Doc::text(keyword), args)
+    }
+
+    fn expr_doc(&self, expr: &DamlExpr<'_>) -> Doc {
+        match expr {
+            DamlExpr::Var(var) => Doc::text(var.to_string()),
+            DamlExpr::Val(name) => Doc::text(self.value_name(name)),
+            DamlExpr::Builtin(builtin) => Doc::text(screaming_snake_case(&format!("{builtin:?}"))),
+            DamlExpr::PrimCon(prim_con) => Doc::text(prim_con_str(*prim_con)),
+            DamlExpr::PrimLit(prim_lit) => Doc::text(prim_lit_string(prim_lit)),
+            DamlExpr::RecCon(rec_con) => self.rec_con_doc(rec_con),
+            DamlExpr::RecProj(rec_proj) =>
+                Doc::concat(vec![self.expr_atom(rec_proj.record()), Doc::text(format!(".{}", rec_proj.field()))]),
+            DamlExpr::RecUpd(rec_upd) => self.with_update(rec_upd.record(), rec_upd.field(), rec_upd.update()),
+            DamlExpr::VariantCon(variant_con) => self.variant_con_doc(variant_con),
+            DamlExpr::EnumCon(enum_con) =>
+                Doc::text(format!("{}:{}", self.tycon_name(enum_con.tycon()), enum_con.enum_con())),
+            DamlExpr::StructCon(struct_con) => self.fields_with_expr("<", struct_con.fields(), ">"),
+            DamlExpr::StructProj(struct_proj) => Doc::concat(vec![
+                self.expr_atom(struct_proj.struct_expr()),
+                Doc::text(format!(".{}", struct_proj.field())),
+            ]),
+            DamlExpr::StructUpd(struct_upd) =>
+                self.with_update(struct_upd.struct_expr(), struct_upd.field(), struct_upd.update()),
+            DamlExpr::App(app) =>
+                self.apply(self.expr_atom(app.fun()), app.args().iter().map(|arg| self.expr_atom(arg)).collect()),
+            DamlExpr::TyApp(ty_app) =>
+                self.apply(self.expr_atom(ty_app.expr()), ty_app.types().iter().map(|ty| self.type_arg(ty)).collect()),
+            DamlExpr::Abs(abs) => {
+                let params = abs.params().iter().map(|param| self.var_with_type(param)).join(" ");
+                self.keyword_item(&format!("\\{params} ->"), self.expr_doc(abs.body()))
+            },
+            DamlExpr::TyAbs(ty_abs) => {
+                let params = ty_abs.params().iter().map(type_var_with_kind).join(" ");
+                self.keyword_item(&format!("/\\{params} ->"), self.expr_doc(ty_abs.body()))
+            },
+            DamlExpr::Case(case) => self.case_doc(case),
+            DamlExpr::Let(block) => self.let_doc(block),
+            DamlExpr::Nil(_) => Doc::text("[]"),
+            DamlExpr::Cons(cons) => self.cons_doc(cons),
+            DamlExpr::Update(update) => self.update_doc(update),
+            DamlExpr::Scenario(scenario) => self.scenario_doc(scenario),
+            DamlExpr::OptionalNone(_) => Doc::text("None"),
+            DamlExpr::OptionalSome(some) => self.keyword_app("Some", vec![], &[some.body()]),
+            DamlExpr::ToAny(to_any) => self.keyword_app("toAny", vec![self.type_arg(to_any.ty())], &[to_any.expr()]),
+            DamlExpr::FromAny(from_any) =>
+                self.keyword_app("fromAny", vec![self.type_arg(from_any.ty())], &[from_any.expr()]),
+            DamlExpr::TypeRep(ty) => self.keyword_app("typeRep", vec![self.type_arg(ty)], &[]),
+            DamlExpr::ToAnyException(to_any) =>
+                self.keyword_app("toAnyException", vec![self.type_arg(to_any.ty())], &[to_any.expr()]),
+            DamlExpr::FromAnyException(from_any) =>
+                self.keyword_app("fromAnyException", vec![self.type_arg(from_any.ty())], &[from_any.expr()]),
+            DamlExpr::Throw(throw) => self.keyword_app(
+                "throw",
+                vec![self.type_arg(throw.return_type()), self.type_arg(throw.exception_type())],
+                &[throw.exception_expr()],
+            ),
+            DamlExpr::ToInterface(cast) => self.interface_cast_doc("toInterface", cast),
+            DamlExpr::FromInterface(cast) => self.interface_cast_doc("fromInterface", cast),
+            DamlExpr::UnsafeFromInterface(cast) => self.unsafe_from_interface_doc(cast),
+            DamlExpr::CallInterface(call) => self.keyword_app(
+                "callMethod",
+                vec![self.tycon_arg(call.interface_type()), Doc::text(call.method())],
+                &[call.interface_expr()],
+            ),
+            DamlExpr::ToRequiredInterface(cast) => self.required_interface_doc("toRequiredInterface", cast),
+            DamlExpr::FromRequiredInterface(cast) => self.required_interface_doc("fromRequiredInterface", cast),
+            DamlExpr::UnsafeFromRequiredInterface(cast) => self.unsafe_from_required_interface_doc(cast),
+            DamlExpr::InterfaceTemplateTypeRep(interface_expr) =>
+                self.interface_expr_doc("interfaceTypeRep", interface_expr),
+            DamlExpr::SignatoryInterface(interface_expr) =>
+                self.interface_expr_doc("signatoryInterface", interface_expr),
+            DamlExpr::ObserverInterface(interface_expr) => self.interface_expr_doc("observerInterface", interface_expr),
+            DamlExpr::ViewInterface(interface_expr) => self.interface_expr_doc("view", interface_expr),
+        }
+    }
+
+    fn rec_con_doc(&self, rec_con: &DamlRecCon<'_>) -> Doc {
+        let head = self.type_app(&self.tycon_name(rec_con.tycon().tycon()), rec_con.tycon().type_arguments());
+        Doc::concat(vec![head, Doc::text(" "), self.fields_with_expr("{", rec_con.fields(), "}")])
+    }
+
+    fn variant_con_doc(&self, variant_con: &DamlVariantCon<'_>) -> Doc {
+        let con = format!("{}:{}", self.tycon_name(variant_con.tycon().tycon()), variant_con.variant_con());
+        let args = variant_con
+            .tycon()
+            .type_arguments()
+            .iter()
+            .map(|ty| self.type_arg(ty))
+            .chain(std::iter::once(self.expr_atom(variant_con.variant_arg())))
+            .collect();
+        self.apply(Doc::text(con), args)
+    }
+
+    fn case_doc(&self, case: &DamlCase<'_>) -> Doc {
+        let header = Doc::group(Doc::concat(vec![
+            Doc::text("case"),
+            Doc::nest(self.indent, Doc::concat(vec![Doc::Line, self.expr_doc(case.scrut())])),
+            Doc::Line,
+            Doc::text("of"),
+        ]));
+        let alts = case.alts().iter().map(|alt| Doc::concat(vec![Doc::HardLine, self.case_alt_doc(alt)]));
+        Doc::concat(vec![header, Doc::nest(self.indent, Doc::concat(alts))])
+    }
+
+    fn cons_doc(&self, cons: &DamlCons<'_>) -> Doc {
+        match cons.tail() {
+            DamlExpr::Nil(_) => {
+                let elems = cons.front().iter().map(|elem| self.expr_doc(elem));
+                Doc::group(Doc::concat(vec![
+                    Doc::text("["),
+                    Doc::join(elems, &Doc::concat(vec![Doc::text(","), Doc::Line])),
+                    Doc::text("]"),
+                ]))
+            },
+            tail => {
+                let elems = cons.front().iter().chain(std::iter::once(tail)).map(|elem| self.expr_atom(elem));
+                Doc::group(Doc::join(elems, &Doc::concat(vec![Doc::Line, Doc::text(":: ")])))
+            },
+        }
+    }
+
+    fn interface_cast_doc(&self, keyword: &str, cast: &DamlInterfaceCast<'_>) -> Doc {
+        self.keyword_app(keyword, vec![self.tycon_arg(cast.interface_type()), self.tycon_arg(cast.template_type())], &[
+            cast.expr(),
+        ])
+    }
+
+    fn unsafe_from_interface_doc(&self, cast: &DamlUnsafeFromInterface<'_>) -> Doc {
+        self.keyword_app(
+            "unsafeFromInterface",
+            vec![self.tycon_arg(cast.interface_type()), self.tycon_arg(cast.template_type())],
+            &[cast.contract_id_expr(), cast.interface_expr()],
+        )
+    }
+
+    fn required_interface_doc(&self, keyword: &str, cast: &DamlRequiredInterfaceCast<'_>) -> Doc {
+        self.keyword_app(
+            keyword,
+            vec![self.tycon_arg(cast.required_interface()), self.tycon_arg(cast.requiring_interface())],
+            &[cast.expr()],
+        )
+    }
+
+    fn unsafe_from_required_interface_doc(&self, cast: &DamlUnsafeFromRequiredInterface<'_>) -> Doc {
+        self.keyword_app(
+            "unsafeFromRequiredInterface",
+            vec![self.tycon_arg(cast.required_interface()), self.tycon_arg(cast.requiring_interface())],
+            &[cast.contract_id_expr(), cast.interface_expr()],
+        )
+    }
+
+    fn interface_expr_doc(&self, keyword: &str, interface_expr: &DamlInterfaceExpr<'_>) -> Doc {
+        self.keyword_app(keyword, vec![self.tycon_arg(interface_expr.interface())], &[interface_expr.expr()])
+    }
+
+    /// Render an expression in argument position, parenthesized if required.
+    fn expr_atom(&self, expr: &DamlExpr<'_>) -> Doc {
+        let is_atomic = match expr {
+            DamlExpr::PrimLit(DamlPrimLit::Int64(i)) => *i >= 0,
+            DamlExpr::PrimLit(DamlPrimLit::Numeric(n)) => !n.starts_with('-'),
+            DamlExpr::Var(_)
+            | DamlExpr::Val(_)
+            | DamlExpr::Builtin(_)
+            | DamlExpr::PrimCon(_)
+            | DamlExpr::RecProj(_)
+            | DamlExpr::EnumCon(_)
+            | DamlExpr::StructCon(_)
+            | DamlExpr::StructProj(_)
+            | DamlExpr::Nil(_)
+            | DamlExpr::OptionalNone(_)
+            | DamlExpr::PrimLit(_) => true,
+            DamlExpr::Cons(cons) => matches!(cons.tail(), DamlExpr::Nil(_)),
+            _ => false,
+        };
+        parens_if(!is_atomic, self.expr_doc(expr))
+    }
+
+    fn fields_with_expr(&self, open: &str, fields: &[DamlFieldWithExpr<'_>], close: &str) -> Doc {
+        let fields =
+            fields.iter().map(|field| self.keyword_item(&format!("{} =", field.field()), self.expr_doc(field.expr())));
+        Doc::group(Doc::concat(vec![
+            Doc::text(open),
+            Doc::nest(
+                self.indent,
+                Doc::concat(vec![Doc::Line, Doc::join(fields, &Doc::concat(vec![Doc::text(","), Doc::Line]))]),
+            ),
+            Doc::Line,
+            Doc::text(close),
+        ]))
+    }
+
+    fn with_update(&self, record: &DamlExpr<'_>, field: &str, update: &DamlExpr<'_>) -> Doc {
+        Doc::group(Doc::concat(vec![
+            self.expr_atom(record),
+            Doc::nest(
+                self.indent,
+                Doc::concat(vec![Doc::Line, self.keyword_item(&format!("with {field} ="), self.expr_doc(update))]),
+            ),
+        ]))
+    }
+
+    fn var_with_type(&self, var: &DamlVarWithType<'_>) -> String {
+        format!("({} : {})", var.var(), self.type_doc(var.ty()).render(usize::MAX))
+    }
+
+    fn case_alt_doc(&self, alt: &DamlCaseAlt<'_>) -> Doc {
+        let pattern = match alt.sum() {
+            DamlCaseAltSum::Default => "_".to_owned(),
+            DamlCaseAltSum::Variant(variant) =>
+                format!("{}:{} {}", self.tycon_name(variant.con()), variant.variant(), variant.binder()),
+            DamlCaseAltSum::PrimCon(prim_con) => prim_con_str(*prim_con).to_owned(),
+            DamlCaseAltSum::Nil => "[]".to_owned(),
+            DamlCaseAltSum::Cons(cons) => format!("{} :: {}", cons.var_head(), cons.var_tail()),
+            DamlCaseAltSum::OptionalNone => "None".to_owned(),
+            DamlCaseAltSum::OptionalSome(some) => format!("Some {}", some.var_body()),
+            DamlCaseAltSum::Enum(enum_alt) => format!("{}:{}", self.tycon_name(enum_alt.con()), enum_alt.constructor()),
+        };
+        self.keyword_item(&format!("{pattern} ->"), self.expr_doc(alt.body()))
+    }
+
+    fn binding_doc(&self, binding: &DamlBinding<'_>, arrow: &str) -> Doc {
+        let binder = binding.binder();
+        self.keyword_item(
+            &format!("{} : {} {}", binder.var(), self.type_doc(binder.ty()).render(usize::MAX), arrow),
+            self.expr_doc(binding.bound()),
+        )
+    }
+
+    fn let_doc(&self, block: &DamlBlock<'_>) -> Doc {
+        let bindings =
+            block.bindings().iter().map(|binding| Doc::concat(vec![Doc::HardLine, self.binding_doc(binding, "=")]));
+        Doc::concat(vec![
+            Doc::text("let"),
+            Doc::nest(self.indent, Doc::concat(bindings)),
+            Doc::HardLine,
+            Doc::text("in "),
+            self.expr_doc(block.body()),
+        ])
+    }
+
+    fn do_doc(&self, block: &DamlBlock<'_>) -> Doc {
+        let bindings = block
+            .bindings()
+            .iter()
+            .map(|binding| Doc::concat(vec![Doc::HardLine, self.binding_doc(binding, "<-")]))
+            .chain(std::iter::once(Doc::concat(vec![Doc::HardLine, self.expr_doc(block.body())])));
+        Doc::concat(vec![Doc::text("do"), Doc::nest(self.indent, Doc::concat(bindings))])
+    }
+
+    fn update_doc(&self, update: &DamlUpdate<'_>) -> Doc {
+        match update {
+            DamlUpdate::Pure(pure) => self.keyword_app("pure", vec![self.type_arg(pure.ty())], &[pure.expr()]),
+            DamlUpdate::Block(block) => self.do_doc(block),
+            DamlUpdate::Create(create) =>
+                self.keyword_app("create", vec![self.tycon_arg(create.template())], &[create.expr()]),
+            DamlUpdate::Exercise(exercise) => self.keyword_app(
+                "exercise",
+                vec![self.tycon_arg(exercise.template()), Doc::text(exercise.choice())],
+                &[exercise.cid(), exercise.arg()],
+            ),
+            DamlUpdate::ExerciseByKey(exercise) => self.keyword_app(
+                "exerciseByKey",
+                vec![self.tycon_arg(exercise.template()), Doc::text(exercise.choice())],
+                &[exercise.key(), exercise.arg()],
+            ),
+            DamlUpdate::Fetch(fetch) =>
+                self.keyword_app("fetch", vec![self.tycon_arg(fetch.template())], &[fetch.cid()]),
+            DamlUpdate::GetTime => Doc::text("getTime"),
+            DamlUpdate::LookupByKey(retrieve) =>
+                self.keyword_app("lookupByKey", vec![self.tycon_arg(retrieve.template())], &[retrieve.key()]),
+            DamlUpdate::FetchByKey(retrieve) =>
+                self.keyword_app("fetchByKey", vec![self.tycon_arg(retrieve.template())], &[retrieve.key()]),
+            DamlUpdate::EmbedExpr(embed) => self.keyword_app("embed", vec![self.type_arg(embed.ty())], &[embed.body()]),
+            DamlUpdate::TryCatch(try_catch) => Doc::concat(vec![
+                self.keyword_item("try", self.expr_doc(try_catch.try_expr())),
+                Doc::HardLine,
+                self.keyword_item(&format!("catch {} ->", try_catch.var()), self.expr_doc(try_catch.catch_expr())),
+            ]),
+            DamlUpdate::ExerciseInterface(exercise) => self.keyword_app(
+                "exercise",
+                vec![self.tycon_arg(exercise.interface()), Doc::text(exercise.choice())],
+                &[exercise.cid(), exercise.arg()],
+            ),
+            DamlUpdate::FetchInterface(fetch) =>
+                self.keyword_app("fetch", vec![self.tycon_arg(fetch.interface())], &[fetch.cid()]),
+        }
+    }
+
+    fn scenario_doc(&self, scenario: &DamlScenario<'_>) -> Doc {
+        match scenario {
+            DamlScenario::Pure(pure) => self.keyword_app("pure", vec![self.type_arg(pure.ty())], &[pure.expr()]),
+            DamlScenario::Block(block) => self.do_doc(block),
+            DamlScenario::Commit(commit) =>
+                self.keyword_app("submit", vec![self.type_arg(commit.ret_type())], &[commit.party(), commit.expr()]),
+            DamlScenario::MustFailAt(commit) =>
+                self.keyword_app("submitMustFail", vec![self.type_arg(commit.ret_type())], &[
+                    commit.party(),
+                    commit.expr(),
+                ]),
+            DamlScenario::Pass(expr) => self.keyword_app("pass", vec![], &[expr.as_ref()]),
+            DamlScenario::GetTime => Doc::text("getTime"),
+            DamlScenario::GetParty(expr) => self.keyword_app("getParty", vec![], &[expr.as_ref()]),
+            DamlScenario::EmbedExpr(embed) =>
+                self.keyword_app("embed", vec![self.type_arg(embed.ty())], &[embed.body()]),
+        }
+    }
+}
+
+fn parens_if(parens: bool, doc: Doc) -> Doc {
+    if parens {
+        Doc::concat(vec![Doc::text("("), doc, Doc::text(")")])
+    } else {
+        doc
+    }
+}
+
+fn blank_line() -> Doc {
+    Doc::concat(vec![Doc::HardLine, Doc::HardLine])
+}
+
+fn type_params(params: &[DamlTypeVarWithKind<'_>]) -> String {
+    params.iter().map(|param| format!(" {}", type_var_with_kind(param))).join("")
+}
+
+fn type_var_with_kind(param: &DamlTypeVarWithKind<'_>) -> String {
+    match param.kind() {
+        DamlKind::Star => param.var().to_owned(),
+        kind => format!("({} : {})", param.var(), kind_string(kind)),
+    }
+}
+
+pub fn kind_string(kind: &DamlKind) -> String {
+    match kind {
+        DamlKind::Star => "*".to_owned(),
+        DamlKind::Nat => "nat".to_owned(),
+        DamlKind::Arrow(arrow) => arrow_kind_string(arrow),
+    }
+}
+
+fn arrow_kind_string(arrow: &DamlArrow) -> String {
+    arrow
+        .params()
+        .iter()
+        .chain(std::iter::once(arrow.result()))
+        .map(|kind| match kind {
+            DamlKind::Arrow(inner) => format!("({})", arrow_kind_string(inner)),
+            _ => kind_string(kind),
+        })
+        .join(" -> ")
+}
+
+/// All modules in the tree rooted at `module`, ordered by module path.
+fn all_modules<'a, 'b>(module: &'b DamlModule<'a>) -> Vec<&'b DamlModule<'a>> {
+    std::iter::once(module)
+        .chain(module.child_modules().sorted_by(|a, b| a.local_name().cmp(b.local_name())).flat_map(all_modules))
+        .collect()
+}
+
+fn has_definitions(module: &DamlModule<'_>) -> bool {
+    #[cfg(feature = "full")]
+    let has_values = module.values().next().is_some();
+    #[cfg(not(feature = "full"))]
+    let has_values = false;
+    !module.synonyms().is_empty()
+        || module.data_types().next().is_some()
+        || module.interfaces().next().is_some()
+        || has_values
+}
+
+#[cfg(feature = "full")]
+fn prim_con_str(prim_con: DamlPrimCon) -> &'static str {
+    match prim_con {
+        DamlPrimCon::Unit => "()",
+        DamlPrimCon::False => "False",
+        DamlPrimCon::True => "True",
+    }
+}
+
+#[cfg(feature = "full")]
+fn prim_lit_string(prim_lit: &DamlPrimLit<'_>) -> String {
+    match prim_lit {
+        DamlPrimLit::Int64(i) => i.to_string(),
+        DamlPrimLit::Text(text) => format!("{text:?}"),
+        DamlPrimLit::Party(party) => format!("'{party}'"),
+        DamlPrimLit::Date(days) => format_date(i64::from(*days)),
+        DamlPrimLit::Timestamp(micros) => format_timestamp(*micros),
+        DamlPrimLit::Numeric(numeric) => numeric.to_string(),
+        DamlPrimLit::RoundingMode(mode) => format!("ROUNDING_{}", screaming_snake_case(&format!("{mode:?}"))),
+    }
+}
+
+/// Convert a `CamelCase` identifier to `SCREAMING_SNAKE_CASE`.
+#[cfg(feature = "full")]
+fn screaming_snake_case(s: &str) -> String {
+    s.chars()
+        .enumerate()
+        .flat_map(|(i, c)| {
+            let sep = if i > 0 && c.is_uppercase() {
+                Some('_')
+            } else {
+                None
+            };
+            sep.into_iter().chain(c.to_uppercase())
+        })
+        .collect()
+}
+
+/// Format a number of days since the unix epoch as `YYYY-MM-DD`.
+#[cfg(feature = "full")]
+fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Format a number of microseconds since the unix epoch as `YYYY-MM-DDTHH:MM:SS.ffffffZ`.
+#[cfg(feature = "full")]
+fn format_timestamp(micros: i64) -> String {
+    const MICROS_PER_DAY: i64 = 86_400_000_000;
+    let days = micros.div_euclid(MICROS_PER_DAY);
+    let micros_of_day = micros.rem_euclid(MICROS_PER_DAY);
+    let secs_of_day = micros_of_day / 1_000_000;
+    format!(
+        "{}T{:02}:{:02}:{:02}.{:06}Z",
+        format_date(days),
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+        micros_of_day % 1_000_000
+    )
+}
+
+/// Convert a number of days since the unix epoch to a proleptic Gregorian (year, month, day).
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+#[cfg(feature = "full")]
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 {
+        mp + 3
+    } else {
+        mp - 9
+    };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::element::DamlData;
+    use crate::element::{DamlField, DamlKind, DamlRecord, DamlTyCon, DamlType, DamlTypeVarWithKind, DamlVar};
+    use crate::pretty::PrettyPrinter;
+    use std::borrow::Cow;
+
+    fn party_list() -> DamlType<'static> {
+        DamlType::List(vec![DamlType::Party])
+    }
+
+    #[test]
+    fn test_render_type() {
+        let ty = DamlType::TyCon(DamlTyCon::new_absolute_with_type_args("pkg", &["Fuji", "Shapes"], "Circle", vec![
+            DamlType::Optional(vec![DamlType::Int64]),
+            party_list(),
+        ]));
+        assert_eq!("pkg:Fuji.Shapes:Circle (Optional Int64) [Party]", PrettyPrinter::new().render_type(&ty));
+        assert_eq!(
+            "Fuji.Shapes:Circle (Optional Int64) [Party]",
+            PrettyPrinter::new().elide_package_ids(true).render_type(&ty)
+        );
+    }
+
+    #[test]
+    fn test_render_record() {
+        let record = DamlData::Record(DamlRecord::new(
+            Cow::from("Person"),
+            Cow::from("pkg"),
+            vec![Cow::from("Fuji")],
+            vec![
+                DamlField::new(Cow::from("name"), DamlType::Text),
+                DamlField::new(Cow::from("friends"), party_list()),
+                DamlField::new(Cow::from("extra"), DamlType::Var(DamlVar::new(Cow::from("a"), vec![]))),
+            ],
+            vec![DamlTypeVarWithKind::new(Cow::from("a"), DamlKind::Star)],
+            true,
+        ));
+        assert_eq!(
+            "record Person a = { name : Text, friends : [Party], extra : a }",
+            PrettyPrinter::new().render_data(&record)
+        );
+        assert_eq!(
+            "record Person a =\n  { name : Text\n  , friends : [Party]\n  , extra : a\n  }",
+            PrettyPrinter::new().width(40).render_data(&record)
+        );
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!("1970-01-01", super::format_date(0));
+        assert_eq!("2000-03-01", super::format_date(11_017));
+        assert_eq!("1969-12-31T23:59:59.999999Z", super::format_timestamp(-1));
+        assert_eq!("2022-03-08T12:30:00.000001Z", super::format_timestamp(1_646_742_600_000_001));
+    }
+}
@@ -0,0 +1,128 @@
+use itertools::Itertools;
+
+/// A document which can be laid out for a given width.
+///
+/// This is a minimal variant of the document algebra described in "A prettier printer" (Wadler).  A [`Doc::Group`] is
+/// rendered on a single line if it fits in the remaining width, otherwise every [`Doc::Line`] and [`Doc::SoftLine`]
+/// it (directly) contains is rendered as a newline.
+#[derive(Debug, Clone)]
+pub enum Doc {
+    Text(String),
+    /// A newline, or a single space when rendered flat.
+    Line,
+    /// A newline, or nothing when rendered flat.
+    SoftLine,
+    /// A newline which is never rendered flat.
+    HardLine,
+    Concat(Vec<Doc>),
+    Nest(usize, Box<Doc>),
+    Group(Box<Doc>),
+}
+
+impl Doc {
+    pub fn text(s: impl Into<String>) -> Self {
+        Doc::Text(s.into())
+    }
+
+    pub fn concat(docs: impl IntoIterator<Item = Doc>) -> Self {
+        Doc::Concat(docs.into_iter().collect())
+    }
+
+    pub fn nest(indent: usize, doc: Doc) -> Self {
+        Doc::Nest(indent, Box::new(doc))
+    }
+
+    pub fn group(doc: Doc) -> Self {
+        Doc::Group(Box::new(doc))
+    }
+
+    /// Join `docs` with the given `separator`.
+    pub fn join(docs: impl IntoIterator<Item = Doc>, separator: &Doc) -> Self {
+        Doc::Concat(Itertools::intersperse(docs.into_iter(), separator.clone()).collect())
+    }
+
+    /// Render this document such that lines do not exceed `width` where possible.
+    pub fn render(&self, width: usize) -> String {
+        let mut out = String::new();
+        let mut column = 0;
+        let mut stack: Vec<(usize, bool, &Doc)> = vec![(0, false, self)];
+        while let Some((indent, flat, doc)) = stack.pop() {
+            match doc {
+                Doc::Text(s) => {
+                    out.push_str(s);
+                    column += s.chars().count();
+                },
+                Doc::Line if flat => {
+                    out.push(' ');
+                    column += 1;
+                },
+                Doc::SoftLine if flat => {},
+                Doc::Line | Doc::SoftLine | Doc::HardLine => {
+                    out.push('\n');
+                    out.push_str(&" ".repeat(indent));
+                    column = indent;
+                },
+                Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|d| (indent, flat, d))),
+                Doc::Nest(i, d) => stack.push((indent + i, flat, d)),
+                Doc::Group(d) => stack.push((indent, flat || fits(width.saturating_sub(column), d), d)),
+            }
+        }
+        out.lines().map(str::trim_end).join("\n")
+    }
+}
+
+/// Does `doc` fit within `remaining` columns when rendered flat?
+fn fits(remaining: usize, doc: &Doc) -> bool {
+    let mut remaining = remaining;
+    let mut stack = vec![doc];
+    while let Some(doc) = stack.pop() {
+        let used = match doc {
+            Doc::SoftLine => 0,
+            Doc::Text(s) => s.chars().count(),
+            Doc::Line => 1,
+            Doc::HardLine => return false,
+            Doc::Concat(docs) => {
+                stack.extend(docs.iter().rev());
+                0
+            },
+            Doc::Nest(_, d) | Doc::Group(d) => {
+                stack.push(d);
+                0
+            },
+        };
+        match remaining.checked_sub(used) {
+            Some(r) => remaining = r,
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::Doc;
+
+    fn sample() -> Doc {
+        Doc::group(Doc::concat(vec![
+            Doc::text("f"),
+            Doc::nest(2, Doc::concat(vec![Doc::Line, Doc::text("alpha"), Doc::Line, Doc::text("beta")])),
+        ]))
+    }
+
+    #[test]
+    fn test_group_fits() {
+        assert_eq!("f alpha beta", sample().render(80));
+    }
+
+    #[test]
+    fn test_group_breaks() {
+        assert_eq!("f\n  alpha\n  beta", sample().render(8));
+    }
+
+    #[test]
+    fn test_hard_line_breaks_group() {
+        let doc =
+            Doc::group(Doc::concat(vec![Doc::text("a"), Doc::HardLine, Doc::text("b"), Doc::Line, Doc::text("c")]));
+        assert_eq!("a\nb\nc", doc.render(80));
+    }
+}
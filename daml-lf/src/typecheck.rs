@@ -0,0 +1,1236 @@
+mod builtin;
+mod types;
+
+use crate::element::{
+    DamlArchive, DamlBlock, DamlCase, DamlCaseAltSum, DamlChoice, DamlData, DamlDefKey, DamlDefTypeSyn, DamlDefValue,
+    DamlExpr, DamlForall, DamlInterface, DamlInterfaceInstance, DamlKind, DamlModule, DamlPackage, DamlPrimCon,
+    DamlPrimLit, DamlScenario, DamlTemplate, DamlTyCon, DamlTyConName, DamlType, DamlTypeVarWithKind, DamlUpdate,
+    DamlValueName,
+};
+use crate::error::{DamlTypeCheckError, DamlTypeCheckErrorKind};
+use crate::pretty::kind_string;
+use crate::LanguageFeatureVersion;
+use builtin::builtin_type;
+use itertools::Itertools;
+use std::borrow::Cow;
+pub use types::TypeCheckResult;
+use types::{
+    absolute_tycon, arrow, arrows, contract_id, flatten_kind, kind_eq, list, make_kind, map_children, optional,
+    render_tycon_name, render_type, scenario, struct_type, substitute, tycon, types_equal, update,
+    LEGACY_DECIMAL_SCALE, MAX_NUMERIC_SCALE,
+};
+
+/// Type check the definitions and expressions of the packages in a [`DamlArchive`].
+///
+/// The checker infers the [`DamlType`] of every [`DamlExpr`] and verifies that it agrees with the declared types of
+/// values, template bodies, keys, choices and interface instances.  Types are kind checked, type synonyms are expanded
+/// and the scale of every `Numeric` is checked to be in range.  The types of template keys, choice arguments and
+/// choice results must be serializable.
+///
+/// Checking stops at the first error found within a given definition, but continues with the remaining definitions
+/// such that every erroneous definition is reported.  Each [`DamlTypeCheckError`] records the package, module and
+/// definition in which it was found.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use daml_lf::{DarFile, DamlTypeChecker};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let dar = DarFile::from_file("Example.dar")?;
+/// dar.apply(|archive| {
+///     for error in DamlTypeChecker::new(archive).check_archive() {
+///         println!("{}", error);
+///     }
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DamlTypeChecker<'a> {
+    archive: &'a DamlArchive<'a>,
+}
+
+impl<'a> DamlTypeChecker<'a> {
+    /// Create a type checker for the given [`DamlArchive`].
+    pub const fn new(archive: &'a DamlArchive<'a>) -> Self {
+        Self {
+            archive,
+        }
+    }
+
+    /// Type check every package in the archive, returning all errors found.
+    pub fn check_archive(&self) -> Vec<DamlTypeCheckError> {
+        self.archive
+            .packages()
+            .sorted_by(|a, b| a.name().cmp(b.name()).then_with(|| a.package_id().cmp(b.package_id())))
+            .flat_map(|package| self.check_package(package))
+            .collect()
+    }
+
+    /// Type check a single package, returning all errors found.
+    ///
+    /// Any packages referred to by `package` must be contained in the archive.
+    pub fn check_package(&self, package: &'a DamlPackage<'a>) -> Vec<DamlTypeCheckError> {
+        let mut errors = vec![];
+        self.check_module(package, package.root_module(), &mut errors);
+        errors
+    }
+
+    /// Infer the type of an expression in the context of the given package.
+    ///
+    /// The expression must be closed, that is it must not refer to any term or type variables which it does not bind.
+    pub fn infer_expr(&self, package: &'a DamlPackage<'a>, expr: &'a DamlExpr<'a>) -> TypeCheckResult<DamlType<'a>> {
+        Checker::new(self.archive, package).infer(expr)
+    }
+
+    fn check_module(
+        &self,
+        package: &'a DamlPackage<'a>,
+        module: &'a DamlModule<'a>,
+        errors: &mut Vec<DamlTypeCheckError>,
+    ) {
+        let mut results = vec![];
+        for syn in module.synonyms().iter().sorted_by(|a, b| a.name().cmp(b.name())) {
+            results.push((syn.name().join("."), self.checker(package).check_synonym(syn)));
+        }
+        for data in module.data_types().sorted_by(|a, b| a.name().cmp(b.name())) {
+            results.push((data.name().to_owned(), self.checker(package).check_data(data)));
+            if let DamlData::Template(template) = data {
+                self.check_template(package, template, &mut results);
+            }
+        }
+        for interface in module.interfaces().sorted_by(|a, b| a.name().cmp(b.name())) {
+            self.check_interface(package, interface, &mut results);
+        }
+        for value in module.values().sorted_by(|a, b| a.name().cmp(b.name())) {
+            results.push((value.name().to_owned(), self.checker(package).check_value(value)));
+        }
+        let module_name = module.path().join(".");
+        errors.extend(results.into_iter().filter_map(|(definition, result)| {
+            result.err().map(|kind| DamlTypeCheckError::new(package.package_id(), &module_name, definition, kind))
+        }));
+        for child in module.child_modules().sorted_by(|a, b| a.local_name().cmp(b.local_name())) {
+            self.check_module(package, child, errors);
+        }
+    }
+
+    fn check_template(
+        &self,
+        package: &'a DamlPackage<'a>,
+        template: &'a DamlTemplate<'a>,
+        results: &mut Vec<(String, TypeCheckResult<()>)>,
+    ) {
+        let name = template.name();
+        let this = absolute_tycon(template.package_id(), template.module_path(), name);
+        let with_this = || self.checker(package).with_var(template.param(), this.clone());
+        if let Some(precond) = template.precond() {
+            results.push((format!("{}.precondition", name), with_this().check(precond, &DamlType::Bool)));
+        }
+        results
+            .push((format!("{}.signatories", name), with_this().check(template.signatories(), &list(DamlType::Party))));
+        results.push((format!("{}.observers", name), with_this().check(template.observers(), &list(DamlType::Party))));
+        results.push((format!("{}.agreement", name), with_this().check(template.agreement(), &DamlType::Text)));
+        if let Some(key) = template.key() {
+            results.push((format!("{}.key", name), with_this().check_key(key.ty(), key.key_expr())));
+            results.push((
+                format!("{}.maintainers", name),
+                self.checker(package).check(key.maintainers(), &arrow(key.ty().clone(), list(DamlType::Party))),
+            ));
+        }
+        for choice in template.choices() {
+            self.check_choice(
+                package,
+                &this,
+                template.param(),
+                choice,
+                &format!("{}.{}", name, choice.name()),
+                results,
+            );
+        }
+        for instance in template.implements() {
+            self.check_interface_instance(package, &this, template.param(), instance, name, results);
+        }
+    }
+
+    fn check_interface_instance(
+        &self,
+        package: &'a DamlPackage<'a>,
+        this: &DamlType<'a>,
+        this_binder: &'a str,
+        instance: &'a DamlInterfaceInstance<'a>,
+        template_name: &str,
+        results: &mut Vec<(String, TypeCheckResult<()>)>,
+    ) {
+        let prefix = format!("{}.{}", template_name, instance.interface().data_name());
+        let interface = match self.checker(package).interface(instance.interface()) {
+            Ok(interface) => interface,
+            Err(err) => return results.push((prefix, Err(err))),
+        };
+        let with_this = || self.checker(package).with_var(this_binder, this.clone());
+        results.push((format!("{}.view", prefix), with_this().check(instance.view(), interface.view())));
+        for method in instance.methods() {
+            let result = interface.methods().iter().find(|m| m.name() == method.name()).map_or_else(
+                || {
+                    Err(DamlTypeCheckErrorKind::UnknownMethod(
+                        method.name().to_owned(),
+                        render_tycon_name(instance.interface()),
+                    ))
+                },
+                |declared| with_this().check(method.value(), declared.ty()),
+            );
+            results.push((format!("{}.{}", prefix, method.name()), result));
+        }
+    }
+
+    fn check_interface(
+        &self,
+        package: &'a DamlPackage<'a>,
+        interface: &'a DamlInterface<'a>,
+        results: &mut Vec<(String, TypeCheckResult<()>)>,
+    ) {
+        let name = interface.name();
+        results.push((name.to_owned(), self.checker(package).check_interface_types(interface)));
+        let this = absolute_tycon(interface.package_id(), interface.module_path(), name);
+        for choice in interface.choices() {
+            self.check_choice(
+                package,
+                &this,
+                interface.param(),
+                choice,
+                &format!("{}.{}", name, choice.name()),
+                results,
+            );
+        }
+    }
+
+    fn check_choice(
+        &self,
+        package: &'a DamlPackage<'a>,
+        this: &DamlType<'a>,
+        this_binder: &'a str,
+        choice: &'a DamlChoice<'a>,
+        prefix: &str,
+        results: &mut Vec<(String, TypeCheckResult<()>)>,
+    ) {
+        let with_arg = || {
+            self.checker(package)
+                .with_var(this_binder, this.clone())
+                .with_var(choice.argument_binder(), choice.argument_type().clone())
+        };
+        results.push((prefix.to_owned(), self.checker(package).check_choice_types(choice)));
+        results
+            .push((format!("{}.controllers", prefix), with_arg().check(choice.controllers(), &list(DamlType::Party))));
+        results.push((format!("{}.observers", prefix), with_arg().check(choice.observers(), &list(DamlType::Party))));
+        results.push((
+            format!("{}.update", prefix),
+            with_arg()
+                .with_var(choice.self_binder(), contract_id(this.clone()))
+                .check(choice.update(), &update(choice.return_type().clone())),
+        ));
+    }
+
+    fn checker(&self, package: &'a DamlPackage<'a>) -> Checker<'a> {
+        Checker::new(self.archive, package)
+    }
+}
+
+/// The kind of effect of an update or scenario expression.
+#[derive(Clone, Copy)]
+enum Effect {
+    Update,
+    Scenario,
+}
+
+impl Effect {
+    fn wrap(self, ty: DamlType<'_>) -> DamlType<'_> {
+        match self {
+            Effect::Update => update(ty),
+            Effect::Scenario => scenario(ty),
+        }
+    }
+
+    const fn description(self) -> &'static str {
+        match self {
+            Effect::Update => "an update type",
+            Effect::Scenario => "a scenario type",
+        }
+    }
+}
+
+/// Type checks a single definition, tracking the term and type variables in scope.
+struct Checker<'a> {
+    archive: &'a DamlArchive<'a>,
+    package: &'a DamlPackage<'a>,
+    vars: Vec<(&'a str, DamlType<'a>)>,
+    type_vars: Vec<(Cow<'a, str>, DamlKind)>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(archive: &'a DamlArchive<'a>, package: &'a DamlPackage<'a>) -> Self {
+        Self {
+            archive,
+            package,
+            vars: vec![],
+            type_vars: vec![],
+        }
+    }
+
+    fn with_var(mut self, name: &'a str, ty: DamlType<'a>) -> Self {
+        self.vars.push((name, ty));
+        self
+    }
+
+    /// Run `f` and then restore the term and type variables in scope.
+    fn scoped<T>(&mut self, f: impl FnOnce(&mut Self) -> TypeCheckResult<T>) -> TypeCheckResult<T> {
+        let vars = self.vars.len();
+        let type_vars = self.type_vars.len();
+        let result = f(self);
+        self.vars.truncate(vars);
+        self.type_vars.truncate(type_vars);
+        result
+    }
+
+    fn push_type_params(&mut self, params: &'a [DamlTypeVarWithKind<'a>]) {
+        self.type_vars.extend(params.iter().map(|param| (Cow::Borrowed(param.var()), param.kind().clone())));
+    }
+
+    fn check_synonym(&mut self, syn: &'a DamlDefTypeSyn<'a>) -> TypeCheckResult<()> {
+        self.push_type_params(syn.params());
+        self.expect_kind(syn.ty(), &DamlKind::Star)
+    }
+
+    fn check_data(&mut self, data: &'a DamlData<'a>) -> TypeCheckResult<()> {
+        self.push_type_params(data.type_params());
+        for field in data.fields() {
+            self.expect_kind(field.ty(), &DamlKind::Star)?;
+            if data.serializable() {
+                self.expect_serializable(&format!("field {}", field.name()), field.ty())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_value(&mut self, value: &'a DamlDefValue<'a>) -> TypeCheckResult<()> {
+        self.expect_kind(&value.ty, &DamlKind::Star)?;
+        self.check(&value.expr, &value.ty)
+    }
+
+    fn check_key(&mut self, ty: &'a DamlType<'a>, key_expr: &'a DamlExpr<'a>) -> TypeCheckResult<()> {
+        self.expect_kind(ty, &DamlKind::Star)?;
+        self.expect_serializable("key", ty)?;
+        self.check(key_expr, ty)
+    }
+
+    fn check_choice_types(&mut self, choice: &'a DamlChoice<'a>) -> TypeCheckResult<()> {
+        self.expect_kind(choice.argument_type(), &DamlKind::Star)?;
+        self.expect_serializable("choice argument", choice.argument_type())?;
+        self.expect_kind(choice.return_type(), &DamlKind::Star)?;
+        self.expect_serializable("choice result", choice.return_type())
+    }
+
+    fn check_interface_types(&mut self, interface: &'a DamlInterface<'a>) -> TypeCheckResult<()> {
+        self.expect_kind(interface.view(), &DamlKind::Star)?;
+        for method in interface.methods() {
+            self.expect_kind(method.ty(), &DamlKind::Star)?;
+        }
+        Ok(())
+    }
+
+    // Lookups
+
+    fn data(&self, name: &DamlTyConName<'_>) -> TypeCheckResult<&'a DamlData<'a>> {
+        self.archive
+            .data_by_tycon_name(name)
+            .ok_or_else(|| DamlTypeCheckErrorKind::UnknownType(render_tycon_name(name)))
+    }
+
+    fn template(&self, name: &DamlTyConName<'_>) -> TypeCheckResult<&'a DamlTemplate<'a>> {
+        match self.data(name)? {
+            DamlData::Template(template) => Ok(template),
+            _ => Err(DamlTypeCheckErrorKind::UnexpectedType("a template".to_owned(), render_tycon_name(name))),
+        }
+    }
+
+    fn template_choice(&self, name: &DamlTyConName<'_>, choice: &str) -> TypeCheckResult<&'a DamlChoice<'a>> {
+        self.template(name)?
+            .choices()
+            .iter()
+            .find(|c| c.name() == choice)
+            .ok_or_else(|| DamlTypeCheckErrorKind::UnknownChoice(choice.to_owned(), render_tycon_name(name)))
+    }
+
+    fn template_key(&self, name: &DamlTyConName<'_>) -> TypeCheckResult<&'a DamlType<'a>> {
+        self.template(name)?
+            .key()
+            .map(DamlDefKey::ty)
+            .ok_or_else(|| DamlTypeCheckErrorKind::MissingKey(render_tycon_name(name)))
+    }
+
+    fn interface(&self, name: &DamlTyConName<'_>) -> TypeCheckResult<&'a DamlInterface<'a>> {
+        let (package_id, module_path, data_name) = name.reference_parts();
+        self.module(package_id, module_path)
+            .and_then(|module| module.interface(data_name))
+            .ok_or_else(|| DamlTypeCheckErrorKind::UnknownType(render_tycon_name(name)))
+    }
+
+    fn synonym(&self, name: &DamlTyConName<'_>) -> TypeCheckResult<&'a DamlDefTypeSyn<'a>> {
+        let (package_id, module_path, data_name) = name.reference_parts();
+        self.module(package_id, module_path)
+            .and_then(|module| module.synonyms().iter().find(|syn| syn.name().last() == Some(data_name)))
+            .ok_or_else(|| DamlTypeCheckErrorKind::UnknownType(render_tycon_name(name)))
+    }
+
+    fn module(&self, package_id: &str, module_path: &[Cow<'_, str>]) -> Option<&'a DamlModule<'a>> {
+        self.archive.package_by_id(package_id)?.root_module().child_module_path(module_path)
+    }
+
+    fn lookup_var(&self, name: &str) -> TypeCheckResult<DamlType<'a>> {
+        self.vars
+            .iter()
+            .rev()
+            .find(|(var, _)| *var == name)
+            .map(|(_, ty)| ty.clone())
+            .ok_or_else(|| DamlTypeCheckErrorKind::UnknownVariable(name.to_owned()))
+    }
+
+    fn lookup_type_var(&self, name: &str) -> TypeCheckResult<DamlKind> {
+        self.type_vars
+            .iter()
+            .rev()
+            .find(|(var, _)| var == name)
+            .map(|(_, kind)| kind.clone())
+            .ok_or_else(|| DamlTypeCheckErrorKind::UnknownTypeVariable(name.to_owned()))
+    }
+
+    // Types
+
+    /// Expand all type synonyms in `ty`.
+    fn normalize(&self, ty: &DamlType<'a>) -> TypeCheckResult<DamlType<'a>> {
+        self.normalize_with(ty, &mut vec![])
+    }
+
+    fn normalize_with(&self, ty: &DamlType<'a>, expanding: &mut Vec<String>) -> TypeCheckResult<DamlType<'a>> {
+        match ty {
+            DamlType::Syn(syn) => {
+                let name = render_tycon_name(syn.tysyn());
+                let def = self.synonym(syn.tysyn())?;
+                if def.params().len() != syn.args().len() {
+                    return Err(DamlTypeCheckErrorKind::TypeArgumentCount(name, def.params().len(), syn.args().len()));
+                }
+                if expanding.contains(&name) {
+                    return Err(DamlTypeCheckErrorKind::CyclicSynonym(name));
+                }
+                let args = syn
+                    .args()
+                    .iter()
+                    .map(|arg| self.normalize_with(arg, expanding))
+                    .collect::<TypeCheckResult<Vec<_>>>()?;
+                let subst = def.params().iter().map(DamlTypeVarWithKind::var).zip(args).collect::<Vec<_>>();
+                let expanded = substitute(def.ty(), &subst)?;
+                expanding.push(name);
+                let normalized = self.normalize_with(&expanded, expanding);
+                expanding.pop();
+                normalized
+            },
+            _ => map_children(ty, &mut |child| self.normalize_with(child, expanding)),
+        }
+    }
+
+    fn expect_type(&self, expected: &DamlType<'a>, actual: &DamlType<'a>) -> TypeCheckResult<()> {
+        let expected = self.normalize(expected)?;
+        let actual = self.normalize(actual)?;
+        if types_equal(&expected, &actual) {
+            Ok(())
+        } else {
+            Err(DamlTypeCheckErrorKind::TypeMismatch(render_type(&expected), render_type(&actual)))
+        }
+    }
+
+    fn expect_kind(&mut self, ty: &DamlType<'a>, expected: &DamlKind) -> TypeCheckResult<()> {
+        let actual = self.kind_of(ty)?;
+        if kind_eq(expected, &actual) {
+            Ok(())
+        } else {
+            Err(DamlTypeCheckErrorKind::KindMismatch(kind_string(expected), kind_string(&actual), render_type(ty)))
+        }
+    }
+
+    fn kind_of(&mut self, ty: &DamlType<'a>) -> TypeCheckResult<DamlKind> {
+        match ty {
+            DamlType::Int64
+            | DamlType::Text
+            | DamlType::Timestamp
+            | DamlType::Party
+            | DamlType::Bool
+            | DamlType::Unit
+            | DamlType::Date
+            | DamlType::Any
+            | DamlType::TypeRep
+            | DamlType::Bignumeric
+            | DamlType::RoundingMode
+            | DamlType::AnyException => Ok(DamlKind::Star),
+            DamlType::Nat(n) if *n <= MAX_NUMERIC_SCALE => Ok(DamlKind::Nat),
+            DamlType::Nat(n) => Err(DamlTypeCheckErrorKind::NatOutOfRange(*n)),
+            DamlType::ContractId(None) => Ok(make_kind(vec![DamlKind::Star], DamlKind::Star)),
+            DamlType::ContractId(Some(inner)) =>
+                self.apply_kind("ContractId", &[DamlKind::Star], std::slice::from_ref(inner.as_ref())),
+            DamlType::Numeric(args) => self.apply_kind("Numeric", &[DamlKind::Nat], args),
+            DamlType::List(args) => self.apply_kind("List", &[DamlKind::Star], args),
+            DamlType::Optional(args) => self.apply_kind("Optional", &[DamlKind::Star], args),
+            DamlType::TextMap(args) => self.apply_kind("TextMap", &[DamlKind::Star], args),
+            DamlType::Update(args) => self.apply_kind("Update", &[DamlKind::Star], args),
+            DamlType::Scenario(args) => self.apply_kind("Scenario", &[DamlKind::Star], args),
+            DamlType::GenMap(args) => self.apply_kind("GenMap", &[DamlKind::Star, DamlKind::Star], args),
+            DamlType::Arrow(args) => self.apply_kind("(->)", &[DamlKind::Star, DamlKind::Star], args),
+            DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) => {
+                let params = match self.archive.data_by_tycon_name(tycon.tycon()) {
+                    Some(data) => data.type_params().iter().map(|param| param.kind().clone()).collect(),
+                    None => self.interface(tycon.tycon()).map(|_| vec![])?,
+                };
+                self.apply_kind(&render_tycon_name(tycon.tycon()), &params, tycon.type_arguments())
+            },
+            DamlType::Var(var) => {
+                let (params, result) = flatten_kind(&self.lookup_type_var(var.var())?);
+                let remaining = self.apply_kind(var.var(), &params, var.type_arguments())?;
+                let (remaining, _) = flatten_kind(&remaining);
+                Ok(make_kind(remaining, result))
+            },
+            DamlType::Forall(forall) => self.scoped(|checker| {
+                checker
+                    .type_vars
+                    .extend(forall.vars().iter().map(|v| (Cow::Owned(v.var().to_owned()), v.kind().clone())));
+                checker.expect_kind(forall.body(), &DamlKind::Star)?;
+                Ok(DamlKind::Star)
+            }),
+            DamlType::Struct(tuple) => {
+                for field in tuple.fields() {
+                    self.expect_kind(field.ty(), &DamlKind::Star)?;
+                }
+                Ok(DamlKind::Star)
+            },
+            DamlType::Syn(syn) => {
+                let def = self.synonym(syn.tysyn())?;
+                if def.params().len() != syn.args().len() {
+                    return Err(DamlTypeCheckErrorKind::TypeArgumentCount(
+                        render_tycon_name(syn.tysyn()),
+                        def.params().len(),
+                        syn.args().len(),
+                    ));
+                }
+                for (param, arg) in def.params().iter().zip(syn.args()) {
+                    self.expect_kind(arg, param.kind())?;
+                }
+                Ok(DamlKind::Star)
+            },
+        }
+    }
+
+    /// Check the kinds of the `args` applied to a type with the given parameter kinds and return the resulting kind.
+    fn apply_kind(&mut self, name: &str, params: &[DamlKind], args: &[DamlType<'a>]) -> TypeCheckResult<DamlKind> {
+        if args.len() > params.len() {
+            return Err(DamlTypeCheckErrorKind::TypeArgumentCount(name.to_owned(), params.len(), args.len()));
+        }
+        for (param, arg) in params.iter().zip(args) {
+            self.expect_kind(arg, param)?;
+        }
+        Ok(make_kind(params[args.len()..].to_vec(), DamlKind::Star))
+    }
+
+    fn expect_serializable(&self, what: &str, ty: &DamlType<'a>) -> TypeCheckResult<()> {
+        if self.is_serializable(&self.normalize(ty)?) {
+            Ok(())
+        } else {
+            Err(DamlTypeCheckErrorKind::NotSerializable(what.to_owned(), render_type(ty)))
+        }
+    }
+
+    fn is_serializable(&self, ty: &DamlType<'a>) -> bool {
+        match ty {
+            DamlType::Int64
+            | DamlType::Text
+            | DamlType::Timestamp
+            | DamlType::Party
+            | DamlType::Bool
+            | DamlType::Unit
+            | DamlType::Date
+            | DamlType::ContractId(Some(_)) => true,
+            DamlType::Numeric(args) => matches!(args.as_slice(), [DamlType::Nat(_) | DamlType::Var(_)]),
+            DamlType::List(args) | DamlType::Optional(args) | DamlType::TextMap(args) =>
+                matches!(args.as_slice(), [arg] if self.is_serializable(arg)),
+            DamlType::GenMap(args) =>
+                matches!(args.as_slice(), [key, value] if self.is_serializable(key) && self.is_serializable(value)),
+            DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) =>
+                self.archive.data_by_tycon_name(tycon.tycon()).map_or(false, |data| {
+                    data.serializable()
+                        && data.type_params().len() == tycon.type_arguments().len()
+                        && tycon.type_arguments().iter().all(|arg| self.is_serializable(arg))
+                }),
+            DamlType::Var(var) => var.type_arguments().is_empty(),
+            _ => false,
+        }
+    }
+
+    fn expect_arrow(&self, ty: &DamlType<'a>) -> TypeCheckResult<(DamlType<'a>, DamlType<'a>)> {
+        let ty = self.normalize(ty)?;
+        match &ty {
+            DamlType::Arrow(args) if args.len() == 2 => Ok((args[0].clone(), args[1].clone())),
+            _ => Err(DamlTypeCheckErrorKind::UnexpectedType("a function type".to_owned(), render_type(&ty))),
+        }
+    }
+
+    fn expect_list(&self, ty: &DamlType<'a>) -> TypeCheckResult<DamlType<'a>> {
+        let ty = self.normalize(ty)?;
+        match &ty {
+            DamlType::List(args) if args.len() == 1 => Ok(args[0].clone()),
+            _ => Err(DamlTypeCheckErrorKind::UnexpectedType("a list type".to_owned(), render_type(&ty))),
+        }
+    }
+
+    fn expect_optional(&self, ty: &DamlType<'a>) -> TypeCheckResult<DamlType<'a>> {
+        let ty = self.normalize(ty)?;
+        match &ty {
+            DamlType::Optional(args) if args.len() == 1 => Ok(args[0].clone()),
+            _ => Err(DamlTypeCheckErrorKind::UnexpectedType("an optional type".to_owned(), render_type(&ty))),
+        }
+    }
+
+    fn expect_effect(&self, effect: Effect, ty: &DamlType<'a>) -> TypeCheckResult<DamlType<'a>> {
+        let ty = self.normalize(ty)?;
+        match (effect, &ty) {
+            (Effect::Update, DamlType::Update(args)) | (Effect::Scenario, DamlType::Scenario(args))
+                if args.len() == 1 =>
+                Ok(args[0].clone()),
+            _ => Err(DamlTypeCheckErrorKind::UnexpectedType(effect.description().to_owned(), render_type(&ty))),
+        }
+    }
+
+    /// Check that `ty` is the type constructor `name` and return the applied type constructor.
+    fn expect_tycon(ty: &DamlType<'a>, name: &DamlTyConName<'_>) -> TypeCheckResult<DamlTyCon<'a>> {
+        match ty {
+            DamlType::TyCon(found) | DamlType::BoxedTyCon(found)
+                if found.tycon().reference_parts() == name.reference_parts() =>
+                Ok(found.clone()),
+            _ => Err(DamlTypeCheckErrorKind::TypeMismatch(render_tycon_name(name), render_type(ty))),
+        }
+    }
+
+    /// The fields of a record, variant or template applied to the type arguments of `tycon`.
+    fn fields(
+        &mut self,
+        data: &'a DamlData<'a>,
+        tycon: &DamlTyCon<'a>,
+    ) -> TypeCheckResult<Vec<(&'a str, DamlType<'a>)>> {
+        self.expect_kind(&DamlType::TyCon(tycon.clone()), &DamlKind::Star)?;
+        let subst = data
+            .type_params()
+            .iter()
+            .map(DamlTypeVarWithKind::var)
+            .zip(tycon.type_arguments().iter().cloned())
+            .collect::<Vec<_>>();
+        data.fields().iter().map(|field| Ok((field.name(), substitute(field.ty(), &subst)?))).collect()
+    }
+
+    fn record_fields(&mut self, tycon: &DamlTyCon<'a>) -> TypeCheckResult<Vec<(&'a str, DamlType<'a>)>> {
+        match self.data(tycon.tycon())? {
+            data @ (DamlData::Record(_) | DamlData::Template(_)) => self.fields(data, tycon),
+            _ => Err(DamlTypeCheckErrorKind::UnexpectedType(
+                "a record type".to_owned(),
+                render_tycon_name(tycon.tycon()),
+            )),
+        }
+    }
+
+    fn record_field(&mut self, tycon: &DamlTyCon<'a>, field: &str) -> TypeCheckResult<DamlType<'a>> {
+        self.record_fields(tycon)?
+            .into_iter()
+            .find(|(name, _)| *name == field)
+            .map(|(_, ty)| ty)
+            .ok_or_else(|| DamlTypeCheckErrorKind::UnknownField(field.to_owned(), render_tycon_name(tycon.tycon())))
+    }
+
+    fn variant_field(&mut self, tycon: &DamlTyCon<'a>, constructor: &str) -> TypeCheckResult<DamlType<'a>> {
+        let fields = match self.data(tycon.tycon())? {
+            data @ DamlData::Variant(_) => self.fields(data, tycon)?,
+            _ =>
+                return Err(DamlTypeCheckErrorKind::UnexpectedType(
+                    "a variant type".to_owned(),
+                    render_tycon_name(tycon.tycon()),
+                )),
+        };
+        fields.into_iter().find(|(name, _)| *name == constructor).map(|(_, ty)| ty).ok_or_else(|| {
+            DamlTypeCheckErrorKind::UnknownConstructor(constructor.to_owned(), render_tycon_name(tycon.tycon()))
+        })
+    }
+
+    fn expect_enum_constructor(&self, name: &DamlTyConName<'_>, constructor: &str) -> TypeCheckResult<()> {
+        match self.data(name)? {
+            DamlData::Enum(data_enum) if data_enum.constructors().any(|c| c == constructor) => Ok(()),
+            DamlData::Enum(_) =>
+                Err(DamlTypeCheckErrorKind::UnknownConstructor(constructor.to_owned(), render_tycon_name(name))),
+            _ => Err(DamlTypeCheckErrorKind::UnexpectedType("an enum type".to_owned(), render_tycon_name(name))),
+        }
+    }
+
+    fn struct_field(&self, ty: &DamlType<'a>, field: &str) -> TypeCheckResult<DamlType<'a>> {
+        let ty = self.normalize(ty)?;
+        match &ty {
+            DamlType::Struct(tuple) => tuple
+                .fields()
+                .iter()
+                .find(|f| f.name() == field)
+                .map(|f| f.ty().clone())
+                .ok_or_else(|| DamlTypeCheckErrorKind::UnknownField(field.to_owned(), render_type(&ty))),
+            _ => Err(DamlTypeCheckErrorKind::UnexpectedType("a struct type".to_owned(), render_type(&ty))),
+        }
+    }
+
+    fn interface_type(&self, name: &DamlTyConName<'a>) -> TypeCheckResult<DamlType<'a>> {
+        self.interface(name).map(|_| tycon(name))
+    }
+
+    fn template_type(&self, name: &DamlTyConName<'a>) -> TypeCheckResult<DamlType<'a>> {
+        self.template(name).map(|_| tycon(name))
+    }
+
+    // Expressions
+
+    fn check(&mut self, expr: &'a DamlExpr<'a>, expected: &DamlType<'a>) -> TypeCheckResult<()> {
+        let actual = self.infer(expr)?;
+        self.expect_type(expected, &actual)
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn infer(&mut self, expr: &'a DamlExpr<'a>) -> TypeCheckResult<DamlType<'a>> {
+        match expr {
+            DamlExpr::Var(var) => self.lookup_var(var),
+            DamlExpr::Val(name) => self
+                .archive
+                .value_by_name(name)
+                .map(|value| value.ty.clone())
+                .ok_or_else(|| DamlTypeCheckErrorKind::UnknownValue(render_value_name(name))),
+            DamlExpr::Builtin(builtin) => Ok(builtin_type(builtin)),
+            DamlExpr::PrimCon(DamlPrimCon::Unit) => Ok(DamlType::Unit),
+            DamlExpr::PrimCon(DamlPrimCon::True | DamlPrimCon::False) => Ok(DamlType::Bool),
+            DamlExpr::PrimLit(lit) => self.infer_prim_lit(lit),
+            DamlExpr::RecCon(rec_con) => {
+                let fields = self.record_fields(rec_con.tycon())?;
+                if fields.len() != rec_con.fields().len()
+                    || fields.iter().zip(rec_con.fields()).any(|((name, _), field)| *name != field.field())
+                {
+                    return Err(DamlTypeCheckErrorKind::FieldMismatch(render_tycon_name(rec_con.tycon().tycon())));
+                }
+                for ((_, ty), field) in fields.iter().zip(rec_con.fields()) {
+                    self.check(field.expr(), ty)?;
+                }
+                Ok(DamlType::TyCon(rec_con.tycon().clone()))
+            },
+            DamlExpr::RecProj(rec_proj) => {
+                let field_ty = self.record_field(rec_proj.tycon(), rec_proj.field())?;
+                self.check(rec_proj.record(), &DamlType::TyCon(rec_proj.tycon().clone()))?;
+                Ok(field_ty)
+            },
+            DamlExpr::RecUpd(rec_upd) => {
+                let record_ty = DamlType::TyCon(rec_upd.tycon().clone());
+                let field_ty = self.record_field(rec_upd.tycon(), rec_upd.field())?;
+                self.check(rec_upd.record(), &record_ty)?;
+                self.check(rec_upd.update(), &field_ty)?;
+                Ok(record_ty)
+            },
+            DamlExpr::VariantCon(variant_con) => {
+                let arg_ty = self.variant_field(variant_con.tycon(), variant_con.variant_con())?;
+                self.check(variant_con.variant_arg(), &arg_ty)?;
+                Ok(DamlType::TyCon(variant_con.tycon().clone()))
+            },
+            DamlExpr::EnumCon(enum_con) => {
+                self.expect_enum_constructor(enum_con.tycon(), enum_con.enum_con())?;
+                Ok(tycon(enum_con.tycon()))
+            },
+            DamlExpr::StructCon(struct_con) => {
+                let fields = struct_con
+                    .fields()
+                    .iter()
+                    .map(|field| Ok((field.field(), self.infer(field.expr())?)))
+                    .collect::<TypeCheckResult<Vec<_>>>()?;
+                Ok(struct_type(fields))
+            },
+            DamlExpr::StructProj(struct_proj) => {
+                let struct_ty = self.infer(struct_proj.struct_expr())?;
+                self.struct_field(&struct_ty, struct_proj.field())
+            },
+            DamlExpr::StructUpd(struct_upd) => {
+                let struct_ty = self.infer(struct_upd.struct_expr())?;
+                let field_ty = self.struct_field(&struct_ty, struct_upd.field())?;
+                self.check(struct_upd.update(), &field_ty)?;
+                Ok(struct_ty)
+            },
+            DamlExpr::App(app) => {
+                let mut ty = self.infer(app.fun())?;
+                for arg in app.args() {
+                    let (param, result) = self.expect_arrow(&ty)?;
+                    self.check(arg, &param)?;
+                    ty = result;
+                }
+                Ok(ty)
+            },
+            DamlExpr::TyApp(ty_app) => {
+                let mut ty = self.infer(ty_app.expr())?;
+                for arg in ty_app.types() {
+                    ty = self.instantiate(&ty, arg)?;
+                }
+                Ok(ty)
+            },
+            DamlExpr::Abs(abs) => self.scoped(|checker| {
+                for param in abs.params() {
+                    checker.expect_kind(param.ty(), &DamlKind::Star)?;
+                    checker.vars.push((param.var(), param.ty().clone()));
+                }
+                let body = checker.infer(abs.body())?;
+                Ok(arrows(abs.params().iter().map(|param| param.ty().clone()).collect(), body))
+            }),
+            DamlExpr::TyAbs(ty_abs) => self.scoped(|checker| {
+                checker.push_type_params(ty_abs.params());
+                let body = checker.infer(ty_abs.body())?;
+                Ok(DamlType::Forall(DamlForall::new(ty_abs.params().to_vec(), Box::new(body))))
+            }),
+            DamlExpr::Case(case) => self.infer_case(case),
+            DamlExpr::Let(block) => self.scoped(|checker| {
+                for binding in block.bindings() {
+                    let binder = binding.binder();
+                    checker.expect_kind(binder.ty(), &DamlKind::Star)?;
+                    checker.check(binding.bound(), binder.ty())?;
+                    checker.vars.push((binder.var(), binder.ty().clone()));
+                }
+                checker.infer(block.body())
+            }),
+            DamlExpr::Nil(ty) => {
+                self.expect_kind(ty, &DamlKind::Star)?;
+                Ok(list(ty.clone()))
+            },
+            DamlExpr::Cons(cons) => {
+                self.expect_kind(cons.ty(), &DamlKind::Star)?;
+                for front in cons.front() {
+                    self.check(front, cons.ty())?;
+                }
+                let list_ty = list(cons.ty().clone());
+                self.check(cons.tail(), &list_ty)?;
+                Ok(list_ty)
+            },
+            DamlExpr::Update(update) => self.infer_update(update),
+            DamlExpr::Scenario(scenario) => self.infer_scenario(scenario),
+            DamlExpr::OptionalNone(ty) => {
+                self.expect_kind(ty, &DamlKind::Star)?;
+                Ok(optional(ty.clone()))
+            },
+            DamlExpr::OptionalSome(some) => {
+                self.expect_kind(some.ty(), &DamlKind::Star)?;
+                self.check(some.body(), some.ty())?;
+                Ok(optional(some.ty().clone()))
+            },
+            DamlExpr::ToAny(to_any) => {
+                self.expect_kind(to_any.ty(), &DamlKind::Star)?;
+                self.check(to_any.expr(), to_any.ty())?;
+                Ok(DamlType::Any)
+            },
+            DamlExpr::FromAny(from_any) => {
+                self.expect_kind(from_any.ty(), &DamlKind::Star)?;
+                self.check(from_any.expr(), &DamlType::Any)?;
+                Ok(optional(from_any.ty().clone()))
+            },
+            DamlExpr::TypeRep(ty) => {
+                self.expect_kind(ty, &DamlKind::Star)?;
+                Ok(DamlType::TypeRep)
+            },
+            DamlExpr::ToAnyException(to_any_exception) => {
+                self.expect_kind(to_any_exception.ty(), &DamlKind::Star)?;
+                self.check(to_any_exception.expr(), to_any_exception.ty())?;
+                Ok(DamlType::AnyException)
+            },
+            DamlExpr::FromAnyException(from_any_exception) => {
+                self.expect_kind(from_any_exception.ty(), &DamlKind::Star)?;
+                self.check(from_any_exception.expr(), &DamlType::AnyException)?;
+                Ok(optional(from_any_exception.ty().clone()))
+            },
+            DamlExpr::Throw(throw) => {
+                self.expect_kind(throw.return_type(), &DamlKind::Star)?;
+                self.expect_kind(throw.exception_type(), &DamlKind::Star)?;
+                self.check(throw.exception_expr(), throw.exception_type())?;
+                Ok(throw.return_type().clone())
+            },
+            DamlExpr::ToInterface(cast) => {
+                let interface_ty = self.interface_type(cast.interface_type())?;
+                let template_ty = self.template_type(cast.template_type())?;
+                self.check(cast.expr(), &template_ty)?;
+                Ok(interface_ty)
+            },
+            DamlExpr::FromInterface(cast) => {
+                let interface_ty = self.interface_type(cast.interface_type())?;
+                let template_ty = self.template_type(cast.template_type())?;
+                self.check(cast.expr(), &interface_ty)?;
+                Ok(optional(template_ty))
+            },
+            DamlExpr::UnsafeFromInterface(cast) => {
+                let interface_ty = self.interface_type(cast.interface_type())?;
+                let template_ty = self.template_type(cast.template_type())?;
+                self.check(cast.contract_id_expr(), &contract_id(interface_ty.clone()))?;
+                self.check(cast.interface_expr(), &interface_ty)?;
+                Ok(template_ty)
+            },
+            DamlExpr::CallInterface(call) => {
+                let interface = self.interface(call.interface_type())?;
+                self.check(call.interface_expr(), &tycon(call.interface_type()))?;
+                interface
+                    .methods()
+                    .iter()
+                    .find(|method| method.name() == call.method())
+                    .map(|method| method.ty().clone())
+                    .ok_or_else(|| {
+                        DamlTypeCheckErrorKind::UnknownMethod(
+                            call.method().to_owned(),
+                            render_tycon_name(call.interface_type()),
+                        )
+                    })
+            },
+            DamlExpr::ToRequiredInterface(cast) => {
+                let required_ty = self.interface_type(cast.required_interface())?;
+                let requiring_ty = self.interface_type(cast.requiring_interface())?;
+                self.check(cast.expr(), &requiring_ty)?;
+                Ok(required_ty)
+            },
+            DamlExpr::FromRequiredInterface(cast) => {
+                let required_ty = self.interface_type(cast.required_interface())?;
+                let requiring_ty = self.interface_type(cast.requiring_interface())?;
+                self.check(cast.expr(), &required_ty)?;
+                Ok(optional(requiring_ty))
+            },
+            DamlExpr::UnsafeFromRequiredInterface(cast) => {
+                let required_ty = self.interface_type(cast.required_interface())?;
+                let requiring_ty = self.interface_type(cast.requiring_interface())?;
+                self.check(cast.contract_id_expr(), &contract_id(required_ty.clone()))?;
+                self.check(cast.interface_expr(), &required_ty)?;
+                Ok(requiring_ty)
+            },
+            DamlExpr::InterfaceTemplateTypeRep(interface_expr) => {
+                let interface_ty = self.interface_type(interface_expr.interface())?;
+                self.check(interface_expr.expr(), &interface_ty)?;
+                Ok(DamlType::TypeRep)
+            },
+            DamlExpr::SignatoryInterface(interface_expr) | DamlExpr::ObserverInterface(interface_expr) => {
+                let interface_ty = self.interface_type(interface_expr.interface())?;
+                self.check(interface_expr.expr(), &interface_ty)?;
+                Ok(list(DamlType::Party))
+            },
+            DamlExpr::ViewInterface(interface_expr) => {
+                let interface = self.interface(interface_expr.interface())?;
+                self.check(interface_expr.expr(), &tycon(interface_expr.interface()))?;
+                Ok(interface.view().clone())
+            },
+        }
+    }
+
+    fn infer_prim_lit(&self, lit: &DamlPrimLit<'_>) -> TypeCheckResult<DamlType<'a>> {
+        Ok(match lit {
+            DamlPrimLit::Int64(_) => DamlType::Int64,
+            DamlPrimLit::Text(_) => DamlType::Text,
+            DamlPrimLit::Party(_) => DamlType::Party,
+            DamlPrimLit::Date(_) => DamlType::Date,
+            DamlPrimLit::Timestamp(_) => DamlType::Timestamp,
+            DamlPrimLit::RoundingMode(_) => DamlType::RoundingMode,
+            DamlPrimLit::Numeric(numeric) => {
+                let scale = if self.package.language_version().supports_feature(&LanguageFeatureVersion::NUMERIC) {
+                    numeric.split_once('.').map_or(0, |(_, fraction)| fraction.len())
+                } else {
+                    usize::from(LEGACY_DECIMAL_SCALE)
+                };
+                match u8::try_from(scale) {
+                    Ok(scale) if scale <= MAX_NUMERIC_SCALE => types::numeric(DamlType::Nat(scale)),
+                    _ => return Err(DamlTypeCheckErrorKind::NatOutOfRange(u8::try_from(scale).unwrap_or(u8::MAX))),
+                }
+            },
+        })
+    }
+
+    /// Instantiate the outermost type variable of the polymorphic type `ty` with `arg`.
+    fn instantiate(&mut self, ty: &DamlType<'a>, arg: &DamlType<'a>) -> TypeCheckResult<DamlType<'a>> {
+        let ty = self.normalize(ty)?;
+        let forall = match &ty {
+            DamlType::Forall(forall) if !forall.vars().is_empty() => forall,
+            _ => return Err(DamlTypeCheckErrorKind::UnexpectedType("a polymorphic type".to_owned(), render_type(&ty))),
+        };
+        let (var, rest) = (&forall.vars()[0], &forall.vars()[1..]);
+        self.expect_kind(arg, var.kind())?;
+        let body = if rest.is_empty() {
+            forall.body().clone()
+        } else {
+            DamlType::Forall(DamlForall::new(rest.to_vec(), Box::new(forall.body().clone())))
+        };
+        substitute(&body, &[(var.var(), arg.clone())])
+    }
+
+    fn infer_case(&mut self, case: &'a DamlCase<'a>) -> TypeCheckResult<DamlType<'a>> {
+        let scrut_ty = self.infer(case.scrut())?;
+        let scrut_ty = self.normalize(&scrut_ty)?;
+        let mut result: Option<DamlType<'a>> = None;
+        for alt in case.alts() {
+            let alt_ty = self.scoped(|checker| {
+                checker.bind_pattern(&scrut_ty, alt.sum())?;
+                checker.infer(alt.body())
+            })?;
+            match &result {
+                Some(expected) => self.expect_type(expected, &alt_ty)?,
+                None => result = Some(alt_ty),
+            }
+        }
+        result.ok_or(DamlTypeCheckErrorKind::EmptyCase)
+    }
+
+    /// Check that the pattern `sum` matches the type `scrut_ty` and bring any variables it binds into scope.
+    fn bind_pattern(&mut self, scrut_ty: &DamlType<'a>, sum: &'a DamlCaseAltSum<'a>) -> TypeCheckResult<()> {
+        match sum {
+            DamlCaseAltSum::Default => {},
+            DamlCaseAltSum::Variant(variant) => {
+                let tycon = Self::expect_tycon(scrut_ty, variant.con())?;
+                let arg_ty = self.variant_field(&tycon, variant.variant())?;
+                self.vars.push((variant.binder(), arg_ty));
+            },
+            DamlCaseAltSum::Enum(enum_alt) => {
+                Self::expect_tycon(scrut_ty, enum_alt.con())?;
+                self.expect_enum_constructor(enum_alt.con(), enum_alt.constructor())?;
+            },
+            DamlCaseAltSum::PrimCon(DamlPrimCon::Unit) => self.expect_type(&DamlType::Unit, scrut_ty)?,
+            DamlCaseAltSum::PrimCon(DamlPrimCon::True | DamlPrimCon::False) =>
+                self.expect_type(&DamlType::Bool, scrut_ty)?,
+            DamlCaseAltSum::Nil => {
+                self.expect_list(scrut_ty)?;
+            },
+            DamlCaseAltSum::Cons(cons) => {
+                let elem = self.expect_list(scrut_ty)?;
+                self.vars.push((cons.var_head(), elem.clone()));
+                self.vars.push((cons.var_tail(), list(elem)));
+            },
+            DamlCaseAltSum::OptionalNone => {
+                self.expect_optional(scrut_ty)?;
+            },
+            DamlCaseAltSum::OptionalSome(some) => {
+                let elem = self.expect_optional(scrut_ty)?;
+                self.vars.push((some.var_body(), elem));
+            },
+        }
+        Ok(())
+    }
+
+    fn infer_block(&mut self, effect: Effect, block: &'a DamlBlock<'a>) -> TypeCheckResult<DamlType<'a>> {
+        self.scoped(|checker| {
+            for binding in block.bindings() {
+                let binder = binding.binder();
+                checker.expect_kind(binder.ty(), &DamlKind::Star)?;
+                checker.check(binding.bound(), &effect.wrap(binder.ty().clone()))?;
+                checker.vars.push((binder.var(), binder.ty().clone()));
+            }
+            let body_ty = checker.infer(block.body())?;
+            checker.expect_effect(effect, &body_ty)?;
+            Ok(body_ty)
+        })
+    }
+
+    fn infer_update(&mut self, expr: &'a DamlUpdate<'a>) -> TypeCheckResult<DamlType<'a>> {
+        match expr {
+            DamlUpdate::Pure(pure) => {
+                self.expect_kind(pure.ty(), &DamlKind::Star)?;
+                self.check(pure.expr(), pure.ty())?;
+                Ok(update(pure.ty().clone()))
+            },
+            DamlUpdate::Block(block) => self.infer_block(Effect::Update, block),
+            DamlUpdate::Create(create) => {
+                let template_ty = self.template_type(create.template())?;
+                self.check(create.expr(), &template_ty)?;
+                Ok(update(contract_id(template_ty)))
+            },
+            DamlUpdate::Exercise(exercise) => {
+                let choice = self.template_choice(exercise.template(), exercise.choice())?;
+                self.check(exercise.cid(), &contract_id(tycon(exercise.template())))?;
+                self.check(exercise.arg(), choice.argument_type())?;
+                Ok(update(choice.return_type().clone()))
+            },
+            DamlUpdate::ExerciseByKey(exercise) => {
+                let choice = self.template_choice(exercise.template(), exercise.choice())?;
+                let key_ty = self.template_key(exercise.template())?;
+                self.check(exercise.key(), key_ty)?;
+                self.check(exercise.arg(), choice.argument_type())?;
+                Ok(update(choice.return_type().clone()))
+            },
+            DamlUpdate::Fetch(fetch) => {
+                let template_ty = self.template_type(fetch.template())?;
+                self.check(fetch.cid(), &contract_id(template_ty.clone()))?;
+                Ok(update(template_ty))
+            },
+            DamlUpdate::GetTime => Ok(update(DamlType::Timestamp)),
+            DamlUpdate::LookupByKey(lookup) => {
+                let key_ty = self.template_key(lookup.template())?;
+                self.check(lookup.key(), key_ty)?;
+                Ok(update(optional(contract_id(tycon(lookup.template())))))
+            },
+            DamlUpdate::FetchByKey(fetch) => {
+                let key_ty = self.template_key(fetch.template())?;
+                self.check(fetch.key(), key_ty)?;
+                let template_ty = tycon(fetch.template());
+                Ok(update(struct_type(vec![
+                    ("contractId", contract_id(template_ty.clone())),
+                    ("contract", template_ty),
+                ])))
+            },
+            DamlUpdate::EmbedExpr(embed) => {
+                let update_ty = update(embed.ty().clone());
+                self.check(embed.body(), &update_ty)?;
+                Ok(update_ty)
+            },
+            DamlUpdate::TryCatch(try_catch) => {
+                let update_ty = update(try_catch.return_type().clone());
+                self.check(try_catch.try_expr(), &update_ty)?;
+                self.scoped(|checker| {
+                    checker.vars.push((try_catch.var(), DamlType::AnyException));
+                    checker.check(try_catch.catch_expr(), &optional(update_ty.clone()))
+                })?;
+                Ok(update_ty)
+            },
+            DamlUpdate::ExerciseInterface(exercise) => {
+                let interface = self.interface(exercise.interface())?;
+                let choice = interface.choices().iter().find(|c| c.name() == exercise.choice()).ok_or_else(|| {
+                    DamlTypeCheckErrorKind::UnknownChoice(
+                        exercise.choice().to_owned(),
+                        render_tycon_name(exercise.interface()),
+                    )
+                })?;
+                self.check(exercise.cid(), &contract_id(tycon(exercise.interface())))?;
+                self.check(exercise.arg(), choice.argument_type())?;
+                Ok(update(choice.return_type().clone()))
+            },
+            DamlUpdate::FetchInterface(fetch) => {
+                let interface_ty = self.interface_type(fetch.interface())?;
+                self.check(fetch.cid(), &contract_id(interface_ty.clone()))?;
+                Ok(update(interface_ty))
+            },
+        }
+    }
+
+    fn infer_scenario(&mut self, expr: &'a DamlScenario<'a>) -> TypeCheckResult<DamlType<'a>> {
+        match expr {
+            DamlScenario::Pure(pure) => {
+                self.expect_kind(pure.ty(), &DamlKind::Star)?;
+                self.check(pure.expr(), pure.ty())?;
+                Ok(scenario(pure.ty().clone()))
+            },
+            DamlScenario::Block(block) => self.infer_block(Effect::Scenario, block),
+            DamlScenario::Commit(commit) => {
+                self.check(commit.party(), &DamlType::Party)?;
+                self.check(commit.expr(), &update(commit.ret_type().clone()))?;
+                Ok(scenario(commit.ret_type().clone()))
+            },
+            DamlScenario::MustFailAt(commit) => {
+                self.check(commit.party(), &DamlType::Party)?;
+                self.check(commit.expr(), &update(commit.ret_type().clone()))?;
+                Ok(scenario(DamlType::Unit))
+            },
+            DamlScenario::Pass(delta) => {
+                self.check(delta, &DamlType::Int64)?;
+                Ok(scenario(DamlType::Timestamp))
+            },
+            DamlScenario::GetTime => Ok(scenario(DamlType::Timestamp)),
+            DamlScenario::GetParty(name) => {
+                self.check(name, &DamlType::Text)?;
+                Ok(scenario(DamlType::Party))
+            },
+            DamlScenario::EmbedExpr(embed) => {
+                let scenario_ty = scenario(embed.ty().clone());
+                self.check(embed.body(), &scenario_ty)?;
+                Ok(scenario_ty)
+            },
+        }
+    }
+}
+
+fn render_value_name(name: &DamlValueName<'_>) -> String {
+    format!("{}:{}", name.module_path().join("."), name.name())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::element::{
+        DamlAbs, DamlApp, DamlArchive, DamlBuiltinFunction, DamlExpr, DamlKind, DamlModule, DamlPackage, DamlPrimLit,
+        DamlTyApp, DamlType, DamlVarWithType,
+    };
+    use crate::error::DamlTypeCheckErrorKind;
+    use crate::typecheck::types::{arrow, forall, numeric, substitute, types_equal, var};
+    use crate::typecheck::DamlTypeChecker;
+    use crate::LanguageVersion;
+    use std::borrow::Cow;
+
+    fn package() -> DamlPackage<'static> {
+        DamlPackage::new(Cow::from("Test"), Cow::from("pkg"), None, LanguageVersion::V1_14, DamlModule::new_root())
+    }
+
+    fn add_int64(arg: DamlExpr<'static>) -> DamlExpr<'static> {
+        DamlExpr::App(DamlApp::new(Box::new(DamlExpr::Builtin(DamlBuiltinFunction::AddInt64)), vec![arg]))
+    }
+
+    #[test]
+    fn test_infer_abs() {
+        let archive = DamlArchive::default();
+        let package = package();
+        let expr = DamlExpr::Abs(DamlAbs::new(
+            vec![DamlVarWithType::new(DamlType::Int64, Cow::from("x"))],
+            Box::new(add_int64(DamlExpr::Var(Cow::from("x")))),
+        ));
+        let ty = DamlTypeChecker::new(&archive).infer_expr(&package, &expr).unwrap();
+        assert!(types_equal(&arrow(DamlType::Int64, arrow(DamlType::Int64, DamlType::Int64)), &ty));
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let archive = DamlArchive::default();
+        let package = package();
+        let expr = add_int64(DamlExpr::PrimLit(DamlPrimLit::Text(Cow::from("one"))));
+        let err = DamlTypeChecker::new(&archive).infer_expr(&package, &expr).unwrap_err();
+        assert_eq!(DamlTypeCheckErrorKind::TypeMismatch("Int64".to_owned(), "Text".to_owned()), err);
+    }
+
+    #[test]
+    fn test_unknown_variable() {
+        let archive = DamlArchive::default();
+        let package = package();
+        let expr = add_int64(DamlExpr::Var(Cow::from("y")));
+        let err = DamlTypeChecker::new(&archive).infer_expr(&package, &expr).unwrap_err();
+        assert_eq!(DamlTypeCheckErrorKind::UnknownVariable("y".to_owned()), err);
+    }
+
+    #[test]
+    fn test_nat_out_of_range() {
+        let archive = DamlArchive::default();
+        let package = package();
+        let expr =
+            DamlExpr::TyApp(DamlTyApp::new(Box::new(DamlExpr::Builtin(DamlBuiltinFunction::NumericToText)), vec![
+                DamlType::Nat(38),
+            ]));
+        let err = DamlTypeChecker::new(&archive).infer_expr(&package, &expr).unwrap_err();
+        assert_eq!(DamlTypeCheckErrorKind::NatOutOfRange(38), err);
+    }
+
+    #[test]
+    fn test_instantiate_numeric() {
+        let archive = DamlArchive::default();
+        let package = package();
+        let expr =
+            DamlExpr::TyApp(DamlTyApp::new(Box::new(DamlExpr::Builtin(DamlBuiltinFunction::NumericToText)), vec![
+                DamlType::Nat(10),
+            ]));
+        let ty = DamlTypeChecker::new(&archive).infer_expr(&package, &expr).unwrap();
+        assert!(types_equal(&arrow(numeric(DamlType::Nat(10)), DamlType::Text), &ty));
+    }
+
+    #[test]
+    fn test_substitute_avoids_capture() {
+        let ty = forall(&[("b", DamlKind::Star)], arrow(var("a"), var("b")));
+        let substituted = substitute(&ty, &[("a", var("b"))]).unwrap();
+        let expected = forall(&[("c", DamlKind::Star)], arrow(var("b"), var("c")));
+        assert!(types_equal(&expected, &substituted));
+        assert!(!types_equal(&forall(&[("b", DamlKind::Star)], arrow(var("b"), var("b"))), &substituted));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::element::{DamlBuiltinFunction, DamlKind, DamlType};
+use crate::typecheck::types::{
+    arrows, contract_id, forall, gen_map, list, numeric, optional, struct_type, text_map, var, LEGACY_DECIMAL_SCALE,
+};
+
+/// The type of a builtin function.
+#[allow(clippy::too_many_lines)]
+pub fn builtin_type(builtin: &DamlBuiltinFunction) -> DamlType<'static> {
+    match builtin {
+        DamlBuiltinFunction::AddDecimal
+        | DamlBuiltinFunction::SubDecimal
+        | DamlBuiltinFunction::MulDecimal
+        | DamlBuiltinFunction::DivDecimal => binary(decimal()),
+        DamlBuiltinFunction::RoundDecimal => arrows(vec![DamlType::Int64, decimal()], decimal()),
+        DamlBuiltinFunction::AddNumeric | DamlBuiltinFunction::SubNumeric => for_nats(&["n"], binary(scaled("n"))),
+        DamlBuiltinFunction::MulNumeric | DamlBuiltinFunction::DivNumeric =>
+            for_nats(&["n1", "n2", "n3"], arrows(vec![scaled("n1"), scaled("n2")], scaled("n3"))),
+        DamlBuiltinFunction::RoundNumeric => for_nats(&["n"], arrows(vec![DamlType::Int64, scaled("n")], scaled("n"))),
+        DamlBuiltinFunction::CastNumeric | DamlBuiltinFunction::ShiftNumeric =>
+            for_nats(&["n1", "n2"], arrows(vec![scaled("n1")], scaled("n2"))),
+        DamlBuiltinFunction::AddInt64
+        | DamlBuiltinFunction::SubInt64
+        | DamlBuiltinFunction::MulInt64
+        | DamlBuiltinFunction::DivInt64
+        | DamlBuiltinFunction::ModInt64
+        | DamlBuiltinFunction::ExpInt64 => binary(DamlType::Int64),
+        DamlBuiltinFunction::Foldl => for_types(
+            &["a", "b"],
+            arrows(vec![arrows(vec![var("b"), var("a")], var("b")), var("b"), list(var("a"))], var("b")),
+        ),
+        DamlBuiltinFunction::Foldr => for_types(
+            &["a", "b"],
+            arrows(vec![arrows(vec![var("a"), var("b")], var("b")), var("b"), list(var("a"))], var("b")),
+        ),
+        DamlBuiltinFunction::TextmapEmpty => for_types(&["a"], text_map(var("a"))),
+        DamlBuiltinFunction::TextmapInsert =>
+            for_types(&["a"], arrows(vec![DamlType::Text, var("a"), text_map(var("a"))], text_map(var("a")))),
+        DamlBuiltinFunction::TextmapLookup =>
+            for_types(&["a"], arrows(vec![DamlType::Text, text_map(var("a"))], optional(var("a")))),
+        DamlBuiltinFunction::TextmapDelete =>
+            for_types(&["a"], arrows(vec![DamlType::Text, text_map(var("a"))], text_map(var("a")))),
+        DamlBuiltinFunction::TextmapToList => for_types(
+            &["a"],
+            arrows(vec![text_map(var("a"))], list(struct_type(vec![("key", DamlType::Text), ("value", var("a"))]))),
+        ),
+        DamlBuiltinFunction::TextmapSize => for_types(&["a"], arrows(vec![text_map(var("a"))], DamlType::Int64)),
+        DamlBuiltinFunction::ExplodeText => arrows(vec![DamlType::Text], list(DamlType::Text)),
+        DamlBuiltinFunction::AppendText => binary(DamlType::Text),
+        DamlBuiltinFunction::Error => for_types(&["a"], arrows(vec![DamlType::Text], var("a"))),
+        DamlBuiltinFunction::AnyExceptionMessage => arrows(vec![DamlType::AnyException], DamlType::Text),
+        DamlBuiltinFunction::LeqInt64
+        | DamlBuiltinFunction::LessInt64
+        | DamlBuiltinFunction::GeqInt64
+        | DamlBuiltinFunction::GreaterInt64
+        | DamlBuiltinFunction::EqualInt64 => comparison(DamlType::Int64),
+        DamlBuiltinFunction::LeqDecimal
+        | DamlBuiltinFunction::LessDecimal
+        | DamlBuiltinFunction::GeqDecimal
+        | DamlBuiltinFunction::GreaterDecimal
+        | DamlBuiltinFunction::EqualDecimal => comparison(decimal()),
+        DamlBuiltinFunction::LeqNumeric
+        | DamlBuiltinFunction::LessNumeric
+        | DamlBuiltinFunction::GeqNumeric
+        | DamlBuiltinFunction::GreaterNumeric
+        | DamlBuiltinFunction::EqualNumeric => for_nats(&["n"], comparison(scaled("n"))),
+        DamlBuiltinFunction::LeqText
+        | DamlBuiltinFunction::LessText
+        | DamlBuiltinFunction::GeqText
+        | DamlBuiltinFunction::GreaterText
+        | DamlBuiltinFunction::EqualText => comparison(DamlType::Text),
+        DamlBuiltinFunction::LeqTimestamp
+        | DamlBuiltinFunction::LessTimestamp
+        | DamlBuiltinFunction::GeqTimestamp
+        | DamlBuiltinFunction::GreaterTimestamp
+        | DamlBuiltinFunction::EqualTimestamp => comparison(DamlType::Timestamp),
+        DamlBuiltinFunction::LeqDate
+        | DamlBuiltinFunction::LessDate
+        | DamlBuiltinFunction::GeqDate
+        | DamlBuiltinFunction::GreaterDate
+        | DamlBuiltinFunction::EqualDate => comparison(DamlType::Date),
+        DamlBuiltinFunction::LeqParty
+        | DamlBuiltinFunction::LessParty
+        | DamlBuiltinFunction::GeqParty
+        | DamlBuiltinFunction::GreaterParty
+        | DamlBuiltinFunction::EqualParty => comparison(DamlType::Party),
+        DamlBuiltinFunction::EqualBool => comparison(DamlType::Bool),
+        DamlBuiltinFunction::EqualTypeRep => comparison(DamlType::TypeRep),
+        DamlBuiltinFunction::EqualContractId => for_types(&["a"], comparison(contract_id(var("a")))),
+        DamlBuiltinFunction::EqualList =>
+            for_types(&["a"], arrows(vec![comparison(var("a")), list(var("a")), list(var("a"))], DamlType::Bool)),
+        DamlBuiltinFunction::Int64ToText => arrows(vec![DamlType::Int64], DamlType::Text),
+        DamlBuiltinFunction::DecimalToText => arrows(vec![decimal()], DamlType::Text),
+        DamlBuiltinFunction::NumericToText => for_nats(&["n"], arrows(vec![scaled("n")], DamlType::Text)),
+        DamlBuiltinFunction::TextToText | DamlBuiltinFunction::Sha256Text =>
+            arrows(vec![DamlType::Text], DamlType::Text),
+        DamlBuiltinFunction::TimestampToText => arrows(vec![DamlType::Timestamp], DamlType::Text),
+        DamlBuiltinFunction::DateToText => arrows(vec![DamlType::Date], DamlType::Text),
+        DamlBuiltinFunction::PartyToQuotedText | DamlBuiltinFunction::PartyToText =>
+            arrows(vec![DamlType::Party], DamlType::Text),
+        DamlBuiltinFunction::TextToParty => arrows(vec![DamlType::Text], optional(DamlType::Party)),
+        DamlBuiltinFunction::TextToInt64 => arrows(vec![DamlType::Text], optional(DamlType::Int64)),
+        DamlBuiltinFunction::TextToDecimal => arrows(vec![DamlType::Text], optional(decimal())),
+        DamlBuiltinFunction::TextToNumeric => for_nats(&["n"], arrows(vec![DamlType::Text], optional(scaled("n")))),
+        DamlBuiltinFunction::ContractIdToText =>
+            for_types(&["a"], arrows(vec![contract_id(var("a"))], optional(DamlType::Text))),
+        DamlBuiltinFunction::DateToUnixDays => arrows(vec![DamlType::Date], DamlType::Int64),
+        DamlBuiltinFunction::UnixDaysToDate => arrows(vec![DamlType::Int64], DamlType::Date),
+        DamlBuiltinFunction::TimestampToUnixMicroseconds => arrows(vec![DamlType::Timestamp], DamlType::Int64),
+        DamlBuiltinFunction::UnixMicrosecondsToTimestamp => arrows(vec![DamlType::Int64], DamlType::Timestamp),
+        DamlBuiltinFunction::Int64ToDecimal => arrows(vec![DamlType::Int64], decimal()),
+        DamlBuiltinFunction::DecimalToInt64 => arrows(vec![decimal()], DamlType::Int64),
+        DamlBuiltinFunction::Int64ToNumeric => for_nats(&["n"], arrows(vec![DamlType::Int64], scaled("n"))),
+        DamlBuiltinFunction::NumericToInt64 => for_nats(&["n"], arrows(vec![scaled("n")], DamlType::Int64)),
+        DamlBuiltinFunction::ImplodeText => arrows(vec![list(DamlType::Text)], DamlType::Text),
+        DamlBuiltinFunction::Trace => for_types(&["a"], arrows(vec![DamlType::Text, var("a")], var("a"))),
+        DamlBuiltinFunction::CoerceContractId =>
+            for_types(&["a", "b"], arrows(vec![contract_id(var("a"))], contract_id(var("b")))),
+        DamlBuiltinFunction::CodePointsToText => arrows(vec![list(DamlType::Int64)], DamlType::Text),
+        DamlBuiltinFunction::TextPointsToCode => arrows(vec![DamlType::Text], list(DamlType::Int64)),
+        DamlBuiltinFunction::ScaleBignumeric | DamlBuiltinFunction::PrecisionBignumeric =>
+            arrows(vec![DamlType::Bignumeric], DamlType::Int64),
+        DamlBuiltinFunction::AddBignumeric
+        | DamlBuiltinFunction::SubBignumeric
+        | DamlBuiltinFunction::MulBignumeric => binary(DamlType::Bignumeric),
+        DamlBuiltinFunction::DivBignumeric => arrows(
+            vec![DamlType::Int64, DamlType::RoundingMode, DamlType::Bignumeric, DamlType::Bignumeric],
+            DamlType::Bignumeric,
+        ),
+        DamlBuiltinFunction::ShiftBignumeric | DamlBuiltinFunction::ShiftRightBignumeric =>
+            arrows(vec![DamlType::Int64, DamlType::Bignumeric], DamlType::Bignumeric),
+        DamlBuiltinFunction::BigNumericToNumeric => for_nats(&["n"], arrows(vec![DamlType::Bignumeric], scaled("n"))),
+        DamlBuiltinFunction::NumericToBigNumeric => for_nats(&["n"], arrows(vec![scaled("n")], DamlType::Bignumeric)),
+        DamlBuiltinFunction::BigNumericToText => arrows(vec![DamlType::Bignumeric], DamlType::Text),
+        DamlBuiltinFunction::GenmapEmpty => for_types(&["k", "v"], map()),
+        DamlBuiltinFunction::GenmapInsert => for_types(&["k", "v"], arrows(vec![var("k"), var("v"), map()], map())),
+        DamlBuiltinFunction::GenmapLookup => for_types(&["k", "v"], arrows(vec![var("k"), map()], optional(var("v")))),
+        DamlBuiltinFunction::GenmapDelete => for_types(&["k", "v"], arrows(vec![var("k"), map()], map())),
+        DamlBuiltinFunction::GenmapKeys => for_types(&["k", "v"], arrows(vec![map()], list(var("k")))),
+        DamlBuiltinFunction::GenmapValues => for_types(&["k", "v"], arrows(vec![map()], list(var("v")))),
+        DamlBuiltinFunction::GenmapSize => for_types(&["k", "v"], arrows(vec![map()], DamlType::Int64)),
+        DamlBuiltinFunction::Equal
+        | DamlBuiltinFunction::LessEq
+        | DamlBuiltinFunction::Less
+        | DamlBuiltinFunction::GreaterEq
+        | DamlBuiltinFunction::Greater => for_types(&["a"], comparison(var("a"))),
+    }
+}
+
+/// A legacy `Decimal`, which is a `Numeric` with a fixed scale.
+fn decimal() -> DamlType<'static> {
+    numeric(DamlType::Nat(LEGACY_DECIMAL_SCALE))
+}
+
+/// A `Numeric` with the scale given by the type variable `n`.
+fn scaled(n: &'static str) -> DamlType<'static> {
+    numeric(var(n))
+}
+
+/// The `GenMap k v` type.
+fn map() -> DamlType<'static> {
+    gen_map(var("k"), var("v"))
+}
+
+fn binary(ty: DamlType<'static>) -> DamlType<'static> {
+    arrows(vec![ty.clone(), ty.clone()], ty)
+}
+
+fn comparison(ty: DamlType<'static>) -> DamlType<'static> {
+    arrows(vec![ty.clone(), ty], DamlType::Bool)
+}
+
+fn for_types(vars: &[&'static str], body: DamlType<'static>) -> DamlType<'static> {
+    forall(&vars.iter().map(|v| (*v, DamlKind::Star)).collect::<Vec<_>>(), body)
+}
+
+fn for_nats(vars: &[&'static str], body: DamlType<'static>) -> DamlType<'static> {
+    forall(&vars.iter().map(|v| (*v, DamlKind::Nat)).collect::<Vec<_>>(), body)
+}
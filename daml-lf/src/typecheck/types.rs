@@ -0,0 +1,383 @@
+use crate::element::{
+    DamlAbsoluteTyCon, DamlArrow, DamlField, DamlForall, DamlKind, DamlStruct, DamlSyn, DamlTyCon, DamlTyConName,
+    DamlType, DamlTypeVarWithKind, DamlVar,
+};
+use crate::error::DamlTypeCheckErrorKind;
+use crate::PrettyPrinter;
+use itertools::Itertools;
+use std::borrow::Cow;
+
+/// The result of checking a single type or expression.
+pub type TypeCheckResult<T> = Result<T, DamlTypeCheckErrorKind>;
+
+/// The maximum scale of a `Numeric`.
+pub const MAX_NUMERIC_SCALE: u8 = 37;
+
+/// The scale of a legacy `Decimal`.
+pub const LEGACY_DECIMAL_SCALE: u8 = 10;
+
+pub fn arrow<'a>(param: DamlType<'a>, result: DamlType<'a>) -> DamlType<'a> {
+    DamlType::Arrow(vec![param, result])
+}
+
+/// The curried function type `param1 -> param2 -> ... -> result`.
+pub fn arrows<'a>(params: Vec<DamlType<'a>>, result: DamlType<'a>) -> DamlType<'a> {
+    params.into_iter().rev().fold(result, |result, param| arrow(param, result))
+}
+
+pub fn var(name: &str) -> DamlType<'_> {
+    DamlType::Var(DamlVar::new(Cow::Borrowed(name), vec![]))
+}
+
+pub fn forall<'a>(vars: &[(&'a str, DamlKind)], body: DamlType<'a>) -> DamlType<'a> {
+    let vars = vars.iter().map(|(name, kind)| DamlTypeVarWithKind::new(Cow::Borrowed(*name), kind.clone())).collect();
+    DamlType::Forall(DamlForall::new(vars, Box::new(body)))
+}
+
+pub fn list(elem: DamlType<'_>) -> DamlType<'_> {
+    DamlType::List(vec![elem])
+}
+
+pub fn optional(elem: DamlType<'_>) -> DamlType<'_> {
+    DamlType::Optional(vec![elem])
+}
+
+pub fn text_map(value: DamlType<'_>) -> DamlType<'_> {
+    DamlType::TextMap(vec![value])
+}
+
+pub fn gen_map<'a>(key: DamlType<'a>, value: DamlType<'a>) -> DamlType<'a> {
+    DamlType::GenMap(vec![key, value])
+}
+
+pub fn contract_id(template: DamlType<'_>) -> DamlType<'_> {
+    DamlType::ContractId(Some(Box::new(template)))
+}
+
+pub fn numeric(scale: DamlType<'_>) -> DamlType<'_> {
+    DamlType::Numeric(vec![scale])
+}
+
+pub fn update(result: DamlType<'_>) -> DamlType<'_> {
+    DamlType::Update(vec![result])
+}
+
+pub fn scenario(result: DamlType<'_>) -> DamlType<'_> {
+    DamlType::Scenario(vec![result])
+}
+
+pub fn struct_type<'a>(fields: Vec<(&'a str, DamlType<'a>)>) -> DamlType<'a> {
+    DamlType::Struct(DamlStruct::new(
+        fields.into_iter().map(|(name, ty)| DamlField::new(Cow::Borrowed(name), ty)).collect(),
+    ))
+}
+
+/// The (unapplied) type of the data type or interface with the given name.
+pub fn tycon<'a>(name: &DamlTyConName<'a>) -> DamlType<'a> {
+    DamlType::TyCon(DamlTyCon::new(Box::new(name.clone()), vec![]))
+}
+
+/// The type of the data type or interface defined with the given package id, module path and name.
+pub fn absolute_tycon<'a>(
+    package_id: &'a str,
+    module_path: impl Iterator<Item = &'a str>,
+    name: &'a str,
+) -> DamlType<'a> {
+    tycon(&DamlTyConName::Absolute(DamlAbsoluteTyCon::new(
+        Cow::Borrowed(name),
+        Cow::Borrowed(package_id),
+        Cow::default(),
+        module_path.map(Cow::Borrowed).collect(),
+    )))
+}
+
+/// Build a kind from a list of parameter kinds and a result kind.
+pub fn make_kind(params: Vec<DamlKind>, result: DamlKind) -> DamlKind {
+    if params.is_empty() {
+        result
+    } else {
+        DamlKind::Arrow(Box::new(DamlArrow::new(params, result)))
+    }
+}
+
+/// Split a kind into its (flattened) parameter kinds and result kind.
+pub fn flatten_kind(kind: &DamlKind) -> (Vec<DamlKind>, DamlKind) {
+    let mut params = vec![];
+    let mut kind = kind;
+    while let DamlKind::Arrow(arrow) = kind {
+        params.extend(arrow.params().iter().cloned());
+        kind = arrow.result();
+    }
+    (params, kind.clone())
+}
+
+pub fn kind_eq(a: &DamlKind, b: &DamlKind) -> bool {
+    match (a, b) {
+        (DamlKind::Star, DamlKind::Star) | (DamlKind::Nat, DamlKind::Nat) => true,
+        (DamlKind::Arrow(_), DamlKind::Arrow(_)) => {
+            let (a_params, a_result) = flatten_kind(a);
+            let (b_params, b_result) = flatten_kind(b);
+            a_params.len() == b_params.len()
+                && a_params.iter().zip(&b_params).all(|(a, b)| kind_eq(a, b))
+                && kind_eq(&a_result, &b_result)
+        },
+        _ => false,
+    }
+}
+
+/// Rebuild `ty` with each of its immediate child types replaced by the result of `f`.
+pub fn map_children<'a, F>(ty: &DamlType<'a>, f: &mut F) -> TypeCheckResult<DamlType<'a>>
+where
+    F: FnMut(&DamlType<'a>) -> TypeCheckResult<DamlType<'a>>,
+{
+    Ok(match ty {
+        DamlType::ContractId(Some(inner)) => DamlType::ContractId(Some(Box::new(f(inner)?))),
+        DamlType::Numeric(args) => DamlType::Numeric(map_all(args, f)?),
+        DamlType::List(args) => DamlType::List(map_all(args, f)?),
+        DamlType::TextMap(args) => DamlType::TextMap(map_all(args, f)?),
+        DamlType::GenMap(args) => DamlType::GenMap(map_all(args, f)?),
+        DamlType::Optional(args) => DamlType::Optional(map_all(args, f)?),
+        DamlType::Arrow(args) => DamlType::Arrow(map_all(args, f)?),
+        DamlType::Update(args) => DamlType::Update(map_all(args, f)?),
+        DamlType::Scenario(args) => DamlType::Scenario(map_all(args, f)?),
+        DamlType::TyCon(tycon) =>
+            DamlType::TyCon(DamlTyCon::new(Box::new(tycon.tycon().clone()), map_all(tycon.type_arguments(), f)?)),
+        DamlType::BoxedTyCon(tycon) =>
+            DamlType::BoxedTyCon(DamlTyCon::new(Box::new(tycon.tycon().clone()), map_all(tycon.type_arguments(), f)?)),
+        DamlType::Var(var) if !var.type_arguments().is_empty() =>
+            DamlType::Var(DamlVar::new(Cow::Owned(var.var().to_owned()), map_all(var.type_arguments(), f)?)),
+        DamlType::Forall(forall) =>
+            DamlType::Forall(DamlForall::new(forall.vars().to_vec(), Box::new(f(forall.body())?))),
+        DamlType::Struct(tuple) => DamlType::Struct(DamlStruct::new(
+            tuple
+                .fields()
+                .iter()
+                .map(|field| Ok(DamlField::new(Cow::Owned(field.name().to_owned()), f(field.ty())?)))
+                .collect::<TypeCheckResult<_>>()?,
+        )),
+        DamlType::Syn(syn) => DamlType::Syn(DamlSyn::new(Box::new(syn.tysyn().clone()), map_all(syn.args(), f)?)),
+        DamlType::ContractId(None)
+        | DamlType::Int64
+        | DamlType::Text
+        | DamlType::Timestamp
+        | DamlType::Party
+        | DamlType::Bool
+        | DamlType::Unit
+        | DamlType::Date
+        | DamlType::Var(_)
+        | DamlType::Nat(_)
+        | DamlType::Any
+        | DamlType::TypeRep
+        | DamlType::Bignumeric
+        | DamlType::RoundingMode
+        | DamlType::AnyException => ty.clone(),
+    })
+}
+
+fn map_all<'a, F>(types: &[DamlType<'a>], f: &mut F) -> TypeCheckResult<Vec<DamlType<'a>>>
+where
+    F: FnMut(&DamlType<'a>) -> TypeCheckResult<DamlType<'a>>,
+{
+    types.iter().map(f).collect()
+}
+
+/// Apply the type `head` to additional type arguments.
+pub fn apply<'a>(head: DamlType<'a>, args: Vec<DamlType<'a>>) -> TypeCheckResult<DamlType<'a>> {
+    if args.is_empty() {
+        return Ok(head);
+    }
+    if matches!(head, DamlType::ContractId(None)) {
+        return match <[DamlType<'a>; 1]>::try_from(args) {
+            Ok([arg]) => Ok(contract_id(arg)),
+            Err(args) => Err(DamlTypeCheckErrorKind::TypeArgumentCount("ContractId".to_owned(), 1, args.len())),
+        };
+    }
+    let extend = |mut existing: Vec<DamlType<'a>>| {
+        existing.extend(args);
+        existing
+    };
+    Ok(match head {
+        DamlType::Var(var) =>
+            DamlType::Var(DamlVar::new(Cow::Owned(var.var().to_owned()), extend(var.type_arguments().to_vec()))),
+        DamlType::TyCon(tycon) =>
+            DamlType::TyCon(DamlTyCon::new(Box::new(tycon.tycon().clone()), extend(tycon.type_arguments().to_vec()))),
+        DamlType::BoxedTyCon(tycon) => DamlType::BoxedTyCon(DamlTyCon::new(
+            Box::new(tycon.tycon().clone()),
+            extend(tycon.type_arguments().to_vec()),
+        )),
+        DamlType::Numeric(existing) => DamlType::Numeric(extend(existing)),
+        DamlType::List(existing) => DamlType::List(extend(existing)),
+        DamlType::TextMap(existing) => DamlType::TextMap(extend(existing)),
+        DamlType::GenMap(existing) => DamlType::GenMap(extend(existing)),
+        DamlType::Optional(existing) => DamlType::Optional(extend(existing)),
+        DamlType::Arrow(existing) => DamlType::Arrow(extend(existing)),
+        DamlType::Update(existing) => DamlType::Update(extend(existing)),
+        DamlType::Scenario(existing) => DamlType::Scenario(extend(existing)),
+        other =>
+            return Err(DamlTypeCheckErrorKind::UnexpectedType("a type constructor".to_owned(), render_type(&other))),
+    })
+}
+
+/// Substitute type variables in `ty`, renaming bound type variables where required to avoid capture.
+pub fn substitute<'a>(ty: &DamlType<'a>, subst: &[(&str, DamlType<'a>)]) -> TypeCheckResult<DamlType<'a>> {
+    if subst.is_empty() {
+        return Ok(ty.clone());
+    }
+    match ty {
+        DamlType::Var(var) => {
+            let args = map_all(var.type_arguments(), &mut |arg| substitute(arg, subst))?;
+            match subst.iter().rev().find(|(name, _)| *name == var.var()) {
+                Some((_, replacement)) => apply(replacement.clone(), args),
+                None if args.is_empty() => Ok(ty.clone()),
+                None => Ok(DamlType::Var(DamlVar::new(Cow::Owned(var.var().to_owned()), args))),
+            }
+        },
+        DamlType::Forall(forall) => {
+            let mut inner: Vec<(&str, DamlType<'a>)> =
+                subst.iter().filter(|(name, _)| forall.vars().iter().all(|v| v.var() != *name)).cloned().collect();
+            let mut vars = Vec::with_capacity(forall.vars().len());
+            for v in forall.vars() {
+                if inner.iter().any(|(_, replacement)| mentions(replacement, v.var())) {
+                    let fresh = fresh_name(v.var(), |candidate| {
+                        mentions(forall.body(), candidate)
+                            || inner.iter().any(|(_, replacement)| mentions(replacement, candidate))
+                    });
+                    inner.push((v.var(), DamlType::Var(DamlVar::new(Cow::Owned(fresh.clone()), vec![]))));
+                    vars.push(DamlTypeVarWithKind::new(Cow::Owned(fresh), v.kind().clone()));
+                } else {
+                    vars.push(v.clone());
+                }
+            }
+            Ok(DamlType::Forall(DamlForall::new(vars, Box::new(substitute(forall.body(), &inner)?))))
+        },
+        _ => map_children(ty, &mut |child| substitute(child, subst)),
+    }
+}
+
+/// Does `ty` mention the type variable `name`, including within the type arguments of a type variable?
+fn mentions(ty: &DamlType<'_>, name: &str) -> bool {
+    match ty {
+        DamlType::Var(var) => var.var() == name || var.type_arguments().iter().any(|arg| mentions(arg, name)),
+        DamlType::List(args)
+        | DamlType::Optional(args)
+        | DamlType::TextMap(args)
+        | DamlType::GenMap(args)
+        | DamlType::Numeric(args)
+        | DamlType::Update(args)
+        | DamlType::Scenario(args)
+        | DamlType::Arrow(args) => args.iter().any(|arg| mentions(arg, name)),
+        DamlType::ContractId(inner) => inner.as_ref().map_or(false, |ty| mentions(ty, name)),
+        DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) =>
+            tycon.type_arguments().iter().any(|arg| mentions(arg, name)),
+        DamlType::Forall(forall) => mentions(forall.body(), name),
+        DamlType::Struct(tuple) => tuple.fields().iter().any(|field| mentions(field.ty(), name)),
+        DamlType::Syn(syn) => syn.args().iter().any(|arg| mentions(arg, name)),
+        DamlType::Int64
+        | DamlType::Text
+        | DamlType::Timestamp
+        | DamlType::Party
+        | DamlType::Bool
+        | DamlType::Unit
+        | DamlType::Date
+        | DamlType::Any
+        | DamlType::TypeRep
+        | DamlType::Bignumeric
+        | DamlType::RoundingMode
+        | DamlType::AnyException
+        | DamlType::Nat(_) => false,
+    }
+}
+
+fn fresh_name(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut n = 1;
+    loop {
+        let candidate = format!("{}'{}", base, n);
+        if !taken(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// Are the (synonym free) types `a` and `b` equal, up to the renaming of bound type variables?
+pub fn types_equal(a: &DamlType<'_>, b: &DamlType<'_>) -> bool {
+    alpha_equal(a, b, &mut vec![])
+}
+
+fn alpha_equal<'x>(a: &'x DamlType<'_>, b: &'x DamlType<'_>, bound: &mut Vec<(&'x str, &'x str)>) -> bool {
+    match (a, b) {
+        (DamlType::Var(a), DamlType::Var(b)) =>
+            var_equal(a.var(), b.var(), bound) && all_equal(a.type_arguments(), b.type_arguments(), bound),
+        (DamlType::TyCon(a) | DamlType::BoxedTyCon(a), DamlType::TyCon(b) | DamlType::BoxedTyCon(b)) =>
+            a.tycon().reference_parts() == b.tycon().reference_parts()
+                && all_equal(a.type_arguments(), b.type_arguments(), bound),
+        (DamlType::ContractId(a), DamlType::ContractId(b)) => match (a, b) {
+            (Some(a), Some(b)) => alpha_equal(a, b, bound),
+            (None, None) => true,
+            _ => false,
+        },
+        (DamlType::Numeric(a), DamlType::Numeric(b))
+        | (DamlType::List(a), DamlType::List(b))
+        | (DamlType::TextMap(a), DamlType::TextMap(b))
+        | (DamlType::GenMap(a), DamlType::GenMap(b))
+        | (DamlType::Optional(a), DamlType::Optional(b))
+        | (DamlType::Arrow(a), DamlType::Arrow(b))
+        | (DamlType::Update(a), DamlType::Update(b))
+        | (DamlType::Scenario(a), DamlType::Scenario(b)) => all_equal(a, b, bound),
+        (DamlType::Nat(a), DamlType::Nat(b)) => a == b,
+        (DamlType::Forall(a), DamlType::Forall(b)) => {
+            let (a_vars, a_body) = flatten_forall(a);
+            let (b_vars, b_body) = flatten_forall(b);
+            if a_vars.len() != b_vars.len() || a_vars.iter().zip(&b_vars).any(|(a, b)| !kind_eq(a.kind(), b.kind())) {
+                return false;
+            }
+            let depth = bound.len();
+            bound.extend(a_vars.iter().zip(&b_vars).map(|(a, b)| (a.var(), b.var())));
+            let equal = alpha_equal(a_body, b_body, bound);
+            bound.truncate(depth);
+            equal
+        },
+        (DamlType::Struct(a), DamlType::Struct(b)) => {
+            let a_fields = a.fields().iter().sorted_by(|x, y| x.name().cmp(y.name())).collect::<Vec<_>>();
+            let b_fields = b.fields().iter().sorted_by(|x, y| x.name().cmp(y.name())).collect::<Vec<_>>();
+            a_fields.len() == b_fields.len()
+                && a_fields
+                    .iter()
+                    .zip(&b_fields)
+                    .all(|(a, b)| a.name() == b.name() && alpha_equal(a.ty(), b.ty(), bound))
+        },
+        (DamlType::Syn(a), DamlType::Syn(b)) =>
+            a.tysyn().reference_parts() == b.tysyn().reference_parts() && all_equal(a.args(), b.args(), bound),
+        _ => std::mem::discriminant(a) == std::mem::discriminant(b),
+    }
+}
+
+fn all_equal<'x>(a: &'x [DamlType<'_>], b: &'x [DamlType<'_>], bound: &mut Vec<(&'x str, &'x str)>) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| alpha_equal(a, b, bound))
+}
+
+fn var_equal(a: &str, b: &str, bound: &[(&str, &str)]) -> bool {
+    match bound.iter().rev().find(|(x, y)| *x == a || *y == b) {
+        Some((x, y)) => *x == a && *y == b,
+        None => a == b,
+    }
+}
+
+/// Collect the type variables of directly nested `Forall` types.
+fn flatten_forall<'x, 'a>(forall: &'x DamlForall<'a>) -> (Vec<&'x DamlTypeVarWithKind<'a>>, &'x DamlType<'a>) {
+    let mut vars = forall.vars().iter().collect::<Vec<_>>();
+    let mut body = forall.body();
+    while let DamlType::Forall(inner) = body {
+        vars.extend(inner.vars());
+        body = inner.body();
+    }
+    (vars, body)
+}
+
+pub fn render_type(ty: &DamlType<'_>) -> String {
+    PrettyPrinter::new().elide_package_ids(true).width(usize::MAX).render_type(ty)
+}
+
+pub fn render_tycon_name(name: &DamlTyConName<'_>) -> String {
+    format!("{}:{}", name.module_path().join("."), name.data_name())
+}
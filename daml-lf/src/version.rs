@@ -19,6 +19,7 @@ impl LanguageVersion {
     pub const V1_12: LanguageVersion = LanguageVersion::Lv1(LanguageV1MinorVersion::V12);
     pub const V1_13: LanguageVersion = LanguageVersion::Lv1(LanguageV1MinorVersion::V13);
     pub const V1_14: LanguageVersion = LanguageVersion::Lv1(LanguageV1MinorVersion::V14);
+    pub const V1_15: LanguageVersion = LanguageVersion::Lv1(LanguageV1MinorVersion::V15);
     pub const V1_2: LanguageVersion = LanguageVersion::Lv1(LanguageV1MinorVersion::V2);
     pub const V1_3: LanguageVersion = LanguageVersion::Lv1(LanguageV1MinorVersion::V3);
     pub const V1_4: LanguageVersion = LanguageVersion::Lv1(LanguageV1MinorVersion::V4);
@@ -85,6 +86,18 @@ impl LanguageFeatureVersion {
         name: "ENUM",
         min_version: LanguageVersion::V1_6,
     };
+    pub const EXCEPTIONS: LanguageFeatureVersion = LanguageFeatureVersion {
+        name: "EXCEPTIONS",
+        min_version: LanguageVersion::V1_14,
+    };
+    pub const INTERFACES: LanguageFeatureVersion = LanguageFeatureVersion {
+        name: "INTERFACES",
+        min_version: LanguageVersion::V1_15,
+    };
+    pub const INTERFACE_VIEWS: LanguageFeatureVersion = LanguageFeatureVersion {
+        name: "INTERFACE_VIEWS",
+        min_version: LanguageVersion::V1_15,
+    };
     pub const INTERNED_DOTTED_NAMES: LanguageFeatureVersion = LanguageFeatureVersion {
         name: "INTERNED_DOTTED_NAMES",
         min_version: LanguageVersion::V1_7,
@@ -159,6 +172,7 @@ pub enum LanguageV1MinorVersion {
     V12,
     V13,
     V14,
+    V15,
     Dev,
 }
 
@@ -178,6 +192,7 @@ impl Display for LanguageV1MinorVersion {
             LanguageV1MinorVersion::V12 => write!(f, "12"),
             LanguageV1MinorVersion::V13 => write!(f, "13"),
             LanguageV1MinorVersion::V14 => write!(f, "14"),
+            LanguageV1MinorVersion::V15 => write!(f, "15"),
             LanguageV1MinorVersion::Dev => write!(f, "dev"),
         }
     }
@@ -201,6 +216,7 @@ impl TryFrom<&str> for LanguageV1MinorVersion {
             "12" => Ok(LanguageV1MinorVersion::V12),
             "13" => Ok(LanguageV1MinorVersion::V13),
             "14" => Ok(LanguageV1MinorVersion::V14),
+            "15" => Ok(LanguageV1MinorVersion::V15),
             "dev" => Ok(LanguageV1MinorVersion::Dev),
             _ => Err(DamlLfError::new_unknown_version(minor_version)),
         }
@@ -225,7 +241,8 @@ mod tests {
         assert!(LanguageV1MinorVersion::V11 < LanguageV1MinorVersion::V12);
         assert!(LanguageV1MinorVersion::V12 < LanguageV1MinorVersion::V13);
         assert!(LanguageV1MinorVersion::V13 < LanguageV1MinorVersion::V14);
-        assert!(LanguageV1MinorVersion::V14 < LanguageV1MinorVersion::Dev);
+        assert!(LanguageV1MinorVersion::V14 < LanguageV1MinorVersion::V15);
+        assert!(LanguageV1MinorVersion::V15 < LanguageV1MinorVersion::Dev);
     }
 
     #[test]
@@ -249,7 +266,8 @@ mod tests {
         assert!(LanguageVersion::V1_11 < LanguageVersion::V1_12);
         assert!(LanguageVersion::V1_12 < LanguageVersion::V1_13);
         assert!(LanguageVersion::V1_13 < LanguageVersion::V1_14);
-        assert!(LanguageVersion::V1_14 < LanguageVersion::V1_DEV);
+        assert!(LanguageVersion::V1_14 < LanguageVersion::V1_15);
+        assert!(LanguageVersion::V1_15 < LanguageVersion::V1_DEV);
     }
 
     #[test]
@@ -2,13 +2,26 @@
 #![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
 
 use daml_lf::element::DamlVisitableElement;
-use daml_lf::element::{DamlElementVisitor, DamlEnum};
-use daml_lf::DamlLfResult;
-use daml_lf::DarFile;
+use daml_lf::element::{
+    DamlArchive, DamlChoice, DamlData, DamlElementVisitor, DamlEnum, DamlField, DamlInterface, DamlInterfaceInstance,
+    DamlInterfaceInstanceMethod, DamlInterfaceMethod, DamlModule, DamlTyConName, DamlType,
+};
 use daml_lf::LanguageVersion;
-use daml_lf::{DamlLfArchive, DamlLfHashFunction};
+use daml_lf::PrettyPrinter;
+use daml_lf::DEFAULT_ARCHIVE_NAME;
+use daml_lf::{
+    DamlArchiveDiff, DamlBreakingChange, DamlChangeKind, DamlCompatibilityChecker, DamlDependencyGraph, DamlLfResult,
+    DamlPropertyDiff,
+};
+use daml_lf::{DamlLazyArchive, LazyDarFile};
+use daml_lf::{DamlLfArchive, DamlLfArchivePayload, DamlLfHashFunction, DamlLfPackage};
+use daml_lf::{DarBuilder, DarFile};
 use daml_lf::{DarEncryptionType, DarManifestFormat, DarManifestVersion};
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
 use std::collections::HashSet;
+use std::fs::File;
+use std::io::BufReader;
 
 #[test]
 fn test_dalf() -> DamlLfResult<()> {
@@ -51,6 +64,107 @@ pub fn test_fat_dar() -> DamlLfResult<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_dar_from_bytes() -> DamlLfResult<()> {
+    let dar = DarFile::from_bytes(&include_bytes!("../test_resources/TestingTypes-1_9_0-sdk_1_18_1-lf_1_14.dar")[..])?;
+    assert_eq!(DarManifestVersion::V1, dar.manifest().version());
+    assert_eq!(25, dar.dependencies().len());
+    assert!(dar.main().payload().contains_module("Fuji.PingPong"));
+    Ok(())
+}
+
+#[test]
+pub fn test_legacy_dar_from_reader() -> DamlLfResult<()> {
+    let file = File::open("../resources/testing_types_sandbox/archive/legacy/Legacy.dar")?;
+    let dar = DarFile::from_reader(BufReader::new(file))?;
+    assert_eq!("implied", dar.manifest().created_by());
+    assert_eq!("PingPongExample/PingPongExample.dalf", dar.manifest().dalf_main());
+    assert_eq!(0, dar.dependencies().len());
+    assert!(dar.main().payload().contains_module("PingPong"));
+    Ok(())
+}
+
+#[cfg(feature = "async")]
+#[test]
+pub fn test_dar_from_async_reader() -> DamlLfResult<()> {
+    let bytes = std::fs::read("test_resources/TestingTypes-1_9_0-sdk_1_18_1-lf_1_14.dar")?;
+    let dar = futures::executor::block_on(DarFile::from_async_reader(futures::io::Cursor::new(bytes)))?;
+    assert_eq!(25, dar.dependencies().len());
+    assert!(dar.main().payload().contains_module("Fuji.PingPong"));
+    Ok(())
+}
+
+#[test]
+fn test_dalf_from_reader() -> DamlLfResult<()> {
+    let file = File::open("../resources/testing_types_sandbox/archive/legacy/Legacy.dalf")?;
+    let archive = DamlLfArchive::from_reader(file)?;
+    assert_eq!("2efa7ef832162fcb17abe86cd8675e31b8e641f25aba36a05098f7e9f4023d7e", archive.hash());
+    assert_eq!(DEFAULT_ARCHIVE_NAME, archive.name());
+    assert!(archive.payload().contains_module("PingPong"));
+    Ok(())
+}
+
+#[test]
+pub fn test_write_dar() -> DamlLfResult<()> {
+    let dar = DarFile::from_file("test_resources/TestingTypes-1_9_0-sdk_1_18_1-lf_1_14.dar")?;
+    let bytes = dar.to_bytes()?;
+    assert_eq!(bytes, dar.to_bytes()?);
+    let path = std::env::temp_dir().join("daml-lf-test-write-dar.dar");
+    dar.write_to_file(&path)?;
+    let written = DarFile::from_file(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(DarManifestVersion::V1, written.manifest().version());
+    assert_eq!("damlc", written.manifest().created_by());
+    assert_eq!(25, written.manifest().dalf_dependencies().len());
+    assert_eq!(dar.main().name(), written.main().name());
+    assert_eq!(dar.main().hash(), written.main().hash());
+    assert_eq!(25, written.dependencies().len());
+    assert_eq!(LanguageVersion::V1_14, *written.main().payload().language_version());
+    assert!(written.main().payload().contains_module("Fuji.PingPong"));
+    Ok(())
+}
+
+#[test]
+pub fn test_written_dar_package_ids() -> DamlLfResult<()> {
+    for path in [
+        "test_resources/TestingTypes-1_0_0-sdk_0_13_36-lf_1_6.dar",
+        "test_resources/TestingTypes-1_0_0-sdk_0_13_55-lf_1_8.dar",
+        "test_resources/TestingTypes-1_9_0-sdk_1_18_1-lf_1_14.dar",
+        "../resources/testing_types_sandbox/TestingTypes-latest.dar",
+    ] {
+        let dar = DarFile::from_file(path)?;
+        let written = DarFile::from_bytes(dar.to_bytes()?)?;
+        assert_eq!(dar.dependencies().len(), written.dependencies().len());
+        for archive in std::iter::once(written.main()).chain(written.dependencies()) {
+            let payload_bytes = archive.payload_bytes().unwrap();
+            assert_eq!(archive.hash(), format!("{:x}", Sha256::digest(payload_bytes)), "{path}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+pub fn test_build_dar() -> DamlLfResult<()> {
+    let dar = DarFile::from_file("test_resources/TestingTypes-1_9_0-sdk_1_18_1-lf_1_14.dar")?;
+    let built = DarBuilder::new(dar.main().clone())
+        .created_by("test")
+        .add_dependencies(dar.dependencies().iter().cloned())
+        .add_dependency(dar.main().clone())
+        .build();
+    assert_eq!("test", built.manifest().created_by());
+    assert_eq!(25, built.dependencies().len());
+    assert!(built.manifest().dalf_main().ends_with(&format!("{}-{}.dalf", dar.main().name(), dar.main().hash())));
+    let path = std::env::temp_dir().join("daml-lf-test-build-dar.dar");
+    built.write_to_file(&path)?;
+    let written = DarFile::from_file(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(built.manifest().dalf_main(), written.manifest().dalf_main());
+    assert_eq!(built.manifest().dalf_dependencies(), written.manifest().dalf_dependencies());
+    assert_eq!(DarManifestFormat::DamlLf, written.manifest().format());
+    assert_eq!(DarEncryptionType::NotEncrypted, written.manifest().encryption());
+    Ok(())
+}
+
 #[test]
 pub fn test_daml_lf_1_6() -> DamlLfResult<()> {
     let dar = DarFile::from_file("test_resources/TestingTypes-1_0_0-sdk_0_13_36-lf_1_6.dar")?;
@@ -105,7 +219,7 @@ fn test_apply_dalf() -> DamlLfResult<()> {
 #[test]
 fn test_apply_payload() -> DamlLfResult<()> {
     let mut dar = DarFile::from_file("test_resources/TestingTypes-1_0_0-sdk_0_13_55-lf_1_8.dar")?;
-    let payload = dar.dependencies.swap_remove(1).payload;
+    let payload = dar.dependencies.swap_remove(1).into_payload();
     let name = payload.apply(|package| package.name().to_owned())?;
     assert_eq!("unnamed", name);
     Ok(())
@@ -119,6 +233,20 @@ fn test_convert_dar() -> DamlLfResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_convert_dar_packages() -> DamlLfResult<()> {
+    let dar = DarFile::from_file("test_resources/TestingTypes-1_9_0-sdk_1_18_1-lf_1_14.dar")?;
+    let main_package_id = dar.main().hash().to_owned();
+    let mut archive = DamlArchive::default();
+    archive.extend(dar.to_owned_packages(&[&main_package_id])?);
+    assert_eq!(1, archive.packages().count());
+    assert_eq!("TestingTypes", archive.package_by_id(&main_package_id).unwrap().name());
+    archive.extend(dar.to_owned_packages(&dar.dependencies().iter().map(DamlLfArchive::hash).collect::<Vec<_>>())?);
+    assert_eq!(dar.to_owned_archive()?.packages().count(), archive.packages().count());
+    assert!(dar.to_owned_packages(&["unknown"]).is_err());
+    Ok(())
+}
+
 #[test]
 fn test_visitor() -> DamlLfResult<()> {
     #[derive(Default)]
@@ -134,3 +262,356 @@ fn test_visitor() -> DamlLfResult<()> {
     assert!(visitor.0.contains("SimpleColor"));
     Ok(())
 }
+
+#[test]
+fn test_convert_interfaces() -> DamlLfResult<()> {
+    let dar = DarFile::from_file("test_resources/InterfaceTypes-1_0_0-lf_1_15.dar")?;
+    assert_eq!(LanguageVersion::V1_15, *dar.main().payload().language_version());
+    assert!(dar.main().payload().contains_module("DA.Asset"));
+    dar.apply(|archive| {
+        let package = archive.main_package().unwrap();
+        let module = package.root_module().child_module_path(&["DA", "Asset"]).unwrap();
+        assert_eq!(vec!["Asset", "Token"], module.interfaces().map(DamlInterface::name).sorted().collect_vec());
+
+        let asset = module.interface("Asset").unwrap();
+        assert_eq!(package.package_id(), asset.package_id());
+        assert_eq!("this", asset.param());
+        assert_eq!(vec!["Transfer"], asset.choices().iter().map(DamlChoice::name).collect_vec());
+        let transfer = &asset.choices()[0];
+        assert!(transfer.consuming());
+        assert_eq!(vec!["newOwner"], transfer.fields().iter().map(DamlField::name).collect_vec());
+        assert!(matches!(transfer.return_type(), DamlType::ContractId(Some(_))));
+        assert_eq!(vec!["getOwner", "describe"], asset.methods().iter().map(DamlInterfaceMethod::name).collect_vec());
+        assert!(matches!(asset.methods()[0].ty(), DamlType::Party));
+        assert!(matches!(asset.methods()[1].ty(), DamlType::Text));
+        assert_eq!(vec!["Token"], asset.requires().iter().map(DamlTyConName::data_name).collect_vec());
+        assert_eq!(package.package_id(), asset.requires()[0].package_id());
+        assert!(matches!(asset.view(), DamlType::TyCon(view) if view.tycon().data_name() == "AssetView"));
+
+        let token = module.interface("Token").unwrap();
+        assert!(token.choices().is_empty());
+        assert!(token.requires().is_empty());
+        assert_eq!(vec!["getIssuer"], token.methods().iter().map(DamlInterfaceMethod::name).collect_vec());
+        assert!(matches!(token.view(), DamlType::TyCon(view) if view.tycon().data_name() == "TokenView"));
+
+        let coin = match module.data_type("Coin") {
+            Some(DamlData::Template(template)) => template,
+            _ => panic!("expected template Coin"),
+        };
+        assert_eq!(
+            vec![("Token", vec!["getIssuer"]), ("Asset", vec!["getOwner", "describe"])],
+            coin.implements()
+                .iter()
+                .map(|instance| (
+                    instance.interface().data_name(),
+                    instance.methods().iter().map(DamlInterfaceInstanceMethod::name).collect_vec()
+                ))
+                .collect_vec()
+        );
+        #[cfg(feature = "full")]
+        assert!(matches!(
+            coin.implements()[1].view(),
+            daml_lf::element::DamlExpr::RecCon(view) if view.tycon().tycon().data_name() == "AssetView"
+        ));
+    })?;
+    Ok(())
+}
+
+#[test]
+fn test_interface_visitor() -> DamlLfResult<()> {
+    #[derive(Default)]
+    pub struct GatherInterfacesVisitor {
+        current: Option<String>,
+        methods: Vec<(String, String)>,
+        instance_methods: Vec<(String, String)>,
+    }
+    impl DamlElementVisitor for GatherInterfacesVisitor {
+        fn pre_visit_interface<'a>(&mut self, interface: &'a DamlInterface<'a>) {
+            self.current = Some(interface.name().to_owned());
+        }
+
+        fn post_visit_interface<'a>(&mut self, _interface: &'a DamlInterface<'a>) {
+            self.current = None;
+        }
+
+        fn pre_visit_interface_method<'a>(&mut self, interface_method: &'a DamlInterfaceMethod<'a>) {
+            self.methods.push((self.current.clone().unwrap(), interface_method.name().to_owned()));
+        }
+
+        fn pre_visit_interface_instance<'a>(&mut self, interface_instance: &'a DamlInterfaceInstance<'a>) {
+            self.current = Some(interface_instance.interface().data_name().to_owned());
+        }
+
+        fn post_visit_interface_instance<'a>(&mut self, _interface_instance: &'a DamlInterfaceInstance<'a>) {
+            self.current = None;
+        }
+
+        fn pre_visit_interface_instance_method<'a>(&mut self, instance_method: &'a DamlInterfaceInstanceMethod<'a>) {
+            self.instance_methods.push((self.current.clone().unwrap(), instance_method.name().to_owned()));
+        }
+    }
+    let mut visitor = GatherInterfacesVisitor::default();
+    let dar = DarFile::from_file("test_resources/InterfaceTypes-1_0_0-lf_1_15.dar")?;
+    dar.apply(|archive| archive.accept(&mut visitor))?;
+    let expected = [("Asset", "describe"), ("Asset", "getOwner"), ("Token", "getIssuer")]
+        .iter()
+        .map(|&(interface, method)| (interface.to_owned(), method.to_owned()))
+        .collect_vec();
+    assert_eq!(expected, visitor.methods.into_iter().sorted().collect_vec());
+    assert_eq!(expected, visitor.instance_methods.into_iter().sorted().collect_vec());
+    assert!(visitor.current.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_pretty_print_package() -> DamlLfResult<()> {
+    let dar = DarFile::from_file("test_resources/TestingTypes-1_0_0-sdk_0_13_55-lf_1_8.dar")?;
+    let printer = PrettyPrinter::new().elide_package_ids(true);
+    let rendered = dar.apply(|archive| archive.main_package().map(|package| printer.render_package(package)))?.unwrap();
+    assert!(rendered.starts_with("package TestingTypes-1.0.0"));
+    assert!(rendered.contains("enum SimpleColor ="));
+    assert!(rendered.contains("template ("));
+    assert!(!rendered.contains(dar.main().hash()));
+    #[cfg(feature = "full")]
+    assert!(rendered.contains("signatory"));
+    Ok(())
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_type_check_archive() -> DamlLfResult<()> {
+    for path in [
+        "test_resources/TestingTypes-1_0_0-sdk_0_13_55-lf_1_8.dar",
+        "test_resources/TestingTypes-1_0_0-sdk_1_1_1-lf_1_8.dar",
+        "test_resources/TestingTypes-1_9_0-sdk_1_18_1-lf_1_14.dar",
+        "test_resources/InterfaceTypes-1_0_0-lf_1_15.dar",
+    ] {
+        let dar = DarFile::from_file(path)?;
+        let errors = dar.apply(|archive| daml_lf::DamlTypeChecker::new(archive).check_archive())?;
+        assert_eq!(Vec::<daml_lf::DamlTypeCheckError>::new(), errors, "{path}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_dependency_graph() -> DamlLfResult<()> {
+    let dar = DarFile::from_file("test_resources/TestingTypes-1_9_0-sdk_1_18_1-lf_1_14.dar")?;
+    let main_package_id = dar.main().hash().to_owned();
+    let graph = DamlDependencyGraph::extract_from_dar(&dar)?;
+    assert_eq!(26, graph.packages().count());
+    assert!(graph.package_cycles().is_empty());
+    let order = graph.package_order()?;
+    assert_eq!(26, order.len());
+    for package_id in &order {
+        let position = order.iter().position(|p| p == package_id).unwrap();
+        assert!(graph.package_dependencies(package_id).iter().all(|dep| order[..position].contains(dep)));
+    }
+    assert!(!graph.package_dependencies(&main_package_id).is_empty());
+    assert!(graph.package_dependents(&main_package_id).is_empty());
+    assert!(graph.module_cycles().is_empty());
+    assert_eq!(graph.modules().count(), graph.module_order()?.len());
+    Ok(())
+}
+
+#[test]
+fn test_compatibility_check() -> DamlLfResult<()> {
+    let v1_4 = DarFile::from_file(
+        "../resources/testing_types_sandbox/archive/\
+         TestingTypes-1_4_0-sdk_1_15_0-snapshot_20210623_7217_0_5b73813d-lf_1_12.dar",
+    )?;
+    let v1_6 =
+        DarFile::from_file("../resources/testing_types_sandbox/archive/TestingTypes-1_6_0-sdk_1_18_1-lf_1_14.dar")?;
+    let v1_8 =
+        DarFile::from_file("../resources/testing_types_sandbox/archive/TestingTypes-1_8_0-sdk_1_18_1-lf_1_14.dar")?;
+    let checker = DamlCompatibilityChecker::new();
+    assert!(checker.check_dars(&v1_6, &v1_8)?.is_compatible());
+    let report = checker.check_dars(&v1_8, &v1_4)?;
+    assert_eq!(Some("1.8.0"), report.old_package().version.as_deref());
+    assert_eq!(Some("1.4.0"), report.new_package().version.as_deref());
+    assert_eq!(15, report.changes().len());
+    assert!(report.changes().contains(&DamlBreakingChange::TemplateRemoved("DA.DupMod1:DupTemplate".to_owned())));
+    assert!(report
+        .changes()
+        .contains(&DamlBreakingChange::ChoiceRemoved("DA.RentDemo:RentalProposal".to_owned(), "Clone".to_owned())));
+    Ok(())
+}
+
+#[test]
+fn test_archive_diff() -> DamlLfResult<()> {
+    let old =
+        DarFile::from_file("../resources/testing_types_sandbox/archive/TestingTypes-1_3_0-sdk_1_13_0-lf_1_12.dar")?;
+    let new =
+        DarFile::from_file("../resources/testing_types_sandbox/archive/TestingTypes-1_0_0-sdk_0_13_36-lf_1_6.dar")?;
+    assert!(DamlArchiveDiff::from_dars(&new, &new)?.is_empty());
+    let diff = DamlArchiveDiff::from_dars(&old, &new)?;
+    let module = |name: &str| diff.modules.iter().find(|m| m.name == name).unwrap();
+    assert_eq!(DamlChangeKind::Removed, module("DA.JsonTest").change);
+    let vehicle = module("DA.Vehicle");
+    assert_eq!(DamlChangeKind::Changed, vehicle.change);
+    let car = vehicle.data.iter().find(|d| d.name == "Car").unwrap();
+    assert_eq!("template", car.kind);
+    assert_eq!(
+        vec![
+            DamlPropertyDiff::new("reg_year", Some("Date".to_owned()), None),
+            DamlPropertyDiff::new("purchase_time", Some("Timestamp".to_owned()), None)
+        ],
+        car.fields
+    );
+    let ping = module("DA.PingPong").data.iter().find(|d| d.name == "Ping").unwrap();
+    assert_eq!(vec![DamlPropertyDiff::new("key", Some("DA.PingPong:PingPongKey".to_owned()), None)], ping.properties);
+    let text = diff.to_string();
+    assert!(text.contains("~ module DA.Vehicle\n  ~ template Car\n    - field reg_year: Date\n"));
+    Ok(())
+}
+
+#[test]
+fn test_lazy_archive() -> DamlLfResult<()> {
+    let path = "../resources/testing_types_sandbox/TestingTypes-latest.dar";
+    let dar = DarFile::from_file(path)?;
+    let lazy_dar = LazyDarFile::from_file(path)?;
+    assert_eq!(dar.main().hash(), lazy_dar.main_package_id());
+    assert_eq!(dar.main().name(), lazy_dar.name());
+    let info = lazy_dar.package_info(lazy_dar.main_package_id())?.unwrap();
+    assert_eq!("TestingTypes", info.package_name);
+    let printer = PrettyPrinter::new();
+    dar.apply(|archive| {
+        let main = archive.main_package().unwrap();
+        let modules = modules_with_data(main.root_module());
+        assert!(!modules.is_empty());
+        lazy_dar.apply(|lazy| assert_same_modules(&printer, &modules, main.package_id(), lazy))?;
+        dar.apply_lazy(|lazy| assert_same_modules(&printer, &modules, main.package_id(), lazy))
+    })??;
+    lazy_dar.apply(|lazy| {
+        let prim = lazy.package_by_name("daml-prim")?.unwrap();
+        assert_eq!("daml-prim", prim.name());
+        assert!(lazy.package_by_name("unknown")?.is_none());
+        assert!(lazy.module(lazy.main_package_id(), &["Unknown"])?.is_none());
+        let ping = lazy.data(lazy.main_package_id(), &["Fuji", "PingPong"], "Ping")?.unwrap();
+        assert_eq!("Ping", ping.name());
+        let sender = ping.fields().iter().find(|field| field.name() == "sender").unwrap();
+        assert!(matches!(sender.ty(), DamlType::Party));
+        let nested = lazy.data(lazy.main_package_id(), &["Fuji", "Nested"], "NestedTemplate")?.unwrap();
+        let map_of_data = nested.fields().iter().find(|field| field.name() == "map_of_data_to_text").unwrap();
+        let tycon = match map_of_data.ty() {
+            DamlType::GenMap(args) => match args.first() {
+                Some(DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon)) => tycon,
+                _ => panic!("expected TyCon key"),
+            },
+            _ => panic!("expected GenMap"),
+        };
+        let data = lazy.data_by_tycon(tycon)?.unwrap();
+        assert_eq!(data.name(), "MyNestedData");
+        Ok(())
+    })
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_encode_package_round_trip() -> DamlLfResult<()> {
+    let printer = PrettyPrinter::new().elide_package_ids(true);
+    for path in [
+        "test_resources/TestingTypes-1_0_0-sdk_0_13_36-lf_1_6.dar",
+        "test_resources/TestingTypes-1_0_0-sdk_0_13_55-lf_1_8.dar",
+        "test_resources/TestingTypes-1_9_0-sdk_1_18_1-lf_1_14.dar",
+    ] {
+        let dar = DarFile::from_file(path)?;
+        let archive = dar.to_owned_archive()?;
+        let package = archive.main_package().unwrap();
+        let payload = DamlLfArchivePayload::from_package(package)?;
+        assert_eq!(package.language_version(), payload.language_version);
+        let encoded = DamlLfArchive::from_payload(dar.main().name(), payload)?;
+        let again = DamlLfArchive::from_payload(dar.main().name(), DamlLfArchivePayload::from_package(package)?)?;
+        assert_eq!(encoded.hash(), again.hash(), "{path}");
+        let rebuilt = DarBuilder::new(encoded).add_dependencies(dar.dependencies().iter().cloned()).build();
+        let rendered = rebuilt.apply(|archive| printer.render_package(archive.main_package().unwrap()))?;
+        assert_eq!(printer.render_package(package), rendered, "{path}");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_encode_modified_package() -> DamlLfResult<()> {
+    let dar = DarFile::from_file("test_resources/TestingTypes-1_0_0-sdk_0_13_55-lf_1_8.dar")?;
+    let mut archive = dar.to_owned_archive()?;
+    let package = archive.main_package_mut().unwrap();
+    package.set_name("Renamed");
+    assert!(package.remove_module(&["DA", "Vehicle"]).is_some());
+    assert!(package.remove_module(&["DA", "Vehicle"]).is_none());
+    let encoded = DamlLfArchive::from_payload("Renamed", DamlLfArchivePayload::from_package(package)?)?;
+    assert_ne!(dar.main().hash(), encoded.hash());
+    assert!(!encoded.payload().contains_module("DA.Vehicle"));
+    assert!(encoded.payload().contains_module("DA.PingPong"));
+    let rebuilt = DarBuilder::new(encoded).add_dependencies(dar.dependencies().iter().cloned()).build();
+    let name = rebuilt.apply(|archive| archive.main_package().unwrap().name().to_owned())?;
+    assert_eq!("Renamed", name);
+    Ok(())
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_encode_package_exceptions_round_trip() -> DamlLfResult<()> {
+    let printer = PrettyPrinter::new().elide_package_ids(true);
+    let dar = DarFile::from_file("../resources/testing_types_sandbox/TestingTypes-latest.dar")?;
+    let archive = dar.to_owned_archive()?;
+    let dalfs = dar.dependencies().iter().chain(std::iter::once(dar.main())).collect_vec();
+    let mut encoded_count = 0;
+    for dalf in &dalfs {
+        let DamlLfPackage::V1(source) = dalf.payload().package();
+        let source_count = source.modules.iter().map(|module| module.exceptions.len()).sum::<usize>();
+        if source_count == 0 {
+            continue;
+        }
+        let package = archive.package_by_id(dalf.hash()).unwrap();
+        let original = render_exceptions(&printer, package.root_module());
+        assert_eq!(source_count, original.len(), "{}", dalf.name());
+        let encoded = DamlLfArchive::from_payload(dalf.name(), DamlLfArchivePayload::from_package(package)?)?;
+        let DamlLfPackage::V1(encoded_package) = encoded.payload().package();
+        assert_eq!(source_count, encoded_package.modules.iter().map(|module| module.exceptions.len()).sum::<usize>());
+        assert_ne!(dalf.hash(), encoded.hash());
+        let rebuilt = DarBuilder::new(encoded).add_dependencies(dalfs.iter().map(|&dalf| dalf.clone())).build();
+        let rendered =
+            rebuilt.apply(|archive| render_exceptions(&printer, archive.main_package().unwrap().root_module()))?;
+        assert_eq!(original, rendered, "{}", dalf.name());
+        encoded_count += source_count;
+    }
+    assert!(encoded_count > 0);
+    Ok(())
+}
+
+/// Render the name and message of every exception in the tree rooted at `module`, ordered by qualified name.
+#[cfg(feature = "full")]
+fn render_exceptions(printer: &PrettyPrinter, module: &DamlModule<'_>) -> Vec<(String, String)> {
+    let path = module.path().join(".");
+    module
+        .exceptions()
+        .map(|exception| (format!("{path}:{}", exception.name()), printer.render_expr(exception.message())))
+        .chain(module.child_modules().flat_map(|child| render_exceptions(printer, child)))
+        .sorted()
+        .collect()
+}
+
+fn modules_with_data<'a>(module: &'a DamlModule<'a>) -> Vec<&'a DamlModule<'a>> {
+    let children = module.child_modules().flat_map(modules_with_data);
+    if module.data_types().next().is_some() {
+        std::iter::once(module).chain(children).collect()
+    } else {
+        children.collect()
+    }
+}
+
+fn assert_same_modules(
+    printer: &PrettyPrinter,
+    modules: &[&DamlModule<'_>],
+    package_id: &str,
+    lazy: &DamlLazyArchive<'_>,
+) -> DamlLfResult<()> {
+    let render =
+        |module: &DamlModule<'_>| module.data_types().map(|data| printer.render_data(data)).sorted().collect_vec();
+    for module in modules {
+        let path = module.path().collect::<Vec<_>>();
+        let lazy_module = lazy.module(package_id, &path)?.unwrap();
+        assert_eq!(render(module), render(lazy_module));
+    }
+    Ok(())
+}
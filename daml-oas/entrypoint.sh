@@ -0,0 +1,4 @@
+#!/bin/bash -x
+cd /rust
+cargo build -p daml-oas --release --target x86_64-unknown-linux-musl
+strip target/x86_64-unknown-linux-musl/release/daml-oas
@@ -1,10 +1,11 @@
-use daml::json_api::schema_encoder::JsonSchemaEncoder;
-use daml::lf::element::{DamlArchive, DamlPackage};
+use daml_json::schema_encoder::JsonSchemaEncoder;
+use daml_lf::element::{DamlArchive, DamlPackage};
 
 use crate::a2s::asyncapi_data::{AsyncAPI, Channels, Components, Info, Server, Servers};
 use crate::a2s::channel_item_encoder::ChannelItemEncoder;
 use crate::companion::CompanionData;
 use crate::component_encoder::ComponentEncoder;
+use crate::error::DamlOasResult;
 use crate::filter::TemplateFilter;
 use crate::util::Required;
 use std::collections::BTreeMap;
@@ -41,7 +42,7 @@ impl<'arc> AsyncAPIEncoder<'arc> {
     }
 
     /// Encode a `DamlArchive` as a JSON A2S document.
-    pub fn encode_archive(&self) -> anyhow::Result<AsyncAPI> {
+    pub fn encode_archive(&self) -> DamlOasResult<AsyncAPI> {
         let info = self.encode_info()?;
         let servers = self.encode_servers();
         let channels = self.encode_channels()?;
@@ -49,7 +50,7 @@ impl<'arc> AsyncAPIEncoder<'arc> {
         Ok(AsyncAPI::new(info, servers, channels, components))
     }
 
-    fn encode_info(&self) -> anyhow::Result<Info> {
+    fn encode_info(&self) -> DamlOasResult<Info> {
         let title =
             self.companion_data.title.as_ref().map_or_else(|| self.archive.name().to_string(), ToString::to_string);
         let version = self
@@ -71,15 +72,14 @@ impl<'arc> AsyncAPIEncoder<'arc> {
         let servers = self
             .companion_data
             .servers
-            .as_ref()
-            .unwrap_or(&Vec::default())
             .iter()
-            .map(|s| (String::from("default"), Server::new(s.to_string(), String::from("ws"))))
+            .flatten()
+            .map(|s| (String::from("default"), Server::new(s.clone(), String::from("ws"))))
             .collect::<BTreeMap<_, _>>();
         Servers::new(servers)
     }
 
-    fn encode_channels(&self) -> anyhow::Result<Channels> {
+    fn encode_channels(&self) -> DamlOasResult<Channels> {
         Ok(Channels::new(
             ChannelItemEncoder::new(
                 self.archive,
@@ -93,7 +93,7 @@ impl<'arc> AsyncAPIEncoder<'arc> {
         ))
     }
 
-    fn encode_components(&self) -> anyhow::Result<Components> {
+    fn encode_components(&self) -> DamlOasResult<Components> {
         let encoder = ComponentEncoder::new(self.archive, self.module_path, &self.encoder, self.filter);
         let schemas = encoder.encode_schema_components()?;
         Ok(Components::new(schemas))
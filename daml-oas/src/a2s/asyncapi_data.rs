@@ -53,7 +53,7 @@ pub struct Servers {
 }
 
 impl Servers {
-    pub fn new(servers: BTreeMap<String, Server>) -> Self {
+    pub const fn new(servers: BTreeMap<String, Server>) -> Self {
         Self {
             servers,
         }
@@ -82,7 +82,7 @@ pub struct Channels {
 }
 
 impl Channels {
-    pub fn new(items: BTreeMap<String, ChannelItem>) -> Self {
+    pub const fn new(items: BTreeMap<String, ChannelItem>) -> Self {
         Self {
             items,
         }
@@ -131,7 +131,7 @@ pub struct OneOfMessages {
 }
 
 impl OneOfMessages {
-    pub fn new(one_of: Vec<Message>) -> Self {
+    pub const fn new(one_of: Vec<Message>) -> Self {
         Self {
             one_of,
         }
@@ -178,7 +178,7 @@ pub struct Components {
 }
 
 impl Components {
-    pub fn new(schemas: BTreeMap<String, Schema>) -> Self {
+    pub const fn new(schemas: BTreeMap<String, Schema>) -> Self {
         Self {
             schemas,
         }
@@ -4,19 +4,20 @@ use maplit::btreemap;
 use serde_json::Value;
 
 use bounded_static::ToBoundedStatic;
-use daml::json_api::error::DamlJsonSchemaCodecResult;
-use daml::json_api::schema_encoder::JsonSchemaEncoder;
-use daml::lf::element::{DamlArchive, DamlElementVisitor, DamlTemplate, DamlType, DamlVisitableElement};
+use daml_json::error::DamlJsonSchemaCodecResult;
+use daml_json::schema_encoder::JsonSchemaEncoder;
+use daml_lf::element::{DamlArchive, DamlElementVisitor, DamlTemplate, DamlType, DamlVisitableElement};
 
 use crate::a2s::asyncapi_data::{
     ChannelBindings, ChannelItem, ExternalDocumentation, Message, OneOfMessages, Operation, Tag,
     WebSocketsChannelBinding,
 };
 use crate::common::DataId;
+use crate::error::DamlOasResult;
 use crate::filter::TemplateFilter;
-use crate::json_api_schema::DamlJsonApiSchema;
 use crate::schema::Schema;
 use crate::util::{ChildModulePathOrError, Required};
+use daml_json::api_schema::DamlJsonApiSchema;
 
 /// DOCME
 pub struct ChannelItemEncoder<'arc> {
@@ -45,8 +46,8 @@ impl<'arc> ChannelItemEncoder<'arc> {
         }
     }
 
-    ///
-    pub fn encode_channel_items(self) -> anyhow::Result<BTreeMap<String, ChannelItem>> {
+    /// Encode the `/v1/stream/query` and `/v1/stream/fetch` channel items for the configured templates.
+    pub fn encode_channel_items(self) -> DamlOasResult<BTreeMap<String, ChannelItem>> {
         let template_ids = self.extract_template_info()?;
         Ok(btreemap! {
             String::from("/v1/stream/query") => self.encode_query_channel_item(&template_ids),
@@ -76,7 +77,7 @@ impl<'arc> ChannelItemEncoder<'arc> {
         )
     }
 
-    fn encode_fetch_channel_item(&self, templates: &[TemplateData]) -> anyhow::Result<ChannelItem> {
+    fn encode_fetch_channel_item(&self, templates: &[TemplateData]) -> DamlOasResult<ChannelItem> {
         let template_ids = templates
             .iter()
             .cloned()
@@ -214,11 +215,15 @@ impl<'arc> ChannelItemEncoder<'arc> {
         )
     }
 
-    fn extract_template_info(&self) -> anyhow::Result<Vec<TemplateData>> {
+    fn extract_template_info(&self) -> DamlOasResult<Vec<TemplateData>> {
         struct TemplateVisitor {
             pub templates: Vec<TemplateData>,
         }
         impl DamlElementVisitor for TemplateVisitor {
+            fn sort_elements(&self) -> bool {
+                true
+            }
+
             fn pre_visit_template<'a>(&mut self, template: &'a DamlTemplate<'a>) {
                 let template_id = make_template_id(template.package_id(), template.module_path(), template.name());
                 let key = template.key().map(|k| k.ty().to_static());
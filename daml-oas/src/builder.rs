@@ -0,0 +1,396 @@
+use daml_json::schema_encoder::{
+    DataDict, JsonSchemaEncoder, ReferenceMode, RenderDescription, RenderSchema, RenderTitle, SchemaEncoderConfig,
+};
+use daml_lf::element::DamlArchive;
+use daml_lf::DarFile;
+
+use crate::a2s::{AsyncAPI, AsyncAPIEncoder};
+use crate::companion::CompanionData;
+use crate::config::PathStyle;
+use crate::error::DamlOasResult;
+use crate::filter::TemplateFilter;
+use crate::oas::{OpenAPI, OpenAPIEncoder};
+
+const DEFAULT_REFERENCE_PREFIX: &str = "#/components/schemas/";
+
+/// The options which are common to OpenAPI and AsyncAPI generation.
+#[derive(Debug, Clone)]
+struct DocumentConfig {
+    module_path: Vec<String>,
+    template_filter: TemplateFilter,
+    companion_data: CompanionData,
+    data_dict: DataDict,
+    render_title: RenderTitle,
+    render_description: RenderDescription,
+    reference_prefix: String,
+    reference_mode: ReferenceMode,
+    emit_package_id: bool,
+}
+
+impl DocumentConfig {
+    fn schema_encoder_config(&self) -> SchemaEncoderConfig {
+        SchemaEncoderConfig::new(
+            RenderSchema::None,
+            self.render_title,
+            self.render_description,
+            self.reference_mode.clone(),
+            self.data_dict.clone(),
+        )
+    }
+
+    fn module_path(&self) -> Vec<&str> {
+        self.module_path.iter().map(AsRef::as_ref).collect()
+    }
+}
+
+impl Default for DocumentConfig {
+    fn default() -> Self {
+        Self {
+            module_path: vec![],
+            template_filter: TemplateFilter::default(),
+            companion_data: CompanionData::default(),
+            data_dict: DataDict::default(),
+            render_title: RenderTitle::Data,
+            render_description: RenderDescription::All,
+            reference_prefix: DEFAULT_REFERENCE_PREFIX.to_string(),
+            reference_mode: ReferenceMode::Reference {
+                prefix: DEFAULT_REFERENCE_PREFIX.to_string(),
+            },
+            emit_package_id: false,
+        }
+    }
+}
+
+/// Build an [`OpenAPI`] document for the Daml JSON API from a Daml archive.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use daml_oas::{OpenApiBuilder, PathStyle, DamlOasResult};
+/// # use daml_lf::DarFile;
+/// # fn main() -> DamlOasResult<()> {
+/// let dar = DarFile::from_file("TestingTypes-latest.dar")?;
+/// let openapi = OpenApiBuilder::default()
+///     .module_path(["Fuji", "PingPong"])
+///     .include_archive_choice(true)
+///     .path_style(PathStyle::Slash)
+///     .build_from_dar(&dar)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OpenApiBuilder {
+    config: DocumentConfig,
+    include_archive_choice: bool,
+    include_general_operations: bool,
+    path_style: PathStyle,
+}
+
+impl OpenApiBuilder {
+    /// Only generate items for the module with the given path, and its child modules.
+    #[must_use]
+    pub fn module_path<S: Into<String>>(self, module_path: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            config: DocumentConfig {
+                module_path: module_path.into_iter().map(Into::into).collect(),
+                ..self.config
+            },
+            ..self
+        }
+    }
+
+    /// Only generate items for the templates and choices selected by the given [`TemplateFilter`].
+    #[must_use]
+    pub fn template_filter(self, template_filter: TemplateFilter) -> Self {
+        Self {
+            config: DocumentConfig {
+                template_filter,
+                ..self.config
+            },
+            ..self
+        }
+    }
+
+    /// Augment the generated document with the given [`CompanionData`].
+    #[must_use]
+    pub fn companion_data(self, companion_data: CompanionData) -> Self {
+        Self {
+            config: DocumentConfig {
+                companion_data,
+                ..self.config
+            },
+            ..self
+        }
+    }
+
+    /// Augment the generated JSON schema with the given [`DataDict`].
+    #[must_use]
+    pub fn data_dict(self, data_dict: DataDict) -> Self {
+        Self {
+            config: DocumentConfig {
+                data_dict,
+                ..self.config
+            },
+            ..self
+        }
+    }
+
+    /// Set the [`RenderTitle`] of the generated JSON schema, defaults to [`RenderTitle::Data`].
+    #[must_use]
+    pub fn render_title(self, render_title: RenderTitle) -> Self {
+        Self {
+            config: DocumentConfig {
+                render_title,
+                ..self.config
+            },
+            ..self
+        }
+    }
+
+    /// Set the [`RenderDescription`] of the generated JSON schema, defaults to [`RenderDescription::All`].
+    #[must_use]
+    pub fn render_description(self, render_description: RenderDescription) -> Self {
+        Self {
+            config: DocumentConfig {
+                render_description,
+                ..self.config
+            },
+            ..self
+        }
+    }
+
+    /// Set the prefix used for `$ref` links from request and response schemas to data schemas, defaults to
+    /// `#/components/schemas/`.
+    #[must_use]
+    pub fn reference_prefix(self, reference_prefix: impl Into<String>) -> Self {
+        Self {
+            config: DocumentConfig {
+                reference_prefix: reference_prefix.into(),
+                ..self.config
+            },
+            ..self
+        }
+    }
+
+    /// Set the [`ReferenceMode`] used for data types nested within data schemas.
+    ///
+    /// Defaults to [`ReferenceMode::Reference`] with the prefix `#/components/schemas/`.
+    #[must_use]
+    pub fn reference_mode(self, reference_mode: ReferenceMode) -> Self {
+        Self {
+            config: DocumentConfig {
+                reference_mode,
+                ..self.config
+            },
+            ..self
+        }
+    }
+
+    /// Include the package id in fully qualified template ids.
+    #[must_use]
+    pub fn emit_package_id(self, emit_package_id: bool) -> Self {
+        Self {
+            config: DocumentConfig {
+                emit_package_id,
+                ..self.config
+            },
+            ..self
+        }
+    }
+
+    /// Include the `Archive` choice which is available on every template.
+    #[must_use]
+    pub fn include_archive_choice(self, include_archive_choice: bool) -> Self {
+        Self {
+            include_archive_choice,
+            ..self
+        }
+    }
+
+    /// Include the general (non-template specific) `/v1/create`, `/v1/exercise`, `/v1/create_and_exercise` &
+    /// `/v1/fetch` operations.
+    #[must_use]
+    pub fn include_general_operations(self, include_general_operations: bool) -> Self {
+        Self {
+            include_general_operations,
+            ..self
+        }
+    }
+
+    /// Set the [`PathStyle`] of the generated paths, defaults to [`PathStyle::Fragment`].
+    #[must_use]
+    pub fn path_style(self, path_style: PathStyle) -> Self {
+        Self {
+            path_style,
+            ..self
+        }
+    }
+
+    /// Build an [`OpenAPI`] document from a [`DamlArchive`].
+    pub fn build(&self, archive: &DamlArchive<'_>) -> DamlOasResult<OpenAPI> {
+        let module_path = self.config.module_path();
+        let encoder = JsonSchemaEncoder::new_with_config(archive, self.config.schema_encoder_config());
+        OpenAPIEncoder::new(
+            archive,
+            &module_path,
+            &self.config.template_filter,
+            &self.config.reference_prefix,
+            self.config.emit_package_id,
+            self.include_archive_choice,
+            self.include_general_operations,
+            self.path_style,
+            &self.config.companion_data,
+            encoder,
+        )
+        .encode_archive()
+    }
+
+    /// Build an [`OpenAPI`] document from a [`DarFile`].
+    pub fn build_from_dar(&self, dar: &DarFile) -> DamlOasResult<OpenAPI> {
+        dar.apply(|archive| self.build(archive))?
+    }
+}
+
+/// Build an [`AsyncAPI`] document for the Daml JSON API streaming endpoints from a Daml archive.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use daml_oas::{AsyncApiBuilder, DamlOasResult};
+/// # use daml_lf::DarFile;
+/// # fn main() -> DamlOasResult<()> {
+/// let dar = DarFile::from_file("TestingTypes-latest.dar")?;
+/// let asyncapi =
+///     AsyncApiBuilder::default().module_path(["Fuji", "PingPong"]).build_from_dar(&dar)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AsyncApiBuilder {
+    config: DocumentConfig,
+}
+
+impl AsyncApiBuilder {
+    /// Only generate items for the module with the given path, and its child modules.
+    #[must_use]
+    pub fn module_path<S: Into<String>>(self, module_path: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            config: DocumentConfig {
+                module_path: module_path.into_iter().map(Into::into).collect(),
+                ..self.config
+            },
+        }
+    }
+
+    /// Only generate items for the templates and choices selected by the given [`TemplateFilter`].
+    #[must_use]
+    pub fn template_filter(self, template_filter: TemplateFilter) -> Self {
+        Self {
+            config: DocumentConfig {
+                template_filter,
+                ..self.config
+            },
+        }
+    }
+
+    /// Augment the generated document with the given [`CompanionData`].
+    #[must_use]
+    pub fn companion_data(self, companion_data: CompanionData) -> Self {
+        Self {
+            config: DocumentConfig {
+                companion_data,
+                ..self.config
+            },
+        }
+    }
+
+    /// Augment the generated JSON schema with the given [`DataDict`].
+    #[must_use]
+    pub fn data_dict(self, data_dict: DataDict) -> Self {
+        Self {
+            config: DocumentConfig {
+                data_dict,
+                ..self.config
+            },
+        }
+    }
+
+    /// Set the [`RenderTitle`] of the generated JSON schema, defaults to [`RenderTitle::Data`].
+    #[must_use]
+    pub fn render_title(self, render_title: RenderTitle) -> Self {
+        Self {
+            config: DocumentConfig {
+                render_title,
+                ..self.config
+            },
+        }
+    }
+
+    /// Set the [`RenderDescription`] of the generated JSON schema, defaults to [`RenderDescription::All`].
+    #[must_use]
+    pub fn render_description(self, render_description: RenderDescription) -> Self {
+        Self {
+            config: DocumentConfig {
+                render_description,
+                ..self.config
+            },
+        }
+    }
+
+    /// Set the prefix used for `$ref` links from message schemas to data schemas, defaults to
+    /// `#/components/schemas/`.
+    #[must_use]
+    pub fn reference_prefix(self, reference_prefix: impl Into<String>) -> Self {
+        Self {
+            config: DocumentConfig {
+                reference_prefix: reference_prefix.into(),
+                ..self.config
+            },
+        }
+    }
+
+    /// Set the [`ReferenceMode`] used for data types nested within data schemas.
+    ///
+    /// Defaults to [`ReferenceMode::Reference`] with the prefix `#/components/schemas/`.
+    #[must_use]
+    pub fn reference_mode(self, reference_mode: ReferenceMode) -> Self {
+        Self {
+            config: DocumentConfig {
+                reference_mode,
+                ..self.config
+            },
+        }
+    }
+
+    /// Include the package id in fully qualified template ids.
+    #[must_use]
+    pub fn emit_package_id(self, emit_package_id: bool) -> Self {
+        Self {
+            config: DocumentConfig {
+                emit_package_id,
+                ..self.config
+            },
+        }
+    }
+
+    /// Build an [`AsyncAPI`] document from a [`DamlArchive`].
+    pub fn build(&self, archive: &DamlArchive<'_>) -> DamlOasResult<AsyncAPI> {
+        let module_path = self.config.module_path();
+        let encoder = JsonSchemaEncoder::new_with_config(archive, self.config.schema_encoder_config());
+        AsyncAPIEncoder::new(
+            archive,
+            &module_path,
+            &self.config.template_filter,
+            &self.config.reference_prefix,
+            self.config.emit_package_id,
+            &self.config.companion_data,
+            encoder,
+        )
+        .encode_archive()
+    }
+
+    /// Build an [`AsyncAPI`] document from a [`DarFile`].
+    pub fn build_from_dar(&self, dar: &DarFile) -> DamlOasResult<AsyncAPI> {
+        dar.apply(|archive| self.build(archive))?
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use crate::common::ARCHIVE_CHOICE_NAME;
 use bounded_static::ToBoundedStatic;
-use daml::lf::element::{
+use daml_lf::element::{
     DamlArchive, DamlChoice, DamlCreate, DamlData, DamlElementVisitor, DamlExercise, DamlExerciseByKey, DamlTyCon,
     DamlValueName, DamlVisitableElement,
 };
@@ -42,7 +42,7 @@ impl<'a> ChoiceEvents<'a> {
     }
 }
 
-impl<'a> ChoiceEventExtractor for DamlArchive<'a> {
+impl ChoiceEventExtractor for DamlArchive<'_> {
     fn extract_choice_events<S: AsRef<str>>(
         &self,
         package_id: &str,
@@ -76,18 +76,17 @@ struct ChoiceEventVisitor<'arc> {
 impl DamlElementVisitor for ChoiceEventVisitor<'_> {
     fn pre_visit_value_name<'a>(&mut self, value_name: &'a DamlValueName<'a>) {
         let name = value_name.to_string();
-        if !self.visited.contains(&name) {
-            self.visited.insert(name);
+        if self.visited.insert(name) {
             self.archive.value_by_name(value_name).unwrap().accept(self);
         }
     }
 
-    fn pre_visit_create<'a>(&mut self, create: &DamlCreate<'a>) {
+    fn pre_visit_create(&mut self, create: &DamlCreate<'_>) {
         let template_name = DamlTyCon::new(Box::new(create.template().to_static()), vec![]);
         self.created.insert(template_name);
     }
 
-    fn pre_visit_exercise<'a>(&mut self, exercise: &DamlExercise<'a>) {
+    fn pre_visit_exercise(&mut self, exercise: &DamlExercise<'_>) {
         if exercise.choice() == ARCHIVE_CHOICE_NAME {
             let template_name = DamlTyCon::new(Box::new(exercise.template().to_static()), vec![]);
             self.archived.insert(template_name);
@@ -104,7 +103,7 @@ impl DamlElementVisitor for ChoiceEventVisitor<'_> {
         }
     }
 
-    fn pre_visit_exercise_by_key<'a>(&mut self, exercise_by_key: &DamlExerciseByKey<'a>) {
+    fn pre_visit_exercise_by_key(&mut self, exercise_by_key: &DamlExerciseByKey<'_>) {
         self.archive.data_by_tycon_name(exercise_by_key.template()).unwrap().accept(self);
     }
 }
@@ -1,5 +1,5 @@
 //! Common items
-use daml::json_api::request_converter::DamlJsonTemplateId;
+use daml_json::request_converter::DamlJsonTemplateId;
 
 /// The name given to the JSON schema item holding the Daml JSON error response.
 pub const ERROR_RESPONSE_SCHEMA_NAME: &str = "ErrorResponse";
@@ -13,7 +13,7 @@ pub const GENERAL_OPERATION_TAG: &str = "General";
 /// A fully qualified entity.
 pub type DataId = DamlJsonTemplateId;
 
-///
+/// An item together with the name under which it is encoded.
 pub struct NamedItem<T> {
     pub name: String,
     pub item: T,
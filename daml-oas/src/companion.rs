@@ -1,7 +1,7 @@
 use serde::Deserialize;
 use std::collections::HashMap;
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default)]
 pub struct CompanionData {
     pub title: Option<String>,
     pub summary: Option<String>,
@@ -12,14 +12,14 @@ pub struct CompanionData {
     pub operations: Option<HashMap<String, OperationInfo>>,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default)]
 pub struct Contact {
     pub name: Option<String>,
     pub url: Option<String>,
     pub email: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default)]
 pub struct OperationInfo {
     pub create: Option<String>,
     #[serde(rename = "createAndExercise")]
@@ -1,12 +1,11 @@
 use std::collections::BTreeMap;
 
-use anyhow::{anyhow, Result};
-
-use daml::json_api::schema_encoder::JsonSchemaEncoder;
-use daml::lf::element::{DamlArchive, DamlData, DamlModule, DamlPackage};
+use daml_json::schema_encoder::JsonSchemaEncoder;
+use daml_lf::element::{DamlArchive, DamlData, DamlModule, DamlPackage};
 
 use crate::common::NamedItem;
 use crate::data_searcher::DamlEntitySearcher;
+use crate::error::{DamlOasError, DamlOasResult};
 use crate::filter::TemplateFilter;
 use crate::format::format_oas_data;
 use crate::schema::Schema;
@@ -43,7 +42,7 @@ impl<'arc> ComponentEncoder<'arc> {
     }
 
     /// Encode the data types in the `DamlArchive` as a map JSON `Schema` objects.
-    pub fn encode_schema_components(&self) -> Result<BTreeMap<String, Schema>> {
+    pub fn encode_schema_components(&self) -> DamlOasResult<BTreeMap<String, Schema>> {
         Ok(self
             .encode_package(self.archive.main_package().req()?)?
             .into_iter()
@@ -51,11 +50,11 @@ impl<'arc> ComponentEncoder<'arc> {
             .collect::<BTreeMap<String, Schema>>())
     }
 
-    fn encode_package(&self, package: &DamlPackage<'_>) -> Result<Vec<NamedSchema>> {
+    fn encode_package(&self, package: &DamlPackage<'_>) -> DamlOasResult<Vec<NamedSchema>> {
         self.encode_module(package.root_module().child_module_path_or_err(self.module_prefix)?)
     }
 
-    fn encode_module(&self, module: &DamlModule<'_>) -> Result<Vec<NamedSchema>> {
+    fn encode_module(&self, module: &DamlModule<'_>) -> DamlOasResult<Vec<NamedSchema>> {
         let mut result = Vec::new();
         for sub in module.child_modules() {
             result.extend(self.encode_module(sub)?);
@@ -68,7 +67,7 @@ impl<'arc> ComponentEncoder<'arc> {
         Ok(result)
     }
 
-    fn encode_data(&self, module: &DamlModule<'_>, data: &DamlData<'_>) -> Result<NamedSchema> {
+    fn encode_data(&self, module: &DamlModule<'_>, data: &DamlData<'_>) -> DamlOasResult<NamedSchema> {
         let name = format_oas_data(module, data);
         let schema = Schema::new(self.json_schema_encoder.encode_data(data)?);
         Ok(NamedSchema::new(name, schema))
@@ -77,15 +76,15 @@ impl<'arc> ComponentEncoder<'arc> {
     /// Is the given data item referenced by the filter templates?
     ///
     /// If no filters are defined then all items are included.
-    fn filter_contains(&self, needle: &DamlData<'_>) -> Result<bool> {
+    fn filter_contains(&self, needle: &DamlData<'_>) -> DamlOasResult<bool> {
         self.filter.items.is_empty().not().then(|| self.check_filter(needle)).transpose().map(|o| o.unwrap_or(false))
     }
 
-    fn check_filter(&self, needle: &DamlData<'_>) -> Result<bool> {
+    fn check_filter(&self, needle: &DamlData<'_>) -> DamlOasResult<bool> {
         let it = self.filter.items.iter().map(|(template_id, choice_filter)| {
             self.archive
                 .data(self.archive.main_package_id(), &template_id.module, &template_id.entity)
-                .ok_or_else(|| anyhow!("filter entity {} not found", template_id.to_string()))
+                .ok_or_else(|| DamlOasError::UnknownFilterTemplate(template_id.to_string()))
                 .map(|haystack| match haystack {
                     DamlData::Template(template) =>
                         DamlEntitySearcher::new(self.archive, needle).search_template(template, choice_filter),
@@ -0,0 +1,26 @@
+/// The style of the generated OpenAPI paths.
+///
+/// The Daml JSON API uses the same endpoint (i.e. `/v1/create`) for all templates, however OpenAPI requires that each
+/// path is unique and so the template and choice are appended to the path.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PathStyle {
+    /// Append the template and choice as a fragment, i.e. `/v1/create#Fuji.PingPong.Ping`.
+    Fragment,
+    /// Append the template and choice as a path segment, i.e. `/v1/create/Fuji.PingPong.Ping`.
+    Slash,
+}
+
+impl PathStyle {
+    pub const fn separator(self) -> char {
+        match self {
+            Self::Fragment => '#',
+            Self::Slash => '/',
+        }
+    }
+}
+
+impl Default for PathStyle {
+    fn default() -> Self {
+        Self::Fragment
+    }
+}
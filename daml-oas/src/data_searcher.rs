@@ -1,6 +1,6 @@
 use crate::choice_event_extractor::ChoiceEventExtractor;
 use crate::filter::ChoiceFilter;
-use daml::lf::element::{DamlArchive, DamlChoice, DamlData, DamlField, DamlTemplate, DamlTyCon, DamlType};
+use daml_lf::element::{DamlArchive, DamlChoice, DamlData, DamlField, DamlTemplate, DamlTyCon, DamlType};
 
 /// Search for any usage of a `DamlData` needle in a `DamlTemplate` haystack.
 pub struct DamlEntitySearcher<'v> {
@@ -119,7 +119,7 @@ impl<'v> DamlEntitySearcher<'v> {
         } else {
             self.archive
                 .data_by_tycon(tycon)
-                .map_or_else(|| panic!("data_by_tycon returned None for {:?}", tycon), |data| self.check_data(data))
+                .map_or_else(|| panic!("data_by_tycon returned None for {tycon:?}"), |data| self.check_data(data))
         }
     }
 
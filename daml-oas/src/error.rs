@@ -0,0 +1,31 @@
+use daml_json::error::DamlJsonSchemaCodecError;
+use daml_lf::DamlLfError;
+use thiserror::Error;
+
+/// Daml OAS Result.
+pub type DamlOasResult<T> = Result<T, DamlOasError>;
+
+/// Daml OAS Error.
+#[derive(Error, Debug)]
+pub enum DamlOasError {
+    #[error("DamlOasError: Daml-LF error: {0}")]
+    DamlLfError(#[from] DamlLfError),
+    #[error("DamlOasError: schema error: {0}")]
+    SchemaCodecError(#[from] DamlJsonSchemaCodecError),
+    #[error("DamlOasError: filter template {0} not found")]
+    UnknownFilterTemplate(String),
+    #[error(transparent)]
+    MissingRequiredValue(#[from] RequiredError),
+    #[error(transparent)]
+    UnknownModulePath(#[from] UnknownModulePathError),
+}
+
+/// A required value was not supplied.
+#[derive(Error, Debug)]
+#[error("required value was not supplied")]
+pub struct RequiredError;
+
+/// A module path was not found in the archive.
+#[derive(Error, Debug)]
+#[error("unknown module path {0}")]
+pub struct UnknownModulePathError(pub String);
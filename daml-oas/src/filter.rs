@@ -1,32 +1,31 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 
-use daml::json_api::error::{DamlJsonReqConError, DamlJsonReqConResult};
-use daml::json_api::request_converter::DamlJsonTemplateId;
+use daml_json::error::DamlJsonReqConError;
+use daml_json::request_converter::DamlJsonTemplateId;
 use serde::Deserialize;
 
-///
-#[derive(Debug, Deserialize, Default)]
+/// A [`TemplateFilter`] as read from a template filter file, keyed by template id strings.
+#[derive(Debug, Clone, Deserialize, Default)]
 #[serde(transparent)]
 pub struct TemplateFilterInput {
-    pub items: HashMap<String, ChoiceFilter>,
+    pub items: BTreeMap<String, ChoiceFilter>,
 }
 
 /// A collection of template and choice filtering rules.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct TemplateFilter {
-    pub items: HashMap<DamlJsonTemplateId, ChoiceFilter>,
+    pub items: BTreeMap<DamlJsonTemplateId, ChoiceFilter>,
 }
 
 impl TryFrom<TemplateFilterInput> for TemplateFilter {
     type Error = DamlJsonReqConError;
 
     fn try_from(value: TemplateFilterInput) -> Result<Self, Self::Error> {
-        let items = value
-            .items
-            .into_iter()
-            .map(|(template, filter)| DamlJsonTemplateId::try_from(template.as_str()).map(|di| (di, filter)))
-            .collect::<DamlJsonReqConResult<HashMap<_, _>>>()?;
+        let mut items = BTreeMap::new();
+        for (template, filter) in value.items {
+            items.insert(DamlJsonTemplateId::try_from(template.as_str())?, filter);
+        }
         Ok(Self {
             items,
         })
@@ -34,7 +33,7 @@ impl TryFrom<TemplateFilterInput> for TemplateFilter {
 }
 
 /// Specifies Which choices of a given template to include.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub enum ChoiceFilter {
     #[serde(rename = "none")]
     None,
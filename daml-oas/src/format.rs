@@ -1,10 +1,9 @@
 //! Formatting function.
 use crate::common::DataId;
-use daml::lf::element::{DamlData, DamlModule, DamlTemplate};
+use daml_lf::element::{DamlData, DamlModule, DamlTemplate};
 use itertools::Itertools;
 
 const PATH_SEPARATOR: &str = ".";
-const PATH_SEPARATOR_REGEX_SAFE: &str = "\\.";
 
 /// Format a `DamlTemplate`.
 pub fn format_template(template_id: &DamlTemplate<'_>) -> String {
@@ -43,11 +42,6 @@ pub fn format_path_slice<T: AsRef<str>>(path: &[T]) -> String {
     format_path_join(path.iter().map(AsRef::as_ref), PATH_SEPARATOR)
 }
 
-/// Format a url safe module path.
-pub fn format_path_slice_regex_safe<T: AsRef<str>>(path: &[T]) -> String {
-    format_path_join(path.iter().map(AsRef::as_ref), PATH_SEPARATOR_REGEX_SAFE)
-}
-
 /// Format a module path from an iterator.
 pub fn format_path<'a>(path: impl Iterator<Item = &'a str>) -> String {
     format_path_join(path, PATH_SEPARATOR)
@@ -0,0 +1,32 @@
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, rust_2018_idioms)]
+#![allow(clippy::module_name_repetitions, clippy::must_use_candidate, clippy::missing_errors_doc, clippy::doc_markdown)]
+#![forbid(unsafe_code)]
+#![doc(html_favicon_url = "https://docs.daml.com/_static/images/favicon/favicon-32x32.png")]
+#![doc(html_logo_url = "https://docs.daml.com/_static/images/DAML_Logo_Blue.svg")]
+#![doc(html_root_url = "https://docs.rs/daml-oas/0.2.2")]
+// importing the crate README as the rust doc breaks the link to the LICENCE file.
+#![allow(rustdoc::broken_intra_doc_links)]
+#![doc = include_str!("../README.md")]
+
+mod a2s;
+mod builder;
+mod choice_event_extractor;
+mod common;
+mod companion;
+mod component_encoder;
+mod config;
+mod data_searcher;
+mod error;
+mod filter;
+mod format;
+mod oas;
+mod schema;
+mod util;
+
+pub use a2s::AsyncAPI;
+pub use builder::{AsyncApiBuilder, OpenApiBuilder};
+pub use companion::{CompanionData, Contact, OperationInfo};
+pub use config::PathStyle;
+pub use error::{DamlOasError, DamlOasResult, RequiredError, UnknownModulePathError};
+pub use filter::{ChoiceFilter, TemplateFilter, TemplateFilterInput};
+pub use oas::OpenAPI;
@@ -1,4 +1,3 @@
-#![doc = include_str!("../README.md")]
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, rust_2018_idioms)]
 #![allow(clippy::module_name_repetitions, clippy::must_use_candidate, clippy::missing_errors_doc)]
 #![forbid(unsafe_code)]
@@ -11,51 +10,16 @@ use std::path::PathBuf;
 
 use anyhow::{anyhow, Context, Result};
 use clap::{crate_description, crate_name, crate_version, Arg, ArgMatches, Command};
-use serde::Serialize;
-
-use companion::CompanionData;
-use config::PathStyle;
-use config::{Config, OutputFormat};
-use daml::json_api::schema_encoder::{
-    DataDict, JsonSchemaEncoder, ReferenceMode, RenderDescription, RenderSchema, RenderTitle, SchemaEncoderConfig,
-};
-use daml::lf::DarFile;
-use filter::{TemplateFilter, TemplateFilterInput};
-use oas::OpenAPI;
-use oas::OpenAPIEncoder;
-
-use crate::a2s::AsyncAPI;
-use crate::a2s::AsyncAPIEncoder;
 use log::LevelFilter;
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 use simple_logger::SimpleLogger;
 
-#[doc(hidden)]
-mod a2s;
-#[doc(hidden)]
-mod choice_event_extractor;
-#[doc(hidden)]
-mod common;
-#[doc(hidden)]
-mod companion;
-#[doc(hidden)]
-mod component_encoder;
-#[doc(hidden)]
-mod config;
-#[doc(hidden)]
-mod data_searcher;
-#[doc(hidden)]
-mod filter;
-#[doc(hidden)]
-mod format;
-#[doc(hidden)]
-mod json_api_schema;
-#[doc(hidden)]
-mod oas;
-#[doc(hidden)]
-mod schema;
-#[doc(hidden)]
-mod util;
+use daml_json::schema_encoder::{DataDict, ReferenceMode, RenderDescription, RenderTitle};
+use daml_lf::DarFile;
+use daml_oas::{
+    AsyncAPI, AsyncApiBuilder, CompanionData, OpenAPI, OpenApiBuilder, PathStyle, TemplateFilter, TemplateFilterInput,
+};
 
 #[doc(hidden)]
 const DEFAULT_DATA_DICT_FILE: &str = ".datadict.yaml";
@@ -64,6 +28,34 @@ const DEFAULT_TEMPLATE_FILTER_FILE: &str = ".template_filter.yaml";
 #[doc(hidden)]
 const DEFAULT_COMPANION_FILE: &str = ".companion.yaml";
 
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug)]
+enum OutputFormat {
+    Json,
+    Yaml,
+}
+
+#[doc(hidden)]
+#[allow(clippy::struct_excessive_bools)]
+struct Config<'a> {
+    dar_file: String,
+    companion_file: Option<String>,
+    data_dict_file: Option<String>,
+    template_filter_file: Option<String>,
+    format: OutputFormat,
+    output_file: Option<String>,
+    module_path: Vec<&'a str>,
+    render_title: RenderTitle,
+    render_description: RenderDescription,
+    reference_prefix: &'a str,
+    reference_mode: ReferenceMode,
+    emit_package_id: bool,
+    include_archive_choice: bool,
+    include_general_operations: bool,
+    path_style: PathStyle,
+    level_filter: LevelFilter,
+}
+
 #[doc(hidden)]
 fn main() -> Result<()> {
     let oas = Command::new("oas")
@@ -110,7 +102,7 @@ fn main() -> Result<()> {
         Some(("oas", sub)) => execute_oas(&parse_config(sub))?,
         Some(("a2s", sub)) => execute_a2s(&parse_config(sub))?,
         _ => {},
-    };
+    }
     Ok(())
 }
 
@@ -130,7 +122,7 @@ fn make_format_arg() -> Arg<'static> {
         .short('f')
         .long("format")
         .takes_value(true)
-        .possible_values(&["json", "yaml"])
+        .possible_values(["json", "yaml"])
         .default_value("json")
         .required(false)
         .help("the output format")
@@ -186,7 +178,7 @@ fn make_data_title_arg() -> Arg<'static> {
     Arg::new("data-title")
         .long("data-title")
         .takes_value(true)
-        .possible_values(&["none", "data"])
+        .possible_values(["none", "data"])
         .default_value("data")
         .required(false)
         .help("include the `title` property describing the data item name (i.e. Foo.Bar:Baz)")
@@ -197,7 +189,7 @@ fn make_type_description_arg() -> Arg<'static> {
     Arg::new("type-description")
         .long("type-description")
         .takes_value(true)
-        .possible_values(&["none", "data", "all"])
+        .possible_values(["none", "data", "all"])
         .default_value("all")
         .required(false)
         .help("include the `description` property describing the Daml type")
@@ -220,7 +212,7 @@ fn make_reference_mode_arg() -> Arg<'static> {
         .short('r')
         .long("reference-mode")
         .takes_value(true)
-        .possible_values(&["ref", "inline"])
+        .possible_values(["ref", "inline"])
         .default_value("ref")
         .required(false)
         .help("encode references as as $ref schema links or inline")
@@ -256,7 +248,7 @@ fn make_path_style_arg() -> Arg<'static> {
         .short('s')
         .long("path-style")
         .takes_value(true)
-        .possible_values(&["fragment", "slash"])
+        .possible_values(["fragment", "slash"])
         .default_value("fragment")
         .required(false)
         .help("encode paths with fragment (i.e. '#') or slash ('/')")
@@ -274,46 +266,42 @@ fn parse_config(matches: &ArgMatches) -> Config<'_> {
     let data_dict_file = matches.value_of("datadict-file").map(ToString::to_string);
     let template_filter_file = matches.value_of("template-filter-file").map(ToString::to_string);
     let format = match matches.value_of("format") {
-        None => OutputFormat::Json,
-        Some(s) if s == "json" => OutputFormat::Json,
-        Some(s) if s == "yaml" => OutputFormat::Yaml,
-        Some(s) => panic!("unknown format {}", s),
+        None | Some("json") => OutputFormat::Json,
+        Some("yaml") => OutputFormat::Yaml,
+        Some(s) => panic!("unknown format {s}"),
     };
     let output_file = matches.value_of("output").map(ToString::to_string);
     let module_path = matches.value_of("module-path").map(|v| v.split('.').collect::<Vec<_>>()).unwrap_or_default();
     let render_title = match matches.value_of("data-title") {
-        None => RenderTitle::None,
-        Some(s) if s == "none" => RenderTitle::None,
-        Some(s) if s == "data" => RenderTitle::Data,
-        Some(s) => panic!("unknown data-title {}", s),
+        None | Some("none") => RenderTitle::None,
+        Some("data") => RenderTitle::Data,
+        Some(s) => panic!("unknown data-title {s}"),
     };
     let render_description = match matches.value_of("type-description") {
-        None => RenderDescription::None,
-        Some(s) if s == "none" => RenderDescription::None,
-        Some(s) if s == "data" => RenderDescription::Data,
-        Some(s) if s == "all" => RenderDescription::All,
-        Some(s) => panic!("unknown type-description {}", s),
+        None | Some("none") => RenderDescription::None,
+        Some("data") => RenderDescription::Data,
+        Some("all") => RenderDescription::All,
+        Some(s) => panic!("unknown type-description {s}"),
     };
     let reference_prefix = matches.value_of("reference-prefix").unwrap();
     let reference_mode = match matches.value_of("reference-mode") {
         None => ReferenceMode::default(),
-        Some(s) if s == "ref" => ReferenceMode::Reference {
+        Some("ref") => ReferenceMode::Reference {
             prefix: reference_prefix.to_string(),
         },
-        Some(s) if s == "inline" => ReferenceMode::Inline,
-        Some(s) => panic!("unknown reference-prefix {}", s),
+        Some("inline") => ReferenceMode::Inline,
+        Some(s) => panic!("unknown reference-prefix {s}"),
     };
     let emit_package_id = matches.is_present("include-package-id");
-    let include_archive_choice = matches.is_present("include-archive-choice");
-
-    let include_general_operations = matches.is_present("include-general-operations");
-
-    let path_style = match matches.value_of("path-style") {
-        None => PathStyle::default(),
-        Some(s) if s == "fragment" => PathStyle::Fragment,
-        Some(s) if s == "slash" => PathStyle::Slash,
-        Some(s) => panic!("unknown path-style {}", s),
-    };
+    let include_archive_choice = is_present_for_command(matches, "include-archive-choice");
+    let include_general_operations = is_present_for_command(matches, "include-general-operations");
+    let path_style =
+        match is_present_for_command(matches, "path-style").then(|| matches.value_of("path-style")).flatten() {
+            None => PathStyle::default(),
+            Some("fragment") => PathStyle::Fragment,
+            Some("slash") => PathStyle::Slash,
+            Some(s) => panic!("unknown path-style {s}"),
+        };
 
     Config {
         dar_file,
@@ -335,7 +323,13 @@ fn parse_config(matches: &ArgMatches) -> Config<'_> {
     }
 }
 
-/// OAS
+/// Is an argument, which is not defined for all subcommands, present?
+#[doc(hidden)]
+fn is_present_for_command(matches: &ArgMatches, id: &str) -> bool {
+    matches.try_contains_id(id).unwrap_or(false)
+}
+
+// OAS
 
 #[doc(hidden)]
 fn execute_oas(config: &Config<'_>) -> Result<()> {
@@ -344,13 +338,13 @@ fn execute_oas(config: &Config<'_>) -> Result<()> {
     log::info!("Loading dar file...");
     let dar = DarFile::from_file(&config.dar_file).context(format!("dar file not found: {}", &config.dar_file))?;
     log::info!("Loading companion data file...");
-    let companion_data = get_companion_data(&config.companion_file)?;
+    let companion_data = get_companion_data(config.companion_file.as_deref())?;
     log::info!("Loading data dict file...");
-    let data_dict = get_data_dict(&config.data_dict_file)?;
+    let data_dict = get_data_dict(config.data_dict_file.as_deref())?;
     log::info!("Loading template filter file...");
-    let template_filter = get_template_filter(&config.template_filter_file)?;
+    let template_filter = get_template_filter(config.template_filter_file.as_deref())?;
     log::info!("Generating API document...");
-    let oas = generate_openapi(&dar, config, &companion_data, data_dict, &template_filter)?;
+    let oas = generate_openapi(&dar, config, companion_data, data_dict, template_filter)?;
     log::info!("Rendering API document...");
     let rendered = render(&oas, config.format)?;
     log::info!("Writing API document...");
@@ -361,46 +355,37 @@ fn execute_oas(config: &Config<'_>) -> Result<()> {
 fn generate_openapi(
     dar_file: &DarFile,
     config: &Config<'_>,
-    companion_data: &CompanionData,
+    companion_data: CompanionData,
     data_dict: DataDict,
-    template_filter: &TemplateFilter,
+    template_filter: TemplateFilter,
 ) -> Result<OpenAPI> {
-    let encoder_config = SchemaEncoderConfig::new(
-        RenderSchema::None,
-        config.render_title,
-        config.render_description,
-        config.reference_mode.clone(),
-        data_dict,
-    );
-    dar_file.apply(|archive| {
-        let encoder = JsonSchemaEncoder::new_with_config(archive, encoder_config);
-        let generator = OpenAPIEncoder::new(
-            archive,
-            &config.module_path,
-            template_filter,
-            config.reference_prefix,
-            config.emit_package_id,
-            config.include_archive_choice,
-            config.include_general_operations,
-            config.path_style,
-            companion_data,
-            encoder,
-        );
-        generator.encode_archive()
-    })?
+    Ok(OpenApiBuilder::default()
+        .module_path(config.module_path.iter().copied())
+        .template_filter(template_filter)
+        .companion_data(companion_data)
+        .data_dict(data_dict)
+        .render_title(config.render_title)
+        .render_description(config.render_description)
+        .reference_prefix(config.reference_prefix)
+        .reference_mode(config.reference_mode.clone())
+        .emit_package_id(config.emit_package_id)
+        .include_archive_choice(config.include_archive_choice)
+        .include_general_operations(config.include_general_operations)
+        .path_style(config.path_style)
+        .build_from_dar(dar_file)?)
 }
 
-/// A2S
+// A2S
 
 #[doc(hidden)]
 fn execute_a2s(config: &Config<'_>) -> Result<()> {
     SimpleLogger::new().with_level(config.level_filter).init().unwrap();
     log::info!("Generating A2S specification documents for {}", config.dar_file);
     let dar = DarFile::from_file(&config.dar_file).context(format!("dar file not found: {}", &config.dar_file))?;
-    let companion_data = get_companion_data(&config.companion_file)?;
-    let data_dict = get_data_dict(&config.data_dict_file)?;
-    let template_filter = get_template_filter(&config.template_filter_file)?;
-    let a2s = generate_asyncapi(&dar, config, &companion_data, data_dict, &template_filter)?;
+    let companion_data = get_companion_data(config.companion_file.as_deref())?;
+    let data_dict = get_data_dict(config.data_dict_file.as_deref())?;
+    let template_filter = get_template_filter(config.template_filter_file.as_deref())?;
+    let a2s = generate_asyncapi(&dar, config, companion_data, data_dict, template_filter)?;
     write_document(&render(&a2s, config.format)?, config.output_file.as_deref())
 }
 
@@ -408,60 +393,50 @@ fn execute_a2s(config: &Config<'_>) -> Result<()> {
 fn generate_asyncapi(
     dar_file: &DarFile,
     config: &Config<'_>,
-    companion_data: &CompanionData,
+    companion_data: CompanionData,
     data_dict: DataDict,
-    template_filter: &TemplateFilter,
+    template_filter: TemplateFilter,
 ) -> Result<AsyncAPI> {
-    let encoder_config = SchemaEncoderConfig::new(
-        RenderSchema::None,
-        config.render_title,
-        config.render_description,
-        config.reference_mode.clone(),
-        data_dict,
-    );
-    dar_file.apply(|archive| {
-        let encoder = JsonSchemaEncoder::new_with_config(archive, encoder_config);
-        let generator = AsyncAPIEncoder::new(
-            archive,
-            &config.module_path,
-            template_filter,
-            config.reference_prefix,
-            config.emit_package_id,
-            companion_data,
-            encoder,
-        );
-        generator.encode_archive()
-    })?
+    Ok(AsyncApiBuilder::default()
+        .module_path(config.module_path.iter().copied())
+        .template_filter(template_filter)
+        .companion_data(companion_data)
+        .data_dict(data_dict)
+        .render_title(config.render_title)
+        .render_description(config.render_description)
+        .reference_prefix(config.reference_prefix)
+        .reference_mode(config.reference_mode.clone())
+        .emit_package_id(config.emit_package_id)
+        .build_from_dar(dar_file)?)
 }
 
-/// Common
+// Common
 
 #[doc(hidden)]
-fn get_companion_data(filter_file_name: &Option<String>) -> Result<CompanionData> {
+fn get_companion_data(filter_file_name: Option<&str>) -> Result<CompanionData> {
     read_file(filter_file_name, DEFAULT_COMPANION_FILE)
         .map_err(|err| anyhow!("failed to parse companion file").context(err))
 }
 
 #[doc(hidden)]
-fn get_data_dict(data_dict_file_name: &Option<String>) -> Result<DataDict> {
+fn get_data_dict(data_dict_file_name: Option<&str>) -> Result<DataDict> {
     read_file(data_dict_file_name, DEFAULT_DATA_DICT_FILE)
         .map_err(|err| anyhow!("failed to parse datadict file").context(err))
 }
 
 #[doc(hidden)]
-fn get_template_filter(filter_file_name: &Option<String>) -> Result<TemplateFilter> {
+fn get_template_filter(filter_file_name: Option<&str>) -> Result<TemplateFilter> {
     let filter: TemplateFilterInput = read_file(filter_file_name, DEFAULT_TEMPLATE_FILTER_FILE)?;
     TemplateFilter::try_from(filter).map_err(|err| anyhow!("failed to parse template filter file").context(err))
 }
 
 #[doc(hidden)]
-fn read_file<T: DeserializeOwned + Default, S: AsRef<str>>(file_name: &Option<String>, fallback: S) -> Result<T> {
-    let path = file_name.as_ref();
-    if let Some(name) = path {
+fn read_file<T: DeserializeOwned + Default, S: AsRef<str>>(file_name: Option<&str>, fallback: S) -> Result<T> {
+    if let Some(name) = file_name {
         let path = PathBuf::from(name);
         if path.is_file() && path.exists() {
             let f = std::fs::File::open(path)?;
-            Ok(serde_yaml::from_reader(f).map_err(|err| anyhow!("failed to parse file {}", name).context(err))?)
+            Ok(serde_yaml::from_reader(f).map_err(|err| anyhow!("failed to parse file {name}").context(err))?)
         } else {
             Err(anyhow!(format!("file {} not found", path.display())))
         }
@@ -1,18 +1,19 @@
 use itertools::Itertools;
 
-use daml::json_api::schema_encoder::JsonSchemaEncoder;
-use daml::lf::element::{DamlArchive, DamlModule, DamlPackage};
+use daml_json::schema_encoder::JsonSchemaEncoder;
+use daml_lf::element::{DamlArchive, DamlModule, DamlPackage};
 
 use crate::common::{ERROR_RESPONSE_SCHEMA_NAME, GENERAL_OPERATION_TAG};
 use crate::companion::CompanionData;
 use crate::component_encoder::ComponentEncoder;
 use crate::config::PathStyle;
+use crate::error::DamlOasResult;
 use crate::filter::TemplateFilter;
-use crate::json_api_schema::DamlJsonApiSchema;
 use crate::oas::openapi_data::{Components, Contact, Info, OpenAPI, Paths, Server, Tag};
 use crate::oas::path_item_encoder::PathItemEncoder;
 use crate::schema::Schema;
 use crate::util::{ChildModulePathOrError, Required};
+use daml_json::api_schema::DamlJsonApiSchema;
 
 pub struct OpenAPIEncoder<'arc> {
     archive: &'arc DamlArchive<'arc>,
@@ -56,7 +57,7 @@ impl<'arc> OpenAPIEncoder<'arc> {
     }
 
     /// Encode a `DamlArchive` as a JSON OAS document.
-    pub fn encode_archive(&self) -> anyhow::Result<OpenAPI> {
+    pub fn encode_archive(&self) -> DamlOasResult<OpenAPI> {
         let info = self.encode_info()?;
         let servers = self.encode_servers();
         let paths = self.encode_paths()?;
@@ -65,7 +66,7 @@ impl<'arc> OpenAPIEncoder<'arc> {
         Ok(OpenAPI::new(info, servers, paths, components, tags))
     }
 
-    fn encode_info(&self) -> anyhow::Result<Info> {
+    fn encode_info(&self) -> DamlOasResult<Info> {
         log::info!("encoding info");
         let title =
             self.companion_data.title.as_ref().map_or_else(|| self.archive.name().to_string(), ToString::to_string);
@@ -104,22 +105,16 @@ impl<'arc> OpenAPIEncoder<'arc> {
             .or_else(|| self.archive.main_package().and_then(DamlPackage::version))
             .req()?;
         let info = Info::new(title, self.companion_data.summary.clone(), Some(contact), description, version);
-        log::debug!("Info: {:#?}", info);
+        log::debug!("Info: {info:#?}");
         Ok(info)
     }
 
     fn encode_servers(&self) -> Vec<Server> {
         log::info!("encoding servers");
-        self.companion_data
-            .servers
-            .as_ref()
-            .unwrap_or(&Vec::default())
-            .iter()
-            .map(|s| Server::new(s.to_string(), None))
-            .collect()
+        self.companion_data.servers.iter().flatten().map(|s| Server::new(s.clone(), None)).collect()
     }
 
-    fn encode_paths(&self) -> anyhow::Result<Paths> {
+    fn encode_paths(&self) -> DamlOasResult<Paths> {
         log::info!("encoding paths");
         Ok(Paths::new(
             PathItemEncoder::new(
@@ -138,7 +133,7 @@ impl<'arc> OpenAPIEncoder<'arc> {
         ))
     }
 
-    fn encode_components(&self) -> anyhow::Result<Components> {
+    fn encode_components(&self) -> DamlOasResult<Components> {
         log::info!("encoding components");
         let encoder = ComponentEncoder::new(self.archive, self.module_path, &self.encoder, self.filter);
         let mut schemas = encoder.encode_schema_components()?;
@@ -146,19 +141,17 @@ impl<'arc> OpenAPIEncoder<'arc> {
         Ok(Components::new(schemas))
     }
 
-    fn encode_tags(&self) -> anyhow::Result<Vec<Tag>> {
+    fn encode_tags(&self) -> DamlOasResult<Vec<Tag>> {
         log::info!("encoding tags");
         let root = self.archive.main_package().req()?.root_module().child_module_path_or_err(self.module_path)?;
-        Ok(std::iter::once(Tag::new(GENERAL_OPERATION_TAG.to_string(), None))
-            .chain(self.module_path(root).into_iter())
-            .collect())
+        Ok(std::iter::once(Tag::new(GENERAL_OPERATION_TAG.to_string(), None)).chain(Self::module_path(root)).collect())
     }
 
-    fn module_path(&self, module: &DamlModule<'_>) -> Vec<Tag> {
+    fn module_path(module: &DamlModule<'_>) -> Vec<Tag> {
         Self::has_data(module)
             .then(|| Tag::new(module.path().join("."), None))
             .into_iter()
-            .chain(module.child_modules().flat_map(|cm| self.module_path(cm)))
+            .chain(module.child_modules().sorted_by_key(|cm| cm.path().join(".")).flat_map(Self::module_path))
             .collect()
     }
 
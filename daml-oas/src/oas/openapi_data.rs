@@ -123,7 +123,7 @@ pub struct Paths {
 }
 
 impl Paths {
-    pub fn new(items: BTreeMap<String, PathItem>) -> Self {
+    pub const fn new(items: BTreeMap<String, PathItem>) -> Self {
         Self {
             items,
         }
@@ -136,7 +136,7 @@ pub struct Components {
 }
 
 impl Components {
-    pub fn new(schemas: BTreeMap<String, Schema>) -> Self {
+    pub const fn new(schemas: BTreeMap<String, Schema>) -> Self {
         Self {
             schemas,
         }
@@ -162,7 +162,7 @@ pub struct Operation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "operationId")]
-    pub operation_id: Option<String>,
+    pub id: Option<String>,
     pub tags: Vec<String>,
     #[serde(rename = "requestBody")]
     pub request_body: RequestBody,
@@ -172,14 +172,14 @@ pub struct Operation {
 impl Operation {
     pub const fn new(
         description: Option<String>,
-        operation_id: Option<String>,
+        id: Option<String>,
         tags: Vec<String>,
         request_body: RequestBody,
         responses: Responses,
     ) -> Self {
         Self {
             description,
-            operation_id,
+            id,
             tags,
             request_body,
             responses,
@@ -207,7 +207,7 @@ pub struct Responses {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<ResponseType>,
     #[serde(flatten)]
-    pub responses: BTreeMap<String, ResponseType>,
+    pub items: BTreeMap<String, ResponseType>,
 }
 
 #[derive(Debug, Serialize)]
@@ -216,16 +216,6 @@ pub struct Response {
     pub content: BTreeMap<String, MediaType>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct Reference {
-    #[serde(rename = "$ref")]
-    pub dollar_ref: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub summary: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
-}
-
 #[derive(Debug, Serialize)]
 pub struct MediaType {
     pub schema: Schema,
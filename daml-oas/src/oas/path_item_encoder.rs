@@ -4,20 +4,21 @@ use crate::choice_event_extractor::ChoiceEventExtractor;
 use crate::common::{DataId, NamedItem, ARCHIVE_CHOICE_NAME, ERROR_RESPONSE_SCHEMA_NAME, GENERAL_OPERATION_TAG};
 use crate::companion::{CompanionData, OperationInfo};
 use crate::config::PathStyle;
+use crate::error::DamlOasResult;
 use crate::filter::{ChoiceFilter, TemplateFilter};
 use crate::format;
 use crate::format::{format_daml_template, format_path_slice};
 use crate::format::{format_path, format_template};
-use crate::json_api_schema::DamlJsonApiSchema;
 use crate::oas::openapi_data::{MediaType, Operation, PathItem, RequestBody, Response, ResponseType, Responses};
 use crate::oas::operation::OperationIdFactory;
 use crate::schema::Schema;
 use crate::util::{ChildModulePathOrError, Required};
-use daml::json_api::schema_encoder::JsonSchemaEncoder;
-use daml::lf::element::{
+use daml_json::api_schema::DamlJsonApiSchema;
+use daml_json::schema_encoder::JsonSchemaEncoder;
+use daml_lf::element::{
     DamlArchive, DamlChoice, DamlData, DamlDefKey, DamlModule, DamlPackage, DamlTemplate, DamlTyCon,
 };
-use itertools::{chain, process_results};
+use itertools::{chain, process_results, Itertools};
 use maplit::btreemap;
 use serde_json::Value;
 
@@ -68,8 +69,8 @@ impl<'arc> PathItemEncoder<'arc> {
         }
     }
 
-    ///
-    pub fn encode_path_items(self) -> anyhow::Result<BTreeMap<String, PathItem>> {
+    /// Encode the path items for all templates of the configured module path of the main package.
+    pub fn encode_path_items(self) -> DamlOasResult<BTreeMap<String, PathItem>> {
         Ok(self
             .encode_package(self.archive.main_package().req()?)?
             .into_iter()
@@ -77,12 +78,12 @@ impl<'arc> PathItemEncoder<'arc> {
             .collect::<BTreeMap<String, PathItem>>())
     }
 
-    fn encode_package(&self, package: &DamlPackage<'_>) -> anyhow::Result<Vec<NamedPathItem>> {
+    fn encode_package(&self, package: &DamlPackage<'_>) -> DamlOasResult<Vec<NamedPathItem>> {
         log::info!("encoding package {}", package.name());
         self.encode_module(package, package.root_module().child_module_path_or_err(self.module_path)?)
     }
 
-    fn encode_module(&self, package: &DamlPackage<'_>, module: &DamlModule<'_>) -> anyhow::Result<Vec<NamedPathItem>> {
+    fn encode_module(&self, package: &DamlPackage<'_>, module: &DamlModule<'_>) -> DamlOasResult<Vec<NamedPathItem>> {
         log::info!("encoding module {}", format_path(module.path()));
         let dt_iter = module.data_types().map(|dt| self.encode_data(package, module, dt));
         let child_mod_iter = module.child_modules().map(|m| self.encode_module(package, m));
@@ -94,7 +95,7 @@ impl<'arc> PathItemEncoder<'arc> {
         package: &DamlPackage<'_>,
         module: &DamlModule<'_>,
         data: &DamlData<'_>,
-    ) -> anyhow::Result<Vec<NamedPathItem>> {
+    ) -> DamlOasResult<Vec<NamedPathItem>> {
         Ok(if let DamlData::Template(template) = data {
             if self.filter_contains(template) {
                 log::info!("encoding template {}", format_template(template));
@@ -113,7 +114,7 @@ impl<'arc> PathItemEncoder<'arc> {
         package: &DamlPackage<'_>,
         module: &DamlModule<'_>,
         template: &DamlTemplate<'_>,
-    ) -> anyhow::Result<Vec<NamedPathItem>> {
+    ) -> DamlOasResult<Vec<NamedPathItem>> {
         let create = self.encode_template_create(package, module, template);
         let create_and_exercise = self.encode_template_create_and_exercise(package, module, template)?;
         let exercise_by_id = self.encode_template_exercise_by_id(package, module, template)?;
@@ -131,7 +132,6 @@ impl<'arc> PathItemEncoder<'arc> {
             vec![]
         };
         Ok(std::iter::once(create)
-            .into_iter()
             .chain(create_and_exercise)
             .chain(exercise_by_id)
             .chain(exercise_by_key)
@@ -163,15 +163,13 @@ impl<'arc> PathItemEncoder<'arc> {
         package: &DamlPackage<'_>,
         module: &DamlModule<'_>,
         template: &DamlTemplate<'_>,
-    ) -> anyhow::Result<Vec<NamedPathItem>> {
+    ) -> DamlOasResult<Vec<NamedPathItem>> {
         template
             .choices()
             .iter()
-            .filter_map(|choice| {
-                self.should_include_choice(module, template, choice)
-                    .then(|| self.encode_template_exercise_by_id_choice(package, module, template, choice))
-            })
-            .collect::<anyhow::Result<Vec<_>>>()
+            .filter(|&choice| self.should_include_choice(module, template, choice))
+            .map(|choice| self.encode_template_exercise_by_id_choice(package, module, template, choice))
+            .collect::<DamlOasResult<Vec<_>>>()
     }
 
     fn encode_template_exercise_by_key(
@@ -179,7 +177,7 @@ impl<'arc> PathItemEncoder<'arc> {
         package: &DamlPackage<'_>,
         module: &DamlModule<'_>,
         template: &DamlTemplate<'_>,
-    ) -> anyhow::Result<Vec<NamedPathItem>> {
+    ) -> DamlOasResult<Vec<NamedPathItem>> {
         template
             .choices()
             .iter()
@@ -188,7 +186,7 @@ impl<'arc> PathItemEncoder<'arc> {
                     Some(self.encode_template_exercise_by_key_choice(package, module, template, choice, key)),
                 _ => None,
             })
-            .collect::<anyhow::Result<Vec<_>>>()
+            .collect::<DamlOasResult<Vec<_>>>()
     }
 
     fn encode_template_create_and_exercise(
@@ -196,15 +194,13 @@ impl<'arc> PathItemEncoder<'arc> {
         package: &DamlPackage<'_>,
         module: &DamlModule<'_>,
         template: &DamlTemplate<'_>,
-    ) -> anyhow::Result<Vec<NamedPathItem>> {
+    ) -> DamlOasResult<Vec<NamedPathItem>> {
         template
             .choices()
             .iter()
-            .filter_map(|choice| {
-                self.should_include_choice(module, template, choice)
-                    .then(|| self.encode_template_create_and_exercise_choice(package, module, template, choice))
-            })
-            .collect::<anyhow::Result<Vec<_>>>()
+            .filter(|&choice| self.should_include_choice(module, template, choice))
+            .map(|choice| self.encode_template_create_and_exercise_choice(package, module, template, choice))
+            .collect::<DamlOasResult<Vec<_>>>()
     }
 
     fn encode_template_fetch_by_id(
@@ -230,7 +226,7 @@ impl<'arc> PathItemEncoder<'arc> {
         module: &DamlModule<'_>,
         template: &DamlTemplate<'_>,
         key: &DamlDefKey<'_>,
-    ) -> anyhow::Result<NamedPathItem> {
+    ) -> DamlOasResult<NamedPathItem> {
         let template_id = make_template_id(package, module, template);
         let operation_id = self.operation_id_factory.fetch_by_key(&template_id);
         log::info!("encoding fetch by key path for template {} ({})", format_template(template), operation_id);
@@ -250,7 +246,7 @@ impl<'arc> PathItemEncoder<'arc> {
         module: &DamlModule<'_>,
         template: &DamlTemplate<'_>,
         choice: &DamlChoice<'_>,
-    ) -> anyhow::Result<NamedPathItem> {
+    ) -> DamlOasResult<NamedPathItem> {
         let template_id = make_template_id(package, module, template);
         let operation_id = self.operation_id_factory.exercise_by_id(&template_id, choice.name());
         log::info!(
@@ -278,7 +274,7 @@ impl<'arc> PathItemEncoder<'arc> {
         template: &DamlTemplate<'_>,
         choice: &DamlChoice<'_>,
         key: &DamlDefKey<'_>,
-    ) -> anyhow::Result<NamedPathItem> {
+    ) -> DamlOasResult<NamedPathItem> {
         let template_id = make_template_id(package, module, template);
         let operation_id = self.operation_id_factory.exercise_by_key(&template_id, choice.name());
         log::info!(
@@ -307,7 +303,7 @@ impl<'arc> PathItemEncoder<'arc> {
         module: &DamlModule<'_>,
         template: &DamlTemplate<'_>,
         choice: &DamlChoice<'_>,
-    ) -> anyhow::Result<NamedPathItem> {
+    ) -> DamlOasResult<NamedPathItem> {
         let template_id = make_template_id(package, module, template);
         let operation_id = self.operation_id_factory.create_and_exercise(&template_id, choice.name());
         log::info!(
@@ -405,9 +401,9 @@ impl<'arc> PathItemEncoder<'arc> {
         let created = include_creating_template
             .then(|| make_template_id(package, module, template))
             .into_iter()
-            .chain(events.created().map(make_template_id_from_tycon))
+            .chain(events.created().map(make_template_id_from_tycon).sorted())
             .collect::<Vec<_>>();
-        let archived = events.archived().map(make_template_id_from_tycon).collect::<Vec<_>>();
+        let archived = events.archived().map(make_template_id_from_tycon).sorted().collect::<Vec<_>>();
         (created, archived)
     }
 
@@ -436,7 +432,7 @@ impl<'arc> PathItemEncoder<'arc> {
     fn make_json_responses_with_error(&self, success_response: Value) -> Responses {
         Responses {
             default: Some(self.make_json_error_response()),
-            responses: btreemap! { "200".to_string() => Self::make_json_success_response(success_response) },
+            items: btreemap! { "200".to_string() => Self::make_json_success_response(success_response) },
         }
     }
 
@@ -475,19 +471,19 @@ impl<'arc> PathItemEncoder<'arc> {
     fn get_create_description(&self, template_path: &str) -> String {
         self.get_operation_info(template_path)
             .and_then(|op| op.create.clone())
-            .unwrap_or_else(|| format!("Create a contract of the {} template", template_path))
+            .unwrap_or_else(|| format!("Create a contract of the {template_path} template"))
     }
 
     fn get_fetch_by_id_description(&self, template_path: &str) -> String {
         self.get_operation_info(template_path)
             .and_then(|op| op.fetch_by_id.clone())
-            .unwrap_or_else(|| format!("Fetch a contract of the {} template by contract id", template_path))
+            .unwrap_or_else(|| format!("Fetch a contract of the {template_path} template by contract id"))
     }
 
     fn get_fetch_by_key_description(&self, template_path: &str) -> String {
         self.get_operation_info(template_path)
             .and_then(|op| op.fetch_by_key.clone())
-            .unwrap_or_else(|| format!("Fetch a contract of the {} template by contract key", template_path))
+            .unwrap_or_else(|| format!("Fetch a contract of the {template_path} template by contract key"))
     }
 
     fn get_exercise_by_id_description(&self, template_path: &str, choice: &str) -> String {
@@ -495,7 +491,7 @@ impl<'arc> PathItemEncoder<'arc> {
             .and_then(|op| op.exercise_by_id.as_ref())
             .and_then(|choices| choices.get(choice).map(ToString::to_string))
             .unwrap_or_else(|| {
-                format!("Exercise the {} choice on a contract of the {} template by contract id", template_path, choice)
+                format!("Exercise the {template_path} choice on a contract of the {choice} template by contract id")
             })
     }
 
@@ -504,10 +500,7 @@ impl<'arc> PathItemEncoder<'arc> {
             .and_then(|op| op.exercise_by_key.as_ref())
             .and_then(|choices| choices.get(choice).map(ToString::to_string))
             .unwrap_or_else(|| {
-                format!(
-                    "Exercise the {} choice on a contract of the {} template by contract key",
-                    template_path, choice
-                )
+                format!("Exercise the {template_path} choice on a contract of the {choice} template by contract key")
             })
     }
 
@@ -517,8 +510,8 @@ impl<'arc> PathItemEncoder<'arc> {
             .and_then(|choices| choices.get(choice).map(ToString::to_string))
             .unwrap_or_else(|| {
                 format!(
-                    "Create a contract of the {} template and immediately exercise the {} choice on it",
-                    template_path, choice
+                    "Create a contract of the {template_path} template and immediately exercise the {choice} choice \
+                     on it"
                 )
             })
     }
@@ -558,7 +551,7 @@ impl<'arc> PathItemEncoder<'arc> {
     }
 
     fn should_include_archived_choice(&self, choice: &DamlChoice<'_>) -> bool {
-        return self.include_archive_choice || (choice.name() != ARCHIVE_CHOICE_NAME);
+        self.include_archive_choice || (choice.name() != ARCHIVE_CHOICE_NAME)
     }
 
     fn should_include_filtered_choice(
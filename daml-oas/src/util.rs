@@ -1,11 +1,8 @@
 use itertools::Itertools;
-use thiserror::Error;
 
-use daml::lf::element::DamlModule;
+use daml_lf::element::DamlModule;
 
-#[derive(Error, Debug)]
-#[error("required value was not supplied")]
-pub struct RequiredError;
+use crate::error::{RequiredError, UnknownModulePathError};
 
 /// Required value.
 pub trait Required<T> {
@@ -18,10 +15,6 @@ impl<T> Required<T> for Option<T> {
     }
 }
 
-#[derive(Error, Debug)]
-#[error("unknown module path {0}")]
-pub struct UnknownModulePathError(String);
-
 pub trait ChildModulePathOrError {
     fn child_module_path_or_err<S: AsRef<str>>(
         &self,
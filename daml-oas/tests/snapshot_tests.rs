@@ -0,0 +1,101 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::convert::TryFrom;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use daml_json::schema_encoder::{DataDict, ReferenceMode};
+use daml_lf::DarFile;
+use daml_oas::{AsyncApiBuilder, CompanionData, OpenApiBuilder, PathStyle, TemplateFilter, TemplateFilterInput};
+
+static TESTING_TYPES_DAR_PATH: &str = "../resources/testing_types_sandbox/TestingTypes-latest.dar";
+
+/// Set this environment variable to regenerate the snapshot files rather than compare against them.
+static UPDATE_SNAPSHOTS_ENV: &str = "DAML_OAS_UPDATE_SNAPSHOTS";
+
+#[test]
+fn test_openapi_vehicle() -> Result<()> {
+    let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+    let openapi = OpenApiBuilder::default().module_path(["Fuji", "Vehicle"]).build_from_dar(&dar)?;
+    assert_snapshot("openapi_vehicle.json", &openapi)
+}
+
+#[test]
+fn test_openapi_ping_pong_filtered() -> Result<()> {
+    let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+    let openapi = OpenApiBuilder::default()
+        .module_path(["Fuji", "PingPong"])
+        .template_filter(template_filter()?)
+        .companion_data(companion_data()?)
+        .data_dict(data_dict()?)
+        .include_archive_choice(true)
+        .path_style(PathStyle::Slash)
+        .build_from_dar(&dar)?;
+    assert_snapshot("openapi_ping_pong_filtered.json", &openapi)
+}
+
+#[test]
+fn test_openapi_nested_inline() -> Result<()> {
+    let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+    let openapi = OpenApiBuilder::default()
+        .module_path(["Fuji", "Nested"])
+        .reference_mode(ReferenceMode::Inline)
+        .emit_package_id(true)
+        .include_general_operations(true)
+        .build_from_dar(&dar)?;
+    assert_snapshot("openapi_nested_inline.json", &openapi)
+}
+
+#[test]
+fn test_asyncapi_shape() -> Result<()> {
+    let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+    let asyncapi = AsyncApiBuilder::default().module_path(["Fuji", "Shape"]).build_from_dar(&dar)?;
+    assert_snapshot("asyncapi_shape.json", &asyncapi)
+}
+
+#[test]
+fn test_asyncapi_ping_pong_filtered() -> Result<()> {
+    let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+    let asyncapi = AsyncApiBuilder::default()
+        .module_path(["Fuji", "PingPong"])
+        .template_filter(template_filter()?)
+        .companion_data(companion_data()?)
+        .data_dict(data_dict()?)
+        .build_from_dar(&dar)?;
+    assert_snapshot("asyncapi_ping_pong_filtered.json", &asyncapi)
+}
+
+#[test]
+fn test_unknown_module_path() -> Result<()> {
+    let dar = DarFile::from_file(TESTING_TYPES_DAR_PATH)?;
+    let result = OpenApiBuilder::default().module_path(["Fuji", "Unknown"]).build_from_dar(&dar);
+    assert_eq!(result.map(|_| ()).map_err(|e| e.to_string()), Err(String::from("unknown module path Fuji.Unknown")));
+    Ok(())
+}
+
+fn template_filter() -> Result<TemplateFilter> {
+    let input: TemplateFilterInput = serde_yaml::from_str(include_str!("../.template_filter.yaml"))?;
+    Ok(TemplateFilter::try_from(input)?)
+}
+
+fn companion_data() -> Result<CompanionData> {
+    Ok(serde_yaml::from_str(include_str!("../.companion.yaml"))?)
+}
+
+fn data_dict() -> Result<DataDict> {
+    Ok(serde_yaml::from_str(include_str!("../.datadict.yaml"))?)
+}
+
+fn assert_snapshot<T: Serialize>(name: &str, document: &T) -> Result<()> {
+    let path = PathBuf::from("test_resources/snapshots").join(name);
+    let actual = serde_json::to_value(document)?;
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+        std::fs::write(&path, serde_json::to_string_pretty(&actual)? + "\n")?;
+    }
+    let expected: Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    assert_eq!(actual, expected, "snapshot {name} does not match, set {UPDATE_SNAPSHOTS_ENV} to update");
+    Ok(())
+}
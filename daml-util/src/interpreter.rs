@@ -0,0 +1,333 @@
+//! An interpreter for `Daml-LF` expressions.
+//!
+//! The [`DamlInterpreter`] evaluates the pure value definitions of a [`DamlArchive`] and simulates the effect of
+//! ledger commands against a [`DamlContractStore`] without requiring a ledger.  This allows, for example, the result
+//! of exercising a choice to be previewed before the command is submitted.
+
+mod builtin;
+mod convert;
+mod error;
+mod eval;
+mod ledger;
+mod primitive;
+mod types;
+mod value;
+
+use crate::interpreter::convert::{from_daml_value, micros_from_timestamp, to_daml_value};
+use crate::interpreter::eval::{unhandled, Machine};
+pub use crate::interpreter::eval::{DEFAULT_MAX_DEPTH, DEFAULT_MAX_STEPS};
+use chrono::{DateTime, Utc};
+use daml_grpc::data::command::DamlCommand;
+use daml_grpc::data::value::DamlValue;
+use daml_lf::element::{DamlArchive, DamlDefValue, DamlType};
+pub use error::{DamlInterpreterError, DamlInterpreterResult};
+pub use ledger::{DamlContractStore, DamlSimulatedEvent, DamlSimulation, DamlStoredContract};
+
+/// Evaluate `Daml-LF` values and simulate commands over a [`DamlArchive`].
+///
+/// Value definitions are evaluated at most once and their results shared between calls.
+///
+/// Commands are simulated as a single transaction which is either applied in full or not at all.  The simulation
+/// checks that contracts are active, that contract keys are unique and that template preconditions hold but does not
+/// check that the transaction is authorized by the submitting parties.
+///
+/// Evaluation is strict and is bounded by a maximum depth of nested evaluation and a maximum number of evaluation
+/// steps per call, see [`DamlInterpreter::with_max_depth`] and [`DamlInterpreter::with_max_steps`].  The default
+/// depth of [`DEFAULT_MAX_DEPTH`] is safe for a thread with a stack of 2MB.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use daml_lf::DarFile;
+/// # use daml_util::interpreter::{DamlContractStore, DamlInterpreter};
+/// # use daml_grpc::data::command::{DamlCommand, DamlExerciseCommand};
+/// # use daml_grpc::data::DamlIdentifier;
+/// # use daml_grpc::data::value::{DamlRecord, DamlValue};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let dar = DarFile::from_file("Example.dar")?;
+/// dar.apply(|archive| {
+///     let interpreter = DamlInterpreter::new(archive);
+///     let store = DamlContractStore::new();
+///     let template_id = DamlIdentifier::new(archive.main_package_id(), "Fuji.PingPong", "Ping");
+///     let command = DamlExerciseCommand::new(
+///         template_id,
+///         "#0:0",
+///         "RespondPong",
+///         DamlValue::new_record(DamlRecord::empty()),
+///     );
+///     let simulation = interpreter.simulate(&store, &DamlCommand::Exercise(command));
+///     println!("{:?}", simulation);
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DamlInterpreter<'a> {
+    machine: Machine<'a>,
+    ledger_time: DateTime<Utc>,
+}
+
+impl<'a> DamlInterpreter<'a> {
+    /// Create an interpreter for the given [`DamlArchive`] which uses the current time as the ledger time.
+    pub fn new(archive: &'a DamlArchive<'a>) -> Self {
+        Self {
+            machine: Machine::new(archive),
+            ledger_time: Utc::now(),
+        }
+    }
+
+    /// Set the ledger time returned by `getTime` within simulated commands.
+    #[must_use]
+    pub fn with_ledger_time(self, ledger_time: DateTime<Utc>) -> Self {
+        Self {
+            ledger_time,
+            ..self
+        }
+    }
+
+    /// Set the maximum depth of nested evaluation, beyond which evaluation fails with
+    /// [`DamlInterpreterError::DepthLimitExceeded`].
+    ///
+    /// A greater depth than [`DEFAULT_MAX_DEPTH`] requires a correspondingly larger stack.
+    #[must_use]
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self {
+            machine: self.machine.with_max_depth(max_depth),
+            ..self
+        }
+    }
+
+    /// Set the maximum number of evaluation steps for each call, beyond which evaluation fails with
+    /// [`DamlInterpreterError::StepLimitExceeded`].
+    #[must_use]
+    pub fn with_max_steps(self, max_steps: u64) -> Self {
+        Self {
+            machine: self.machine.with_max_steps(max_steps),
+            ..self
+        }
+    }
+
+    /// Evaluate the value definition `name` in the given package and module.
+    ///
+    /// The value must be serializable, a function should instead be invoked with [`DamlInterpreter::call`].
+    pub fn evaluate<S: AsRef<str>>(
+        &self,
+        package_id: &str,
+        module_path: &[S],
+        name: &str,
+    ) -> DamlInterpreterResult<DamlValue> {
+        let def = self.value_def(package_id, module_path, name)?;
+        self.machine.reset_steps();
+        to_daml_value(&self.machine.global_def(def)?)
+    }
+
+    /// Apply the function value definition `name` in the given package and module to the supplied arguments.
+    ///
+    /// The function must not be polymorphic and the arguments and result must be serializable.
+    pub fn call<S: AsRef<str>>(
+        &self,
+        package_id: &str,
+        module_path: &[S],
+        name: &str,
+        args: &[DamlValue],
+    ) -> DamlInterpreterResult<DamlValue> {
+        let def = self.value_def(package_id, module_path, name)?;
+        let mut ty = def.ty();
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            match ty {
+                DamlType::Arrow(arrow) if arrow.len() == 2 => {
+                    values.push(from_daml_value(self.machine.archive(), arg, &arrow[0])?);
+                    ty = &arrow[1];
+                },
+                DamlType::Forall(_) =>
+                    return Err(DamlInterpreterError::Unsupported(format!("calling polymorphic function {name}"))),
+                _ =>
+                    return Err(DamlInterpreterError::UnexpectedValue(
+                        format!("a function of at least {} arguments", args.len()),
+                        name.to_owned(),
+                    )),
+            }
+        }
+        self.machine.reset_steps();
+        let fun = self.machine.global_def(def)?;
+        let result = self.machine.apply(fun, values).map_err(unhandled)?;
+        to_daml_value(&result)
+    }
+
+    /// Simulate submitting a command against the contracts of a [`DamlContractStore`].
+    ///
+    /// The store is not modified, see [`DamlInterpreter::submit`].
+    pub fn simulate(&self, store: &DamlContractStore, command: &DamlCommand) -> DamlInterpreterResult<DamlSimulation> {
+        self.machine.reset_steps();
+        ledger::simulate(&self.machine, store, micros_from_timestamp(&self.ledger_time), command)
+    }
+
+    /// Simulate submitting a command and apply the resulting events to the [`DamlContractStore`].
+    pub fn submit(
+        &self,
+        store: &mut DamlContractStore,
+        command: &DamlCommand,
+    ) -> DamlInterpreterResult<DamlSimulation> {
+        let simulation = self.simulate(store, command)?;
+        store.apply(&simulation);
+        Ok(simulation)
+    }
+
+    fn value_def<S: AsRef<str>>(
+        &self,
+        package_id: &str,
+        module_path: &[S],
+        name: &str,
+    ) -> DamlInterpreterResult<&'a DamlDefValue<'a>> {
+        self.machine.archive().value(package_id, module_path, name).ok_or_else(|| {
+            let module = module_path.iter().map(AsRef::as_ref).collect::<Vec<_>>().join(".");
+            DamlInterpreterError::UnknownValue(format!("{module}:{name}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interpreter::{DamlContractStore, DamlInterpreter, DamlInterpreterError, DamlSimulatedEvent};
+    use chrono::{NaiveDate, Utc};
+    use daml_grpc::data::command::{DamlCommand, DamlCreateAndExerciseCommand, DamlCreateCommand, DamlExerciseCommand};
+    use daml_grpc::data::event::DamlArchivedEvent;
+    use daml_grpc::data::value::{DamlRecordBuilder, DamlValue};
+    use daml_grpc::data::DamlIdentifier;
+    use daml_grpc::primitive_types::DamlDate;
+    use daml_lf::{DamlLfResult, DarFile};
+    use std::error::Error;
+
+    type TestResult = Result<(), Box<dyn Error>>;
+
+    fn with_archive<F>(f: F) -> TestResult
+    where
+        F: FnOnce(&DamlInterpreter<'_>, &str) -> TestResult,
+    {
+        let dar = DarFile::from_file("../resources/testing_types_sandbox/TestingTypes-latest.dar")?;
+        let result: DamlLfResult<TestResult> =
+            dar.apply(|archive| f(&DamlInterpreter::new(archive), archive.main_package_id()));
+        result?
+    }
+
+    fn ping(package_id: &str, count: i64) -> DamlCreateCommand {
+        let arguments = DamlRecordBuilder::new()
+            .add_field("sender", DamlValue::new_party("Alice"))
+            .add_field("receiver", DamlValue::new_party("Bob"))
+            .add_field("count", DamlValue::new_int64(count))
+            .build();
+        DamlCreateCommand::new(DamlIdentifier::new(package_id, "Fuji.PingPong", "Ping"), arguments)
+    }
+
+    #[test]
+    fn test_call_pure_function() -> TestResult {
+        with_archive(|interpreter, package_id| {
+            let result = interpreter.call(package_id, &["Fuji", "Vehicle"], "runFoo", &[DamlValue::Unit])?;
+            assert_eq!(NaiveDate::from_ymd_opt(2019, 1, 1), Some(result.try_date()?.naive_utc()));
+            let date = DamlDate::from_utc(NaiveDate::from_ymd_opt(2021, 6, 30).ok_or("invalid date")?, Utc);
+            let result = interpreter.call(package_id, &["Fuji", "Vehicle"], "foo", &[DamlValue::new_date(date)])?;
+            assert_eq!(date, result.try_date()?);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_unknown_value() -> TestResult {
+        with_archive(|interpreter, package_id| {
+            let result = interpreter.evaluate(package_id, &["Fuji", "Vehicle"], "unknown");
+            assert!(matches!(result, Err(DamlInterpreterError::UnknownValue(_))));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_step_limit_exceeded() -> TestResult {
+        let dar = DarFile::from_file("../resources/testing_types_sandbox/TestingTypes-latest.dar")?;
+        dar.apply(|archive| {
+            let interpreter = DamlInterpreter::new(archive).with_max_steps(3);
+            let result =
+                interpreter.call(archive.main_package_id(), &["Fuji", "Vehicle"], "runFoo", &[DamlValue::Unit]);
+            assert_eq!(Err(DamlInterpreterError::StepLimitExceeded(3)), result);
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_depth_limit_exceeded() -> TestResult {
+        let dar = DarFile::from_file("../resources/testing_types_sandbox/TestingTypes-latest.dar")?;
+        dar.apply(|archive| {
+            let interpreter = DamlInterpreter::new(archive).with_max_depth(2);
+            let result = interpreter
+                .simulate(&DamlContractStore::new(), &DamlCommand::Create(ping(archive.main_package_id(), 0)));
+            assert_eq!(Err(DamlInterpreterError::DepthLimitExceeded(2)), result.map(|_| ()));
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_exercise() -> TestResult {
+        with_archive(|interpreter, package_id| {
+            let mut store = DamlContractStore::new();
+            let created = interpreter.submit(&mut store, &DamlCommand::Create(ping(package_id, 0)))?;
+            let ping_id = created.result().try_contract_id()?.clone();
+            assert_eq!(1, store.len());
+            let exercise = DamlExerciseCommand::new(
+                DamlIdentifier::new(package_id, "Fuji.PingPong", "Ping"),
+                ping_id.as_str(),
+                "RespondPong",
+                DamlValue::new_record(DamlRecordBuilder::new().build()),
+            );
+            let simulation = interpreter.simulate(&store, &DamlCommand::Exercise(exercise))?;
+            assert_eq!(&DamlValue::Unit, simulation.result());
+            assert!(matches!(simulation.events()[0], DamlSimulatedEvent::Exercised(_)));
+            let exercised = simulation.exercised().collect::<Vec<_>>();
+            assert_eq!(1, exercised.len());
+            assert_eq!("RespondPong", exercised[0].choice());
+            assert_eq!(
+                vec![ping_id.as_str()],
+                simulation.archived().map(DamlArchivedEvent::contract_id).collect::<Vec<_>>()
+            );
+            let pong = simulation.created().next().ok_or("expected a created event")?;
+            assert_eq!("Pong", pong.template_id().entity_name());
+            assert_eq!(&DamlValue::new_party("Bob"), pong.create_arguments().field("sender")?);
+            assert_eq!(&DamlValue::new_int64(1), pong.create_arguments().field("count")?);
+            assert_eq!(1, store.len());
+            store.apply(&simulation);
+            assert!(store.contract(ping_id.as_str()).is_none());
+            assert!(store.contract(pong.contract_id()).is_some());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_simulate_duplicate_key() -> TestResult {
+        with_archive(|interpreter, package_id| {
+            let mut store = DamlContractStore::new();
+            interpreter.submit(&mut store, &DamlCommand::Create(ping(package_id, 0)))?;
+            let result = interpreter.simulate(&store, &DamlCommand::Create(ping(package_id, 0)));
+            assert!(result.is_err());
+            assert_eq!(1, store.len());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_simulate_recreate_key() -> TestResult {
+        with_archive(|interpreter, package_id| {
+            let mut store = DamlContractStore::new();
+            let command = DamlCreateAndExerciseCommand::new(
+                DamlIdentifier::new(package_id, "Fuji.PingPong", "Ping"),
+                ping(package_id, 3).create_arguments().clone(),
+                "ResetPingCount",
+                DamlValue::new_record(DamlRecordBuilder::new().build()),
+            );
+            let simulation = interpreter.submit(&mut store, &DamlCommand::CreateAndExercise(command))?;
+            assert_eq!(2, simulation.created().count());
+            assert_eq!(1, simulation.archived().count());
+            assert_eq!(1, store.len());
+            let remaining = store.contracts().next().ok_or("expected a contract")?;
+            assert_eq!(&DamlValue::new_int64(3), remaining.arguments().field("count")?);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,593 @@
+use crate::interpreter::convert::binary_search;
+use crate::interpreter::error::DamlInterpreterError;
+use crate::interpreter::eval::{exception_message, Eval, Failure, Machine};
+use crate::interpreter::primitive;
+use crate::interpreter::types::type_eq;
+use crate::interpreter::value::{compare, Value, ValueList};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use daml_lf::element::{DamlBuiltinFunction, DamlType, RoundingMode};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::fmt::Write;
+use std::rc::Rc;
+
+/// The scale of the legacy `Decimal` type.
+const DECIMAL_SCALE: i64 = 10;
+
+/// The maximum length of a `Party` identifier.
+const MAX_PARTY_LENGTH: usize = 255;
+
+/// The number of value arguments taken by a builtin function.
+#[allow(clippy::match_same_arms)]
+pub const fn arity(builtin: &DamlBuiltinFunction) -> usize {
+    match builtin {
+        DamlBuiltinFunction::TextmapEmpty | DamlBuiltinFunction::GenmapEmpty => 0,
+        DamlBuiltinFunction::CastNumeric
+        | DamlBuiltinFunction::ShiftNumeric
+        | DamlBuiltinFunction::TextmapToList
+        | DamlBuiltinFunction::TextmapSize
+        | DamlBuiltinFunction::ExplodeText
+        | DamlBuiltinFunction::Error
+        | DamlBuiltinFunction::AnyExceptionMessage
+        | DamlBuiltinFunction::Int64ToText
+        | DamlBuiltinFunction::DecimalToText
+        | DamlBuiltinFunction::NumericToText
+        | DamlBuiltinFunction::TextToText
+        | DamlBuiltinFunction::TimestampToText
+        | DamlBuiltinFunction::DateToText
+        | DamlBuiltinFunction::PartyToQuotedText
+        | DamlBuiltinFunction::PartyToText
+        | DamlBuiltinFunction::TextToParty
+        | DamlBuiltinFunction::TextToInt64
+        | DamlBuiltinFunction::TextToDecimal
+        | DamlBuiltinFunction::TextToNumeric
+        | DamlBuiltinFunction::ContractIdToText
+        | DamlBuiltinFunction::Sha256Text
+        | DamlBuiltinFunction::DateToUnixDays
+        | DamlBuiltinFunction::UnixDaysToDate
+        | DamlBuiltinFunction::TimestampToUnixMicroseconds
+        | DamlBuiltinFunction::UnixMicrosecondsToTimestamp
+        | DamlBuiltinFunction::Int64ToDecimal
+        | DamlBuiltinFunction::DecimalToInt64
+        | DamlBuiltinFunction::Int64ToNumeric
+        | DamlBuiltinFunction::NumericToInt64
+        | DamlBuiltinFunction::ImplodeText
+        | DamlBuiltinFunction::CoerceContractId
+        | DamlBuiltinFunction::CodePointsToText
+        | DamlBuiltinFunction::TextPointsToCode
+        | DamlBuiltinFunction::ScaleBignumeric
+        | DamlBuiltinFunction::PrecisionBignumeric
+        | DamlBuiltinFunction::BigNumericToNumeric
+        | DamlBuiltinFunction::NumericToBigNumeric
+        | DamlBuiltinFunction::BigNumericToText
+        | DamlBuiltinFunction::GenmapKeys
+        | DamlBuiltinFunction::GenmapValues
+        | DamlBuiltinFunction::GenmapSize => 1,
+        DamlBuiltinFunction::Foldl
+        | DamlBuiltinFunction::Foldr
+        | DamlBuiltinFunction::TextmapInsert
+        | DamlBuiltinFunction::GenmapInsert
+        | DamlBuiltinFunction::EqualList => 3,
+        DamlBuiltinFunction::DivBignumeric => 4,
+        _ => 2,
+    }
+}
+
+/// Does the builtin function require its type arguments at runtime?
+pub const fn needs_types(builtin: &DamlBuiltinFunction) -> bool {
+    matches!(
+        builtin,
+        DamlBuiltinFunction::MulNumeric
+            | DamlBuiltinFunction::DivNumeric
+            | DamlBuiltinFunction::CastNumeric
+            | DamlBuiltinFunction::ShiftNumeric
+            | DamlBuiltinFunction::TextToNumeric
+            | DamlBuiltinFunction::Int64ToNumeric
+            | DamlBuiltinFunction::BigNumericToNumeric
+    )
+}
+
+impl<'a> Machine<'a> {
+    /// Call a fully applied builtin function.
+    #[allow(clippy::too_many_lines)]
+    pub fn call_builtin(
+        &self,
+        builtin: &DamlBuiltinFunction,
+        types: &[DamlType<'a>],
+        args: &[Value<'a>],
+    ) -> Eval<'a, Value<'a>> {
+        let arg = |i: usize| &args[i];
+        Ok(match builtin {
+            DamlBuiltinFunction::AddDecimal | DamlBuiltinFunction::AddNumeric => {
+                let (x, y) = (arg(0).as_numeric()?, arg(1).as_numeric()?);
+                Value::Numeric(self.numeric(&(x + y), primitive::scale(x), "ADD_NUMERIC")?)
+            },
+            DamlBuiltinFunction::SubDecimal | DamlBuiltinFunction::SubNumeric => {
+                let (x, y) = (arg(0).as_numeric()?, arg(1).as_numeric()?);
+                Value::Numeric(self.numeric(&(x - y), primitive::scale(x), "SUB_NUMERIC")?)
+            },
+            DamlBuiltinFunction::MulDecimal | DamlBuiltinFunction::MulNumeric => {
+                let scale = result_scale(builtin, types, 2)?;
+                let product = arg(0).as_numeric()? * arg(1).as_numeric()?;
+                let rounded = primitive::round(&product, scale, &RoundingMode::HalfEven).unwrap_or(product);
+                Value::Numeric(self.numeric(&rounded, scale, "MUL_NUMERIC")?)
+            },
+            DamlBuiltinFunction::DivDecimal | DamlBuiltinFunction::DivNumeric => {
+                let scale = result_scale(builtin, types, 2)?;
+                let quotient =
+                    primitive::divide(arg(0).as_numeric()?, arg(1).as_numeric()?, scale, &RoundingMode::HalfEven)
+                        .ok_or_else(|| self.arithmetic_error("DIV_NUMERIC: division by zero"))?;
+                Value::Numeric(self.numeric(&quotient, scale, "DIV_NUMERIC")?)
+            },
+            DamlBuiltinFunction::RoundDecimal | DamlBuiltinFunction::RoundNumeric => {
+                let places = arg(0).as_int64()?;
+                let x = arg(1).as_numeric()?;
+                let rounded = primitive::round_numeric(x, places).unwrap_or_else(|| x.clone());
+                Value::Numeric(self.numeric(&rounded, primitive::scale(x), "ROUND_NUMERIC")?)
+            },
+            DamlBuiltinFunction::CastNumeric => {
+                let scale = result_scale(builtin, types, 1)?;
+                Value::Numeric(self.numeric(arg(0).as_numeric()?, scale, "CAST_NUMERIC")?)
+            },
+            DamlBuiltinFunction::ShiftNumeric => {
+                let scale = result_scale(builtin, types, 1)?;
+                let (digits, _) = arg(0).as_numeric()?.as_bigint_and_exponent();
+                Value::Numeric(BigDecimal::new(digits, scale))
+            },
+            DamlBuiltinFunction::AddInt64 =>
+                self.int64(arg(0).as_int64()?.checked_add(arg(1).as_int64()?), "ADD_INT64")?,
+            DamlBuiltinFunction::SubInt64 =>
+                self.int64(arg(0).as_int64()?.checked_sub(arg(1).as_int64()?), "SUB_INT64")?,
+            DamlBuiltinFunction::MulInt64 =>
+                self.int64(arg(0).as_int64()?.checked_mul(arg(1).as_int64()?), "MUL_INT64")?,
+            DamlBuiltinFunction::DivInt64 =>
+                self.int64(arg(0).as_int64()?.checked_div(arg(1).as_int64()?), "DIV_INT64")?,
+            DamlBuiltinFunction::ModInt64 =>
+                self.int64(arg(0).as_int64()?.checked_rem(arg(1).as_int64()?), "MOD_INT64")?,
+            DamlBuiltinFunction::ExpInt64 => {
+                let (base, exponent) = (arg(0).as_int64()?, arg(1).as_int64()?);
+                self.int64(u32::try_from(exponent).ok().and_then(|exponent| base.checked_pow(exponent)), "EXP_INT64")?
+            },
+            DamlBuiltinFunction::Foldl => {
+                let mut acc = arg(1).clone();
+                for item in arg(2).as_list()?.iter() {
+                    acc = self.apply(arg(0).clone(), vec![acc, item.clone()])?;
+                }
+                acc
+            },
+            DamlBuiltinFunction::Foldr => {
+                let mut acc = arg(1).clone();
+                for item in arg(2).as_list()?.iter().rev() {
+                    acc = self.apply(arg(0).clone(), vec![item.clone(), acc])?;
+                }
+                acc
+            },
+            DamlBuiltinFunction::TextmapEmpty => Value::TextMap(Rc::default()),
+            DamlBuiltinFunction::TextmapInsert => {
+                let mut map = arg(2).as_text_map()?.as_ref().clone();
+                map.insert(arg(0).as_text()?.clone(), arg(1).clone());
+                Value::TextMap(Rc::new(map))
+            },
+            DamlBuiltinFunction::TextmapLookup =>
+                Value::optional(arg(1).as_text_map()?.get(arg(0).as_text()?.as_ref()).cloned()),
+            DamlBuiltinFunction::TextmapDelete => {
+                let mut map = arg(1).as_text_map()?.as_ref().clone();
+                map.remove(arg(0).as_text()?.as_ref());
+                Value::TextMap(Rc::new(map))
+            },
+            DamlBuiltinFunction::TextmapToList => Value::List(ValueList::new(
+                arg(0)
+                    .as_text_map()?
+                    .iter()
+                    .map(|(k, v)| Value::Struct(Rc::new(vec![("key", Value::Text(k.clone())), ("value", v.clone())])))
+                    .collect(),
+            )),
+            DamlBuiltinFunction::TextmapSize => Value::Int64(len(arg(0).as_text_map()?.len())),
+            DamlBuiltinFunction::ExplodeText =>
+                Value::List(ValueList::new(arg(0).as_text()?.chars().map(|c| Value::text(c.to_string())).collect())),
+            DamlBuiltinFunction::AppendText => Value::text(format!("{}{}", arg(0).as_text()?, arg(1).as_text()?)),
+            DamlBuiltinFunction::ImplodeText => Value::text(
+                arg(0).as_list()?.iter().map(|t| t.as_text().map(AsRef::as_ref)).collect::<Result<String, _>>()?,
+            ),
+            DamlBuiltinFunction::Error =>
+                return Err(Failure::Error(DamlInterpreterError::UserError(arg(0).as_text()?.to_string()))),
+            DamlBuiltinFunction::AnyExceptionMessage => match arg(0) {
+                Value::AnyException(exception) => Value::text(exception_message(&exception.1)),
+                other => return other.unexpected("AnyException").map_err(Failure::from),
+            },
+            DamlBuiltinFunction::LeqInt64
+            | DamlBuiltinFunction::LeqDecimal
+            | DamlBuiltinFunction::LeqNumeric
+            | DamlBuiltinFunction::LeqText
+            | DamlBuiltinFunction::LeqTimestamp
+            | DamlBuiltinFunction::LeqDate
+            | DamlBuiltinFunction::LeqParty
+            | DamlBuiltinFunction::LessEq => Value::Bool(compare(arg(0), arg(1))? != Ordering::Greater),
+            DamlBuiltinFunction::LessInt64
+            | DamlBuiltinFunction::LessDecimal
+            | DamlBuiltinFunction::LessNumeric
+            | DamlBuiltinFunction::LessText
+            | DamlBuiltinFunction::LessTimestamp
+            | DamlBuiltinFunction::LessDate
+            | DamlBuiltinFunction::LessParty
+            | DamlBuiltinFunction::Less => Value::Bool(compare(arg(0), arg(1))? == Ordering::Less),
+            DamlBuiltinFunction::GeqInt64
+            | DamlBuiltinFunction::GeqDecimal
+            | DamlBuiltinFunction::GeqNumeric
+            | DamlBuiltinFunction::GeqText
+            | DamlBuiltinFunction::GeqTimestamp
+            | DamlBuiltinFunction::GeqDate
+            | DamlBuiltinFunction::GeqParty
+            | DamlBuiltinFunction::GreaterEq => Value::Bool(compare(arg(0), arg(1))? != Ordering::Less),
+            DamlBuiltinFunction::GreaterInt64
+            | DamlBuiltinFunction::GreaterDecimal
+            | DamlBuiltinFunction::GreaterNumeric
+            | DamlBuiltinFunction::GreaterText
+            | DamlBuiltinFunction::GreaterTimestamp
+            | DamlBuiltinFunction::GreaterDate
+            | DamlBuiltinFunction::GreaterParty
+            | DamlBuiltinFunction::Greater => Value::Bool(compare(arg(0), arg(1))? == Ordering::Greater),
+            DamlBuiltinFunction::EqualInt64
+            | DamlBuiltinFunction::EqualDecimal
+            | DamlBuiltinFunction::EqualNumeric
+            | DamlBuiltinFunction::EqualText
+            | DamlBuiltinFunction::EqualTimestamp
+            | DamlBuiltinFunction::EqualDate
+            | DamlBuiltinFunction::EqualParty
+            | DamlBuiltinFunction::EqualBool
+            | DamlBuiltinFunction::EqualContractId
+            | DamlBuiltinFunction::Equal => Value::Bool(compare(arg(0), arg(1))? == Ordering::Equal),
+            DamlBuiltinFunction::EqualTypeRep => Value::Bool(type_eq(arg(0).as_type_rep()?, arg(1).as_type_rep()?)),
+            DamlBuiltinFunction::EqualList => {
+                let (left, right) = (arg(1).as_list()?, arg(2).as_list()?);
+                let mut equal = left.len() == right.len();
+                for (l, r) in left.iter().zip(right.iter()) {
+                    if !equal {
+                        break;
+                    }
+                    equal = self.apply(arg(0).clone(), vec![l.clone(), r.clone()])?.as_bool()?;
+                }
+                Value::Bool(equal)
+            },
+            DamlBuiltinFunction::Int64ToText => Value::text(arg(0).as_int64()?.to_string()),
+            DamlBuiltinFunction::DecimalToText | DamlBuiltinFunction::NumericToText =>
+                Value::text(primitive::to_text(arg(0).as_numeric()?)),
+            DamlBuiltinFunction::TextToText | DamlBuiltinFunction::CoerceContractId => arg(0).clone(),
+            DamlBuiltinFunction::TimestampToText => Value::text(
+                primitive::timestamp_to_text(arg(0).as_timestamp()?)
+                    .ok_or_else(|| self.arithmetic_error("TIMESTAMP_TO_TEXT"))?,
+            ),
+            DamlBuiltinFunction::DateToText => Value::text(
+                primitive::date_to_text(arg(0).as_date()?).ok_or_else(|| self.arithmetic_error("DATE_TO_TEXT"))?,
+            ),
+            DamlBuiltinFunction::PartyToQuotedText => Value::text(format!("'{}'", arg(0).as_party()?)),
+            DamlBuiltinFunction::PartyToText => Value::Text(arg(0).as_party()?.clone()),
+            DamlBuiltinFunction::TextToParty => {
+                let text = arg(0).as_text()?;
+                Value::optional(is_party(text).then(|| Value::Party(text.clone())))
+            },
+            DamlBuiltinFunction::TextToInt64 => {
+                let text = arg(0).as_text()?;
+                let valid =
+                    !text.is_empty() && text.strip_prefix('-').unwrap_or(text).bytes().all(|b| b.is_ascii_digit());
+                Value::optional(valid.then(|| text.parse::<i64>().ok()).flatten().map(Value::Int64))
+            },
+            DamlBuiltinFunction::TextToDecimal =>
+                Value::optional(primitive::from_text(arg(0).as_text()?, DECIMAL_SCALE).map(Value::Numeric)),
+            DamlBuiltinFunction::TextToNumeric => {
+                let scale = result_scale(builtin, types, 0)?;
+                Value::optional(primitive::from_text(arg(0).as_text()?, scale).map(Value::Numeric))
+            },
+            DamlBuiltinFunction::ContractIdToText => Value::Optional(None),
+            DamlBuiltinFunction::Sha256Text =>
+                Value::text(Sha256::digest(arg(0).as_text()?.as_bytes()).iter().fold(String::new(), |mut hex, b| {
+                    let _ = write!(hex, "{b:02x}");
+                    hex
+                })),
+            DamlBuiltinFunction::DateToUnixDays => Value::Int64(i64::from(arg(0).as_date()?)),
+            DamlBuiltinFunction::UnixDaysToDate => Value::Date(
+                primitive::check_date(arg(0).as_int64()?).ok_or_else(|| self.arithmetic_error("UNIX_DAYS_TO_DATE"))?,
+            ),
+            DamlBuiltinFunction::TimestampToUnixMicroseconds => Value::Int64(arg(0).as_timestamp()?),
+            DamlBuiltinFunction::UnixMicrosecondsToTimestamp => Value::Timestamp(
+                primitive::check_timestamp(arg(0).as_int64()?)
+                    .ok_or_else(|| self.arithmetic_error("UNIX_MICROSECONDS_TO_TIMESTAMP"))?,
+            ),
+            DamlBuiltinFunction::Int64ToDecimal | DamlBuiltinFunction::Int64ToNumeric => {
+                let scale = result_scale(builtin, types, 0)?;
+                Value::Numeric(self.numeric(&BigDecimal::from(arg(0).as_int64()?), scale, "INT64_TO_NUMERIC")?)
+            },
+            DamlBuiltinFunction::DecimalToInt64 | DamlBuiltinFunction::NumericToInt64 => {
+                let truncated = primitive::round(arg(0).as_numeric()?, 0, &RoundingMode::Down)
+                    .and_then(|n| n.to_i64())
+                    .ok_or_else(|| self.arithmetic_error("NUMERIC_TO_INT64: overflow"))?;
+                Value::Int64(truncated)
+            },
+            DamlBuiltinFunction::Trace => arg(1).clone(),
+            DamlBuiltinFunction::CodePointsToText => Value::text(
+                arg(0)
+                    .as_list()?
+                    .iter()
+                    .map(|c| {
+                        let code = c.as_int64()?;
+                        u32::try_from(code).ok().and_then(char::from_u32).ok_or_else(|| {
+                            self.arithmetic_error(format!("CODE_POINTS_TO_TEXT: invalid code point {code}"))
+                        })
+                    })
+                    .collect::<Eval<'a, String>>()?,
+            ),
+            DamlBuiltinFunction::TextPointsToCode => Value::List(ValueList::new(
+                arg(0).as_text()?.chars().map(|c| Value::Int64(i64::from(u32::from(c)))).collect(),
+            )),
+            DamlBuiltinFunction::ScaleBignumeric => Value::Int64(primitive::scale(arg(0).as_big_numeric()?)),
+            DamlBuiltinFunction::PrecisionBignumeric => {
+                let value = arg(0).as_big_numeric()?;
+                Value::Int64(len(usize::try_from(value.digits()).unwrap_or(usize::MAX)).max(primitive::scale(value)))
+            },
+            DamlBuiltinFunction::AddBignumeric =>
+                Value::BigNumeric(arg(0).as_big_numeric()? + arg(1).as_big_numeric()?),
+            DamlBuiltinFunction::SubBignumeric =>
+                Value::BigNumeric(arg(0).as_big_numeric()? - arg(1).as_big_numeric()?),
+            DamlBuiltinFunction::MulBignumeric =>
+                Value::BigNumeric(arg(0).as_big_numeric()? * arg(1).as_big_numeric()?),
+            DamlBuiltinFunction::DivBignumeric => {
+                let (scale, mode) = (arg(0).as_int64()?, arg(1).as_rounding_mode()?);
+                Value::BigNumeric(
+                    primitive::divide(arg(2).as_big_numeric()?, arg(3).as_big_numeric()?, scale, mode).ok_or_else(
+                        || self.arithmetic_error("DIV_BIGNUMERIC: division by zero or rounding required"),
+                    )?,
+                )
+            },
+            DamlBuiltinFunction::ShiftBignumeric | DamlBuiltinFunction::ShiftRightBignumeric => {
+                let (digits, scale) = arg(1).as_big_numeric()?.as_bigint_and_exponent();
+                Value::BigNumeric(BigDecimal::new(digits, scale + arg(0).as_int64()?))
+            },
+            DamlBuiltinFunction::BigNumericToNumeric => {
+                let scale = result_scale(builtin, types, 0)?;
+                Value::Numeric(self.numeric(arg(0).as_big_numeric()?, scale, "BIGNUMERIC_TO_NUMERIC")?)
+            },
+            DamlBuiltinFunction::NumericToBigNumeric => Value::BigNumeric(arg(0).as_numeric()?.clone()),
+            DamlBuiltinFunction::BigNumericToText => Value::text(primitive::to_text(arg(0).as_big_numeric()?)),
+            DamlBuiltinFunction::GenmapEmpty => Value::GenMap(Rc::default()),
+            DamlBuiltinFunction::GenmapInsert => {
+                let mut entries = arg(2).as_gen_map()?.as_ref().clone();
+                match binary_search(&entries, arg(0))? {
+                    Ok(index) => entries[index].1 = arg(1).clone(),
+                    Err(index) => entries.insert(index, (arg(0).clone(), arg(1).clone())),
+                }
+                Value::GenMap(Rc::new(entries))
+            },
+            DamlBuiltinFunction::GenmapLookup => {
+                let entries = arg(1).as_gen_map()?;
+                Value::optional(binary_search(entries, arg(0))?.ok().map(|index| entries[index].1.clone()))
+            },
+            DamlBuiltinFunction::GenmapDelete => {
+                let mut entries = arg(1).as_gen_map()?.as_ref().clone();
+                if let Ok(index) = binary_search(&entries, arg(0))? {
+                    entries.remove(index);
+                }
+                Value::GenMap(Rc::new(entries))
+            },
+            DamlBuiltinFunction::GenmapKeys =>
+                Value::List(ValueList::new(arg(0).as_gen_map()?.iter().map(|(k, _)| k.clone()).collect())),
+            DamlBuiltinFunction::GenmapValues =>
+                Value::List(ValueList::new(arg(0).as_gen_map()?.iter().map(|(_, v)| v.clone()).collect())),
+            DamlBuiltinFunction::GenmapSize => Value::Int64(len(arg(0).as_gen_map()?.len())),
+        })
+    }
+
+    /// Returns `value` if it is a valid `Numeric` of the given scale, otherwise raises an `ArithmeticError`.
+    fn numeric(&self, value: &BigDecimal, scale: i64, name: &str) -> Eval<'a, BigDecimal> {
+        primitive::check_numeric(value, scale)
+            .ok_or_else(|| self.arithmetic_error(format!("{name}: numeric overflow or loss of precision")))
+    }
+
+    /// Returns an `Int64` value if the operation succeeded, otherwise raises an `ArithmeticError`.
+    fn int64(&self, value: Option<i64>, name: &str) -> Eval<'a, Value<'a>> {
+        value.map(Value::Int64).ok_or_else(|| self.arithmetic_error(format!("{name}: overflow or division by zero")))
+    }
+}
+
+/// The scale of the result of a numeric builtin, given by the `Nat` type argument at `index`.
+fn result_scale(builtin: &DamlBuiltinFunction, types: &[DamlType<'_>], index: usize) -> Eval<'static, i64> {
+    if !needs_types(builtin) {
+        return Ok(DECIMAL_SCALE);
+    }
+    match types.get(index) {
+        Some(DamlType::Nat(scale)) => Ok(i64::from(*scale)),
+        _ => Err(Failure::Error(DamlInterpreterError::UnexpectedValue(
+            format!("a Nat type argument for {builtin:?}"),
+            format!("{} type arguments", types.len()),
+        ))),
+    }
+}
+
+/// Is `text` a valid Daml `Party` identifier?
+fn is_party(text: &str) -> bool {
+    !text.is_empty()
+        && text.len() <= MAX_PARTY_LENGTH
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, ':' | '-' | '_' | ' '))
+}
+
+#[allow(clippy::cast_possible_wrap)]
+const fn len(len: usize) -> i64 {
+    len as i64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use daml_lf::element::DamlArchive;
+    use std::slice;
+    use std::str::FromStr;
+
+    fn num(s: &str) -> Value<'static> {
+        Value::Numeric(BigDecimal::from_str(s).unwrap())
+    }
+
+    fn text(s: &str) -> Value<'static> {
+        Value::text(s)
+    }
+
+    /// Call a builtin with a `Machine` over an empty archive, rendering the resulting value or error.
+    fn call(builtin: &DamlBuiltinFunction, types: &[DamlType<'static>], args: &[Value<'static>]) -> String {
+        let archive = DamlArchive::default();
+        match Machine::new(&archive).call_builtin(builtin, types, args) {
+            Ok(value) => value.to_string(),
+            Err(Failure::Error(error)) => format!("error: {error}"),
+            Err(Failure::Throw(exception)) => format!("throw: {}", exception.1),
+        }
+    }
+
+    /// The scale of the `Numeric` result of calling a builtin.
+    fn scale_of(builtin: &DamlBuiltinFunction, types: &[DamlType<'static>], args: &[Value<'static>]) -> i64 {
+        let archive = DamlArchive::default();
+        let result = Machine::new(&archive).call_builtin(builtin, types, args);
+        match result {
+            Ok(Value::Numeric(n)) => primitive::scale(&n),
+            _ => panic!("expected a Numeric result"),
+        }
+    }
+
+    fn text_map(entries: &[(&str, i64)]) -> Value<'static> {
+        Value::TextMap(Rc::new(entries.iter().map(|(k, v)| (Rc::from(*k), Value::Int64(*v))).collect()))
+    }
+
+    fn gen_map(entries: &[(i64, &str)]) -> Value<'static> {
+        Value::GenMap(Rc::new(entries.iter().map(|(k, v)| (Value::Int64(*k), text(v))).collect()))
+    }
+
+    #[test]
+    fn test_numeric_scale() {
+        let nat = |n| DamlType::Nat(n);
+        let (two, four) = ([num("1.25"), num("1.50")], [nat(2), nat(2), nat(4)]);
+        assert_eq!("2.75", call(&DamlBuiltinFunction::AddNumeric, &[], &two));
+        assert_eq!(2, scale_of(&DamlBuiltinFunction::AddNumeric, &[], &two));
+        assert_eq!("1.875", call(&DamlBuiltinFunction::MulNumeric, &four, &two));
+        assert_eq!(4, scale_of(&DamlBuiltinFunction::MulNumeric, &four, &two));
+        assert_eq!("1.88", call(&DamlBuiltinFunction::MulNumeric, &[nat(2), nat(2), nat(2)], &two));
+        assert_eq!("0.3333", call(&DamlBuiltinFunction::DivNumeric, &four, &[num("1.00"), num("3.00")]));
+        assert_eq!(10, scale_of(&DamlBuiltinFunction::DivDecimal, &[], &[num("1.0000000000"), num("3.0000000000")]));
+        assert_eq!("1.5", call(&DamlBuiltinFunction::CastNumeric, &[nat(2), nat(4)], &[num("1.50")]));
+        assert_eq!(4, scale_of(&DamlBuiltinFunction::CastNumeric, &[nat(2), nat(4)], &[num("1.50")]));
+        assert_eq!("0.15", call(&DamlBuiltinFunction::ShiftNumeric, &[nat(2), nat(3)], &[num("1.50")]));
+        assert_eq!("2.0", call(&DamlBuiltinFunction::RoundNumeric, &[], &[Value::Int64(0), num("2.50")]));
+    }
+
+    #[test]
+    fn test_numeric_overflow() {
+        let max = num("99999999999999999999999999999999999999");
+        assert_eq!(
+            "error: arithmetic error: ADD_NUMERIC: numeric overflow or loss of precision",
+            call(&DamlBuiltinFunction::AddNumeric, &[], &[max, num("1")])
+        );
+        assert_eq!(
+            "error: arithmetic error: CAST_NUMERIC: numeric overflow or loss of precision",
+            call(&DamlBuiltinFunction::CastNumeric, &[DamlType::Nat(2), DamlType::Nat(1)], &[num("1.25")])
+        );
+        assert_eq!(
+            "error: arithmetic error: DIV_NUMERIC: division by zero",
+            call(&DamlBuiltinFunction::DivDecimal, &[], &[num("1.0"), num("0.0")])
+        );
+        assert_eq!(
+            "error: arithmetic error: NUMERIC_TO_INT64: overflow",
+            call(&DamlBuiltinFunction::NumericToInt64, &[], &[num("9223372036854775808.0")])
+        );
+        assert_eq!("-1", call(&DamlBuiltinFunction::NumericToInt64, &[], &[num("-1.9")]));
+    }
+
+    #[test]
+    fn test_int64_overflow() {
+        assert_eq!(
+            "error: arithmetic error: ADD_INT64: overflow or division by zero",
+            call(&DamlBuiltinFunction::AddInt64, &[], &[Value::Int64(i64::MAX), Value::Int64(1)])
+        );
+        assert_eq!(
+            "error: arithmetic error: DIV_INT64: overflow or division by zero",
+            call(&DamlBuiltinFunction::DivInt64, &[], &[Value::Int64(1), Value::Int64(0)])
+        );
+        assert_eq!(
+            "error: arithmetic error: EXP_INT64: overflow or division by zero",
+            call(&DamlBuiltinFunction::ExpInt64, &[], &[Value::Int64(2), Value::Int64(-1)])
+        );
+        assert_eq!("1024", call(&DamlBuiltinFunction::ExpInt64, &[], &[Value::Int64(2), Value::Int64(10)]));
+        assert_eq!("-1", call(&DamlBuiltinFunction::ModInt64, &[], &[Value::Int64(-7), Value::Int64(3)]));
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!("\"foobar\"", call(&DamlBuiltinFunction::AppendText, &[], &[text("foo"), text("bar")]));
+        assert_eq!("[\"a\", \"b\"]", call(&DamlBuiltinFunction::ExplodeText, &[], &[text("ab")]));
+        let letters = Value::List(ValueList::new(vec![text("a"), text("b")]));
+        assert_eq!("\"ab\"", call(&DamlBuiltinFunction::ImplodeText, &[], &[letters]));
+        assert_eq!("Some (-42)", call(&DamlBuiltinFunction::TextToInt64, &[], &[text("-42")]));
+        assert_eq!("None", call(&DamlBuiltinFunction::TextToInt64, &[], &[text("+42")]));
+        assert_eq!("None", call(&DamlBuiltinFunction::TextToInt64, &[], &[text("9223372036854775808")]));
+        assert_eq!("Some ('Alice')", call(&DamlBuiltinFunction::TextToParty, &[], &[text("Alice")]));
+        assert_eq!("None", call(&DamlBuiltinFunction::TextToParty, &[], &[text("Alice!")]));
+        assert_eq!("Some (1.5)", call(&DamlBuiltinFunction::TextToNumeric, &[DamlType::Nat(2)], &[text("1.50")]));
+        assert_eq!("None", call(&DamlBuiltinFunction::TextToNumeric, &[DamlType::Nat(1)], &[text("1.25")]));
+        assert_eq!("\"'Alice'\"", call(&DamlBuiltinFunction::PartyToQuotedText, &[], &[Value::Party("Alice".into())]));
+        assert_eq!(
+            "\"e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855\"",
+            call(&DamlBuiltinFunction::Sha256Text, &[], &[text("")])
+        );
+        assert_eq!("[104, 105]", call(&DamlBuiltinFunction::TextPointsToCode, &[], &[text("hi")]));
+        let points = Value::List(ValueList::new(vec![Value::Int64(104), Value::Int64(105)]));
+        assert_eq!("\"hi\"", call(&DamlBuiltinFunction::CodePointsToText, &[], &[points]));
+    }
+
+    #[test]
+    fn test_text_map() {
+        let map = text_map(&[("a", 1), ("b", 2)]);
+        assert_eq!(
+            "TextMap [\"a\": 1, \"b\": 3]",
+            call(&DamlBuiltinFunction::TextmapInsert, &[], &[text("b"), Value::Int64(3), map.clone()])
+        );
+        assert_eq!("Some (2)", call(&DamlBuiltinFunction::TextmapLookup, &[], &[text("b"), map.clone()]));
+        assert_eq!("None", call(&DamlBuiltinFunction::TextmapLookup, &[], &[text("c"), map.clone()]));
+        assert_eq!("TextMap [\"b\": 2]", call(&DamlBuiltinFunction::TextmapDelete, &[], &[text("a"), map.clone()]));
+        assert_eq!("2", call(&DamlBuiltinFunction::TextmapSize, &[], slice::from_ref(&map)));
+        assert_eq!(
+            "[<key = \"a\", value = 1>, <key = \"b\", value = 2>]",
+            call(&DamlBuiltinFunction::TextmapToList, &[], &[map])
+        );
+        assert_eq!("TextMap []", call(&DamlBuiltinFunction::TextmapEmpty, &[], &[]));
+    }
+
+    #[test]
+    fn test_gen_map() {
+        let map = gen_map(&[(1, "one"), (3, "three")]);
+        assert_eq!(
+            "Map [1: \"one\", 2: \"two\", 3: \"three\"]",
+            call(&DamlBuiltinFunction::GenmapInsert, &[], &[Value::Int64(2), text("two"), map.clone()])
+        );
+        assert_eq!(
+            "Map [1: \"uno\", 3: \"three\"]",
+            call(&DamlBuiltinFunction::GenmapInsert, &[], &[Value::Int64(1), text("uno"), map.clone()])
+        );
+        assert_eq!("Some (\"three\")", call(&DamlBuiltinFunction::GenmapLookup, &[], &[Value::Int64(3), map.clone()]));
+        assert_eq!("None", call(&DamlBuiltinFunction::GenmapLookup, &[], &[Value::Int64(2), map.clone()]));
+        assert_eq!(
+            "Map [3: \"three\"]",
+            call(&DamlBuiltinFunction::GenmapDelete, &[], &[Value::Int64(1), map.clone()])
+        );
+        assert_eq!("[1, 3]", call(&DamlBuiltinFunction::GenmapKeys, &[], slice::from_ref(&map)));
+        assert_eq!("[\"one\", \"three\"]", call(&DamlBuiltinFunction::GenmapValues, &[], slice::from_ref(&map)));
+        assert_eq!("2", call(&DamlBuiltinFunction::GenmapSize, &[], &[map]));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!("error: user error: boom", call(&DamlBuiltinFunction::Error, &[], &[text("boom")]));
+        assert_eq!(
+            "error: unexpected runtime value: expected a Nat type argument for MulNumeric but found 0 type arguments",
+            call(&DamlBuiltinFunction::MulNumeric, &[], &[num("1.0"), num("1.0")])
+        );
+        assert!(call(&DamlBuiltinFunction::AddInt64, &[], &[text("1"), Value::Int64(1)]).starts_with("error: "));
+        assert_eq!(
+            "error: arithmetic error: CODE_POINTS_TO_TEXT: invalid code point -1",
+            call(&DamlBuiltinFunction::CodePointsToText, &[], &[Value::List(ValueList::new(vec![Value::Int64(-1)]))])
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use crate::interpreter::error::{DamlInterpreterError, DamlInterpreterResult};
+use crate::interpreter::primitive;
+use crate::interpreter::types::{data_identifier, instantiate, render_type};
+use crate::interpreter::value::{compare, enum_constructor, Value, ValueList};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use daml_grpc::data::value::{DamlEnum, DamlRecord, DamlRecordField, DamlValue, DamlVariant};
+use daml_grpc::primitive_types::{DamlContractId, DamlDate, DamlGenMap, DamlParty, DamlTextMap, DamlTimestamp};
+use daml_lf::element::{DamlArchive, DamlData, DamlType};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// The number of days between 0001-01-01 (day 1 of the common era) and 1970-01-01.
+const EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Convert a `DamlValue` of the given type to a runtime value.
+pub fn from_daml_value<'a>(
+    archive: &'a DamlArchive<'a>,
+    value: &DamlValue,
+    ty: &DamlType<'a>,
+) -> DamlInterpreterResult<Value<'a>> {
+    let mismatch = || DamlInterpreterError::ValueMismatch(value.variant_name().to_owned(), render_type(ty));
+    let first_arg = |args: &'_ [DamlType<'a>]| args.first().cloned().ok_or_else(mismatch);
+    Ok(match (value, ty) {
+        (DamlValue::Unit, DamlType::Unit) => Value::Unit,
+        (DamlValue::Bool(b), DamlType::Bool) => Value::Bool(*b),
+        (DamlValue::Int64(i), DamlType::Int64) => Value::Int64(*i),
+        (DamlValue::Text(t), DamlType::Text) => Value::text(t.as_str()),
+        (DamlValue::Party(p), DamlType::Party) => Value::Party(p.as_str().into()),
+        (DamlValue::ContractId(cid), DamlType::ContractId(_)) => Value::ContractId(cid.as_str().into()),
+        (DamlValue::Timestamp(ts), DamlType::Timestamp) =>
+            Value::Timestamp(primitive::check_timestamp(micros_from_timestamp(ts)).ok_or_else(mismatch)?),
+        (DamlValue::Date(date), DamlType::Date) => Value::Date(days_from_date(*date)),
+        (DamlValue::Numeric(n), DamlType::Numeric(args)) => match args.first() {
+            Some(DamlType::Nat(scale)) =>
+                Value::Numeric(primitive::check_numeric(n, i64::from(*scale)).ok_or_else(mismatch)?),
+            _ => Value::Numeric(primitive::check_numeric(n, 10).ok_or_else(mismatch)?),
+        },
+        (DamlValue::List(items), DamlType::List(args)) => {
+            let item_ty = first_arg(args)?;
+            Value::List(ValueList::new(
+                items.iter().map(|item| from_daml_value(archive, item, &item_ty)).collect::<Result<_, _>>()?,
+            ))
+        },
+        (DamlValue::Optional(opt), DamlType::Optional(args)) => {
+            let item_ty = first_arg(args)?;
+            Value::optional(opt.as_ref().map(|item| from_daml_value(archive, item, &item_ty)).transpose()?)
+        },
+        (DamlValue::Map(map), DamlType::TextMap(args)) => {
+            let item_ty = first_arg(args)?;
+            Value::TextMap(Rc::new(
+                map.0
+                    .iter()
+                    .map(|(k, v)| Ok((Rc::from(k.as_str()), from_daml_value(archive, v, &item_ty)?)))
+                    .collect::<DamlInterpreterResult<BTreeMap<_, _>>>()?,
+            ))
+        },
+        (DamlValue::GenMap(map), DamlType::GenMap(args)) => {
+            let key_ty = first_arg(args)?;
+            let value_ty = args.get(1).cloned().ok_or_else(mismatch)?;
+            let mut entries = map
+                .iter()
+                .map(|(k, v)| Ok((from_daml_value(archive, k, &key_ty)?, from_daml_value(archive, v, &value_ty)?)))
+                .collect::<DamlInterpreterResult<Vec<_>>>()?;
+            sort_entries(&mut entries)?;
+            Value::GenMap(Rc::new(entries))
+        },
+        (_, DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon)) => {
+            let data = archive
+                .data_by_tycon_name(tycon.tycon())
+                .ok_or_else(|| DamlInterpreterError::UnknownType(render_type(ty)))?;
+            from_daml_data(archive, value, data, tycon.type_arguments()).map_err(|_| mismatch())?
+        },
+        _ => return Err(mismatch()),
+    })
+}
+
+/// Convert a `DamlValue` of the given data type to a runtime value.
+pub fn from_daml_data<'a>(
+    archive: &'a DamlArchive<'a>,
+    value: &DamlValue,
+    data: &'a DamlData<'a>,
+    type_args: &[DamlType<'a>],
+) -> DamlInterpreterResult<Value<'a>> {
+    let field_type = |ty: &DamlType<'a>| instantiate(ty, data.type_params(), type_args);
+    let mismatch = || DamlInterpreterError::ValueMismatch(value.variant_name().to_owned(), data.name().to_owned());
+    match (value, data) {
+        (DamlValue::Record(record), DamlData::Record(_) | DamlData::Template(_)) => {
+            let fields = data
+                .fields()
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let found = record
+                        .fields()
+                        .iter()
+                        .find(|f| f.label().as_deref() == Some(field.name()))
+                        .or_else(|| record.fields().get(i).filter(|f| f.label().as_deref().map_or(true, str::is_empty)))
+                        .ok_or_else(|| {
+                            DamlInterpreterError::ValueMismatch(
+                                format!("record without field {}", field.name()),
+                                data.name().to_owned(),
+                            )
+                        })?;
+                    from_daml_value(archive, found.value(), &field_type(field.ty()))
+                })
+                .collect::<DamlInterpreterResult<Vec<_>>>()?;
+            Ok(Value::record(data, fields))
+        },
+        (DamlValue::Variant(variant), DamlData::Variant(_)) => {
+            let (rank, field) = data
+                .fields()
+                .iter()
+                .enumerate()
+                .find(|(_, field)| field.name() == variant.constructor())
+                .ok_or_else(mismatch)?;
+            Ok(Value::variant(data, rank, from_daml_value(archive, variant.value(), &field_type(field.ty()))?))
+        },
+        (DamlValue::Enum(data_enum), DamlData::Enum(def)) =>
+            Ok(Value::Enum(data, def.constructors().position(|c| c == data_enum.constructor()).ok_or_else(mismatch)?)),
+        _ => Err(mismatch()),
+    }
+}
+
+/// Convert a runtime value to a `DamlValue`.
+pub fn to_daml_value(value: &Value<'_>) -> DamlInterpreterResult<DamlValue> {
+    Ok(match value {
+        Value::Unit => DamlValue::Unit,
+        Value::Bool(b) => DamlValue::Bool(*b),
+        Value::Int64(i) => DamlValue::Int64(*i),
+        Value::Numeric(n) => DamlValue::Numeric(n.clone()),
+        Value::Text(t) => DamlValue::Text(t.to_string()),
+        Value::Party(p) => DamlValue::Party(DamlParty::new(p.as_ref())),
+        Value::ContractId(cid) => DamlValue::ContractId(DamlContractId::new(cid.as_ref())),
+        Value::Timestamp(micros) => DamlValue::Timestamp(timestamp_from_micros(*micros)?),
+        Value::Date(days) => DamlValue::Date(date_from_days(*days)?),
+        Value::List(list) => DamlValue::List(list.iter().map(to_daml_value).collect::<Result<_, _>>()?),
+        Value::Optional(opt) => DamlValue::Optional(opt.as_deref().map(to_daml_value).transpose()?.map(Box::new)),
+        Value::TextMap(map) => {
+            let mut converted = DamlTextMap::new();
+            for (k, v) in map.iter() {
+                converted.0.insert(k.to_string(), to_daml_value(v)?);
+            }
+            DamlValue::Map(converted)
+        },
+        Value::GenMap(map) => DamlValue::GenMap(
+            map.iter()
+                .map(|(k, v)| Ok((to_daml_value(k)?, to_daml_value(v)?)))
+                .collect::<DamlInterpreterResult<DamlGenMap<_, _>>>()?,
+        ),
+        Value::Record(_) => DamlValue::Record(to_daml_record(value)?),
+        Value::Variant(variant) => DamlValue::Variant(DamlVariant::new(
+            variant.constructor(),
+            Box::new(to_daml_value(&variant.value)?),
+            Some(data_identifier(variant.data)),
+        )),
+        Value::Enum(data, rank) =>
+            DamlValue::Enum(DamlEnum::new(enum_constructor(data, *rank), Some(data_identifier(data)))),
+        Value::BigNumeric(_)
+        | Value::RoundingMode(_)
+        | Value::Struct(_)
+        | Value::Any(_)
+        | Value::TypeRep(_)
+        | Value::AnyException(_)
+        | Value::Closure(_)
+        | Value::TyClosure(_)
+        | Value::Builtin(_)
+        | Value::Update(_) => return Err(DamlInterpreterError::NotSerializable(value.kind().to_owned())),
+    })
+}
+
+/// Convert a runtime record value to a `DamlRecord`.
+pub fn to_daml_record(value: &Value<'_>) -> DamlInterpreterResult<DamlRecord> {
+    let record = value.as_record()?;
+    let fields = record
+        .data
+        .fields()
+        .iter()
+        .zip(&record.fields)
+        .map(|(field, value)| Ok(DamlRecordField::new(Some(field.name()), to_daml_value(value)?)))
+        .collect::<DamlInterpreterResult<Vec<_>>>()?;
+    Ok(DamlRecord::new(fields, Some(data_identifier(record.data))))
+}
+
+/// Sort the entries of a generic map by key.
+pub fn sort_entries(entries: &mut Vec<(Value<'_>, Value<'_>)>) -> DamlInterpreterResult<()> {
+    let mut sorted: Vec<(Value<'_>, Value<'_>)> = Vec::with_capacity(entries.len());
+    for (key, value) in entries.drain(..) {
+        match binary_search(&sorted, &key)? {
+            Ok(index) => sorted[index] = (key, value),
+            Err(index) => sorted.insert(index, (key, value)),
+        }
+    }
+    *entries = sorted;
+    Ok(())
+}
+
+/// Search the sorted generic map `entries` for `key`.
+pub fn binary_search(
+    entries: &[(Value<'_>, Value<'_>)],
+    key: &Value<'_>,
+) -> DamlInterpreterResult<Result<usize, usize>> {
+    let (mut low, mut high) = (0, entries.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match compare(&entries[mid].0, key)? {
+            std::cmp::Ordering::Less => low = mid + 1,
+            std::cmp::Ordering::Greater => high = mid,
+            std::cmp::Ordering::Equal => return Ok(Ok(mid)),
+        }
+    }
+    Ok(Err(low))
+}
+
+/// Convert a number of microseconds since the epoch to a `DamlTimestamp`.
+pub fn timestamp_from_micros(micros: i64) -> DamlInterpreterResult<DamlTimestamp> {
+    let nanos = u32::try_from(micros.rem_euclid(1_000_000) * 1000).unwrap_or_default();
+    Utc.timestamp_opt(micros.div_euclid(1_000_000), nanos)
+        .single()
+        .ok_or_else(|| DamlInterpreterError::NotSerializable(format!("Timestamp {micros}")))
+}
+
+/// Convert a `DamlTimestamp` to a number of microseconds since the epoch.
+pub fn micros_from_timestamp(timestamp: &DateTime<Utc>) -> i64 {
+    timestamp.timestamp() * 1_000_000 + i64::from(timestamp.timestamp_subsec_micros())
+}
+
+#[allow(deprecated)]
+fn date_from_days(days: i32) -> DamlInterpreterResult<DamlDate> {
+    days.checked_add(EPOCH_DAYS_FROM_CE)
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+        .map(|date| DamlDate::from_utc(date, Utc))
+        .ok_or_else(|| DamlInterpreterError::NotSerializable(format!("Date {days}")))
+}
+
+#[allow(deprecated)]
+fn days_from_date(date: DamlDate) -> i32 {
+    use chrono::Datelike;
+    date.naive_utc().num_days_from_ce() - EPOCH_DAYS_FROM_CE
+}
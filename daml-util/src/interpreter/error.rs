@@ -0,0 +1,51 @@
+use thiserror::Error;
+
+/// Daml interpreter result.
+pub type DamlInterpreterResult<T> = Result<T, DamlInterpreterError>;
+
+/// Daml interpreter error.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DamlInterpreterError {
+    #[error("unknown value {0}")]
+    UnknownValue(String),
+    #[error("unknown type {0}")]
+    UnknownType(String),
+    #[error("unknown choice {0} on {1}")]
+    UnknownChoice(String, String),
+    #[error("unknown variable {0}")]
+    UnknownVariable(String),
+    #[error("contract {0} not found")]
+    ContractNotFound(String),
+    #[error("contract {0} is not active")]
+    ContractNotActive(String),
+    #[error("contract {0} has template {1} but {2} was expected")]
+    TemplateMismatch(String, String, String),
+    #[error("contract {0} does not implement interface {1}")]
+    InterfaceNotImplemented(String, String),
+    #[error("no contract of template {0} found with key {1}")]
+    ContractKeyNotFound(String, String),
+    #[error("a contract of template {0} already exists with key {1}")]
+    DuplicateContractKey(String, String),
+    #[error("precondition of template {0} failed for contract argument {1}")]
+    PreconditionFailed(String, String),
+    #[error("user error: {0}")]
+    UserError(String),
+    #[error("unhandled exception: {0}")]
+    UnhandledException(String),
+    #[error("arithmetic error: {0}")]
+    ArithmeticError(String),
+    #[error("value {0} does not have expected type {1}")]
+    ValueMismatch(String, String),
+    #[error("cannot convert {0} to a DamlValue")]
+    NotSerializable(String),
+    #[error("unexpected runtime value: expected {0} but found {1}")]
+    UnexpectedValue(String, String),
+    #[error("no case alternative matched {0}")]
+    MatchFailure(String),
+    #[error("evaluation exceeded the maximum depth of {0}")]
+    DepthLimitExceeded(usize),
+    #[error("evaluation exceeded the maximum of {0} steps")]
+    StepLimitExceeded(u64),
+    #[error("unsupported: {0}")]
+    Unsupported(String),
+}
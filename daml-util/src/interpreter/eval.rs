@@ -0,0 +1,898 @@
+use crate::interpreter::builtin::{arity, needs_types};
+use crate::interpreter::error::{DamlInterpreterError, DamlInterpreterResult};
+use crate::interpreter::types::{
+    data_type, is_data, render_data_name, render_tycon_name, resolve, tycon_name_eq, type_eq,
+};
+use crate::interpreter::value::{
+    enum_constructor, BuiltinValue, Closure, Env, TyClosure, UpdateValue, Value, ValueList,
+};
+use bigdecimal::BigDecimal;
+use daml_lf::element::{
+    DamlAbs, DamlApp, DamlArchive, DamlBlock, DamlBuiltinFunction, DamlCallInterface, DamlCase, DamlCaseAltSum,
+    DamlCons, DamlData, DamlDefValue, DamlEnumCon, DamlExpr, DamlFromAny, DamlFromAnyException, DamlInterfaceCast,
+    DamlInterfaceExpr, DamlInterfaceInstance, DamlOptionalSome, DamlPrimCon, DamlPrimLit, DamlRecCon, DamlRecProj,
+    DamlRecUpd, DamlRequiredInterfaceCast, DamlStructCon, DamlStructProj, DamlStructUpd, DamlTemplate, DamlThrow,
+    DamlToAny, DamlToAnyException, DamlTyAbs, DamlTyApp, DamlTyConName, DamlType, DamlUnsafeFromInterface,
+    DamlUnsafeFromRequiredInterface, DamlUpdate, DamlValueName, DamlVariantCon,
+};
+use itertools::Itertools;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// The module path and name of the exception thrown by arithmetic builtin functions.
+const ARITHMETIC_ERROR_MODULE: [&str; 3] = ["DA", "Exception", "ArithmeticError"];
+const ARITHMETIC_ERROR_NAME: &str = "ArithmeticError";
+
+/// The default maximum depth of nested evaluation.
+pub const DEFAULT_MAX_DEPTH: usize = 300;
+
+/// The default maximum number of evaluation steps.
+pub const DEFAULT_MAX_STEPS: u64 = 10_000_000;
+
+/// The reason evaluation did not produce a value.
+#[derive(Debug)]
+pub enum Failure<'a> {
+    /// A Daml exception which may be caught by an enclosing `try` block.
+    Throw(Rc<(DamlType<'a>, Value<'a>)>),
+    /// An error which aborts evaluation.
+    Error(DamlInterpreterError),
+}
+
+impl From<DamlInterpreterError> for Failure<'_> {
+    fn from(error: DamlInterpreterError) -> Self {
+        Failure::Error(error)
+    }
+}
+
+/// The result of evaluating an expression.
+pub type Eval<'a, T> = Result<T, Failure<'a>>;
+
+/// Evaluates Daml-LF expressions over a `DamlArchive`.
+///
+/// The values of top-level definitions are computed at most once and shared between evaluations.
+///
+/// Evaluation is bounded by a maximum depth of nested evaluation, which guards against exhausting the stack, and a
+/// maximum number of evaluation steps, which guards against non-termination.
+pub struct Machine<'a> {
+    archive: &'a DamlArchive<'a>,
+    arithmetic_error: Option<&'a DamlData<'a>>,
+    globals: RefCell<HashMap<*const DamlDefValue<'a>, Value<'a>>>,
+    evaluating: RefCell<HashSet<*const DamlDefValue<'a>>>,
+    max_depth: usize,
+    max_steps: u64,
+    depth: Cell<usize>,
+    steps: Cell<u64>,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(archive: &'a DamlArchive<'a>) -> Self {
+        let arithmetic_error = archive
+            .packages()
+            .find_map(|package| archive.data(package.package_id(), &ARITHMETIC_ERROR_MODULE, ARITHMETIC_ERROR_NAME));
+        Self {
+            archive,
+            arithmetic_error,
+            globals: RefCell::default(),
+            evaluating: RefCell::default(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_steps: DEFAULT_MAX_STEPS,
+            depth: Cell::default(),
+            steps: Cell::default(),
+        }
+    }
+
+    /// Set the maximum depth of nested evaluation.
+    #[must_use]
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self {
+            max_depth,
+            ..self
+        }
+    }
+
+    /// Set the maximum number of evaluation steps.
+    #[must_use]
+    pub fn with_max_steps(self, max_steps: u64) -> Self {
+        Self {
+            max_steps,
+            ..self
+        }
+    }
+
+    /// Reset the count of evaluation steps taken, to be called at the start of each top-level evaluation.
+    pub fn reset_steps(&self) {
+        self.steps.set(0);
+    }
+
+    pub const fn archive(&self) -> &'a DamlArchive<'a> {
+        self.archive
+    }
+
+    /// Evaluate an expression in the given environment.
+    ///
+    /// Each evaluation counts against the step budget and nested evaluations against the depth budget of this
+    /// `Machine`, evaluation is aborted if either is exceeded.
+    pub fn eval(&self, expr: &'a DamlExpr<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        self.enter()?;
+        let result = self.eval_expr(expr, env);
+        self.leave();
+        result
+    }
+
+    /// Account for one evaluation step at one greater depth, every call must be paired with a call to `leave`.
+    pub fn enter(&self) -> DamlInterpreterResult<()> {
+        let steps = self.steps.get() + 1;
+        if steps > self.max_steps {
+            return Err(DamlInterpreterError::StepLimitExceeded(self.max_steps));
+        }
+        let depth = self.depth.get() + 1;
+        if depth > self.max_depth {
+            return Err(DamlInterpreterError::DepthLimitExceeded(self.max_depth));
+        }
+        self.steps.set(steps);
+        self.depth.set(depth);
+        Ok(())
+    }
+
+    /// Return to the depth prior to the matching call to `enter`.
+    pub fn leave(&self) {
+        self.depth.set(self.depth.get() - 1);
+    }
+
+    // Each variant with a non-trivial evaluation is handled by a separate method to keep the stack frame of this
+    // (deeply recursive) function small.
+    fn eval_expr(&self, expr: &'a DamlExpr<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        match expr {
+            DamlExpr::Var(var) => eval_var(var, env),
+            DamlExpr::Val(name) => self.eval_val(name),
+            DamlExpr::Builtin(builtin) => self.eval_builtin(builtin),
+            DamlExpr::PrimCon(prim_con) => Ok(eval_prim_con(*prim_con)),
+            DamlExpr::PrimLit(lit) => eval_prim_lit(lit),
+            DamlExpr::RecCon(rec_con) => self.eval_rec_con(rec_con, env),
+            DamlExpr::RecProj(rec_proj) => self.eval_rec_proj(rec_proj, env),
+            DamlExpr::RecUpd(rec_upd) => self.eval_rec_upd(rec_upd, env),
+            DamlExpr::VariantCon(variant_con) => self.eval_variant_con(variant_con, env),
+            DamlExpr::EnumCon(enum_con) => self.eval_enum_con(enum_con),
+            DamlExpr::StructCon(struct_con) => self.eval_struct_con(struct_con, env),
+            DamlExpr::StructProj(struct_proj) => self.eval_struct_proj(struct_proj, env),
+            DamlExpr::StructUpd(struct_upd) => self.eval_struct_upd(struct_upd, env),
+            DamlExpr::App(app) => self.eval_app(app, env),
+            DamlExpr::TyApp(ty_app) => self.eval_ty_app(ty_app, env),
+            DamlExpr::Abs(abs) => Ok(eval_abs(abs, env)),
+            DamlExpr::TyAbs(ty_abs) => Ok(eval_ty_abs(ty_abs, env)),
+            DamlExpr::Case(case) => self.eval_case(case, env),
+            DamlExpr::Let(block) => self.eval_let(block, env),
+            DamlExpr::Nil(_) => Ok(Value::List(ValueList::default())),
+            DamlExpr::Cons(cons) => self.eval_cons(cons, env),
+            DamlExpr::Update(update) => Ok(eval_update(update, env)),
+            DamlExpr::Scenario(_) => Err(DamlInterpreterError::Unsupported("scenario".to_owned()).into()),
+            DamlExpr::OptionalNone(_) => Ok(Value::Optional(None)),
+            DamlExpr::OptionalSome(some) => self.eval_optional_some(some, env),
+            DamlExpr::ToAny(to_any) => self.eval_to_any(to_any, env),
+            DamlExpr::FromAny(from_any) => self.eval_from_any(from_any, env),
+            DamlExpr::TypeRep(ty) => Ok(Value::TypeRep(Rc::new(resolve(ty, env)))),
+            DamlExpr::ToAnyException(to_any) => self.eval_to_any_exception(to_any, env),
+            DamlExpr::FromAnyException(from_any) => self.eval_from_any_exception(from_any, env),
+            DamlExpr::Throw(throw) => self.eval_throw(throw, env),
+            DamlExpr::ToInterface(cast) => self.eval(cast.expr(), env),
+            DamlExpr::ToRequiredInterface(cast) => self.eval(cast.expr(), env),
+            DamlExpr::FromInterface(cast) => self.eval_from_interface(cast, env),
+            DamlExpr::UnsafeFromInterface(cast) => self.eval_unsafe_from_interface(cast, env),
+            DamlExpr::FromRequiredInterface(cast) => self.eval_from_required_interface(cast, env),
+            DamlExpr::UnsafeFromRequiredInterface(cast) => self.eval_unsafe_from_required_interface(cast, env),
+            DamlExpr::CallInterface(call) => self.eval_call_interface(call, env),
+            DamlExpr::InterfaceTemplateTypeRep(interface_expr) => self.eval_template_type_rep(interface_expr, env),
+            DamlExpr::SignatoryInterface(interface_expr) =>
+                self.eval_template_expr(interface_expr, env, DamlTemplate::signatories),
+            DamlExpr::ObserverInterface(interface_expr) =>
+                self.eval_template_expr(interface_expr, env, DamlTemplate::observers),
+            DamlExpr::ViewInterface(interface_expr) => self.eval_view_interface(interface_expr, env),
+        }
+    }
+
+    fn eval_val(&self, name: &'a DamlValueName<'a>) -> Eval<'a, Value<'a>> {
+        Ok(self.global(name)?)
+    }
+
+    fn eval_builtin(&self, builtin: &'a DamlBuiltinFunction) -> Eval<'a, Value<'a>> {
+        if arity(builtin) == 0 {
+            self.call_builtin(builtin, &[], &[])
+        } else {
+            Ok(Value::Builtin(Rc::new(BuiltinValue {
+                builtin,
+                types: vec![],
+                args: vec![],
+            })))
+        }
+    }
+
+    fn eval_rec_con(&self, rec_con: &'a DamlRecCon<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let data = self.data(rec_con.tycon().tycon())?;
+        let fields = rec_con.fields().iter().map(|field| self.eval(field.expr(), env)).collect::<Result<_, _>>()?;
+        Ok(Value::record(data, fields))
+    }
+
+    fn eval_rec_proj(&self, rec_proj: &'a DamlRecProj<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let record = self.eval(rec_proj.record(), env)?;
+        let record = record.as_record()?;
+        Ok(record.field(rec_proj.field())?.clone())
+    }
+
+    fn eval_rec_upd(&self, rec_upd: &'a DamlRecUpd<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let record = self.eval(rec_upd.record(), env)?;
+        let update = self.eval(rec_upd.update(), env)?;
+        let record = record.as_record()?;
+        let mut fields = record.fields.clone();
+        fields[record.field_index(rec_upd.field())?] = update;
+        Ok(Value::record(record.data, fields))
+    }
+
+    fn eval_variant_con(&self, variant_con: &'a DamlVariantCon<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let data = self.data(variant_con.tycon().tycon())?;
+        let rank =
+            data.fields().iter().position(|field| field.name() == variant_con.variant_con()).ok_or_else(|| {
+                DamlInterpreterError::UnexpectedValue(
+                    format!("constructor {}", variant_con.variant_con()),
+                    render_data_name(data),
+                )
+            })?;
+        Ok(Value::variant(data, rank, self.eval(variant_con.variant_arg(), env)?))
+    }
+
+    fn eval_enum_con(&self, enum_con: &'a DamlEnumCon<'a>) -> Eval<'a, Value<'a>> {
+        let data = self.data(enum_con.tycon())?;
+        let rank = match data {
+            DamlData::Enum(data_enum) => data_enum.constructors().position(|c| c == enum_con.enum_con()),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            DamlInterpreterError::UnexpectedValue(
+                format!("constructor {}", enum_con.enum_con()),
+                render_data_name(data),
+            )
+        })?;
+        Ok(Value::Enum(data, rank))
+    }
+
+    fn eval_struct_con(&self, struct_con: &'a DamlStructCon<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        Ok(Value::Struct(Rc::new(
+            struct_con.fields().iter().map(|field| Ok((field.field(), self.eval(field.expr(), env)?))).collect::<Eval<
+                'a,
+                Vec<_>,
+            >>(
+            )?,
+        )))
+    }
+
+    fn eval_struct_proj(&self, struct_proj: &'a DamlStructProj<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let value = self.eval(struct_proj.struct_expr(), env)?;
+        Ok(struct_field(&value, struct_proj.field())?.clone())
+    }
+
+    fn eval_struct_upd(&self, struct_upd: &'a DamlStructUpd<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let value = self.eval(struct_upd.struct_expr(), env)?;
+        let update = self.eval(struct_upd.update(), env)?;
+        let mut fields = value.as_struct()?.as_ref().clone();
+        let field = fields.iter_mut().find(|(name, _)| *name == struct_upd.field()).ok_or_else(|| {
+            DamlInterpreterError::UnexpectedValue(format!("struct field {}", struct_upd.field()), value.to_string())
+        })?;
+        field.1 = update;
+        Ok(Value::Struct(Rc::new(fields)))
+    }
+
+    fn eval_app(&self, app: &'a DamlApp<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let fun = self.eval(app.fun(), env)?;
+        let args = app.args().iter().map(|arg| self.eval(arg, env)).collect::<Result<_, _>>()?;
+        self.apply(fun, args)
+    }
+
+    fn eval_ty_app(&self, ty_app: &'a DamlTyApp<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let value = self.eval(ty_app.expr(), env)?;
+        self.apply_types(value, ty_app.types().iter().map(|ty| resolve(ty, env)).collect())
+    }
+
+    fn eval_let(&self, block: &'a DamlBlock<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let mut env = env.clone();
+        for binding in block.bindings() {
+            let value = self.eval(binding.bound(), &env)?;
+            env = env.bind(binding.binder().var(), value);
+        }
+        self.eval(block.body(), &env)
+    }
+
+    fn eval_cons(&self, cons: &'a DamlCons<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let front = cons.front().iter().map(|item| self.eval(item, env)).collect::<Result<_, _>>()?;
+        let tail = self.eval(cons.tail(), env)?;
+        Ok(Value::List(ValueList::cons(front, tail.as_list()?)))
+    }
+
+    fn eval_optional_some(&self, some: &'a DamlOptionalSome<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        Ok(Value::optional(Some(self.eval(some.body(), env)?)))
+    }
+
+    fn eval_to_any(&self, to_any: &'a DamlToAny<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        Ok(Value::Any(Rc::new((resolve(to_any.ty(), env), self.eval(to_any.expr(), env)?))))
+    }
+
+    fn eval_from_any(&self, from_any: &'a DamlFromAny<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        match self.eval(from_any.expr(), env)? {
+            Value::Any(any) =>
+                Ok(Value::optional(type_eq(&any.0, &resolve(from_any.ty(), env)).then(|| any.1.clone()))),
+            other => other.unexpected("Any").map_err(Failure::from),
+        }
+    }
+
+    fn eval_to_any_exception(&self, to_any: &'a DamlToAnyException<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        Ok(Value::AnyException(Rc::new((resolve(to_any.ty(), env), self.eval(to_any.expr(), env)?))))
+    }
+
+    fn eval_from_any_exception(&self, from_any: &'a DamlFromAnyException<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        match self.eval(from_any.expr(), env)? {
+            Value::AnyException(any) =>
+                Ok(Value::optional(type_eq(&any.0, &resolve(from_any.ty(), env)).then(|| any.1.clone()))),
+            other => other.unexpected("AnyException").map_err(Failure::from),
+        }
+    }
+
+    fn eval_throw(&self, throw: &'a DamlThrow<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let exception = self.eval(throw.exception_expr(), env)?;
+        Err(Failure::Throw(Rc::new((resolve(throw.exception_type(), env), exception))))
+    }
+
+    fn eval_from_interface(&self, cast: &'a DamlInterfaceCast<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let value = self.eval(cast.expr(), env)?;
+        let matches = is_data(cast.template_type(), value.as_record()?.data);
+        Ok(Value::optional(matches.then(|| value)))
+    }
+
+    fn eval_unsafe_from_interface(&self, cast: &'a DamlUnsafeFromInterface<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let cid = self.eval(cast.contract_id_expr(), env)?;
+        let value = self.eval(cast.interface_expr(), env)?;
+        let data = value.as_record()?.data;
+        if !is_data(cast.template_type(), data) {
+            return Err(DamlInterpreterError::TemplateMismatch(
+                cid.as_contract_id()?.to_string(),
+                render_data_name(data),
+                render_tycon_name(cast.template_type()),
+            )
+            .into());
+        }
+        Ok(value)
+    }
+
+    fn eval_from_required_interface(
+        &self,
+        cast: &'a DamlRequiredInterfaceCast<'a>,
+        env: &Env<'a>,
+    ) -> Eval<'a, Value<'a>> {
+        let value = self.eval(cast.expr(), env)?;
+        let implements = interface_instance(value.as_record()?.data, cast.requiring_interface()).is_some();
+        Ok(Value::optional(implements.then(|| value)))
+    }
+
+    fn eval_unsafe_from_required_interface(
+        &self,
+        cast: &'a DamlUnsafeFromRequiredInterface<'a>,
+        env: &Env<'a>,
+    ) -> Eval<'a, Value<'a>> {
+        let cid = self.eval(cast.contract_id_expr(), env)?;
+        let value = self.eval(cast.interface_expr(), env)?;
+        if interface_instance(value.as_record()?.data, cast.requiring_interface()).is_none() {
+            return Err(DamlInterpreterError::InterfaceNotImplemented(
+                cid.as_contract_id()?.to_string(),
+                render_tycon_name(cast.requiring_interface()),
+            )
+            .into());
+        }
+        Ok(value)
+    }
+
+    fn eval_call_interface(&self, call: &'a DamlCallInterface<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let value = self.eval(call.interface_expr(), env)?;
+        let (template, instance) = expect_interface_instance(&value, call.interface_type())?;
+        let method = instance.methods().iter().find(|method| method.name() == call.method()).ok_or_else(|| {
+            DamlInterpreterError::UnknownValue(format!(
+                "{}.{}",
+                render_tycon_name(call.interface_type()),
+                call.method()
+            ))
+        })?;
+        self.eval(method.value(), &Env::default().bind(template.param(), value))
+    }
+
+    fn eval_template_type_rep(&self, interface_expr: &'a DamlInterfaceExpr<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let value = self.eval(interface_expr.expr(), env)?;
+        Ok(Value::TypeRep(Rc::new(data_type(value.as_record()?.data))))
+    }
+
+    /// Evaluate the template expression selected by `select` for the contract of an interface value.
+    fn eval_template_expr(
+        &self,
+        interface_expr: &'a DamlInterfaceExpr<'a>,
+        env: &Env<'a>,
+        select: fn(&'a DamlTemplate<'a>) -> &'a DamlExpr<'a>,
+    ) -> Eval<'a, Value<'a>> {
+        let value = self.eval(interface_expr.expr(), env)?;
+        let template = expect_template(value.as_record()?.data)?;
+        self.eval(select(template), &Env::default().bind(template.param(), value))
+    }
+
+    fn eval_view_interface(&self, interface_expr: &'a DamlInterfaceExpr<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let value = self.eval(interface_expr.expr(), env)?;
+        let (template, instance) = expect_interface_instance(&value, interface_expr.interface())?;
+        self.eval(instance.view(), &Env::default().bind(template.param(), value))
+    }
+
+    /// Apply a function value to some arguments.
+    pub fn apply(&self, fun: Value<'a>, args: Vec<Value<'a>>) -> Eval<'a, Value<'a>> {
+        let mut fun = fun;
+        let mut args = args;
+        while !args.is_empty() {
+            fun = match fun {
+                Value::Closure(closure) => self.apply_closure(&closure, &mut args)?,
+                Value::Builtin(builtin) => self.apply_builtin(&builtin, &mut args)?,
+                other => return other.unexpected("Function").map_err(Failure::from),
+            };
+        }
+        Ok(fun)
+    }
+
+    /// Apply a closure to as many of `args` as it accepts, leaving any remaining arguments in `args`.
+    ///
+    /// If there are fewer arguments than parameters the partially applied closure is returned.
+    fn apply_closure(&self, closure: &Rc<Closure<'a>>, args: &mut Vec<Value<'a>>) -> Eval<'a, Value<'a>> {
+        let mut all = closure.applied.clone();
+        all.append(args);
+        if all.len() < closure.params.len() {
+            return Ok(Value::Closure(Rc::new(Closure {
+                env: closure.env.clone(),
+                params: closure.params,
+                body: closure.body,
+                applied: all,
+            })));
+        }
+        *args = all.split_off(closure.params.len());
+        let env = closure
+            .params
+            .iter()
+            .zip(all)
+            .fold(closure.env.clone(), |env, (param, value)| env.bind(param.var(), value));
+        self.eval(closure.body, &env)
+    }
+
+    /// Apply a builtin function to as many of `args` as it accepts, leaving any remaining arguments in `args`.
+    ///
+    /// If there are fewer arguments than the arity of the builtin the partially applied builtin is returned.
+    fn apply_builtin(&self, builtin: &Rc<BuiltinValue<'a>>, args: &mut Vec<Value<'a>>) -> Eval<'a, Value<'a>> {
+        let mut all = builtin.args.clone();
+        all.append(args);
+        let arity = arity(builtin.builtin);
+        if all.len() < arity {
+            return Ok(Value::Builtin(Rc::new(BuiltinValue {
+                builtin: builtin.builtin,
+                types: builtin.types.clone(),
+                args: all,
+            })));
+        }
+        *args = all.split_off(arity);
+        self.call_builtin(builtin.builtin, &builtin.types, &all)
+    }
+
+    /// Apply a value to some (resolved) type arguments.
+    ///
+    /// Types are erased at runtime except where they are needed to evaluate an expression, and so applying a type to
+    /// any value other than a type abstraction or builtin function has no effect.
+    pub fn apply_types(&self, value: Value<'a>, types: Vec<DamlType<'a>>) -> Eval<'a, Value<'a>> {
+        if types.is_empty() {
+            return Ok(value);
+        }
+        match value {
+            Value::TyClosure(closure) => {
+                let mut all = closure.applied.clone();
+                all.extend(types);
+                if all.len() < closure.params.len() {
+                    return Ok(Value::TyClosure(Rc::new(TyClosure {
+                        env: closure.env.clone(),
+                        params: closure.params,
+                        body: closure.body,
+                        applied: all,
+                    })));
+                }
+                let rest = all.split_off(closure.params.len());
+                let env = closure
+                    .params
+                    .iter()
+                    .zip(all)
+                    .fold(closure.env.clone(), |env, (param, ty)| env.bind_type(param.var(), ty));
+                let result = self.eval(closure.body, &env)?;
+                self.apply_types(result, rest)
+            },
+            Value::Builtin(builtin) if needs_types(builtin.builtin) => Ok(Value::Builtin(Rc::new(BuiltinValue {
+                builtin: builtin.builtin,
+                types: builtin.types.iter().cloned().chain(types).collect(),
+                args: builtin.args.clone(),
+            }))),
+            other => Ok(other),
+        }
+    }
+
+    /// Evaluate a top-level value definition.
+    pub fn global(&self, name: &DamlValueName<'_>) -> DamlInterpreterResult<Value<'a>> {
+        let def = self.archive.value_by_name(name).ok_or_else(|| {
+            DamlInterpreterError::UnknownValue(format!("{}:{}", name.module_path().join("."), name.name()))
+        })?;
+        self.global_def(def)
+    }
+
+    /// Evaluate a top-level value definition.
+    pub fn global_def(&self, def: &'a DamlDefValue<'a>) -> DamlInterpreterResult<Value<'a>> {
+        let key: *const DamlDefValue<'a> = def;
+        if let Some(value) = self.globals.borrow().get(&key) {
+            return Ok(value.clone());
+        }
+        if !self.evaluating.borrow_mut().insert(key) {
+            return Err(DamlInterpreterError::Unsupported(format!("recursive value definition {}", def.name())));
+        }
+        let result = self.eval(def.expr(), &Env::default());
+        self.evaluating.borrow_mut().remove(&key);
+        match result {
+            Ok(value) => {
+                self.globals.borrow_mut().insert(key, value.clone());
+                Ok(value)
+            },
+            Err(failure) => Err(unhandled(failure)),
+        }
+    }
+
+    /// Raise an `ArithmeticError` exception, or abort evaluation if the archive does not define it.
+    pub fn arithmetic_error(&self, message: impl Into<String>) -> Failure<'a> {
+        let message = message.into();
+        match self.arithmetic_error {
+            Some(data) if data.fields().len() == 1 =>
+                Failure::Throw(Rc::new((data_type(data), Value::record(data, vec![Value::text(message)])))),
+            _ => Failure::Error(DamlInterpreterError::ArithmeticError(message)),
+        }
+    }
+
+    pub fn data(&self, name: &DamlTyConName<'_>) -> DamlInterpreterResult<&'a DamlData<'a>> {
+        self.archive.data_by_tycon_name(name).ok_or_else(|| DamlInterpreterError::UnknownType(render_tycon_name(name)))
+    }
+
+    fn eval_case(&self, case: &'a DamlCase<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let scrut = self.eval(case.scrut(), env)?;
+        for alt in case.alts() {
+            if let Some(env) = match_alt(&scrut, alt.sum(), env)? {
+                return self.eval(alt.body(), &env);
+            }
+        }
+        Err(DamlInterpreterError::MatchFailure(scrut.to_string()).into())
+    }
+}
+
+fn eval_var<'a>(var: &str, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+    Ok(env.lookup(var).cloned().ok_or_else(|| DamlInterpreterError::UnknownVariable(var.to_owned()))?)
+}
+
+const fn eval_prim_con<'a>(prim_con: DamlPrimCon) -> Value<'a> {
+    match prim_con {
+        DamlPrimCon::Unit => Value::Unit,
+        DamlPrimCon::True => Value::Bool(true),
+        DamlPrimCon::False => Value::Bool(false),
+    }
+}
+
+fn eval_abs<'a>(abs: &'a DamlAbs<'a>, env: &Env<'a>) -> Value<'a> {
+    Value::Closure(Rc::new(Closure {
+        env: env.clone(),
+        params: abs.params(),
+        body: abs.body(),
+        applied: vec![],
+    }))
+}
+
+fn eval_ty_abs<'a>(ty_abs: &'a DamlTyAbs<'a>, env: &Env<'a>) -> Value<'a> {
+    Value::TyClosure(Rc::new(TyClosure {
+        env: env.clone(),
+        params: ty_abs.params(),
+        body: ty_abs.body(),
+        applied: vec![],
+    }))
+}
+
+fn eval_update<'a>(update: &'a DamlUpdate<'a>, env: &Env<'a>) -> Value<'a> {
+    Value::Update(Rc::new(UpdateValue {
+        env: env.clone(),
+        update,
+    }))
+}
+
+fn eval_prim_lit<'a>(lit: &'a DamlPrimLit<'a>) -> Eval<'a, Value<'a>> {
+    Ok(match lit {
+        DamlPrimLit::Int64(i) => Value::Int64(*i),
+        DamlPrimLit::Text(text) => Value::text(text.as_ref()),
+        DamlPrimLit::Party(party) => Value::Party(party.as_ref().into()),
+        DamlPrimLit::Date(date) => Value::Date(*date),
+        DamlPrimLit::Timestamp(timestamp) => Value::Timestamp(*timestamp),
+        DamlPrimLit::Numeric(numeric) => Value::Numeric(BigDecimal::from_str(numeric).map_err(|_| {
+            Failure::from(DamlInterpreterError::UnexpectedValue("a numeric literal".to_owned(), numeric.to_string()))
+        })?),
+        DamlPrimLit::RoundingMode(mode) => Value::RoundingMode(mode),
+    })
+}
+
+/// Convert an uncaught failure into an error.
+pub fn unhandled(failure: Failure<'_>) -> DamlInterpreterError {
+    match failure {
+        Failure::Throw(exception) => DamlInterpreterError::UnhandledException(exception_message(&exception.1)),
+        Failure::Error(error) => error,
+    }
+}
+
+pub fn expect_template<'a>(data: &'a DamlData<'a>) -> DamlInterpreterResult<&'a DamlTemplate<'a>> {
+    match data {
+        DamlData::Template(template) => Ok(template),
+        _ => Err(DamlInterpreterError::UnexpectedValue("a template".to_owned(), render_data_name(data))),
+    }
+}
+
+/// The instance of `interface` implemented by the template of `data`, if any.
+pub fn interface_instance<'a>(
+    data: &'a DamlData<'a>,
+    interface: &DamlTyConName<'_>,
+) -> Option<&'a DamlInterfaceInstance<'a>> {
+    match data {
+        DamlData::Template(template) =>
+            template.implements().iter().find(|instance| tycon_name_eq(instance.interface(), interface)),
+        _ => None,
+    }
+}
+
+fn expect_interface_instance<'a>(
+    value: &Value<'a>,
+    interface: &DamlTyConName<'_>,
+) -> DamlInterpreterResult<(&'a DamlTemplate<'a>, &'a DamlInterfaceInstance<'a>)> {
+    let data = value.as_record()?.data;
+    let template = expect_template(data)?;
+    let instance = interface_instance(data, interface).ok_or_else(|| {
+        DamlInterpreterError::InterfaceNotImplemented(render_data_name(data), render_tycon_name(interface))
+    })?;
+    Ok((template, instance))
+}
+
+/// Match a value against a case alternative pattern, returning the extended environment if it matches.
+fn match_alt<'a>(
+    scrut: &Value<'a>,
+    sum: &'a DamlCaseAltSum<'a>,
+    env: &Env<'a>,
+) -> DamlInterpreterResult<Option<Env<'a>>> {
+    Ok(match (sum, scrut) {
+        (DamlCaseAltSum::Default, _) | (DamlCaseAltSum::PrimCon(DamlPrimCon::Unit), Value::Unit) => Some(env.clone()),
+        (DamlCaseAltSum::Variant(alt), Value::Variant(variant)) =>
+            (variant.constructor() == alt.variant()).then(|| env.bind(alt.binder(), variant.value.clone())),
+        (DamlCaseAltSum::Enum(alt), Value::Enum(data, rank)) =>
+            (enum_constructor(data, *rank) == alt.constructor()).then(|| env.clone()),
+        (DamlCaseAltSum::PrimCon(DamlPrimCon::True), Value::Bool(b)) => b.then(|| env.clone()),
+        (DamlCaseAltSum::PrimCon(DamlPrimCon::False), Value::Bool(b)) => (!b).then(|| env.clone()),
+        (DamlCaseAltSum::Nil, Value::List(list)) => list.is_empty().then(|| env.clone()),
+        (DamlCaseAltSum::Cons(alt), Value::List(list)) => list
+            .split_first()
+            .map(|(head, tail)| env.bind(alt.var_head(), head.clone()).bind(alt.var_tail(), Value::List(tail))),
+        (DamlCaseAltSum::OptionalNone, Value::Optional(opt)) => opt.is_none().then(|| env.clone()),
+        (DamlCaseAltSum::OptionalSome(alt), Value::Optional(opt)) =>
+            opt.as_ref().map(|value| env.bind(alt.var_body(), value.as_ref().clone())),
+        (_, other) => return other.unexpected("a value matching the case alternative"),
+    })
+}
+
+/// Lookup a field of a struct value.
+pub fn struct_field<'v, 'a>(value: &'v Value<'a>, field: &str) -> DamlInterpreterResult<&'v Value<'a>> {
+    value
+        .as_struct()?
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, value)| value)
+        .ok_or_else(|| DamlInterpreterError::UnexpectedValue(format!("struct field {field}"), value.to_string()))
+}
+
+/// The message of an exception value.
+///
+/// The exception definitions of a package are not part of the element model and so the `message` field of the
+/// exception record is used if it has one, otherwise the exception value is rendered.
+pub fn exception_message(value: &Value<'_>) -> String {
+    match value {
+        Value::Record(record) => match record.field("message") {
+            Ok(Value::Text(message)) => message.to_string(),
+            _ => value.to_string(),
+        },
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use daml_lf::element::{
+        DamlBinding, DamlCaseAlt, DamlCaseAltCons, DamlCaseAltOptionalSome, DamlFieldWithExpr, DamlOptionalSome,
+        DamlTyCon, DamlVarWithType,
+    };
+    use daml_lf::DarFile;
+    use std::borrow::Cow;
+    use std::error::Error;
+    use std::thread;
+
+    type TestResult = Result<(), Box<dyn Error>>;
+
+    fn unit() -> DamlExpr<'static> {
+        DamlExpr::PrimCon(DamlPrimCon::Unit)
+    }
+
+    fn var<'a>(name: &'static str) -> DamlExpr<'a> {
+        DamlExpr::Var(Cow::Borrowed(name))
+    }
+
+    fn binder<'a>(name: &'static str) -> DamlVarWithType<'a> {
+        DamlVarWithType::new(DamlType::Unit, Cow::Borrowed(name))
+    }
+
+    fn lambda(body: DamlExpr<'static>) -> DamlExpr<'static> {
+        DamlExpr::Abs(DamlAbs::new(vec![binder("x")], Box::new(body)))
+    }
+
+    fn some(body: DamlExpr<'static>) -> DamlExpr<'static> {
+        DamlExpr::OptionalSome(DamlOptionalSome::new(DamlType::Unit, Box::new(body)))
+    }
+
+    fn app(fun: DamlExpr<'static>, arg: DamlExpr<'static>) -> DamlExpr<'static> {
+        DamlExpr::App(DamlApp::new(Box::new(fun), vec![arg]))
+    }
+
+    fn let_x(bound: DamlExpr<'static>, body: DamlExpr<'static>) -> DamlExpr<'static> {
+        let_in(vec![("x", bound)], body)
+    }
+
+    fn let_in<'a>(bindings: Vec<(&'static str, DamlExpr<'a>)>, body: DamlExpr<'a>) -> DamlExpr<'a> {
+        let bindings = bindings.into_iter().map(|(name, bound)| DamlBinding::new(binder(name), bound)).collect();
+        DamlExpr::Let(DamlBlock::new(bindings, Box::new(body)))
+    }
+
+    fn int<'a>(i: i64) -> DamlExpr<'a> {
+        DamlExpr::PrimLit(DamlPrimLit::Int64(i))
+    }
+
+    fn add<'a>(x: DamlExpr<'a>, y: DamlExpr<'a>) -> DamlExpr<'a> {
+        DamlExpr::App(DamlApp::new(Box::new(DamlExpr::Builtin(DamlBuiltinFunction::AddInt64)), vec![x, y]))
+    }
+
+    fn case(scrut: DamlExpr<'static>, alts: Vec<(DamlCaseAltSum<'static>, DamlExpr<'static>)>) -> DamlExpr<'static> {
+        let alts = alts.into_iter().map(|(sum, body)| DamlCaseAlt::new(body, sum)).collect();
+        DamlExpr::Case(DamlCase::new(Box::new(scrut), alts))
+    }
+
+    /// Evaluate an expression over an archive, rendering the resulting value or error.
+    fn render<'a>(archive: &'a DamlArchive<'a>, expr: &'a DamlExpr<'a>) -> String {
+        match Machine::new(archive).eval(expr, &Env::default()) {
+            Ok(value) => value.to_string(),
+            Err(Failure::Error(error)) => format!("error: {error}"),
+            Err(Failure::Throw(exception)) => format!("throw: {}", exception.1),
+        }
+    }
+
+    /// Nest `depth` expressions produced by `wrap` around `()`.
+    fn nested(depth: usize, wrap: fn(DamlExpr<'static>) -> DamlExpr<'static>) -> DamlExpr<'static> {
+        (0..depth).fold(unit(), |expr, _| wrap(expr))
+    }
+
+    fn error(result: Eval<'_, Value<'_>>) -> Option<DamlInterpreterError> {
+        match result {
+            Err(Failure::Error(error)) => Some(error),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_let() {
+        let archive = DamlArchive::default();
+        let sequential = let_in(vec![("x", int(1)), ("y", add(var("x"), int(1)))], add(var("x"), var("y")));
+        assert_eq!("3", render(&archive, &sequential));
+        let shadowed = let_in(vec![("x", int(1)), ("x", add(var("x"), int(10)))], var("x"));
+        assert_eq!("11", render(&archive, &shadowed));
+        let scoped = let_in(vec![("x", let_in(vec![("y", int(1))], var("y")))], var("y"));
+        assert_eq!("error: unknown variable y", render(&archive, &scoped));
+    }
+
+    #[test]
+    fn test_case() {
+        let archive = DamlArchive::default();
+        let optional = |scrut| {
+            case(scrut, vec![
+                (DamlCaseAltSum::OptionalNone, int(0)),
+                (DamlCaseAltSum::OptionalSome(DamlCaseAltOptionalSome::new(Cow::Borrowed("v"))), var("v")),
+            ])
+        };
+        let none = optional(DamlExpr::OptionalNone(DamlType::Int64));
+        assert_eq!("0", render(&archive, &none));
+        let some = optional(DamlExpr::OptionalSome(DamlOptionalSome::new(DamlType::Int64, Box::new(int(5)))));
+        assert_eq!("5", render(&archive, &some));
+        let list = DamlExpr::Cons(DamlCons::new(
+            DamlType::Int64,
+            vec![int(1), int(2)],
+            Box::new(DamlExpr::Nil(DamlType::Int64)),
+        ));
+        let cons = DamlCaseAltSum::Cons(DamlCaseAltCons::new(Cow::Borrowed("h"), Cow::Borrowed("t")));
+        let head = case(list.clone(), vec![(DamlCaseAltSum::Nil, int(0)), (cons.clone(), var("h"))]);
+        assert_eq!("1", render(&archive, &head));
+        let tail = case(list, vec![(cons, var("t")), (DamlCaseAltSum::Default, int(0))]);
+        assert_eq!("[2]", render(&archive, &tail));
+        let unmatched =
+            case(DamlExpr::PrimCon(DamlPrimCon::False), vec![(DamlCaseAltSum::PrimCon(DamlPrimCon::True), int(1))]);
+        assert_eq!("error: no case alternative matched False", render(&archive, &unmatched));
+    }
+
+    #[test]
+    fn test_rec_update() -> TestResult {
+        let dar = DarFile::from_file("../resources/testing_types_sandbox/TestingTypes-latest.dar")?;
+        dar.apply(|archive| {
+            let ping = || DamlTyCon::new_absolute(archive.main_package_id(), &["Fuji", "PingPong"], "Ping");
+            let party = |name| DamlExpr::PrimLit(DamlPrimLit::Party(Cow::Borrowed(name)));
+            let record = DamlExpr::RecCon(DamlRecCon::new(ping(), vec![
+                DamlFieldWithExpr::new(Cow::Borrowed("sender"), party("Alice")),
+                DamlFieldWithExpr::new(Cow::Borrowed("receiver"), party("Bob")),
+                DamlFieldWithExpr::new(Cow::Borrowed("count"), int(1)),
+            ]));
+            let update = |field, value| {
+                DamlExpr::RecUpd(DamlRecUpd::new(ping(), Box::new(var("p")), Box::new(value), Cow::Borrowed(field)))
+            };
+            let project =
+                |record, field| DamlExpr::RecProj(DamlRecProj::new(ping(), Box::new(record), Cow::Borrowed(field)));
+            let updated = let_in(vec![("p", record.clone())], update("count", add(project(var("p"), "count"), int(1))));
+            assert_eq!("Ping {sender = 'Alice', receiver = 'Bob', count = 2}", render(archive, &updated));
+            let original =
+                let_in(vec![("p", record.clone()), ("q", update("count", int(5)))], project(var("p"), "count"));
+            assert_eq!("1", render(archive, &original));
+            let unknown = let_in(vec![("p", record)], update("missing", int(5)));
+            assert!(render(archive, &unknown).starts_with("error: "));
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_depth_limit_exceeded() {
+        let archive = DamlArchive::default();
+        let (within, beyond) = (nested(9, some), nested(10, some));
+        let machine = Machine::new(&archive).with_max_depth(10);
+        assert!(machine.eval(&within, &Env::default()).is_ok());
+        assert_eq!(Some(DamlInterpreterError::DepthLimitExceeded(10)), error(machine.eval(&beyond, &Env::default())));
+        assert!(machine.eval(&within, &Env::default()).is_ok());
+    }
+
+    #[test]
+    fn test_step_limit_exceeded() {
+        let archive = DamlArchive::default();
+        let (expr, within) = (nested(5, |body| let_x(body, var("x"))), nested(9, some));
+        let machine = Machine::new(&archive).with_max_steps(10);
+        assert_eq!(Some(DamlInterpreterError::StepLimitExceeded(10)), error(machine.eval(&expr, &Env::default())));
+        machine.reset_steps();
+        assert!(machine.eval(&within, &Env::default()).is_ok());
+    }
+
+    #[test]
+    fn test_default_depth_limit_on_small_stack() {
+        let wraps: [fn(DamlExpr<'static>) -> DamlExpr<'static>; 4] =
+            [some, |body| app(lambda(body), unit()), |arg| app(lambda(var("x")), arg), |bound| let_x(bound, var("x"))];
+        for wrap in wraps {
+            let result = thread::Builder::new()
+                .stack_size(2 * 1024 * 1024)
+                .spawn(move || {
+                    let expr = nested(DEFAULT_MAX_DEPTH * 2, wrap);
+                    let archive = DamlArchive::default();
+                    error(Machine::new(&archive).eval(&expr, &Env::default()))
+                })
+                .unwrap()
+                .join()
+                .unwrap();
+            assert_eq!(Some(DamlInterpreterError::DepthLimitExceeded(DEFAULT_MAX_DEPTH)), result);
+        }
+    }
+}
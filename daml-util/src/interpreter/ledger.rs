@@ -0,0 +1,874 @@
+use crate::interpreter::convert::{from_daml_data, from_daml_value, to_daml_record, to_daml_value};
+use crate::interpreter::error::{DamlInterpreterError, DamlInterpreterResult};
+use crate::interpreter::eval::{expect_template, interface_instance, unhandled, Eval, Failure, Machine};
+use crate::interpreter::types::{data_identifier, render_data_name, render_tycon_name};
+use crate::interpreter::value::{compare, sorted_parties, Env, Value};
+use daml_grpc::data::command::DamlCommand;
+use daml_grpc::data::event::{DamlArchivedEvent, DamlCreatedEvent, DamlExercisedEvent};
+use daml_grpc::data::value::{DamlRecord, DamlValue};
+use daml_grpc::data::DamlIdentifier;
+use daml_lf::element::{
+    DamlBlock, DamlChoice, DamlCreate, DamlData, DamlDefKey, DamlExercise, DamlExerciseByKey, DamlExerciseInterface,
+    DamlExpr, DamlFetch, DamlFetchInterface, DamlInterface, DamlRetrieveByKey, DamlTemplate, DamlTryCatch,
+    DamlTyConName, DamlUpdate,
+};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+use std::ptr;
+use std::rc::Rc;
+
+/// An active contract held in a [`DamlContractStore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamlStoredContract {
+    contract_id: String,
+    template_id: DamlIdentifier,
+    arguments: DamlRecord,
+}
+
+impl DamlStoredContract {
+    pub fn new(
+        contract_id: impl Into<String>,
+        template_id: impl Into<DamlIdentifier>,
+        arguments: impl Into<DamlRecord>,
+    ) -> Self {
+        Self {
+            contract_id: contract_id.into(),
+            template_id: template_id.into(),
+            arguments: arguments.into(),
+        }
+    }
+
+    pub fn contract_id(&self) -> &str {
+        &self.contract_id
+    }
+
+    pub const fn template_id(&self) -> &DamlIdentifier {
+        &self.template_id
+    }
+
+    pub const fn arguments(&self) -> &DamlRecord {
+        &self.arguments
+    }
+}
+
+impl From<&DamlCreatedEvent> for DamlStoredContract {
+    fn from(event: &DamlCreatedEvent) -> Self {
+        Self::new(event.contract_id(), event.template_id().clone(), event.create_arguments().clone())
+    }
+}
+
+/// An in-memory set of active contracts against which commands can be simulated.
+///
+/// A store may be seeded with contracts read from a ledger, such as the created events of the active contract set,
+/// and is updated by [`DamlContractStore::apply`] with the events of a [`DamlSimulation`].
+#[derive(Debug, Clone, Default)]
+pub struct DamlContractStore {
+    contracts: BTreeMap<String, DamlStoredContract>,
+    transaction_count: u64,
+}
+
+impl DamlContractStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an active contract to the store, replacing any existing contract with the same contract id.
+    pub fn add(&mut self, contract: impl Into<DamlStoredContract>) {
+        let contract = contract.into();
+        self.contracts.insert(contract.contract_id.clone(), contract);
+    }
+
+    /// Remove an active contract from the store.
+    pub fn remove(&mut self, contract_id: &str) -> Option<DamlStoredContract> {
+        self.contracts.remove(contract_id)
+    }
+
+    pub fn contract(&self, contract_id: &str) -> Option<&DamlStoredContract> {
+        self.contracts.get(contract_id)
+    }
+
+    pub fn contracts(&self) -> impl Iterator<Item = &DamlStoredContract> {
+        self.contracts.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.contracts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+    }
+
+    /// Apply the events of a simulated transaction, adding the created contracts and removing the archived contracts.
+    pub fn apply(&mut self, simulation: &DamlSimulation) {
+        for event in simulation.events() {
+            match event {
+                DamlSimulatedEvent::Created(created) => self.add(created),
+                DamlSimulatedEvent::Archived(archived) => {
+                    self.remove(archived.contract_id());
+                },
+                DamlSimulatedEvent::Exercised(_) => {},
+            }
+        }
+        self.transaction_count += 1;
+    }
+}
+
+/// An event produced by a simulated transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DamlSimulatedEvent {
+    Created(DamlCreatedEvent),
+    Archived(DamlArchivedEvent),
+    Exercised(DamlExercisedEvent),
+}
+
+/// The outcome of simulating a command.
+///
+/// The events are in execution order; an exercised event is followed by the archived event of its contract, if the
+/// choice is consuming, and then by the events of its consequences.  Events within rolled back `try` blocks are
+/// omitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamlSimulation {
+    result: DamlValue,
+    events: Vec<DamlSimulatedEvent>,
+}
+
+impl DamlSimulation {
+    /// The result of the command, the contract id for a create command or the choice result for an exercise command.
+    pub const fn result(&self) -> &DamlValue {
+        &self.result
+    }
+
+    pub fn events(&self) -> &[DamlSimulatedEvent] {
+        &self.events
+    }
+
+    pub fn created(&self) -> impl Iterator<Item = &DamlCreatedEvent> {
+        self.events.iter().filter_map(|event| match event {
+            DamlSimulatedEvent::Created(created) => Some(created),
+            _ => None,
+        })
+    }
+
+    pub fn archived(&self) -> impl Iterator<Item = &DamlArchivedEvent> {
+        self.events.iter().filter_map(|event| match event {
+            DamlSimulatedEvent::Archived(archived) => Some(archived),
+            _ => None,
+        })
+    }
+
+    pub fn exercised(&self) -> impl Iterator<Item = &DamlExercisedEvent> {
+        self.events.iter().filter_map(|event| match event {
+            DamlSimulatedEvent::Exercised(exercised) => Some(exercised),
+            _ => None,
+        })
+    }
+}
+
+/// Simulate a command as a single transaction against a contract store.
+pub fn simulate(
+    machine: &Machine<'_>,
+    store: &DamlContractStore,
+    ledger_time: i64,
+    command: &DamlCommand,
+) -> DamlInterpreterResult<DamlSimulation> {
+    let mut transaction = Transaction::new(machine, store, ledger_time);
+    let result = transaction.command(command).map_err(unhandled)?;
+    Ok(DamlSimulation {
+        result: to_daml_value(&result)?,
+        events: transaction.into_events(),
+    })
+}
+
+/// A contract which is active within a transaction.
+#[derive(Clone)]
+struct Contract<'a> {
+    data: &'a DamlData<'a>,
+    template: &'a DamlTemplate<'a>,
+    argument: Value<'a>,
+}
+
+/// The choice being exercised and the variable bound to the contract within the choice body.
+struct ChoiceTarget<'a> {
+    choice: &'a DamlChoice<'a>,
+    param: &'a str,
+}
+
+struct Node {
+    event_id: String,
+    parent: Option<usize>,
+    event: NodeEvent,
+}
+
+enum NodeEvent {
+    Created(DamlCreatedEvent),
+    Archived(DamlArchivedEvent),
+    Exercised(ExerciseNode),
+}
+
+struct ExerciseNode {
+    contract_id: String,
+    template_id: DamlIdentifier,
+    choice: String,
+    argument: DamlValue,
+    actors: Vec<String>,
+    consuming: bool,
+    witnesses: Vec<String>,
+    result: Option<DamlValue>,
+}
+
+/// The state of a transaction which can be restored when a `try` block is rolled back.
+struct Snapshot<'a> {
+    created: BTreeMap<Rc<str>, Contract<'a>>,
+    consumed: HashSet<Rc<str>>,
+    nodes: usize,
+}
+
+struct Transaction<'m, 'a> {
+    machine: &'m Machine<'a>,
+    store: &'m DamlContractStore,
+    ledger_time: i64,
+    number: u64,
+    next_node: usize,
+    created: BTreeMap<Rc<str>, Contract<'a>>,
+    consumed: HashSet<Rc<str>>,
+    nodes: Vec<Node>,
+    parent: Option<usize>,
+}
+
+impl<'m, 'a> Transaction<'m, 'a> {
+    fn new(machine: &'m Machine<'a>, store: &'m DamlContractStore, ledger_time: i64) -> Self {
+        Self {
+            machine,
+            store,
+            ledger_time,
+            number: store.transaction_count,
+            next_node: 0,
+            created: BTreeMap::new(),
+            consumed: HashSet::new(),
+            nodes: vec![],
+            parent: None,
+        }
+    }
+
+    fn command(&mut self, command: &DamlCommand) -> Eval<'a, Value<'a>> {
+        let archive = self.machine.archive();
+        match command {
+            DamlCommand::Create(create) => {
+                let data = self.data_by_identifier(create.template_id())?;
+                let argument =
+                    from_daml_data(archive, &DamlValue::Record(create.create_arguments().clone()), data, &[])?;
+                self.create(data, argument)
+            },
+            DamlCommand::Exercise(exercise) => {
+                let data = self.data_by_identifier(exercise.template_id())?;
+                let target = template_choice(data, exercise.choice())?;
+                let argument = from_daml_value(archive, exercise.choice_argument(), target.choice.argument_type())?;
+                self.exercise(Some(data), None, exercise.contract_id().into(), &target, &argument)
+            },
+            DamlCommand::ExerciseByKeyCommand(exercise) => {
+                let data = self.data_by_identifier(exercise.template_id())?;
+                let key_type = expect_template(data)?.key().map(DamlDefKey::ty).ok_or_else(|| {
+                    DamlInterpreterError::UnexpectedValue("a template with a key".to_owned(), render_data_name(data))
+                })?;
+                let key = from_daml_value(archive, exercise.contract_key(), key_type)?;
+                let cid = self.fetch_key(data, &key)?;
+                let target = template_choice(data, exercise.choice())?;
+                let argument = from_daml_value(archive, exercise.choice_argument(), target.choice.argument_type())?;
+                self.exercise(Some(data), None, cid, &target, &argument)
+            },
+            DamlCommand::CreateAndExercise(create_and_exercise) => {
+                let data = self.data_by_identifier(create_and_exercise.template_id())?;
+                let argument = from_daml_data(
+                    archive,
+                    &DamlValue::Record(create_and_exercise.create_arguments().clone()),
+                    data,
+                    &[],
+                )?;
+                let cid = self.create(data, argument)?;
+                let target = template_choice(data, create_and_exercise.choice())?;
+                let choice_argument =
+                    from_daml_value(archive, create_and_exercise.choice_argument(), target.choice.argument_type())?;
+                self.exercise(Some(data), None, cid.as_contract_id()?.clone(), &target, &choice_argument)
+            },
+        }
+    }
+
+    /// Run an `Update` value.
+    fn run(&mut self, update: &Value<'a>) -> Eval<'a, Value<'a>> {
+        match update {
+            Value::Update(update) => self.run_update(update.update, &update.env),
+            other => other.unexpected("Update").map_err(Failure::from),
+        }
+    }
+
+    /// Evaluate an expression of type `Update` and run the resulting update.
+    fn run_expr(&mut self, expr: &'a DamlExpr<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let update = self.machine.eval(expr, env)?;
+        self.run(&update)
+    }
+
+    /// Run an update in the given environment.
+    ///
+    /// Each nested update counts against the depth budget of the `Machine` in the same way as nested expressions.
+    fn run_update(&mut self, update: &'a DamlUpdate<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        self.machine.enter()?;
+        let result = self.run_update_kind(update, env);
+        self.machine.leave();
+        result
+    }
+
+    // As with `Machine::eval`, each update with a non-trivial evaluation is handled by a separate method to keep the
+    // stack frame of this (deeply recursive) function small.
+    fn run_update_kind(&mut self, update: &'a DamlUpdate<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        match update {
+            DamlUpdate::Pure(pure) => self.machine.eval(pure.expr(), env),
+            DamlUpdate::Block(block) => self.run_block(block, env),
+            DamlUpdate::Create(create) => self.run_create(create, env),
+            DamlUpdate::Exercise(exercise) => self.run_exercise(exercise, env),
+            DamlUpdate::ExerciseByKey(exercise) => self.run_exercise_by_key(exercise, env),
+            DamlUpdate::ExerciseInterface(exercise) => self.run_exercise_interface(exercise, env),
+            DamlUpdate::Fetch(fetch) => self.run_fetch(fetch, env),
+            DamlUpdate::FetchInterface(fetch) => self.run_fetch_interface(fetch, env),
+            DamlUpdate::FetchByKey(retrieve) => self.run_fetch_by_key(retrieve, env),
+            DamlUpdate::LookupByKey(retrieve) => self.run_lookup_by_key(retrieve, env),
+            DamlUpdate::GetTime => Ok(Value::Timestamp(self.ledger_time)),
+            DamlUpdate::EmbedExpr(embed) => self.run_expr(embed.body(), env),
+            DamlUpdate::TryCatch(try_catch) => self.run_try_catch(try_catch, env),
+        }
+    }
+
+    fn run_block(&mut self, block: &'a DamlBlock<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let mut env = env.clone();
+        for binding in block.bindings() {
+            let value = self.run_expr(binding.bound(), &env)?;
+            env = env.bind(binding.binder().var(), value);
+        }
+        self.run_expr(block.body(), &env)
+    }
+
+    fn run_create(&mut self, create: &'a DamlCreate<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let data = self.machine.data(create.template())?;
+        let argument = self.machine.eval(create.expr(), env)?;
+        self.create(data, argument)
+    }
+
+    fn run_exercise(&mut self, exercise: &'a DamlExercise<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let data = self.machine.data(exercise.template())?;
+        let target = template_choice(data, exercise.choice())?;
+        let cid = self.machine.eval(exercise.cid(), env)?;
+        let argument = self.machine.eval(exercise.arg(), env)?;
+        self.exercise(Some(data), None, cid.as_contract_id()?.clone(), &target, &argument)
+    }
+
+    fn run_exercise_by_key(&mut self, exercise: &'a DamlExerciseByKey<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let data = self.machine.data(exercise.template())?;
+        let target = template_choice(data, exercise.choice())?;
+        let key = self.machine.eval(exercise.key(), env)?;
+        let argument = self.machine.eval(exercise.arg(), env)?;
+        let cid = self.fetch_key(data, &key)?;
+        self.exercise(Some(data), None, cid, &target, &argument)
+    }
+
+    fn run_exercise_interface(
+        &mut self,
+        exercise: &'a DamlExerciseInterface<'a>,
+        env: &Env<'a>,
+    ) -> Eval<'a, Value<'a>> {
+        let interface = self.interface(exercise.interface())?;
+        let choice = interface.choices().iter().find(|choice| choice.name() == exercise.choice()).ok_or_else(|| {
+            DamlInterpreterError::UnknownChoice(render_tycon_name(exercise.interface()), exercise.choice().to_owned())
+        })?;
+        let target = ChoiceTarget {
+            choice,
+            param: interface.param(),
+        };
+        let cid = self.machine.eval(exercise.cid(), env)?;
+        let argument = self.machine.eval(exercise.arg(), env)?;
+        self.exercise(None, Some(exercise.interface()), cid.as_contract_id()?.clone(), &target, &argument)
+    }
+
+    fn run_fetch(&self, fetch: &'a DamlFetch<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let data = self.machine.data(fetch.template())?;
+        let cid = self.machine.eval(fetch.cid(), env)?;
+        let cid = cid.as_contract_id()?;
+        let contract = self.contract(cid)?;
+        check_template(cid, &contract, data)?;
+        Ok(contract.argument)
+    }
+
+    fn run_fetch_interface(&self, fetch: &'a DamlFetchInterface<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let cid = self.machine.eval(fetch.cid(), env)?;
+        let cid = cid.as_contract_id()?;
+        let contract = self.contract(cid)?;
+        check_interface(cid, &contract, fetch.interface())?;
+        Ok(contract.argument)
+    }
+
+    fn run_fetch_by_key(&self, retrieve: &'a DamlRetrieveByKey<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let data = self.machine.data(retrieve.template())?;
+        let key = self.machine.eval(retrieve.key(), env)?;
+        let cid = self.fetch_key(data, &key)?;
+        let contract = self.contract(&cid)?;
+        Ok(Value::Struct(Rc::new(vec![("contractId", Value::ContractId(cid)), ("contract", contract.argument)])))
+    }
+
+    fn run_lookup_by_key(&self, retrieve: &'a DamlRetrieveByKey<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let data = self.machine.data(retrieve.template())?;
+        let key = self.machine.eval(retrieve.key(), env)?;
+        Ok(Value::optional(self.lookup_key(data, &key)?.map(Value::ContractId)))
+    }
+
+    fn run_try_catch(&mut self, try_catch: &'a DamlTryCatch<'a>, env: &Env<'a>) -> Eval<'a, Value<'a>> {
+        let snapshot = self.snapshot();
+        match self.run_expr(try_catch.try_expr(), env) {
+            Err(Failure::Throw(exception)) => {
+                self.restore(snapshot);
+                let handler_env = env.bind(try_catch.var(), Value::AnyException(exception.clone()));
+                let handler = self.machine.eval(try_catch.catch_expr(), &handler_env)?;
+                handler.as_optional()?.map_or(Err(Failure::Throw(exception)), |handler| self.run(handler))
+            },
+            result => result,
+        }
+    }
+
+    /// Create a contract of the template `data`.
+    fn create(&mut self, data: &'a DamlData<'a>, argument: Value<'a>) -> Eval<'a, Value<'a>> {
+        let template = expect_template(data)?;
+        let env = Env::default().bind(template.param(), argument.clone());
+        if let Some(precond) = template.precond() {
+            if !self.machine.eval(precond, &env)?.as_bool()? {
+                return Err(
+                    DamlInterpreterError::PreconditionFailed(render_data_name(data), argument.to_string()).into()
+                );
+            }
+        }
+        let signatories = self.machine.eval(template.signatories(), &env)?.as_parties()?;
+        let observers = self.machine.eval(template.observers(), &env)?.as_parties()?;
+        let agreement = self.machine.eval(template.agreement(), &env)?.as_text()?.to_string();
+        let key = match template.key() {
+            Some(def_key) => {
+                let key = self.machine.eval(def_key.key_expr(), &env)?;
+                if self.lookup_key(data, &key)?.is_some() {
+                    return Err(
+                        DamlInterpreterError::DuplicateContractKey(render_data_name(data), key.to_string()).into()
+                    );
+                }
+                Some(to_daml_value(&key)?)
+            },
+            None => None,
+        };
+        let (event_id, cid) = self.next_ids();
+        let witnesses = sorted_parties(signatories.iter().chain(&observers).cloned().collect());
+        let event = DamlCreatedEvent::new(
+            event_id.as_str(),
+            cid.as_ref(),
+            data_identifier(data),
+            key,
+            to_daml_record(&argument)?,
+            witnesses,
+            signatories,
+            observers,
+            agreement,
+        );
+        self.push(event_id, NodeEvent::Created(event));
+        self.created.insert(cid.clone(), Contract {
+            data,
+            template,
+            argument,
+        });
+        Ok(Value::ContractId(cid))
+    }
+
+    /// Exercise a choice on a contract, which must be an instance of `template` or implement `interface` if given.
+    fn exercise(
+        &mut self,
+        template: Option<&'a DamlData<'a>>,
+        interface: Option<&DamlTyConName<'_>>,
+        cid: Rc<str>,
+        target: &ChoiceTarget<'a>,
+        argument: &Value<'a>,
+    ) -> Eval<'a, Value<'a>> {
+        let (index, env) = self.begin_exercise(template, interface, cid, target, argument)?;
+        let parent = self.parent.replace(index);
+        let result = self.run_expr(target.choice.update(), &env);
+        self.parent = parent;
+        let result = result?;
+        if let NodeEvent::Exercised(node) = &mut self.nodes[index].event {
+            node.result = Some(to_daml_value(&result)?);
+        }
+        Ok(result)
+    }
+
+    /// Check and record the exercise of a choice, returning the index of the exercise node and the environment in
+    /// which to run the choice.
+    ///
+    /// This is separate from `exercise` to keep the stack frame of nested exercises small.
+    fn begin_exercise(
+        &mut self,
+        template: Option<&'a DamlData<'a>>,
+        interface: Option<&DamlTyConName<'_>>,
+        cid: Rc<str>,
+        target: &ChoiceTarget<'a>,
+        argument: &Value<'a>,
+    ) -> Eval<'a, (usize, Env<'a>)> {
+        let contract = self.contract(&cid)?;
+        if let Some(data) = template {
+            check_template(&cid, &contract, data)?;
+        }
+        if let Some(interface) = interface {
+            check_interface(&cid, &contract, interface)?;
+        }
+        let choice = target.choice;
+        let env = Env::default()
+            .bind(target.param, contract.argument.clone())
+            .bind(choice.self_binder(), Value::ContractId(cid.clone()))
+            .bind(choice.argument_binder(), argument.clone());
+        let actors = self.machine.eval(choice.controllers(), &env)?.as_parties()?;
+        let choice_observers = self.machine.eval(choice.observers(), &env)?.as_parties()?;
+        let (signatories, stakeholders) = self.stakeholders(&contract)?;
+        let informees = if choice.consuming() {
+            &stakeholders
+        } else {
+            &signatories
+        };
+        let witnesses = sorted_parties(informees.iter().chain(&actors).chain(&choice_observers).cloned().collect());
+        let template_id = data_identifier(contract.data);
+        let (event_id, _) = self.next_ids();
+        let index = self.push(
+            event_id,
+            NodeEvent::Exercised(ExerciseNode {
+                contract_id: cid.to_string(),
+                template_id: template_id.clone(),
+                choice: choice.name().to_owned(),
+                argument: to_daml_value(argument)?,
+                actors,
+                consuming: choice.consuming(),
+                witnesses,
+                result: None,
+            }),
+        );
+        if choice.consuming() {
+            let (archived_id, _) = self.next_ids();
+            let archived = DamlArchivedEvent::new(archived_id.as_str(), cid.as_ref(), template_id, stakeholders);
+            self.push(archived_id, NodeEvent::Archived(archived));
+            self.consumed.insert(cid);
+        }
+        Ok((index, env))
+    }
+
+    /// The signatories and stakeholders of a contract.
+    fn stakeholders(&self, contract: &Contract<'a>) -> Eval<'a, (Vec<String>, Vec<String>)> {
+        let env = Env::default().bind(contract.template.param(), contract.argument.clone());
+        let signatories = self.machine.eval(contract.template.signatories(), &env)?.as_parties()?;
+        let observers = self.machine.eval(contract.template.observers(), &env)?.as_parties()?;
+        let stakeholders = sorted_parties(signatories.iter().chain(&observers).cloned().collect());
+        Ok((signatories, stakeholders))
+    }
+
+    /// Find an active contract by id.
+    fn contract(&self, cid: &str) -> DamlInterpreterResult<Contract<'a>> {
+        if self.consumed.contains(cid) {
+            return Err(DamlInterpreterError::ContractNotActive(cid.to_owned()));
+        }
+        if let Some(contract) = self.created.get(cid) {
+            return Ok(contract.clone());
+        }
+        let stored = self.store.contract(cid).ok_or_else(|| DamlInterpreterError::ContractNotFound(cid.to_owned()))?;
+        self.stored_contract(stored)
+    }
+
+    fn stored_contract(&self, stored: &DamlStoredContract) -> DamlInterpreterResult<Contract<'a>> {
+        let data = self.data_by_identifier(&stored.template_id)?;
+        let template = expect_template(data)?;
+        let argument = from_daml_data(self.machine.archive(), &DamlValue::Record(stored.arguments.clone()), data, &[])?;
+        Ok(Contract {
+            data,
+            template,
+            argument,
+        })
+    }
+
+    /// Find the active contract of the template `data` with the given key.
+    fn lookup_key(&self, data: &'a DamlData<'a>, key: &Value<'a>) -> Eval<'a, Option<Rc<str>>> {
+        let template = expect_template(data)?;
+        let def_key = template.key().ok_or_else(|| {
+            DamlInterpreterError::UnexpectedValue("a template with a key".to_owned(), render_data_name(data))
+        })?;
+        let has_key = |argument: &Value<'a>| -> Eval<'a, bool> {
+            let env = Env::default().bind(template.param(), argument.clone());
+            Ok(compare(&self.machine.eval(def_key.key_expr(), &env)?, key)? == Ordering::Equal)
+        };
+        for (cid, contract) in &self.created {
+            if ptr::eq(contract.data, data) && !self.consumed.contains(cid) && has_key(&contract.argument)? {
+                return Ok(Some(cid.clone()));
+            }
+        }
+        let template_id = data_identifier(data);
+        for stored in self.store.contracts() {
+            if stored.template_id == template_id && !self.consumed.contains(stored.contract_id.as_str()) {
+                let contract = self.stored_contract(stored)?;
+                if has_key(&contract.argument)? {
+                    return Ok(Some(stored.contract_id.as_str().into()));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn fetch_key(&self, data: &'a DamlData<'a>, key: &Value<'a>) -> Eval<'a, Rc<str>> {
+        self.lookup_key(data, key)?
+            .ok_or_else(|| DamlInterpreterError::ContractKeyNotFound(render_data_name(data), key.to_string()).into())
+    }
+
+    fn interface(&self, name: &DamlTyConName<'_>) -> DamlInterpreterResult<&'a DamlInterface<'a>> {
+        let module_path = name.module_path().collect::<Vec<_>>();
+        self.machine
+            .archive()
+            .package_by_id(name.package_id())
+            .and_then(|package| package.root_module().child_module_path(&module_path))
+            .and_then(|module| module.interface(name.data_name()))
+            .ok_or_else(|| DamlInterpreterError::UnknownType(render_tycon_name(name)))
+    }
+
+    fn data_by_identifier(&self, identifier: &DamlIdentifier) -> DamlInterpreterResult<&'a DamlData<'a>> {
+        let module_path = identifier.module_name().split('.').collect::<Vec<_>>();
+        self.machine
+            .archive()
+            .data(identifier.package_id(), &module_path, identifier.entity_name())
+            .ok_or_else(|| DamlInterpreterError::UnknownType(identifier.to_string()))
+    }
+
+    /// Allocate the next event id and the contract id of the contract created by that event.
+    fn next_ids(&mut self) -> (String, Rc<str>) {
+        let id = format!("#{}:{}", self.number, self.next_node);
+        self.next_node += 1;
+        let cid = Rc::from(id.as_str());
+        (id, cid)
+    }
+
+    fn push(&mut self, event_id: String, event: NodeEvent) -> usize {
+        self.nodes.push(Node {
+            event_id,
+            parent: self.parent,
+            event,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn snapshot(&self) -> Snapshot<'a> {
+        Snapshot {
+            created: self.created.clone(),
+            consumed: self.consumed.clone(),
+            nodes: self.nodes.len(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot<'a>) {
+        self.created = snapshot.created;
+        self.consumed = snapshot.consumed;
+        self.nodes.truncate(snapshot.nodes);
+    }
+
+    fn into_events(self) -> Vec<DamlSimulatedEvent> {
+        let children = |index: usize| {
+            self.nodes
+                .iter()
+                .filter(|node| node.parent == Some(index) && !matches!(node.event, NodeEvent::Archived(_)))
+                .map(|node| node.event_id.clone())
+                .collect::<Vec<_>>()
+        };
+        let child_ids = (0..self.nodes.len()).map(children).collect::<Vec<_>>();
+        self.nodes
+            .into_iter()
+            .zip(child_ids)
+            .map(|(node, child_ids)| match node.event {
+                NodeEvent::Created(created) => DamlSimulatedEvent::Created(created),
+                NodeEvent::Archived(archived) => DamlSimulatedEvent::Archived(archived),
+                NodeEvent::Exercised(exercise) => DamlSimulatedEvent::Exercised(DamlExercisedEvent::new(
+                    node.event_id,
+                    exercise.contract_id,
+                    exercise.template_id,
+                    exercise.choice,
+                    exercise.argument,
+                    exercise.actors,
+                    exercise.consuming,
+                    exercise.witnesses,
+                    child_ids,
+                    exercise.result.unwrap_or(DamlValue::Unit),
+                )),
+            })
+            .collect()
+    }
+}
+
+fn check_template<'a>(cid: &str, contract: &Contract<'a>, expected: &'a DamlData<'a>) -> DamlInterpreterResult<()> {
+    if ptr::eq(contract.data, expected) {
+        Ok(())
+    } else {
+        Err(DamlInterpreterError::TemplateMismatch(
+            cid.to_owned(),
+            render_data_name(contract.data),
+            render_data_name(expected),
+        ))
+    }
+}
+
+fn check_interface(cid: &str, contract: &Contract<'_>, interface: &DamlTyConName<'_>) -> DamlInterpreterResult<()> {
+    match interface_instance(contract.data, interface) {
+        Some(_) => Ok(()),
+        None => Err(DamlInterpreterError::InterfaceNotImplemented(cid.to_owned(), render_tycon_name(interface))),
+    }
+}
+
+fn template_choice<'a>(data: &'a DamlData<'a>, choice: &str) -> DamlInterpreterResult<ChoiceTarget<'a>> {
+    let template = expect_template(data)?;
+    let found = template
+        .choices()
+        .iter()
+        .find(|c| c.name() == choice)
+        .ok_or_else(|| DamlInterpreterError::UnknownChoice(render_data_name(data), choice.to_owned()))?;
+    Ok(ChoiceTarget {
+        choice: found,
+        param: template.param(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use daml_grpc::data::command::DamlExerciseCommand;
+    use daml_grpc::data::value::DamlRecordBuilder;
+    use daml_lf::element::{DamlArchive, DamlPrimCon};
+    use daml_lf::{DamlLfResult, DarFile};
+    use std::borrow::Cow;
+    use std::error::Error;
+
+    type TestResult = Result<(), Box<dyn Error>>;
+
+    const PING_ID: &str = "#1:0";
+
+    fn with_archive<F>(f: F) -> TestResult
+    where
+        F: FnOnce(&DamlArchive<'_>) -> TestResult,
+    {
+        let dar = DarFile::from_file("../resources/testing_types_sandbox/TestingTypes-latest.dar")?;
+        let result: DamlLfResult<TestResult> = dar.apply(f);
+        result?
+    }
+
+    /// A store holding a single `Ping` contract with id `PING_ID`.
+    fn ping_store(package_id: &str) -> DamlContractStore {
+        let arguments = DamlRecordBuilder::new()
+            .add_field("sender", DamlValue::new_party("Alice"))
+            .add_field("receiver", DamlValue::new_party("Bob"))
+            .add_field("count", DamlValue::new_int64(0))
+            .build();
+        let mut store = DamlContractStore::new();
+        store.add(DamlStoredContract::new(
+            PING_ID,
+            DamlIdentifier::new(package_id, "Fuji.PingPong", "Ping"),
+            arguments,
+        ));
+        store
+    }
+
+    fn ping_pong<'a>(package_id: &'a str, entity: &'a str) -> Box<DamlTyConName<'a>> {
+        Box::new(DamlTyConName::new_absolute(package_id, &["Fuji", "PingPong"], entity))
+    }
+
+    /// Fetch the contract bound to `cid` as an instance of the `Fuji.PingPong` template `entity`.
+    fn fetch<'a>(package_id: &'a str, entity: &'a str) -> DamlUpdate<'a> {
+        DamlUpdate::Fetch(DamlFetch::new(ping_pong(package_id, entity), Box::new(DamlExpr::Var(Cow::Borrowed("cid")))))
+    }
+
+    /// Exercise the `Archive` choice on the `Ping` contract bound to `cid`.
+    fn archive_ping(package_id: &str) -> DamlUpdate<'_> {
+        DamlUpdate::Exercise(DamlExercise::new(
+            ping_pong(package_id, "Ping"),
+            Box::new(DamlExpr::Var(Cow::Borrowed("cid"))),
+            Box::new(DamlExpr::PrimCon(DamlPrimCon::Unit)),
+            Cow::Borrowed("Archive"),
+        ))
+    }
+
+    fn cid_env<'a>(cid: &str) -> Env<'a> {
+        Env::default().bind("cid", Value::ContractId(Rc::from(cid)))
+    }
+
+    fn error<T>(result: Eval<'_, T>) -> Option<DamlInterpreterError> {
+        match result {
+            Err(Failure::Error(error)) => Some(error),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_fetch() -> TestResult {
+        with_archive(|archive| {
+            let package_id = archive.main_package_id();
+            let (fetch_ping, fetch_as_pong) = (fetch(package_id, "Ping"), fetch(package_id, "Pong"));
+            let store = ping_store(package_id);
+            let machine = Machine::new(archive);
+            let mut transaction = Transaction::new(&machine, &store, 0);
+            let ping = transaction.run_update(&fetch_ping, &cid_env(PING_ID)).map_err(|_| "fetch failed")?;
+            assert_eq!("Ping {sender = 'Alice', receiver = 'Bob', count = 0}", ping.to_string());
+            assert!(matches!(
+                error(transaction.run_update(&fetch_as_pong, &cid_env(PING_ID))),
+                Some(DamlInterpreterError::TemplateMismatch(..))
+            ));
+            assert_eq!(
+                Some(DamlInterpreterError::ContractNotFound("#9:9".to_owned())),
+                error(transaction.run_update(&fetch_ping, &cid_env("#9:9")))
+            );
+            assert!(transaction.into_events().is_empty());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_consumed_contract() -> TestResult {
+        with_archive(|archive| {
+            let package_id = archive.main_package_id();
+            let (fetch_ping, archive_ping) = (fetch(package_id, "Ping"), archive_ping(package_id));
+            let store = ping_store(package_id);
+            let machine = Machine::new(archive);
+            let mut transaction = Transaction::new(&machine, &store, 0);
+            transaction.run_update(&archive_ping, &cid_env(PING_ID)).map_err(|_| "archive failed")?;
+            let not_active = Some(DamlInterpreterError::ContractNotActive(PING_ID.to_owned()));
+            assert_eq!(not_active, error(transaction.run_update(&fetch_ping, &cid_env(PING_ID))));
+            assert_eq!(not_active, error(transaction.run_update(&archive_ping, &cid_env(PING_ID))));
+            assert!(store.contract(PING_ID).is_some());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_archive() -> TestResult {
+        with_archive(|archive| {
+            let package_id = archive.main_package_id();
+            let mut store = ping_store(package_id);
+            let machine = Machine::new(archive);
+            let command = DamlCommand::Exercise(DamlExerciseCommand::new(
+                DamlIdentifier::new(package_id, "Fuji.PingPong", "Ping"),
+                PING_ID,
+                "Archive",
+                DamlValue::new_record(DamlRecordBuilder::new().build()),
+            ));
+            let simulation = simulate(&machine, &store, 0, &command)?;
+            let exercised = simulation.exercised().collect::<Vec<_>>();
+            assert_eq!(1, exercised.len());
+            assert_eq!("Archive", exercised[0].choice());
+            assert!(exercised[0].consuming());
+            assert_eq!(vec![PING_ID], simulation.archived().map(DamlArchivedEvent::contract_id).collect::<Vec<_>>());
+            assert_eq!(0, simulation.created().count());
+            store.apply(&simulation);
+            assert!(store.is_empty());
+            assert_eq!(
+                Err(DamlInterpreterError::ContractNotFound(PING_ID.to_owned())),
+                simulate(&machine, &store, 0, &command).map(|_| ())
+            );
+            Ok(())
+        })
+    }
+}
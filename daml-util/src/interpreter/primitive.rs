@@ -0,0 +1,235 @@
+use bigdecimal::num_bigint::{BigInt, Sign};
+use bigdecimal::{BigDecimal, Signed, Zero};
+use daml_lf::element::RoundingMode;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+/// The maximum number of significant digits of a Daml `Numeric`.
+const MAX_NUMERIC_PRECISION: u32 = 38;
+
+/// The number of days between 0001-01-01 and 1970-01-01.
+const MIN_DATE: i32 = -719_162;
+
+/// The number of days between 1970-01-01 and 9999-12-31.
+const MAX_DATE: i32 = 2_932_896;
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// Round `value` to `scale` decimal digits using the given rounding mode.
+///
+/// Returns `None` if the rounding mode is `Unnecessary` and rounding is required.
+pub fn round(value: &BigDecimal, scale: i64, mode: &RoundingMode) -> Option<BigDecimal> {
+    let (digits, exponent) = value.as_bigint_and_exponent();
+    if exponent <= scale {
+        Some(value.with_scale(scale))
+    } else {
+        Some(BigDecimal::new(round_div(&digits, &pow10(exponent - scale), mode)?, scale))
+    }
+}
+
+/// Divide `left` by `right` and round the result to `scale` decimal digits using the given rounding mode.
+///
+/// Returns `None` if `right` is zero or if the rounding mode is `Unnecessary` and rounding is required.
+pub fn divide(left: &BigDecimal, right: &BigDecimal, scale: i64, mode: &RoundingMode) -> Option<BigDecimal> {
+    let (numerator, left_scale) = left.as_bigint_and_exponent();
+    let (denominator, right_scale) = right.as_bigint_and_exponent();
+    if denominator.is_zero() {
+        return None;
+    }
+    let shift = scale - left_scale + right_scale;
+    let quotient = if shift >= 0 {
+        round_div(&(numerator * pow10(shift)), &denominator, mode)?
+    } else {
+        round_div(&numerator, &(denominator * pow10(-shift)), mode)?
+    };
+    Some(BigDecimal::new(quotient, scale))
+}
+
+/// Returns `value` if it fits within the bounds of a `Numeric` of the given scale, `None` otherwise.
+pub fn check_numeric(value: &BigDecimal, scale: i64) -> Option<BigDecimal> {
+    let scaled = value.with_scale(scale);
+    let (digits, _) = scaled.as_bigint_and_exponent();
+    (digits.abs() < pow10(i64::from(MAX_NUMERIC_PRECISION)) && &scaled == value).then(|| scaled)
+}
+
+/// The scale of a `Numeric` or `BigNumeric` value.
+pub fn scale(value: &BigDecimal) -> i64 {
+    value.as_bigint_and_exponent().1
+}
+
+/// Render a numeric using the Daml text representation, which has at least one decimal digit and no trailing zeros.
+pub fn to_text(value: &BigDecimal) -> String {
+    let (digits, exponent) = value.as_bigint_and_exponent();
+    let rendered = if exponent <= 0 {
+        format!("{}.0", BigDecimal::new(digits, exponent).with_scale(0))
+    } else {
+        let plain = value.to_string();
+        let trimmed = plain.trim_end_matches('0');
+        if trimmed.ends_with('.') {
+            format!("{trimmed}0")
+        } else {
+            trimmed.to_owned()
+        }
+    };
+    if rendered.starts_with("-0.") && value.is_zero() {
+        rendered[1..].to_owned()
+    } else {
+        rendered
+    }
+}
+
+/// Parse a Daml text representation of a numeric, `[+-]?[0-9]+(\.[0-9]+)?`.
+///
+/// Returns `None` if the text is not a valid numeric or cannot be represented at the given scale without loss of
+/// precision.
+pub fn from_text(text: &str, scale: i64) -> Option<BigDecimal> {
+    let unsigned = text.strip_prefix(['+', '-']).unwrap_or(text);
+    let (integral, fractional) = unsigned.split_once('.').unwrap_or((unsigned, "0"));
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(integral) || !is_digits(fractional) {
+        return None;
+    }
+    let value = BigDecimal::from_str(text.strip_prefix('+').unwrap_or(text)).ok()?;
+    check_numeric(&value, scale)
+}
+
+/// Round `value` to `places` decimal places using banker's rounding, preserving the original scale.
+pub fn round_numeric(value: &BigDecimal, places: i64) -> Option<BigDecimal> {
+    let original = scale(value);
+    round(value, places, &RoundingMode::HalfEven).map(|rounded| rounded.with_scale(original))
+}
+
+/// Render a number of microseconds since the epoch in the ISO-8601 format used by Daml.
+pub fn timestamp_to_text(micros: i64) -> Option<String> {
+    let days = i32::try_from(micros.div_euclid(MICROS_PER_DAY)).ok()?;
+    let date = date_to_text(days)?;
+    let micros_of_day = micros.rem_euclid(MICROS_PER_DAY);
+    let seconds = micros_of_day / 1_000_000;
+    let fraction = micros_of_day % 1_000_000;
+    let time = format!("{:02}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60);
+    Some(match fraction {
+        0 => format!("{date}T{time}Z"),
+        f if f % 1000 == 0 => format!("{}T{}.{:03}Z", date, time, f / 1000),
+        f => format!("{date}T{time}.{f:06}Z"),
+    })
+}
+
+/// Render a number of days since the epoch in the ISO-8601 `YYYY-MM-DD` format.
+pub fn date_to_text(days: i32) -> Option<String> {
+    let (year, month, day) = civil_from_days(days)?;
+    Some(format!("{year:04}-{month:02}-{day:02}"))
+}
+
+/// Returns `Some(days)` if `days` is within the range of a Daml `Date`.
+pub fn check_date(days: i64) -> Option<i32> {
+    i32::try_from(days).ok().filter(|days| (MIN_DATE..=MAX_DATE).contains(days))
+}
+
+/// Returns `Some(micros)` if `micros` is within the range of a Daml `Timestamp`.
+pub fn check_timestamp(micros: i64) -> Option<i64> {
+    let min = i64::from(MIN_DATE) * MICROS_PER_DAY;
+    let max = (i64::from(MAX_DATE) + 1) * MICROS_PER_DAY - 1;
+    (min..=max).contains(&micros).then(|| micros)
+}
+
+/// Convert a number of days since the epoch to a `(year, month, day)` triple in the proleptic Gregorian calendar.
+fn civil_from_days(days: i32) -> Option<(i64, u32, u32)> {
+    check_date(i64::from(days))?;
+    let z = i64::from(days) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 {
+        mp + 3
+    } else {
+        mp - 9
+    };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    Some((year, u32::try_from(month).ok()?, u32::try_from(day).ok()?))
+}
+
+/// Divide two integers and round the result using the given rounding mode.
+#[allow(clippy::match_same_arms)]
+fn round_div(numerator: &BigInt, denominator: &BigInt, mode: &RoundingMode) -> Option<BigInt> {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder.is_zero() {
+        return Some(quotient);
+    }
+    let positive = (numerator.sign() == Sign::Minus) == (denominator.sign() == Sign::Minus);
+    let away = if positive {
+        &quotient + 1
+    } else {
+        &quotient - 1
+    };
+    let half = (remainder.magnitude() * 2u32).cmp(denominator.magnitude());
+    Some(match mode {
+        RoundingMode::Up => away,
+        RoundingMode::Down => quotient,
+        RoundingMode::Ceiling if positive => away,
+        RoundingMode::Floor if !positive => away,
+        RoundingMode::Ceiling | RoundingMode::Floor => quotient,
+        RoundingMode::HalfUp if half != Ordering::Less => away,
+        RoundingMode::HalfDown if half == Ordering::Greater => away,
+        RoundingMode::HalfUp | RoundingMode::HalfDown => quotient,
+        RoundingMode::HalfEven => match half {
+            Ordering::Greater => away,
+            Ordering::Equal if quotient.bit(0) => away,
+            _ => quotient,
+        },
+        RoundingMode::Unnecessary => return None,
+    })
+}
+
+fn pow10(exponent: i64) -> BigInt {
+    BigInt::from(10).pow(u32::try_from(exponent).unwrap_or(u32::MAX))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dec(s: &str) -> BigDecimal {
+        BigDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_round_half_even() {
+        assert_eq!(round(&dec("2.5"), 0, &RoundingMode::HalfEven), Some(dec("2")));
+        assert_eq!(round(&dec("3.5"), 0, &RoundingMode::HalfEven), Some(dec("4")));
+        assert_eq!(round(&dec("-2.5"), 0, &RoundingMode::HalfEven), Some(dec("-2")));
+        assert_eq!(round(&dec("-2.51"), 0, &RoundingMode::HalfEven), Some(dec("-3")));
+        assert_eq!(round(&dec("1.25"), 1, &RoundingMode::Unnecessary), None);
+    }
+
+    #[test]
+    fn test_divide() {
+        assert_eq!(divide(&dec("1.0"), &dec("3.0"), 10, &RoundingMode::HalfEven), Some(dec("0.3333333333")));
+        assert_eq!(divide(&dec("2"), &dec("3"), 2, &RoundingMode::HalfEven), Some(dec("0.67")));
+        assert_eq!(divide(&dec("-2"), &dec("3"), 2, &RoundingMode::Down), Some(dec("-0.66")));
+        assert_eq!(divide(&dec("1"), &dec("0"), 2, &RoundingMode::Down), None);
+    }
+
+    #[test]
+    fn test_numeric_text() {
+        assert_eq!(to_text(&dec("1.0000000000")), "1.0");
+        assert_eq!(to_text(&dec("-12.5000")), "-12.5");
+        assert_eq!(to_text(&dec("100")), "100.0");
+        assert_eq!(from_text("1.50", 1), Some(dec("1.5")));
+        assert_eq!(from_text("1.55", 1), None);
+        assert_eq!(from_text("-7", 2), Some(dec("-7.00")));
+        assert_eq!(from_text("1e3", 2), None);
+    }
+
+    #[test]
+    fn test_time_text() {
+        assert_eq!(date_to_text(0).as_deref(), Some("1970-01-01"));
+        assert_eq!(date_to_text(MIN_DATE).as_deref(), Some("0001-01-01"));
+        assert_eq!(date_to_text(MAX_DATE).as_deref(), Some("9999-12-31"));
+        assert_eq!(timestamp_to_text(1_500_000).as_deref(), Some("1970-01-01T00:00:01.500Z"));
+        assert_eq!(timestamp_to_text(-1).as_deref(), Some("1969-12-31T23:59:59.999999Z"));
+    }
+}
@@ -0,0 +1,242 @@
+use crate::interpreter::value::Env;
+use daml_grpc::data::DamlIdentifier;
+use daml_lf::element::{
+    DamlAbsoluteTyCon, DamlData, DamlField, DamlForall, DamlStruct, DamlSyn, DamlTyCon, DamlTyConName, DamlType,
+    DamlTypeVarWithKind, DamlVar,
+};
+use itertools::Itertools;
+use std::borrow::Cow;
+
+/// Resolve all type variables bound in `env` within `ty`.
+pub fn resolve<'a>(ty: &DamlType<'a>, env: &Env<'a>) -> DamlType<'a> {
+    substitute(ty, &|var| env.lookup_type(var).cloned())
+}
+
+/// Instantiate the type parameters of a data type with the given type arguments.
+pub fn instantiate<'a>(ty: &DamlType<'a>, params: &[DamlTypeVarWithKind<'a>], args: &[DamlType<'a>]) -> DamlType<'a> {
+    substitute(ty, &|var| params.iter().position(|param| param.var() == var).and_then(|i| args.get(i).cloned()))
+}
+
+/// Replace the free type variables of `ty` for which `lookup` returns a type.
+///
+/// The replacement types are always closed and so no care needs to be taken to avoid variable capture.
+fn substitute<'a>(ty: &DamlType<'a>, lookup: &dyn Fn(&str) -> Option<DamlType<'a>>) -> DamlType<'a> {
+    let all = |args: &[DamlType<'a>]| args.iter().map(|arg| substitute(arg, lookup)).collect::<Vec<_>>();
+    match ty {
+        DamlType::Var(var) => {
+            let args = all(var.type_arguments());
+            match lookup(var.var()) {
+                Some(resolved) if args.is_empty() => resolved,
+                Some(DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon)) => DamlType::TyCon(DamlTyCon::new(
+                    Box::new(tycon.tycon().clone()),
+                    tycon.type_arguments().iter().cloned().chain(args).collect(),
+                )),
+                Some(resolved) => apply_builtin(resolved, args),
+                None => DamlType::Var(DamlVar::new(Cow::Owned(var.var().to_owned()), args)),
+            }
+        },
+        DamlType::ContractId(inner) =>
+            DamlType::ContractId(inner.as_ref().map(|inner| Box::new(substitute(inner, lookup)))),
+        DamlType::Numeric(args) => DamlType::Numeric(all(args)),
+        DamlType::List(args) => DamlType::List(all(args)),
+        DamlType::TextMap(args) => DamlType::TextMap(all(args)),
+        DamlType::GenMap(args) => DamlType::GenMap(all(args)),
+        DamlType::Optional(args) => DamlType::Optional(all(args)),
+        DamlType::Arrow(args) => DamlType::Arrow(all(args)),
+        DamlType::Update(args) => DamlType::Update(all(args)),
+        DamlType::Scenario(args) => DamlType::Scenario(all(args)),
+        DamlType::TyCon(tycon) =>
+            DamlType::TyCon(DamlTyCon::new(Box::new(tycon.tycon().clone()), all(tycon.type_arguments()))),
+        DamlType::BoxedTyCon(tycon) =>
+            DamlType::BoxedTyCon(DamlTyCon::new(Box::new(tycon.tycon().clone()), all(tycon.type_arguments()))),
+        DamlType::Syn(syn) => DamlType::Syn(DamlSyn::new(Box::new(syn.tysyn().clone()), all(syn.args()))),
+        DamlType::Struct(fields) => DamlType::Struct(DamlStruct::new(
+            fields
+                .fields()
+                .iter()
+                .map(|field| DamlField::new(Cow::Owned(field.name().to_owned()), substitute(field.ty(), lookup)))
+                .collect(),
+        )),
+        DamlType::Forall(forall) => {
+            let bound = |var: &str| forall.vars().iter().any(|bound| bound.var() == var);
+            let body = substitute(forall.body(), &|var| {
+                if bound(var) {
+                    None
+                } else {
+                    lookup(var)
+                }
+            });
+            DamlType::Forall(DamlForall::new(forall.vars().to_vec(), Box::new(body)))
+        },
+        DamlType::Int64
+        | DamlType::Text
+        | DamlType::Timestamp
+        | DamlType::Party
+        | DamlType::Bool
+        | DamlType::Unit
+        | DamlType::Date
+        | DamlType::Nat(_)
+        | DamlType::Any
+        | DamlType::TypeRep
+        | DamlType::Bignumeric
+        | DamlType::RoundingMode
+        | DamlType::AnyException => ty.clone(),
+    }
+}
+
+/// Apply type arguments to a resolved builtin type constructor such as `List` or `Numeric`.
+fn apply_builtin<'a>(ty: DamlType<'a>, args: Vec<DamlType<'a>>) -> DamlType<'a> {
+    let extend = |mut existing: Vec<DamlType<'a>>| {
+        existing.extend(args.iter().cloned());
+        existing
+    };
+    match ty {
+        DamlType::Numeric(existing) => DamlType::Numeric(extend(existing)),
+        DamlType::List(existing) => DamlType::List(extend(existing)),
+        DamlType::TextMap(existing) => DamlType::TextMap(extend(existing)),
+        DamlType::GenMap(existing) => DamlType::GenMap(extend(existing)),
+        DamlType::Optional(existing) => DamlType::Optional(extend(existing)),
+        DamlType::Arrow(existing) => DamlType::Arrow(extend(existing)),
+        DamlType::Update(existing) => DamlType::Update(extend(existing)),
+        DamlType::Scenario(existing) => DamlType::Scenario(extend(existing)),
+        DamlType::ContractId(None) => DamlType::ContractId(args.into_iter().next().map(Box::new)),
+        other => other,
+    }
+}
+
+/// Are two closed types structurally equal?
+pub fn type_eq(left: &DamlType<'_>, right: &DamlType<'_>) -> bool {
+    let all_eq =
+        |l: &[DamlType<'_>], r: &[DamlType<'_>]| l.len() == r.len() && l.iter().zip(r).all(|(l, r)| type_eq(l, r));
+    match (left, right) {
+        (DamlType::ContractId(l), DamlType::ContractId(r)) => match (l, r) {
+            (Some(l), Some(r)) => type_eq(l, r),
+            (None, None) => true,
+            _ => false,
+        },
+        (DamlType::Numeric(l), DamlType::Numeric(r))
+        | (DamlType::List(l), DamlType::List(r))
+        | (DamlType::TextMap(l), DamlType::TextMap(r))
+        | (DamlType::GenMap(l), DamlType::GenMap(r))
+        | (DamlType::Optional(l), DamlType::Optional(r))
+        | (DamlType::Arrow(l), DamlType::Arrow(r))
+        | (DamlType::Update(l), DamlType::Update(r))
+        | (DamlType::Scenario(l), DamlType::Scenario(r)) => all_eq(l, r),
+        (DamlType::TyCon(l) | DamlType::BoxedTyCon(l), DamlType::TyCon(r) | DamlType::BoxedTyCon(r)) =>
+            tycon_name_eq(l.tycon(), r.tycon()) && all_eq(l.type_arguments(), r.type_arguments()),
+        (DamlType::Syn(l), DamlType::Syn(r)) => tycon_name_eq(l.tysyn(), r.tysyn()) && all_eq(l.args(), r.args()),
+        (DamlType::Var(l), DamlType::Var(r)) => l.var() == r.var() && all_eq(l.type_arguments(), r.type_arguments()),
+        (DamlType::Nat(l), DamlType::Nat(r)) => l == r,
+        (DamlType::Struct(l), DamlType::Struct(r)) =>
+            l.fields().len() == r.fields().len()
+                && l.fields().iter().zip(r.fields()).all(|(l, r)| l.name() == r.name() && type_eq(l.ty(), r.ty())),
+        (DamlType::Forall(l), DamlType::Forall(r)) =>
+            l.vars().len() == r.vars().len()
+                && l.vars().iter().zip(r.vars()).all(|(l, r)| l.var() == r.var())
+                && type_eq(l.body(), r.body()),
+        (DamlType::Int64, DamlType::Int64)
+        | (DamlType::Text, DamlType::Text)
+        | (DamlType::Timestamp, DamlType::Timestamp)
+        | (DamlType::Party, DamlType::Party)
+        | (DamlType::Bool, DamlType::Bool)
+        | (DamlType::Unit, DamlType::Unit)
+        | (DamlType::Date, DamlType::Date)
+        | (DamlType::Any, DamlType::Any)
+        | (DamlType::TypeRep, DamlType::TypeRep)
+        | (DamlType::Bignumeric, DamlType::Bignumeric)
+        | (DamlType::RoundingMode, DamlType::RoundingMode)
+        | (DamlType::AnyException, DamlType::AnyException) => true,
+        _ => false,
+    }
+}
+
+/// Do two type constructor names refer to the same data type?
+pub fn tycon_name_eq(left: &DamlTyConName<'_>, right: &DamlTyConName<'_>) -> bool {
+    left.package_id() == right.package_id()
+        && left.data_name() == right.data_name()
+        && left.module_path().eq(right.module_path())
+}
+
+/// Does the type constructor name refer to the given data type?
+pub fn is_data(name: &DamlTyConName<'_>, data: &DamlData<'_>) -> bool {
+    (name.package_id(), name.data_name()) == (data.package_id(), data.name())
+        && name.module_path().eq(data.module_path())
+}
+
+/// The type constructor of a (non-parameterized) data type.
+pub fn data_type<'a>(data: &'a DamlData<'a>) -> DamlType<'a> {
+    DamlType::TyCon(DamlTyCon::new(
+        Box::new(DamlTyConName::Absolute(DamlAbsoluteTyCon::new(
+            Cow::Borrowed(data.name()),
+            Cow::Borrowed(data.package_id()),
+            Cow::default(),
+            data.module_path().map(Cow::Borrowed).collect(),
+        ))),
+        vec![],
+    ))
+}
+
+/// The ledger API identifier of a data type.
+pub fn data_identifier(data: &DamlData<'_>) -> DamlIdentifier {
+    DamlIdentifier::new(data.package_id(), data.module_path().join("."), data.name())
+}
+
+/// Render a data type name in the form `Module.Path:Name`.
+pub fn render_data_name(data: &DamlData<'_>) -> String {
+    format!("{}:{}", data.module_path().join("."), data.name())
+}
+
+/// Render a type constructor name in the form `Module.Path:Name`.
+pub fn render_tycon_name(name: &DamlTyConName<'_>) -> String {
+    format!("{}:{}", name.module_path().join("."), name.data_name())
+}
+
+/// Render a type for use in diagnostics.
+pub fn render_type(ty: &DamlType<'_>) -> String {
+    let with_args = |name: &str, args: &[DamlType<'_>]| {
+        if args.is_empty() {
+            name.to_owned()
+        } else {
+            format!("({} {})", name, args.iter().map(render_type).join(" "))
+        }
+    };
+    match ty {
+        DamlType::ContractId(inner) => inner
+            .as_ref()
+            .map_or_else(|| "ContractId".to_owned(), |inner| format!("(ContractId {})", render_type(inner))),
+        DamlType::Int64 => "Int64".to_owned(),
+        DamlType::Numeric(args) => with_args("Numeric", args),
+        DamlType::Text => "Text".to_owned(),
+        DamlType::Timestamp => "Timestamp".to_owned(),
+        DamlType::Party => "Party".to_owned(),
+        DamlType::Bool => "Bool".to_owned(),
+        DamlType::Unit => "Unit".to_owned(),
+        DamlType::Date => "Date".to_owned(),
+        DamlType::List(args) => with_args("List", args),
+        DamlType::TextMap(args) => with_args("TextMap", args),
+        DamlType::GenMap(args) => with_args("GenMap", args),
+        DamlType::Optional(args) => with_args("Optional", args),
+        DamlType::TyCon(tycon) | DamlType::BoxedTyCon(tycon) =>
+            with_args(&render_tycon_name(tycon.tycon()), tycon.type_arguments()),
+        DamlType::Var(var) => with_args(var.var(), var.type_arguments()),
+        DamlType::Nat(n) => n.to_string(),
+        DamlType::Arrow(args) => with_args("->", args),
+        DamlType::Any => "Any".to_owned(),
+        DamlType::TypeRep => "TypeRep".to_owned(),
+        DamlType::Bignumeric => "BigNumeric".to_owned(),
+        DamlType::RoundingMode => "RoundingMode".to_owned(),
+        DamlType::AnyException => "AnyException".to_owned(),
+        DamlType::Update(args) => with_args("Update", args),
+        DamlType::Scenario(args) => with_args("Scenario", args),
+        DamlType::Forall(forall) => format!(
+            "(forall {}. {})",
+            forall.vars().iter().map(DamlTypeVarWithKind::var).join(" "),
+            render_type(forall.body())
+        ),
+        DamlType::Struct(fields) => format!(
+            "<{}>",
+            fields.fields().iter().map(|field| format!("{}: {}", field.name(), render_type(field.ty()))).join(", ")
+        ),
+        DamlType::Syn(syn) => with_args(&render_tycon_name(syn.tysyn()), syn.args()),
+    }
+}
@@ -0,0 +1,546 @@
+use crate::interpreter::error::{DamlInterpreterError, DamlInterpreterResult};
+use crate::interpreter::primitive;
+use crate::interpreter::types::{render_data_name, render_type, type_eq};
+use bigdecimal::BigDecimal;
+use daml_lf::element::{
+    DamlBuiltinFunction, DamlData, DamlExpr, DamlType, DamlTypeVarWithKind, DamlUpdate, DamlVarWithType, RoundingMode,
+};
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+use std::{fmt, ptr};
+
+/// A runtime Daml-LF value.
+#[derive(Clone, Debug)]
+pub enum Value<'a> {
+    Unit,
+    Bool(bool),
+    Int64(i64),
+    Numeric(BigDecimal),
+    BigNumeric(BigDecimal),
+    Text(Rc<str>),
+    Party(Rc<str>),
+    ContractId(Rc<str>),
+    Timestamp(i64),
+    Date(i32),
+    RoundingMode(&'a RoundingMode),
+    List(ValueList<'a>),
+    Optional(Option<Rc<Self>>),
+    TextMap(Rc<BTreeMap<Rc<str>, Self>>),
+    GenMap(Rc<Vec<(Self, Self)>>),
+    Record(Rc<RecordValue<'a>>),
+    Variant(Rc<VariantValue<'a>>),
+    Enum(&'a DamlData<'a>, usize),
+    Struct(Rc<Vec<(&'a str, Self)>>),
+    Any(Rc<(DamlType<'a>, Self)>),
+    TypeRep(Rc<DamlType<'a>>),
+    AnyException(Rc<(DamlType<'a>, Self)>),
+    Closure(Rc<Closure<'a>>),
+    TyClosure(Rc<TyClosure<'a>>),
+    Builtin(Rc<BuiltinValue<'a>>),
+    Update(Rc<UpdateValue<'a>>),
+}
+
+impl<'a> Value<'a> {
+    pub fn text(text: impl Into<Rc<str>>) -> Self {
+        Value::Text(text.into())
+    }
+
+    pub fn optional(value: Option<Self>) -> Self {
+        Value::Optional(value.map(Rc::new))
+    }
+
+    pub fn record(data: &'a DamlData<'a>, fields: Vec<Self>) -> Self {
+        Value::Record(Rc::new(RecordValue {
+            data,
+            fields,
+        }))
+    }
+
+    pub fn variant(data: &'a DamlData<'a>, rank: usize, value: Self) -> Self {
+        Value::Variant(Rc::new(VariantValue {
+            data,
+            rank,
+            value,
+        }))
+    }
+
+    /// A short description of the kind of this value, used in error messages.
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Value::Unit => "Unit",
+            Value::Bool(_) => "Bool",
+            Value::Int64(_) => "Int64",
+            Value::Numeric(_) => "Numeric",
+            Value::BigNumeric(_) => "BigNumeric",
+            Value::Text(_) => "Text",
+            Value::Party(_) => "Party",
+            Value::ContractId(_) => "ContractId",
+            Value::Timestamp(_) => "Timestamp",
+            Value::Date(_) => "Date",
+            Value::RoundingMode(_) => "RoundingMode",
+            Value::List(_) => "List",
+            Value::Optional(_) => "Optional",
+            Value::TextMap(_) => "TextMap",
+            Value::GenMap(_) => "GenMap",
+            Value::Record(_) => "Record",
+            Value::Variant(_) => "Variant",
+            Value::Enum(..) => "Enum",
+            Value::Struct(_) => "Struct",
+            Value::Any(_) => "Any",
+            Value::TypeRep(_) => "TypeRep",
+            Value::AnyException(_) => "AnyException",
+            Value::Closure(_) | Value::TyClosure(_) | Value::Builtin(_) => "Function",
+            Value::Update(_) => "Update",
+        }
+    }
+
+    pub fn unexpected<T>(&self, expected: &str) -> DamlInterpreterResult<T> {
+        Err(DamlInterpreterError::UnexpectedValue(expected.to_owned(), self.kind().to_owned()))
+    }
+
+    pub fn as_bool(&self) -> DamlInterpreterResult<bool> {
+        match self {
+            Value::Bool(b) => Ok(*b),
+            _ => self.unexpected("Bool"),
+        }
+    }
+
+    pub fn as_int64(&self) -> DamlInterpreterResult<i64> {
+        match self {
+            Value::Int64(i) => Ok(*i),
+            _ => self.unexpected("Int64"),
+        }
+    }
+
+    pub fn as_numeric(&self) -> DamlInterpreterResult<&BigDecimal> {
+        match self {
+            Value::Numeric(n) => Ok(n),
+            _ => self.unexpected("Numeric"),
+        }
+    }
+
+    pub fn as_big_numeric(&self) -> DamlInterpreterResult<&BigDecimal> {
+        match self {
+            Value::BigNumeric(n) => Ok(n),
+            _ => self.unexpected("BigNumeric"),
+        }
+    }
+
+    pub fn as_text(&self) -> DamlInterpreterResult<&Rc<str>> {
+        match self {
+            Value::Text(t) => Ok(t),
+            _ => self.unexpected("Text"),
+        }
+    }
+
+    pub fn as_party(&self) -> DamlInterpreterResult<&Rc<str>> {
+        match self {
+            Value::Party(p) => Ok(p),
+            _ => self.unexpected("Party"),
+        }
+    }
+
+    pub fn as_contract_id(&self) -> DamlInterpreterResult<&Rc<str>> {
+        match self {
+            Value::ContractId(cid) => Ok(cid),
+            _ => self.unexpected("ContractId"),
+        }
+    }
+
+    pub fn as_timestamp(&self) -> DamlInterpreterResult<i64> {
+        match self {
+            Value::Timestamp(t) => Ok(*t),
+            _ => self.unexpected("Timestamp"),
+        }
+    }
+
+    pub fn as_date(&self) -> DamlInterpreterResult<i32> {
+        match self {
+            Value::Date(d) => Ok(*d),
+            _ => self.unexpected("Date"),
+        }
+    }
+
+    pub fn as_rounding_mode(&self) -> DamlInterpreterResult<&'a RoundingMode> {
+        match self {
+            Value::RoundingMode(mode) => Ok(mode),
+            _ => self.unexpected("RoundingMode"),
+        }
+    }
+
+    pub fn as_list(&self) -> DamlInterpreterResult<&ValueList<'a>> {
+        match self {
+            Value::List(list) => Ok(list),
+            _ => self.unexpected("List"),
+        }
+    }
+
+    pub fn as_optional(&self) -> DamlInterpreterResult<Option<&Self>> {
+        match self {
+            Value::Optional(opt) => Ok(opt.as_deref()),
+            _ => self.unexpected("Optional"),
+        }
+    }
+
+    pub fn as_text_map(&self) -> DamlInterpreterResult<&Rc<BTreeMap<Rc<str>, Self>>> {
+        match self {
+            Value::TextMap(map) => Ok(map),
+            _ => self.unexpected("TextMap"),
+        }
+    }
+
+    pub fn as_gen_map(&self) -> DamlInterpreterResult<&Rc<Vec<(Self, Self)>>> {
+        match self {
+            Value::GenMap(map) => Ok(map),
+            _ => self.unexpected("GenMap"),
+        }
+    }
+
+    pub fn as_record(&self) -> DamlInterpreterResult<&Rc<RecordValue<'a>>> {
+        match self {
+            Value::Record(record) => Ok(record),
+            _ => self.unexpected("Record"),
+        }
+    }
+
+    pub fn as_struct(&self) -> DamlInterpreterResult<&Rc<Vec<(&'a str, Self)>>> {
+        match self {
+            Value::Struct(fields) => Ok(fields),
+            _ => self.unexpected("Struct"),
+        }
+    }
+
+    pub fn as_type_rep(&self) -> DamlInterpreterResult<&DamlType<'a>> {
+        match self {
+            Value::TypeRep(ty) => Ok(ty),
+            _ => self.unexpected("TypeRep"),
+        }
+    }
+
+    /// Convert a list of `Party` values into a sorted and deduplicated list of party names.
+    pub fn as_parties(&self) -> DamlInterpreterResult<Vec<String>> {
+        let parties =
+            self.as_list()?.iter().map(|p| p.as_party().map(ToString::to_string)).collect::<Result<_, _>>()?;
+        Ok(sorted_parties(parties))
+    }
+}
+
+/// Sort and deduplicate a list of party names.
+pub fn sorted_parties(parties: Vec<String>) -> Vec<String> {
+    parties.into_iter().sorted().dedup().collect()
+}
+
+/// An immutable list which shares its elements with the lists it was built from.
+#[derive(Clone, Debug, Default)]
+pub struct ValueList<'a> {
+    items: Rc<Vec<Value<'a>>>,
+    start: usize,
+}
+
+impl<'a> ValueList<'a> {
+    pub fn new(items: Vec<Value<'a>>) -> Self {
+        Self {
+            items: Rc::new(items),
+            start: 0,
+        }
+    }
+
+    /// Prepend the `front` values to the `tail` list.
+    pub fn cons(front: Vec<Value<'a>>, tail: &Self) -> Self {
+        if front.is_empty() {
+            tail.clone()
+        } else {
+            Self::new(front.into_iter().chain(tail.iter().cloned()).collect())
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len() - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Value<'a>> + ExactSizeIterator {
+        self.items[self.start..].iter()
+    }
+
+    pub fn split_first(&self) -> Option<(&Value<'a>, Self)> {
+        self.items.get(self.start).map(|head| {
+            (head, Self {
+                items: self.items.clone(),
+                start: self.start + 1,
+            })
+        })
+    }
+}
+
+/// A record value.
+#[derive(Debug)]
+pub struct RecordValue<'a> {
+    pub data: &'a DamlData<'a>,
+    pub fields: Vec<Value<'a>>,
+}
+
+impl<'a> RecordValue<'a> {
+    pub fn field(&self, name: &str) -> DamlInterpreterResult<&Value<'a>> {
+        self.field_index(name).map(|index| &self.fields[index])
+    }
+
+    pub fn field_index(&self, name: &str) -> DamlInterpreterResult<usize> {
+        self.data
+            .fields()
+            .iter()
+            .position(|field| field.name() == name)
+            .ok_or_else(|| DamlInterpreterError::UnexpectedValue(format!("field {name}"), render_data_name(self.data)))
+    }
+}
+
+/// A variant value, the constructor is identified by its position in the variant definition.
+#[derive(Debug)]
+pub struct VariantValue<'a> {
+    pub data: &'a DamlData<'a>,
+    pub rank: usize,
+    pub value: Value<'a>,
+}
+
+impl VariantValue<'_> {
+    pub fn constructor(&self) -> &str {
+        self.data.fields().get(self.rank).map_or("", |field| field.name())
+    }
+}
+
+/// A (possibly partially applied) lambda abstraction.
+#[derive(Debug)]
+pub struct Closure<'a> {
+    pub env: Env<'a>,
+    pub params: &'a [DamlVarWithType<'a>],
+    pub body: &'a DamlExpr<'a>,
+    pub applied: Vec<Value<'a>>,
+}
+
+/// A (possibly partially applied) type abstraction.
+#[derive(Debug)]
+pub struct TyClosure<'a> {
+    pub env: Env<'a>,
+    pub params: &'a [DamlTypeVarWithKind<'a>],
+    pub body: &'a DamlExpr<'a>,
+    pub applied: Vec<DamlType<'a>>,
+}
+
+/// A (possibly partially applied) builtin function.
+///
+/// Type arguments are only retained for the builtin functions which require them at runtime, such as the scale of a
+/// `Numeric` result.
+#[derive(Debug)]
+pub struct BuiltinValue<'a> {
+    pub builtin: &'a DamlBuiltinFunction,
+    pub types: Vec<DamlType<'a>>,
+    pub args: Vec<Value<'a>>,
+}
+
+/// A suspended `Update` expression and the environment it was evaluated in.
+#[derive(Debug)]
+pub struct UpdateValue<'a> {
+    pub env: Env<'a>,
+    pub update: &'a DamlUpdate<'a>,
+}
+
+/// The term and type variables in scope during evaluation.
+#[derive(Clone, Default)]
+pub struct Env<'a> {
+    vars: Option<Rc<Binding<'a, Value<'a>>>>,
+    types: Option<Rc<Binding<'a, DamlType<'a>>>>,
+}
+
+struct Binding<'a, T> {
+    name: &'a str,
+    value: T,
+    next: Option<Rc<Self>>,
+}
+
+impl<'a> Env<'a> {
+    pub fn bind(&self, name: &'a str, value: Value<'a>) -> Self {
+        Self {
+            vars: Some(Rc::new(Binding {
+                name,
+                value,
+                next: self.vars.clone(),
+            })),
+            types: self.types.clone(),
+        }
+    }
+
+    pub fn bind_type(&self, name: &'a str, ty: DamlType<'a>) -> Self {
+        Self {
+            vars: self.vars.clone(),
+            types: Some(Rc::new(Binding {
+                name,
+                value: ty,
+                next: self.types.clone(),
+            })),
+        }
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<&Value<'a>> {
+        lookup(self.vars.as_ref(), name)
+    }
+
+    pub fn lookup_type(&self, name: &str) -> Option<&DamlType<'a>> {
+        lookup(self.types.as_ref(), name)
+    }
+}
+
+fn lookup<'e, T>(mut binding: Option<&'e Rc<Binding<'_, T>>>, name: &str) -> Option<&'e T> {
+    while let Some(current) = binding {
+        if current.name == name {
+            return Some(&current.value);
+        }
+        binding = current.next.as_ref();
+    }
+    None
+}
+
+impl Debug for Env<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Env")
+    }
+}
+
+/// Compare two values using the Daml-LF generic ordering.
+#[allow(clippy::match_same_arms)]
+pub fn compare(left: &Value<'_>, right: &Value<'_>) -> DamlInterpreterResult<Ordering> {
+    Ok(match (left, right) {
+        (Value::Unit, Value::Unit) => Ordering::Equal,
+        (Value::Bool(l), Value::Bool(r)) => l.cmp(r),
+        (Value::Int64(l), Value::Int64(r)) => l.cmp(r),
+        (Value::Numeric(l), Value::Numeric(r)) | (Value::BigNumeric(l), Value::BigNumeric(r)) => l.cmp(r),
+        (Value::Text(l), Value::Text(r)) | (Value::Party(l), Value::Party(r)) => l.cmp(r),
+        (Value::ContractId(l), Value::ContractId(r)) => l.cmp(r),
+        (Value::Timestamp(l), Value::Timestamp(r)) => l.cmp(r),
+        (Value::Date(l), Value::Date(r)) => l.cmp(r),
+        (Value::List(l), Value::List(r)) => compare_all(l.iter(), r.iter())?,
+        (Value::Optional(l), Value::Optional(r)) => match (l, r) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(l), Some(r)) => compare(l, r)?,
+        },
+        (Value::TextMap(l), Value::TextMap(r)) => {
+            for ((lk, lv), (rk, rv)) in l.iter().zip(r.iter()) {
+                let ordering = lk.cmp(rk).then(compare(lv, rv)?);
+                if ordering != Ordering::Equal {
+                    return Ok(ordering);
+                }
+            }
+            l.len().cmp(&r.len())
+        },
+        (Value::GenMap(l), Value::GenMap(r)) =>
+            compare_all(l.iter().flat_map(|(k, v)| [k, v]), r.iter().flat_map(|(k, v)| [k, v]))?,
+        (Value::Record(l), Value::Record(r)) if ptr::eq(l.data, r.data) =>
+            compare_all(l.fields.iter(), r.fields.iter())?,
+        (Value::Variant(l), Value::Variant(r)) if ptr::eq(l.data, r.data) => match l.rank.cmp(&r.rank) {
+            Ordering::Equal => compare(&l.value, &r.value)?,
+            ordering => ordering,
+        },
+        (Value::Enum(l, lr), Value::Enum(r, rr)) if ptr::eq(*l, *r) => lr.cmp(rr),
+        (Value::Struct(l), Value::Struct(r)) => compare_all(l.iter().map(|(_, v)| v), r.iter().map(|(_, v)| v))?,
+        (Value::Any(l), Value::Any(r)) | (Value::AnyException(l), Value::AnyException(r)) =>
+            if type_eq(&l.0, &r.0) {
+                compare(&l.1, &r.1)?
+            } else {
+                render_type(&l.0).cmp(&render_type(&r.0))
+            },
+        (Value::TypeRep(l), Value::TypeRep(r)) =>
+            if type_eq(l, r) {
+                Ordering::Equal
+            } else {
+                render_type(l).cmp(&render_type(r))
+            },
+        _ =>
+            return Err(DamlInterpreterError::UnexpectedValue(
+                format!("comparable value of kind {}", left.kind()),
+                right.kind().to_owned(),
+            )),
+    })
+}
+
+fn compare_all<'v, 'a: 'v>(
+    mut left: impl Iterator<Item = &'v Value<'a>>,
+    mut right: impl Iterator<Item = &'v Value<'a>>,
+) -> DamlInterpreterResult<Ordering> {
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return Ok(Ordering::Equal),
+            (None, Some(_)) => return Ok(Ordering::Less),
+            (Some(_), None) => return Ok(Ordering::Greater),
+            (Some(l), Some(r)) => match compare(l, r)? {
+                Ordering::Equal => {},
+                ordering => return Ok(ordering),
+            },
+        }
+    }
+}
+
+impl Display for Value<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Unit => f.write_str("()"),
+            Value::Bool(b) => write!(
+                f,
+                "{}",
+                if *b {
+                    "True"
+                } else {
+                    "False"
+                }
+            ),
+            Value::Int64(i) => write!(f, "{i}"),
+            Value::Numeric(n) | Value::BigNumeric(n) => f.write_str(&primitive::to_text(n)),
+            Value::Text(t) => write!(f, "{t:?}"),
+            Value::Party(p) => write!(f, "'{p}'"),
+            Value::ContractId(cid) => write!(f, "<{cid}>"),
+            Value::Timestamp(t) => f.write_str(&primitive::timestamp_to_text(*t).unwrap_or_else(|| t.to_string())),
+            Value::Date(d) => f.write_str(&primitive::date_to_text(*d).unwrap_or_else(|| d.to_string())),
+            Value::RoundingMode(mode) => write!(f, "{mode:?}"),
+            Value::List(list) => write!(f, "[{}]", list.iter().join(", ")),
+            Value::Optional(None) => f.write_str("None"),
+            Value::Optional(Some(v)) => write!(f, "Some ({v})"),
+            Value::TextMap(map) => write!(f, "TextMap [{}]", map.iter().map(|(k, v)| format!("{k:?}: {v}")).join(", ")),
+            Value::GenMap(map) => write!(f, "Map [{}]", map.iter().map(|(k, v)| format!("{k}: {v}")).join(", ")),
+            Value::Record(record) => write!(
+                f,
+                "{} {{{}}}",
+                record.data.name(),
+                record
+                    .data
+                    .fields()
+                    .iter()
+                    .zip(&record.fields)
+                    .map(|(field, v)| format!("{} = {}", field.name(), v))
+                    .join(", ")
+            ),
+            Value::Variant(variant) => write!(f, "{} ({})", variant.constructor(), variant.value),
+            Value::Enum(data, rank) => f.write_str(enum_constructor(data, *rank)),
+            Value::Struct(fields) =>
+                write!(f, "<{}>", fields.iter().map(|(name, v)| format!("{name} = {v}")).join(", ")),
+            Value::Any(any) => write!(f, "toAny @{} ({})", render_type(&any.0), any.1),
+            Value::TypeRep(ty) => write!(f, "typeRep @{}", render_type(ty)),
+            Value::AnyException(ex) => write!(f, "{}", ex.1),
+            Value::Closure(_) | Value::TyClosure(_) | Value::Builtin(_) => f.write_str("<function>"),
+            Value::Update(_) => f.write_str("<update>"),
+        }
+    }
+}
+
+/// The name of the enum constructor at `rank` or an empty string if no such constructor exists.
+pub fn enum_constructor<'a>(data: &'a DamlData<'a>, rank: usize) -> &'a str {
+    match data {
+        DamlData::Enum(data_enum) => data_enum.constructors().nth(rank).unwrap_or_default(),
+        _ => "",
+    }
+}
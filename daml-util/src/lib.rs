@@ -24,5 +24,8 @@ mod sandbox_auth;
 /// Conveniences for working with a collection of [`DamlPackage`](daml_grpc::data::package::DamlPackage).
 pub mod package;
 
+#[cfg(feature = "interpreter")]
+pub mod interpreter;
+
 #[cfg(feature = "sandbox")]
 pub use sandbox_auth::{DamlSandboxAuthError, DamlSandboxAuthResult, DamlSandboxAuthToken, DamlSandboxTokenBuilder};
@@ -1,11 +1,14 @@
-use daml_grpc::data::package::DamlPackage;
+use daml_grpc::data::package::{DamlHashFunction, DamlPackage};
 use daml_grpc::data::{DamlError, DamlResult};
 use daml_grpc::DamlGrpcClient;
-use daml_lf::{DamlLfArchive, DamlLfArchivePayload, DamlLfHashFunction, DarFile, DarManifest};
+use daml_lf::{DamlLfArchive, DamlLfArchivePayload, DamlLfHashFunction, DarBuilder, DarFile};
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
+use std::path::Path;
 use uuid::Uuid;
 
+const DAR_CREATED_BY: &str = "daml-util";
+
 /// Convenience methods for working with a collection of [`DamlPackage`].
 ///
 /// In the following example a [`DamlPackages`] is created from all known [`DamlPackage`] on a Daml ledger and then
@@ -39,9 +42,17 @@ impl DamlPackages {
     /// Create a [`DamlPackages`] from all known [`DamlPackage`] on a Daml ledger.
     pub async fn from_ledger(ledger_client: &DamlGrpcClient) -> DamlResult<Self> {
         let packages = ledger_client.package_service().list_packages().await?;
-        let handles = packages
+        Self::from_ledger_with_ids(ledger_client, &packages).await
+    }
+
+    /// Create a [`DamlPackages`] from the [`DamlPackage`] with the given package ids on a Daml ledger.
+    pub async fn from_ledger_with_ids<S: AsRef<str> + Sync>(
+        ledger_client: &DamlGrpcClient,
+        package_ids: &[S],
+    ) -> DamlResult<Self> {
+        let handles = package_ids
             .iter()
-            .map(|pd| async move { ledger_client.package_service().get_package(pd).await })
+            .map(|pd| async move { ledger_client.package_service().get_package(pd.as_ref()).await })
             .collect::<FuturesUnordered<_>>();
         let all_packages =
             handles.collect::<Vec<DamlResult<_>>>().await.into_iter().collect::<DamlResult<Vec<DamlPackage>>>()?;
@@ -69,21 +80,45 @@ impl DamlPackages {
         Self::archives_to_dar(all_archives)
     }
 
+    /// Package all contained [`DamlPackage`] into a single [`DarFile`] and write it to a `dar` file at `path`.
+    ///
+    /// See [`DamlPackages::into_dar`] for details of how the packages are combined and [`DarFile::write_to_file`] for
+    /// details of the generated `dar` file.
+    ///
+    /// ```no_run
+    /// # use daml_grpc::DamlGrpcClientBuilder;
+    /// # use daml_util::package::{DamlPackages, ArchiveAutoNamingStyle};
+    /// # fn main() {
+    /// # futures::executor::block_on(async {
+    /// let ledger_client = DamlGrpcClientBuilder::uri("http://127.0.0.1").connect().await.unwrap();
+    /// let packages = DamlPackages::from_ledger(&ledger_client).await.unwrap();
+    /// packages.write_dar("snapshot.dar", ArchiveAutoNamingStyle::Hash).unwrap();
+    /// # })
+    /// # }
+    /// ```
+    pub fn write_dar(self, path: impl AsRef<Path>, auto_naming_style: ArchiveAutoNamingStyle) -> DamlResult<()> {
+        self.into_dar(auto_naming_style)?.write_to_file(path).map_err(|e| DamlError::Other(e.to_string()))
+    }
+
     /// Convert all contained [`DamlPackage`] into [`DamlLfArchive`].
     ///
-    /// Note that the created archive is not named.
+    /// Note that the created archive is not named.  The payload bytes received from the ledger are retained such that
+    /// they match the package id when written to a `dar` file.
     pub fn into_archives(self, auto_naming_style: ArchiveAutoNamingStyle) -> DamlResult<Vec<DamlLfArchive>> {
         self.packages
             .into_iter()
             .map(|p| {
                 let hash = p.hash().to_owned();
-                let payload = Self::package_into_payload(p)?;
+                let hash_function = match p.hash_function() {
+                    DamlHashFunction::Sha256 => DamlLfHashFunction::Sha256,
+                };
                 let name = match auto_naming_style {
                     ArchiveAutoNamingStyle::Empty => String::default(),
                     ArchiveAutoNamingStyle::Hash => hash.clone(),
                     ArchiveAutoNamingStyle::Uuid => Uuid::new_v4().to_string(),
                 };
-                Ok(DamlLfArchive::new(name, payload, DamlLfHashFunction::Sha256, hash))
+                DamlLfArchive::from_payload_bytes(name, p.take_payload(), hash_function, hash)
+                    .map_err(|e| DamlError::Other(e.to_string()))
             })
             .collect()
     }
@@ -108,8 +143,7 @@ impl DamlPackages {
             Err("expected at least one archive".into())
         } else {
             let (first, rest) = all_packages.try_swap_remove(0).map(|removed| (removed, all_packages)).unwrap();
-            let manifest = DarManifest::new_implied(first.name.clone(), rest.iter().map(|n| n.name.clone()).collect());
-            Ok(DarFile::new(manifest, first, rest))
+            Ok(DarBuilder::new(first).created_by(DAR_CREATED_BY).add_dependencies(rest).build())
         }
     }
 }
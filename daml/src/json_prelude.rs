@@ -0,0 +1,11 @@
+#![allow(unused_imports, unused)]
+pub use daml_json::data::DamlJsonEvent;
+pub use daml_json::error::{DamlJsonError, DamlJsonResult};
+pub use daml_json::service::DamlJsonClient;
+pub use daml_json::typed::serde;
+pub use daml_json::typed::serde::{Deserialize, Serialize};
+pub use daml_json::typed::{
+    to_json_value, DamlJsonBool, DamlJsonContract, DamlJsonContractId, DamlJsonDate, DamlJsonGenMap, DamlJsonInt64,
+    DamlJsonList, DamlJsonNestedOptional, DamlJsonNumeric, DamlJsonOptional, DamlJsonParty, DamlJsonTemplate,
+    DamlJsonText, DamlJsonTextMap, DamlJsonTimestamp, DamlJsonTypedExerciseResult, DamlJsonUnit, DamlJsonValue,
+};
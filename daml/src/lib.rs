@@ -15,13 +15,14 @@
 //! | [daml-util](::daml_util)       | Utilities for working with Daml ledgers                                   |
 //! | [daml-lf](::daml_lf)           | Library for working with Daml-LF archives                                 |
 //! | [daml-bridge]                  | Library for Daml JSON <> GRPC Ledger bridging                             |
+//! | [daml-oas]                     | Library for generating Daml JSON API specification documents              |
 //!
 //! # Features
 //!
 //! The following feature may be enabled:
 //!
 //! - `grpc` - enable the `daml-grpc` library
-//! - `json` - enable the `daml-json` library
+//! - `json` - enable the `daml-json` library and the `daml` JSON prelude
 //! - `codegen` - enable the `daml-codegen` library
 //! - `derive` - enable the `daml-derive` library
 //! - `macros` - enable the `daml-macros` library
@@ -173,6 +174,17 @@
 #[cfg(feature = "prelude")]
 pub mod prelude;
 
+/// The Daml JSON prelude.
+///
+/// Include the Daml JSON prelude to bring into scope all types required by code generated with the `Json` render
+/// method of the code generator.  Include the JSON prelude as follows:
+///
+/// ```no_rust
+/// use daml::json_prelude::*;
+/// ```
+#[cfg(feature = "json")]
+pub mod json_prelude;
+
 #[cfg(feature = "grpc")]
 #[doc(hidden)]
 pub mod grpc_api {
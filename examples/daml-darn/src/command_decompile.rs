@@ -0,0 +1,65 @@
+use crate::DarnCommand;
+use anyhow::{anyhow, Context, Result};
+use clap::{Arg, ArgMatches, Command};
+use daml::lf::{DarFile, PrettyPrinter};
+use std::str::FromStr;
+
+/// Darn command for rendering the main package of a dar in a readable form.
+pub struct CommandDecompile {}
+
+impl DarnCommand for CommandDecompile {
+    fn name(&self) -> &str {
+        "decompile"
+    }
+
+    fn args<'a>(&self) -> Command<'a> {
+        Command::new("decompile")
+            .about("Show the definitions of the main package of a dar in a readable form")
+            .arg(Arg::new("dar").help("Sets the input dar file to use").required(true).index(1))
+            .arg(
+                Arg::new("module")
+                    .short('m')
+                    .long("module")
+                    .takes_value(true)
+                    .required(false)
+                    .help("Only show the given (dotted) module"),
+            )
+            .arg(
+                Arg::new("width")
+                    .short('w')
+                    .long("width")
+                    .takes_value(true)
+                    .default_value("100")
+                    .help("Sets the maximum line width"),
+            )
+            .arg(Arg::new("elide-package-ids").short('e').long("elide-package-ids").help("Omit package ids from names"))
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<()> {
+        let dar_path = matches.value_of("dar").unwrap();
+        let width = matches.value_of("width").unwrap();
+        let width = usize::from_str(width).context(format!("parsing width from '{}'", width))?;
+        let printer = PrettyPrinter::new().width(width).elide_package_ids(matches.is_present("elide-package-ids"));
+        execute(dar_path, matches.value_of("module"), &printer)
+    }
+}
+
+fn execute(dar_path: &str, module: Option<&str>, printer: &PrettyPrinter) -> Result<()> {
+    let dar = DarFile::from_file(dar_path)?;
+    let rendered = dar.apply(|archive| -> Result<String> {
+        let package = archive.main_package().ok_or_else(|| anyhow!("main package not found"))?;
+        match module {
+            Some(module) => {
+                let path: Vec<_> = module.split('.').collect();
+                let module = package
+                    .root_module()
+                    .child_module_path(&path)
+                    .ok_or_else(|| anyhow!("module {} not found", module))?;
+                Ok(printer.render_module(module))
+            },
+            None => Ok(printer.render_package(package)),
+        }
+    })??;
+    println!("{}", rendered);
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::DarnCommand;
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use daml::lf::{DamlArchiveDiff, DarFile};
+
+/// Darn command for displaying the structural differences between two dars.
+pub struct CommandDiff {}
+
+impl DarnCommand for CommandDiff {
+    fn name(&self) -> &str {
+        "diff"
+    }
+
+    fn args<'a>(&self) -> Command<'a> {
+        Command::new("diff")
+            .about("Show the structural differences between the main packages of two dars")
+            .arg(Arg::new("old").help("Sets the old dar file to use").required(true).index(1))
+            .arg(Arg::new("new").help("Sets the new dar file to use").required(true).index(2))
+            .arg(
+                Arg::new("output")
+                    .long("output")
+                    .short('o')
+                    .takes_value(true)
+                    .possible_values(&["text", "json"])
+                    .default_value("text")
+                    .help("Sets the output format"),
+            )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<()> {
+        let old_path = matches.value_of("old").unwrap();
+        let new_path = matches.value_of("new").unwrap();
+        let json = matches.value_of("output") == Some("json");
+        execute(old_path, new_path, json)
+    }
+}
+
+fn execute(old_path: &str, new_path: &str, json: bool) -> Result<()> {
+    let old = DarFile::from_file(old_path)?;
+    let new = DarFile::from_file(new_path)?;
+    let diff = DamlArchiveDiff::from_dars(&old, &new)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        print!("{}", diff);
+    }
+    Ok(())
+}
@@ -71,7 +71,7 @@ enum SortOrder {
 
 fn intern_string(dar_path: &str, show_mangled: bool, sort_order: &SortOrder, filter: &[usize]) -> Result<()> {
     let dar = DarFile::from_file(dar_path)?;
-    match dar.main.payload.package {
+    match dar.main.into_payload().package {
         DamlLfPackage::V1(package) => {
             let mut res: Vec<_> = package
                 .interned_strings
@@ -108,7 +108,7 @@ fn intern_string(dar_path: &str, show_mangled: bool, sort_order: &SortOrder, fil
 
 fn intern_dotted(dar_path: &str, show_mangled: bool, sort_order: &SortOrder, filter: &[usize]) -> Result<()> {
     let dar = DarFile::from_file(dar_path)?;
-    match dar.main.payload.package {
+    match dar.main.into_payload().package {
         DamlLfPackage::V1(package) => {
             let mut res: Vec<_> = package
                 .interned_dotted_names
@@ -9,6 +9,8 @@
 )]
 #![forbid(unsafe_code)]
 
+use crate::command_decompile::CommandDecompile;
+use crate::command_diff::CommandDiff;
 use crate::command_intern::CommandIntern;
 use crate::command_package::CommandPackage;
 use crate::command_token::CommandToken;
@@ -16,6 +18,10 @@ use anyhow::Result;
 use clap::{crate_description, crate_name, crate_version, ArgMatches, Command};
 use std::collections::HashMap;
 
+#[doc(hidden)]
+pub mod command_decompile;
+#[doc(hidden)]
+pub mod command_diff;
 #[doc(hidden)]
 pub mod command_intern;
 #[doc(hidden)]
@@ -40,8 +46,13 @@ macro_rules! command {
 #[doc(hidden)]
 #[tokio::main]
 async fn main() -> Result<()> {
-    let commands: Vec<Box<dyn DarnCommand>> =
-        vec![command!(CommandPackage), command!(CommandToken), command!(CommandIntern)];
+    let commands: Vec<Box<dyn DarnCommand>> = vec![
+        command!(CommandPackage),
+        command!(CommandToken),
+        command!(CommandIntern),
+        command!(CommandDecompile),
+        command!(CommandDiff),
+    ];
     let command_map: HashMap<_, _> = commands.into_iter().map(|cmd| (cmd.name().to_owned(), cmd)).collect();
     let matches = Command::new(crate_name!())
         .version(crate_version!())